@@ -57,6 +57,7 @@ fn benchmark_files_no_partitions(c: &mut Criterion) {
                         idle_timeout: Duration::from_secs(30),
                         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
                         compression: sinks::file::Compression::None,
+                        rotation: None,
                         acknowledgements: Default::default(),
                         timezone: Default::default(),
                         internal_metrics: Default::default(),
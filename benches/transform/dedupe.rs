@@ -46,6 +46,7 @@ fn dedupe(c: &mut Criterion) {
                 fields: Some(FieldMatchConfig::IgnoreFields(vec!["message".into()])),
                 cache: cache.clone(),
                 time_settings: None,
+                persistence: None,
             },
         },
         // Modification of previous where field "message" is matched.
@@ -56,6 +57,7 @@ fn dedupe(c: &mut Criterion) {
                 fields: Some(FieldMatchConfig::MatchFields(vec!["message".into()])),
                 cache: cache.clone(),
                 time_settings: None,
+                persistence: None,
             },
         },
         // Modification of previous where deduplication with max age is used.
@@ -69,6 +71,7 @@ fn dedupe(c: &mut Criterion) {
                     max_age_ms: Duration::from_secs(5),
                     refresh_on_drop: false,
                 }),
+                persistence: None,
             },
         },
         // Modification of previous where refresh on drop is enabled.
@@ -82,6 +85,7 @@ fn dedupe(c: &mut Criterion) {
                     max_age_ms: Duration::from_secs(5),
                     refresh_on_drop: true,
                 }),
+                persistence: None,
             },
         },
         // Measurement where ignore fields do not exist in the event.
@@ -98,6 +102,7 @@ fn dedupe(c: &mut Criterion) {
                     "bcdea".into(),
                 ])),
                 time_settings: None,
+                persistence: None,
             },
         },
         // Modification of previous where match fields do not exist in the
@@ -115,6 +120,7 @@ fn dedupe(c: &mut Criterion) {
                     "bcdea".into(),
                 ])),
                 time_settings: None,
+                persistence: None,
             },
         },
     ] {
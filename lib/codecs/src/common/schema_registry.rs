@@ -0,0 +1,739 @@
+//! A client of the [Confluent Schema Registry][schema_registry], used by the Avro and Protobuf
+//! codecs to handle messages in the Confluent wire format.
+//!
+//! In this format, each message is prefixed with a zero magic byte and the ID of its schema in the
+//! registry, as a 4-byte big-endian integer.
+//!
+//! [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use base64::prelude::{BASE64_STANDARD, Engine as _};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use prost::encoding::{decode_varint, encode_varint};
+use prost_reflect::{
+    DescriptorPool, FileDescriptor, MessageDescriptor,
+    prost::Message as _,
+    prost_types::{FileDescriptorProto, FileDescriptorSet},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use snafu::{ResultExt, Snafu};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+/// The magic byte starting messages in the Confluent wire format.
+const MAGIC_BYTE: u8 = 0;
+
+/// The length of the magic byte and schema ID prefixing messages.
+const HEADER_LEN: usize = 5;
+
+/// How long a failure to resolve a schema is remembered before it is resolved again.
+const FAILURE_TTL: Duration = Duration::from_secs(30);
+
+/// Configuration for looking up schemas in a [Confluent Schema Registry][schema_registry].
+///
+/// [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the Schema Registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    /// The username to authenticate to the Schema Registry with, using HTTP basic authentication.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_USERNAME}"))]
+    pub username: Option<String>,
+
+    /// The password to authenticate to the Schema Registry with, using HTTP basic authentication.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_PASSWORD}"))]
+    pub password: Option<SensitiveString>,
+
+    /// The timeout, in seconds, of requests to the Schema Registry.
+    #[serde(default = "default_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub timeout_secs: u64,
+}
+
+const fn default_timeout_secs() -> u64 {
+    10
+}
+
+impl SchemaRegistryConfig {
+    /// Creates a new `SchemaRegistryConfig` for the registry at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            username: None,
+            password: None,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+/// Configuration for resolving the schema of encoded messages in a
+/// [Confluent Schema Registry][schema_registry].
+///
+/// The schema is resolved when the encoder is built, and its ID prefixes each encoded message.
+///
+/// [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaRegistrySerializerConfig {
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub registry: SchemaRegistryConfig,
+
+    /// The subject under which the schema is registered.
+    #[configurable(metadata(docs::examples = "logs-value"))]
+    pub subject: String,
+
+    /// Whether the configured schema is registered under `subject` if it is not already.
+    ///
+    /// Otherwise, the configured schema must already be registered under `subject`. If no schema
+    /// is configured, the latest schema registered under `subject` is used.
+    ///
+    /// Only supported by the `avro` codec.
+    #[serde(default)]
+    pub auto_register: bool,
+}
+
+#[derive(Debug, Snafu)]
+pub(crate) enum SchemaRegistryError {
+    #[snafu(display("Schema Registry request failed: {source}"))]
+    Request { source: reqwest::Error },
+
+    #[snafu(display("Schema Registry responded with status {status}: {message}"))]
+    Response { status: u16, message: String },
+
+    #[snafu(display("failed to start Schema Registry client: {source}"))]
+    Runtime { source: std::io::Error },
+
+    #[snafu(display("Schema Registry requests can't block a single-threaded runtime"))]
+    CurrentThreadRuntime,
+
+    #[snafu(display("Expected message to be prefixed with a magic byte and schema ID"))]
+    MissingHeader,
+
+    #[snafu(display("Expected message to be prefixed with Protobuf message indexes"))]
+    InvalidMessageIndexes,
+
+    #[snafu(display("Invalid Protobuf schema {schema}: {message}"))]
+    InvalidProtobufSchema { schema: String, message: String },
+
+    #[snafu(display("Protobuf schema {schema} has no message at indexes {indexes:?}"))]
+    MissingMessage { schema: String, indexes: Vec<i32> },
+
+    #[snafu(display("Schema {schema} is of type {actual}, expected {expected}"))]
+    UnexpectedSchemaType {
+        schema: String,
+        actual: String,
+        expected: &'static str,
+    },
+}
+
+/// A schema as returned by the Schema Registry.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RegisteredSchema {
+    /// The ID of the schema, which is only returned when looking it up by subject.
+    #[serde(default)]
+    pub(crate) id: Option<u32>,
+    pub(crate) schema: String,
+    #[serde(default)]
+    schema_type: Option<String>,
+    #[serde(default)]
+    pub(crate) references: Vec<SchemaReference>,
+}
+
+impl RegisteredSchema {
+    /// Checks that the schema is of the given type, as schemas without a type are Avro schemas.
+    pub(crate) fn expect_type(
+        &self,
+        schema: impl fmt::Display,
+        expected: &'static str,
+    ) -> Result<(), SchemaRegistryError> {
+        let actual = self.schema_type.as_deref().unwrap_or("AVRO");
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(SchemaRegistryError::UnexpectedSchemaType {
+                schema: schema.to_string(),
+                actual: actual.to_string(),
+                expected,
+            })
+        }
+    }
+}
+
+/// A reference of a schema to another schema, such as an imported Protobuf file.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SchemaReference {
+    pub(crate) name: String,
+    pub(crate) subject: String,
+    pub(crate) version: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SchemaId {
+    id: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaRequest<'a> {
+    schema: &'a str,
+    schema_type: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+/// A client of the Schema Registry.
+///
+/// Codecs are synchronous, so requests block the calling thread until the registry responds.
+/// They are rare, as the schemas looked up are cached by the codecs.
+///
+/// Within a multi-threaded runtime, such as Vector's, the other tasks of the blocked worker thread
+/// are handed over to another thread meanwhile.
+#[derive(Clone, Debug)]
+pub(crate) struct SchemaRegistry {
+    client: reqwest::Client,
+    url: String,
+    username: Option<String>,
+    password: Option<SensitiveString>,
+}
+
+impl SchemaRegistry {
+    pub(crate) fn new(config: &SchemaRegistryConfig) -> Result<Self, SchemaRegistryError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            // Requests made outside of a runtime run on their own, which closes its connections
+            // once done.
+            .pool_max_idle_per_host(0)
+            .build()
+            .context(RequestSnafu)?;
+
+        Ok(Self {
+            client,
+            url: config.url.trim_end_matches('/').to_string(),
+            username: config.username.clone(),
+            password: config.password.clone(),
+        })
+    }
+
+    /// Gets the schema with the given ID.
+    pub(crate) fn schema_by_id(
+        &self,
+        id: u32,
+        format: Option<&str>,
+    ) -> Result<RegisteredSchema, SchemaRegistryError> {
+        self.send(self.get(&format!("/schemas/ids/{id}"), format))
+    }
+
+    /// Gets the schema registered under `subject` at `version`, which can also be `latest`.
+    pub(crate) fn schema_by_version(
+        &self,
+        subject: &str,
+        version: &str,
+        format: Option<&str>,
+    ) -> Result<RegisteredSchema, SchemaRegistryError> {
+        self.send(self.get(&format!("/subjects/{subject}/versions/{version}"), format))
+    }
+
+    /// Gets the ID of `schema`, which must be registered under `subject`.
+    pub(crate) fn lookup(
+        &self,
+        subject: &str,
+        schema: &str,
+        schema_type: &str,
+    ) -> Result<u32, SchemaRegistryError> {
+        let request = self
+            .client
+            .post(format!("{}/subjects/{subject}", self.url))
+            .json(&SchemaRequest {
+                schema,
+                schema_type,
+            });
+        self.send::<SchemaId>(request).map(|response| response.id)
+    }
+
+    /// Registers `schema` under `subject`, returning its ID.
+    ///
+    /// If the schema is already registered, its existing ID is returned.
+    pub(crate) fn register(
+        &self,
+        subject: &str,
+        schema: &str,
+        schema_type: &str,
+    ) -> Result<u32, SchemaRegistryError> {
+        let request = self
+            .client
+            .post(format!("{}/subjects/{subject}/versions", self.url))
+            .json(&SchemaRequest {
+                schema,
+                schema_type,
+            });
+        self.send::<SchemaId>(request).map(|response| response.id)
+    }
+
+    /// Builds the file descriptor of a Protobuf schema fetched in the `serialized` format, along
+    /// with the schemas it references.
+    ///
+    /// The schema is named `name` in the descriptor pool, while referenced schemas are named as
+    /// they are imported.
+    pub(crate) fn protobuf_file_descriptor(
+        &self,
+        schema: RegisteredSchema,
+        name: &str,
+    ) -> Result<FileDescriptor, SchemaRegistryError> {
+        let mut files = Vec::new();
+        self.collect_protobuf_files(schema, name.to_string(), &mut files, &mut HashSet::new())?;
+
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_set(FileDescriptorSet { file: files })
+            .map_err(|error| SchemaRegistryError::InvalidProtobufSchema {
+                schema: name.to_string(),
+                message: error.to_string(),
+            })?;
+        Ok(pool
+            .get_file_by_name(name)
+            .expect("schema was added to the pool"))
+    }
+
+    /// Collects the file descriptors of a Protobuf schema and its references, in dependency order.
+    fn collect_protobuf_files(
+        &self,
+        schema: RegisteredSchema,
+        name: String,
+        files: &mut Vec<FileDescriptorProto>,
+        seen: &mut HashSet<String>,
+    ) -> Result<(), SchemaRegistryError> {
+        schema.expect_type(&name, "PROTOBUF")?;
+        for reference in &schema.references {
+            if seen.insert(reference.name.clone()) {
+                let referenced = self.schema_by_version(
+                    &reference.subject,
+                    &reference.version.to_string(),
+                    Some("serialized"),
+                )?;
+                self.collect_protobuf_files(referenced, reference.name.clone(), files, seen)?;
+            }
+        }
+
+        let invalid = |message: String| SchemaRegistryError::InvalidProtobufSchema {
+            schema: name.clone(),
+            message,
+        };
+        let bytes = BASE64_STANDARD
+            .decode(&schema.schema)
+            .map_err(|error| invalid(error.to_string()))?;
+        let mut file = FileDescriptorProto::decode(bytes.as_slice())
+            .map_err(|error| invalid(error.to_string()))?;
+        file.name = Some(name);
+        files.push(file);
+        Ok(())
+    }
+
+    fn get(&self, path: &str, format: Option<&str>) -> reqwest::RequestBuilder {
+        let request = self.client.get(format!("{}{path}", self.url));
+        match format {
+            Some(format) => request.query(&[("format", format)]),
+            None => request,
+        }
+    }
+
+    fn send<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, SchemaRegistryError> {
+        let request = match &self.username {
+            Some(username) => {
+                request.basic_auth(username, self.password.as_ref().map(SensitiveString::inner))
+            }
+            None => request,
+        }
+        .header("Accept", "application/vnd.schemaregistry.v1+json");
+
+        let response = async {
+            let response = request.send().await.context(RequestSnafu)?;
+            let status = response.status();
+            if status.is_success() {
+                response.json().await.context(RequestSnafu)
+            } else {
+                let body = response.bytes().await.context(RequestSnafu)?;
+                let message = serde_json::from_slice::<ErrorResponse>(&body)
+                    .map(|error| error.message)
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+                Err(SchemaRegistryError::Response {
+                    status: status.as_u16(),
+                    message,
+                })
+            }
+        };
+
+        match Handle::try_current() {
+            Ok(handle) => match handle.runtime_flavor() {
+                RuntimeFlavor::MultiThread => {
+                    tokio::task::block_in_place(|| handle.block_on(response))
+                }
+                _ => Err(SchemaRegistryError::CurrentThreadRuntime),
+            },
+            Err(_) => tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .context(RuntimeSnafu)?
+                .block_on(response),
+        }
+    }
+}
+
+/// A cache of the schemas looked up by ID, shared between clones of a codec.
+#[derive(Clone, Debug)]
+pub(crate) struct SchemaCache<T> {
+    schemas: Arc<Mutex<HashMap<u32, CachedSchema<T>>>>,
+    failure_ttl: Duration,
+}
+
+#[derive(Debug)]
+enum CachedSchema<T> {
+    Resolved(Arc<T>),
+    /// The failure to resolve the schema, remembered so that messages referencing a schema that
+    /// can't be resolved don't each send a request.
+    Failed {
+        message: String,
+        expires_at: Instant,
+    },
+}
+
+impl<T> Default for SchemaCache<T> {
+    fn default() -> Self {
+        Self::new(FAILURE_TTL)
+    }
+}
+
+impl<T> SchemaCache<T> {
+    /// Creates a cache remembering failures to resolve a schema for `failure_ttl`.
+    pub(crate) fn new(failure_ttl: Duration) -> Self {
+        Self {
+            schemas: Arc::default(),
+            failure_ttl,
+        }
+    }
+
+    /// Gets the schema with the given ID, resolving it with `resolve` if it is not cached yet.
+    ///
+    /// Failures are cached for a limited time, after which the schema is resolved again.
+    pub(crate) fn get_or_resolve<E: fmt::Display>(
+        &self,
+        id: u32,
+        resolve: impl FnOnce(u32) -> Result<T, E>,
+    ) -> vector_common::Result<Arc<T>> {
+        match self.schemas.lock().expect("mutex poisoned").get(&id) {
+            Some(CachedSchema::Resolved(schema)) => return Ok(Arc::clone(schema)),
+            Some(CachedSchema::Failed {
+                message,
+                expires_at,
+            }) if *expires_at > Instant::now() => return Err(message.clone().into()),
+            _ => {}
+        }
+
+        // The lock isn't held while resolving, so that other schemas can still be read meanwhile.
+        let resolved = resolve(id);
+        let mut schemas = self.schemas.lock().expect("mutex poisoned");
+        match resolved {
+            Ok(schema) => {
+                let schema = Arc::new(schema);
+                schemas.insert(id, CachedSchema::Resolved(Arc::clone(&schema)));
+                Ok(schema)
+            }
+            Err(error) => {
+                let now = Instant::now();
+                // Expired failures are dropped, so that unknown IDs don't accumulate.
+                schemas.retain(|_, cached| match cached {
+                    CachedSchema::Resolved(_) => true,
+                    CachedSchema::Failed { expires_at, .. } => *expires_at > now,
+                });
+                let message = error.to_string();
+                schemas.insert(
+                    id,
+                    CachedSchema::Failed {
+                        message: message.clone(),
+                        expires_at: now + self.failure_ttl,
+                    },
+                );
+                Err(message.into())
+            }
+        }
+    }
+}
+
+/// Splits a message in the Confluent wire format into its schema ID and payload.
+pub(crate) fn split_header(mut bytes: Bytes) -> Result<(u32, Bytes), SchemaRegistryError> {
+    if bytes.len() < HEADER_LEN || bytes[0] != MAGIC_BYTE {
+        return Err(SchemaRegistryError::MissingHeader);
+    }
+    bytes.advance(1);
+    let id = bytes.get_u32();
+    Ok((id, bytes))
+}
+
+/// Writes the header of a message in the Confluent wire format.
+pub(crate) fn write_header(buffer: &mut BytesMut, id: u32) {
+    buffer.reserve(HEADER_LEN);
+    buffer.put_u8(MAGIC_BYTE);
+    buffer.put_u32(id);
+}
+
+/// Splits the Protobuf message indexes prefixing a payload, following its header, from the message.
+///
+/// The indexes locate the message type in the schema, from its top-level messages down to its
+/// nested ones. They are written as a count followed by each index, all as zigzag varints, with
+/// the common `[0]` indexes shortened to a zero count.
+pub(crate) fn split_message_indexes(
+    mut bytes: Bytes,
+) -> Result<(Vec<i32>, Bytes), SchemaRegistryError> {
+    let mut read = || {
+        decode_varint(&mut bytes)
+            .ok()
+            .and_then(|value| i32::try_from(decode_zigzag(value)).ok())
+            .ok_or(SchemaRegistryError::InvalidMessageIndexes)
+    };
+
+    let count = read()?;
+    let indexes = match usize::try_from(count) {
+        Ok(0) => vec![0],
+        Ok(count) => (0..count).map(|_| read()).collect::<Result<_, _>>()?,
+        Err(_) => return Err(SchemaRegistryError::InvalidMessageIndexes),
+    };
+    Ok((indexes, bytes))
+}
+
+/// Writes the Protobuf message indexes following the header of a message.
+pub(crate) fn write_message_indexes(buffer: &mut BytesMut, indexes: &[i32]) {
+    if indexes == [0] {
+        buffer.put_u8(0);
+        return;
+    }
+    encode_varint(encode_zigzag(indexes.len() as i64), buffer);
+    for &index in indexes {
+        encode_varint(encode_zigzag(index.into()), buffer);
+    }
+}
+
+const fn decode_zigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+const fn encode_zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Finds the message of a Protobuf schema located by its message indexes.
+pub(crate) fn message_by_indexes(
+    file: &FileDescriptor,
+    indexes: &[i32],
+) -> Result<MessageDescriptor, SchemaRegistryError> {
+    let missing = || SchemaRegistryError::MissingMessage {
+        schema: file.name().to_string(),
+        indexes: indexes.to_vec(),
+    };
+
+    let mut indexes = indexes.iter().map(|&index| usize::try_from(index));
+    let first = indexes.next().ok_or_else(missing)?.map_err(|_| missing())?;
+    let mut message = file.messages().nth(first).ok_or_else(missing)?;
+    for index in indexes {
+        let index = index.map_err(|_| missing())?;
+        message = message.child_messages().nth(index).ok_or_else(missing)?;
+    }
+    Ok(message)
+}
+
+/// Computes the message indexes locating a message in its Protobuf schema.
+pub(crate) fn message_indexes(message: &MessageDescriptor) -> Vec<i32> {
+    let mut indexes = Vec::new();
+    let mut current = message.clone();
+    loop {
+        let (index, parent) = match current.parent_message() {
+            Some(parent) => (
+                parent.child_messages().position(|child| child == current),
+                Some(parent),
+            ),
+            None => (
+                current
+                    .parent_file()
+                    .messages()
+                    .position(|child| child == current),
+                None,
+            ),
+        };
+        indexes.push(index.expect("message is a child of its parent") as i32);
+        match parent {
+            Some(parent) => current = parent,
+            None => break,
+        }
+    }
+    indexes.reverse();
+    indexes
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use std::{env, fs, path::PathBuf};
+
+    use base64::prelude::{BASE64_STANDARD, Engine as _};
+    use prost_reflect::{prost::Message as _, prost_types::FileDescriptorSet};
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    /// Starts a mock Schema Registry serving the given schemas by ID.
+    pub(crate) async fn mock_registry(schemas: &[(u32, serde_json::Value)]) -> MockServer {
+        let server = MockServer::start().await;
+        for (id, schema) in schemas {
+            Mock::given(method("GET"))
+                .and(path(format!("/schemas/ids/{id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(schema))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "error_code": 40403,
+                "message": "Schema not found",
+            })))
+            .with_priority(u8::MAX)
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// The `test_protobuf.proto` schema of the test data, as served by the Schema Registry in the
+    /// `serialized` format.
+    pub(crate) fn protobuf_test_schema() -> serde_json::Value {
+        let path = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+            .join("tests/data/protobuf/protos/test_protobuf.desc");
+        let set = FileDescriptorSet::decode(fs::read(path).unwrap().as_slice()).unwrap();
+        let file = set
+            .file
+            .into_iter()
+            .find(|file| file.name() == "test_protobuf.proto")
+            .unwrap();
+        json!({
+            "schemaType": "PROTOBUF",
+            "schema": BASE64_STANDARD.encode(file.encode_to_vec()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{test_util::mock_registry, *};
+
+    #[test]
+    fn splits_header() {
+        let (id, payload) = split_header(Bytes::from_static(b"\0\0\0\x01\x2ahello")).unwrap();
+        assert_eq!(id, 298);
+        assert_eq!(payload, Bytes::from_static(b"hello"));
+
+        let mut buffer = BytesMut::new();
+        write_header(&mut buffer, 298);
+        assert_eq!(buffer.freeze(), Bytes::from_static(b"\0\0\0\x01\x2a"));
+
+        assert!(matches!(
+            split_header(Bytes::from_static(b"\x01\0\0\0\x01hello")),
+            Err(SchemaRegistryError::MissingHeader)
+        ));
+    }
+
+    #[test]
+    fn splits_message_indexes() {
+        for (indexes, encoded) in [
+            (vec![0], &b"\0"[..]),
+            (vec![1], &b"\x02\x02"[..]),
+            (vec![0, 2], &b"\x04\0\x04"[..]),
+        ] {
+            let mut buffer = BytesMut::new();
+            write_message_indexes(&mut buffer, &indexes);
+            assert_eq!(&buffer[..], encoded);
+
+            buffer.extend_from_slice(b"hello");
+            let (split, payload) = split_message_indexes(buffer.freeze()).unwrap();
+            assert_eq!(split, indexes);
+            assert_eq!(payload, Bytes::from_static(b"hello"));
+        }
+
+        assert!(matches!(
+            split_message_indexes(Bytes::from_static(b"\x01")),
+            Err(SchemaRegistryError::InvalidMessageIndexes)
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn caches_schemas_by_id() {
+        let server = mock_registry(&[(1, json!({ "schema": "\"string\"" }))]).await;
+        let registry = SchemaRegistry::new(&SchemaRegistryConfig::new(server.uri())).unwrap();
+        let cache = SchemaCache::default();
+
+        for _ in 0..2 {
+            let schema = cache
+                .get_or_resolve(1, |id| registry.schema_by_id(id, None))
+                .unwrap();
+            assert_eq!(schema.schema, "\"string\"");
+        }
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        let error = cache
+            .get_or_resolve(2, |id| registry.schema_by_id(id, None))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Schema Registry responded with status 404: Schema not found"
+        );
+    }
+
+    #[test]
+    fn caches_failures_until_they_expire() {
+        let resolve = |id: u32| Err::<u32, _>(format!("Schema {id} not found"));
+        let cache = SchemaCache::default();
+        assert_eq!(
+            cache.get_or_resolve(1, resolve).unwrap_err().to_string(),
+            "Schema 1 not found"
+        );
+        // The failure is returned again without resolving the schema.
+        let error = cache
+            .get_or_resolve(1, |_| -> Result<u32, String> { unreachable!() })
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Schema 1 not found");
+
+        let cache = SchemaCache::new(Duration::ZERO);
+        assert!(cache.get_or_resolve(1, resolve).is_err());
+        assert_eq!(
+            *cache.get_or_resolve(1, |id| Ok::<_, String>(id)).unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_multi_threaded_runtime() {
+        let registry =
+            SchemaRegistry::new(&SchemaRegistryConfig::new("http://localhost:8081")).unwrap();
+        assert!(matches!(
+            registry.schema_by_id(1, None),
+            Err(SchemaRegistryError::CurrentThreadRuntime)
+        ));
+    }
+}
@@ -1,4 +1,9 @@
+use std::sync::Arc;
+
 use super::Deserializer;
+use crate::common::schema_registry::{
+    SchemaCache, SchemaRegistry, SchemaRegistryConfig, split_header,
+};
 use crate::encoding::AvroSerializerOptions;
 use bytes::Buf;
 use bytes::Bytes;
@@ -34,19 +39,25 @@ impl AvroDeserializerConfig {
             avro_options: AvroDeserializerOptions {
                 schema,
                 strip_schema_id_prefix,
+                schema_registry: None,
             },
         }
     }
 
     /// Build the `AvroDeserializer` from this configuration.
-    pub fn build(&self) -> AvroDeserializer {
+    pub fn build(&self) -> vector_common::Result<AvroDeserializer> {
+        if let Some(config) = &self.avro_options.schema_registry {
+            return Ok(AvroDeserializer::with_schema_registry(SchemaRegistry::new(
+                config,
+            )?));
+        }
+
         let schema = apache_avro::Schema::parse_str(&self.avro_options.schema)
-            .map_err(|error| format!("Failed building Avro serializer: {error}"))
-            .unwrap();
-        AvroDeserializer {
+            .map_err(|error| format!("Failed building Avro deserializer: {error}"))?;
+        Ok(AvroDeserializer::new(
             schema,
-            strip_schema_id_prefix: self.avro_options.strip_schema_id_prefix,
-        }
+            self.avro_options.strip_schema_id_prefix,
+        ))
     }
 
     /// The data type of events that are accepted by `AvroDeserializer`.
@@ -82,6 +93,7 @@ impl From<&AvroDeserializerOptions> for AvroSerializerOptions {
     fn from(value: &AvroDeserializerOptions) -> Self {
         Self {
             schema: value.schema.clone(),
+            schema_registry: None,
         }
     }
 }
@@ -101,28 +113,91 @@ pub struct AvroDeserializerOptions {
         docs::additional_props_description = r#"Supports most avro data types, unsupported data types includes
         ["decimal", "duration", "local-timestamp-millis", "local-timestamp-micros"]"#,
     ))]
+    #[serde(default)]
     pub schema: String,
 
     /// For Avro datum encoded in Kafka messages, the bytes are prefixed with the schema ID.  Set this to `true` to strip the schema ID prefix.
     /// According to [Confluent Kafka's document](https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format).
+    #[serde(default)]
     pub strip_schema_id_prefix: bool,
+
+    /// Looks up the schema of each message in a Schema Registry, by the schema ID prefixing it.
+    ///
+    /// When set, `schema` and `strip_schema_id_prefix` are ignored.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_registry: Option<SchemaRegistryConfig>,
 }
 
 /// Serializer that converts bytes to an `Event` using the Apache Avro format.
 #[derive(Debug, Clone)]
 pub struct AvroDeserializer {
-    schema: apache_avro::Schema,
+    schema: AvroSchema,
     strip_schema_id_prefix: bool,
 }
 
+/// Where the schemas of the messages come from.
+#[derive(Debug, Clone)]
+enum AvroSchema {
+    Static(Arc<apache_avro::Schema>),
+    Registry(SchemaRegistry, SchemaCache<apache_avro::Schema>),
+}
+
 impl AvroDeserializer {
     /// Creates a new `AvroDeserializer`.
-    pub const fn new(schema: apache_avro::Schema, strip_schema_id_prefix: bool) -> Self {
+    pub fn new(schema: apache_avro::Schema, strip_schema_id_prefix: bool) -> Self {
         Self {
-            schema,
+            schema: AvroSchema::Static(Arc::new(schema)),
             strip_schema_id_prefix,
         }
     }
+
+    fn with_schema_registry(registry: SchemaRegistry) -> Self {
+        Self {
+            schema: AvroSchema::Registry(registry, SchemaCache::default()),
+            strip_schema_id_prefix: true,
+        }
+    }
+
+    /// Gets the schema of the message, and its payload.
+    fn resolve_schema(
+        &self,
+        bytes: Bytes,
+    ) -> vector_common::Result<(Arc<apache_avro::Schema>, Bytes)> {
+        let (registry, cache) = match &self.schema {
+            AvroSchema::Static(schema) => {
+                let bytes = if self.strip_schema_id_prefix {
+                    if bytes.len() >= CONFLUENT_SCHEMA_PREFIX_LEN
+                        && bytes[0] == CONFLUENT_MAGIC_BYTE
+                    {
+                        bytes.slice(CONFLUENT_SCHEMA_PREFIX_LEN..)
+                    } else {
+                        return Err(vector_common::Error::from(
+                            "Expected avro datum to be prefixed with schema id",
+                        ));
+                    }
+                } else {
+                    bytes
+                };
+                return Ok((Arc::clone(schema), bytes));
+            }
+            AvroSchema::Registry(registry, cache) => (registry, cache),
+        };
+
+        let (id, bytes) = split_header(bytes)?;
+        let schema = cache.get_or_resolve(id, |id| {
+            let registered = registry.schema_by_id(id, None)?;
+            registered.expect_type(id, "AVRO")?;
+            if !registered.references.is_empty() {
+                return Err(vector_common::Error::from(format!(
+                    "Avro schema {id} has references, which are not supported"
+                )));
+            }
+            apache_avro::Schema::parse_str(&registered.schema)
+                .map_err(|error| format!("Failed parsing Avro schema {id}: {error}").into())
+        })?;
+        Ok((schema, bytes))
+    }
 }
 
 impl Deserializer for AvroDeserializer {
@@ -136,19 +211,8 @@ impl Deserializer for AvroDeserializer {
             return Ok(smallvec![]);
         }
 
-        let bytes = if self.strip_schema_id_prefix {
-            if bytes.len() >= CONFLUENT_SCHEMA_PREFIX_LEN && bytes[0] == CONFLUENT_MAGIC_BYTE {
-                bytes.slice(CONFLUENT_SCHEMA_PREFIX_LEN..)
-            } else {
-                return Err(vector_common::Error::from(
-                    "Expected avro datum to be prefixed with schema id",
-                ));
-            }
-        } else {
-            bytes
-        };
-
-        let value = apache_avro::from_avro_datum(&self.schema, &mut bytes.reader(), None)?;
+        let (schema, bytes) = self.resolve_schema(bytes)?;
+        let value = apache_avro::from_avro_datum(&schema, &mut bytes.reader(), None)?;
 
         let apache_avro::types::Value::Record(fields) = value else {
             return Err(vector_common::Error::from("Expected an avro Record"));
@@ -240,6 +304,7 @@ mod tests {
     use uuid::Uuid;
 
     use super::*;
+    use crate::common::schema_registry::test_util::mock_registry;
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
     struct Log {
@@ -313,6 +378,47 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn deserialize_avro_schema_registry() {
+        let schema = get_schema();
+        let server =
+            mock_registry(&[(42, serde_json::json!({ "schema": schema.canonical_form() }))]).await;
+
+        let event = Log {
+            message: "hello from avro".to_owned(),
+        };
+        let record_value = apache_avro::to_value(event).unwrap();
+        let record_datum = apache_avro::to_avro_datum(&schema, record_value).unwrap();
+
+        let mut bytes = BytesMut::new();
+        bytes.extend([0, 0, 0, 0, 42]);
+        bytes.extend(record_datum);
+        let bytes = bytes.freeze();
+
+        let mut config = AvroDeserializerConfig::new(String::new(), false);
+        config.avro_options.schema_registry = Some(SchemaRegistryConfig::new(server.uri()));
+        let deserializer = config.build().unwrap();
+        for _ in 0..2 {
+            let events = deserializer
+                .parse(bytes.clone(), LogNamespace::Vector)
+                .unwrap();
+            assert_eq!(
+                events[0].as_log().get("message").unwrap(),
+                &VrlValue::from("hello from avro")
+            );
+        }
+        // The schema is cached after the first message.
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        let mut unknown = BytesMut::new();
+        unknown.extend([0, 0, 0, 0, 43]);
+        assert!(
+            deserializer
+                .parse(unknown.freeze(), LogNamespace::Vector)
+                .is_err()
+        );
+    }
+
     #[test]
     fn deserialize_avro_uuid() {
         let schema = get_schema();
@@ -0,0 +1,265 @@
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use ciborium::value::Value as CborValue;
+use serde::{Deserialize, Serialize};
+use smallvec::{SmallVec, smallvec};
+use vector_core::{
+    config::{DataType, LogNamespace},
+    event::Event,
+    schema,
+};
+use vrl::value::{KeyString, ObjectMap, Value};
+
+use super::{Deserializer, events_from_value, self_describing_schema_definition};
+
+/// The CBOR tag for a date/time string, as defined in RFC 8949.
+const CBOR_DATE_TIME_TAG: u64 = 0;
+
+/// The CBOR tag for an epoch-based date/time, as defined in RFC 8949.
+const CBOR_EPOCH_TIME_TAG: u64 = 1;
+
+/// The CBOR tag for an unsigned bignum, as defined in RFC 8949.
+const CBOR_POSITIVE_BIGNUM_TAG: u64 = 2;
+
+/// The CBOR tag for a negative bignum, as defined in RFC 8949.
+const CBOR_NEGATIVE_BIGNUM_TAG: u64 = 3;
+
+/// Config used to build a `CborDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CborDeserializerConfig;
+
+impl CborDeserializerConfig {
+    /// Build the `CborDeserializer` from this configuration.
+    pub const fn build(&self) -> CborDeserializer {
+        CborDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        self_describing_schema_definition(log_namespace)
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a [CBOR][cbor] value.
+///
+/// Byte strings are decoded as bytes, and date/time values (tags 0 and 1) are decoded as
+/// timestamps. Integers which do not fit in a signed 64-bit integer, including bignums, are decoded
+/// as floats. Any other tag is ignored, and its content decoded as-is.
+///
+/// [cbor]: https://cbor.io/
+#[derive(Debug, Clone, Default)]
+pub struct CborDeserializer;
+
+impl Deserializer for CborDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let mut reader = &bytes[..];
+        let value: CborValue = ciborium::de::from_reader(&mut reader)
+            .map_err(|error| format!("Error parsing CBOR: {error}"))?;
+        if !reader.is_empty() {
+            return Err(format!(
+                "Error parsing CBOR: {} trailing bytes after value",
+                reader.len()
+            )
+            .into());
+        }
+
+        events_from_value(try_from(value)?, log_namespace)
+    }
+}
+
+fn try_from(value: CborValue) -> vector_common::Result<Value> {
+    Ok(match value {
+        CborValue::Null => Value::Null,
+        CborValue::Bool(boolean) => Value::Boolean(boolean),
+        CborValue::Integer(integer) => match i64::try_from(integer) {
+            Ok(integer) => Value::Integer(integer),
+            Err(_) => Value::from_f64_or_zero(i128::from(integer) as f64),
+        },
+        CborValue::Float(float) => Value::from_f64_or_zero(float),
+        CborValue::Text(text) => Value::Bytes(text.into()),
+        CborValue::Bytes(bytes) => Value::Bytes(bytes.into()),
+        CborValue::Array(array) => Value::Array(
+            array
+                .into_iter()
+                .map(try_from)
+                .collect::<vector_common::Result<_>>()?,
+        ),
+        CborValue::Map(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| Ok((try_key_from(key)?, try_from(value)?)))
+                .collect::<vector_common::Result<ObjectMap>>()?,
+        ),
+        CborValue::Tag(CBOR_DATE_TIME_TAG, content) => match *content {
+            CborValue::Text(text) => Value::Timestamp(
+                DateTime::parse_from_rfc3339(&text)
+                    .map_err(|error| format!("Invalid CBOR date/time string: {error}"))?
+                    .with_timezone(&Utc),
+            ),
+            _ => return Err("Invalid CBOR date/time, expected a text string".into()),
+        },
+        CborValue::Tag(CBOR_EPOCH_TIME_TAG, content) => {
+            let timestamp = match *content {
+                CborValue::Integer(seconds) => i64::try_from(seconds)
+                    .ok()
+                    .and_then(|seconds| DateTime::from_timestamp(seconds, 0)),
+                CborValue::Float(seconds) => DateTime::from_timestamp(
+                    seconds.floor() as i64,
+                    ((seconds - seconds.floor()) * 1e9) as u32,
+                ),
+                _ => None,
+            };
+            Value::Timestamp(timestamp.ok_or("Invalid CBOR epoch-based date/time")?)
+        }
+        CborValue::Tag(tag @ (CBOR_POSITIVE_BIGNUM_TAG | CBOR_NEGATIVE_BIGNUM_TAG), content) => {
+            let CborValue::Bytes(bytes) = *content else {
+                return Err("Invalid CBOR bignum, expected a byte string".into());
+            };
+            let magnitude = bytes
+                .iter()
+                .fold(0_f64, |value, byte| value * 256.0 + f64::from(*byte));
+            if tag == CBOR_POSITIVE_BIGNUM_TAG {
+                Value::from_f64_or_zero(magnitude)
+            } else {
+                Value::from_f64_or_zero(-1.0 - magnitude)
+            }
+        }
+        CborValue::Tag(_, content) => try_from(*content)?,
+        _ => return Err("Unsupported CBOR value".into()),
+    })
+}
+
+fn try_key_from(key: CborValue) -> vector_common::Result<KeyString> {
+    Ok(match key {
+        CborValue::Text(text) => text.into(),
+        CborValue::Bytes(bytes) => String::from_utf8_lossy(&bytes).into(),
+        CborValue::Null => "null".into(),
+        CborValue::Bool(boolean) => boolean.to_string().into(),
+        CborValue::Integer(integer) => i128::from(integer).to_string().into(),
+        CborValue::Float(float) => float.to_string().into(),
+        CborValue::Tag(_, content) => try_key_from(*content)?,
+        _ => return Err("Unsupported CBOR map key, expected a scalar value".into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::config::log_schema;
+
+    use super::*;
+
+    fn encode(value: &CborValue) -> Bytes {
+        let mut buffer = Vec::new();
+        ciborium::ser::into_writer(value, &mut buffer).unwrap();
+        buffer.into()
+    }
+
+    fn text(text: &str) -> CborValue {
+        CborValue::Text(text.to_string())
+    }
+
+    #[test]
+    fn deserialize_cbor() {
+        let input = encode(&CborValue::Map(vec![
+            (text("foo"), CborValue::Integer(123.into())),
+            (text("bar"), CborValue::Bytes(vec![0xff, 0x00])),
+            (text("big"), CborValue::Integer(u64::MAX.into())),
+            (
+                text("nested"),
+                CborValue::Array(vec![CborValue::Bool(true), CborValue::Float(1.5)]),
+            ),
+            (CborValue::Integer(7.into()), CborValue::Null),
+        ]));
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = CborDeserializer.parse(input.clone(), namespace).unwrap();
+            assert_eq!(events.len(), 1);
+
+            let log = events[0].as_log();
+            assert_eq!(log["foo"], 123.into());
+            assert_eq!(log["bar"], Value::Bytes(Bytes::from_static(&[0xff, 0x00])));
+            assert_eq!(log["big"], Value::from_f64_or_zero(u64::MAX as f64));
+            assert_eq!(log["nested[0]"], true.into());
+            assert_eq!(log["nested[1]"], Value::from_f64_or_zero(1.5));
+            assert_eq!(log.as_map().unwrap().get("7"), Some(&Value::Null));
+            assert_eq!(
+                log.get((
+                    lookup::PathPrefix::Event,
+                    log_schema().timestamp_key().unwrap()
+                ))
+                .is_some(),
+                namespace == LogNamespace::Legacy
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_cbor_timestamps() {
+        let input = encode(&CborValue::Map(vec![
+            (
+                text("string"),
+                CborValue::Tag(0, Box::new(text("2023-11-14T22:13:20.5Z"))),
+            ),
+            (
+                text("integer"),
+                CborValue::Tag(1, Box::new(CborValue::Integer(1_700_000_000.into()))),
+            ),
+            (
+                text("float"),
+                CborValue::Tag(1, Box::new(CborValue::Float(1_700_000_000.5))),
+            ),
+            (
+                text("other"),
+                CborValue::Tag(32, Box::new(text("https://vector.dev"))),
+            ),
+        ]));
+
+        let events = CborDeserializer.parse(input, LogNamespace::Vector).unwrap();
+        let log = events[0].as_log();
+        let expected = Utc.timestamp_opt(1_700_000_000, 500_000_000).unwrap();
+        assert_eq!(log["string"], Value::Timestamp(expected));
+        assert_eq!(
+            log["integer"],
+            Value::Timestamp(Utc.timestamp_opt(1_700_000_000, 0).unwrap())
+        );
+        assert_eq!(log["float"], Value::Timestamp(expected));
+        assert_eq!(log["other"], "https://vector.dev".into());
+    }
+
+    #[test]
+    fn deserialize_cbor_array_root() {
+        let input = encode(&CborValue::Array(vec![
+            CborValue::Map(vec![(text("foo"), CborValue::Integer(1.into()))]),
+            CborValue::Map(vec![(text("foo"), CborValue::Integer(2.into()))]),
+        ]));
+
+        let events = CborDeserializer.parse(input, LogNamespace::Legacy).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].as_log()["foo"], 2.into());
+    }
+
+    #[test]
+    fn deserialize_cbor_trailing_bytes() {
+        let mut input = encode(&CborValue::Map(vec![])).to_vec();
+        input.push(0xf6);
+
+        assert!(
+            CborDeserializer
+                .parse(input.into(), LogNamespace::Vector)
+                .is_err()
+        );
+    }
+}
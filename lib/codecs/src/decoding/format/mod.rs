@@ -5,9 +5,11 @@
 
 mod avro;
 mod bytes;
+mod cbor;
 mod gelf;
 mod influxdb;
 mod json;
+mod msgpack;
 mod native;
 mod native_json;
 mod protobuf;
@@ -17,20 +19,25 @@ mod vrl;
 
 use ::bytes::Bytes;
 pub use avro::{AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions};
+pub use cbor::{CborDeserializer, CborDeserializerConfig};
+use chrono::Utc;
 use dyn_clone::DynClone;
 pub use gelf::{GelfDeserializer, GelfDeserializerConfig, GelfDeserializerOptions};
 pub use influxdb::{InfluxdbDeserializer, InfluxdbDeserializerConfig};
 pub use json::{JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions};
+pub use msgpack::{MsgpackDeserializer, MsgpackDeserializerConfig};
 pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{
     NativeJsonDeserializer, NativeJsonDeserializerConfig, NativeJsonDeserializerOptions,
 };
 pub use protobuf::{ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
-use smallvec::SmallVec;
+use smallvec::{SmallVec, smallvec};
 #[cfg(feature = "syslog")]
 pub use syslog::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
-use vector_core::config::LogNamespace;
-use vector_core::event::Event;
+use vector_core::config::{LogNamespace, log_schema};
+use vector_core::event::{Event, LogEvent};
+use vector_core::schema;
+use vrl::value::{Kind, Value};
 
 pub use self::bytes::{BytesDeserializer, BytesDeserializerConfig};
 
@@ -67,3 +74,64 @@ pub type BoxedDeserializer = Box<dyn Deserializer>;
 const fn default_lossy() -> bool {
     true
 }
+
+/// Builds events from a value decoded from a self-describing binary format, such as MessagePack or
+/// CBOR.
+///
+/// Like for JSON, a root array is split into one event per element. With the legacy namespace, each
+/// event must be an object, and a timestamp is added to it unless it already has one.
+fn events_from_value(
+    value: Value,
+    log_namespace: LogNamespace,
+) -> vector_common::Result<SmallVec<[Event; 1]>> {
+    let values = match value {
+        Value::Array(values) => values.into_iter().collect(),
+        value => smallvec![value],
+    };
+
+    let timestamp = Utc::now();
+    values
+        .into_iter()
+        .map(|value| match log_namespace {
+            LogNamespace::Vector => Ok(Event::from(LogEvent::from(value))),
+            LogNamespace::Legacy => {
+                let Value::Object(fields) = value else {
+                    return Err(vector_common::Error::from(
+                        "Attempted to convert non-Object value into an Event.",
+                    ));
+                };
+
+                let mut log = LogEvent::from(fields);
+                if let Some(timestamp_key) = log_schema().timestamp_key_target_path()
+                    && !log.contains(timestamp_key)
+                {
+                    log.insert(timestamp_key, timestamp);
+                }
+                Ok(Event::from(log))
+            }
+        })
+        .collect()
+}
+
+/// The schema produced by deserializers of self-describing binary formats, such as MessagePack or
+/// CBOR, which can produce any kind of value, including bytes and timestamps.
+fn self_describing_schema_definition(log_namespace: LogNamespace) -> schema::Definition {
+    match log_namespace {
+        LogNamespace::Legacy => {
+            let mut definition =
+                schema::Definition::empty_legacy_namespace().unknown_fields(Kind::any());
+
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                definition = definition.try_with_field(
+                    timestamp_key,
+                    Kind::any().or_timestamp(),
+                    Some("timestamp"),
+                );
+            }
+            definition
+        }
+        LogNamespace::Vector => {
+            schema::Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use rmpv::Value as MsgpackValue;
+use serde::{Deserialize, Serialize};
+use smallvec::{SmallVec, smallvec};
+use vector_core::{
+    config::{DataType, LogNamespace},
+    event::Event,
+    schema,
+};
+use vrl::value::{KeyString, ObjectMap, Value};
+
+use super::{Deserializer, events_from_value, self_describing_schema_definition};
+
+/// The MessagePack extension type reserved for timestamps.
+const MSGPACK_TIMESTAMP_EXT_TYPE: i8 = -1;
+
+/// Config used to build a `MsgpackDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackDeserializerConfig;
+
+impl MsgpackDeserializerConfig {
+    /// Build the `MsgpackDeserializer` from this configuration.
+    pub const fn build(&self) -> MsgpackDeserializer {
+        MsgpackDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        self_describing_schema_definition(log_namespace)
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a [MessagePack][msgpack] value.
+///
+/// Binary values are decoded as bytes, and timestamp extension values are decoded as timestamps.
+/// Other extension values are decoded as the bytes of their payload. Unsigned integers which do not
+/// fit in a signed 64-bit integer are decoded as floats.
+///
+/// [msgpack]: https://msgpack.org/
+#[derive(Debug, Clone, Default)]
+pub struct MsgpackDeserializer;
+
+impl Deserializer for MsgpackDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let mut reader = &bytes[..];
+        let value = rmpv::decode::read_value(&mut reader)
+            .map_err(|error| format!("Error parsing MessagePack: {error}"))?;
+        if !reader.is_empty() {
+            return Err(format!(
+                "Error parsing MessagePack: {} trailing bytes after value",
+                reader.len()
+            )
+            .into());
+        }
+
+        events_from_value(try_from(value)?, log_namespace)
+    }
+}
+
+fn try_from(value: MsgpackValue) -> vector_common::Result<Value> {
+    Ok(match value {
+        MsgpackValue::Nil => Value::Null,
+        MsgpackValue::Boolean(boolean) => Value::Boolean(boolean),
+        MsgpackValue::Integer(integer) => match integer.as_i64() {
+            Some(integer) => Value::Integer(integer),
+            // Only unsigned integers above `i64::MAX` don't fit, which we can only represent as a
+            // float.
+            None => Value::from_f64_or_zero(integer.as_u64().unwrap_or(u64::MAX) as f64),
+        },
+        MsgpackValue::F32(float) => Value::from_f64_or_zero(f64::from(float)),
+        MsgpackValue::F64(float) => Value::from_f64_or_zero(float),
+        // Strings with invalid UTF-8 are kept as-is, as `Value::Bytes` does not require valid UTF-8.
+        MsgpackValue::String(string) => Value::Bytes(string.into_bytes().into()),
+        MsgpackValue::Binary(binary) => Value::Bytes(binary.into()),
+        MsgpackValue::Array(array) => Value::Array(
+            array
+                .into_iter()
+                .map(try_from)
+                .collect::<vector_common::Result<_>>()?,
+        ),
+        MsgpackValue::Map(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| Ok((try_key_from(key)?, try_from(value)?)))
+                .collect::<vector_common::Result<ObjectMap>>()?,
+        ),
+        MsgpackValue::Ext(MSGPACK_TIMESTAMP_EXT_TYPE, data) => Value::Timestamp(
+            decode_timestamp(&data).ok_or("Invalid MessagePack timestamp extension value")?,
+        ),
+        MsgpackValue::Ext(_, data) => Value::Bytes(data.into()),
+    })
+}
+
+fn try_key_from(key: MsgpackValue) -> vector_common::Result<KeyString> {
+    Ok(match key {
+        MsgpackValue::String(string) => String::from_utf8_lossy(string.as_bytes()).into(),
+        MsgpackValue::Binary(binary) => String::from_utf8_lossy(&binary).into(),
+        MsgpackValue::Nil => "null".into(),
+        MsgpackValue::Boolean(boolean) => boolean.to_string().into(),
+        MsgpackValue::Integer(integer) => integer.to_string().into(),
+        MsgpackValue::F32(float) => float.to_string().into(),
+        MsgpackValue::F64(float) => float.to_string().into(),
+        MsgpackValue::Array(_) | MsgpackValue::Map(_) | MsgpackValue::Ext(..) => {
+            return Err("Unsupported MessagePack map key, expected a scalar value".into());
+        }
+    })
+}
+
+/// Decodes the payload of a timestamp extension value, in any of its three formats.
+fn decode_timestamp(data: &[u8]) -> Option<DateTime<Utc>> {
+    let (seconds, nanoseconds) = match data.len() {
+        // timestamp 32: unsigned 32-bit seconds.
+        4 => (i64::from(u32::from_be_bytes(data.try_into().ok()?)), 0),
+        // timestamp 64: unsigned 30-bit nanoseconds and unsigned 34-bit seconds.
+        8 => {
+            let value = u64::from_be_bytes(data.try_into().ok()?);
+            ((value & 0x3_ffff_ffff) as i64, (value >> 34) as u32)
+        }
+        // timestamp 96: unsigned 32-bit nanoseconds and signed 64-bit seconds.
+        12 => (
+            i64::from_be_bytes(data[4..].try_into().ok()?),
+            u32::from_be_bytes(data[..4].try_into().ok()?),
+        ),
+        _ => return None,
+    };
+
+    if nanoseconds >= 1_000_000_000 {
+        return None;
+    }
+    DateTime::from_timestamp(seconds, nanoseconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::config::log_schema;
+
+    use super::*;
+
+    fn encode(value: &MsgpackValue) -> Bytes {
+        let mut buffer = Vec::new();
+        rmpv::encode::write_value(&mut buffer, value).unwrap();
+        buffer.into()
+    }
+
+    #[test]
+    fn deserialize_msgpack() {
+        let input = encode(&MsgpackValue::Map(vec![
+            ("foo".into(), 123.into()),
+            ("bar".into(), MsgpackValue::Binary(vec![0xff, 0x00])),
+            ("big".into(), u64::MAX.into()),
+            (
+                "nested".into(),
+                MsgpackValue::Array(vec![true.into(), 1.5.into()]),
+            ),
+        ]));
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = MsgpackDeserializer.parse(input.clone(), namespace).unwrap();
+            assert_eq!(events.len(), 1);
+
+            let log = events[0].as_log();
+            assert_eq!(log["foo"], 123.into());
+            assert_eq!(log["bar"], Value::Bytes(Bytes::from_static(&[0xff, 0x00])));
+            assert_eq!(log["big"], Value::from_f64_or_zero(u64::MAX as f64));
+            assert_eq!(log["nested[0]"], true.into());
+            assert_eq!(log["nested[1]"], Value::from_f64_or_zero(1.5));
+            assert_eq!(
+                log.get((
+                    lookup::PathPrefix::Event,
+                    log_schema().timestamp_key().unwrap()
+                ))
+                .is_some(),
+                namespace == LogNamespace::Legacy
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_msgpack_timestamps() {
+        let expected = Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap();
+        let timestamp_64 = ((123_456_789_u64) << 34) | 1_700_000_000;
+        let mut timestamp_96 = 123_456_789_u32.to_be_bytes().to_vec();
+        timestamp_96.extend(1_700_000_000_i64.to_be_bytes());
+
+        let input = encode(&MsgpackValue::Map(vec![
+            (
+                "ts32".into(),
+                MsgpackValue::Ext(-1, 1_700_000_000_u32.to_be_bytes().to_vec()),
+            ),
+            (
+                "ts64".into(),
+                MsgpackValue::Ext(-1, timestamp_64.to_be_bytes().to_vec()),
+            ),
+            ("ts96".into(), MsgpackValue::Ext(-1, timestamp_96)),
+            ("other".into(), MsgpackValue::Ext(5, vec![1, 2, 3])),
+        ]));
+
+        let events = MsgpackDeserializer
+            .parse(input, LogNamespace::Vector)
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(
+            log["ts32"],
+            Value::Timestamp(Utc.timestamp_opt(1_700_000_000, 0).unwrap())
+        );
+        assert_eq!(log["ts64"], Value::Timestamp(expected));
+        assert_eq!(log["ts96"], Value::Timestamp(expected));
+        assert_eq!(log["other"], Value::Bytes(Bytes::from_static(&[1, 2, 3])));
+    }
+
+    #[test]
+    fn deserialize_msgpack_array_root() {
+        let input = encode(&MsgpackValue::Array(vec![
+            MsgpackValue::Map(vec![("foo".into(), 1.into())]),
+            MsgpackValue::Map(vec![("foo".into(), 2.into())]),
+        ]));
+
+        let events = MsgpackDeserializer
+            .parse(input, LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].as_log()["foo"], 2.into());
+    }
+
+    #[test]
+    fn deserialize_msgpack_non_object_legacy_namespace() {
+        let input = encode(&MsgpackValue::from(42));
+
+        assert!(
+            MsgpackDeserializer
+                .parse(input.clone(), LogNamespace::Legacy)
+                .is_err()
+        );
+        let events = MsgpackDeserializer
+            .parse(input, LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events[0].as_log()["."], 42.into());
+    }
+
+    #[test]
+    fn deserialize_msgpack_trailing_bytes() {
+        let mut input = encode(&MsgpackValue::Map(vec![])).to_vec();
+        input.push(0xc0);
+
+        assert!(
+            MsgpackDeserializer
+                .parse(input.into(), LogNamespace::Vector)
+                .is_err()
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use bytes::Bytes;
 use chrono::Utc;
 use derivative::Derivative;
-use prost_reflect::{DynamicMessage, MessageDescriptor};
+use prost_reflect::{DynamicMessage, FileDescriptor, MessageDescriptor};
 use smallvec::{SmallVec, smallvec};
 use vector_config::configurable_component;
 use vector_core::event::{LogEvent, TraceEvent};
@@ -17,6 +17,10 @@ use vrl::protobuf::parse::{Options, proto_to_value};
 use vrl::value::{Kind, Value};
 
 use super::Deserializer;
+use crate::common::schema_registry::{
+    SchemaCache, SchemaRegistry, SchemaRegistryConfig, message_by_indexes, split_header,
+    split_message_indexes,
+};
 
 /// Config used to build a `ProtobufDeserializer`.
 #[configurable_component]
@@ -73,17 +77,36 @@ pub struct ProtobufDeserializerOptions {
     /// This file is the output of `protoc -I <include path> -o <desc output path> <proto>`
     ///
     /// You can read more [here](https://buf.build/docs/reference/images/#how-buf-images-work).
+    #[serde(default)]
     pub desc_file: PathBuf,
 
     /// The name of the message type to use for serializing.
     #[configurable(metadata(docs::examples = "package.Message"))]
+    #[serde(default)]
     pub message_type: String,
+
+    /// Decodes messages in the Confluent wire format, looking up their schema in a Schema Registry
+    /// by the ID prefixing them.
+    ///
+    /// When set, `desc_file` and `message_type` are ignored.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_registry: Option<SchemaRegistryConfig>,
+}
+
+/// The schemas of the messages decoded by a `ProtobufDeserializer`.
+#[derive(Debug, Clone)]
+enum ProtobufSchema {
+    /// A single message type, set when the deserializer is built.
+    Static(MessageDescriptor),
+    /// The schemas looked up in a Schema Registry by the ID prefixing each message.
+    Registry(SchemaRegistry, SchemaCache<FileDescriptor>),
 }
 
 /// Deserializer that builds `Event`s from a byte frame containing protobuf.
 #[derive(Debug, Clone)]
 pub struct ProtobufDeserializer {
-    message_descriptor: MessageDescriptor,
+    schema: ProtobufSchema,
     options: Options,
 }
 
@@ -91,7 +114,14 @@ impl ProtobufDeserializer {
     /// Creates a new `ProtobufDeserializer`.
     pub fn new(message_descriptor: MessageDescriptor) -> Self {
         Self {
-            message_descriptor,
+            schema: ProtobufSchema::Static(message_descriptor),
+            options: Default::default(),
+        }
+    }
+
+    fn with_schema_registry(registry: SchemaRegistry) -> Self {
+        Self {
+            schema: ProtobufSchema::Registry(registry, SchemaCache::default()),
             options: Default::default(),
         }
     }
@@ -104,10 +134,26 @@ impl ProtobufDeserializer {
     ) -> vector_common::Result<Self> {
         let message_descriptor = get_message_descriptor_from_bytes(desc_bytes, message_type)?;
         Ok(Self {
-            message_descriptor,
+            schema: ProtobufSchema::Static(message_descriptor),
             options,
         })
     }
+
+    /// Resolves the message type of `bytes`, returning it along with the message itself.
+    fn resolve_message(&self, bytes: Bytes) -> vector_common::Result<(MessageDescriptor, Bytes)> {
+        match &self.schema {
+            ProtobufSchema::Static(message_descriptor) => Ok((message_descriptor.clone(), bytes)),
+            ProtobufSchema::Registry(registry, cache) => {
+                let (id, bytes) = split_header(bytes)?;
+                let (indexes, bytes) = split_message_indexes(bytes)?;
+                let file = cache.get_or_resolve(id, |id| {
+                    let schema = registry.schema_by_id(id, Some("serialized"))?;
+                    registry.protobuf_file_descriptor(schema, &format!("schema-{id}.proto"))
+                })?;
+                Ok((message_by_indexes(&file, &indexes)?, bytes))
+            }
+        }
+    }
 }
 
 fn extract_vrl_value(
@@ -131,7 +177,8 @@ impl Deserializer for ProtobufDeserializer {
         bytes: Bytes,
         log_namespace: LogNamespace,
     ) -> vector_common::Result<SmallVec<[Event; 1]>> {
-        let vrl_value = extract_vrl_value(bytes, &self.message_descriptor, &self.options)?;
+        let (message_descriptor, bytes) = self.resolve_message(bytes)?;
+        let vrl_value = extract_vrl_value(bytes, &message_descriptor, &self.options)?;
         let mut event = Event::Log(LogEvent::from(vrl_value));
 
         let event = match log_namespace {
@@ -152,7 +199,8 @@ impl Deserializer for ProtobufDeserializer {
     }
 
     fn parse_traces(&self, bytes: Bytes) -> vector_common::Result<SmallVec<[Event; 1]>> {
-        let vrl_value = extract_vrl_value(bytes, &self.message_descriptor, &self.options)?;
+        let (message_descriptor, bytes) = self.resolve_message(bytes)?;
+        let vrl_value = extract_vrl_value(bytes, &message_descriptor, &self.options)?;
         let trace_event = Event::Trace(TraceEvent::from(vrl_value));
         Ok(smallvec![trace_event])
     }
@@ -161,6 +209,9 @@ impl Deserializer for ProtobufDeserializer {
 impl TryFrom<&ProtobufDeserializerConfig> for ProtobufDeserializer {
     type Error = vector_common::Error;
     fn try_from(config: &ProtobufDeserializerConfig) -> vector_common::Result<Self> {
+        if let Some(registry) = &config.protobuf.schema_registry {
+            return Ok(Self::with_schema_registry(SchemaRegistry::new(registry)?));
+        }
         let message_descriptor =
             get_message_descriptor(&config.protobuf.desc_file, &config.protobuf.message_type)?;
         Ok(Self::new(message_descriptor))
@@ -176,6 +227,7 @@ mod tests {
     use vector_core::config::log_schema;
 
     use super::*;
+    use crate::common::schema_registry::test_util::{mock_registry, protobuf_test_schema};
 
     fn test_data_dir() -> PathBuf {
         PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("tests/data/protobuf")
@@ -260,6 +312,46 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn deserialize_protobuf_schema_registry() {
+        let server = mock_registry(&[(7, protobuf_test_schema())]).await;
+        let config = ProtobufDeserializerConfig {
+            protobuf: ProtobufDeserializerOptions {
+                schema_registry: Some(SchemaRegistryConfig::new(server.uri())),
+                ..Default::default()
+            },
+        };
+        let deserializer = config.build().unwrap();
+
+        // Magic byte, schema ID and the `[0]` message indexes of `test_protobuf.Person`.
+        let mut bytes = b"\0\0\0\0\x07\0".to_vec();
+        bytes.extend(fs::read(test_data_dir().join("pbs/person_someone.pb")).unwrap());
+        let events = deserializer
+            .parse(Bytes::from(bytes), LogNamespace::Vector)
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["name"], "someone".into());
+
+        // `test_protobuf.AddressBook`, at `[1]`, with no people.
+        let events = deserializer
+            .parse(
+                Bytes::from_static(b"\0\0\0\0\x07\x02\x02"),
+                LogNamespace::Vector,
+            )
+            .unwrap();
+        assert!(!events[0].as_log().contains("people"));
+
+        assert!(
+            deserializer
+                .parse(
+                    Bytes::from_static(b"\0\0\0\0\x07\x02\x04"),
+                    LogNamespace::Vector
+                )
+                .is_err()
+        );
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
     #[test]
     fn deserialize_empty_buffer() {
         let protobuf_bin_message = "".to_string();
@@ -9,12 +9,13 @@ use crate::decoding::format::{VrlDeserializer, VrlDeserializerConfig};
 use bytes::{Bytes, BytesMut};
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
-    GelfDeserializerConfig, GelfDeserializerOptions, InfluxdbDeserializer,
-    InfluxdbDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions,
-    NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
-    NativeJsonDeserializerConfig, NativeJsonDeserializerOptions, ProtobufDeserializer,
-    ProtobufDeserializerConfig, ProtobufDeserializerOptions,
+    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, CborDeserializer,
+    CborDeserializerConfig, GelfDeserializer, GelfDeserializerConfig, GelfDeserializerOptions,
+    InfluxdbDeserializer, InfluxdbDeserializerConfig, JsonDeserializer, JsonDeserializerConfig,
+    JsonDeserializerOptions, MsgpackDeserializer, MsgpackDeserializerConfig, NativeDeserializer,
+    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    NativeJsonDeserializerOptions, ProtobufDeserializer, ProtobufDeserializerConfig,
+    ProtobufDeserializerOptions,
 };
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
@@ -303,6 +304,25 @@ pub enum DeserializerConfig {
         avro: AvroDeserializerOptions,
     },
 
+    /// Decodes the raw bytes as a [MessagePack][msgpack] value.
+    ///
+    /// Binary values are decoded as bytes, and timestamp extension values are decoded as
+    /// timestamps. Unsigned integers that do not fit in a signed 64-bit integer are decoded as
+    /// floats. If the value is an array, each of its elements is decoded as a separate event.
+    ///
+    /// [msgpack]: https://msgpack.org/
+    Msgpack,
+
+    /// Decodes the raw bytes as a [CBOR][cbor] value.
+    ///
+    /// Byte strings are decoded as bytes, and date/time values (tags 0 and 1) are decoded as
+    /// timestamps. Integers that do not fit in a signed 64-bit integer, including bignums, are
+    /// decoded as floats. If the value is an array, each of its elements is decoded as a separate
+    /// event.
+    ///
+    /// [cbor]: https://cbor.io/
+    Cbor,
+
     /// Decodes the raw bytes as a string and passes them as input to a [VRL][vrl] program.
     ///
     /// [vrl]: https://vector.dev/docs/reference/vrl
@@ -346,6 +366,18 @@ impl From<NativeJsonDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<MsgpackDeserializerConfig> for DeserializerConfig {
+    fn from(_: MsgpackDeserializerConfig) -> Self {
+        Self::Msgpack
+    }
+}
+
+impl From<CborDeserializerConfig> for DeserializerConfig {
+    fn from(_: CborDeserializerConfig) -> Self {
+        Self::Cbor
+    }
+}
+
 impl From<InfluxdbDeserializerConfig> for DeserializerConfig {
     fn from(config: InfluxdbDeserializerConfig) -> Self {
         Self::Influxdb(config)
@@ -360,7 +392,7 @@ impl DeserializerConfig {
                 AvroDeserializerConfig {
                     avro_options: avro.clone(),
                 }
-                .build(),
+                .build()?,
             )),
             DeserializerConfig::Bytes => Ok(Deserializer::Bytes(BytesDeserializerConfig.build())),
             DeserializerConfig::Json(config) => Ok(Deserializer::Json(config.build())),
@@ -374,6 +406,10 @@ impl DeserializerConfig {
             DeserializerConfig::Gelf(config) => Ok(Deserializer::Gelf(config.build())),
             DeserializerConfig::Influxdb(config) => Ok(Deserializer::Influxdb(config.build())),
             DeserializerConfig::Vrl(config) => Ok(Deserializer::Vrl(config.build()?)),
+            DeserializerConfig::Msgpack => {
+                Ok(Deserializer::Msgpack(MsgpackDeserializerConfig.build()))
+            }
+            DeserializerConfig::Cbor => Ok(Deserializer::Cbor(CborDeserializerConfig.build())),
         }
     }
 
@@ -381,7 +417,9 @@ impl DeserializerConfig {
     pub fn default_stream_framing(&self) -> FramingConfig {
         match self {
             DeserializerConfig::Avro { .. } => FramingConfig::Bytes,
-            DeserializerConfig::Native => FramingConfig::LengthDelimited(Default::default()),
+            DeserializerConfig::Native | DeserializerConfig::Msgpack | DeserializerConfig::Cbor => {
+                FramingConfig::LengthDelimited(Default::default())
+            }
             DeserializerConfig::Bytes
             | DeserializerConfig::Json(_)
             | DeserializerConfig::Influxdb(_)
@@ -423,6 +461,8 @@ impl DeserializerConfig {
             DeserializerConfig::Gelf(config) => config.output_type(),
             DeserializerConfig::Vrl(config) => config.output_type(),
             DeserializerConfig::Influxdb(config) => config.output_type(),
+            DeserializerConfig::Msgpack => MsgpackDeserializerConfig.output_type(),
+            DeserializerConfig::Cbor => CborDeserializerConfig.output_type(),
         }
     }
 
@@ -443,6 +483,10 @@ impl DeserializerConfig {
             DeserializerConfig::Gelf(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Influxdb(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Vrl(config) => config.schema_definition(log_namespace),
+            DeserializerConfig::Msgpack => {
+                MsgpackDeserializerConfig.schema_definition(log_namespace)
+            }
+            DeserializerConfig::Cbor => CborDeserializerConfig.schema_definition(log_namespace),
         }
     }
 
@@ -469,6 +513,8 @@ impl DeserializerConfig {
                 "application/octet-stream"
             }
             (DeserializerConfig::Protobuf(_), _) => "application/octet-stream",
+            (DeserializerConfig::Msgpack, _) => "application/msgpack",
+            (DeserializerConfig::Cbor, _) => "application/cbor",
             (
                 DeserializerConfig::Json(_)
                 | DeserializerConfig::NativeJson(_)
@@ -511,6 +557,10 @@ pub enum Deserializer {
     Influxdb(InfluxdbDeserializer),
     /// Uses a `VrlDeserializer` for deserialization.
     Vrl(VrlDeserializer),
+    /// Uses a `MsgpackDeserializer` for deserialization.
+    Msgpack(MsgpackDeserializer),
+    /// Uses a `CborDeserializer` for deserialization.
+    Cbor(CborDeserializer),
 }
 
 impl format::Deserializer for Deserializer {
@@ -532,6 +582,8 @@ impl format::Deserializer for Deserializer {
             Deserializer::Gelf(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Influxdb(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Vrl(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Msgpack(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Cbor(deserializer) => deserializer.parse(bytes, log_namespace),
         }
     }
 }
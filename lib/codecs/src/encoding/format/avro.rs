@@ -1,3 +1,6 @@
+use crate::common::schema_registry::{
+    SchemaRegistry, SchemaRegistrySerializerConfig, write_header,
+};
 use crate::encoding::BuildError;
 use bytes::{BufMut, BytesMut};
 use serde::{Deserialize, Serialize};
@@ -16,15 +19,45 @@ impl AvroSerializerConfig {
     /// Creates a new `AvroSerializerConfig`.
     pub const fn new(schema: String) -> Self {
         Self {
-            avro: AvroSerializerOptions { schema },
+            avro: AvroSerializerOptions {
+                schema,
+                schema_registry: None,
+            },
         }
     }
 
     /// Build the `AvroSerializer` from this configuration.
+    ///
+    /// When a Schema Registry is configured, the schema is resolved in it, which blocks until the
+    /// registry responds.
     pub fn build(&self) -> Result<AvroSerializer, BuildError> {
-        let schema = apache_avro::Schema::parse_str(&self.avro.schema)
-            .map_err(|error| format!("Failed building Avro serializer: {error}"))?;
-        Ok(AvroSerializer { schema })
+        let Some(config) = &self.avro.schema_registry else {
+            return Ok(AvroSerializer::new(parse_schema(&self.avro.schema)?));
+        };
+
+        let registry = SchemaRegistry::new(&config.registry)?;
+        let (schema_id, schema) = if self.avro.schema.is_empty() {
+            if config.auto_register {
+                return Err("A `schema` is required to be registered by `auto_register`.".into());
+            }
+            let registered = registry.schema_by_version(&config.subject, "latest", None)?;
+            registered.expect_type(&config.subject, "AVRO")?;
+            let schema_id = registered
+                .id
+                .ok_or("Schema Registry response is missing the schema ID.")?;
+            (schema_id, registered.schema)
+        } else if config.auto_register {
+            let schema_id = registry.register(&config.subject, &self.avro.schema, "AVRO")?;
+            (schema_id, self.avro.schema.clone())
+        } else {
+            let schema_id = registry.lookup(&config.subject, &self.avro.schema, "AVRO")?;
+            (schema_id, self.avro.schema.clone())
+        };
+
+        Ok(AvroSerializer {
+            schema: parse_schema(&schema)?,
+            schema_id: Some(schema_id),
+        })
     }
 
     /// The data type of events that are accepted by `AvroSerializer`.
@@ -48,19 +81,38 @@ pub struct AvroSerializerOptions {
         docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#
     ))]
     #[configurable(metadata(docs::human_name = "Schema JSON"))]
+    #[serde(default)]
     pub schema: String,
+
+    /// Prefixes each message with the ID of its schema in a Schema Registry, in the Confluent wire
+    /// format.
+    ///
+    /// When `schema` is not set, the latest schema registered under the subject is used.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_registry: Option<SchemaRegistrySerializerConfig>,
+}
+
+fn parse_schema(schema: &str) -> Result<apache_avro::Schema, BuildError> {
+    apache_avro::Schema::parse_str(schema)
+        .map_err(|error| format!("Failed building Avro serializer: {error}").into())
 }
 
 /// Serializer that converts an `Event` to bytes using the Apache Avro format.
 #[derive(Debug, Clone)]
 pub struct AvroSerializer {
     schema: apache_avro::Schema,
+    /// The ID of the schema in the Schema Registry, if any, which prefixes each message.
+    schema_id: Option<u32>,
 }
 
 impl AvroSerializer {
     /// Creates a new `AvroSerializer`.
     pub const fn new(schema: apache_avro::Schema) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            schema_id: None,
+        }
     }
 }
 
@@ -72,6 +124,9 @@ impl Encoder<Event> for AvroSerializer {
         let value = apache_avro::to_value(log)?;
         let value = value.resolve(&self.schema)?;
         let bytes = apache_avro::to_avro_datum(&self.schema, value)?;
+        if let Some(schema_id) = self.schema_id {
+            write_header(buffer, schema_id);
+        }
         buffer.put_slice(&bytes);
         Ok(())
     }
@@ -81,10 +136,16 @@ impl Encoder<Event> for AvroSerializer {
 mod tests {
     use bytes::BytesMut;
     use indoc::indoc;
+    use serde_json::json;
     use vector_core::event::{LogEvent, Value};
     use vrl::btreemap;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_partial_json, method, path},
+    };
 
     use super::*;
+    use crate::common::schema_registry::SchemaRegistryConfig;
 
     #[test]
     fn serialize_avro() {
@@ -112,4 +173,35 @@ mod tests {
 
         assert_eq!(bytes.freeze(), b"\0\x06bar".as_slice());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serialize_avro_schema_registry() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/subjects/logs-value/versions"))
+            .and(body_partial_json(json!({ "schemaType": "AVRO" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": 42 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "foo" => Value::from("bar")
+        }));
+        let mut config = AvroSerializerConfig::new(
+            r#"{ "type": "record", "name": "Log", "fields": [{ "name": "foo", "type": "string" }] }"#
+                .to_owned(),
+        );
+        config.avro.schema_registry = Some(SchemaRegistrySerializerConfig {
+            registry: SchemaRegistryConfig::new(server.uri()),
+            subject: "logs-value".to_owned(),
+            auto_register: true,
+        });
+        let mut serializer = config.build().unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(bytes.freeze(), b"\0\0\0\0\x2a\x06bar".as_slice());
+    }
 }
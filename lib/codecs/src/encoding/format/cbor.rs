@@ -0,0 +1,150 @@
+use bytes::{BufMut, BytesMut};
+use chrono::SecondsFormat;
+use ciborium::value::Value as CborValue;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_core::{config::DataType, event::Event, schema};
+use vrl::value::Value;
+
+use crate::encoding::format::common::event_into_value;
+
+/// The CBOR tag for a date/time string, as defined in RFC 8949.
+const CBOR_DATE_TIME_TAG: u64 = 0;
+
+/// Config used to build a `CborSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CborSerializerConfig;
+
+impl CborSerializerConfig {
+    /// Build the `CborSerializer` from this configuration.
+    pub const fn build(&self) -> CborSerializer {
+        CborSerializer
+    }
+
+    /// The data type of events that are accepted by `CborSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::all_bits()
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the [CBOR][cbor] format.
+///
+/// [cbor]: https://cbor.io/
+#[derive(Debug, Clone)]
+pub struct CborSerializer;
+
+impl Encoder<Event> for CborSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let value = to_cbor(event_into_value(event)?);
+        ciborium::ser::into_writer(&value, buffer.writer())
+            .map_err(|error| format!("Error serializing CBOR: {error}"))?;
+        Ok(())
+    }
+}
+
+fn to_cbor(value: Value) -> CborValue {
+    match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(text) => CborValue::Text(text),
+            Err(error) => CborValue::Bytes(error.into_bytes()),
+        },
+        Value::Regex(regex) => CborValue::Text(regex.as_str().to_string()),
+        Value::Integer(integer) => CborValue::Integer(integer.into()),
+        Value::Float(float) => CborValue::Float(float.into_inner()),
+        Value::Boolean(boolean) => CborValue::Bool(boolean),
+        Value::Timestamp(timestamp) => CborValue::Tag(
+            CBOR_DATE_TIME_TAG,
+            Box::new(CborValue::Text(
+                timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            )),
+        ),
+        Value::Object(object) => CborValue::Map(
+            object
+                .into_iter()
+                .map(|(key, value)| (CborValue::Text(key.as_str().to_owned()), to_cbor(value)))
+                .collect(),
+        ),
+        Value::Array(array) => CborValue::Array(array.into_iter().map(to_cbor).collect()),
+        Value::Null => CborValue::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::{LogEvent, Metric, MetricKind, MetricValue};
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn encode(event: Event) -> CborValue {
+        let mut buffer = BytesMut::new();
+        CborSerializer.encode(event, &mut buffer).unwrap();
+        ciborium::de::from_reader(&buffer[..]).unwrap()
+    }
+
+    fn get<'a>(value: &'a CborValue, key: &str) -> &'a CborValue {
+        value
+            .as_map()
+            .unwrap()
+            .iter()
+            .find(|(k, _)| k.as_text() == Some(key))
+            .map(|(_, v)| v)
+            .unwrap()
+    }
+
+    #[test]
+    fn serialize_cbor_log() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "string" => "foo",
+            "binary" => Value::Bytes(Bytes::from_static(&[0xff, 0x00])),
+            "integer" => -42,
+            "float" => 1.5,
+            "timestamp" => Utc.timestamp_opt(1_700_000_000, 500_000_000).unwrap(),
+            "nested" => btreemap! { "null" => Value::Null, "array" => vec![true] },
+        }));
+
+        let value = encode(event);
+        assert_eq!(get(&value, "string"), &CborValue::Text("foo".to_string()));
+        assert_eq!(get(&value, "binary"), &CborValue::Bytes(vec![0xff, 0x00]));
+        assert_eq!(get(&value, "integer"), &CborValue::Integer((-42).into()));
+        assert_eq!(get(&value, "float"), &CborValue::Float(1.5));
+        assert_eq!(
+            get(&value, "timestamp"),
+            &CborValue::Tag(
+                CBOR_DATE_TIME_TAG,
+                Box::new(CborValue::Text("2023-11-14T22:13:20.500Z".to_string()))
+            )
+        );
+        let nested = get(&value, "nested");
+        assert_eq!(get(nested, "null"), &CborValue::Null);
+        assert_eq!(
+            get(nested, "array"),
+            &CborValue::Array(vec![CborValue::Bool(true)])
+        );
+    }
+
+    #[test]
+    fn serialize_cbor_metric() {
+        let event = Event::Metric(Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        let value = encode(event);
+        assert_eq!(get(&value, "name"), &CborValue::Text("counter".to_string()));
+        assert_eq!(
+            get(&value, "kind"),
+            &CborValue::Text("incremental".to_string())
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use vector_core::config::log_schema;
+use vector_core::event::Event;
 use vector_core::schema;
-use vrl::value::Kind;
+use vrl::value::{Kind, Value};
 
 /// Inspect the global log schema and create a schema requirement.
 pub fn get_serializer_schema_requirement() -> schema::Requirement {
@@ -10,3 +11,16 @@ pub fn get_serializer_schema_requirement() -> schema::Requirement {
         schema::Requirement::empty()
     }
 }
+
+/// Converts an event into a `Value`, for serializers of self-describing formats that can
+/// represent any `Value`, such as MessagePack or CBOR.
+///
+/// Metrics have no `Value` representation of their own, so they are converted through their JSON
+/// representation instead.
+pub fn event_into_value(event: Event) -> Result<Value, vector_common::Error> {
+    Ok(match event {
+        Event::Log(log) => log.into_parts().0,
+        Event::Metric(metric) => Value::from(serde_json::to_value(&metric)?),
+        Event::Trace(trace) => Value::Object(trace.into_parts().0),
+    })
+}
@@ -4,14 +4,17 @@
 #![deny(missing_docs)]
 
 mod avro;
+mod cbor;
 mod cef;
 mod common;
 mod csv;
 mod gelf;
 mod json;
 mod logfmt;
+mod msgpack;
 mod native;
 mod native_json;
+mod parquet;
 mod protobuf;
 mod raw_message;
 mod text;
@@ -20,13 +23,19 @@ use std::fmt::Debug;
 
 pub use self::csv::{CsvSerializer, CsvSerializerConfig};
 pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
+pub use cbor::{CborSerializer, CborSerializerConfig};
 pub use cef::{CefSerializer, CefSerializerConfig};
 use dyn_clone::DynClone;
 pub use gelf::{GelfSerializer, GelfSerializerConfig};
 pub use json::{JsonSerializer, JsonSerializerConfig, JsonSerializerOptions};
 pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
+pub use msgpack::{MsgpackSerializer, MsgpackSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
+pub use parquet::{
+    ParquetCompression, ParquetField, ParquetFieldType, ParquetSerializer, ParquetSerializerConfig,
+    ParquetSerializerOptions,
+};
 pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
 pub use text::{TextSerializer, TextSerializerConfig};
@@ -0,0 +1,181 @@
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, Utc};
+use rmpv::Value as MsgpackValue;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_core::{config::DataType, event::Event, schema};
+use vrl::value::Value;
+
+use crate::encoding::format::common::event_into_value;
+
+/// The MessagePack extension type reserved for timestamps.
+const MSGPACK_TIMESTAMP_EXT_TYPE: i8 = -1;
+
+/// Config used to build a `MsgpackSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackSerializerConfig;
+
+impl MsgpackSerializerConfig {
+    /// Build the `MsgpackSerializer` from this configuration.
+    pub const fn build(&self) -> MsgpackSerializer {
+        MsgpackSerializer
+    }
+
+    /// The data type of events that are accepted by `MsgpackSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::all_bits()
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the [MessagePack][msgpack] format.
+///
+/// [msgpack]: https://msgpack.org/
+#[derive(Debug, Clone)]
+pub struct MsgpackSerializer;
+
+impl Encoder<Event> for MsgpackSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let value = to_msgpack(event_into_value(event)?);
+        rmpv::encode::write_value(&mut buffer.writer(), &value)?;
+        Ok(())
+    }
+}
+
+fn to_msgpack(value: Value) -> MsgpackValue {
+    match value {
+        Value::Bytes(bytes) => match std::str::from_utf8(&bytes) {
+            Ok(string) => MsgpackValue::from(string),
+            Err(_) => MsgpackValue::Binary(bytes.to_vec()),
+        },
+        Value::Regex(regex) => MsgpackValue::from(regex.as_str()),
+        Value::Integer(integer) => MsgpackValue::from(integer),
+        Value::Float(float) => MsgpackValue::F64(float.into_inner()),
+        Value::Boolean(boolean) => MsgpackValue::Boolean(boolean),
+        Value::Timestamp(timestamp) => {
+            MsgpackValue::Ext(MSGPACK_TIMESTAMP_EXT_TYPE, encode_timestamp(timestamp))
+        }
+        Value::Object(object) => MsgpackValue::Map(
+            object
+                .into_iter()
+                .map(|(key, value)| (MsgpackValue::from(key.as_str()), to_msgpack(value)))
+                .collect(),
+        ),
+        Value::Array(array) => MsgpackValue::Array(array.into_iter().map(to_msgpack).collect()),
+        Value::Null => MsgpackValue::Nil,
+    }
+}
+
+/// Encodes the payload of a timestamp extension value, using the smallest format that can
+/// represent it.
+fn encode_timestamp(timestamp: DateTime<Utc>) -> Vec<u8> {
+    let seconds = timestamp.timestamp();
+    let nanoseconds = timestamp.timestamp_subsec_nanos();
+
+    if seconds >> 34 == 0 {
+        let value = (u64::from(nanoseconds) << 34) | seconds as u64;
+        if value >> 32 == 0 {
+            // timestamp 32: unsigned 32-bit seconds.
+            (value as u32).to_be_bytes().to_vec()
+        } else {
+            // timestamp 64: unsigned 30-bit nanoseconds and unsigned 34-bit seconds.
+            value.to_be_bytes().to_vec()
+        }
+    } else {
+        // timestamp 96: unsigned 32-bit nanoseconds and signed 64-bit seconds.
+        let mut data = nanoseconds.to_be_bytes().to_vec();
+        data.extend(seconds.to_be_bytes());
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::TimeZone;
+    use vector_core::event::{LogEvent, Metric, MetricKind, MetricValue};
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn encode(event: Event) -> MsgpackValue {
+        let mut buffer = BytesMut::new();
+        MsgpackSerializer.encode(event, &mut buffer).unwrap();
+        rmpv::decode::read_value(&mut &buffer[..]).unwrap()
+    }
+
+    fn get<'a>(value: &'a MsgpackValue, key: &str) -> &'a MsgpackValue {
+        value
+            .as_map()
+            .unwrap()
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|(_, v)| v)
+            .unwrap()
+    }
+
+    #[test]
+    fn serialize_msgpack_log() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "string" => "foo",
+            "binary" => Value::Bytes(Bytes::from_static(&[0xff, 0x00])),
+            "integer" => -42,
+            "float" => 1.5,
+            "nested" => btreemap! { "null" => Value::Null, "array" => vec![true] },
+        }));
+
+        let value = encode(event);
+        assert_eq!(get(&value, "string"), &MsgpackValue::from("foo"));
+        assert_eq!(
+            get(&value, "binary"),
+            &MsgpackValue::Binary(vec![0xff, 0x00])
+        );
+        assert_eq!(get(&value, "integer"), &MsgpackValue::from(-42));
+        assert_eq!(get(&value, "float"), &MsgpackValue::F64(1.5));
+        let nested = get(&value, "nested");
+        assert_eq!(get(nested, "null"), &MsgpackValue::Nil);
+        assert_eq!(
+            get(nested, "array"),
+            &MsgpackValue::Array(vec![MsgpackValue::Boolean(true)])
+        );
+    }
+
+    #[test]
+    fn serialize_msgpack_timestamps() {
+        let cases = [
+            (Utc.timestamp_opt(1_700_000_000, 0).unwrap(), 4),
+            (Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap(), 8),
+            (Utc.timestamp_opt(-1, 500).unwrap(), 12),
+        ];
+
+        for (timestamp, len) in cases {
+            let value = encode(Event::Log(LogEvent::from(btreemap! {
+                "timestamp" => timestamp,
+            })));
+            let MsgpackValue::Ext(MSGPACK_TIMESTAMP_EXT_TYPE, data) = get(&value, "timestamp")
+            else {
+                panic!("timestamp should be encoded as an extension value");
+            };
+            assert_eq!(data.len(), len);
+        }
+    }
+
+    #[test]
+    fn serialize_msgpack_metric() {
+        let event = Event::Metric(Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        let value = encode(event);
+        assert_eq!(get(&value, "name"), &MsgpackValue::from("counter"));
+        assert_eq!(get(&value, "kind"), &MsgpackValue::from("incremental"));
+    }
+}
@@ -0,0 +1,785 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use arrow_array::{
+    ArrayRef, RecordBatch,
+    builder::{
+        BinaryBuilder, BooleanBuilder, Float64Builder, Int64Builder, StringBuilder,
+        TimestampMicrosecondBuilder,
+    },
+};
+use arrow_schema::{DataType as ArrowDataType, Field, Schema, SchemaRef, TimeUnit};
+use bytes::{BufMut, BytesMut};
+use lookup::lookup_v2::ConfigTargetPath;
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, GzipLevel, ZstdLevel},
+    file::properties::WriterProperties,
+};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+use vrl::{owned_value_path, path::OwnedTargetPath, value::Kind};
+
+use crate::encoding::BuildError;
+
+/// Config used to build a `ParquetSerializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct ParquetSerializerConfig {
+    /// Apache Parquet-specific encoder options.
+    #[serde(default)]
+    pub parquet: ParquetSerializerOptions,
+}
+
+impl ParquetSerializerConfig {
+    /// Creates a new `ParquetSerializerConfig`.
+    pub const fn new(parquet: ParquetSerializerOptions) -> Self {
+        Self { parquet }
+    }
+
+    /// Derives the Parquet columns from the given schema definition, unless columns were
+    /// configured explicitly.
+    ///
+    /// Every top-level field known to the definition becomes an optional column. Fields whose
+    /// kind is not a single scalar type are written as JSON strings.
+    pub fn with_schema_definition(mut self, definition: &schema::Definition) -> Self {
+        if self.parquet.schema.is_empty() {
+            self.parquet.schema = fields_from_definition(definition);
+        }
+        self
+    }
+
+    /// Build the `ParquetSerializer` from this configuration.
+    pub fn build(&self) -> Result<ParquetSerializer, BuildError> {
+        if self.parquet.row_group_size == 0 {
+            return Err("Parquet `row_group_size` must be greater than zero".into());
+        }
+
+        let mut columns = Vec::with_capacity(self.parquet.schema.len());
+        for field in &self.parquet.schema {
+            if field.name.is_empty() {
+                return Err("Parquet column names must not be empty".into());
+            }
+            if columns
+                .iter()
+                .any(|column: &ParquetColumn| column.name == field.name)
+            {
+                return Err(format!("Duplicate Parquet column name `{}`", field.name).into());
+            }
+            columns.push(ParquetColumn::from(field));
+        }
+
+        let properties = WriterProperties::builder()
+            .set_compression(self.parquet.compression.into())
+            .set_max_row_group_size(self.parquet.row_group_size)
+            .build();
+
+        Ok(ParquetSerializer {
+            columns: Arc::new(columns),
+            properties: Arc::new(properties),
+        })
+    }
+
+    /// The data type of events that are accepted by `ParquetSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        // Missing or mistyped values are written as nulls, so no particular schema is required.
+        schema::Requirement::empty()
+    }
+}
+
+/// Apache Parquet serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ParquetSerializerOptions {
+    /// The columns written to each Parquet file, in order.
+    ///
+    /// When no columns are configured, the columns are derived from the top-level fields of the
+    /// schema definition of the events when it knows any, and otherwise from the top-level fields
+    /// of the events in each batch. Events without any top-level field, such as events whose root
+    /// is a string, are written to a single `message` column.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub schema: Vec<ParquetField>,
+
+    /// The maximum number of rows written to a single row group.
+    ///
+    /// Each batch of events is written as one file, split into as many row groups as needed.
+    #[serde(default = "default_row_group_size")]
+    pub row_group_size: usize,
+
+    /// The compression codec applied to the column chunks.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub compression: ParquetCompression,
+}
+
+const fn default_row_group_size() -> usize {
+    128 * 1024
+}
+
+impl Default for ParquetSerializerOptions {
+    fn default() -> Self {
+        Self {
+            schema: Vec::new(),
+            row_group_size: default_row_group_size(),
+            compression: ParquetCompression::default(),
+        }
+    }
+}
+
+/// A column of the Parquet schema.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParquetField {
+    /// The name of the column.
+    #[configurable(metadata(docs::examples = "message"))]
+    pub name: String,
+
+    /// The path of the event field written to the column.
+    ///
+    /// Defaults to the top-level field with the same name as the column.
+    #[configurable(metadata(docs::examples = ".http.status"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub path: Option<ConfigTargetPath>,
+
+    /// The type of the column.
+    #[serde(rename = "type")]
+    pub r#type: ParquetFieldType,
+
+    /// Whether the column accepts null values.
+    ///
+    /// When disabled, encoding a batch fails if any event is missing a value for this column.
+    #[serde(default = "default_nullable")]
+    pub nullable: bool,
+}
+
+const fn default_nullable() -> bool {
+    true
+}
+
+/// The type of a Parquet column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetFieldType {
+    /// UTF-8 string. Non-string scalar values are converted to their string representation.
+    String,
+
+    /// 64-bit signed integer.
+    Int64,
+
+    /// 64-bit floating point number. Integers are converted to floats.
+    Double,
+
+    /// Boolean.
+    Boolean,
+
+    /// Timestamp with microsecond precision, in UTC.
+    Timestamp,
+
+    /// Raw bytes.
+    Binary,
+
+    /// JSON-encoded string. Useful for objects and arrays.
+    Json,
+}
+
+impl ParquetFieldType {
+    fn from_kind(kind: &Kind) -> Self {
+        let is_collection =
+            kind.contains_object() || kind.contains_array() || kind.contains_regex();
+        let scalars = [
+            (kind.contains_bytes(), Self::String),
+            (kind.contains_integer(), Self::Int64),
+            (kind.contains_float(), Self::Double),
+            (kind.contains_boolean(), Self::Boolean),
+            (kind.contains_timestamp(), Self::Timestamp),
+        ];
+        let mut contained = scalars.iter().filter(|(contains, _)| *contains);
+
+        match (
+            is_collection,
+            contained.next(),
+            contained.next(),
+            contained.next(),
+        ) {
+            (false, Some((_, field_type)), None, _) => *field_type,
+            (false, Some((_, Self::Int64)), Some((_, Self::Double)), None) => Self::Double,
+            _ => Self::Json,
+        }
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bytes(_) => Some(Self::String),
+            Value::Integer(_) => Some(Self::Int64),
+            Value::Float(_) => Some(Self::Double),
+            Value::Boolean(_) => Some(Self::Boolean),
+            Value::Timestamp(_) => Some(Self::Timestamp),
+            Value::Null => None,
+            Value::Regex(_) | Value::Object(_) | Value::Array(_) => Some(Self::Json),
+        }
+    }
+
+    const fn arrow_type(self) -> ArrowDataType {
+        match self {
+            Self::String | Self::Json => ArrowDataType::Utf8,
+            Self::Int64 => ArrowDataType::Int64,
+            Self::Double => ArrowDataType::Float64,
+            Self::Boolean => ArrowDataType::Boolean,
+            Self::Timestamp => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+            Self::Binary => ArrowDataType::Binary,
+        }
+    }
+}
+
+/// The compression codec applied to Parquet column chunks.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    #[default]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParquetColumn {
+    name: String,
+    path: OwnedTargetPath,
+    field_type: ParquetFieldType,
+    nullable: bool,
+}
+
+impl From<&ParquetField> for ParquetColumn {
+    fn from(field: &ParquetField) -> Self {
+        let path = match &field.path {
+            Some(path) => path.0.clone(),
+            None => OwnedTargetPath::event(owned_value_path!(field.name.as_str())),
+        };
+        Self {
+            name: field.name.clone(),
+            path,
+            field_type: field.r#type,
+            nullable: field.nullable,
+        }
+    }
+}
+
+/// Serializer that converts batches of `Event`s to bytes using the Apache Parquet format.
+///
+/// Every batch is written as a complete Parquet file. Encoding a single event produces a file
+/// holding a single row.
+#[derive(Debug, Clone)]
+pub struct ParquetSerializer {
+    columns: Arc<Vec<ParquetColumn>>,
+    properties: Arc<WriterProperties>,
+}
+
+impl ParquetSerializer {
+    /// Encodes the given events into a Parquet file, written to the provided buffer.
+    pub fn encode_batch(
+        &self,
+        events: Vec<Event>,
+        buffer: &mut BytesMut,
+    ) -> Result<(), vector_common::Error> {
+        let logs = events.into_iter().map(Event::into_log).collect::<Vec<_>>();
+        let columns = if self.columns.is_empty() {
+            derive_columns(&logs)
+        } else {
+            self.columns.as_ref().clone()
+        };
+
+        let schema: SchemaRef = Arc::new(Schema::new(
+            columns
+                .iter()
+                .map(|column| {
+                    Field::new(
+                        column.name.as_str(),
+                        column.field_type.arrow_type(),
+                        column.nullable,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        ));
+        let arrays = columns
+            .iter()
+            .map(|column| build_array(column, &logs))
+            .collect::<Result<Vec<_>, _>>()?;
+        let batch = RecordBatch::try_new(Arc::clone(&schema), arrays)?;
+
+        let mut bytes = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut bytes, schema, Some(self.properties.as_ref().clone()))?;
+        writer.write(&batch)?;
+        writer.close()?;
+
+        buffer.put_slice(&bytes);
+        Ok(())
+    }
+}
+
+impl Encoder<Event> for ParquetSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode_batch(vec![event], buffer)
+    }
+}
+
+/// Returns optional columns for the top-level fields known to a schema definition.
+fn fields_from_definition(definition: &schema::Definition) -> Vec<ParquetField> {
+    definition
+        .event_kind()
+        .as_object()
+        .map(|object| {
+            object
+                .known()
+                .iter()
+                .map(|(field, kind)| ParquetField {
+                    name: field.to_string(),
+                    path: None,
+                    r#type: ParquetFieldType::from_kind(kind),
+                    nullable: true,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Derives the columns of a batch whose columns weren't configured.
+///
+/// The columns come from the schema definition of the first event if it knows any top-level
+/// field, and are otherwise inferred from the events. When neither gives any column, the whole
+/// events are written to a single `message` column.
+fn derive_columns(logs: &[LogEvent]) -> Vec<ParquetColumn> {
+    let mut columns = logs
+        .first()
+        .map(|log| fields_from_definition(log.metadata().schema_definition()))
+        .unwrap_or_default()
+        .iter()
+        .map(ParquetColumn::from)
+        .collect::<Vec<_>>();
+    if columns.is_empty() {
+        columns = infer_columns(logs);
+    }
+    if columns.is_empty() {
+        columns.push(ParquetColumn {
+            name: "message".to_owned(),
+            path: OwnedTargetPath::event_root(),
+            field_type: ParquetFieldType::String,
+            nullable: true,
+        });
+    }
+    columns
+}
+
+/// Derives optional columns from the top-level fields of the given events.
+///
+/// Fields holding values of different types across events are written as JSON strings, except
+/// for a mix of integers and floats, which is widened to doubles.
+fn infer_columns(logs: &[LogEvent]) -> Vec<ParquetColumn> {
+    let mut fields = BTreeMap::<String, Option<ParquetFieldType>>::new();
+
+    for (key, value) in logs.iter().filter_map(LogEvent::as_map).flatten() {
+        let observed = ParquetFieldType::from_value(value);
+        let field_type = fields.entry(key.to_string()).or_insert(observed);
+        *field_type = match (*field_type, observed) {
+            (current, None) => current,
+            (None, observed) => observed,
+            (Some(current), Some(observed)) if current == observed => Some(current),
+            (
+                Some(ParquetFieldType::Int64 | ParquetFieldType::Double),
+                Some(ParquetFieldType::Int64 | ParquetFieldType::Double),
+            ) => Some(ParquetFieldType::Double),
+            _ => Some(ParquetFieldType::Json),
+        };
+    }
+
+    fields
+        .into_iter()
+        .map(|(name, field_type)| ParquetColumn {
+            path: OwnedTargetPath::event(owned_value_path!(name.as_str())),
+            name,
+            field_type: field_type.unwrap_or(ParquetFieldType::String),
+            nullable: true,
+        })
+        .collect()
+}
+
+fn build_array(
+    column: &ParquetColumn,
+    logs: &[LogEvent],
+) -> Result<ArrayRef, vector_common::Error> {
+    let mut values = Vec::with_capacity(logs.len());
+    for log in logs {
+        let value = log.get(&column.path).filter(|value| !value.is_null());
+        if value.is_none() && !column.nullable {
+            return Err(format!(
+                "Missing value for non-nullable Parquet column `{}`",
+                column.name
+            )
+            .into());
+        }
+        values.push(value);
+    }
+
+    // Values that can't be represented in the column's type are written as nulls.
+    let array: ArrayRef = match column.field_type {
+        ParquetFieldType::String => {
+            let mut builder = StringBuilder::with_capacity(values.len(), 0);
+            for value in values {
+                match value {
+                    Some(Value::Bytes(bytes)) => {
+                        builder.append_value(String::from_utf8_lossy(bytes))
+                    }
+                    Some(value @ (Value::Object(_) | Value::Array(_))) => {
+                        builder.append_value(serde_json::to_string(value)?)
+                    }
+                    Some(value) => builder.append_value(value.to_string_lossy()),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ParquetFieldType::Json => {
+            let mut builder = StringBuilder::with_capacity(values.len(), 0);
+            for value in values {
+                match value {
+                    Some(value) => builder.append_value(serde_json::to_string(value)?),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ParquetFieldType::Int64 => {
+            let mut builder = Int64Builder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.and_then(Value::as_integer));
+            }
+            Arc::new(builder.finish())
+        }
+        ParquetFieldType::Double => {
+            let mut builder = Float64Builder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.and_then(|value| match value {
+                    Value::Float(float) => Some(float.into_inner()),
+                    Value::Integer(integer) => Some(*integer as f64),
+                    _ => None,
+                }));
+            }
+            Arc::new(builder.finish())
+        }
+        ParquetFieldType::Boolean => {
+            let mut builder = BooleanBuilder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.and_then(Value::as_boolean));
+            }
+            Arc::new(builder.finish())
+        }
+        ParquetFieldType::Timestamp => {
+            let mut builder = TimestampMicrosecondBuilder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(
+                    value
+                        .and_then(Value::as_timestamp)
+                        .map(|timestamp| timestamp.timestamp_micros()),
+                );
+            }
+            Arc::new(builder.finish())
+        }
+        ParquetFieldType::Binary => {
+            let mut builder = BinaryBuilder::with_capacity(values.len(), 0);
+            for value in values {
+                match value {
+                    Some(Value::Bytes(bytes)) => builder.append_value(bytes),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+    };
+
+    Ok(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::Field as RecordField,
+    };
+    use vector_core::event::LogEvent;
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn read_rows(bytes: BytesMut) -> (usize, Vec<Vec<(String, RecordField)>>) {
+        let reader = SerializedFileReader::new(Bytes::from(bytes)).unwrap();
+        let row_groups = reader.metadata().num_row_groups();
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap().into_columns())
+            .collect();
+        (row_groups, rows)
+    }
+
+    fn field(name: &str, r#type: ParquetFieldType) -> ParquetField {
+        ParquetField {
+            name: name.to_owned(),
+            path: None,
+            r#type,
+            nullable: true,
+        }
+    }
+
+    #[test]
+    fn serialize_batch_with_explicit_schema() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let events = vec![
+            Event::Log(LogEvent::from(btreemap! {
+                "message" => "first",
+                "status" => 200,
+                "timestamp" => timestamp,
+            })),
+            Event::Log(LogEvent::from(btreemap! {
+                "message" => "second",
+                "status" => "not a number",
+            })),
+        ];
+        let config = ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: vec![
+                field("message", ParquetFieldType::String),
+                field("status", ParquetFieldType::Int64),
+                field("timestamp", ParquetFieldType::Timestamp),
+            ],
+            ..Default::default()
+        });
+        let serializer = config.build().unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode_batch(events, &mut bytes).unwrap();
+
+        let (row_groups, rows) = read_rows(bytes);
+        assert_eq!(row_groups, 1);
+        assert_eq!(
+            rows[0],
+            vec![
+                ("message".to_owned(), RecordField::Str("first".to_owned())),
+                ("status".to_owned(), RecordField::Long(200)),
+                (
+                    "timestamp".to_owned(),
+                    RecordField::TimestampMicros(timestamp.timestamp_micros())
+                ),
+            ]
+        );
+        assert_eq!(
+            rows[1],
+            vec![
+                ("message".to_owned(), RecordField::Str("second".to_owned())),
+                ("status".to_owned(), RecordField::Null),
+                ("timestamp".to_owned(), RecordField::Null),
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_batch_with_inferred_schema() {
+        let events = vec![
+            Event::Log(LogEvent::from(btreemap! {
+                "count" => 1,
+                "nested" => btreemap! { "a" => true },
+            })),
+            Event::Log(LogEvent::from(btreemap! {
+                "count" => 2.5,
+                "extra" => "value",
+            })),
+        ];
+        let serializer = ParquetSerializerConfig::default().build().unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode_batch(events, &mut bytes).unwrap();
+
+        let (_, rows) = read_rows(bytes);
+        assert_eq!(
+            rows[0],
+            vec![
+                ("count".to_owned(), RecordField::Double(1.0)),
+                ("extra".to_owned(), RecordField::Null),
+                (
+                    "nested".to_owned(),
+                    RecordField::Str(r#"{"a":true}"#.to_owned())
+                ),
+            ]
+        );
+        assert_eq!(rows[1][0], ("count".to_owned(), RecordField::Double(2.5)));
+    }
+
+    #[test]
+    fn serialize_batch_into_row_groups() {
+        let events = (0..5)
+            .map(|index| Event::Log(LogEvent::from(btreemap! { "index" => index })))
+            .collect();
+        let config = ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: vec![field("index", ParquetFieldType::Int64)],
+            row_group_size: 2,
+            compression: ParquetCompression::Zstd,
+        });
+        let serializer = config.build().unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode_batch(events, &mut bytes).unwrap();
+
+        let (row_groups, rows) = read_rows(bytes);
+        assert_eq!(row_groups, 3);
+        assert_eq!(rows.len(), 5);
+    }
+
+    #[test]
+    fn serialize_missing_non_nullable_value() {
+        let config = ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: vec![ParquetField {
+                nullable: false,
+                ..field("message", ParquetFieldType::String)
+            }],
+            ..Default::default()
+        });
+        let serializer = config.build().unwrap();
+        let event = Event::Log(LogEvent::from(btreemap! { "other" => "value" }));
+
+        assert!(
+            serializer
+                .encode_batch(vec![event], &mut BytesMut::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn schema_from_definition() {
+        let definition = schema::Definition::new_with_default_metadata(
+            Kind::object(btreemap! {
+                "message" => Kind::bytes(),
+                "status" => Kind::integer().or_null(),
+                "tags" => Kind::array(vrl::value::kind::Collection::any()),
+            }),
+            [vector_core::config::LogNamespace::Legacy],
+        );
+
+        let config = ParquetSerializerConfig::default().with_schema_definition(&definition);
+
+        assert_eq!(
+            config.parquet.schema,
+            vec![
+                field("message", ParquetFieldType::String),
+                field("status", ParquetFieldType::Int64),
+                field("tags", ParquetFieldType::Json),
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_batch_with_schema_definition_of_events() {
+        let definition = Arc::new(schema::Definition::new_with_default_metadata(
+            Kind::object(btreemap! {
+                "message" => Kind::bytes(),
+                "status" => Kind::integer(),
+            }),
+            [vector_core::config::LogNamespace::Legacy],
+        ));
+        let mut log = LogEvent::from(btreemap! {
+            "message" => "hello",
+            "status" => "200",
+            "extra" => true,
+        });
+        log.metadata_mut().set_schema_definition(&definition);
+        let serializer = ParquetSerializerConfig::default().build().unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer
+            .encode_batch(vec![Event::Log(log)], &mut bytes)
+            .unwrap();
+
+        // Only the fields of the definition are written, with the types it gives.
+        let (_, rows) = read_rows(bytes);
+        assert_eq!(
+            rows[0],
+            vec![
+                ("message".to_owned(), RecordField::Str("hello".to_owned())),
+                ("status".to_owned(), RecordField::Null),
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_batch_without_fields() {
+        let events = vec![
+            Event::Log(LogEvent::from(Value::from("a raw message"))),
+            Event::Log(LogEvent::default()),
+        ];
+        let serializer = ParquetSerializerConfig::default().build().unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode_batch(events, &mut bytes).unwrap();
+
+        let (_, rows) = read_rows(bytes);
+        assert_eq!(
+            rows,
+            vec![
+                vec![(
+                    "message".to_owned(),
+                    RecordField::Str("a raw message".to_owned())
+                )],
+                vec![("message".to_owned(), RecordField::Str("{}".to_owned()))],
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_duplicate_columns() {
+        let config = ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: vec![
+                field("message", ParquetFieldType::String),
+                field("message", ParquetFieldType::Binary),
+            ],
+            ..Default::default()
+        });
+
+        assert!(config.build().is_err());
+    }
+}
@@ -1,3 +1,7 @@
+use crate::common::schema_registry::{
+    SchemaRegistry, SchemaRegistrySerializerConfig, message_indexes, write_header,
+    write_message_indexes,
+};
 use crate::encoding::BuildError;
 use bytes::BytesMut;
 use prost_reflect::{MessageDescriptor, prost::Message as _};
@@ -22,10 +26,41 @@ pub struct ProtobufSerializerConfig {
 
 impl ProtobufSerializerConfig {
     /// Build the `ProtobufSerializer` from this configuration.
+    ///
+    /// When a Schema Registry is configured, the schema is resolved in it, which blocks until the
+    /// registry responds.
     pub fn build(&self) -> Result<ProtobufSerializer, BuildError> {
-        let message_descriptor =
-            get_message_descriptor(&self.protobuf.desc_file, &self.protobuf.message_type)?;
-        Ok(ProtobufSerializer { message_descriptor })
+        let Some(config) = &self.protobuf.schema_registry else {
+            let message_descriptor =
+                get_message_descriptor(&self.protobuf.desc_file, &self.protobuf.message_type)?;
+            return Ok(ProtobufSerializer::new(message_descriptor));
+        };
+        if config.auto_register {
+            return Err("`auto_register` is not supported by the `protobuf` codec.".into());
+        }
+
+        let registry = SchemaRegistry::new(&config.registry)?;
+        let schema = registry.schema_by_version(&config.subject, "latest", Some("serialized"))?;
+        let schema_id = schema
+            .id
+            .ok_or("Schema Registry response is missing the schema ID.")?;
+        let file =
+            registry.protobuf_file_descriptor(schema, &format!("{}.proto", config.subject))?;
+        let message_descriptor = file
+            .parent_pool()
+            .get_message_by_name(&self.protobuf.message_type)
+            .filter(|message| message.parent_file() == file)
+            .ok_or_else(|| {
+                format!(
+                    "Message type {} not found in the latest schema of subject {}.",
+                    self.protobuf.message_type, config.subject
+                )
+            })?;
+
+        Ok(ProtobufSerializer {
+            header: Some((schema_id, message_indexes(&message_descriptor))),
+            message_descriptor,
+        })
     }
 
     /// The data type of events that are accepted by `ProtobufSerializer`.
@@ -51,11 +86,20 @@ pub struct ProtobufSerializerOptions {
     ///
     /// You can read more [here](https://buf.build/docs/reference/images/#how-buf-images-work).
     #[configurable(metadata(docs::examples = "/etc/vector/protobuf_descriptor_set.desc"))]
+    #[serde(default)]
     pub desc_file: PathBuf,
 
     /// The name of the message type to use for serializing.
     #[configurable(metadata(docs::examples = "package.Message"))]
     pub message_type: String,
+
+    /// Encodes messages in the Confluent wire format, prefixing them with the ID of the latest
+    /// schema registered under the subject in a Schema Registry.
+    ///
+    /// When set, `desc_file` is ignored and `message_type` is looked up in that schema.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_registry: Option<SchemaRegistrySerializerConfig>,
 }
 
 /// Serializer that converts an `Event` to bytes using the Protobuf format.
@@ -63,12 +107,17 @@ pub struct ProtobufSerializerOptions {
 pub struct ProtobufSerializer {
     /// The protobuf message definition to use for serialization.
     message_descriptor: MessageDescriptor,
+    /// The schema ID and message indexes prefixing each message, when using a Schema Registry.
+    header: Option<(u32, Vec<i32>)>,
 }
 
 impl ProtobufSerializer {
     /// Creates a new `ProtobufSerializer`.
     pub fn new(message_descriptor: MessageDescriptor) -> Self {
-        Self { message_descriptor }
+        Self {
+            message_descriptor,
+            header: None,
+        }
     }
 
     /// Get a description of the message type used in serialization.
@@ -89,6 +138,60 @@ impl Encoder<Event> for ProtobufSerializer {
                 Value::Object(trace.into_parts().0),
             ),
         }?;
+        if let Some((schema_id, indexes)) = &self.header {
+            write_header(buffer, *schema_id);
+            write_message_indexes(buffer, indexes);
+        }
         message.encode(buffer).map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+    use vrl::btreemap;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use super::*;
+    use crate::common::schema_registry::{SchemaRegistryConfig, test_util::protobuf_test_schema};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serialize_protobuf_schema_registry() {
+        let mut schema = protobuf_test_schema();
+        schema["id"] = 7.into();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/subjects/people-value/versions/latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schema))
+            .mount(&server)
+            .await;
+
+        let config = ProtobufSerializerConfig {
+            protobuf: ProtobufSerializerOptions {
+                desc_file: PathBuf::new(),
+                message_type: "test_protobuf.Person.PhoneNumber".to_owned(),
+                schema_registry: Some(SchemaRegistrySerializerConfig {
+                    registry: SchemaRegistryConfig::new(server.uri()),
+                    subject: "people-value".to_owned(),
+                    auto_register: false,
+                }),
+            },
+        };
+        let mut serializer = config.build().unwrap();
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "number" => Value::from("123")
+        }));
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        // Magic byte, schema ID, the `[0, 0]` message indexes of the nested message and the message.
+        assert_eq!(
+            bytes.freeze(),
+            b"\0\0\0\0\x07\x04\0\0\x0a\x03123".as_slice()
+        );
+    }
+}
@@ -29,6 +29,7 @@ fn build_serializer_pair(
         protobuf: ProtobufSerializerOptions {
             desc_file: desc_file.clone(),
             message_type: message_type.clone(),
+            schema_registry: None,
         },
     }
     .build()
@@ -37,6 +38,7 @@ fn build_serializer_pair(
         protobuf: ProtobufDeserializerOptions {
             desc_file,
             message_type,
+            schema_registry: None,
         },
     }
     .build()
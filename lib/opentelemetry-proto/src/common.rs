@@ -1,7 +1,18 @@
-use super::proto::common::v1::{KeyValue, any_value::Value as PBValue};
+use std::collections::HashMap;
+
+use super::proto::{
+    common::v1::{
+        AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+        any_value::Value as PBValue,
+    },
+    resource::v1::Resource,
+};
 use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
 use ordered_float::NotNan;
+use prost::Message;
 use vector_core::event::metric::TagValue;
+use vrl::path;
 use vrl::value::{ObjectMap, Value};
 
 impl From<PBValue> for Value {
@@ -58,9 +69,117 @@ pub fn to_hex(d: &[u8]) -> String {
     hex::encode(d)
 }
 
+impl From<Value> for AnyValue {
+    fn from(value: Value) -> Self {
+        let value = match value {
+            Value::Bytes(b) => match String::from_utf8(b.to_vec()) {
+                Ok(s) => PBValue::StringValue(s),
+                Err(error) => PBValue::BytesValue(error.into_bytes()),
+            },
+            Value::Regex(regex) => PBValue::StringValue(regex.as_str().to_string()),
+            Value::Integer(i) => PBValue::IntValue(i),
+            Value::Float(f) => PBValue::DoubleValue(f.into_inner()),
+            Value::Boolean(b) => PBValue::BoolValue(b),
+            Value::Timestamp(ts) => {
+                PBValue::StringValue(ts.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            }
+            Value::Object(_) => PBValue::KvlistValue(KeyValueList {
+                values: value_into_kv_list(value),
+            }),
+            Value::Array(values) => PBValue::ArrayValue(ArrayValue {
+                values: values.into_iter().map(Into::into).collect(),
+            }),
+            Value::Null => return AnyValue { value: None },
+        };
+        AnyValue { value: Some(value) }
+    }
+}
+
+/// Converts an object into a list of key/values, the inverse of [`kv_list_into_value`].
+///
+/// Values other than objects are converted to an empty list.
+pub fn value_into_kv_list(value: Value) -> Vec<KeyValue> {
+    match value {
+        Value::Object(object) => object
+            .into_iter()
+            .map(|(key, value)| KeyValue {
+                key: key.into(),
+                value: Some(value.into()),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Decodes a hex-encoded identifier, the inverse of [`to_hex`].
+///
+/// Invalid identifiers are decoded as empty, which OTLP treats as missing.
+pub fn from_hex(s: &str) -> Vec<u8> {
+    hex::decode(s).unwrap_or_default()
+}
+
+pub(crate) fn take_string(fields: &mut Value, key: &str) -> Option<String> {
+    fields
+        .remove(path!(key), false)
+        .and_then(|value| value.as_str().map(|s| s.into_owned()))
+}
+
+pub(crate) fn take_integer(fields: &mut Value, key: &str) -> Option<i64> {
+    fields
+        .remove(path!(key), false)
+        .and_then(|value| value.as_integer())
+}
+
+/// Returns the number of nanoseconds since the epoch of a timestamp, or zero, meaning unknown in
+/// OTLP, if the value is not a timestamp.
+pub(crate) fn value_timestamp_nanos(value: &Value) -> u64 {
+    value.as_timestamp().map_or(0, timestamp_nanos)
+}
+
+pub(crate) fn timestamp_nanos(timestamp: &DateTime<Utc>) -> u64 {
+    timestamp
+        .timestamp_nanos_opt()
+        .map_or(0, |nanos| nanos.max(0) as u64)
+}
+
+/// Items sharing the same resource, grouped by instrumentation scope.
+pub type ResourceGroup<T> = (Resource, Vec<(InstrumentationScope, Vec<T>)>);
+
+/// Groups items by resource, then by instrumentation scope, keeping the order in which resources,
+/// scopes and items are first seen.
+pub fn group_by_resource<T>(
+    items: impl IntoIterator<Item = (Resource, InstrumentationScope, T)>,
+) -> Vec<ResourceGroup<T>> {
+    // Resources and scopes are built from ordered objects, so equal ones encode the same way.
+    let mut resource_indexes = HashMap::<Vec<u8>, usize>::new();
+    let mut scope_indexes = Vec::<HashMap<Vec<u8>, usize>>::new();
+    let mut groups = Vec::<ResourceGroup<T>>::new();
+
+    for (resource, scope, item) in items {
+        let resource_index = *resource_indexes
+            .entry(resource.encode_to_vec())
+            .or_insert_with(|| {
+                groups.push((resource, Vec::new()));
+                scope_indexes.push(HashMap::new());
+                groups.len() - 1
+            });
+        let scopes = &mut groups[resource_index].1;
+        let scope_index = *scope_indexes[resource_index]
+            .entry(scope.encode_to_vec())
+            .or_insert_with(|| {
+                scopes.push((scope, Vec::new()));
+                scopes.len() - 1
+            });
+        scopes[scope_index].1.push(item);
+    }
+
+    groups
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vrl::btreemap;
 
     #[test]
     fn test_pb_double_value_nan_handling() {
@@ -91,4 +210,57 @@ mod tests {
             _ => panic!("Expected Float value, got {result:?}"),
         }
     }
+
+    #[test]
+    fn test_value_round_trip() {
+        let value = Value::from(btreemap! {
+            "string" => "foo",
+            "int" => 42,
+            "float" => Value::Float(NotNan::new(1.5).unwrap()),
+            "bool" => true,
+            "array" => vec![Value::from(1), Value::from("bar")],
+            "object" => btreemap! { "nested" => "baz" },
+        });
+        let kv_list = value_into_kv_list(value.clone());
+        assert_eq!(kv_list.len(), 6);
+        assert_eq!(kv_list_into_value(kv_list), value);
+    }
+
+    #[test]
+    fn test_from_hex() {
+        assert_eq!(
+            from_hex(&to_hex(&[0xde, 0xad, 0xbe, 0xef])),
+            [0xde, 0xad, 0xbe, 0xef]
+        );
+        assert!(from_hex("not hex").is_empty());
+        assert!(from_hex("").is_empty());
+    }
+
+    #[test]
+    fn test_group_by_resource() {
+        let resource = |name: &str| Resource {
+            attributes: value_into_kv_list(Value::from(btreemap! { "service.name" => name })),
+            dropped_attributes_count: 0,
+        };
+        let scope = |name: &str| InstrumentationScope {
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        let groups = group_by_resource([
+            (resource("a"), scope("x"), 1),
+            (resource("b"), scope("x"), 2),
+            (resource("a"), scope("y"), 3),
+            (resource("a"), scope("x"), 4),
+        ]);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, resource("a"));
+        assert_eq!(
+            groups[0].1,
+            vec![(scope("x"), vec![1, 4]), (scope("y"), vec![3])]
+        );
+        assert_eq!(groups[1].0, resource("b"));
+        assert_eq!(groups[1].1, vec![(scope("x"), vec![2])]);
+    }
 }
@@ -1,7 +1,11 @@
-use super::common::{kv_list_into_value, to_hex};
+use super::common::{
+    from_hex, group_by_resource, kv_list_into_value, take_integer, take_string, to_hex,
+    value_into_kv_list, value_timestamp_nanos,
+};
 use crate::proto::{
+    collector::logs::v1::ExportLogsServiceRequest,
     common::v1::{InstrumentationScope, any_value::Value as PBValue},
-    logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
+    logs::v1::{LogRecord, ResourceLogs, ScopeLogs, SeverityNumber},
     resource::v1::Resource,
 };
 use bytes::Bytes;
@@ -236,3 +240,234 @@ impl ResourceLog {
         log.into()
     }
 }
+
+/// Builds an OTLP export request from log events, grouping their records by resource and
+/// instrumentation scope.
+///
+/// This is the inverse of [`ResourceLogs::into_event_iter`], reading the OTLP fields from where
+/// the `opentelemetry` source writes them for the log namespace of each event. In the legacy
+/// namespace, the message becomes the body of the record, and the fields unknown to OTLP are
+/// added to its attributes.
+pub fn build_export_request(logs: impl IntoIterator<Item = LogEvent>) -> ExportLogsServiceRequest {
+    let records = logs.into_iter().map(into_resource_log);
+
+    ExportLogsServiceRequest {
+        resource_logs: group_by_resource(records)
+            .into_iter()
+            .map(|(resource, scopes)| ResourceLogs {
+                resource: Some(resource),
+                scope_logs: scopes
+                    .into_iter()
+                    .map(|(scope, log_records)| ScopeLogs {
+                        scope: Some(scope),
+                        log_records,
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+            .collect(),
+    }
+}
+
+fn into_resource_log(log: LogEvent) -> (Resource, InstrumentationScope, LogRecord) {
+    let log_namespace = log.namespace();
+    let timestamp = log.get_timestamp().cloned();
+    let (value, mut metadata) = log.into_parts();
+
+    let (mut fields, body) = match log_namespace {
+        LogNamespace::Vector => (
+            metadata
+                .value_mut()
+                .remove(path!(SOURCE_NAME), false)
+                .unwrap_or(Value::Null),
+            Some(value),
+        ),
+        LogNamespace::Legacy => {
+            let mut fields = value;
+            let body = log_schema()
+                .message_key()
+                .and_then(|key| fields.remove(key, false));
+            // The timestamp was already read, and the source type is specific to Vector.
+            for key in [log_schema().timestamp_key(), log_schema().source_type_key()]
+                .into_iter()
+                .flatten()
+            {
+                fields.remove(key, false);
+            }
+            (fields, body)
+        }
+    };
+
+    let resource = Resource {
+        attributes: value_into_kv_list(
+            fields
+                .remove(path!(RESOURCE_KEY), false)
+                .unwrap_or(Value::Null),
+        ),
+        ..Default::default()
+    };
+    let scope = fields
+        .remove(path!(SCOPE_KEY), false)
+        .map(into_scope)
+        .unwrap_or_default();
+
+    let mut attributes = fields
+        .remove(path!(ATTRIBUTES_KEY), false)
+        .unwrap_or(Value::Null);
+    let trace_id = take_string(&mut fields, TRACE_ID_KEY).map(|id| from_hex(&id));
+    let span_id = take_string(&mut fields, SPAN_ID_KEY).map(|id| from_hex(&id));
+    let severity_text = take_string(&mut fields, SEVERITY_TEXT_KEY);
+    let severity_number = take_integer(&mut fields, SEVERITY_NUMBER_KEY);
+    let flags = take_integer(&mut fields, FLAGS_KEY);
+    let dropped_attributes_count = take_integer(&mut fields, DROPPED_ATTRIBUTES_COUNT_KEY);
+    let observed_timestamp = fields.remove(path!(OBSERVED_TIMESTAMP_KEY), false);
+    let timestamp = match log_namespace {
+        LogNamespace::Vector => fields.remove(path!("timestamp"), false).or(timestamp),
+        LogNamespace::Legacy => timestamp,
+    };
+
+    if log_namespace == LogNamespace::Legacy
+        && let Value::Object(remaining) = fields
+    {
+        // Attributes carried by the event take precedence over its other fields.
+        if let Value::Object(existing) = &mut attributes {
+            for (key, value) in remaining {
+                existing.entry(key).or_insert(value);
+            }
+        } else if !remaining.is_empty() {
+            attributes = Value::Object(remaining);
+        }
+    }
+
+    let log_record = LogRecord {
+        time_unix_nano: timestamp.as_ref().map_or(0, value_timestamp_nanos),
+        observed_time_unix_nano: observed_timestamp.as_ref().map_or(0, value_timestamp_nanos),
+        severity_number: severity_number.unwrap_or_default() as i32,
+        severity_text: severity_text.unwrap_or_default(),
+        body: body.filter(|body| !body.is_null()).map(Into::into),
+        attributes: value_into_kv_list(attributes),
+        dropped_attributes_count: dropped_attributes_count.unwrap_or_default() as u32,
+        flags: flags.unwrap_or_default() as u32,
+        trace_id: trace_id.unwrap_or_default(),
+        span_id: span_id.unwrap_or_default(),
+        ..Default::default()
+    };
+
+    (resource, scope, log_record)
+}
+
+pub(crate) fn into_scope(mut scope: Value) -> InstrumentationScope {
+    InstrumentationScope {
+        name: take_string(&mut scope, NAME_KEY).unwrap_or_default(),
+        version: take_string(&mut scope, VERSION_KEY).unwrap_or_default(),
+        attributes: value_into_kv_list(
+            scope
+                .remove(path!(ATTRIBUTES_KEY), false)
+                .unwrap_or(Value::Null),
+        ),
+        dropped_attributes_count: take_integer(&mut scope, DROPPED_ATTRIBUTES_COUNT_KEY)
+            .unwrap_or_default() as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::common::v1::{AnyValue, KeyValue};
+    use vrl::event_path;
+
+    fn string_kv(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(PBValue::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn resource_logs() -> ResourceLogs {
+        ResourceLogs {
+            resource: Some(Resource {
+                attributes: vec![string_kv("service.name", "checkout")],
+                ..Default::default()
+            }),
+            scope_logs: vec![ScopeLogs {
+                scope: Some(InstrumentationScope {
+                    name: "logger".to_string(),
+                    version: "1.0".to_string(),
+                    ..Default::default()
+                }),
+                log_records: vec![LogRecord {
+                    time_unix_nano: 1_700_000_000_000_000_000,
+                    observed_time_unix_nano: 1_700_000_000_000_000_001,
+                    severity_number: SeverityNumber::Error as i32,
+                    severity_text: "ERROR".to_string(),
+                    body: Some(AnyValue {
+                        value: Some(PBValue::StringValue("payment failed".to_string())),
+                    }),
+                    attributes: vec![string_kv("user.id", "42")],
+                    trace_id: vec![1; 16],
+                    span_id: vec![2; 8],
+                    flags: 1,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn round_trips_records() {
+        for log_namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let logs = resource_logs()
+                .into_event_iter(log_namespace)
+                .map(Event::into_log);
+            let request = build_export_request(logs);
+            assert_eq!(
+                request.resource_logs,
+                vec![resource_logs()],
+                "{log_namespace:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn groups_records_by_resource() {
+        let logs = resource_logs()
+            .into_event_iter(LogNamespace::Legacy)
+            .chain(resource_logs().into_event_iter(LogNamespace::Legacy))
+            .map(Event::into_log);
+        let request = build_export_request(logs);
+
+        assert_eq!(request.resource_logs.len(), 1);
+        assert_eq!(request.resource_logs[0].scope_logs.len(), 1);
+        assert_eq!(request.resource_logs[0].scope_logs[0].log_records.len(), 2);
+    }
+
+    #[test]
+    fn adds_unknown_fields_to_attributes() {
+        let mut log = LogEvent::default();
+        log.insert(log_schema().message_key_target_path().unwrap(), "hello");
+        log.insert(event_path!("host"), "localhost");
+        log.insert(event_path!(ATTRIBUTES_KEY, "host"), "attribute");
+        log.insert(event_path!(ATTRIBUTES_KEY, "other"), 1);
+        log.insert(event_path!("unknown"), true);
+
+        let request = build_export_request([log]);
+        let record = &request.resource_logs[0].scope_logs[0].log_records[0];
+        assert_eq!(
+            record.body.clone().and_then(|body| body.value),
+            Some(PBValue::StringValue("hello".to_string()))
+        );
+        assert_eq!(
+            kv_list_into_value(record.attributes.clone()),
+            Value::from(vrl::btreemap! {
+                "host" => "attribute",
+                "other" => 1,
+                "unknown" => true,
+            })
+        );
+    }
+}
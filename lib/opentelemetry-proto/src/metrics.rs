@@ -1,18 +1,37 @@
+use std::num::NonZeroU32;
+
+use super::common::{
+    from_hex, group_by_resource, kv_list_into_value, timestamp_nanos, to_hex, value_into_kv_list,
+    value_timestamp_nanos,
+};
 use super::proto::{
-    common::v1::{InstrumentationScope, KeyValue},
+    collector::metrics::v1::ExportMetricsServiceRequest,
+    common::v1::{AnyValue, InstrumentationScope, KeyValue, any_value::Value as PBValue},
     metrics::v1::{
-        AggregationTemporality, ExponentialHistogram, ExponentialHistogramDataPoint, Gauge,
-        Histogram, HistogramDataPoint, NumberDataPoint, ResourceMetrics, Sum, Summary,
-        SummaryDataPoint, metric::Data, number_data_point::Value as NumberDataPointValue,
+        AggregationTemporality, Exemplar, ExponentialHistogram, ExponentialHistogramDataPoint,
+        Gauge, Histogram, HistogramDataPoint, Metric, NumberDataPoint, ResourceMetrics,
+        ScopeMetrics, Sum, Summary, SummaryDataPoint, exemplar::Value as ExemplarValue,
+        exponential_histogram_data_point::Buckets, metric::Data,
+        number_data_point::Value as NumberDataPointValue, summary_data_point::ValueAtQuantile,
     },
     resource::v1::Resource,
 };
 use chrono::{TimeZone, Utc};
-use vector_core::event::{
-    Event, Metric as MetricEvent, MetricKind, MetricTags, MetricValue,
-    metric::{Bucket, Quantile, TagValue},
+use vector_core::{
+    event::{
+        Event, EventMetadata, Metric as MetricEvent, MetricKind, MetricTags, MetricValue,
+        metric::{Bucket, MetricSketch, Quantile, TagValue, samples_to_buckets},
+    },
+    metrics::ExponentialBuckets,
+};
+use vrl::{
+    path,
+    value::{ObjectMap, Value},
 };
 
+/// The key of the metric metadata under which the exemplars of a data point are kept.
+pub const EXEMPLARS_KEY: &str = "exemplars";
+
 impl ResourceMetrics {
     pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
         let resource = self.resource.clone();
@@ -274,6 +293,12 @@ impl SumMetric {
         MetricEvent::new(metric_name, kind, metric_value)
             .with_tags(Some(attributes))
             .with_timestamp(timestamp)
+            .with_interval_ms(interval_ms(
+                kind,
+                self.point.start_time_unix_nano,
+                self.point.time_unix_nano,
+            ))
+            .with_exemplars(self.point.exemplars)
             .into()
     }
 }
@@ -291,6 +316,7 @@ impl GaugeMetric {
         )
         .with_timestamp(timestamp)
         .with_tags(Some(attributes))
+        .with_exemplars(self.point.exemplars)
         .into()
     }
 }
@@ -336,62 +362,59 @@ impl HistogramMetric {
         )
         .with_timestamp(timestamp)
         .with_tags(Some(attributes))
+        .with_interval_ms(interval_ms(
+            kind,
+            self.point.start_time_unix_nano,
+            self.point.time_unix_nano,
+        ))
+        .with_exemplars(self.point.exemplars)
         .into()
     }
 }
 
 impl ExpHistogramMetric {
     fn into_metric(self, metric_name: String) -> Event {
-        // we have to convert Exponential Histogram to agg histogram using scale and base
         let timestamp = Some(Utc.timestamp_nanos(self.point.time_unix_nano as i64));
         let attributes = build_metric_tags(self.resource, self.scope, &self.point.attributes);
-
-        let scale = self.point.scale;
-        // from Opentelemetry docs: base = 2**(2**(-scale))
-        let base = 2f64.powf(2f64.powi(-scale));
-
-        let mut buckets = Vec::new();
-
-        if let Some(negative_buckets) = self.point.negative {
-            for (i, &count) in negative_buckets.bucket_counts.iter().enumerate() {
-                let index = negative_buckets.offset + i as i32;
-                let upper_limit = -base.powi(index);
-                buckets.push(Bucket { count, upper_limit });
-            }
-        }
-
-        if self.point.zero_count > 0 {
-            buckets.push(Bucket {
-                count: self.point.zero_count,
-                upper_limit: 0.0,
-            });
-        }
-
-        if let Some(positive_buckets) = self.point.positive {
-            for (i, &count) in positive_buckets.bucket_counts.iter().enumerate() {
-                let index = positive_buckets.offset + i as i32;
-                let upper_limit = base.powi(index + 1);
-                buckets.push(Bucket { count, upper_limit });
-            }
-        }
-
         let kind = if self.aggregation_temporality == AggregationTemporality::Delta as i32 {
             MetricKind::Incremental
         } else {
             MetricKind::Absolute
         };
 
+        let into_buckets = |buckets: Option<Buckets>| {
+            buckets.map_or_else(ExponentialBuckets::default, |buckets| ExponentialBuckets {
+                offset: buckets.offset,
+                counts: buckets.bucket_counts,
+            })
+        };
+        let histogram = vector_core::metrics::ExponentialHistogram {
+            scale: self.point.scale,
+            zero_count: self.point.zero_count,
+            zero_threshold: self.point.zero_threshold,
+            positive: into_buckets(self.point.positive),
+            negative: into_buckets(self.point.negative),
+            count: self.point.count,
+            sum: self.point.sum.unwrap_or(0.0),
+            min: self.point.min,
+            max: self.point.max,
+        };
+
         MetricEvent::new(
             metric_name,
             kind,
-            MetricValue::AggregatedHistogram {
-                buckets,
-                count: self.point.count,
-                sum: self.point.sum.unwrap_or(0.0),
+            MetricValue::Sketch {
+                sketch: MetricSketch::ExponentialHistogram(histogram),
             },
         )
         .with_timestamp(timestamp)
         .with_tags(Some(attributes))
+        .with_interval_ms(interval_ms(
+            kind,
+            self.point.start_time_unix_nano,
+            self.point.time_unix_nano,
+        ))
+        .with_exemplars(self.point.exemplars)
         .into()
     }
 }
@@ -426,6 +449,100 @@ impl SummaryMetric {
     }
 }
 
+/// Returns the interval covered by a delta data point, from its start time to its time.
+///
+/// Cumulative data points all start at the same time, so their interval is left unset.
+fn interval_ms(
+    kind: MetricKind,
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+) -> Option<NonZeroU32> {
+    if kind != MetricKind::Incremental || start_time_unix_nano == 0 {
+        return None;
+    }
+    let interval_ms = time_unix_nano.saturating_sub(start_time_unix_nano) / 1_000_000;
+    NonZeroU32::new(u32::try_from(interval_ms).unwrap_or(u32::MAX))
+}
+
+trait WithExemplars {
+    /// Keeps the exemplars of a data point in the metadata of its metric.
+    fn with_exemplars(self, exemplars: Vec<Exemplar>) -> Self;
+}
+
+impl WithExemplars for MetricEvent {
+    fn with_exemplars(mut self, exemplars: Vec<Exemplar>) -> Self {
+        if !exemplars.is_empty() {
+            let exemplars = exemplars.into_iter().map(exemplar_into_value).collect();
+            self.metadata_mut()
+                .value_mut()
+                .insert(path!(EXEMPLARS_KEY), Value::Array(exemplars));
+        }
+        self
+    }
+}
+
+fn exemplar_into_value(exemplar: Exemplar) -> Value {
+    let mut obj = ObjectMap::new();
+    let value = match exemplar.value {
+        Some(ExemplarValue::AsDouble(value)) => Value::from(value),
+        Some(ExemplarValue::AsInt(value)) => Value::Integer(value),
+        None => Value::Null,
+    };
+    obj.insert("value".into(), value);
+    obj.insert(
+        "timestamp".into(),
+        Value::Timestamp(Utc.timestamp_nanos(exemplar.time_unix_nano as i64)),
+    );
+    if !exemplar.trace_id.is_empty() {
+        obj.insert("trace_id".into(), Value::from(to_hex(&exemplar.trace_id)));
+    }
+    if !exemplar.span_id.is_empty() {
+        obj.insert("span_id".into(), Value::from(to_hex(&exemplar.span_id)));
+    }
+    if !exemplar.filtered_attributes.is_empty() {
+        obj.insert(
+            "attributes".into(),
+            kv_list_into_value(exemplar.filtered_attributes),
+        );
+    }
+    Value::Object(obj)
+}
+
+/// Builds back the exemplars kept in the metadata of a metric by the source.
+fn exemplars_from_metadata(metadata: &EventMetadata) -> Vec<Exemplar> {
+    let Some(Value::Array(exemplars)) = metadata.value().get(path!(EXEMPLARS_KEY)) else {
+        return Vec::new();
+    };
+    exemplars
+        .iter()
+        .filter_map(Value::as_object)
+        .map(|exemplar| {
+            let id = |key: &str| {
+                exemplar
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .map(|id| from_hex(&id))
+                    .unwrap_or_default()
+            };
+            Exemplar {
+                filtered_attributes: exemplar
+                    .get("attributes")
+                    .cloned()
+                    .map(value_into_kv_list)
+                    .unwrap_or_default(),
+                time_unix_nano: exemplar.get("timestamp").map_or(0, value_timestamp_nanos),
+                span_id: id("span_id"),
+                trace_id: id("trace_id"),
+                value: match exemplar.get("value") {
+                    Some(Value::Integer(value)) => Some(ExemplarValue::AsInt(*value)),
+                    Some(Value::Float(value)) => Some(ExemplarValue::AsDouble(value.into_inner())),
+                    _ => None,
+                },
+            }
+        })
+        .collect()
+}
+
 pub trait ToF64 {
     fn to_f64(self) -> Option<f64>;
 }
@@ -439,3 +556,463 @@ impl ToF64 for Option<NumberDataPointValue> {
         }
     }
 }
+
+/// The bounds of the histograms built from distributions, which are the default bounds of the
+/// explicit bucket histograms of OpenTelemetry SDKs.
+pub const DISTRIBUTION_BOUNDS: [f64; 15] = [
+    0.0, 5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1000.0, 2500.0, 5000.0, 7500.0,
+    10000.0,
+];
+
+/// Returns whether a metric value can be represented in OTLP.
+///
+/// Sets and sketches other than exponential histograms have no OTLP equivalent, and are skipped
+/// by [`build_export_request`].
+pub const fn is_supported(value: &MetricValue) -> bool {
+    !matches!(
+        value,
+        MetricValue::Set { .. }
+            | MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(_)
+            }
+    )
+}
+
+/// Builds an OTLP export request from metric events, grouping them by resource and
+/// instrumentation scope.
+///
+/// This is the inverse of [`ResourceMetrics::into_event_iter`]: the tags prefixed with `resource.`
+/// and `scope.` are the attributes of the resource and scope of the metric, and the other tags
+/// are the attributes of its data point. Distributions are sent as histograms, using the
+/// [`DISTRIBUTION_BOUNDS`], and the exemplars kept in the metadata of metrics are sent with their
+/// data point.
+pub fn build_export_request(
+    metrics: impl IntoIterator<Item = MetricEvent>,
+) -> ExportMetricsServiceRequest {
+    let metrics = metrics.into_iter().filter_map(into_resource_metric);
+
+    ExportMetricsServiceRequest {
+        resource_metrics: group_by_resource(metrics)
+            .into_iter()
+            .map(|(resource, scopes)| ResourceMetrics {
+                resource: Some(resource),
+                scope_metrics: scopes
+                    .into_iter()
+                    .map(|(scope, metrics)| ScopeMetrics {
+                        scope: Some(scope),
+                        metrics,
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+            .collect(),
+    }
+}
+
+fn into_resource_metric(metric: MetricEvent) -> Option<(Resource, InstrumentationScope, Metric)> {
+    let (series, data, metadata) = metric.into_parts();
+    let (time, kind, value) = data.into_parts();
+
+    let name = match series.name.namespace {
+        Some(namespace) => format!("{namespace}.{}", series.name.name),
+        None => series.name.name,
+    };
+    let (resource, scope, attributes) = split_metric_tags(series.tags.unwrap_or_default());
+
+    let time_unix_nano = time.timestamp.as_ref().map_or(0, timestamp_nanos);
+    // Metrics covering an interval start at its beginning.
+    let start_time_unix_nano = match (time.timestamp, time.interval_ms) {
+        (Some(timestamp), Some(interval_ms)) => timestamp_nanos(
+            &(timestamp - chrono::Duration::milliseconds(i64::from(interval_ms.get()))),
+        ),
+        _ => 0,
+    };
+    let aggregation_temporality = match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+    let exemplars = exemplars_from_metadata(&metadata);
+    let number_point = |value: f64| NumberDataPoint {
+        attributes: attributes.clone(),
+        start_time_unix_nano,
+        time_unix_nano,
+        value: Some(NumberDataPointValue::AsDouble(value)),
+        exemplars: exemplars.clone(),
+        ..Default::default()
+    };
+    let histogram_point = |buckets: &[Bucket], count: u64, sum: f64| {
+        let mut explicit_bounds = Vec::with_capacity(buckets.len());
+        let mut bucket_counts = Vec::with_capacity(buckets.len() + 1);
+        for bucket in buckets {
+            if bucket.upper_limit.is_finite() {
+                explicit_bounds.push(bucket.upper_limit);
+            }
+            bucket_counts.push(bucket.count);
+        }
+        // The overflow bucket, above the highest bound, holds the remainder of the count.
+        if buckets
+            .last()
+            .is_none_or(|bucket| bucket.upper_limit.is_finite())
+        {
+            let counted = bucket_counts.iter().sum::<u64>();
+            bucket_counts.push(count.saturating_sub(counted));
+        }
+        HistogramDataPoint {
+            attributes: attributes.clone(),
+            start_time_unix_nano,
+            time_unix_nano,
+            count,
+            sum: Some(sum),
+            bucket_counts,
+            explicit_bounds,
+            exemplars: exemplars.clone(),
+            ..Default::default()
+        }
+    };
+
+    let data = match value {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number_point(value)],
+            aggregation_temporality,
+            is_monotonic: true,
+        }),
+        // Incremental gauges are changes of the value, as sent by non-monotonic delta sums.
+        MetricValue::Gauge { value } if kind == MetricKind::Incremental => Data::Sum(Sum {
+            data_points: vec![number_point(value)],
+            aggregation_temporality,
+            is_monotonic: false,
+        }),
+        MetricValue::Gauge { value } => Data::Gauge(Gauge {
+            data_points: vec![number_point(value)],
+        }),
+        MetricValue::Distribution { samples, .. } => {
+            let (buckets, count, sum) = samples_to_buckets(&samples, &DISTRIBUTION_BOUNDS);
+            Data::Histogram(Histogram {
+                data_points: vec![histogram_point(&buckets, count, sum)],
+                aggregation_temporality,
+            })
+        }
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => Data::Histogram(Histogram {
+            data_points: vec![histogram_point(&buckets, count, sum)],
+            aggregation_temporality,
+        }),
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes,
+                start_time_unix_nano,
+                time_unix_nano,
+                count,
+                sum,
+                quantile_values: quantiles
+                    .into_iter()
+                    .map(|q| ValueAtQuantile {
+                        quantile: q.quantile,
+                        value: q.value,
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+        }),
+        MetricValue::Sketch {
+            sketch: MetricSketch::ExponentialHistogram(histogram),
+        } => {
+            let into_buckets = |buckets: ExponentialBuckets| Buckets {
+                offset: buckets.offset,
+                bucket_counts: buckets.counts,
+            };
+            Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![ExponentialHistogramDataPoint {
+                    attributes,
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    count: histogram.count,
+                    sum: Some(histogram.sum),
+                    scale: histogram.scale,
+                    zero_count: histogram.zero_count,
+                    positive: Some(into_buckets(histogram.positive)),
+                    negative: Some(into_buckets(histogram.negative)),
+                    exemplars,
+                    min: histogram.min,
+                    max: histogram.max,
+                    zero_threshold: histogram.zero_threshold,
+                    ..Default::default()
+                }],
+                aggregation_temporality,
+            })
+        }
+        MetricValue::Set { .. }
+        | MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(_),
+        } => return None,
+    };
+
+    Some((
+        resource,
+        scope,
+        Metric {
+            name,
+            data: Some(data),
+            ..Default::default()
+        },
+    ))
+}
+
+/// Splits metric tags back into the resource, scope and data point attributes they were built
+/// from by [`build_metric_tags`].
+fn split_metric_tags(tags: MetricTags) -> (Resource, InstrumentationScope, Vec<KeyValue>) {
+    let mut resource = Resource::default();
+    let mut scope = InstrumentationScope::default();
+    let mut attributes = Vec::new();
+
+    for (key, value) in tags.iter_single() {
+        let kv = || KeyValue {
+            key: String::new(),
+            value: Some(AnyValue {
+                value: Some(PBValue::StringValue(value.to_string())),
+            }),
+        };
+        if let Some(key) = key.strip_prefix("resource.") {
+            resource.attributes.push(KeyValue {
+                key: key.to_string(),
+                ..kv()
+            });
+        } else if key == "scope.name" {
+            scope.name = value.to_string();
+        } else if key == "scope.version" {
+            scope.version = value.to_string();
+        } else if let Some(key) = key.strip_prefix("scope.") {
+            scope.attributes.push(KeyValue {
+                key: key.to_string(),
+                ..kv()
+            });
+        } else {
+            attributes.push(KeyValue {
+                key: key.to_string(),
+                ..kv()
+            });
+        }
+    }
+
+    (resource, scope, attributes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string_kv(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(PBValue::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn number_point(value: f64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![string_kv("host", "a")],
+            time_unix_nano: 1_700_000_000_000_000_000,
+            value: Some(NumberDataPointValue::AsDouble(value)),
+            ..Default::default()
+        }
+    }
+
+    fn resource_metrics() -> ResourceMetrics {
+        ResourceMetrics {
+            resource: Some(Resource {
+                attributes: vec![string_kv("service.name", "checkout")],
+                ..Default::default()
+            }),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "meter".to_string(),
+                    version: "1.0".to_string(),
+                    attributes: vec![string_kv("library", "otel")],
+                    ..Default::default()
+                }),
+                metrics: vec![
+                    Metric {
+                        name: "requests".to_string(),
+                        data: Some(Data::Sum(Sum {
+                            data_points: vec![number_point(10.0)],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                            is_monotonic: true,
+                        })),
+                        ..Default::default()
+                    },
+                    Metric {
+                        name: "queue_depth".to_string(),
+                        data: Some(Data::Gauge(Gauge {
+                            data_points: vec![number_point(3.0)],
+                        })),
+                        ..Default::default()
+                    },
+                    Metric {
+                        name: "latency".to_string(),
+                        data: Some(Data::Histogram(Histogram {
+                            data_points: vec![HistogramDataPoint {
+                                attributes: vec![string_kv("host", "a")],
+                                time_unix_nano: 1_700_000_000_000_000_000,
+                                count: 6,
+                                sum: Some(42.0),
+                                bucket_counts: vec![1, 2, 3],
+                                explicit_bounds: vec![1.0, 10.0],
+                                ..Default::default()
+                            }],
+                            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                        })),
+                        ..Default::default()
+                    },
+                    Metric {
+                        name: "payload_size".to_string(),
+                        data: Some(Data::ExponentialHistogram(ExponentialHistogram {
+                            data_points: vec![ExponentialHistogramDataPoint {
+                                attributes: vec![string_kv("host", "a")],
+                                time_unix_nano: 1_700_000_000_000_000_000,
+                                count: 8,
+                                sum: Some(84.0),
+                                scale: 1,
+                                zero_count: 1,
+                                positive: Some(Buckets {
+                                    offset: 2,
+                                    bucket_counts: vec![3, 0, 2],
+                                }),
+                                negative: Some(Buckets {
+                                    offset: 0,
+                                    bucket_counts: vec![2],
+                                }),
+                                exemplars: vec![Exemplar {
+                                    filtered_attributes: vec![string_kv("user", "b")],
+                                    time_unix_nano: 1_699_999_999_000_000_000,
+                                    span_id: vec![2; 8],
+                                    trace_id: vec![1; 16],
+                                    value: Some(ExemplarValue::AsDouble(3.5)),
+                                }],
+                                min: Some(-1.2),
+                                max: Some(2.7),
+                                ..Default::default()
+                            }],
+                            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                        })),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn round_trips_metrics() {
+        let metrics = resource_metrics().into_event_iter().map(Event::into_metric);
+        let request = build_export_request(metrics);
+        assert_eq!(request.resource_metrics, vec![resource_metrics()]);
+    }
+
+    #[test]
+    fn keeps_exponential_histograms_and_exemplars() {
+        let metric = resource_metrics()
+            .into_event_iter()
+            .map(Event::into_metric)
+            .find(|metric| metric.name() == "payload_size")
+            .unwrap();
+
+        let MetricValue::Sketch {
+            sketch: MetricSketch::ExponentialHistogram(histogram),
+        } = metric.value()
+        else {
+            panic!(
+                "expected an exponential histogram, got {:?}",
+                metric.value()
+            );
+        };
+        assert_eq!(histogram.scale, 1);
+        assert_eq!(histogram.positive.counts, [3, 0, 2]);
+        assert_eq!(histogram.count, 8);
+
+        let exemplars = metric.metadata().value().get(path!(EXEMPLARS_KEY));
+        let Some(Value::Array(exemplars)) = exemplars else {
+            panic!("expected exemplars, got {exemplars:?}");
+        };
+        assert_eq!(
+            exemplars[0].get(path!("trace_id")),
+            Some(&Value::from("01010101010101010101010101010101"))
+        );
+        assert_eq!(exemplars[0].get(path!("value")), Some(&Value::from(3.5)));
+    }
+
+    #[test]
+    fn sets_interval_of_delta_metrics() {
+        let mut metrics = resource_metrics();
+        let Some(Data::Sum(sum)) = &mut metrics.scope_metrics[0].metrics[0].data else {
+            panic!("expected a sum");
+        };
+        sum.data_points[0].start_time_unix_nano = 1_699_999_990_000_000_000;
+
+        let metric = metrics.into_event_iter().next().unwrap().into_metric();
+        assert_eq!(metric.interval_ms(), NonZeroU32::new(10_000));
+
+        let request = build_export_request([metric]);
+        let Some(Data::Sum(sum)) = &request.resource_metrics[0].scope_metrics[0].metrics[0].data
+        else {
+            panic!("expected a sum");
+        };
+        assert_eq!(
+            sum.data_points[0].start_time_unix_nano,
+            1_699_999_990_000_000_000
+        );
+    }
+
+    #[test]
+    fn converts_distributions_to_histograms() {
+        let metric = MetricEvent::new(
+            "sizes",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 1, 7.0 => 2, 20_000.0 => 1],
+                statistic: vector_core::event::StatisticKind::Histogram,
+            },
+        );
+        let request = build_export_request([metric]);
+        let metric = &request.resource_metrics[0].scope_metrics[0].metrics[0];
+        let Some(Data::Histogram(histogram)) = &metric.data else {
+            panic!("expected a histogram, got {:?}", metric.data);
+        };
+
+        assert_eq!(
+            histogram.aggregation_temporality,
+            AggregationTemporality::Delta as i32
+        );
+        let point = &histogram.data_points[0];
+        assert_eq!(point.count, 4);
+        assert_eq!(point.explicit_bounds, DISTRIBUTION_BOUNDS);
+        assert_eq!(point.bucket_counts.len(), DISTRIBUTION_BOUNDS.len() + 1);
+        assert_eq!(point.bucket_counts[1], 1);
+        assert_eq!(point.bucket_counts[2], 2);
+        assert_eq!(point.bucket_counts[DISTRIBUTION_BOUNDS.len()], 1);
+    }
+
+    #[test]
+    fn skips_unsupported_metrics() {
+        let metric = MetricEvent::new(
+            "users",
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: ["a".to_string()].into(),
+            },
+        );
+        assert!(!is_supported(metric.value()));
+        assert!(build_export_request([metric]).resource_metrics.is_empty());
+    }
+}
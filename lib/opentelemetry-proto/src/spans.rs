@@ -1,8 +1,14 @@
-use super::common::{kv_list_into_value, to_hex};
+use super::common::{
+    from_hex, group_by_resource, kv_list_into_value, take_integer, take_string, to_hex,
+    value_into_kv_list, value_timestamp_nanos,
+};
+use super::logs::{SCOPE_KEY, into_scope};
 use super::proto::{
+    collector::trace::v1::ExportTraceServiceRequest,
+    common::v1::{InstrumentationScope, KeyValue},
     resource::v1::Resource,
     trace::v1::{
-        ResourceSpans, Span, Status as SpanStatus,
+        ResourceSpans, ScopeSpans, Span, Status as SpanStatus,
         span::{Event as SpanEvent, Link},
     },
 };
@@ -10,7 +16,7 @@ use chrono::{DateTime, TimeZone, Utc};
 use std::collections::BTreeMap;
 use vector_core::event::{Event, TraceEvent};
 use vrl::{
-    event_path,
+    event_path, path,
     value::{KeyString, Value},
 };
 
@@ -153,3 +159,201 @@ impl From<SpanStatus> for Value {
         Value::Object(obj)
     }
 }
+
+/// Builds an OTLP export request from trace events, grouping their spans by resource and
+/// instrumentation scope.
+///
+/// This is the inverse of [`ResourceSpans::into_event_iter`], reading the span from the fields
+/// written by the `opentelemetry` source. The instrumentation scope is read from the optional
+/// `scope` field.
+pub fn build_export_request(
+    traces: impl IntoIterator<Item = TraceEvent>,
+) -> ExportTraceServiceRequest {
+    let spans = traces.into_iter().map(into_resource_span);
+
+    ExportTraceServiceRequest {
+        resource_spans: group_by_resource(spans)
+            .into_iter()
+            .map(|(resource, scopes)| ResourceSpans {
+                resource: Some(resource),
+                scope_spans: scopes
+                    .into_iter()
+                    .map(|(scope, spans)| ScopeSpans {
+                        scope: Some(scope),
+                        spans,
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+            .collect(),
+    }
+}
+
+fn into_resource_span(trace: TraceEvent) -> (Resource, InstrumentationScope, Span) {
+    let (fields, _) = trace.into_parts();
+    let mut fields = Value::Object(fields);
+
+    let resource = Resource {
+        attributes: value_into_kv_list(
+            fields
+                .remove(path!(RESOURCE_KEY), false)
+                .unwrap_or(Value::Null),
+        ),
+        ..Default::default()
+    };
+    let scope = fields
+        .remove(path!(SCOPE_KEY), false)
+        .map(into_scope)
+        .unwrap_or_default();
+
+    let span = Span {
+        trace_id: take_id(&mut fields, TRACE_ID_KEY),
+        span_id: take_id(&mut fields, SPAN_ID_KEY),
+        trace_state: take_string(&mut fields, "trace_state").unwrap_or_default(),
+        parent_span_id: take_id(&mut fields, "parent_span_id"),
+        name: take_string(&mut fields, "name").unwrap_or_default(),
+        kind: take_integer(&mut fields, "kind").unwrap_or_default() as i32,
+        start_time_unix_nano: take_timestamp(&mut fields, "start_time_unix_nano"),
+        end_time_unix_nano: take_timestamp(&mut fields, "end_time_unix_nano"),
+        attributes: take_attributes(&mut fields),
+        dropped_attributes_count: take_count(&mut fields, DROPPED_ATTRIBUTES_COUNT_KEY),
+        events: take_array(&mut fields, "events")
+            .map(|mut event| SpanEvent {
+                time_unix_nano: take_timestamp(&mut event, "time_unix_nano"),
+                name: take_string(&mut event, "name").unwrap_or_default(),
+                attributes: take_attributes(&mut event),
+                dropped_attributes_count: take_count(&mut event, DROPPED_ATTRIBUTES_COUNT_KEY),
+            })
+            .collect(),
+        dropped_events_count: take_count(&mut fields, "dropped_events_count"),
+        links: take_array(&mut fields, "links")
+            .map(|mut link| Link {
+                trace_id: take_id(&mut link, TRACE_ID_KEY),
+                span_id: take_id(&mut link, SPAN_ID_KEY),
+                trace_state: take_string(&mut link, "trace_state").unwrap_or_default(),
+                attributes: take_attributes(&mut link),
+                dropped_attributes_count: take_count(&mut link, DROPPED_ATTRIBUTES_COUNT_KEY),
+                ..Default::default()
+            })
+            .collect(),
+        dropped_links_count: take_count(&mut fields, "dropped_links_count"),
+        status: fields
+            .remove(path!("status"), false)
+            .map(|mut status| SpanStatus {
+                message: take_string(&mut status, "message").unwrap_or_default(),
+                code: take_integer(&mut status, "code").unwrap_or_default() as i32,
+            }),
+        ..Default::default()
+    };
+
+    (resource, scope, span)
+}
+
+fn take_id(fields: &mut Value, key: &str) -> Vec<u8> {
+    take_string(fields, key)
+        .map(|id| from_hex(&id))
+        .unwrap_or_default()
+}
+
+fn take_timestamp(fields: &mut Value, key: &str) -> u64 {
+    fields
+        .remove(path!(key), false)
+        .map_or(0, |value| value_timestamp_nanos(&value))
+}
+
+fn take_count(fields: &mut Value, key: &str) -> u32 {
+    take_integer(fields, key).unwrap_or_default() as u32
+}
+
+fn take_attributes(fields: &mut Value) -> Vec<KeyValue> {
+    fields
+        .remove(path!(ATTRIBUTES_KEY), false)
+        .map(value_into_kv_list)
+        .unwrap_or_default()
+}
+
+fn take_array(fields: &mut Value, key: &str) -> impl Iterator<Item = Value> {
+    let values = match fields.remove(path!(key), false) {
+        Some(Value::Array(values)) => values,
+        _ => Vec::new(),
+    };
+    values.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{
+        common::v1::{AnyValue, any_value::Value as PBValue},
+        trace::v1::status::StatusCode,
+    };
+
+    fn string_kv(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(PBValue::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn resource_spans(service: &str) -> ResourceSpans {
+        ResourceSpans {
+            resource: Some(Resource {
+                attributes: vec![string_kv("service.name", service)],
+                ..Default::default()
+            }),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(InstrumentationScope::default()),
+                spans: vec![Span {
+                    trace_id: vec![1; 16],
+                    span_id: vec![2; 8],
+                    trace_state: "vendor=value".to_string(),
+                    parent_span_id: vec![3; 8],
+                    name: "checkout".to_string(),
+                    kind: 2,
+                    start_time_unix_nano: 1_700_000_000_000_000_000,
+                    end_time_unix_nano: 1_700_000_000_500_000_000,
+                    attributes: vec![string_kv("http.route", "/checkout")],
+                    events: vec![SpanEvent {
+                        time_unix_nano: 1_700_000_000_100_000_000,
+                        name: "retry".to_string(),
+                        attributes: vec![string_kv("attempt", "2")],
+                        dropped_attributes_count: 0,
+                    }],
+                    links: vec![Link {
+                        trace_id: vec![4; 16],
+                        span_id: vec![5; 8],
+                        ..Default::default()
+                    }],
+                    status: Some(SpanStatus {
+                        message: "failed".to_string(),
+                        code: StatusCode::Error as i32,
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn round_trips_spans() {
+        let traces = resource_spans("checkout")
+            .into_event_iter()
+            .chain(resource_spans("payment").into_event_iter())
+            .chain(resource_spans("checkout").into_event_iter())
+            .map(Event::into_trace);
+        let request = build_export_request(traces);
+
+        let mut checkout = resource_spans("checkout");
+        let span = checkout.scope_spans[0].spans[0].clone();
+        checkout.scope_spans[0].spans.push(span);
+        assert_eq!(
+            request.resource_spans,
+            vec![checkout, resource_spans("payment")]
+        );
+    }
+}
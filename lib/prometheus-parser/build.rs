@@ -1,5 +1,6 @@
 fn main() {
     println!("cargo:rerun-if-changed=proto/prometheus-remote.proto");
+    println!("cargo:rerun-if-changed=proto/prometheus-remote-v2.proto");
     println!("cargo:rerun-if-changed=proto/prometheus-types.proto");
     let mut prost_build = prost_build::Config::new();
     prost_build.btree_map(["."]);
@@ -9,7 +10,10 @@ fn main() {
     prost_build.type_attribute("Label", "#[derive(Eq, Hash, Ord, PartialOrd)]");
     prost_build
         .compile_protos(
-            &["proto/prometheus-remote.proto"],
+            &[
+                "proto/prometheus-remote.proto",
+                "proto/prometheus-remote-v2.proto",
+            ],
             &["proto", "../../proto/third-party"],
         )
         .unwrap();
@@ -6,18 +6,35 @@ use indexmap::IndexMap;
 use snafu::ResultExt;
 
 mod line;
+mod native;
+mod v2;
 
 pub use line::ErrorKind;
 use line::{Line, Metric, MetricKind};
+pub use native::{MAX_SCHEMA, MIN_SCHEMA, NativeHistogramBuckets, NativeHistogramMetric};
 
 pub const METRIC_NAME_LABEL: &str = "__name__";
 
+/// The key of the metric metadata under which the type, help and unit of the family of a metric
+/// are kept.
+pub const METADATA_KEY: &str = "prometheus";
+
+/// The key of the metric metadata under which the exemplars of a series are kept.
+pub const EXEMPLARS_KEY: &str = "exemplars";
+
 #[allow(warnings)] // Ignore some clippy warnings
 pub mod proto {
     include!(concat!(env!("OUT_DIR"), "/prometheus.rs"));
 
     pub use metric_metadata::MetricType;
 
+    /// The messages of the [remote write 2.0][spec] protocol.
+    ///
+    /// [spec]: https://prometheus.io/docs/specs/prw/remote_write_spec_2_0/
+    pub mod v2 {
+        include!(concat!(env!("OUT_DIR"), "/io.prometheus.write.v2.rs"));
+    }
+
     impl MetricType {
         pub fn as_str(&self) -> &'static str {
             match self {
@@ -60,6 +77,18 @@ pub enum ParserError {
     MultipleMetricKinds { name: String },
     #[snafu(display("request is missing metric name label"))]
     RequestNoNameLabel,
+
+    #[snafu(display("unsupported native histogram schema: {}", schema))]
+    UnsupportedHistogramSchema { schema: i32 },
+    #[snafu(display("native histogram bucket spans don't match its bucket counts"))]
+    InvalidBucketSpans,
+    #[snafu(display("native histogram has more than {} buckets", max))]
+    TooManyBuckets { max: usize },
+
+    #[snafu(display("symbol reference out of range: {}", reference))]
+    InvalidSymbolReference { reference: u32 },
+    #[snafu(display("expected label references in name and value pairs"))]
+    OddLabelReferences,
 }
 
 vector_common::impl_event_data_eq!(ParserError);
@@ -101,6 +130,35 @@ pub struct SimpleMetric {
     pub value: f64,
 }
 
+/// An exemplar of a series, as sent by remote write.
+#[derive(Debug, Default, PartialEq)]
+pub struct Exemplar {
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+    pub timestamp: i64,
+}
+
+/// The metadata of a metric family, as sent by remote write.
+#[derive(Debug, Default, PartialEq)]
+pub struct GroupMetadata {
+    pub r#type: Option<proto::MetricType>,
+    pub help: Option<String>,
+    pub unit: Option<String>,
+}
+
+impl GroupMetadata {
+    /// Fills in the fields that aren't known yet.
+    fn update(&mut self, r#type: proto::MetricType, help: String, unit: String) {
+        self.r#type.get_or_insert(r#type);
+        if self.help.is_none() && !help.is_empty() {
+            self.help = Some(help);
+        }
+        if self.unit.is_none() && !unit.is_empty() {
+            self.unit = Some(unit);
+        }
+    }
+}
+
 type MetricMap<T> = IndexMap<GroupKey, T>;
 
 #[derive(Debug)]
@@ -234,6 +292,13 @@ impl GroupKind {
 pub struct MetricGroup {
     pub name: String,
     pub metrics: GroupKind,
+    /// The native histograms of the family, which remote write sends along with its classic
+    /// samples.
+    pub native_histograms: MetricMap<NativeHistogramMetric>,
+    /// The exemplars of each series of the family, by labels. The `le` label of the buckets of
+    /// classic histograms is left out, so that they apply to the whole histogram.
+    pub exemplars: IndexMap<BTreeMap<String, String>, Vec<Exemplar>>,
+    pub metadata: GroupMetadata,
 }
 
 fn try_f64_to_u64(f: f64) -> Result<u64, ParserError> {
@@ -249,8 +314,17 @@ fn try_f64_to_u64(f: f64) -> Result<u64, ParserError> {
 
 impl MetricGroup {
     fn new(name: String, kind: MetricKind) -> Self {
-        let metrics = GroupKind::new(kind);
-        MetricGroup { name, metrics }
+        Self::with_metrics(name, GroupKind::new(kind))
+    }
+
+    fn with_metrics(name: String, metrics: GroupKind) -> Self {
+        MetricGroup {
+            name,
+            metrics,
+            native_histograms: IndexMap::default(),
+            exemplars: IndexMap::default(),
+            metadata: GroupMetadata::default(),
+        }
     }
 
     // For cases where a metric group was not defined with `# TYPE ...`.
@@ -262,10 +336,7 @@ impl MetricGroup {
             timestamp,
         } = metric;
         let key = GroupKey { timestamp, labels };
-        MetricGroup {
-            name,
-            metrics: GroupKind::new_untyped(key, value),
-        }
+        Self::with_metrics(name, GroupKind::new_untyped(key, value))
     }
 
     /// `Err(_)` if there are irrecoverable error.
@@ -314,10 +385,10 @@ pub fn parse_text(input: &str) -> Result<Vec<MetricGroup>, ParserError> {
 }
 
 #[derive(Default)]
-struct MetricGroupSet(IndexMap<String, GroupKind>);
+struct MetricGroupSet(IndexMap<String, MetricGroup>);
 
 impl MetricGroupSet {
-    fn get_group<'a>(&'a mut self, name: &str) -> (usize, &'a String, &'a mut GroupKind) {
+    fn get_group(&mut self, name: &str) -> &mut MetricGroup {
         let len = name.len();
         let name = if self.0.contains_key(name) {
             name
@@ -328,24 +399,35 @@ impl MetricGroupSet {
         } else if name.ends_with("_count") && self.0.contains_key(&name[..len - 6]) {
             &name[..len - 6]
         } else {
-            self.0
-                .insert(name.into(), GroupKind::new(MetricKind::Untyped));
+            self.0.insert(
+                name.into(),
+                MetricGroup::new(name.into(), MetricKind::Untyped),
+            );
             name
         };
-        self.0.get_full_mut(name).unwrap()
+        self.0.get_mut(name).unwrap()
     }
 
-    fn insert_metadata(&mut self, name: String, kind: MetricKind) -> Result<(), ParserError> {
-        match self.0.get(&name) {
-            Some(group) if !group.matches_kind(kind) => {
-                Err(ParserError::MultipleMetricKinds { name })
-            }
-            Some(_) => Ok(()), // metadata already exists and is the right type
-            None => {
-                self.0.insert(name, GroupKind::new(kind));
-                Ok(())
-            }
+    fn insert_metadata(&mut self, metadata: proto::MetricMetadata) -> Result<(), ParserError> {
+        let name = metadata.metric_family_name;
+        let r#type =
+            proto::MetricType::try_from(metadata.r#type).unwrap_or(proto::MetricType::Unknown);
+        let kind = r#type.into();
+        if self
+            .0
+            .get(&name)
+            .is_some_and(|group| !group.metrics.matches_kind(kind))
+        {
+            return Err(ParserError::MultipleMetricKinds { name });
         }
+
+        // The group may already exist with the right type, from previous metadata.
+        self.0
+            .entry(name)
+            .or_insert_with_key(|name| MetricGroup::new(name.clone(), kind))
+            .metadata
+            .update(r#type, metadata.help, metadata.unit);
+        Ok(())
     }
 
     fn insert_sample(
@@ -354,9 +436,9 @@ impl MetricGroupSet {
         labels: &BTreeMap<String, String>,
         sample: proto::Sample,
     ) -> Result<(), ParserError> {
-        let (_, basename, group) = self.get_group(name);
-        if let Some(metric) = group.try_push(
-            basename.len(),
+        let group = self.get_group(name);
+        if let Some(metric) = group.metrics.try_push(
+            group.name.len(),
             Metric {
                 name: name.into(),
                 labels: labels.clone(),
@@ -364,21 +446,58 @@ impl MetricGroupSet {
                 timestamp: Some(sample.timestamp),
             },
         )? {
-            let key = GroupKey {
-                timestamp: metric.timestamp,
-                labels: metric.labels,
-            };
-            let group = GroupKind::new_untyped(key, metric.value);
-            self.0.insert(metric.name, group);
+            let group = MetricGroup::new_untyped(metric);
+            self.0.insert(group.name.clone(), group);
         }
         Ok(())
     }
 
+    fn insert_histogram(
+        &mut self,
+        name: &str,
+        labels: &BTreeMap<String, String>,
+        histogram: proto::Histogram,
+    ) -> Result<(), ParserError> {
+        let key = GroupKey {
+            timestamp: Some(histogram.timestamp),
+            labels: labels.clone(),
+        };
+        let histogram = NativeHistogramMetric::try_from(histogram)?;
+        self.get_group(name)
+            .native_histograms
+            .insert(key, histogram);
+        Ok(())
+    }
+
+    fn insert_exemplars(
+        &mut self,
+        name: &str,
+        mut labels: BTreeMap<String, String>,
+        exemplars: Vec<proto::Exemplar>,
+    ) {
+        let group = self.get_group(name);
+        if name.len() > group.name.len() {
+            labels.remove("le");
+        }
+        group
+            .exemplars
+            .entry(labels)
+            .or_default()
+            .extend(exemplars.into_iter().map(|exemplar| {
+                Exemplar {
+                    labels: exemplar
+                        .labels
+                        .into_iter()
+                        .map(|label| (label.name, label.value))
+                        .collect(),
+                    value: exemplar.value,
+                    timestamp: exemplar.timestamp,
+                }
+            }));
+    }
+
     fn finish(self) -> Vec<MetricGroup> {
-        self.0
-            .into_iter()
-            .map(|(name, metrics)| MetricGroup { name, metrics })
-            .collect()
+        self.0.into_values().collect()
     }
 }
 
@@ -388,11 +507,7 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
     let mut groups = MetricGroupSet::default();
 
     for metadata in request.metadata {
-        let name = metadata.metric_family_name;
-        let kind = proto::MetricType::try_from(metadata.r#type)
-            .unwrap_or(proto::MetricType::Unknown)
-            .into();
-        groups.insert_metadata(name, kind)?;
+        groups.insert_metadata(metadata)?;
     }
 
     for timeseries in request.timeseries {
@@ -409,11 +524,23 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
         for sample in timeseries.samples {
             groups.insert_sample(&name, &labels, sample)?;
         }
+        for histogram in timeseries.histograms {
+            groups.insert_histogram(&name, &labels, histogram)?;
+        }
+        if !timeseries.exemplars.is_empty() {
+            groups.insert_exemplars(&name, labels, timeseries.exemplars);
+        }
     }
 
     Ok(groups.finish())
 }
 
+/// Parse the given remote write 2.0 request, resolving its symbols and grouping the metrics into
+/// higher-level metric types based on the metadata of its series.
+pub fn parse_request_v2(request: proto::v2::Request) -> Result<Vec<MetricGroup>, ParserError> {
+    parse_request(request.try_into()?)
+}
+
 impl From<proto::MetricType> for MetricKind {
     fn from(kind: proto::MetricType) -> Self {
         use proto::MetricType::*;
@@ -734,6 +861,7 @@ mod test {
                     samples: vec![
                         $( proto::Sample { value: $sample as f64, timestamp: $timestamp as i64 }, )*
                     ],
+                    ..Default::default()
                 }, )* ],
             }
         };
@@ -884,4 +1012,148 @@ mod test {
             assert_eq!(metrics.get_index(0).unwrap(), simple_metric!(Some(1395066367700), labels!(), 24.0));
         });
     }
+
+    #[test]
+    fn parse_request_native_histogram() {
+        let mut request = write_request!(["one" = Histogram], []);
+        request.metadata[0].help = "The first one.".into();
+        request.metadata[0].unit = "seconds".into();
+        request.timeseries.push(proto::TimeSeries {
+            labels: vec![proto::Label {
+                name: METRIC_NAME_LABEL.into(),
+                value: "one".into(),
+            }],
+            histograms: vec![
+                NativeHistogramMetric {
+                    schema: 1,
+                    zero_count: 1,
+                    positive: NativeHistogramBuckets {
+                        offset: 2,
+                        counts: vec![3, 4],
+                    },
+                    count: 8,
+                    sum: 12.5,
+                    ..Default::default()
+                }
+                .into_proto(1395066367700),
+            ],
+            exemplars: vec![proto::Exemplar {
+                labels: vec![proto::Label {
+                    name: "trace_id".into(),
+                    value: "abc".into(),
+                }],
+                value: 2.5,
+                timestamp: 1395066367600,
+            }],
+            ..Default::default()
+        });
+
+        let parsed = parse_request(request).unwrap();
+        assert_eq!(parsed.len(), 1);
+        match_group!(parsed[0], "one", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            assert!(metrics.is_empty());
+        });
+        assert_eq!(
+            parsed[0].metadata,
+            GroupMetadata {
+                r#type: Some(proto::MetricType::Histogram),
+                help: Some("The first one.".into()),
+                unit: Some("seconds".into()),
+            }
+        );
+        assert_eq!(
+            parsed[0].native_histograms.get_index(0).unwrap(),
+            (
+                &GroupKey {
+                    timestamp: Some(1395066367700),
+                    labels: labels!(),
+                },
+                &NativeHistogramMetric {
+                    schema: 1,
+                    zero_count: 1,
+                    positive: NativeHistogramBuckets {
+                        offset: 2,
+                        counts: vec![3, 4],
+                    },
+                    count: 8,
+                    sum: 12.5,
+                    ..Default::default()
+                }
+            )
+        );
+        assert_eq!(
+            parsed[0].exemplars.get(&labels!()).unwrap(),
+            &vec![Exemplar {
+                labels: labels!(trace_id => "abc"),
+                value: 2.5,
+                timestamp: 1395066367600,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_request_bucket_exemplars() {
+        let mut request = write_request!(
+            ["one" = Histogram],
+            [
+                [__name__ => "one_bucket", code => 200, le => "1"] => [ 15 @ 1395066367700 ],
+                [__name__ => "one_bucket", code => 200, le => "+Inf"] => [ 19 @ 1395066367700 ]
+            ]
+        );
+        request.timeseries[0].exemplars.push(proto::Exemplar {
+            labels: vec![],
+            value: 0.5,
+            timestamp: 1395066367600,
+        });
+
+        let parsed = parse_request(request).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(
+            parsed[0].exemplars.get(&labels!(code => 200)).unwrap(),
+            &vec![Exemplar {
+                labels: labels!(),
+                value: 0.5,
+                timestamp: 1395066367600,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_request_v2_series() {
+        let request = proto::v2::Request {
+            symbols: vec![
+                "".into(),
+                "__name__".into(),
+                "one_total".into(),
+                "code".into(),
+                "200".into(),
+                "Requests.".into(),
+            ],
+            timeseries: vec![proto::v2::TimeSeries {
+                labels_refs: vec![1, 2, 3, 4],
+                samples: vec![proto::v2::Sample {
+                    value: 24.0,
+                    timestamp: 1395066367700,
+                }],
+                metadata: Some(proto::v2::Metadata {
+                    r#type: proto::v2::metadata::MetricType::Counter as i32,
+                    help_ref: 5,
+                    unit_ref: 0,
+                }),
+                ..Default::default()
+            }],
+        };
+
+        let parsed = parse_request_v2(request).unwrap();
+        assert_eq!(parsed.len(), 1);
+        match_group!(parsed[0], "one_total", Counter => |metrics: &MetricMap<SimpleMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(
+                metrics.get_index(0).unwrap(),
+                simple_metric!(Some(1395066367700), labels!(code => 200), 24.0)
+            );
+        });
+        assert_eq!(parsed[0].metadata.help.as_deref(), Some("Requests."));
+        assert_eq!(parsed[0].metadata.unit, None);
+    }
 }
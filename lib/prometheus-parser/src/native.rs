@@ -0,0 +1,294 @@
+use crate::{
+    ParserError, proto,
+    proto::histogram::{Count, ResetHint, ZeroCount},
+    try_f64_to_u64,
+};
+
+/// The lowest schema of exponential native histograms.
+pub const MIN_SCHEMA: i32 = -4;
+
+/// The highest schema of exponential native histograms.
+pub const MAX_SCHEMA: i32 = 8;
+
+/// The maximum number of buckets, on each side of the zero bucket, of the native histograms that
+/// are accepted. This is far more than the number of buckets needed to cover the range of
+/// `f64` at the highest schema, and bounds the memory taken by spans with large gaps.
+const MAX_BUCKETS: usize = 1 << 16;
+
+/// A contiguous range of buckets of a native histogram.
+#[derive(Debug, Default, PartialEq)]
+pub struct NativeHistogramBuckets {
+    /// The index of the first bucket.
+    pub offset: i32,
+    /// The number of observations in each bucket, starting with the one at `offset`.
+    pub counts: Vec<u64>,
+}
+
+/// A native histogram, with its buckets expanded from their spans.
+///
+/// The boundaries of its buckets are the powers of `base = 2^(2^-schema)`: the bucket at index `i`
+/// holds the observations whose absolute value is in `(base^(i-1), base^i]`.
+#[derive(Debug, Default, PartialEq)]
+pub struct NativeHistogramMetric {
+    pub schema: i32,
+    pub zero_threshold: f64,
+    pub zero_count: u64,
+    pub positive: NativeHistogramBuckets,
+    pub negative: NativeHistogramBuckets,
+    pub count: u64,
+    pub sum: f64,
+}
+
+impl TryFrom<proto::Histogram> for NativeHistogramMetric {
+    type Error = ParserError;
+
+    /// Expands the buckets of a histogram. The counts of float histograms are rounded.
+    fn try_from(histogram: proto::Histogram) -> Result<Self, Self::Error> {
+        let schema = histogram.schema;
+        if !(MIN_SCHEMA..=MAX_SCHEMA).contains(&schema) {
+            return Err(ParserError::UnsupportedHistogramSchema { schema });
+        }
+
+        Ok(Self {
+            schema,
+            zero_threshold: histogram.zero_threshold,
+            zero_count: match histogram.zero_count {
+                Some(ZeroCount::ZeroCountInt(count)) => count,
+                Some(ZeroCount::ZeroCountFloat(count)) => try_f64_to_u64(count.round())?,
+                None => 0,
+            },
+            positive: NativeHistogramBuckets::decode(
+                &histogram.positive_spans,
+                &histogram.positive_deltas,
+                &histogram.positive_counts,
+            )?,
+            negative: NativeHistogramBuckets::decode(
+                &histogram.negative_spans,
+                &histogram.negative_deltas,
+                &histogram.negative_counts,
+            )?,
+            count: match histogram.count {
+                Some(Count::CountInt(count)) => count,
+                Some(Count::CountFloat(count)) => try_f64_to_u64(count.round())?,
+                None => 0,
+            },
+            sum: histogram.sum,
+        })
+    }
+}
+
+impl NativeHistogramMetric {
+    /// Encodes the histogram as an integer histogram, taken at `timestamp`.
+    pub fn into_proto(self, timestamp: i64) -> proto::Histogram {
+        let (negative_spans, negative_deltas) = self.negative.encode();
+        let (positive_spans, positive_deltas) = self.positive.encode();
+        proto::Histogram {
+            count: Some(Count::CountInt(self.count)),
+            sum: self.sum,
+            schema: self.schema,
+            zero_threshold: self.zero_threshold,
+            zero_count: Some(ZeroCount::ZeroCountInt(self.zero_count)),
+            negative_spans,
+            negative_deltas,
+            negative_counts: Vec::new(),
+            positive_spans,
+            positive_deltas,
+            positive_counts: Vec::new(),
+            reset_hint: ResetHint::Unknown as i32,
+            timestamp,
+        }
+    }
+}
+
+impl NativeHistogramBuckets {
+    /// Expands the spans of buckets, with either the deltas of integer histograms or the counts
+    /// of float histograms.
+    fn decode(
+        spans: &[proto::BucketSpan],
+        deltas: &[i64],
+        counts: &[f64],
+    ) -> Result<Self, ParserError> {
+        let counts = if counts.is_empty() {
+            let mut count = 0i64;
+            deltas
+                .iter()
+                .map(|delta| {
+                    count = count.saturating_add(*delta);
+                    u64::try_from(count).map_err(|_| ParserError::ValueOutOfRange {
+                        value: count as f64,
+                        max: u64::MAX,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            counts
+                .iter()
+                .map(|count| try_f64_to_u64(count.round()))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut buckets = Self {
+            offset: spans.first().map_or(0, |span| span.offset),
+            counts: Vec::with_capacity(counts.len()),
+        };
+        let mut counts = counts.into_iter();
+        for (index, span) in spans.iter().enumerate() {
+            // The offset of the first span is the index of its first bucket, while the offsets of
+            // the following ones are the gaps from the previous span.
+            if index > 0 {
+                let gap =
+                    usize::try_from(span.offset).map_err(|_| ParserError::InvalidBucketSpans)?;
+                buckets.resize(buckets.counts.len() + gap)?;
+            }
+            for _ in 0..span.length {
+                let count = counts.next().ok_or(ParserError::InvalidBucketSpans)?;
+                buckets.resize(buckets.counts.len() + 1)?;
+                *buckets.counts.last_mut().expect("bucket was just added") = count;
+            }
+        }
+
+        match counts.next() {
+            Some(_) => Err(ParserError::InvalidBucketSpans),
+            None => Ok(buckets),
+        }
+    }
+
+    fn resize(&mut self, len: usize) -> Result<(), ParserError> {
+        if len > MAX_BUCKETS {
+            return Err(ParserError::TooManyBuckets { max: MAX_BUCKETS });
+        }
+        self.counts.resize(len, 0);
+        Ok(())
+    }
+
+    /// Encodes the buckets as a single span, with the deltas of their counts.
+    fn encode(self) -> (Vec<proto::BucketSpan>, Vec<i64>) {
+        if self.counts.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+
+        let span = proto::BucketSpan {
+            offset: self.offset,
+            length: self.counts.len() as u32,
+        };
+        let mut previous = 0i64;
+        let deltas = self
+            .counts
+            .into_iter()
+            .map(|count| {
+                let count = count as i64;
+                let delta = count.wrapping_sub(previous);
+                previous = count;
+                delta
+            })
+            .collect();
+        (vec![span], deltas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(offset: i32, length: u32) -> proto::BucketSpan {
+        proto::BucketSpan { offset, length }
+    }
+
+    #[test]
+    fn decodes_spans_with_gaps() {
+        let buckets =
+            NativeHistogramBuckets::decode(&[span(-2, 2), span(1, 1)], &[3, -1, 4], &[]).unwrap();
+
+        assert_eq!(
+            buckets,
+            NativeHistogramBuckets {
+                offset: -2,
+                counts: vec![3, 2, 0, 6],
+            }
+        );
+    }
+
+    #[test]
+    fn decodes_float_counts() {
+        let buckets = NativeHistogramBuckets::decode(&[span(1, 2)], &[], &[1.0, 2.6]).unwrap();
+
+        assert_eq!(
+            buckets,
+            NativeHistogramBuckets {
+                offset: 1,
+                counts: vec![1, 3],
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_spans() {
+        assert_eq!(
+            NativeHistogramBuckets::decode(&[span(0, 3)], &[1, 1], &[]),
+            Err(ParserError::InvalidBucketSpans)
+        );
+        assert_eq!(
+            NativeHistogramBuckets::decode(&[span(0, 1)], &[1, 1], &[]),
+            Err(ParserError::InvalidBucketSpans)
+        );
+        assert_eq!(
+            NativeHistogramBuckets::decode(&[span(0, 1), span(i32::MAX, 1)], &[1, 1], &[]),
+            Err(ParserError::TooManyBuckets { max: MAX_BUCKETS })
+        );
+    }
+
+    #[test]
+    fn encodes_and_decodes_histograms() {
+        let histogram = NativeHistogramMetric {
+            schema: 3,
+            zero_threshold: 0.001,
+            zero_count: 2,
+            positive: NativeHistogramBuckets {
+                offset: -1,
+                counts: vec![4, 0, 1],
+            },
+            negative: NativeHistogramBuckets {
+                offset: 2,
+                counts: vec![5],
+            },
+            count: 12,
+            sum: 3.5,
+        };
+
+        let proto = histogram.into_proto(1395066367500);
+        assert_eq!(proto.timestamp, 1395066367500);
+        assert_eq!(proto.positive_deltas, vec![4, -4, 1]);
+
+        assert_eq!(
+            NativeHistogramMetric::try_from(proto).unwrap(),
+            NativeHistogramMetric {
+                schema: 3,
+                zero_threshold: 0.001,
+                zero_count: 2,
+                positive: NativeHistogramBuckets {
+                    offset: -1,
+                    counts: vec![4, 0, 1],
+                },
+                negative: NativeHistogramBuckets {
+                    offset: 2,
+                    counts: vec![5],
+                },
+                count: 12,
+                sum: 3.5,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_custom_buckets() {
+        let histogram = proto::Histogram {
+            schema: -53,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            NativeHistogramMetric::try_from(histogram),
+            Err(ParserError::UnsupportedHistogramSchema { schema: -53 })
+        );
+    }
+}
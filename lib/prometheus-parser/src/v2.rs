@@ -0,0 +1,462 @@
+//! Conversions between the messages of remote write 1.0 and 2.0.
+//!
+//! Remote write 2.0 interns the strings of a request in its symbols, and carries the metadata of
+//! each series instead of each metric family. Converting a request to the other version keeps
+//! its series, samples, native histograms, exemplars and metadata.
+
+use std::collections::HashMap;
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{METRIC_NAME_LABEL, ParserError, proto, proto::v2};
+
+/// The symbols of a remote write 2.0 request.
+///
+/// The first symbol is always the empty string, so that optional references default to it.
+struct SymbolTable(IndexSet<String>);
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self(IndexSet::from([String::new()]))
+    }
+}
+
+impl SymbolTable {
+    /// Returns the reference to a string, adding it to the symbols if needed.
+    fn intern(&mut self, symbol: &str) -> u32 {
+        match self.0.get_index_of(symbol) {
+            Some(index) => index as u32,
+            None => self.0.insert_full(symbol.into()).0 as u32,
+        }
+    }
+
+    fn intern_labels(&mut self, labels: Vec<proto::Label>) -> Vec<u32> {
+        labels
+            .iter()
+            .flat_map(|label| [self.intern(&label.name), self.intern(&label.value)])
+            .collect()
+    }
+
+    fn into_symbols(self) -> Vec<String> {
+        self.0.into_iter().collect()
+    }
+}
+
+/// Resolves the references of a remote write 2.0 request to its symbols.
+struct Symbols<'a>(&'a [String]);
+
+impl Symbols<'_> {
+    fn resolve(&self, reference: u32) -> Result<String, ParserError> {
+        self.0
+            .get(reference as usize)
+            .cloned()
+            .ok_or(ParserError::InvalidSymbolReference { reference })
+    }
+
+    fn labels(&self, references: &[u32]) -> Result<Vec<proto::Label>, ParserError> {
+        if references.len() % 2 != 0 {
+            return Err(ParserError::OddLabelReferences);
+        }
+        references
+            .chunks_exact(2)
+            .map(|pair| {
+                Ok(proto::Label {
+                    name: self.resolve(pair[0])?,
+                    value: self.resolve(pair[1])?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the name of the family of a series, leaving out the suffixes of the series that
+/// make up classic histograms and summaries.
+fn family_name(name: &str, r#type: proto::MetricType) -> &str {
+    use proto::MetricType::*;
+    let suffixes: &[&str] = match r#type {
+        Histogram | Gaugehistogram => &["_bucket", "_sum", "_count"],
+        Summary => &["_sum", "_count"],
+        _ => &[],
+    };
+    suffixes
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .unwrap_or(name)
+}
+
+impl TryFrom<v2::Request> for proto::WriteRequest {
+    type Error = ParserError;
+
+    fn try_from(request: v2::Request) -> Result<Self, Self::Error> {
+        let symbols = Symbols(&request.symbols);
+        let mut metadata = IndexMap::<String, proto::MetricMetadata>::new();
+
+        let timeseries = request
+            .timeseries
+            .into_iter()
+            .map(|series| {
+                let labels = symbols.labels(&series.labels_refs)?;
+                if let Some(series_metadata) = series.metadata {
+                    let r#type = proto::MetricType::from(series_metadata.r#type());
+                    let help = symbols.resolve(series_metadata.help_ref)?;
+                    let unit = symbols.resolve(series_metadata.unit_ref)?;
+                    let name = labels
+                        .iter()
+                        .find(|label| label.name == METRIC_NAME_LABEL)
+                        .map(|label| family_name(&label.value, r#type));
+                    let known = r#type != proto::MetricType::Unknown
+                        || !help.is_empty()
+                        || !unit.is_empty();
+                    if let Some(name) = name.filter(|_| known) {
+                        metadata.entry(name.into()).or_insert_with_key(|name| {
+                            proto::MetricMetadata {
+                                r#type: r#type as i32,
+                                metric_family_name: name.clone(),
+                                help,
+                                unit,
+                            }
+                        });
+                    }
+                }
+
+                Ok(proto::TimeSeries {
+                    labels,
+                    samples: series
+                        .samples
+                        .into_iter()
+                        .map(|sample| proto::Sample {
+                            value: sample.value,
+                            timestamp: sample.timestamp,
+                        })
+                        .collect(),
+                    exemplars: series
+                        .exemplars
+                        .into_iter()
+                        .map(|exemplar| {
+                            Ok(proto::Exemplar {
+                                labels: symbols.labels(&exemplar.labels_refs)?,
+                                value: exemplar.value,
+                                timestamp: exemplar.timestamp,
+                            })
+                        })
+                        .collect::<Result<_, ParserError>>()?,
+                    histograms: series.histograms.into_iter().map(Into::into).collect(),
+                })
+            })
+            .collect::<Result<_, ParserError>>()?;
+
+        Ok(Self {
+            timeseries,
+            metadata: metadata.into_values().collect(),
+        })
+    }
+}
+
+impl From<proto::WriteRequest> for v2::Request {
+    fn from(request: proto::WriteRequest) -> Self {
+        let mut symbols = SymbolTable::default();
+        let metadata = request
+            .metadata
+            .into_iter()
+            .map(|metadata| {
+                let r#type = proto::MetricType::try_from(metadata.r#type)
+                    .unwrap_or(proto::MetricType::Unknown);
+                let help_ref = symbols.intern(&metadata.help);
+                let unit_ref = symbols.intern(&metadata.unit);
+                (metadata.metric_family_name, (r#type, help_ref, unit_ref))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let timeseries = request
+            .timeseries
+            .into_iter()
+            .map(|series| {
+                let metadata = series
+                    .labels
+                    .iter()
+                    .find(|label| label.name == METRIC_NAME_LABEL)
+                    .and_then(|label| {
+                        metadata.get(&label.value).or_else(|| {
+                            metadata
+                                .iter()
+                                .find(|(name, (r#type, _, _))| {
+                                    family_name(&label.value, *r#type) == name.as_str()
+                                })
+                                .map(|(_, metadata)| metadata)
+                        })
+                    })
+                    .map(|&(r#type, help_ref, unit_ref)| v2::Metadata {
+                        r#type: v2::metadata::MetricType::from(r#type) as i32,
+                        help_ref,
+                        unit_ref,
+                    });
+
+                v2::TimeSeries {
+                    labels_refs: symbols.intern_labels(series.labels),
+                    samples: series
+                        .samples
+                        .into_iter()
+                        .map(|sample| v2::Sample {
+                            value: sample.value,
+                            timestamp: sample.timestamp,
+                        })
+                        .collect(),
+                    histograms: series.histograms.into_iter().map(Into::into).collect(),
+                    exemplars: series
+                        .exemplars
+                        .into_iter()
+                        .map(|exemplar| v2::Exemplar {
+                            labels_refs: symbols.intern_labels(exemplar.labels),
+                            value: exemplar.value,
+                            timestamp: exemplar.timestamp,
+                        })
+                        .collect(),
+                    metadata,
+                    created_timestamp: 0,
+                }
+            })
+            .collect();
+
+        Self {
+            symbols: symbols.into_symbols(),
+            timeseries,
+        }
+    }
+}
+
+impl From<v2::metadata::MetricType> for proto::MetricType {
+    fn from(r#type: v2::metadata::MetricType) -> Self {
+        use v2::metadata::MetricType::*;
+        match r#type {
+            Unspecified => Self::Unknown,
+            Counter => Self::Counter,
+            Gauge => Self::Gauge,
+            Histogram => Self::Histogram,
+            Gaugehistogram => Self::Gaugehistogram,
+            Summary => Self::Summary,
+            Info => Self::Info,
+            Stateset => Self::Stateset,
+        }
+    }
+}
+
+impl From<proto::MetricType> for v2::metadata::MetricType {
+    fn from(r#type: proto::MetricType) -> Self {
+        use proto::MetricType::*;
+        match r#type {
+            Unknown => Self::Unspecified,
+            Counter => Self::Counter,
+            Gauge => Self::Gauge,
+            Histogram => Self::Histogram,
+            Gaugehistogram => Self::Gaugehistogram,
+            Summary => Self::Summary,
+            Info => Self::Info,
+            Stateset => Self::Stateset,
+        }
+    }
+}
+
+impl From<v2::Histogram> for proto::Histogram {
+    fn from(histogram: v2::Histogram) -> Self {
+        use proto::histogram::{Count, ZeroCount};
+        Self {
+            count: histogram.count.map(|count| match count {
+                v2::histogram::Count::CountInt(count) => Count::CountInt(count),
+                v2::histogram::Count::CountFloat(count) => Count::CountFloat(count),
+            }),
+            sum: histogram.sum,
+            schema: histogram.schema,
+            zero_threshold: histogram.zero_threshold,
+            zero_count: histogram.zero_count.map(|count| match count {
+                v2::histogram::ZeroCount::ZeroCountInt(count) => ZeroCount::ZeroCountInt(count),
+                v2::histogram::ZeroCount::ZeroCountFloat(count) => ZeroCount::ZeroCountFloat(count),
+            }),
+            negative_spans: histogram
+                .negative_spans
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            negative_deltas: histogram.negative_deltas,
+            negative_counts: histogram.negative_counts,
+            positive_spans: histogram
+                .positive_spans
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            positive_deltas: histogram.positive_deltas,
+            positive_counts: histogram.positive_counts,
+            // Both versions number the reset hints alike.
+            reset_hint: histogram.reset_hint,
+            timestamp: histogram.timestamp,
+        }
+    }
+}
+
+impl From<proto::Histogram> for v2::Histogram {
+    fn from(histogram: proto::Histogram) -> Self {
+        use v2::histogram::{Count, ZeroCount};
+        Self {
+            count: histogram.count.map(|count| match count {
+                proto::histogram::Count::CountInt(count) => Count::CountInt(count),
+                proto::histogram::Count::CountFloat(count) => Count::CountFloat(count),
+            }),
+            sum: histogram.sum,
+            schema: histogram.schema,
+            zero_threshold: histogram.zero_threshold,
+            zero_count: histogram.zero_count.map(|count| match count {
+                proto::histogram::ZeroCount::ZeroCountInt(count) => ZeroCount::ZeroCountInt(count),
+                proto::histogram::ZeroCount::ZeroCountFloat(count) => {
+                    ZeroCount::ZeroCountFloat(count)
+                }
+            }),
+            negative_spans: histogram
+                .negative_spans
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            negative_deltas: histogram.negative_deltas,
+            negative_counts: histogram.negative_counts,
+            positive_spans: histogram
+                .positive_spans
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            positive_deltas: histogram.positive_deltas,
+            positive_counts: histogram.positive_counts,
+            reset_hint: histogram.reset_hint,
+            timestamp: histogram.timestamp,
+            custom_values: Vec::new(),
+        }
+    }
+}
+
+impl From<v2::BucketSpan> for proto::BucketSpan {
+    fn from(span: v2::BucketSpan) -> Self {
+        Self {
+            offset: span.offset,
+            length: span.length,
+        }
+    }
+}
+
+impl From<proto::BucketSpan> for v2::BucketSpan {
+    fn from(span: proto::BucketSpan) -> Self {
+        Self {
+            offset: span.offset,
+            length: span.length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(name: &str, value: &str) -> proto::Label {
+        proto::Label {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    fn write_request() -> proto::WriteRequest {
+        proto::WriteRequest {
+            metadata: vec![proto::MetricMetadata {
+                r#type: proto::MetricType::Histogram as i32,
+                metric_family_name: "latency".into(),
+                help: "The latency.".into(),
+                unit: "seconds".into(),
+            }],
+            timeseries: vec![
+                proto::TimeSeries {
+                    labels: vec![label("__name__", "latency_bucket"), label("le", "1")],
+                    samples: vec![proto::Sample {
+                        value: 3.0,
+                        timestamp: 1395066367500,
+                    }],
+                    exemplars: vec![proto::Exemplar {
+                        labels: vec![label("trace_id", "abc")],
+                        value: 0.5,
+                        timestamp: 1395066367400,
+                    }],
+                    histograms: vec![],
+                },
+                proto::TimeSeries {
+                    labels: vec![label("__name__", "requests"), label("le", "1")],
+                    samples: vec![proto::Sample {
+                        value: 7.0,
+                        timestamp: 1395066367500,
+                    }],
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn interns_symbols() {
+        let request = v2::Request::from(write_request());
+
+        assert_eq!(
+            request.symbols,
+            vec![
+                "",
+                "The latency.",
+                "seconds",
+                "__name__",
+                "latency_bucket",
+                "le",
+                "1",
+                "trace_id",
+                "abc",
+                "requests",
+            ]
+        );
+        assert_eq!(request.timeseries[0].labels_refs, vec![3, 4, 5, 6]);
+        assert_eq!(request.timeseries[0].exemplars[0].labels_refs, vec![7, 8]);
+        assert_eq!(request.timeseries[1].labels_refs, vec![3, 9, 5, 6]);
+        assert_eq!(
+            request.timeseries[0].metadata,
+            Some(v2::Metadata {
+                r#type: v2::metadata::MetricType::Histogram as i32,
+                help_ref: 1,
+                unit_ref: 2,
+            })
+        );
+        assert_eq!(request.timeseries[1].metadata, None);
+    }
+
+    #[test]
+    fn converts_back_to_write_request() {
+        let request = proto::WriteRequest::try_from(v2::Request::from(write_request())).unwrap();
+
+        assert_eq!(request, write_request());
+    }
+
+    #[test]
+    fn rejects_invalid_references() {
+        let request = v2::Request {
+            symbols: vec!["".into(), "__name__".into()],
+            timeseries: vec![v2::TimeSeries {
+                labels_refs: vec![1, 2],
+                ..Default::default()
+            }],
+        };
+        assert_eq!(
+            proto::WriteRequest::try_from(request),
+            Err(ParserError::InvalidSymbolReference { reference: 2 })
+        );
+
+        let request = v2::Request {
+            symbols: vec!["".into(), "__name__".into()],
+            timeseries: vec![v2::TimeSeries {
+                labels_refs: vec![1],
+                ..Default::default()
+            }],
+        };
+        assert_eq!(
+            proto::WriteRequest::try_from(request),
+            Err(ParserError::OddLabelReferences)
+        );
+    }
+}
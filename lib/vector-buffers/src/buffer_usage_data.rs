@@ -11,7 +11,10 @@ use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
+    internal_events::{
+        BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent,
+        BufferRecordsCompressed,
+    },
     spawn_named,
 };
 
@@ -115,6 +118,32 @@ impl CategoryMetrics {
     }
 }
 
+/// Compression metrics.
+///
+/// For buffers which compress what they store, this tracks the size of what was given to the compressor, and the size
+/// of what was actually stored, so that the effectiveness of compression can be observed.
+#[derive(Debug, Default)]
+struct CompressionMetrics {
+    uncompressed_byte_size: AtomicU64,
+    compressed_byte_size: AtomicU64,
+}
+
+impl CompressionMetrics {
+    /// Increments the uncompressed and compressed byte sizes by the given amounts.
+    fn increment(&self, uncompressed_byte_size: u64, compressed_byte_size: u64) {
+        increment_counter(&self.uncompressed_byte_size, uncompressed_byte_size);
+        increment_counter(&self.compressed_byte_size, compressed_byte_size);
+    }
+
+    /// Gets a snapshot of the uncompressed and compressed byte sizes by "consuming" the values.
+    fn consume(&self) -> (u64, u64) {
+        (
+            self.uncompressed_byte_size.swap(0, ORDERING),
+            self.compressed_byte_size.swap(0, ORDERING),
+        )
+    }
+}
+
 /// Handle to buffer usage metrics for a specific buffer stage.
 #[derive(Clone, Debug)]
 pub struct BufferUsageHandle {
@@ -169,6 +198,21 @@ impl BufferUsageHandle {
         }
     }
 
+    /// Increments the number of bytes stored by this buffer component, both before and after compression.
+    ///
+    /// This is only relevant for buffer components which compress the events they store.
+    pub fn increment_compressed_byte_size(
+        &self,
+        uncompressed_byte_size: u64,
+        compressed_byte_size: u64,
+    ) {
+        if uncompressed_byte_size > 0 || compressed_byte_size > 0 {
+            self.state
+                .compression
+                .increment(uncompressed_byte_size, compressed_byte_size);
+        }
+    }
+
     /// Increment the number of dropped events (and their total size) for this buffer component.
     pub fn increment_dropped_event_count_and_byte_size(
         &self,
@@ -196,6 +240,7 @@ struct BufferUsageData {
     dropped_intentional: CategoryMetrics,
     max_size: CategoryMetrics,
     current: CategoryMetrics,
+    compression: CompressionMetrics,
 }
 
 impl BufferUsageData {
@@ -335,6 +380,17 @@ impl BufferUsage {
                         });
                     }
 
+                    let (uncompressed_byte_size, compressed_byte_size) =
+                        stage.compression.consume();
+                    if uncompressed_byte_size > 0 || compressed_byte_size > 0 {
+                        emit(BufferRecordsCompressed {
+                            buffer_id: buffer_id.clone(),
+                            idx: stage.idx,
+                            uncompressed_byte_size,
+                            compressed_byte_size,
+                        });
+                    }
+
                     let dropped = stage.dropped.consume();
                     if dropped.has_updates() {
                         emit(BufferEventsDropped {
@@ -3,6 +3,7 @@ use std::{
     num::{NonZeroU64, NonZeroUsize},
     path::{Path, PathBuf},
     slice,
+    time::Duration,
 };
 
 use serde::{Deserialize, Deserializer, Serialize, de};
@@ -17,7 +18,7 @@ use crate::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
     },
-    variants::{DiskV2Buffer, MemoryBuffer},
+    variants::{DiskV2Buffer, MemoryBuffer, SpillBuffer},
 };
 
 #[derive(Debug, Snafu)]
@@ -36,9 +37,18 @@ enum BufferTypeKind {
     Memory,
     #[serde(rename = "disk")]
     DiskV2,
+    #[serde(rename = "memory_spill")]
+    MemorySpill,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 6] = [
+    "type",
+    "max_events",
+    "max_size",
+    "when_full",
+    "compression",
+    "spill_after_ms",
+];
 
 struct BufferTypeVisitor;
 
@@ -51,6 +61,8 @@ impl BufferTypeVisitor {
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut compression: Option<DiskBufferCompression> = None;
+        let mut spill_after_ms: Option<u64> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -77,6 +89,18 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "compression" => {
+                    if compression.is_some() {
+                        return Err(de::Error::duplicate_field("compression"));
+                    }
+                    compression = Some(map.next_value()?);
+                }
+                "spill_after_ms" => {
+                    if spill_after_ms.is_some() {
+                        return Err(de::Error::duplicate_field("spill_after_ms"));
+                    }
+                    spill_after_ms = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -86,6 +110,18 @@ impl BufferTypeVisitor {
         let when_full = when_full.unwrap_or_default();
         match kind {
             BufferTypeKind::Memory => {
+                if compression.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "compression",
+                        &["type", "max_events", "max_size", "when_full"],
+                    ));
+                }
+                if spill_after_ms.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "spill_after_ms",
+                        &["type", "max_events", "max_size", "when_full"],
+                    ));
+                }
                 let size = match (max_events, max_size) {
                     (Some(_), Some(_)) => {
                         return Err(de::Error::unknown_field(
@@ -117,14 +153,28 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "when_full", "compression"],
+                    ));
+                }
+                if spill_after_ms.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "spill_after_ms",
+                        &["type", "max_size", "when_full", "compression"],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    compression: compression.unwrap_or_default(),
                 })
             }
+            BufferTypeKind::MemorySpill => Ok(BufferType::MemorySpill {
+                max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
+                max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
+                spill_after_ms: spill_after_ms.unwrap_or_else(memory_spill_default_spill_after_ms),
+                when_full,
+                compression: compression.unwrap_or_default(),
+            }),
         }
     }
 }
@@ -157,6 +207,10 @@ pub const fn memory_buffer_default_max_events() -> NonZeroUsize {
     unsafe { NonZeroUsize::new_unchecked(500) }
 }
 
+pub const fn memory_spill_default_spill_after_ms() -> u64 {
+    1000
+}
+
 /// Disk usage configuration for disk-backed buffers.
 #[derive(Debug)]
 pub struct DiskUsage {
@@ -191,6 +245,32 @@ impl DiskUsage {
     }
 }
 
+/// Compression applied to records written to a disk buffer.
+///
+/// Records are compressed individually, so compression is most effective when records contain
+/// many events, or events with repetitive content. Records written with a different compression
+/// setting, including records written by older versions of Vector, can always be read back.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskBufferCompression {
+    /// Records are stored uncompressed.
+    #[default]
+    None,
+
+    /// Records are compressed with [Zstandard][zstd].
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+
+    /// Records are compressed with [LZ4][lz4].
+    ///
+    /// This trades a lower compression ratio for lower CPU usage when compared to `zstd`.
+    ///
+    /// [lz4]: https://lz4.org/
+    Lz4,
+}
+
 /// Enumeration to define exactly what terms the bounds of the buffer is expressed in: length, or
 /// `byte_size`.
 #[configurable_component(no_deser)]
@@ -250,6 +330,57 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        /// The compression applied to each record before it is written to disk.
+        ///
+        /// The `max_size` limit applies to the compressed size of the records.
+        #[configurable(derived)]
+        #[serde(default)]
+        compression: DiskBufferCompression,
+    },
+
+    /// A buffer stage backed by an in-memory channel, which spills to disk under sustained
+    /// backpressure.
+    ///
+    /// Events are buffered in memory until the in-memory channel has stayed full for longer than
+    /// `spill_after_ms`. From then on, events are written to disk instead, and are read back in
+    /// order once the events buffered in memory have been drained. Events go back to being
+    /// buffered in memory once everything written to disk has been read back, so nothing is
+    /// written to disk for as long as the sink keeps up.
+    ///
+    /// Events buffered in memory will be lost if Vector is restarted forcefully or crashes, while
+    /// events that were spilled to disk are not.
+    #[configurable(title = "Events are buffered in memory, and spilled to disk when full.")]
+    #[serde(rename = "memory_spill")]
+    MemorySpill {
+        /// The maximum number of events buffered in memory before spilling to disk.
+        #[serde(default = "memory_buffer_default_max_events")]
+        max_events: NonZeroUsize,
+
+        /// The maximum size of the events spilled to disk.
+        ///
+        /// Must be at least ~256 megabytes (268435488 bytes).
+        #[configurable(
+            validation(range(min = 268435488)),
+            metadata(docs::type_unit = "bytes")
+        )]
+        max_size: NonZeroU64,
+
+        /// How long the in-memory channel must stay full before events are spilled to disk.
+        #[serde(default = "memory_spill_default_spill_after_ms")]
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        spill_after_ms: u64,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        when_full: WhenFull,
+
+        /// The compression applied to each record before it is spilled to disk.
+        ///
+        /// The `max_size` limit applies to the compressed size of the records.
+        #[configurable(derived)]
+        #[serde(default)]
+        compression: DiskBufferCompression,
     },
 }
 
@@ -287,6 +418,12 @@ impl BufferType {
                         id.id(),
                     );
 
+                    Some(DiskUsage::new(id.clone(), data_dir, *max_size))
+                }
+                Self::MemorySpill { max_size, .. } => {
+                    let data_dir =
+                        crate::variants::spill::get_spill_data_dir_path(&global_data_dir, id.id());
+
                     Some(DiskUsage::new(id.clone(), data_dir, *max_size))
                 }
             },
@@ -315,9 +452,33 @@ impl BufferType {
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                compression,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                builder.stage(
+                    DiskV2Buffer::new(id, data_dir, max_size, compression),
+                    when_full,
+                );
+            }
+            BufferType::MemorySpill {
+                max_events,
+                max_size,
+                spill_after_ms,
+                when_full,
+                compression,
+            } => {
+                let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
+                builder.stage(
+                    SpillBuffer::new(
+                        id,
+                        data_dir,
+                        max_events,
+                        max_size,
+                        Duration::from_millis(spill_after_ms),
+                        compression,
+                    ),
+                    when_full,
+                );
             }
         }
 
@@ -418,7 +579,7 @@ impl BufferConfig {
 mod test {
     use std::num::{NonZeroU64, NonZeroUsize};
 
-    use crate::{BufferConfig, BufferType, MemoryBufferSize, WhenFull};
+    use crate::{BufferConfig, BufferType, DiskBufferCompression, MemoryBufferSize, WhenFull};
 
     fn check_single_stage(source: &str, expected: BufferType) {
         let config: BufferConfig = serde_yaml::from_str(source).unwrap();
@@ -561,7 +722,67 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                compression: DiskBufferCompression::None,
+            },
+        );
+
+        check_single_stage(
+            r"
+          type: disk
+          max_size: 1024
+          compression: zstd
+          ",
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::Zstd,
+            },
+        );
+
+        check_single_stage(
+            r"
+          type: memory_spill
+          max_size: 1024
+          ",
+            BufferType::MemorySpill {
+                max_events: NonZeroUsize::new(500).unwrap(),
+                max_size: NonZeroU64::new(1024).unwrap(),
+                spill_after_ms: 1000,
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::None,
             },
         );
     }
+
+    #[test]
+    fn parse_memory_spill() {
+        check_single_stage(
+            r"
+          type: memory_spill
+          max_events: 100
+          max_size: 1024
+          spill_after_ms: 250
+          when_full: drop_newest
+          compression: lz4
+          ",
+            BufferType::MemorySpill {
+                max_events: NonZeroUsize::new(100).unwrap(),
+                max_size: NonZeroU64::new(1024).unwrap(),
+                spill_after_ms: 250,
+                when_full: WhenFull::DropNewest,
+                compression: DiskBufferCompression::Lz4,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_spill_after_only_for_memory_spill() {
+        let source = r"
+          type: disk
+          max_size: 1024
+          spill_after_ms: 250
+          ";
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+    }
 }
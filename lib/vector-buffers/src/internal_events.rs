@@ -114,6 +114,30 @@ impl InternalEvent for BufferEventsSent {
     }
 }
 
+pub struct BufferRecordsCompressed {
+    pub buffer_id: String,
+    pub idx: usize,
+    pub uncompressed_byte_size: u64,
+    pub compressed_byte_size: u64,
+}
+
+impl InternalEvent for BufferRecordsCompressed {
+    fn emit(self) {
+        counter!(
+            "buffer_uncompressed_bytes_total",
+            "buffer_id" => self.buffer_id.clone(),
+            "stage" => self.idx.to_string()
+        )
+        .increment(self.uncompressed_byte_size);
+        counter!(
+            "buffer_compressed_bytes_total",
+            "buffer_id" => self.buffer_id,
+            "stage" => self.idx.to_string()
+        )
+        .increment(self.compressed_byte_size);
+    }
+}
+
 pub struct BufferEventsDropped {
     pub buffer_id: String,
     pub idx: usize,
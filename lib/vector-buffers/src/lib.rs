@@ -17,7 +17,7 @@ extern crate tracing;
 mod buffer_usage_data;
 
 pub mod config;
-pub use config::{BufferConfig, BufferType, MemoryBufferSize};
+pub use config::{BufferConfig, BufferType, DiskBufferCompression, MemoryBufferSize};
 use encoding::Encodable;
 use vector_config::configurable_component;
 
@@ -32,6 +32,10 @@ pub mod test;
 pub mod topology;
 
 pub(crate) mod variants;
+pub use variants::disk_v2::{
+    BufferInspector, BufferRepair, DataFileReport, InspectError, InspectedRecord, LedgerPositions,
+    ReaderError, RepairError, RepairReport,
+};
 
 use std::fmt::Debug;
 
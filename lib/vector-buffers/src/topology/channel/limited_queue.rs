@@ -6,6 +6,7 @@ use std::{
         Arc,
         atomic::{AtomicUsize, Ordering},
     },
+    time::Duration,
 };
 
 use async_stream::stream;
@@ -195,6 +196,43 @@ impl<T: InMemoryBufferable> LimitedSender<T> {
 
         Ok(())
     }
+
+    /// Sends an item into the channel, waiting at most `timeout` for enough capacity to be
+    /// available.
+    ///
+    /// # Errors
+    ///
+    /// If the receiver has disconnected (does not exist anymore), then
+    /// `Err(TrySendError::Disconnected)` be returned with the given `item`. If the channel did not
+    /// have sufficient capacity for the item before the timeout elapsed, then
+    /// `Err(TrySendError::InsufficientCapacity)` will be returned with the given `item`.
+    pub async fn send_timeout(
+        &mut self,
+        item: T,
+        timeout: Duration,
+    ) -> Result<(), TrySendError<T>> {
+        // Calculate how many permits we need, and wait until we can acquire all of them, or until
+        // we time out. We acquire the permits separately from pushing the item so that the item can
+        // be handed back if we time out.
+        let permits_required = self.get_required_permits_for_item(&item);
+        let acquire = self
+            .inner
+            .limiter
+            .clone()
+            .acquire_many_owned(permits_required);
+        let permits = match tokio::time::timeout(timeout, acquire).await {
+            Ok(Ok(permits)) => permits,
+            Ok(Err(_)) => return Err(TrySendError::Disconnected(item)),
+            Err(_) => return Err(TrySendError::InsufficientCapacity(item)),
+        };
+
+        self.inner.data.push((permits, item));
+        self.inner.read_waker.notify_one();
+
+        trace!("Sent item before timing out.");
+
+        Ok(())
+    }
 }
 
 impl<T> Clone for LimitedSender<T> {
@@ -229,6 +267,13 @@ impl<T: Send + 'static> LimitedReceiver<T> {
         self.inner.limiter.available_permits()
     }
 
+    /// Attempts to receive an item from the channel without waiting.
+    ///
+    /// Returns `None` if the channel is currently empty, whether or not it is closed.
+    pub fn try_next(&mut self) -> Option<T> {
+        self.inner.data.pop().map(|(_permit, item)| item)
+    }
+
     pub async fn next(&mut self) -> Option<T> {
         loop {
             if let Some((_permit, item)) = self.inner.data.pop() {
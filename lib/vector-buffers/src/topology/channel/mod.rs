@@ -1,10 +1,13 @@
 mod limited_queue;
 mod receiver;
 mod sender;
+mod spill;
 
 pub use limited_queue::{LimitedReceiver, LimitedSender, SendError, limited};
 pub use receiver::*;
 pub use sender::*;
+pub(crate) use spill::spill_channel;
+pub use spill::{SpillReceiver, SpillSender};
 
 #[cfg(test)]
 mod tests;
@@ -10,7 +10,7 @@ use tokio::select;
 use tokio_util::sync::ReusableBoxFuture;
 use vector_common::internal_event::emit;
 
-use super::limited_queue::LimitedReceiver;
+use super::{limited_queue::LimitedReceiver, spill::SpillReceiver};
 use crate::{
     Bufferable,
     buffer_usage_data::BufferUsageHandle,
@@ -26,6 +26,9 @@ pub enum ReceiverAdapter<T: Bufferable> {
 
     /// The disk v2 buffer.
     DiskV2(disk_v2::BufferReader<T, ProductionFilesystem>),
+
+    /// The in-memory channel buffer which spills to a disk v2 buffer.
+    Spill(SpillReceiver<T>),
 }
 
 impl<T: Bufferable> From<LimitedReceiver<T>> for ReceiverAdapter<T> {
@@ -40,6 +43,12 @@ impl<T: Bufferable> From<disk_v2::BufferReader<T, ProductionFilesystem>> for Rec
     }
 }
 
+impl<T: Bufferable> From<SpillReceiver<T>> for ReceiverAdapter<T> {
+    fn from(v: SpillReceiver<T>) -> Self {
+        Self::Spill(v)
+    }
+}
+
 impl<T> ReceiverAdapter<T>
 where
     T: Bufferable,
@@ -47,18 +56,25 @@ where
     pub(crate) async fn next(&mut self) -> Option<T> {
         match self {
             ReceiverAdapter::InMemory(rx) => rx.next().await,
-            ReceiverAdapter::DiskV2(reader) => loop {
-                match reader.next().await {
-                    Ok(result) => break result,
-                    Err(e) => match e.as_recoverable_error() {
-                        Some(re) => {
-                            // If we've hit a recoverable error, we'll emit an event to indicate as much but we'll still
-                            // keep trying to read the next available record.
-                            emit(re);
-                        }
-                        None => panic!("Reader encountered unrecoverable error: {e:?}"),
-                    },
+            ReceiverAdapter::DiskV2(reader) => next_disk_v2_record(reader).await,
+            ReceiverAdapter::Spill(rx) => rx.next().await,
+        }
+    }
+}
+
+pub(super) async fn next_disk_v2_record<T: Bufferable>(
+    reader: &mut disk_v2::BufferReader<T, ProductionFilesystem>,
+) -> Option<T> {
+    loop {
+        match reader.next().await {
+            Ok(result) => break result,
+            Err(e) => match e.as_recoverable_error() {
+                Some(re) => {
+                    // If we've hit a recoverable error, we'll emit an event to indicate as much but we'll still
+                    // keep trying to read the next available record.
+                    emit(re);
                 }
+                None => panic!("Reader encountered unrecoverable error: {e:?}"),
             },
         }
     }
@@ -6,7 +6,7 @@ use tokio::sync::Mutex;
 use tracing::Span;
 use vector_common::internal_event::{InternalEventHandle, Registered, register};
 
-use super::limited_queue::LimitedSender;
+use super::{limited_queue::LimitedSender, spill::SpillSender};
 use crate::{
     Bufferable, WhenFull,
     buffer_usage_data::BufferUsageHandle,
@@ -22,6 +22,9 @@ pub enum SenderAdapter<T: Bufferable> {
 
     /// The disk v2 buffer.
     DiskV2(Arc<Mutex<disk_v2::BufferWriter<T, ProductionFilesystem>>>),
+
+    /// The in-memory channel buffer which spills to a disk v2 buffer.
+    Spill(SpillSender<T>),
 }
 
 impl<T: Bufferable> From<LimitedSender<T>> for SenderAdapter<T> {
@@ -36,6 +39,12 @@ impl<T: Bufferable> From<disk_v2::BufferWriter<T, ProductionFilesystem>> for Sen
     }
 }
 
+impl<T: Bufferable> From<SpillSender<T>> for SenderAdapter<T> {
+    fn from(v: SpillSender<T>) -> Self {
+        Self::Spill(v)
+    }
+}
+
 impl<T> SenderAdapter<T>
 where
     T: Bufferable,
@@ -57,6 +66,7 @@ where
                     e.into()
                 })
             }
+            Self::Spill(tx) => tx.send(item).await,
         }
     }
 
@@ -80,6 +90,7 @@ where
                     e.into()
                 })
             }
+            Self::Spill(tx) => tx.try_send(item).await,
         }
     }
 
@@ -95,6 +106,7 @@ where
                     e.into()
                 })
             }
+            Self::Spill(tx) => tx.flush().await,
         }
     }
 
@@ -102,6 +114,7 @@ where
         match self {
             Self::InMemory(tx) => Some(tx.available_capacity()),
             Self::DiskV2(_) => None,
+            Self::Spill(tx) => Some(tx.capacity()),
         }
     }
 }
@@ -0,0 +1,405 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::{
+    select,
+    sync::{Mutex, Notify},
+};
+
+use super::{
+    limited_queue::{LimitedReceiver, LimitedSender, SendError, TrySendError},
+    receiver::next_disk_v2_record,
+};
+use crate::{
+    Bufferable,
+    buffer_usage_data::BufferUsageHandle,
+    variants::disk_v2::{self, ProductionFilesystem},
+};
+
+/// State shared between the sender and receiver of a spilling channel.
+#[derive(Debug, Default)]
+struct SpillState {
+    /// The number of events which have been written to disk, but not yet read back.
+    spilled_events: AtomicU64,
+
+    /// Whether or not records have been written to disk since the writer was last flushed.
+    unflushed: AtomicBool,
+
+    /// Notified whenever events are written to disk.
+    spill_waker: Notify,
+}
+
+impl SpillState {
+    fn is_spilling(&self) -> bool {
+        self.spilled_events.load(Ordering::Acquire) > 0
+    }
+
+    fn track_spilled(&self, event_count: u64) {
+        self.spilled_events.fetch_add(event_count, Ordering::AcqRel);
+        self.unflushed.store(true, Ordering::Release);
+        self.spill_waker.notify_one();
+    }
+
+    fn track_unspilled(&self, event_count: u64) {
+        _ = self
+            .spilled_events
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                Some(n.saturating_sub(event_count))
+            });
+    }
+
+    fn reset(&self) {
+        self.spilled_events.store(0, Ordering::Release);
+    }
+}
+
+/// Creates a spilling channel from an in-memory channel and a disk buffer.
+///
+/// Items are sent to the in-memory channel for as long as it has capacity. Once it has been full
+/// for longer than `spill_after`, items are written to the disk buffer instead, and all subsequent
+/// items follow them there until every spilled item has been read back. Since items only spill to
+/// disk when the in-memory channel is full, the receiver drains the in-memory channel before
+/// reading the spilled items, which keeps items in the order they were sent.
+///
+/// `spilled_events` is the number of events already stored in the disk buffer, such as those left
+/// behind by a previous process, which are read back before any new items.
+pub(crate) fn spill_channel<T: Bufferable>(
+    memory_tx: LimitedSender<T>,
+    memory_rx: LimitedReceiver<T>,
+    writer: disk_v2::BufferWriter<T, ProductionFilesystem>,
+    reader: disk_v2::BufferReader<T, ProductionFilesystem>,
+    spilled_events: u64,
+    spill_after: Duration,
+    usage_handle: BufferUsageHandle,
+) -> (SpillSender<T>, SpillReceiver<T>) {
+    let state = Arc::new(SpillState {
+        spilled_events: AtomicU64::new(spilled_events),
+        ..Default::default()
+    });
+
+    let sender = SpillSender {
+        memory: memory_tx,
+        disk: Arc::new(Mutex::new(writer)),
+        state: Arc::clone(&state),
+        spill_after,
+        usage_handle: usage_handle.clone(),
+    };
+    let receiver = SpillReceiver {
+        memory: memory_rx,
+        disk: reader,
+        state,
+        usage_handle,
+    };
+
+    (sender, receiver)
+}
+
+/// The sending side of a spilling channel.
+#[derive(Clone, Debug)]
+pub struct SpillSender<T: Bufferable> {
+    memory: LimitedSender<T>,
+    disk: Arc<Mutex<disk_v2::BufferWriter<T, ProductionFilesystem>>>,
+    state: Arc<SpillState>,
+    spill_after: Duration,
+    usage_handle: BufferUsageHandle,
+}
+
+impl<T: Bufferable> SpillSender<T> {
+    /// Sends an item to the in-memory channel, waiting up to `spill_after` for capacity.
+    ///
+    /// If the item must be spilled to disk instead, it is handed back.
+    async fn send_to_memory(&mut self, item: T) -> crate::Result<Option<T>> {
+        // Once events have spilled to disk, new events must follow them there until they've all
+        // been read back, otherwise they would be read out of order.
+        if self.state.is_spilling() {
+            return Ok(Some(item));
+        }
+
+        let event_count = item.event_count() as u64;
+        let byte_size = item.size_of() as u64;
+        match self.memory.send_timeout(item, self.spill_after).await {
+            Ok(()) => {
+                self.usage_handle
+                    .increment_received_event_count_and_byte_size(event_count, byte_size);
+                Ok(None)
+            }
+            Err(TrySendError::InsufficientCapacity(item)) => Ok(Some(item)),
+            Err(TrySendError::Disconnected(item)) => Err(SendError(item).into()),
+        }
+    }
+
+    pub(crate) async fn send(&mut self, item: T) -> crate::Result<()> {
+        let Some(item) = self.send_to_memory(item).await? else {
+            return Ok(());
+        };
+
+        let event_count = item.event_count() as u64;
+        let mut writer = self.disk.lock().await;
+        writer.write_record(item).await.inspect_err(|_| {
+            error!("Disk buffer writer has encountered an unrecoverable error.");
+        })?;
+
+        // We only track the spilled events once they've been written, while still holding the
+        // writer lock, so that the receiver never waits on a record that was not written.
+        self.state.track_spilled(event_count);
+
+        Ok(())
+    }
+
+    pub(crate) async fn try_send(&mut self, item: T) -> crate::Result<Option<T>> {
+        let Some(item) = self.send_to_memory(item).await? else {
+            return Ok(None);
+        };
+
+        let event_count = item.event_count() as u64;
+        let mut writer = self.disk.lock().await;
+        let result = writer.try_write_record(item).await.inspect_err(|_| {
+            error!("Disk buffer writer has encountered an unrecoverable error.");
+        })?;
+
+        if result.is_none() {
+            self.state.track_spilled(event_count);
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) async fn flush(&mut self) -> crate::Result<()> {
+        // Avoid touching the disk at all unless something was actually spilled.
+        if !self.state.unflushed.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+
+        let mut writer = self.disk.lock().await;
+        writer.flush().await.map_err(|e| {
+            // Errors on the I/O path, which is all that flushing touches, are never recoverable.
+            error!("Disk buffer writer has encountered an unrecoverable error.");
+
+            e.into()
+        })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.memory.available_capacity()
+    }
+}
+
+/// The receiving side of a spilling channel.
+#[derive(Debug)]
+pub struct SpillReceiver<T: Bufferable> {
+    memory: LimitedReceiver<T>,
+    disk: disk_v2::BufferReader<T, ProductionFilesystem>,
+    state: Arc<SpillState>,
+    usage_handle: BufferUsageHandle,
+}
+
+impl<T: Bufferable> SpillReceiver<T> {
+    pub(crate) async fn next(&mut self) -> Option<T> {
+        loop {
+            // Events only spill to disk once the in-memory channel is full, so anything left in the
+            // in-memory channel is older than the spilled events, and has to be read first.
+            if let Some(item) = self.memory.try_next() {
+                return Some(self.track_memory_read(item));
+            }
+
+            if self.state.is_spilling() {
+                match next_disk_v2_record(&mut self.disk).await {
+                    Some(item) => {
+                        self.state.track_unspilled(item.event_count() as u64);
+                        return Some(item);
+                    }
+                    // The writer has been closed, and everything written to disk has been read, so
+                    // there is nothing left to wait for on that side.
+                    None => self.state.reset(),
+                }
+                continue;
+            }
+
+            select! {
+                item = self.memory.next() => match item {
+                    Some(item) => return Some(self.track_memory_read(item)),
+                    None if self.state.is_spilling() => {}
+                    None => return None,
+                },
+                () = self.state.spill_waker.notified() => {}
+            }
+        }
+    }
+
+    fn track_memory_read(&self, item: T) -> T {
+        self.usage_handle.increment_sent_event_count_and_byte_size(
+            item.event_count() as u64,
+            item.size_of() as u64,
+        );
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroUsize, path::Path, time::Duration};
+
+    use vector_common::finalization::Finalizable;
+
+    use super::{SpillReceiver, SpillSender, spill_channel};
+    use crate::{
+        buffer_usage_data::BufferUsageHandle,
+        config::MemoryBufferSize,
+        test::{SizedRecord, acknowledge, with_temp_dir},
+        topology::channel::limited,
+        variants::disk_v2::{Buffer, DiskBufferConfigBuilder},
+    };
+
+    async fn create_spill_channel(
+        data_dir: &Path,
+        max_events: usize,
+        spill_after: Duration,
+    ) -> (SpillSender<SizedRecord>, SpillReceiver<SizedRecord>) {
+        let config = DiskBufferConfigBuilder::from_path(data_dir)
+            .build()
+            .expect("creating buffer should not fail");
+        let usage_handle = BufferUsageHandle::noop();
+        let (writer, reader, ledger) = Buffer::from_config_inner(config, usage_handle.clone())
+            .await
+            .expect("should not fail to create buffer");
+        let (tx, rx) = limited(MemoryBufferSize::MaxEvents(
+            NonZeroUsize::new(max_events).unwrap(),
+        ));
+
+        spill_channel(
+            tx,
+            rx,
+            writer,
+            reader,
+            ledger.get_total_records(),
+            spill_after,
+            usage_handle,
+        )
+    }
+
+    async fn read_all(mut receiver: SpillReceiver<SizedRecord>) -> Vec<SizedRecord> {
+        let mut items = Vec::new();
+        while let Some(mut item) = receiver.next().await {
+            acknowledge(item.take_finalizers()).await;
+            items.push(item);
+        }
+        items
+    }
+
+    #[tokio::test]
+    async fn does_not_spill_while_memory_has_capacity() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                let (mut sender, receiver) =
+                    create_spill_channel(&data_dir, 4, Duration::from_millis(10)).await;
+
+                let expected = (1..=4).map(SizedRecord::new).collect::<Vec<_>>();
+                for item in expected.clone() {
+                    sender.send(item).await.expect("send should not fail");
+                    sender.flush().await.expect("flush should not fail");
+                }
+                assert!(!sender.state.is_spilling());
+                assert_eq!(sender.capacity(), 0);
+
+                drop(sender);
+                assert_eq!(read_all(receiver).await, expected);
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn spills_to_disk_and_drains_in_order() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                let (mut sender, mut receiver) =
+                    create_spill_channel(&data_dir, 2, Duration::from_millis(10)).await;
+
+                // The first two items fit in memory, and the rest have to spill to disk.
+                let expected = (1..=6).map(SizedRecord::new).collect::<Vec<_>>();
+                for item in expected.iter().take(4).cloned() {
+                    sender.send(item).await.expect("send should not fail");
+                    sender.flush().await.expect("flush should not fail");
+                }
+                assert!(sender.state.is_spilling());
+                assert_eq!(sender.capacity(), 0);
+
+                // Reading frees up memory, but new items have to keep going to disk while there
+                // are still spilled items waiting to be read.
+                let mut actual = Vec::new();
+                for _ in 0..3 {
+                    let mut item = receiver.next().await.expect("item should be present");
+                    acknowledge(item.take_finalizers()).await;
+                    actual.push(item);
+                }
+                sender
+                    .send(expected[4].clone())
+                    .await
+                    .expect("send should not fail");
+                sender.flush().await.expect("flush should not fail");
+                assert_eq!(sender.capacity(), 2);
+
+                // Once everything spilled has been read back, items go back to memory.
+                for _ in 0..2 {
+                    let mut item = receiver.next().await.expect("item should be present");
+                    acknowledge(item.take_finalizers()).await;
+                    actual.push(item);
+                }
+                assert!(!receiver.state.is_spilling());
+                sender
+                    .send(expected[5].clone())
+                    .await
+                    .expect("send should not fail");
+                assert_eq!(sender.capacity(), 1);
+
+                drop(sender);
+                actual.extend(read_all(receiver).await);
+                assert_eq!(actual, expected);
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn reads_back_events_spilled_before_restart() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                let (mut sender, receiver) =
+                    create_spill_channel(&data_dir, 1, Duration::from_millis(10)).await;
+                for item in (1..=3).map(SizedRecord::new) {
+                    sender.send(item).await.expect("send should not fail");
+                    sender.flush().await.expect("flush should not fail");
+                }
+                drop(sender);
+                drop(receiver);
+
+                // The item held in memory is lost, but the spilled items must be read back before
+                // anything sent after the restart.
+                let (mut sender, receiver) =
+                    create_spill_channel(&data_dir, 1, Duration::from_millis(10)).await;
+                assert!(sender.state.is_spilling());
+                sender
+                    .send(SizedRecord::new(4))
+                    .await
+                    .expect("send should not fail");
+                sender.flush().await.expect("flush should not fail");
+
+                drop(sender);
+                let expected = (2..=4).map(SizedRecord::new).collect::<Vec<_>>();
+                assert_eq!(read_all(receiver).await, expected);
+            }
+        })
+        .await;
+    }
+}
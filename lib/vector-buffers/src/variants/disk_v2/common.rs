@@ -11,6 +11,7 @@ use super::{
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
 };
+use crate::config::DiskBufferCompression;
 
 // We don't want data files to be bigger than 128MB, but we might end up overshooting slightly.
 pub const DEFAULT_MAX_DATA_FILE_SIZE: usize = 128 * 1024 * 1024;
@@ -153,6 +154,12 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Compression applied to the payload of each record written.
+    ///
+    /// Only affects writes: records are always read back using the compression they were
+    /// written with.
+    pub(crate) compression: DiskBufferCompression,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -173,6 +180,7 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) compression: Option<DiskBufferCompression>,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +196,7 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            compression: None,
             filesystem: ProductionFilesystem,
         }
     }
@@ -268,6 +277,17 @@ where
         self
     }
 
+    /// Sets the compression applied to the payload of each record written.
+    ///
+    /// Records are always read back using the compression they were written with, so this can be
+    /// changed for an existing buffer.
+    ///
+    /// Defaults to no compression.
+    pub fn compression(mut self, compression: DiskBufferCompression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -287,6 +307,7 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            compression: self.compression,
             filesystem,
         }
     }
@@ -301,6 +322,7 @@ where
         let max_record_size = self.max_record_size.unwrap_or(DEFAULT_MAX_RECORD_SIZE);
         let write_buffer_size = self.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE);
         let flush_interval = self.flush_interval.unwrap_or(DEFAULT_FLUSH_INTERVAL);
+        let compression = self.compression.unwrap_or_default();
         let filesystem = self.filesystem;
 
         // Validate the input parameters.
@@ -412,6 +434,7 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            compression,
             filesystem,
         })
     }
@@ -0,0 +1,142 @@
+//! Per-record payload compression.
+//!
+//! Compression is applied to the encoded payload of a record, before it is checksummed and
+//! archived.  The algorithm used for a given record is stored in the upper bits of the record
+//! metadata, which are never used by `Encodable::Metadata` implementations, so that records
+//! written without compression -- including every record written before compression existed --
+//! are read back exactly as they always were.
+//!
+//! As the algorithm is tracked per record, the compression setting of a buffer can be changed
+//! freely between restarts: records already on disk are always decompressed with the algorithm
+//! they were written with.
+
+use std::io;
+
+use crate::config::DiskBufferCompression;
+
+/// Number of bits the compression algorithm is shifted by within the record metadata.
+const COMPRESSION_SHIFT: u32 = 28;
+
+/// Mask of the record metadata bits reserved for the compression algorithm.
+const COMPRESSION_MASK: u32 = 0b1111 << COMPRESSION_SHIFT;
+
+const COMPRESSION_NONE: u32 = 0;
+const COMPRESSION_ZSTD: u32 = 1;
+const COMPRESSION_LZ4: u32 = 2;
+
+/// Compression level used for zstd.
+///
+/// Buffers sit in the hot path of a sink, so we favor throughput over compression ratio.
+const ZSTD_LEVEL: i32 = 1;
+
+/// Splits raw record metadata into the `Encodable` metadata and the compression used for the
+/// record payload.
+///
+/// If the compression bits do not represent a known algorithm, `None` is returned.
+pub(super) fn split_metadata(metadata: u32) -> Option<(u32, DiskBufferCompression)> {
+    let compression = match (metadata & COMPRESSION_MASK) >> COMPRESSION_SHIFT {
+        COMPRESSION_NONE => DiskBufferCompression::None,
+        COMPRESSION_ZSTD => DiskBufferCompression::Zstd,
+        COMPRESSION_LZ4 => DiskBufferCompression::Lz4,
+        _ => return None,
+    };
+
+    Some((metadata & !COMPRESSION_MASK, compression))
+}
+
+/// Combines `Encodable` metadata with the compression used for the record payload.
+pub(super) fn combine_metadata(metadata: u32, compression: DiskBufferCompression) -> u32 {
+    debug_assert_eq!(
+        metadata & COMPRESSION_MASK,
+        0,
+        "encodable metadata must not use the bits reserved for compression"
+    );
+
+    let bits = match compression {
+        DiskBufferCompression::None => COMPRESSION_NONE,
+        DiskBufferCompression::Zstd => COMPRESSION_ZSTD,
+        DiskBufferCompression::Lz4 => COMPRESSION_LZ4,
+    };
+
+    metadata | (bits << COMPRESSION_SHIFT)
+}
+
+/// Compresses `input` with the given algorithm, replacing the contents of `output`.
+///
+/// # Errors
+///
+/// If the compressor encounters an error, an error variant will be returned describing the error.
+pub(super) fn compress(
+    compression: DiskBufferCompression,
+    input: &[u8],
+    output: &mut Vec<u8>,
+) -> io::Result<()> {
+    output.clear();
+    match compression {
+        DiskBufferCompression::None => output.extend_from_slice(input),
+        DiskBufferCompression::Zstd => zstd::stream::copy_encode(input, &mut *output, ZSTD_LEVEL)?,
+        DiskBufferCompression::Lz4 => output.extend(lz4_flex::compress_prepend_size(input)),
+    }
+
+    Ok(())
+}
+
+/// Decompresses `input` with the given algorithm.
+///
+/// # Errors
+///
+/// If the payload is not valid for the given algorithm, an error variant will be returned
+/// describing the error.
+pub(super) fn decompress(compression: DiskBufferCompression, input: &[u8]) -> io::Result<Vec<u8>> {
+    match compression {
+        DiskBufferCompression::None => Ok(input.to_vec()),
+        DiskBufferCompression::Zstd => zstd::stream::decode_all(input),
+        DiskBufferCompression::Lz4 => lz4_flex::decompress_size_prepended(input)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [DiskBufferCompression; 3] = [
+        DiskBufferCompression::None,
+        DiskBufferCompression::Zstd,
+        DiskBufferCompression::Lz4,
+    ];
+
+    #[test]
+    fn metadata_roundtrip() {
+        for compression in ALL {
+            let combined = combine_metadata(0b1, compression);
+            assert_eq!(split_metadata(combined), Some((0b1, compression)));
+        }
+    }
+
+    #[test]
+    fn legacy_metadata_is_uncompressed() {
+        assert_eq!(
+            split_metadata(0b1),
+            Some((0b1, DiskBufferCompression::None))
+        );
+    }
+
+    #[test]
+    fn unknown_compression_bits() {
+        assert_eq!(split_metadata(0b1111 << COMPRESSION_SHIFT), None);
+    }
+
+    #[test]
+    fn payload_roundtrip() {
+        let input = b"hello world, hello world, hello world, hello world".repeat(16);
+        let mut output = Vec::new();
+
+        for compression in ALL {
+            compress(compression, &input, &mut output).expect("compression should not fail");
+            let decompressed =
+                decompress(compression, &output).expect("decompression should not fail");
+            assert_eq!(decompressed, input);
+        }
+    }
+}
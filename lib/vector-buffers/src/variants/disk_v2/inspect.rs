@@ -0,0 +1,221 @@
+use std::{
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use rkyv::AlignedVec;
+use snafu::{ResultExt, Snafu};
+use tokio::fs::File;
+
+use super::{
+    common::MAX_FILE_ID,
+    ledger::LedgerState,
+    reader::{ReaderError, RecordReader},
+    ser::try_as_archive,
+};
+use crate::Bufferable;
+
+/// Error that occurred when opening a disk buffer for inspection.
+#[derive(Debug, Snafu)]
+pub enum InspectError {
+    /// The ledger file could not be read.
+    #[snafu(display("failed to read ledger at {}: {}", path.display(), source))]
+    ReadLedger { path: PathBuf, source: io::Error },
+
+    /// The ledger state was unable to be deserialized.
+    #[snafu(display("failed to deserialize ledger: {}", reason))]
+    DeserializeLedger { reason: String },
+}
+
+/// The positions of the reader and writer of a disk buffer, as last recorded in its ledger.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LedgerPositions {
+    /// The ID of the next record the writer will write.
+    pub writer_next_record_id: u64,
+    /// The ID of the data file the writer is writing to.
+    pub writer_current_data_file_id: u16,
+    /// The ID of the oldest data file that has not been deleted by the reader.
+    pub reader_current_data_file_id: u16,
+    /// The ID of the last record acknowledged by the reader.
+    pub reader_last_record_id: u64,
+}
+
+impl LedgerPositions {
+    /// Gets the number of events written to the buffer, but not yet acknowledged by the reader.
+    pub fn unread_events(&self) -> u64 {
+        self.writer_next_record_id
+            .wrapping_sub(self.reader_last_record_id)
+            .saturating_sub(1)
+    }
+}
+
+/// A record read from a data file by a [`BufferInspector`].
+#[derive(Debug)]
+pub struct InspectedRecord<T: Bufferable> {
+    /// The ID of the data file holding the record.
+    pub data_file_id: u16,
+    /// The ID of the record, which is also the ID of its first event.
+    pub record_id: u64,
+    /// The size of the record in its data file, in bytes.
+    pub size: u64,
+    /// The decoded record, or the error that prevented decoding it.
+    pub value: Result<T, ReaderError<T>>,
+}
+
+/// Reads the records of a disk buffer without modifying it.
+///
+/// Unlike [`BufferReader`][super::BufferReader], the inspector neither locks the buffer nor
+/// updates its ledger, and never deletes data files, so it can read the buffer of a running Vector
+/// process. Records already acknowledged by the reader of the buffer are skipped.
+///
+/// When a record is corrupted, the rest of its data file is skipped, as the boundaries of the
+/// following records can't be trusted anymore.
+pub struct BufferInspector<T: Bufferable> {
+    data_dir: PathBuf,
+    ledger: LedgerPositions,
+    next_data_file_id: Option<u16>,
+    current: Option<(u16, RecordReader<File, T>)>,
+}
+
+impl<T: Bufferable> BufferInspector<T> {
+    /// Opens the disk buffer stored in `data_dir` for inspection.
+    ///
+    /// # Errors
+    ///
+    /// If the ledger of the buffer can't be read or deserialized, an error variant will be
+    /// returned describing the error.
+    pub async fn open(data_dir: impl Into<PathBuf>) -> Result<Self, InspectError> {
+        let data_dir = data_dir.into();
+        let ledger = read_ledger(&data_dir).await?;
+
+        Ok(Self::with_positions(data_dir, ledger))
+    }
+
+    /// Opens the disk buffer stored in `data_dir` for inspection, using the given positions
+    /// instead of the ones of its ledger.
+    ///
+    /// This allows reading a buffer whose ledger is lost or corrupted, with positions rebuilt
+    /// from its data files by [`BufferRepair`][super::BufferRepair].
+    pub fn with_positions(data_dir: impl Into<PathBuf>, ledger: LedgerPositions) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+            next_data_file_id: Some(ledger.reader_current_data_file_id),
+            ledger,
+            current: None,
+        }
+    }
+
+    /// Gets the positions of the reader and writer recorded in the ledger when the buffer was
+    /// opened.
+    pub fn ledger(&self) -> &LedgerPositions {
+        &self.ledger
+    }
+
+    /// Gets the path of a data file of the buffer.
+    pub fn data_file_path(&self, data_file_id: u16) -> PathBuf {
+        self.data_dir
+            .join(format!("buffer-data-{data_file_id}.dat"))
+    }
+
+    /// Reads the next record of the buffer.
+    ///
+    /// Returns `None` once the last record written to the buffer has been read.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while reading a data file, it is returned as is. Errors affecting a
+    /// single record are instead returned as part of the [`InspectedRecord`].
+    pub async fn next(&mut self) -> io::Result<Option<InspectedRecord<T>>> {
+        loop {
+            let Some((data_file_id, reader)) = self.current.as_mut() else {
+                if !self.open_next_data_file().await? {
+                    return Ok(None);
+                }
+                continue;
+            };
+            let data_file_id = *data_file_id;
+            let is_finalized = data_file_id != self.ledger.writer_current_data_file_id;
+
+            let token = match reader.try_next_record(is_finalized).await {
+                Ok(Some(token)) => token,
+                Ok(None) => {
+                    self.current = None;
+                    continue;
+                }
+                Err(ReaderError::Io { source }) => return Err(source),
+                Err(error) => {
+                    // The rest of the data file is unreliable, so move on to the next one.
+                    self.current = None;
+                    return Ok(Some(InspectedRecord {
+                        data_file_id,
+                        record_id: 0,
+                        size: 0,
+                        value: Err(error),
+                    }));
+                }
+            };
+
+            let record_id = token.record_id();
+            let size = token.record_bytes() as u64;
+            if record_id <= self.ledger.reader_last_record_id {
+                continue;
+            }
+
+            return Ok(Some(InspectedRecord {
+                data_file_id,
+                record_id,
+                size,
+                value: reader.read_record(token),
+            }));
+        }
+    }
+
+    /// Opens the next data file between the ones of the reader and writer, skipping missing ones.
+    ///
+    /// Returns `false` if there are no data files left to read.
+    async fn open_next_data_file(&mut self) -> io::Result<bool> {
+        while let Some(data_file_id) = self.next_data_file_id {
+            self.next_data_file_id = (data_file_id != self.ledger.writer_current_data_file_id)
+                .then(|| (data_file_id + 1) % MAX_FILE_ID);
+
+            match File::open(self.data_file_path(data_file_id)).await {
+                Ok(file) => {
+                    self.current = Some((data_file_id, RecordReader::new(file)));
+                    return Ok(true);
+                }
+                // Data files can be deleted by the reader of a running buffer in the meantime.
+                Err(error) if error.kind() == ErrorKind::NotFound => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+pub(super) async fn read_ledger(data_dir: &Path) -> Result<LedgerPositions, InspectError> {
+    let path = data_dir.join("buffer.db");
+    let contents = tokio::fs::read(&path)
+        .await
+        .context(ReadLedgerSnafu { path })?;
+    if contents.is_empty() {
+        return Err(InspectError::DeserializeLedger {
+            reason: "ledger is empty".to_string(),
+        });
+    }
+
+    // Archives can only be accessed from properly aligned buffers.
+    let mut buf = AlignedVec::with_capacity(contents.len());
+    buf.extend_from_slice(&contents);
+    let state =
+        try_as_archive::<LedgerState>(&buf).map_err(|e| InspectError::DeserializeLedger {
+            reason: e.into_inner(),
+        })?;
+
+    Ok(LedgerPositions {
+        writer_next_record_id: state.get_next_writer_record_id(),
+        writer_current_data_file_id: state.get_current_writer_file_id(),
+        reader_current_data_file_id: state.get_current_reader_file_id(),
+        reader_last_record_id: state.get_last_reader_record_id(),
+    })
+}
@@ -20,6 +20,7 @@ use super::{
     Filesystem,
     backed_archive::BackedArchive,
     common::{DiskBufferConfig, MAX_FILE_ID, align16},
+    inspect::LedgerPositions,
     io::{AsyncFile, WritableMemoryMap},
     ser::SerializeError,
 };
@@ -118,8 +119,20 @@ impl Default for LedgerState {
     }
 }
 
+impl LedgerState {
+    /// Creates the ledger state matching the given positions of the reader and writer.
+    pub(super) fn from_positions(positions: &LedgerPositions) -> Self {
+        Self {
+            writer_next_record: AtomicU64::new(positions.writer_next_record_id),
+            writer_current_data_file: AtomicU16::new(positions.writer_current_data_file_id),
+            reader_current_data_file: AtomicU16::new(positions.reader_current_data_file_id),
+            reader_last_record: AtomicU64::new(positions.reader_last_record_id),
+        }
+    }
+}
+
 impl ArchivedLedgerState {
-    fn get_current_writer_file_id(&self) -> u16 {
+    pub(super) fn get_current_writer_file_id(&self) -> u16 {
         self.writer_current_data_file.load(Ordering::Acquire)
     }
 
@@ -141,7 +154,7 @@ impl ArchivedLedgerState {
         previous.wrapping_add(amount)
     }
 
-    fn get_current_reader_file_id(&self) -> u16 {
+    pub(super) fn get_current_reader_file_id(&self) -> u16 {
         self.reader_current_data_file.load(Ordering::Acquire)
     }
 
@@ -388,6 +401,12 @@ where
             .increment_received_event_count_and_byte_size(event_count, record_size);
     }
 
+    /// Tracks the size of a successfully written record both before and after compression.
+    pub fn track_record_compression(&self, uncompressed_size: u64, compressed_size: u64) {
+        self.usage_handle
+            .increment_compressed_byte_size(uncompressed_size, compressed_size);
+    }
+
     /// Tracks the statistics of multiple successful reads.
     pub fn track_reads(&self, event_count: u64, total_record_size: u64) {
         self.decrement_total_buffer_size(total_record_size);
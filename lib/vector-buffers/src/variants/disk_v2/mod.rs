@@ -37,11 +37,17 @@
 //! ```text
 //! record:
 //!   record_len: uint64
-//!   checksum:   uint32(CRC32C of record_id + payload)
+//!   checksum:   uint32(CRC32C of record_id + metadata + payload)
 //!   record_id:  uint64
+//!   metadata:   uint32
 //!   payload:    uint8[record_len]
 //! ```
 //!
+//! When compression is enabled, the payload is compressed before the checksum is calculated, and
+//! the compression algorithm is stored in the upper bits of the record metadata. Records without
+//! those bits set are uncompressed, which keeps data files written before compression existed
+//! readable as-is.
+//!
 //! We say "pseudo-structure" as a helper serialization library, [`rkyv`][rkyv], is used to handle
 //! serialization, and zero-copy deserialization, of records. This effectively adds some amount of
 //! padding to record fields, due to the need to structure record field data in a way that makes it
@@ -184,10 +190,13 @@ use vector_common::finalization::Finalizable;
 
 mod backed_archive;
 mod common;
+mod compression;
+mod inspect;
 mod io;
 mod ledger;
 mod reader;
 mod record;
+mod repair;
 mod ser;
 mod writer;
 
@@ -197,14 +206,17 @@ mod tests;
 use self::ledger::Ledger;
 pub use self::{
     common::{DiskBufferConfig, DiskBufferConfigBuilder},
+    inspect::{BufferInspector, InspectError, InspectedRecord, LedgerPositions},
     io::{Filesystem, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
     reader::{BufferReader, ReaderError},
+    repair::{BufferRepair, DataFileReport, RepairError, RepairReport},
     writer::{BufferWriter, WriterError},
 };
 use crate::{
     Bufferable,
     buffer_usage_data::BufferUsageHandle,
+    config::DiskBufferCompression,
     topology::{
         builder::IntoBuffer,
         channel::{ReceiverAdapter, SenderAdapter},
@@ -301,14 +313,21 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    compression: DiskBufferCompression,
 }
 
 impl DiskV2Buffer {
-    pub fn new(id: String, data_dir: PathBuf, max_size: NonZeroU64) -> Self {
+    pub fn new(
+        id: String,
+        data_dir: PathBuf,
+        max_size: NonZeroU64,
+        compression: DiskBufferCompression,
+    ) -> Self {
         Self {
             id,
             data_dir,
             max_size,
+            compression,
         }
     }
 }
@@ -331,6 +350,7 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.compression,
         )
         .await?;
 
@@ -343,6 +363,7 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    compression: DiskBufferCompression,
 ) -> Result<
     (
         BufferWriter<T, ProductionFilesystem>,
@@ -358,6 +379,7 @@ where
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
     let config = DiskBufferConfigBuilder::from_path(buffer_path)
         .max_buffer_size(max_size.get())
+        .compression(compression)
         .build()?;
     Buffer::from_config(config, usage_handle)
         .await
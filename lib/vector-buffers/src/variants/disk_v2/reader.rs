@@ -16,11 +16,13 @@ use vector_common::{finalization::BatchNotifier, finalizer::OrderedFinalizer};
 use super::{
     Filesystem,
     common::create_crc32c_hasher,
+    compression,
     ledger::Ledger,
     record::{ArchivedRecord, Record, RecordStatus, validate_record_archive},
 };
 use crate::{
     Bufferable,
+    config::DiskBufferCompression,
     encoding::{AsMetadata, Encodable},
     internal_events::BufferReadError,
     topology::acks::{EligibleMarker, EligibleMarkerLength, MarkerError, OrderedAcknowledgements},
@@ -1132,9 +1134,18 @@ where
 pub(crate) fn decode_record_payload<T: Bufferable>(
     record: &ArchivedRecord<'_>,
 ) -> Result<T, ReaderError<T>> {
+    // Split off the compression used for the record payload, if any, from the metadata of `T`.
+    let (metadata, compression) =
+        compression::split_metadata(record.metadata()).ok_or(ReaderError::Incompatible {
+            reason: format!(
+                "unknown record compression (metadata: {:#036b})",
+                record.metadata()
+            ),
+        })?;
+
     // Try and convert the raw record metadata into the true metadata type used by `T`, and then
     // also verify that `T` is able to decode records with the metadata used for this record in particular.
-    let metadata = T::Metadata::from_u32(record.metadata()).ok_or(ReaderError::Incompatible {
+    let metadata = T::Metadata::from_u32(metadata).ok_or(ReaderError::Incompatible {
         reason: format!("invalid metadata for {}", std::any::type_name::<T>()),
     })?;
 
@@ -1147,6 +1158,16 @@ pub(crate) fn decode_record_payload<T: Bufferable>(
         });
     }
 
-    // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    // Now we can finally try decoding, decompressing the payload first if need be.
+    match compression {
+        DiskBufferCompression::None => T::decode(metadata, record.payload()).context(DecodeSnafu),
+        DiskBufferCompression::Zstd | DiskBufferCompression::Lz4 => {
+            let payload = compression::decompress(compression, record.payload()).map_err(|e| {
+                ReaderError::Deserialization {
+                    reason: format!("failed to decompress record payload: {e}"),
+                }
+            })?;
+            T::decode(metadata, &payload[..]).context(DecodeSnafu)
+        }
+    }
 }
@@ -0,0 +1,324 @@
+use std::{
+    io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use bytes::BytesMut;
+use fslock::LockFile;
+use snafu::{ResultExt, Snafu};
+use tokio::fs::{self, File, OpenOptions};
+
+use super::{
+    backed_archive::BackedArchive,
+    inspect::{LedgerPositions, read_ledger},
+    ledger::LedgerState,
+    reader::{ReaderError, RecordReader},
+    ser::SerializeError,
+};
+use crate::Bufferable;
+
+/// Error that occurred during calls to [`BufferRepair`].
+#[derive(Debug, Snafu)]
+pub enum RepairError {
+    /// A general I/O error occurred.
+    #[snafu(display("repair I/O error: {}", source))]
+    Io { source: io::Error },
+
+    /// The buffer is still opened by a Vector process.
+    ///
+    /// Buffers can only be repaired once the Vector process using them is stopped.
+    #[snafu(display(
+        "failed to lock buffer.lock; is a Vector process still running and using this buffer?"
+    ))]
+    BufferLocked,
+
+    /// The rebuilt ledger state was unable to be serialized.
+    #[snafu(display("failed to serialize ledger: {}", reason))]
+    FailedToSerialize { reason: String },
+}
+
+/// The result of validating the records of a data file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataFileReport {
+    /// The ID of the data file.
+    pub data_file_id: u16,
+    /// The size of the data file, in bytes.
+    pub total_bytes: u64,
+    /// The size of the valid records at the start of the data file, in bytes.
+    pub valid_bytes: u64,
+    /// The number of valid records.
+    pub records: u64,
+    /// The number of events within the valid records.
+    pub events: u64,
+    /// The ID of the first valid record, if any.
+    pub first_record_id: Option<u64>,
+    /// The ID following the last event of the valid records, if any.
+    pub next_record_id: Option<u64>,
+    /// The reason the records following the valid ones could not be read, if any.
+    pub corruption: Option<String>,
+}
+
+impl DataFileReport {
+    /// Gets the size of the corrupted tail of the data file, in bytes.
+    pub fn corrupted_bytes(&self) -> u64 {
+        self.total_bytes - self.valid_bytes
+    }
+}
+
+/// The result of validating all the data files of a buffer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepairReport {
+    /// The data files of the buffer, from the oldest to the newest.
+    pub data_files: Vec<DataFileReport>,
+    /// The positions found in the ledger, if it could be read.
+    pub previous_ledger: Option<LedgerPositions>,
+    /// The positions rebuilt from the data files.
+    pub ledger: LedgerPositions,
+}
+
+impl RepairReport {
+    /// Whether or not any data file has a corrupted tail.
+    pub fn is_corrupted(&self) -> bool {
+        self.data_files
+            .iter()
+            .any(|data_file| data_file.corruption.is_some())
+    }
+
+    /// Whether or not the rebuilt ledger differs from the one found in the buffer.
+    pub fn is_ledger_outdated(&self) -> bool {
+        self.previous_ledger != Some(self.ledger)
+    }
+}
+
+/// Validates and repairs a stopped disk buffer.
+///
+/// The buffer is locked for as long as this value lives, so that Vector can't open it in the
+/// meantime. Every record of every data file is read and its checksum validated, and the positions
+/// of the reader and writer are rebuilt from the valid records, so that a buffer can be repaired
+/// even when its ledger is lost or corrupted.
+pub struct BufferRepair<T> {
+    data_dir: PathBuf,
+    #[allow(dead_code)]
+    lock: LockFile,
+    _t: PhantomData<T>,
+}
+
+impl<T: Bufferable> BufferRepair<T> {
+    /// Locks the disk buffer stored in `data_dir` for repair.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer is still used by a Vector process, or its lock file can't be opened, an error
+    /// variant will be returned describing the error.
+    pub fn open(data_dir: impl Into<PathBuf>) -> Result<Self, RepairError> {
+        let data_dir = data_dir.into();
+        let mut lock = LockFile::open(&data_dir.join("buffer.lock")).context(IoSnafu)?;
+        if !lock.try_lock().context(IoSnafu)? {
+            return Err(RepairError::BufferLocked);
+        }
+
+        Ok(Self {
+            data_dir,
+            lock,
+            _t: PhantomData,
+        })
+    }
+
+    /// Gets the directory of the buffer.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Validates every record of the buffer, and rebuilds the ledger from the valid ones, without
+    /// modifying anything.
+    ///
+    /// The reader position of the previous ledger is kept when it is consistent with the valid
+    /// records. Otherwise, all valid records are considered unread, so that none of them are lost.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while reading the data files, an error variant will be returned
+    /// describing the error.
+    pub async fn validate(&self) -> Result<RepairReport, RepairError> {
+        let mut data_files = Vec::new();
+        let mut entries = fs::read_dir(&self.data_dir).await.context(IoSnafu)?;
+        while let Some(entry) = entries.next_entry().await.context(IoSnafu)? {
+            if let Some(data_file_id) = entry.file_name().to_str().and_then(parse_data_file_id) {
+                data_files.push(self.validate_data_file(data_file_id, &entry.path()).await?);
+            }
+        }
+        // File IDs wrap around, so the order of the data files is given by their records instead.
+        data_files.sort_by_key(|data_file| (data_file.first_record_id, data_file.data_file_id));
+
+        let previous_ledger = read_ledger(&self.data_dir).await.ok();
+        let ledger = rebuild_ledger(&data_files, previous_ledger);
+
+        Ok(RepairReport {
+            data_files,
+            previous_ledger,
+            ledger,
+        })
+    }
+
+    /// Repairs the buffer as described by `report`.
+    ///
+    /// The corrupted tails of data files are truncated, data files left without any valid records
+    /// are deleted, and the ledger is rewritten with the rebuilt positions.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while modifying the buffer, or the ledger can't be serialized, an
+    /// error variant will be returned describing the error.
+    pub async fn repair(&self, report: &RepairReport) -> Result<(), RepairError> {
+        for data_file in &report.data_files {
+            let path = self.data_file_path(data_file.data_file_id);
+            if data_file.records == 0 {
+                fs::remove_file(&path).await.context(IoSnafu)?;
+            } else if data_file.corruption.is_some() {
+                let file = OpenOptions::new()
+                    .write(true)
+                    .open(&path)
+                    .await
+                    .context(IoSnafu)?;
+                file.set_len(data_file.valid_bytes).await.context(IoSnafu)?;
+                file.sync_all().await.context(IoSnafu)?;
+            }
+        }
+
+        self.write_ledger(&report.ledger).await
+    }
+
+    fn data_file_path(&self, data_file_id: u16) -> PathBuf {
+        self.data_dir
+            .join(format!("buffer-data-{data_file_id}.dat"))
+    }
+
+    async fn validate_data_file(
+        &self,
+        data_file_id: u16,
+        path: &Path,
+    ) -> Result<DataFileReport, RepairError> {
+        let file = File::open(path).await.context(IoSnafu)?;
+        let total_bytes = file.metadata().await.context(IoSnafu)?.len();
+        let mut reader = RecordReader::<_, T>::new(file);
+        let mut report = DataFileReport {
+            data_file_id,
+            total_bytes,
+            valid_bytes: 0,
+            records: 0,
+            events: 0,
+            first_record_id: None,
+            next_record_id: None,
+            corruption: None,
+        };
+
+        loop {
+            // Records are only valid if they can be decoded, as their event count is needed to
+            // rebuild the ledger.
+            let result = match reader.try_next_record(true).await {
+                Ok(None) => break,
+                Ok(Some(token)) => {
+                    let record_id = token.record_id();
+                    let size = token.record_bytes() as u64;
+                    reader
+                        .read_record(token)
+                        .map(|record| (record_id, size, record.event_count() as u64))
+                }
+                Err(error) => Err(error),
+            };
+
+            match result {
+                Ok((_, _, 0)) => {
+                    report.corruption = Some(ReaderError::<T>::EmptyRecord.to_string());
+                    break;
+                }
+                Ok((record_id, size, events)) => {
+                    report.valid_bytes += size;
+                    report.records += 1;
+                    report.events += events;
+                    report.first_record_id.get_or_insert(record_id);
+                    report.next_record_id = Some(record_id.wrapping_add(events));
+                }
+                Err(ReaderError::Io { source }) => return Err(RepairError::Io { source }),
+                Err(error) => {
+                    report.corruption = Some(error.to_string());
+                    break;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn write_ledger(&self, positions: &LedgerPositions) -> Result<(), RepairError> {
+        let mut buf = BytesMut::new();
+        let archive = loop {
+            match BackedArchive::from_value(&mut buf, LedgerState::from_positions(positions)) {
+                Ok(archive) => break archive,
+                Err(SerializeError::FailedToSerialize(reason)) => {
+                    return Err(RepairError::FailedToSerialize { reason });
+                }
+                // Our buffer wasn't big enough, but that's OK!  Resize it and try again.
+                Err(SerializeError::BackingStoreTooSmall(_, min_len)) => buf.resize(min_len, 0),
+            }
+        };
+
+        // Write the ledger atomically, so that a failure never leaves a partial ledger behind.
+        let ledger_path = self.data_dir.join("buffer.db");
+        let temporary_path = self.data_dir.join("buffer.db.tmp");
+        fs::write(&temporary_path, archive.get_backing_ref())
+            .await
+            .context(IoSnafu)?;
+        File::open(&temporary_path)
+            .await
+            .context(IoSnafu)?
+            .sync_all()
+            .await
+            .context(IoSnafu)?;
+        fs::rename(&temporary_path, &ledger_path)
+            .await
+            .context(IoSnafu)
+    }
+}
+
+fn parse_data_file_id(file_name: &str) -> Option<u16> {
+    file_name
+        .strip_prefix("buffer-data-")?
+        .strip_suffix(".dat")?
+        .parse()
+        .ok()
+}
+
+/// Rebuilds the positions of the reader and writer from the valid records of the data files,
+/// sorted from the oldest to the newest.
+fn rebuild_ledger(
+    data_files: &[DataFileReport],
+    previous: Option<LedgerPositions>,
+) -> LedgerPositions {
+    let mut valid = data_files.iter().filter(|data_file| data_file.records > 0);
+    let (Some(oldest), Some(newest)) = (valid.clone().next(), valid.next_back()) else {
+        // Without any valid records, the buffer is empty, but record IDs keep increasing.
+        let writer_next_record_id = previous.map_or(1, |previous| previous.writer_next_record_id);
+        return LedgerPositions {
+            writer_next_record_id,
+            writer_current_data_file_id: 0,
+            reader_current_data_file_id: 0,
+            reader_last_record_id: writer_next_record_id.wrapping_sub(1),
+        };
+    };
+
+    let first_record_id = oldest.first_record_id.expect("data file has records");
+    let writer_next_record_id = newest.next_record_id.expect("data file has records");
+    let reader_last_record_id = previous
+        .map(|previous| previous.reader_last_record_id)
+        .filter(|&last| last >= first_record_id.wrapping_sub(1) && last < writer_next_record_id)
+        .unwrap_or_else(|| first_record_id.wrapping_sub(1));
+
+    LedgerPositions {
+        writer_next_record_id,
+        writer_current_data_file_id: newest.data_file_id,
+        reader_current_data_file_id: oldest.data_file_id,
+        reader_last_record_id,
+    }
+}
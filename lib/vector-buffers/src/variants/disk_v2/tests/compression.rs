@@ -0,0 +1,73 @@
+use tracing::Instrument;
+
+use super::{create_buffer_v2_with_compression, read_next_some};
+use crate::{
+    assert_buffer_is_empty, assert_buffer_records,
+    config::DiskBufferCompression,
+    test::{SizedRecord, acknowledge, install_tracing_helpers, with_temp_dir},
+};
+
+#[tokio::test]
+async fn compressed_records_survive_reopening_with_different_compression() {
+    let _a = install_tracing_helpers();
+
+    let fut = with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Write a batch of records with zstd compression. Their payloads are highly
+            // repetitive, so they should take up far less space on disk than their encoded size.
+            let (mut writer, reader, ledger) =
+                create_buffer_v2_with_compression(data_dir.clone(), DiskBufferCompression::Zstd)
+                    .await;
+            assert_buffer_is_empty!(ledger);
+
+            let mut bytes_written = 0;
+            for _ in 0..10 {
+                bytes_written += writer
+                    .write_record(SizedRecord::new(4096))
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("writer flush should not fail");
+            assert_buffer_records!(ledger, 10);
+            assert!(
+                bytes_written < 10 * 4096,
+                "records should have been compressed, but {bytes_written} bytes were written"
+            );
+
+            ledger.flush().expect("should not fail to flush ledger");
+            drop(reader);
+            drop(writer);
+            drop(ledger);
+
+            // Reopen the buffer with a different compression, and write one more record. The
+            // records written before reopening are still read back using zstd.
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_compression(data_dir, DiskBufferCompression::Lz4).await;
+            assert_buffer_records!(ledger, 10);
+
+            writer
+                .write_record(SizedRecord::new(4096))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("writer flush should not fail");
+            writer.close();
+            assert_buffer_records!(ledger, 11);
+
+            for _ in 0..11 {
+                let record = read_next_some(&mut reader).await;
+                assert_eq!(record, SizedRecord::new(4096));
+                acknowledge(record).await;
+            }
+
+            // Reading once more lets the reader process the acknowledgements and observe that the
+            // writer is done.
+            assert!(reader.next().await.expect("read should not fail").is_none());
+            assert_buffer_is_empty!(ledger);
+        }
+    });
+
+    let parent = trace_span!("compressed_records_survive_reopening_with_different_compression");
+    fut.instrument(parent.or_current()).await;
+}
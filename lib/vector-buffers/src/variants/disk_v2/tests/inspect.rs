@@ -0,0 +1,59 @@
+use vector_common::finalization::Finalizable;
+
+use super::{create_default_buffer_v2, read_next_some};
+use crate::{
+    assert_buffer_records,
+    test::{SizedRecord, acknowledge, with_temp_dir},
+    variants::disk_v2::BufferInspector,
+};
+
+#[tokio::test]
+async fn inspector_reads_unacknowledged_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, mut reader, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+
+            for size in [32, 64, 128] {
+                writer
+                    .write_record(SizedRecord::new(size))
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("writer flush should not fail");
+            assert_buffer_records!(ledger, 3);
+
+            // Acknowledge the first record, which is only applied to the ledger on the next read.
+            let mut first_read = read_next_some(&mut reader).await;
+            acknowledge(first_read.take_finalizers()).await;
+            let second_read = read_next_some(&mut reader).await;
+            assert_eq!(second_read, SizedRecord::new(64));
+
+            let mut inspector = BufferInspector::<SizedRecord>::open(&data_dir)
+                .await
+                .expect("inspector should open the buffer");
+            assert_eq!(inspector.ledger().writer_next_record_id, 4);
+            assert_eq!(inspector.ledger().reader_last_record_id, 1);
+            assert_eq!(inspector.ledger().unread_events(), 2);
+
+            let mut records = Vec::new();
+            while let Some(record) = inspector.next().await.expect("inspection should not fail") {
+                records.push((
+                    record.record_id,
+                    record.value.expect("record should decode"),
+                ));
+            }
+            assert_eq!(
+                records,
+                vec![(2, SizedRecord::new(64)), (3, SizedRecord::new(128))]
+            );
+
+            // Inspecting the buffer must leave it untouched.
+            assert_buffer_records!(ledger, 2);
+            let third_read = read_next_some(&mut reader).await;
+            assert_eq!(third_read, SizedRecord::new(128));
+        }
+    })
+    .await;
+}
@@ -16,19 +16,22 @@ use super::{
     record::RECORD_HEADER_LEN,
 };
 use crate::{
-    Bufferable, buffer_usage_data::BufferUsageHandle, encoding::FixedEncodable,
-    variants::disk_v2::common::align16,
+    Bufferable, buffer_usage_data::BufferUsageHandle, config::DiskBufferCompression,
+    encoding::FixedEncodable, variants::disk_v2::common::align16,
 };
 
 type FilesystemUnderTest = ProductionFilesystem;
 
 mod acknowledgements;
 mod basic;
+mod compression;
 mod initialization;
+mod inspect;
 mod invariants;
 mod known_errors;
 mod model;
 mod record;
+mod repair;
 mod size_limits;
 
 impl AsyncFile for DuplexStream {
@@ -360,6 +363,30 @@ where
         .expect("should not fail to create buffer")
 }
 
+/// Creates a disk v2 buffer with the specified record compression.
+pub(crate) async fn create_buffer_v2_with_compression<P, R>(
+    data_dir: P,
+    compression: DiskBufferCompression,
+) -> (
+    BufferWriter<R, FilesystemUnderTest>,
+    BufferReader<R, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .compression(compression)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
 pub(crate) fn get_corrected_max_record_size<T>(payload: &T) -> usize
 where
     T: FixedEncodable,
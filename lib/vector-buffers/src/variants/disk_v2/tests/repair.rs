@@ -0,0 +1,95 @@
+use std::io::SeekFrom;
+
+use tokio::{
+    fs::OpenOptions,
+    io::{AsyncSeekExt, AsyncWriteExt},
+};
+
+use super::{create_default_buffer_v2, read_next_some};
+use crate::{
+    test::{SizedRecord, with_temp_dir},
+    variants::disk_v2::{BufferRepair, LedgerPositions, RepairError},
+};
+
+#[tokio::test]
+async fn repair_truncates_corrupted_tail_and_rebuilds_ledger() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) =
+                create_default_buffer_v2::<_, SizedRecord>(data_dir.clone()).await;
+            for size in [32, 64] {
+                writer
+                    .write_record(SizedRecord::new(size))
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("writer flush should not fail");
+            writer.close();
+
+            // The buffer can't be repaired while it's in use.
+            assert!(matches!(
+                BufferRepair::<SizedRecord>::open(&data_dir),
+                Err(RepairError::BufferLocked)
+            ));
+
+            let data_file_path = ledger.get_current_writer_data_file_path();
+            drop(reader);
+            drop(writer);
+            drop(ledger);
+
+            // Simulate a crash in the middle of a write, which also lost the ledger.
+            let mut data_file = OpenOptions::new()
+                .write(true)
+                .open(&data_file_path)
+                .await
+                .expect("data file should open");
+            let valid_len = data_file
+                .seek(SeekFrom::End(0))
+                .await
+                .expect("seek should not fail");
+            data_file
+                .write_all(&[0, 0, 0, 0, 0, 0, 1, 0, 42, 42])
+                .await
+                .expect("write should not fail");
+            data_file.sync_all().await.expect("sync should not fail");
+            std::fs::remove_file(data_dir.join("buffer.db")).expect("ledger should be removed");
+
+            let repair =
+                BufferRepair::<SizedRecord>::open(&data_dir).expect("buffer should not be locked");
+            let report = repair.validate().await.expect("validation should not fail");
+            assert!(report.is_corrupted());
+            assert_eq!(report.previous_ledger, None);
+            assert_eq!(
+                report.ledger,
+                LedgerPositions {
+                    writer_next_record_id: 3,
+                    writer_current_data_file_id: 0,
+                    reader_current_data_file_id: 0,
+                    reader_last_record_id: 0,
+                }
+            );
+            assert_eq!(report.data_files.len(), 1);
+            assert_eq!(report.data_files[0].records, 2);
+            assert_eq!(report.data_files[0].valid_bytes, valid_len);
+            assert_eq!(report.data_files[0].corrupted_bytes(), 10);
+
+            repair
+                .repair(&report)
+                .await
+                .expect("repair should not fail");
+            let report = repair.validate().await.expect("validation should not fail");
+            assert!(!report.is_corrupted());
+            assert!(!report.is_ledger_outdated());
+            drop(repair);
+
+            // The buffer can now be read from the start.
+            let (_writer, mut reader, _ledger) =
+                create_default_buffer_v2::<_, SizedRecord>(data_dir).await;
+            assert_eq!(read_next_some(&mut reader).await, SizedRecord::new(32));
+            assert_eq!(read_next_some(&mut reader).await, SizedRecord::new(64));
+        }
+    })
+    .await;
+}
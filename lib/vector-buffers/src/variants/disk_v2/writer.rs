@@ -25,12 +25,14 @@ use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::{
     common::{DiskBufferConfig, create_crc32c_hasher},
+    compression,
     io::Filesystem,
     ledger::Ledger,
     record::{Record, RecordStatus, validate_record_archive},
 };
 use crate::{
     Bufferable,
+    config::DiskBufferCompression,
     encoding::{AsMetadata, Encodable},
     variants::disk_v2::{
         io::AsyncFile,
@@ -208,6 +210,8 @@ where
 pub(super) struct WriteToken {
     event_count: usize,
     serialized_len: usize,
+    encoded_len: usize,
+    payload_len: usize,
 }
 
 impl WriteToken {
@@ -218,6 +222,16 @@ impl WriteToken {
     pub fn serialized_len(&self) -> usize {
         self.serialized_len
     }
+
+    /// Length of the encoded record, before compression.
+    pub fn encoded_len(&self) -> usize {
+        self.encoded_len
+    }
+
+    /// Length of the record payload as stored, after compression.
+    pub fn payload_len(&self) -> usize {
+        self.payload_len
+    }
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -360,6 +374,8 @@ impl<W: fmt::Debug> fmt::Debug for TrackingBufWriter<W> {
 pub(super) struct RecordWriter<W, T> {
     writer: TrackingBufWriter<W>,
     encode_buf: Vec<u8>,
+    compress_buf: Vec<u8>,
+    compression: DiskBufferCompression,
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
@@ -410,6 +426,8 @@ where
         Self {
             writer: TrackingBufWriter::with_capacity(write_buffer_size, writer),
             encode_buf: Vec::with_capacity(16_384),
+            compress_buf: Vec::new(),
+            compression: DiskBufferCompression::None,
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
@@ -420,6 +438,13 @@ where
         }
     }
 
+    /// Sets the compression applied to the payload of records archived by this writer.
+    #[must_use]
+    pub fn with_compression(mut self, compression: DiskBufferCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     /// Gets a reference to the underlying writer.
     #[cfg(test)]
     pub fn get_ref(&self) -> &W {
@@ -480,9 +505,28 @@ where
             });
         }
 
-        let metadata = T::get_metadata().into_u32();
-        let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+        // If compression is enabled, compress the encoded record, and only keep the compressed form
+        // if it actually ended up being smaller.  Records which are stored uncompressed carry no
+        // compression bits in their metadata, exactly like records written without compression.
+        let mut record_compression = DiskBufferCompression::None;
+        if self.compression != DiskBufferCompression::None {
+            compression::compress(self.compression, &self.encode_buf, &mut self.compress_buf)
+                .map_err(|e| WriterError::FailedToSerialize {
+                    reason: format!("failed to compress encoded record: {e}"),
+                })?;
+            if self.compress_buf.len() < encoded_len {
+                record_compression = self.compression;
+            }
+        }
+        let payload = match record_compression {
+            DiskBufferCompression::None => &self.encode_buf[..],
+            DiskBufferCompression::Zstd | DiskBufferCompression::Lz4 => &self.compress_buf[..],
+        };
+        let payload_len = payload.len();
+
+        let metadata =
+            compression::combine_metadata(T::get_metadata().into_u32(), record_compression);
+        let wrapped_record = Record::with_checksum(id, metadata, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -560,6 +604,8 @@ where
         Ok(WriteToken {
             event_count,
             serialized_len,
+            encoded_len,
+            payload_len,
         })
     }
 
@@ -665,18 +711,9 @@ where
             }
         })?;
 
-        // Now we can actually decode it as `T`.
-        let record_metadata = T::Metadata::from_u32(wrapped_record.metadata()).ok_or(
-            WriterError::InconsistentState {
-                reason: "failed to decode record metadata immediately after encoding it"
-                    .to_string(),
-            },
-        )?;
-
-        T::decode(record_metadata, wrapped_record.payload()).map_err(|_| {
-            WriterError::InconsistentState {
-                reason: "failed to decode record immediately after encoding it".to_string(),
-            }
+        // Now we can actually decode it as `T`, decompressing it first if need be.
+        decode_record_payload::<T>(wrapped_record).map_err(|_| WriterError::InconsistentState {
+            reason: "failed to decode record immediately after encoding it".to_string(),
         })
     }
 
@@ -1123,13 +1160,16 @@ where
                 // Make sure the file is flushed to disk, especially if we just created it.
                 data_file.sync_all().await?;
 
-                self.writer = Some(RecordWriter::new(
-                    data_file,
-                    data_file_size,
-                    self.config.write_buffer_size,
-                    self.config.max_data_file_size,
-                    self.config.max_record_size,
-                ));
+                self.writer = Some(
+                    RecordWriter::new(
+                        data_file,
+                        data_file_size,
+                        self.config.write_buffer_size,
+                        self.config.max_data_file_size,
+                        self.config.max_record_size,
+                    )
+                    .with_compression(self.config.compression),
+                );
                 self.data_file_size = data_file_size;
 
                 // If we opened the "next" data file, we need to increment the current writer
@@ -1229,6 +1269,7 @@ where
         //
         // Otherwise, we proceed with flushing like we normally would.
         let can_write_record = self.can_write_record(token.serialized_len());
+        let (encoded_len, payload_len) = (token.encoded_len(), token.payload_len());
         let writer = self
             .writer
             .as_mut()
@@ -1253,6 +1294,10 @@ where
         // setting the ledger state to a record ID that we may never have actually written, which
         // could lead to record ID gaps.
         self.track_write(record_events.get(), bytes_written as u64);
+        if self.config.compression != DiskBufferCompression::None {
+            self.ledger
+                .track_record_compression(encoded_len as u64, payload_len as u64);
+        }
 
         // If we did flush some buffered writes during this write, however, we now compensate for
         // that after updating our internal state.  We'll also notify the reader, too, since the
@@ -3,3 +3,6 @@ pub use disk_v2::DiskV2Buffer;
 
 pub(crate) mod in_memory;
 pub use in_memory::MemoryBuffer;
+
+pub(crate) mod spill;
+pub use spill::SpillBuffer;
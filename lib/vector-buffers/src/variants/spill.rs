@@ -0,0 +1,97 @@
+use std::{
+    error::Error,
+    num::{NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use vector_common::finalization::Finalizable;
+
+use crate::{
+    Bufferable,
+    buffer_usage_data::BufferUsageHandle,
+    config::{DiskBufferCompression, MemoryBufferSize},
+    topology::{
+        builder::IntoBuffer,
+        channel::{ReceiverAdapter, SenderAdapter, limited, spill_channel},
+    },
+    variants::disk_v2::{Buffer, DiskBufferConfigBuilder},
+};
+
+/// A buffer stage which keeps events in memory, spilling them to disk under sustained backpressure.
+pub struct SpillBuffer {
+    id: String,
+    data_dir: PathBuf,
+    max_events: NonZeroUsize,
+    max_size: NonZeroU64,
+    spill_after: Duration,
+    compression: DiskBufferCompression,
+}
+
+impl SpillBuffer {
+    pub fn new(
+        id: String,
+        data_dir: PathBuf,
+        max_events: NonZeroUsize,
+        max_size: NonZeroU64,
+        spill_after: Duration,
+        compression: DiskBufferCompression,
+    ) -> Self {
+        Self {
+            id,
+            data_dir,
+            max_events,
+            max_size,
+            spill_after,
+            compression,
+        }
+    }
+}
+
+#[async_trait]
+impl<T> IntoBuffer<T> for SpillBuffer
+where
+    T: Bufferable + Clone + Finalizable,
+{
+    fn provides_instrumentation(&self) -> bool {
+        // Events read and written by the disk buffer are instrumented by its ledger, so the spill
+        // channel instruments the events going through memory itself to avoid counting them twice.
+        true
+    }
+
+    async fn into_buffer_parts(
+        self: Box<Self>,
+        usage_handle: BufferUsageHandle,
+    ) -> Result<(SenderAdapter<T>, ReceiverAdapter<T>), Box<dyn Error + Send + Sync>> {
+        usage_handle.set_buffer_limits(Some(self.max_size.get()), Some(self.max_events.get()));
+
+        let buffer_path = get_spill_data_dir_path(&self.data_dir, self.id.as_str());
+        let config = DiskBufferConfigBuilder::from_path(buffer_path)
+            .max_buffer_size(self.max_size.get())
+            .compression(self.compression)
+            .build()?;
+        let (writer, reader, ledger) =
+            Buffer::from_config_inner(config, usage_handle.clone()).await?;
+
+        // Any events left on disk by a previous process have to be read back before new events.
+        let spilled_events = ledger.get_total_records();
+
+        let (tx, rx) = limited(MemoryBufferSize::MaxEvents(self.max_events));
+        let (sender, receiver) = spill_channel(
+            tx,
+            rx,
+            writer,
+            reader,
+            spilled_events,
+            self.spill_after,
+            usage_handle,
+        );
+
+        Ok((sender.into(), receiver.into()))
+    }
+}
+
+pub(crate) fn get_spill_data_dir_path(base_dir: &Path, buffer_id: &str) -> PathBuf {
+    base_dir.join("buffer").join("spill").join(buffer_id)
+}
@@ -3,7 +3,7 @@
 //! as it flows through transforms, being duplicated and merged, and
 //! then report its status when the last copy is delivered or dropped.
 
-use std::{cmp, future::Future, mem, pin::Pin, sync::Arc, task::Poll};
+use std::{any::Any, cmp, future::Future, mem, pin::Pin, sync::Arc, task::Poll};
 
 use crossbeam_utils::atomic::AtomicCell;
 use futures::future::FutureExt;
@@ -86,6 +86,15 @@ impl EventFinalizers {
         }
     }
 
+    /// Returns the contexts of type `T` attached to the batches of the event finalizers.
+    ///
+    /// Finalizers sharing the same batch yield its context once per finalizer.
+    pub fn batch_contexts<T: Any + Send + Sync>(&self) -> impl Iterator<Item = Arc<T>> + '_ {
+        self.0
+            .iter()
+            .filter_map(|finalizer| finalizer.batch.context::<T>())
+    }
+
     /// Consumes all event finalizers and updates their underlying batches immediately.
     pub fn update_sources(&mut self) {
         let finalizers = mem::take(&mut self.0);
@@ -198,14 +207,39 @@ impl BatchNotifier {
     /// Creates a new `BatchNotifier` along with the receiver used to await its finalization status.
     #[must_use]
     pub fn new_with_receiver() -> (Self, BatchStatusReceiver) {
+        Self::new_with_context(None)
+    }
+
+    /// Creates a new `BatchNotifier` carrying the given `context`, along with the receiver used to
+    /// await its finalization status.
+    ///
+    /// The context lets the source hand data over to the sinks finalizing the batch, such as the
+    /// position of the batch in the upstream system.
+    #[must_use]
+    pub fn new_with_receiver_and_context(
+        context: Arc<dyn Any + Send + Sync>,
+    ) -> (Self, BatchStatusReceiver) {
+        Self::new_with_context(Some(context))
+    }
+
+    fn new_with_context(
+        context: Option<Arc<dyn Any + Send + Sync>>,
+    ) -> (Self, BatchStatusReceiver) {
         let (sender, receiver) = oneshot::channel();
         let notifier = OwnedBatchNotifier {
             status: AtomicCell::new(BatchStatus::Delivered),
             notifier: Some(sender),
+            context,
         };
         (Self(Arc::new(notifier)), BatchStatusReceiver(receiver))
     }
 
+    /// Returns the context of the batch, if it has one of type `T`.
+    #[must_use]
+    pub fn context<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        Arc::clone(self.0.context.as_ref()?).downcast().ok()
+    }
+
     /// Optionally creates a new `BatchNotifier` along with the receiver used to await its finalization status.
     #[must_use]
     pub fn maybe_new_with_receiver(enabled: bool) -> (Option<Self>, Option<BatchStatusReceiver>) {
@@ -257,6 +291,7 @@ impl BatchNotifier {
 pub struct OwnedBatchNotifier {
     status: AtomicCell<BatchStatus>,
     notifier: Option<oneshot::Sender<BatchStatus>>,
+    context: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 impl OwnedBatchNotifier {
@@ -507,6 +542,20 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
     }
 
+    #[test]
+    fn batch_contexts() {
+        let (batch, _receiver) = BatchNotifier::new_with_receiver_and_context(Arc::new(42_u64));
+        let (other, _other_receiver) = BatchNotifier::new_with_receiver();
+        let mut finalizers = EventFinalizers::new(EventFinalizer::new(batch.clone()));
+        finalizers.add(EventFinalizer::new(other));
+
+        assert_eq!(batch.context::<String>(), None);
+        assert_eq!(
+            finalizers.batch_contexts::<u64>().collect::<Vec<_>>(),
+            vec![Arc::new(42)]
+        );
+    }
+
     fn make_finalizer() -> (EventFinalizers, BatchStatusReceiver) {
         let (batch, receiver) = BatchNotifier::new_with_receiver();
         let finalizer = EventFinalizers::new(EventFinalizer::new(batch));
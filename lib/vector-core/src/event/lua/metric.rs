@@ -218,6 +218,20 @@ impl IntoLua for LuaMetric {
                         sketch_tbl.raw_set("n", bin_map.counts)?;
                         sketch_tbl
                     }
+                    MetricSketch::ExponentialHistogram(histogram) => {
+                        let sketch_tbl = lua.create_table()?;
+                        sketch_tbl.raw_set("type", "exponential_histogram")?;
+                        sketch_tbl.raw_set("count", histogram.count)?;
+                        sketch_tbl.raw_set("sum", histogram.sum)?;
+                        sketch_tbl.raw_set("min", histogram.min)?;
+                        sketch_tbl.raw_set("max", histogram.max)?;
+                        let buckets = histogram.to_buckets();
+                        let upper_limits: Vec<_> = buckets.iter().map(|b| b.upper_limit).collect();
+                        let counts: Vec<_> = buckets.iter().map(|b| b.count).collect();
+                        sketch_tbl.raw_set("buckets", upper_limits)?;
+                        sketch_tbl.raw_set("counts", counts)?;
+                        sketch_tbl
+                    }
                 };
 
                 tbl.raw_set("sketch", sketch_tbl)?;
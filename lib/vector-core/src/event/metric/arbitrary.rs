@@ -2,7 +2,7 @@ use proptest::collection::{btree_set, hash_map, hash_set};
 use proptest::option;
 use proptest::prelude::*;
 
-use crate::metrics::AgentDDSketch;
+use crate::metrics::{AgentDDSketch, ExponentialBuckets, ExponentialHistogram};
 
 use super::{
     Bucket, MetricSketch, MetricTags, MetricValue, Quantile, Sample, StatisticKind, TagValue,
@@ -69,7 +69,10 @@ impl Arbitrary for MetricSketch {
     type Strategy = BoxedStrategy<MetricSketch>;
 
     fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
-        let strategy = prop_oneof![any::<AgentDDSketch>().prop_map(MetricSketch::AgentDDSketch),];
+        let strategy = prop_oneof![
+            any::<AgentDDSketch>().prop_map(MetricSketch::AgentDDSketch),
+            any::<ExponentialHistogram>().prop_map(MetricSketch::ExponentialHistogram),
+        ];
         strategy.boxed()
     }
 }
@@ -134,6 +137,52 @@ impl Arbitrary for AgentDDSketch {
     }
 }
 
+impl Arbitrary for ExponentialHistogram {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<ExponentialHistogram>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (
+            -4..=8i32,
+            0..1000u64,
+            any::<(ExponentialBuckets, ExponentialBuckets)>(),
+            realistic_float(),
+            option::of((realistic_float(), realistic_float())),
+        )
+            .prop_map(|(scale, zero_count, (positive, negative), sum, bounds)| {
+                // The count is derived from the buckets, as it is in valid histograms.
+                let count = zero_count
+                    + positive.counts.iter().sum::<u64>()
+                    + negative.counts.iter().sum::<u64>();
+                ExponentialHistogram {
+                    scale,
+                    zero_count,
+                    zero_threshold: 0.0,
+                    positive,
+                    negative,
+                    count,
+                    sum,
+                    min: bounds.map(|(min, max)| min.min(max)),
+                    max: bounds.map(|(min, max)| min.max(max)),
+                }
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ExponentialBuckets {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<ExponentialBuckets>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        use proptest::collection::vec as arb_vec;
+
+        (-128..128i32, arb_vec(0..1000u64, 0..32))
+            .prop_map(|(offset, counts)| ExponentialBuckets { offset, counts })
+            .boxed()
+    }
+}
+
 impl Arbitrary for TagValue {
     type Parameters = ();
     type Strategy = BoxedStrategy<TagValue>;
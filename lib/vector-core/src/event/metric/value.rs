@@ -1,12 +1,15 @@
 use core::fmt;
-use std::collections::BTreeSet;
+use std::{borrow::Cow, collections::BTreeSet};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 
 use vector_common::byte_size_of::ByteSizeOf;
 use vector_config::configurable_component;
 
-use crate::{float_eq, metrics::AgentDDSketch};
+use crate::{
+    float_eq,
+    metrics::{AgentDDSketch, ExponentialHistogram},
+};
 
 use super::{samples_to_buckets, write_list, write_word};
 
@@ -200,6 +203,9 @@ impl MetricValue {
                 MetricSketch::AgentDDSketch(ddsketch) => {
                     ddsketch.clear();
                 }
+                MetricSketch::ExponentialHistogram(histogram) => {
+                    histogram.clear();
+                }
             },
         }
     }
@@ -264,6 +270,11 @@ impl MetricValue {
                         MetricSketch::AgentDDSketch(ddsketch),
                         MetricSketch::AgentDDSketch(ddsketch2),
                     ) => ddsketch.merge(ddsketch2).is_ok(),
+                    (
+                        MetricSketch::ExponentialHistogram(histogram),
+                        MetricSketch::ExponentialHistogram(histogram2),
+                    ) => histogram.merge(histogram2),
+                    _ => false,
                 }
             }
             _ => false,
@@ -495,6 +506,16 @@ impl fmt::Display for MetricValue {
                             )
                         })
                     }
+                    MetricSketch::ExponentialHistogram(histogram) => {
+                        write!(
+                            fmt,
+                            "count={} sum={} scale={} ",
+                            histogram.count, histogram.sum, histogram.scale
+                        )?;
+                        write_list(fmt, " ", histogram.to_buckets(), |fmt, bucket| {
+                            write!(fmt, "{}@{}", bucket.count, bucket.upper_limit)
+                        })
+                    }
                 }
             }
         }
@@ -543,6 +564,11 @@ pub enum MetricSketch {
     /// [ddsketch]: https://www.vldb.org/pvldb/vol12/p2195-masson.pdf
     /// [ddagent]: https://github.com/DataDog/datadog-agent
     AgentDDSketch(AgentDDSketch),
+
+    /// Exponential histogram, as defined by [OpenTelemetry][otel].
+    ///
+    /// [otel]: https://opentelemetry.io/docs/specs/otel/metrics/data-model/#exponentialhistogram
+    ExponentialHistogram(ExponentialHistogram),
 }
 
 impl MetricSketch {
@@ -550,6 +576,7 @@ impl MetricSketch {
     pub fn is_empty(&self) -> bool {
         match self {
             MetricSketch::AgentDDSketch(ddsketch) => ddsketch.is_empty(),
+            MetricSketch::ExponentialHistogram(histogram) => histogram.is_empty(),
         }
     }
 
@@ -559,6 +586,15 @@ impl MetricSketch {
     pub fn as_name(&self) -> &'static str {
         match self {
             Self::AgentDDSketch(_) => "agent dd sketch",
+            Self::ExponentialHistogram(_) => "exponential histogram",
+        }
+    }
+
+    /// Gets the sketch as an `AgentDDSketch`, converting it if needed.
+    pub fn to_agent_ddsketch(&self) -> Cow<'_, AgentDDSketch> {
+        match self {
+            Self::AgentDDSketch(ddsketch) => Cow::Borrowed(ddsketch),
+            Self::ExponentialHistogram(histogram) => Cow::Owned(histogram.to_agent_ddsketch()),
         }
     }
 }
@@ -567,6 +603,7 @@ impl ByteSizeOf for MetricSketch {
     fn allocated_bytes(&self) -> usize {
         match self {
             Self::AgentDDSketch(ddsketch) => ddsketch.allocated_bytes(),
+            Self::ExponentialHistogram(histogram) => histogram.allocated_bytes(),
         }
     }
 }
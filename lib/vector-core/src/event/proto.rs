@@ -6,7 +6,10 @@ use ordered_float::NotNan;
 use uuid::Uuid;
 
 use super::{MetricTags, WithMetadata};
-use crate::{event, metrics::AgentDDSketch};
+use crate::{
+    event,
+    metrics::{AgentDDSketch, ExponentialBuckets, ExponentialHistogram},
+};
 
 #[allow(warnings, clippy::all, clippy::pedantic)]
 mod proto_event {
@@ -198,6 +201,9 @@ impl From<MetricValue> for super::MetricValue {
                 sketch::Sketch::AgentDdSketch(ddsketch) => Self::Sketch {
                     sketch: ddsketch.into(),
                 },
+                sketch::Sketch::ExponentialHistogram(histogram) => Self::Sketch {
+                    sketch: MetricSketch::ExponentialHistogram(histogram.into()),
+                },
             },
         }
     }
@@ -410,6 +416,9 @@ impl From<super::MetricValue> for MetricValue {
                         })),
                     })
                 }
+                MetricSketch::ExponentialHistogram(histogram) => Self::Sketch(Sketch {
+                    sketch: Some(sketch::Sketch::ExponentialHistogram(histogram.into())),
+                }),
             },
         }
     }
@@ -562,6 +571,56 @@ impl From<sketch::AgentDdSketch> for MetricSketch {
     }
 }
 
+impl From<ExponentialHistogram> for sketch::ExponentialHistogram {
+    fn from(histogram: ExponentialHistogram) -> Self {
+        Self {
+            scale: histogram.scale,
+            zero_count: histogram.zero_count,
+            zero_threshold: histogram.zero_threshold,
+            positive: Some(histogram.positive.into()),
+            negative: Some(histogram.negative.into()),
+            count: histogram.count,
+            sum: histogram.sum,
+            min: histogram.min,
+            max: histogram.max,
+        }
+    }
+}
+
+impl From<sketch::ExponentialHistogram> for ExponentialHistogram {
+    fn from(histogram: sketch::ExponentialHistogram) -> Self {
+        Self {
+            scale: histogram.scale,
+            zero_count: histogram.zero_count,
+            zero_threshold: histogram.zero_threshold,
+            positive: histogram.positive.map(Into::into).unwrap_or_default(),
+            negative: histogram.negative.map(Into::into).unwrap_or_default(),
+            count: histogram.count,
+            sum: histogram.sum,
+            min: histogram.min,
+            max: histogram.max,
+        }
+    }
+}
+
+impl From<ExponentialBuckets> for sketch::exponential_histogram::Buckets {
+    fn from(buckets: ExponentialBuckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            counts: buckets.counts,
+        }
+    }
+}
+
+impl From<sketch::exponential_histogram::Buckets> for ExponentialBuckets {
+    fn from(buckets: sketch::exponential_histogram::Buckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            counts: buckets.counts,
+        }
+    }
+}
+
 impl From<super::metadata::Secrets> for Secrets {
     fn from(value: super::metadata::Secrets) -> Self {
         Self {
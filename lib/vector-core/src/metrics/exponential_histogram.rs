@@ -0,0 +1,315 @@
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::configurable_component;
+
+use super::AgentDDSketch;
+use crate::{event::metric::Bucket, float_eq};
+
+/// An exponential histogram, as defined by [OpenTelemetry][otel].
+///
+/// The boundaries of its buckets are the powers of `base = 2^(2^-scale)`: the bucket at index `i`
+/// holds the observations whose absolute value is in `(base^i, base^(i+1)]`. Prometheus native
+/// histograms share this layout, with bucket indexes shifted by one.
+///
+/// [otel]: https://opentelemetry.io/docs/specs/otel/metrics/data-model/#exponentialhistogram
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExponentialHistogram {
+    /// The resolution of the histogram.
+    pub scale: i32,
+
+    /// The number of observations whose absolute value is at most `zero_threshold`.
+    pub zero_count: u64,
+
+    /// The absolute value under which observations are counted in the zero bucket.
+    pub zero_threshold: f64,
+
+    /// The buckets of the positive observations.
+    pub positive: ExponentialBuckets,
+
+    /// The buckets of the negative observations, by absolute value.
+    pub negative: ExponentialBuckets,
+
+    /// The number of observations within the histogram.
+    pub count: u64,
+
+    /// The sum of all observations within the histogram.
+    pub sum: f64,
+
+    /// The minimum value of all observations within the histogram, if known.
+    pub min: Option<f64>,
+
+    /// The maximum value of all observations within the histogram, if known.
+    pub max: Option<f64>,
+}
+
+/// A contiguous range of buckets of an exponential histogram.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExponentialBuckets {
+    /// The index of the first bucket.
+    pub offset: i32,
+
+    /// The number of observations in each bucket, starting with the one at `offset`.
+    pub counts: Vec<u64>,
+}
+
+impl ExponentialHistogram {
+    /// Returns the base of the boundaries of the buckets.
+    pub fn base(&self) -> f64 {
+        2f64.powf(2f64.powi(-self.scale))
+    }
+
+    /// Returns `true` if the histogram holds no observations.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Removes all observations from the histogram, keeping its scale.
+    pub fn clear(&mut self) {
+        self.zero_count = 0;
+        self.positive = ExponentialBuckets::default();
+        self.negative = ExponentialBuckets::default();
+        self.count = 0;
+        self.sum = 0.0;
+        self.min = None;
+        self.max = None;
+    }
+
+    /// Merges another histogram into this one.
+    ///
+    /// Both histograms are brought to the lowest of their scales first. If their zero thresholds
+    /// differ, they can't be merged and `false` is returned.
+    #[must_use]
+    pub fn merge(&mut self, other: &Self) -> bool {
+        if !float_eq(self.zero_threshold, other.zero_threshold) {
+            return false;
+        }
+
+        let scale = self.scale.min(other.scale);
+        self.downscale(scale);
+        let mut other = other.clone();
+        other.downscale(scale);
+
+        self.positive.add(&other.positive);
+        self.negative.add(&other.negative);
+        self.zero_count += other.zero_count;
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = merge_option(self.min, other.min, f64::min);
+        self.max = merge_option(self.max, other.max, f64::max);
+        true
+    }
+
+    /// Lowers the resolution of the histogram to `scale`, merging its buckets accordingly.
+    ///
+    /// Does nothing if the histogram already has a lower resolution.
+    pub fn downscale(&mut self, scale: i32) {
+        let shift = self.scale - scale;
+        if shift > 0 {
+            self.positive.downscale(shift);
+            self.negative.downscale(shift);
+            self.scale = scale;
+        }
+    }
+
+    /// Converts the histogram to explicit buckets, sorted by upper limit.
+    ///
+    /// Each bucket holds the count of its own observations only, as in aggregated histograms.
+    pub fn to_buckets(&self) -> Vec<Bucket> {
+        let base = self.base();
+        let negative = self.negative.iter().rev().map(|(index, count)| Bucket {
+            upper_limit: -base.powi(index),
+            count,
+        });
+        let zero = (self.zero_count > 0).then_some(Bucket {
+            upper_limit: self.zero_threshold,
+            count: self.zero_count,
+        });
+        let positive = self.positive.iter().map(|(index, count)| Bucket {
+            upper_limit: base.powi(index + 1),
+            count,
+        });
+
+        negative.chain(zero).chain(positive).collect()
+    }
+
+    /// Converts the histogram to a sketch, interpolating the observations of each bucket.
+    pub fn to_agent_ddsketch(&self) -> AgentDDSketch {
+        let buckets = self
+            .to_buckets()
+            .into_iter()
+            .map(|bucket| Bucket {
+                count: bucket.count.min(u64::from(u32::MAX)),
+                ..bucket
+            })
+            .collect();
+
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch
+            .insert_interpolate_buckets(buckets)
+            .expect("bucket counts are bounded");
+        sketch
+    }
+}
+
+impl Eq for ExponentialHistogram {}
+
+impl ByteSizeOf for ExponentialHistogram {
+    fn allocated_bytes(&self) -> usize {
+        self.positive.allocated_bytes() + self.negative.allocated_bytes()
+    }
+}
+
+impl ExponentialBuckets {
+    /// Iterates over the index and count of each bucket.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (i32, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| (self.offset + i as i32, count))
+    }
+
+    fn end(&self) -> i32 {
+        self.offset + self.counts.len() as i32
+    }
+
+    fn downscale(&mut self, shift: i32) {
+        let offset = self.offset >> shift;
+        let end = if self.counts.is_empty() {
+            offset
+        } else {
+            ((self.end() - 1) >> shift) + 1
+        };
+
+        let mut counts = vec![0; (end - offset) as usize];
+        for (index, count) in self.iter() {
+            counts[((index >> shift) - offset) as usize] += count;
+        }
+        self.offset = offset;
+        self.counts = counts;
+    }
+
+    fn add(&mut self, other: &Self) {
+        if other.counts.is_empty() {
+            return;
+        }
+        if self.counts.is_empty() {
+            self.clone_from(other);
+            return;
+        }
+
+        let offset = self.offset.min(other.offset);
+        let end = self.end().max(other.end());
+        let mut counts = vec![0; (end - offset) as usize];
+        for (index, count) in self.iter().chain(other.iter()) {
+            counts[(index - offset) as usize] += count;
+        }
+        self.offset = offset;
+        self.counts = counts;
+    }
+}
+
+impl ByteSizeOf for ExponentialBuckets {
+    fn allocated_bytes(&self) -> usize {
+        self.counts.allocated_bytes()
+    }
+}
+
+fn merge_option(a: Option<f64>, b: Option<f64>, merge: fn(f64, f64) -> f64) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(merge(a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn histogram(scale: i32, offset: i32, counts: Vec<u64>) -> ExponentialHistogram {
+        ExponentialHistogram {
+            scale,
+            count: counts.iter().sum(),
+            positive: ExponentialBuckets { offset, counts },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn downscales_buckets() {
+        let mut histogram = histogram(1, -1, vec![1, 2, 3, 4]);
+        histogram.downscale(0);
+
+        assert_eq!(histogram.scale, 0);
+        assert_eq!(
+            histogram.positive,
+            ExponentialBuckets {
+                offset: -1,
+                counts: vec![1, 5, 4],
+            }
+        );
+    }
+
+    #[test]
+    fn merges_histograms_with_different_scales() {
+        let mut a = histogram(0, 0, vec![1, 1]);
+        let b = histogram(1, 4, vec![2, 3]);
+
+        assert!(a.merge(&b));
+        assert_eq!(a.scale, 0);
+        assert_eq!(a.count, 7);
+        assert_eq!(
+            a.positive,
+            ExponentialBuckets {
+                offset: 0,
+                counts: vec![1, 1, 5],
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_different_zero_thresholds() {
+        let mut a = histogram(0, 0, vec![1]);
+        let b = ExponentialHistogram {
+            zero_threshold: 1.0,
+            ..histogram(0, 0, vec![1])
+        };
+
+        assert!(!a.merge(&b));
+    }
+
+    #[test]
+    fn converts_to_sorted_buckets() {
+        let histogram = ExponentialHistogram {
+            scale: 0,
+            zero_count: 1,
+            negative: ExponentialBuckets {
+                offset: 0,
+                counts: vec![2, 3],
+            },
+            ..histogram(0, 1, vec![4])
+        };
+
+        assert_eq!(
+            histogram.to_buckets(),
+            vec![
+                Bucket {
+                    upper_limit: -2.0,
+                    count: 3,
+                },
+                Bucket {
+                    upper_limit: -1.0,
+                    count: 2,
+                },
+                Bucket {
+                    upper_limit: 0.0,
+                    count: 1,
+                },
+                Bucket {
+                    upper_limit: 4.0,
+                    count: 4,
+                },
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod ddsketch;
+mod exponential_histogram;
 mod label_filter;
 mod metric_matcher;
 mod recency;
@@ -15,6 +16,7 @@ use metrics_util::layers::Layer;
 use snafu::Snafu;
 
 pub use self::ddsketch::{AgentDDSketch, BinMap, Config};
+pub use self::exponential_histogram::{ExponentialBuckets, ExponentialHistogram};
 use self::{label_filter::VectorLabelFilter, recorder::Registry, recorder::VectorRecorder};
 use crate::{
     config::metrics_expiration::PerMetricSetExpiration,
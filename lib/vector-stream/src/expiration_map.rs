@@ -14,6 +14,11 @@ impl<T> Emitter<T> {
     pub fn emit(&mut self, value: T) {
         self.values.push(value);
     }
+
+    /// Consumes the emitter, returning the values emitted so far.
+    pub fn into_values(self) -> Vec<T> {
+        self.values
+    }
 }
 
 /// Similar to `stream.filter_map(..).flatten(..)` but also allows checking for expired events
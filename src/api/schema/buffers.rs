@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+use async_graphql::{Object, Subscription};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt, future};
+use vector_lib::{event::Event, tap::topology::TapOutput};
+
+use super::{
+    components::{Component, state},
+    events::{log::Log, metric::Metric, output::OutputEventsPayload, trace::Trace},
+};
+use crate::{
+    buffer::{self, BufferSummary},
+    config::{ComponentKey, OutputId},
+};
+
+/// Summary of the events waiting in the disk buffer of a sink
+pub struct Buffer {
+    output: TapOutput,
+    summary: BufferSummary,
+}
+
+#[Object]
+impl Buffer {
+    /// Id of the sink owning the buffer
+    async fn component_id(&self) -> &str {
+        self.output.output_id.component.id()
+    }
+
+    /// Number of records waiting to be read
+    async fn records(&self) -> i64 {
+        self.summary.records as i64
+    }
+
+    /// Number of events within the waiting records
+    async fn events(&self) -> i64 {
+        self.summary.events as i64
+    }
+
+    /// Size of the waiting records, in bytes
+    async fn bytes(&self) -> i64 {
+        self.summary.bytes as i64
+    }
+
+    /// Number of records that could not be read or decoded
+    async fn corrupted_records(&self) -> i64 {
+        self.summary.corrupted_records as i64
+    }
+
+    /// Timestamp of the oldest log or metric event
+    async fn oldest_timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.summary.oldest_timestamp.as_ref()
+    }
+
+    /// Timestamp of the newest log or metric event
+    async fn newest_timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.summary.newest_timestamp.as_ref()
+    }
+
+    /// Oldest events of the buffer
+    async fn samples(&self) -> Vec<OutputEventsPayload> {
+        self.summary
+            .samples
+            .iter()
+            .map(|event| to_output_event(&self.output, event.clone()))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct BuffersQuery;
+
+#[Object]
+impl BuffersQuery {
+    /// Summary of the disk buffer of a sink, read without modifying it
+    async fn buffer(
+        &self,
+        component_id: String,
+        #[graphql(default = 10, validator(maximum = 1_000))] samples: u32,
+    ) -> async_graphql::Result<Option<Buffer>> {
+        let Some((output, data_dir)) = sink_buffer(&component_id) else {
+            return Ok(None);
+        };
+
+        let summary = buffer::summarize_buffer(&data_dir, samples as usize)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+        Ok(Some(Buffer { output, summary }))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BuffersSubscription;
+
+#[Subscription]
+impl BuffersSubscription {
+    /// A stream of the events waiting in the disk buffer of a sink, from the oldest to the newest.
+    /// The stream ends once all of them have been replayed.
+    async fn buffer_events_by_component_id(
+        &self,
+        component_id: String,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>>> {
+        let (output, data_dir) = sink_buffer(&component_id)
+            .ok_or_else(|| format!("Sink \"{component_id}\" does not have a disk buffer."))?;
+
+        Ok(buffer::buffer_events(data_dir)
+            .take_while(|events| {
+                if let Err(error) = events {
+                    error!(message = "Failed to read buffer.", %error);
+                }
+                future::ready(events.is_ok())
+            })
+            .filter_map(move |events| {
+                future::ready(events.ok().map(|events| {
+                    events
+                        .into_iter()
+                        .map(|event| to_output_event(&output, event))
+                        .collect()
+                }))
+            }))
+    }
+}
+
+/// Gets the sink with the given ID, along with the directory of its disk buffer.
+fn sink_buffer(component_id: &str) -> Option<(TapOutput, PathBuf)> {
+    let component_key = ComponentKey::from(component_id);
+    let Component::Sink(sink) = state::component_by_component_key(&component_key)? else {
+        return None;
+    };
+    let data_dir = sink.get_buffer_data_dir()?.to_path_buf();
+
+    let output = TapOutput {
+        output_id: OutputId::from(component_key),
+        component_kind: "sink",
+        component_type: sink.get_component_type().to_string(),
+    };
+    Some((output, data_dir))
+}
+
+fn to_output_event(output: &TapOutput, event: Event) -> OutputEventsPayload {
+    match event {
+        Event::Log(log) => OutputEventsPayload::Log(Log::new(output.clone(), log)),
+        Event::Metric(metric) => OutputEventsPayload::Metric(Metric::new(output.clone(), metric)),
+        Event::Trace(trace) => OutputEventsPayload::Trace(Trace::new(output.clone(), trace)),
+    }
+}
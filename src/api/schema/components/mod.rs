@@ -19,6 +19,7 @@ use crate::{
         filter::{self, filter_items},
         relay, sort,
     },
+    buffer,
     config::{ComponentKey, Config, get_transform_output_ids},
     filter_check,
 };
@@ -322,6 +323,11 @@ pub fn update_config(config: &Config) {
                 component_key: component_key.clone(),
                 component_type: sink.inner.get_component_name().to_string(),
                 inputs: sink.inputs.clone(),
+                buffer_data_dir: buffer::buffer_data_dir(
+                    &sink.buffer,
+                    config.global.data_dir.clone(),
+                    component_key,
+                ),
             })),
         );
     }
@@ -395,6 +401,7 @@ mod tests {
                 component_key: ComponentKey::from("devnull"),
                 component_type: "blackhole".to_string(),
                 inputs: vec![OutputId::from("gen3"), OutputId::from("parse_json")].into(),
+                buffer_data_dir: None,
             })),
         ]
     }
@@ -519,11 +526,13 @@ mod tests {
                 component_key: ComponentKey::from("a"),
                 component_type: "blackhole".to_string(),
                 inputs: vec![OutputId::from("gen3"), OutputId::from("parse_json")].into(),
+                buffer_data_dir: None,
             })),
             Component::Sink(sink::Sink(sink::Data {
                 component_key: ComponentKey::from("b"),
                 component_type: "blackhole".to_string(),
                 inputs: vec![OutputId::from("gen3"), OutputId::from("parse_json")].into(),
+                buffer_data_dir: None,
             })),
             Component::Transform(transform::Transform(transform::Data {
                 component_key: ComponentKey::from("c"),
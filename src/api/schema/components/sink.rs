@@ -1,4 +1,7 @@
-use std::cmp;
+use std::{
+    cmp,
+    path::{Path, PathBuf},
+};
 
 use async_graphql::{Enum, InputObject, Object};
 
@@ -18,6 +21,8 @@ pub struct Data {
     pub component_key: ComponentKey,
     pub component_type: String,
     pub inputs: Inputs<OutputId>,
+    /// The directory of the disk buffer of the sink, if it has one.
+    pub buffer_data_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +36,10 @@ impl Sink {
     pub const fn get_component_type(&self) -> &str {
         self.0.component_type.as_str()
     }
+
+    pub fn get_buffer_data_dir(&self) -> Option<&Path> {
+        self.0.buffer_data_dir.as_deref()
+    }
 }
 
 #[derive(Default, InputObject)]
@@ -130,16 +139,19 @@ mod tests {
                 component_key: ComponentKey::from("webserver"),
                 component_type: "http".to_string(),
                 inputs: Inputs::default(),
+                buffer_data_dir: None,
             }),
             Sink(Data {
                 component_key: ComponentKey::from("db"),
                 component_type: "clickhouse".to_string(),
                 inputs: Inputs::default(),
+                buffer_data_dir: None,
             }),
             Sink(Data {
                 component_key: ComponentKey::from("zip_drive"),
                 component_type: "file".to_string(),
                 inputs: Inputs::default(),
+                buffer_data_dir: None,
             }),
         ]
     }
@@ -1,3 +1,4 @@
+mod buffers;
 pub mod components;
 pub mod events;
 pub mod filter;
@@ -15,6 +16,7 @@ pub struct Query(
     components::ComponentsQuery,
     #[cfg(feature = "sources-host_metrics")] metrics::MetricsQuery,
     meta::MetaQuery,
+    buffers::BuffersQuery,
 );
 
 #[derive(MergedSubscription, Default)]
@@ -23,6 +25,7 @@ pub struct Subscription(
     metrics::MetricsSubscription,
     components::ComponentsSubscription,
     events::EventsSubscription,
+    buffers::BuffersSubscription,
 );
 
 /// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
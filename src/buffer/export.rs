@@ -0,0 +1,93 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use clap::Parser;
+use vector_lib::{
+    buffers::BufferInspector,
+    event::{EventArray, EventContainer},
+};
+
+use super::{BufferLocation, repair::validate_buffer};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub(super) struct Opts {
+    #[command(flatten)]
+    location: BufferLocation,
+
+    /// The file to write the events to, instead of the standard output.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// Exports the events of a stopped buffer as native JSON, one event per line.
+///
+/// Records are read from the positions rebuilt from the data files, so that events can be
+/// salvaged even when the ledger is lost or corrupted. The buffer is not modified.
+pub(super) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let (repair, report) = match validate_buffer(&opts.location).await {
+        Ok(validated) => validated,
+        Err(code) => return code,
+    };
+
+    let output: Box<dyn Write + Send> = match &opts.output {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("Failed to create {}: {error}", path.display());
+                }
+                return exitcode::CANTCREAT;
+            }
+        },
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+
+    let inspector = BufferInspector::<EventArray>::with_positions(repair.data_dir(), report.ledger);
+    match export_events(inspector, output).await {
+        Ok(events) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Exported {events} events.");
+            }
+            exitcode::OK
+        }
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Failed to export events: {error}");
+            }
+            exitcode::IOERR
+        }
+    }
+}
+
+async fn export_events(
+    mut inspector: BufferInspector<EventArray>,
+    mut output: Box<dyn Write + Send>,
+) -> crate::Result<u64> {
+    let mut events = 0;
+    while let Some(record) = inspector.next().await? {
+        match record.value {
+            Ok(array) => {
+                for event in array.into_events() {
+                    serde_json::to_writer(&mut output, &event)?;
+                    output.write_all(b"\n")?;
+                    events += 1;
+                }
+            }
+            Err(error) => warn!(
+                message = "Skipping corrupted buffer record.",
+                data_file_id = record.data_file_id,
+                %error,
+            ),
+        }
+    }
+    output.flush()?;
+
+    Ok(events)
+}
@@ -0,0 +1,185 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use futures::Stream;
+use serde::Serialize;
+use vector_lib::{
+    buffers::{BufferInspector, InspectedRecord},
+    event::{Event, EventArray, EventContainer},
+};
+
+use super::BufferLocation;
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub(super) struct Opts {
+    #[command(flatten)]
+    location: BufferLocation,
+
+    /// The number of events to print as samples, starting with the oldest ones.
+    #[arg(long, default_value_t = 10)]
+    samples: usize,
+
+    /// Set the output format.
+    #[arg(long, default_value = "text")]
+    format: OutputFormat,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// A summary of the events waiting in a disk buffer.
+#[derive(Debug, Default, Serialize)]
+pub struct BufferSummary {
+    /// The number of records waiting to be read.
+    pub records: u64,
+    /// The number of events within these records.
+    pub events: u64,
+    /// The size of these records in the data files, in bytes.
+    pub bytes: u64,
+    /// The number of records that could not be read or decoded.
+    pub corrupted_records: u64,
+    /// The timestamp of the oldest log or metric event, if any.
+    pub oldest_timestamp: Option<DateTime<Utc>>,
+    /// The timestamp of the newest log or metric event, if any.
+    pub newest_timestamp: Option<DateTime<Utc>>,
+    /// The oldest events of the buffer.
+    pub samples: Vec<Event>,
+}
+
+impl BufferSummary {
+    fn add_event(&mut self, event: Event, samples: usize) {
+        self.events += 1;
+        if let Some(timestamp) = event_timestamp(&event) {
+            self.oldest_timestamp = Some(
+                self.oldest_timestamp
+                    .map_or(timestamp, |t| t.min(timestamp)),
+            );
+            self.newest_timestamp = Some(
+                self.newest_timestamp
+                    .map_or(timestamp, |t| t.max(timestamp)),
+            );
+        }
+        if self.samples.len() < samples {
+            self.samples.push(event);
+        }
+    }
+}
+
+/// Summarizes the events waiting in the disk buffer stored in `data_dir`, keeping up to `samples`
+/// of them.
+///
+/// The buffer is not modified, so it can be summarized while Vector is running.
+pub async fn summarize_buffer(data_dir: &Path, samples: usize) -> crate::Result<BufferSummary> {
+    let mut inspector = BufferInspector::<EventArray>::open(data_dir).await?;
+    let mut summary = BufferSummary::default();
+
+    while let Some(record) = inspector.next().await? {
+        let size = record.size;
+        let Some(events) = decoded_events(record) else {
+            summary.corrupted_records += 1;
+            continue;
+        };
+        summary.records += 1;
+        summary.bytes += size;
+        for event in events.into_events() {
+            summary.add_event(event, samples);
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Streams the events waiting in the disk buffer stored in `data_dir`, one record at a time,
+/// starting with the oldest one.
+///
+/// Corrupted records are skipped. The buffer is not modified.
+pub fn buffer_events(data_dir: PathBuf) -> impl Stream<Item = crate::Result<Vec<Event>>> {
+    async_stream::try_stream! {
+        let mut inspector = BufferInspector::<EventArray>::open(data_dir).await?;
+        while let Some(record) = inspector.next().await? {
+            if let Some(events) = decoded_events(record) {
+                yield events.into_events().collect();
+            }
+        }
+    }
+}
+
+fn decoded_events(record: InspectedRecord<EventArray>) -> Option<EventArray> {
+    match record.value {
+        Ok(events) => Some(events),
+        Err(error) => {
+            warn!(
+                message = "Skipping corrupted buffer record.",
+                data_file_id = record.data_file_id,
+                %error,
+            );
+            None
+        }
+    }
+}
+
+fn event_timestamp(event: &Event) -> Option<DateTime<Utc>> {
+    match event {
+        Event::Log(log) => log.get_timestamp()?.as_timestamp().copied(),
+        Event::Metric(metric) => metric.timestamp(),
+        Event::Trace(_) => None,
+    }
+}
+
+pub(super) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let data_dir = match opts.location.data_dir() {
+        Ok(data_dir) => data_dir,
+        Err(code) => return code,
+    };
+
+    let summary = match summarize_buffer(&data_dir, opts.samples).await {
+        Ok(summary) => summary,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!(
+                    "Failed to inspect buffer at {}: {error}",
+                    data_dir.display()
+                );
+            }
+            return exitcode::IOERR;
+        }
+    };
+
+    #[allow(clippy::print_stdout)]
+    match opts.format {
+        OutputFormat::Text => {
+            let timestamp = |timestamp: Option<DateTime<Utc>>| {
+                timestamp.map_or_else(|| "unknown".to_string(), |t| t.to_rfc3339())
+            };
+            println!("Buffer: {}", data_dir.display());
+            println!("Records: {} ({} bytes)", summary.records, summary.bytes);
+            println!("Events: {}", summary.events);
+            println!("Corrupted records: {}", summary.corrupted_records);
+            println!("Oldest event: {}", timestamp(summary.oldest_timestamp));
+            println!("Newest event: {}", timestamp(summary.newest_timestamp));
+            if !summary.samples.is_empty() {
+                println!("\nSamples:");
+                for event in &summary.samples {
+                    println!(
+                        "{}",
+                        serde_json::to_string(event).expect("events serialize to JSON")
+                    );
+                }
+            }
+        }
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string(&summary).expect("events serialize to JSON")
+            );
+        }
+    }
+
+    exitcode::OK
+}
@@ -0,0 +1,178 @@
+//! Commands operating on the disk buffers of sinks.
+//!
+//! Buffers can be inspected while Vector is running, but can only be validated, repaired, or
+//! exported once it is stopped.
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use vector_lib::buffers::BufferConfig;
+
+use crate::config::{self, ComponentKey};
+
+mod export;
+mod inspect;
+mod repair;
+
+pub use inspect::{BufferSummary, buffer_events, summarize_buffer};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Inspect the disk buffer of a sink without modifying it.
+    ///
+    /// The buffer can be inspected while Vector is running.
+    Inspect(inspect::Opts),
+
+    /// Validate the checksum of every record of a stopped disk buffer, and compare its ledger to
+    /// the one rebuilt from its data files.
+    Validate(repair::Opts),
+
+    /// Repair a stopped disk buffer, truncating the corrupted tails of its data files and
+    /// rebuilding its ledger from them.
+    Repair(repair::Opts),
+
+    /// Export the events of a stopped disk buffer as native JSON, one event per line.
+    ///
+    /// Events are read from the ledger rebuilt from the data files, so that they can be salvaged
+    /// even when the ledger is corrupted.
+    Export(export::Opts),
+}
+
+/// The location of a disk buffer, given either by a sink of a configuration or by its directory.
+#[derive(Args, Debug)]
+#[command(rename_all = "kebab-case")]
+struct BufferLocation {
+    /// The ID of the sink whose buffer is used, as found in the configuration.
+    #[arg(required_unless_present = "buffer_dir", conflicts_with = "buffer_dir")]
+    component_id: Option<String>,
+
+    /// The directory of the buffer, used instead of a sink of the configuration.
+    #[arg(long)]
+    buffer_dir: Option<PathBuf>,
+
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.yaml` will be targeted.
+    #[arg(
+        id = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        value_delimiter(',')
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    config_dirs: Vec<PathBuf>,
+}
+
+impl BufferLocation {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+
+    /// Resolves the directory of the buffer, loading the configuration if needed.
+    fn data_dir(&self) -> Result<PathBuf, exitcode::ExitCode> {
+        if let Some(buffer_dir) = &self.buffer_dir {
+            return Ok(buffer_dir.clone());
+        }
+        let component_key = ComponentKey::from(self.component_id.as_deref().unwrap_or_default());
+
+        let paths = self.paths_with_formats();
+        let Some(paths) = config::process_paths(&paths) else {
+            return Err(exitcode::CONFIG);
+        };
+        let config = match config::load_from_paths(&paths) {
+            Ok(config) => config,
+            Err(errs) => {
+                #[allow(clippy::print_stderr)]
+                for err in errs {
+                    eprintln!("{err}");
+                }
+                return Err(exitcode::CONFIG);
+            }
+        };
+
+        let Some(sink) = config.sink(&component_key) else {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Sink \"{component_key}\" not found in the configuration.");
+            }
+            return Err(exitcode::CONFIG);
+        };
+        buffer_data_dir(&sink.buffer, config.global.data_dir.clone(), &component_key).ok_or_else(
+            || {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("Sink \"{component_key}\" does not have a disk buffer.");
+                }
+                exitcode::CONFIG
+            },
+        )
+    }
+}
+
+/// Gets the directory of the disk buffer of a sink, if it has one.
+pub fn buffer_data_dir(
+    buffer: &BufferConfig,
+    global_data_dir: Option<PathBuf>,
+    component_key: &ComponentKey,
+) -> Option<PathBuf> {
+    buffer.stages().iter().find_map(|stage| {
+        stage
+            .disk_usage(global_data_dir.clone(), component_key)
+            .map(|usage| usage.data_dir().to_path_buf())
+    })
+}
+
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.command {
+        Command::Inspect(opts) => inspect::cmd(opts).await,
+        Command::Validate(opts) => repair::validate(opts).await,
+        Command::Repair(opts) => repair::repair(opts).await,
+        Command::Export(opts) => export::cmd(opts).await,
+    }
+}
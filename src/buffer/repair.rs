@@ -0,0 +1,111 @@
+use clap::Parser;
+use vector_lib::{
+    buffers::{BufferRepair, LedgerPositions, RepairReport},
+    event::EventArray,
+};
+
+use super::BufferLocation;
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub(super) struct Opts {
+    #[command(flatten)]
+    location: BufferLocation,
+}
+
+/// Validates every record of a stopped buffer, without modifying it.
+pub(super) async fn validate(opts: &Opts) -> exitcode::ExitCode {
+    let (_, report) = match validate_buffer(&opts.location).await {
+        Ok(validated) => validated,
+        Err(code) => return code,
+    };
+    print_report(&report);
+
+    if report.is_corrupted() || report.is_ledger_outdated() {
+        exitcode::DATAERR
+    } else {
+        exitcode::OK
+    }
+}
+
+/// Truncates the corrupted tails of the data files of a stopped buffer, and rebuilds its ledger.
+pub(super) async fn repair(opts: &Opts) -> exitcode::ExitCode {
+    let (repair, report) = match validate_buffer(&opts.location).await {
+        Ok(validated) => validated,
+        Err(code) => return code,
+    };
+    print_report(&report);
+
+    #[allow(clippy::print_stdout, clippy::print_stderr)]
+    if !report.is_corrupted() && !report.is_ledger_outdated() {
+        println!("\nNothing to repair.");
+    } else if let Err(error) = repair.repair(&report).await {
+        eprintln!("Failed to repair buffer: {error}");
+        return exitcode::IOERR;
+    } else {
+        println!("\nBuffer repaired.");
+    }
+
+    exitcode::OK
+}
+
+/// Locks the buffer and validates it, reporting errors on the way.
+#[allow(clippy::print_stderr)]
+pub(super) async fn validate_buffer(
+    location: &BufferLocation,
+) -> Result<(BufferRepair<EventArray>, RepairReport), exitcode::ExitCode> {
+    let data_dir = location.data_dir()?;
+    let result = match BufferRepair::open(&data_dir) {
+        Ok(repair) => repair.validate().await.map(|report| (repair, report)),
+        Err(error) => Err(error),
+    };
+
+    result.map_err(|error| {
+        eprintln!(
+            "Failed to validate buffer at {}: {error}",
+            data_dir.display()
+        );
+        exitcode::IOERR
+    })
+}
+
+#[allow(clippy::print_stdout)]
+fn print_report(report: &RepairReport) {
+    for data_file in &report.data_files {
+        print!(
+            "Data file {}: {} records, {} events, {} bytes",
+            data_file.data_file_id, data_file.records, data_file.events, data_file.valid_bytes
+        );
+        match &data_file.corruption {
+            Some(reason) => println!(
+                ", followed by {} corrupted bytes ({reason})",
+                data_file.corrupted_bytes()
+            ),
+            None => println!(),
+        }
+    }
+
+    match report.previous_ledger {
+        Some(_) if !report.is_ledger_outdated() => println!("Ledger: valid"),
+        Some(previous) => {
+            println!("Ledger: outdated");
+            print_positions("Current", &previous);
+            print_positions("Rebuilt", &report.ledger);
+        }
+        None => {
+            println!("Ledger: missing or corrupted");
+            print_positions("Rebuilt", &report.ledger);
+        }
+    }
+}
+
+#[allow(clippy::print_stdout)]
+fn print_positions(name: &str, positions: &LedgerPositions) {
+    println!(
+        "  {name}: writer at record {} in data file {}, reader after record {} in data file {}",
+        positions.writer_next_record_id,
+        positions.writer_current_data_file_id,
+        positions.reader_last_record_id,
+        positions.reader_current_data_file_id,
+    );
+}
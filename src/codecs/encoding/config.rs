@@ -1,6 +1,6 @@
 use crate::codecs::Transformer;
 use vector_lib::codecs::{
-    CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
+    BytesEncoder, CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
     encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
 };
 use vector_lib::configurable::configurable_component;
@@ -38,11 +38,20 @@ impl EncodingConfig {
     }
 
     /// Build the `Serializer` for this config.
+    ///
+    /// This is used by sinks encoding each event on its own, so codecs that can only encode whole
+    /// batches of events are rejected.
     pub fn build(&self) -> crate::Result<Serializer> {
+        if let SerializerConfig::Parquet(_) = self.encoding {
+            return Err(BATCH_ONLY_CODEC_ERROR.into());
+        }
         self.encoding.build()
     }
 }
 
+const BATCH_ONLY_CODEC_ERROR: &str = "The `parquet` codec can only be used by sinks writing \
+    batches of events as objects, such as `aws_s3`.";
+
 impl<T> From<T> for EncodingConfig
 where
     T: Into<SerializerConfig>,
@@ -97,7 +106,11 @@ impl EncodingConfigWithFraming {
     /// Build the `Framer` and `Serializer` for this config.
     pub fn build(&self, sink_type: SinkType) -> crate::Result<(Framer, Serializer)> {
         let framer = self.framing.as_ref().map(|framing| framing.build());
-        let serializer = self.encoding.build()?;
+        let serializer = match sink_type {
+            SinkType::StreamBased => self.encoding.build()?,
+            // Message based sinks encode whole batches, which is what batch-only codecs need.
+            SinkType::MessageBased => self.encoding.encoding.build()?,
+        };
 
         let framer = match (framer, &serializer) {
             (Some(framer), _) => framer,
@@ -108,11 +121,21 @@ impl EncodingConfigWithFraming {
             (None, Serializer::Avro(_) | Serializer::Native(_)) => {
                 LengthDelimitedEncoder::default().into()
             }
+            (None, Serializer::Cbor(_) | Serializer::Msgpack(_)) => match sink_type {
+                SinkType::StreamBased => LengthDelimitedEncoder::default().into(),
+                // Both formats are self-delimiting, so a batch of values can simply be
+                // concatenated.
+                SinkType::MessageBased => BytesEncoder.into(),
+            },
             (None, Serializer::Gelf(_)) => {
                 // Graylog/GELF always uses null byte delimiter on TCP, see
                 // https://github.com/Graylog2/graylog2-server/issues/1240
                 CharacterDelimitedEncoder::new(0).into()
             }
+            (None, Serializer::Parquet(_)) => {
+                // Parquet files are self-contained and must not be delimited.
+                BytesEncoder.into()
+            }
             (None, Serializer::Protobuf(_)) => {
                 // Protobuf uses length-delimited messages, see:
                 // https://developers.google.com/protocol-buffers/docs/techniques#streaming
@@ -247,4 +270,21 @@ mod test {
         assert_eq!(transformer.except_fields(), &Some(vec!["ignore_me".into()]));
         assert_eq!(transformer.timestamp_format(), &Some(TimestampFormat::Unix));
     }
+
+    #[test]
+    fn reject_parquet_for_sinks_encoding_single_events() {
+        let encoding = EncodingConfig::from(SerializerConfig::Parquet(Default::default()));
+        assert!(encoding.build().is_err());
+
+        let encoding = EncodingConfigWithFraming::new(
+            None,
+            SerializerConfig::Parquet(Default::default()),
+            Default::default(),
+        );
+        assert!(encoding.build(SinkType::StreamBased).is_err());
+
+        let (framer, serializer) = encoding.build(SinkType::MessageBased).unwrap();
+        assert!(matches!(framer, Framer::Bytes(_)));
+        assert!(matches!(serializer, Serializer::Parquet(_)));
+    }
 }
@@ -116,6 +116,9 @@ impl Encoder<Framer> {
                 Framer::CharacterDelimited(CharacterDelimitedEncoder { delimiter: b',' }),
             ) => "application/json",
             (Serializer::Native(_), _) | (Serializer::Protobuf(_), _) => "application/octet-stream",
+            (Serializer::Parquet(_), _) => "application/vnd.apache.parquet",
+            (Serializer::Msgpack(_), _) => "application/msgpack",
+            (Serializer::Cbor(_), _) => "application/cbor",
             (
                 Serializer::Avro(_)
                 | Serializer::Cef(_)
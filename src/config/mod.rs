@@ -405,6 +405,62 @@ pub struct TestDefinition<T: 'static = OutputId> {
     /// A set of component outputs that should not have emitted any events.
     #[serde(default)]
     pub no_outputs_from: Vec<T>,
+
+    /// An ordered set of steps inserting input events and advancing a simulated clock.
+    ///
+    /// When set, the test runs on a simulated clock that only advances through the steps, so that
+    /// transforms depending on time, such as `reduce` or `aggregate`, can be tested. Input events
+    /// are then inserted by the steps, instead of `inputs`.
+    #[serde(default)]
+    pub timeline: Vec<TestStep<T>>,
+
+    /// Expected output events flushed when the test shuts down, after the last step of its
+    /// timeline.
+    pub shutdown: Option<TestShutdown<T>>,
+}
+
+/// A step of a unit test timeline.
+///
+/// A step first advances the simulated clock, then inserts its input events, and finally checks the
+/// events emitted by the transforms since the previous step.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TestStep<T: 'static = OutputId> {
+    /// The duration, in milliseconds, to advance the simulated clock by.
+    ///
+    /// Timers of the transforms, such as flush intervals, fire as the clock advances.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = 1000))]
+    pub advance_ms: u64,
+
+    /// A set of input events to insert once the clock has advanced.
+    ///
+    /// Their timestamp is set to the time of the simulated clock, unless they already have one.
+    #[serde(default)]
+    pub inputs: Vec<TestInput>,
+
+    /// A set of expected output events emitted during this step.
+    #[serde(default)]
+    pub outputs: Vec<TestOutput<T>>,
+
+    /// A set of component outputs that should not have emitted any events during this step.
+    #[serde(default)]
+    pub no_outputs_from: Vec<T>,
+}
+
+/// The expected output events flushed when a unit test shuts down.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TestShutdown<T: 'static = OutputId> {
+    /// A set of expected output events flushed on shutdown.
+    #[serde(default)]
+    pub outputs: Vec<TestOutput<T>>,
+
+    /// A set of component outputs that should not have flushed any events on shutdown.
+    #[serde(default)]
+    pub no_outputs_from: Vec<T>,
 }
 
 impl TestDefinition<String> {
@@ -418,29 +474,75 @@ impl TestDefinition<String> {
             inputs,
             outputs,
             no_outputs_from,
+            timeline,
+            shutdown,
         } = self;
         let mut errors = Vec::new();
 
         let output_map = graph.input_map().expect("ambiguous outputs");
+        let mut resolver = OutputResolver {
+            name: &name,
+            output_map: &output_map,
+            errors: &mut errors,
+        };
+
+        let outputs = resolver.outputs(outputs);
+        let no_outputs_from = resolver.no_outputs_from(no_outputs_from);
+        let timeline = timeline
+            .into_iter()
+            .map(|step| TestStep {
+                advance_ms: step.advance_ms,
+                inputs: step.inputs,
+                outputs: resolver.outputs(step.outputs),
+                no_outputs_from: resolver.no_outputs_from(step.no_outputs_from),
+            })
+            .collect();
+        let shutdown = shutdown.map(|shutdown| TestShutdown {
+            outputs: resolver.outputs(shutdown.outputs),
+            no_outputs_from: resolver.no_outputs_from(shutdown.no_outputs_from),
+        });
+
+        if errors.is_empty() {
+            Ok(TestDefinition {
+                name,
+                input,
+                inputs,
+                outputs,
+                no_outputs_from,
+                timeline,
+                shutdown,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Resolves the component outputs referenced by a unit test.
+struct OutputResolver<'a> {
+    name: &'a str,
+    output_map: &'a HashMap<String, OutputId>,
+    errors: &'a mut Vec<String>,
+}
 
-        let outputs = outputs
+impl OutputResolver<'_> {
+    fn outputs(&mut self, outputs: Vec<TestOutput<String>>) -> Vec<TestOutput<OutputId>> {
+        outputs
             .into_iter()
-            .map(|old| {
+            .filter_map(|old| {
                 let TestOutput {
                     extract_from,
                     conditions,
                 } = old;
 
-                (extract_from.to_vec(), conditions)
-            })
-            .filter_map(|(extract_from, conditions)| {
                 let mut outputs = Vec::new();
-                for from in extract_from {
-                    if let Some(output_id) = output_map.get(&from) {
+                for from in extract_from.to_vec() {
+                    if let Some(output_id) = self.output_map.get(&from) {
                         outputs.push(output_id.clone());
                     } else {
-                        errors.push(format!(
-                            r#"Invalid extract_from target in test '{name}': '{from}' does not exist"#
+                        self.errors.push(format!(
+                            r#"Invalid extract_from target in test '{}': '{from}' does not exist"#,
+                            self.name
                         ));
                     }
                 }
@@ -453,33 +555,24 @@ impl TestDefinition<String> {
                     })
                 }
             })
-            .collect();
+            .collect()
+    }
 
-        let no_outputs_from = no_outputs_from
+    fn no_outputs_from(&mut self, no_outputs_from: Vec<String>) -> Vec<OutputId> {
+        no_outputs_from
             .into_iter()
             .filter_map(|o| {
-                if let Some(output_id) = output_map.get(&o) {
+                if let Some(output_id) = self.output_map.get(&o) {
                     Some(output_id.clone())
                 } else {
-                    errors.push(format!(
-                        r#"Invalid no_outputs_from target in test '{name}': '{o}' does not exist"#
+                    self.errors.push(format!(
+                        r#"Invalid no_outputs_from target in test '{}': '{o}' does not exist"#,
+                        self.name
                     ));
                     None
                 }
             })
-            .collect();
-
-        if errors.is_empty() {
-            Ok(TestDefinition {
-                name,
-                input,
-                inputs,
-                outputs,
-                no_outputs_from,
-            })
-        } else {
-            Err(errors)
-        }
+            .collect()
     }
 }
 
@@ -491,34 +584,56 @@ impl TestDefinition<OutputId> {
             inputs,
             outputs,
             no_outputs_from,
+            timeline,
+            shutdown,
         } = self;
 
-        let outputs = outputs
+        let timeline = timeline
             .into_iter()
-            .map(|old| TestOutput {
-                extract_from: old
-                    .extract_from
-                    .to_vec()
-                    .into_iter()
-                    .map(|item| item.to_string())
-                    .collect::<Vec<_>>()
-                    .into(),
-                conditions: old.conditions,
+            .map(|step| TestStep {
+                advance_ms: step.advance_ms,
+                inputs: step.inputs,
+                outputs: stringify_outputs(step.outputs),
+                no_outputs_from: stringify_no_outputs_from(&step.no_outputs_from),
             })
             .collect();
-
-        let no_outputs_from = no_outputs_from.iter().map(ToString::to_string).collect();
+        let shutdown = shutdown.map(|shutdown| TestShutdown {
+            outputs: stringify_outputs(shutdown.outputs),
+            no_outputs_from: stringify_no_outputs_from(&shutdown.no_outputs_from),
+        });
 
         TestDefinition {
             name,
             input,
             inputs,
-            outputs,
-            no_outputs_from,
+            outputs: stringify_outputs(outputs),
+            no_outputs_from: stringify_no_outputs_from(&no_outputs_from),
+            timeline,
+            shutdown,
         }
     }
 }
 
+fn stringify_outputs(outputs: Vec<TestOutput<OutputId>>) -> Vec<TestOutput<String>> {
+    outputs
+        .into_iter()
+        .map(|old| TestOutput {
+            extract_from: old
+                .extract_from
+                .to_vec()
+                .into_iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>()
+                .into(),
+            conditions: old.conditions,
+        })
+        .collect()
+}
+
+fn stringify_no_outputs_from(no_outputs_from: &[OutputId]) -> Vec<String> {
+    no_outputs_from.iter().map(ToString::to_string).collect()
+}
+
 /// A unit test input.
 ///
 /// An input describes not only the type of event to insert, but also which transform within the
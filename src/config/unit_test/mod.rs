@@ -9,36 +9,42 @@
     feature = "sinks-console"
 ))]
 mod tests;
+mod timeline;
 mod unit_test_components;
 
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
-    sync::Arc,
+    sync::{Arc, atomic::AtomicUsize},
+    time::Duration,
 };
 
 use futures_util::{StreamExt, stream::FuturesUnordered};
 use indexmap::IndexMap;
 use tokio::sync::{
-    Mutex,
+    Mutex, mpsc,
     oneshot::{self, Receiver},
 };
+use tokio_stream::wrappers::UnboundedReceiverStream;
 use uuid::Uuid;
+use vector_lib::config::log_schema;
 use vrl::{
     compiler::{Context, TargetValue, TimeZone, state::RuntimeState},
     diagnostic::Formatter,
+    path::parse_target_path,
     value,
 };
 
+use self::timeline::{Timeline, TimelineStep};
 pub use self::unit_test_components::{
-    UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult, UnitTestSourceConfig,
-    UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
+    UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult, UnitTestSinkStep,
+    UnitTestSourceConfig, UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
 };
 use super::{OutputId, compiler::expand_globs, graph::Graph, transform::get_transform_output_ids};
 use crate::{
     conditions::Condition,
     config::{
         self, ComponentKey, Config, ConfigBuilder, ConfigPath, SinkOuter, SourceOuter,
-        TestDefinition, TestInput, TestOutput, loading,
+        TestDefinition, TestInput, TestOutput, TestStep, loading,
     },
     event::{Event, EventMetadata, LogEvent},
     signal,
@@ -48,38 +54,53 @@ use crate::{
 pub struct UnitTest {
     pub name: String,
     config: Config,
-    pieces: TopologyPieces,
+    topology: UnitTestTopology,
     test_result_rxs: Vec<Receiver<UnitTestSinkResult>>,
 }
 
+enum UnitTestTopology {
+    /// The topology built along with the test.
+    Built(TopologyPieces),
+    /// The timeline of the test, which builds the topology on its simulated clock.
+    Timeline(Timeline),
+}
+
 pub struct UnitTestResult {
     pub errors: Vec<String>,
 }
 
 impl UnitTest {
     pub async fn run(self) -> UnitTestResult {
-        let diff = config::ConfigDiff::initial(&self.config);
-        let (topology, _) = RunningTopology::start_validated(self.config, diff, self.pieces)
-            .await
-            .unwrap();
-        topology.sources_finished().await;
-        let _stop_complete = topology.stop();
+        let errors = match self.topology {
+            UnitTestTopology::Built(pieces) => {
+                let diff = config::ConfigDiff::initial(&self.config);
+                let (topology, _) = RunningTopology::start_validated(self.config, diff, pieces)
+                    .await
+                    .unwrap();
+                topology.sources_finished().await;
+                let _stop_complete = topology.stop();
+
+                collect_results(self.test_result_rxs).await
+            }
+            UnitTestTopology::Timeline(timeline) => {
+                timeline.run(self.config, self.test_result_rxs).await
+            }
+        };
 
-        let mut in_flight = self
-            .test_result_rxs
-            .into_iter()
-            .collect::<FuturesUnordered<_>>();
+        UnitTestResult { errors }
+    }
+}
 
-        let mut errors = Vec::new();
-        while let Some(partial_result) = in_flight.next().await {
-            let partial_result = partial_result.expect(
-                "An unexpected error occurred while executing unit tests. Please try again.",
-            );
-            errors.extend(partial_result.test_errors);
-        }
+async fn collect_results(test_result_rxs: Vec<Receiver<UnitTestSinkResult>>) -> Vec<String> {
+    let mut in_flight = test_result_rxs.into_iter().collect::<FuturesUnordered<_>>();
 
-        UnitTestResult { errors }
+    let mut errors = Vec::new();
+    while let Some(partial_result) = in_flight.next().await {
+        let partial_result = partial_result
+            .expect("An unexpected error occurred while executing unit tests. Please try again.");
+        errors.extend(partial_result.test_errors);
     }
+    errors
 }
 
 /// Loads Log Schema from configurations and sets global schema.
@@ -261,6 +282,38 @@ impl UnitTestBuildMetadata {
             .collect::<IndexMap<_, _>>())
     }
 
+    /// Convert the steps of a test timeline into sources fed by the timeline
+    fn hydrate_timeline_into_sources(
+        &self,
+        timeline: &[TestStep],
+        current: Arc<AtomicUsize>,
+    ) -> Result<(Timeline, IndexMap<ComponentKey, SourceOuter>), Vec<String>> {
+        let steps = build_and_validate_timeline(timeline, &self.available_insert_targets)?;
+
+        let mut senders = HashMap::new();
+        let mut sources = IndexMap::new();
+        for (insert_at, _) in steps.iter().flat_map(|step| &step.inputs) {
+            if senders.contains_key(insert_at) {
+                continue;
+            }
+            let (tx, rx) = mpsc::unbounded_channel();
+            senders.insert(insert_at.clone(), tx);
+            let id: &str = self
+                .source_ids
+                .get(insert_at)
+                .expect("Corresponding source must exist")
+                .as_ref();
+            sources.insert(
+                ComponentKey::from(id),
+                SourceOuter::new(UnitTestStreamSourceConfig::new(
+                    UnboundedReceiverStream::new(rx),
+                )),
+            );
+        }
+
+        Ok((Timeline::new(steps, senders, current), sources))
+    }
+
     /// Convert test outputs into sinks for use in a unit testing topology
     pub fn hydrate_into_sinks(
         &self,
@@ -280,6 +333,86 @@ impl UnitTestBuildMetadata {
                     .to_string(),
             ]);
         }
+        self.build_sinks(test_name, outputs, no_outputs_from, None)
+    }
+
+    /// Convert the test outputs of a test with a timeline into sinks, checking either all the
+    /// events or the ones emitted during a single step
+    fn hydrate_timeline_into_sinks(
+        &self,
+        test: &TestDefinition,
+        current: &Arc<AtomicUsize>,
+    ) -> Result<
+        (
+            Vec<Receiver<UnitTestSinkResult>>,
+            IndexMap<ComponentKey, SinkOuter<String>>,
+        ),
+        Vec<String>,
+    > {
+        let step = |index, name| UnitTestSinkStep {
+            index,
+            current: Arc::clone(current),
+            name,
+        };
+        let mut checks = vec![(&test.outputs, &test.no_outputs_from, None)];
+        for (index, test_step) in test.timeline.iter().enumerate() {
+            checks.push((
+                &test_step.outputs,
+                &test_step.no_outputs_from,
+                Some(step(index, format!("timeline[{index}]"))),
+            ));
+        }
+        if let Some(shutdown) = &test.shutdown {
+            checks.push((
+                &shutdown.outputs,
+                &shutdown.no_outputs_from,
+                Some(step(test.timeline.len(), "shutdown".to_string())),
+            ));
+        }
+
+        if checks
+            .iter()
+            .all(|(outputs, no_outputs_from, _)| outputs.is_empty() && no_outputs_from.is_empty())
+        {
+            return Err(vec![
+                "unit test must contain at least one of `outputs` or `no_outputs_from`."
+                    .to_string(),
+            ]);
+        }
+
+        let mut test_result_rxs = Vec::new();
+        let mut sinks = IndexMap::new();
+        let mut errors = Vec::new();
+        for (outputs, no_outputs_from, step) in checks {
+            match self.build_sinks(&test.name, outputs, no_outputs_from, step) {
+                Ok((rxs, step_sinks)) => {
+                    test_result_rxs.extend(rxs);
+                    sinks.extend(step_sinks);
+                }
+                Err(step_errors) => errors.extend(step_errors),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok((test_result_rxs, sinks))
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn build_sinks(
+        &self,
+        test_name: &str,
+        outputs: &[TestOutput],
+        no_outputs_from: &[OutputId],
+        step: Option<UnitTestSinkStep>,
+    ) -> Result<
+        (
+            Vec<Receiver<UnitTestSinkResult>>,
+            IndexMap<ComponentKey, SinkOuter<String>>,
+        ),
+        Vec<String>,
+    > {
         let outputs = build_outputs(outputs)?;
 
         let mut template_sinks = IndexMap::new();
@@ -293,6 +426,7 @@ impl UnitTestBuildMetadata {
                 transform_ids: ids.iter().map(|id| id.to_string()).collect(),
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::Checks(checks),
+                step: step.clone(),
             };
 
             test_result_rxs.push(rx);
@@ -307,6 +441,7 @@ impl UnitTestBuildMetadata {
                 transform_ids: vec![id.to_string()],
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::NoOutputs,
+                step: step.clone(),
             };
 
             test_result_rxs.push(rx);
@@ -329,7 +464,10 @@ impl UnitTestBuildMetadata {
                             .as_str()
                     })
                     .collect::<Vec<_>>();
-                let sink_id = sink_ids.join(",");
+                let mut sink_id = sink_ids.join(",");
+                if let Some(step) = &step {
+                    sink_id.push_str(&format!("-step-{}", step.index));
+                }
                 (
                     ComponentKey::from(sink_id),
                     SinkOuter::new(transform_ids_str, sink_config),
@@ -392,9 +530,36 @@ async fn build_unit_test(
     );
     let test = test.resolve_outputs(&transform_only_graph)?;
 
-    let sources = metadata.hydrate_into_sources(&test.inputs)?;
-    let (test_result_rxs, sinks) =
-        metadata.hydrate_into_sinks(&test.name, &test.outputs, &test.no_outputs_from)?;
+    let (sources, (test_result_rxs, sinks), timeline) = if test.timeline.is_empty() {
+        if test.shutdown.is_some() {
+            return Err(vec![
+                "`shutdown` requires a `timeline` to insert the input events.".to_string(),
+            ]);
+        }
+        (
+            metadata.hydrate_into_sources(&test.inputs)?,
+            metadata.hydrate_into_sinks(&test.name, &test.outputs, &test.no_outputs_from)?,
+            None,
+        )
+    } else {
+        if !cfg!(any(test, feature = "unit-test-timelines")) {
+            return Err(vec![
+                "`timeline` requires Vector to be built with the `unit-test-timelines` feature."
+                    .to_string(),
+            ]);
+        }
+        if !test.inputs.is_empty() {
+            return Err(vec![
+                "`inputs` can't be used along with a `timeline`, whose steps insert the input events."
+                    .to_string(),
+            ]);
+        }
+        let current = Arc::new(AtomicUsize::new(0));
+        let (timeline, sources) =
+            metadata.hydrate_timeline_into_sources(&test.timeline, Arc::clone(&current))?;
+        let sinks = metadata.hydrate_timeline_into_sinks(&test, &current)?;
+        (sources, sinks, Some(timeline))
+    };
 
     config_builder.sources = sources;
     config_builder.sinks = sinks;
@@ -454,13 +619,20 @@ async fn build_unit_test(
             .insert(ComponentKey::from(Uuid::new_v4().to_string()), sink);
     }
     let config = config_builder.build()?;
-    let diff = config::ConfigDiff::initial(&config);
-    let pieces = TopologyPieces::build(&config, &diff, HashMap::new(), Default::default()).await?;
+    let topology = match timeline {
+        Some(timeline) => UnitTestTopology::Timeline(timeline),
+        None => {
+            let diff = config::ConfigDiff::initial(&config);
+            let pieces =
+                TopologyPieces::build(&config, &diff, HashMap::new(), Default::default()).await?;
+            UnitTestTopology::Built(pieces)
+        }
+    };
 
     Ok(UnitTest {
         name: test.name,
         config,
-        pieces,
+        topology,
         test_result_rxs,
     })
 }
@@ -506,6 +678,7 @@ fn get_loose_end_outputs_sink(config: &ConfigBuilder) -> Option<SinkOuter<String
             transform_ids: vec![],
             result_tx: Arc::new(Mutex::new(None)),
             check: UnitTestSinkCheck::NoOp,
+            step: None,
         };
         Some(SinkOuter::new(loose_end_outputs, noop_sink))
     }
@@ -550,6 +723,44 @@ fn build_and_validate_inputs(
     }
 }
 
+fn build_and_validate_timeline(
+    timeline: &[TestStep],
+    available_insert_targets: &HashSet<ComponentKey>,
+) -> Result<Vec<TimelineStep>, Vec<String>> {
+    let mut steps = Vec::new();
+    let mut errors = Vec::new();
+    for (step_index, step) in timeline.iter().enumerate() {
+        let mut inputs = Vec::new();
+        for (index, input) in step.inputs.iter().enumerate() {
+            if available_insert_targets.contains(&input.insert_at) {
+                match build_timeline_input_event(input) {
+                    Ok(input_event) => inputs.push((input.insert_at.clone(), input_event)),
+                    Err(error) => errors.push(format!("timeline[{step_index}]: {error}")),
+                }
+            } else {
+                errors.push(format!(
+                    "timeline[{}].inputs[{}]: unable to locate target transform '{}'",
+                    step_index, index, input.insert_at
+                ))
+            }
+        }
+        steps.push(TimelineStep {
+            advance: Duration::from_millis(step.advance_ms),
+            inputs,
+        });
+    }
+
+    if steps.iter().all(|step| step.inputs.is_empty()) && errors.is_empty() {
+        errors.push("must specify at least one input in the timeline.".to_string());
+    }
+
+    if errors.is_empty() {
+        Ok(steps)
+    } else {
+        Err(errors)
+    }
+}
+
 fn build_outputs(
     test_outputs: &[TestOutput],
 ) -> Result<IndexMap<Vec<OutputId>, Vec<Vec<Condition>>>, Vec<String>> {
@@ -586,6 +797,29 @@ fn build_outputs(
     }
 }
 
+/// Builds an input event of a timeline, which only has a timestamp if its input sets one, so that
+/// it otherwise gets the time of the simulated clock at which it is inserted.
+fn build_timeline_input_event(input: &TestInput) -> Result<Event, String> {
+    let mut event = build_input_event(input)?;
+    if let Event::Log(log) = &mut event
+        && let Some(timestamp_key) = log_schema().timestamp_key_target_path()
+    {
+        // `raw` and `log` inputs are timestamped with the current time when built.
+        let sets_timestamp =
+            match input.type_str.as_ref() {
+                "raw" => false,
+                "log" => input.log_fields.iter().flatten().any(|(path, _)| {
+                    parse_target_path(path).is_ok_and(|path| &path == timestamp_key)
+                }),
+                _ => true,
+            };
+        if !sets_timestamp {
+            log.remove(timestamp_key);
+        }
+    }
+    Ok(event)
+}
+
 fn build_input_event(input: &TestInput) -> Result<Event, String> {
     match input.type_str.as_ref() {
         "raw" => match input.value.as_ref() {
@@ -1298,3 +1298,208 @@ async fn test_glob_input() {
     let mut tests = build_unit_tests(config).await.unwrap();
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
+
+#[tokio::test]
+async fn test_timeline() {
+    crate::test_util::trace_init();
+
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.reduce]
+          type = "reduce"
+          inputs = [ "ignored" ]
+          group_by = [ "message" ]
+          expire_after_ms = 30000
+          flush_period_ms = 1000
+
+        [[tests]]
+          name = "timeline test"
+
+          [[tests.timeline]]
+            no_outputs_from = [ "reduce" ]
+
+            [[tests.timeline.inputs]]
+              type = "log"
+              insert_at = "reduce"
+
+              [tests.timeline.inputs.log_fields]
+                message = "first"
+                count = 1
+
+            [[tests.timeline.inputs]]
+              type = "log"
+              insert_at = "reduce"
+
+              [tests.timeline.inputs.log_fields]
+                message = "first"
+                count = 2
+
+          [[tests.timeline]]
+            advance_ms = 10000
+            no_outputs_from = [ "reduce" ]
+
+          [[tests.timeline]]
+            advance_ms = 25000
+
+            [[tests.timeline.inputs]]
+              type = "log"
+              insert_at = "reduce"
+
+              [tests.timeline.inputs.log_fields]
+                message = "second"
+                count = 5
+
+            [[tests.timeline.outputs]]
+              extract_from = "reduce"
+
+              [[tests.timeline.outputs.conditions]]
+                type = "vrl"
+                source = """
+                  assert_eq!(.message, "first", "incorrect message")
+                  assert_eq!(.count, 3, "incorrect count")
+                """
+
+          [tests.shutdown]
+            [[tests.shutdown.outputs]]
+              extract_from = "reduce"
+
+              [[tests.shutdown.outputs.conditions]]
+                type = "vrl"
+                source = """
+                  assert_eq!(.message, "second", "incorrect message")
+                  assert_eq!(.count, 5, "incorrect count")
+                """
+
+        [[tests]]
+          name = "timeline test failure"
+
+          [[tests.timeline]]
+            [[tests.timeline.inputs]]
+              type = "log"
+              insert_at = "reduce"
+
+              [tests.timeline.inputs.log_fields]
+                message = "first"
+
+            [[tests.timeline.outputs]]
+              extract_from = "reduce"
+
+          [[tests.timeline]]
+            advance_ms = 31000
+            no_outputs_from = [ "reduce" ]
+    "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert_eq!(tests.remove(0).run().await.errors, Vec::<String>::new());
+
+    let mut errors = tests.remove(0).run().await.errors;
+    errors.sort();
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(errors[0].starts_with("timeline[0]: checks for transforms"));
+    assert!(errors[1].starts_with("timeline[1]: check for transforms"));
+}
+
+#[tokio::test]
+async fn test_timeline_keeps_input_timestamps() {
+    crate::test_util::trace_init();
+
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.explicit]
+          type = "remap"
+          inputs = [ "ignored" ]
+          source = ".checked = true"
+
+        [transforms.implicit]
+          type = "remap"
+          inputs = [ "ignored" ]
+          source = ".checked = true"
+
+        [[tests]]
+          name = "timeline timestamps"
+
+          [[tests.timeline]]
+            advance_ms = 1000
+
+            [[tests.timeline.inputs]]
+              type = "vrl"
+              insert_at = "explicit"
+              source = """
+                .message = "explicit"
+                .timestamp = t'2020-01-01T00:00:00Z'
+              """
+
+            [[tests.timeline.inputs]]
+              type = "log"
+              insert_at = "implicit"
+
+              [tests.timeline.inputs.log_fields]
+                message = "implicit"
+
+            [[tests.timeline.outputs]]
+              extract_from = "explicit"
+
+              [[tests.timeline.outputs.conditions]]
+                type = "vrl"
+                source = """
+                  assert_eq!(.timestamp, t'2020-01-01T00:00:00Z', "timestamp was overwritten")
+                """
+
+            [[tests.timeline.outputs]]
+              extract_from = "implicit"
+
+              [[tests.timeline.outputs.conditions]]
+                type = "vrl"
+                source = """
+                  assert!(is_timestamp(.timestamp), "timestamp is missing")
+                  assert!(.timestamp != t'2020-01-01T00:00:00Z', "timestamp is incorrect")
+                """
+    "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert_eq!(tests.remove(0).run().await.errors, Vec::<String>::new());
+}
+
+#[tokio::test]
+async fn parse_timeline_with_inputs() {
+    crate::test_util::trace_init();
+
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.bar]
+          inputs = ["foo"]
+          type = "remap"
+          source = '''
+          .my_string_field = "string value"
+          '''
+
+          [[tests]]
+            name = "broken test"
+
+          [[tests.inputs]]
+            insert_at = "bar"
+            value = "nah this doesnt matter"
+
+          [[tests.timeline]]
+            advance_ms = 1000
+
+          [[tests.timeline]]
+            [[tests.timeline.inputs]]
+              insert_at = "foo"
+              value = "nah this doesnt matter"
+
+          [[tests.outputs]]
+            extract_from = "bar"
+    "#})
+    .unwrap();
+
+    let errs = build_unit_tests(config).await.err().unwrap();
+    assert_eq!(
+        errs,
+        vec![
+            indoc! {r"
+            Failed to build test 'broken test':
+              `inputs` can't be used along with a `timeline`, whose steps insert the input events."}
+            .to_owned(),
+        ]
+    );
+}
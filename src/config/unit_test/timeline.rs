@@ -0,0 +1,145 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use chrono::Utc;
+use tokio::{
+    sync::{mpsc, oneshot::Receiver},
+    time::{self, Instant},
+};
+use vector_lib::config::log_schema;
+
+use super::{UnitTestSinkResult, collect_results};
+use crate::{
+    config::{self, ComponentKey, Config},
+    event::Event,
+    topology::{RunningTopology, builder::TopologyPieces},
+};
+
+/// The duration the simulated clock advances by to let the topology process the inputs of a step.
+///
+/// The simulated clock only advances once all tasks of the topology are idle, so sleeping on it
+/// waits for the inputs to be fully processed.
+const SETTLE_DURATION: Duration = Duration::from_millis(1);
+
+/// A step of a unit test timeline, with its input events built.
+pub(super) struct TimelineStep {
+    pub(super) advance: Duration,
+    pub(super) inputs: Vec<(ComponentKey, Event)>,
+}
+
+/// Drives a unit test through the steps of its timeline, on a simulated clock.
+pub(super) struct Timeline {
+    steps: Vec<TimelineStep>,
+    senders: HashMap<ComponentKey, mpsc::UnboundedSender<Event>>,
+    current: Arc<AtomicUsize>,
+}
+
+impl Timeline {
+    pub(super) fn new(
+        steps: Vec<TimelineStep>,
+        senders: HashMap<ComponentKey, mpsc::UnboundedSender<Event>>,
+        current: Arc<AtomicUsize>,
+    ) -> Self {
+        Self {
+            steps,
+            senders,
+            current,
+        }
+    }
+
+    /// Builds and runs the test topology on a runtime whose clock only advances through the steps.
+    ///
+    /// Components create their timers as they are built, so the topology must be built on that
+    /// runtime as well.
+    pub(super) async fn run(
+        self,
+        config: Config,
+        test_result_rxs: Vec<Receiver<UnitTestSinkResult>>,
+    ) -> Vec<String> {
+        tokio::task::spawn_blocking(move || {
+            let mut builder = tokio::runtime::Builder::new_current_thread();
+            builder.enable_all();
+            // Timelines are rejected when building the tests otherwise.
+            #[cfg(any(test, feature = "unit-test-timelines"))]
+            builder.start_paused(true);
+            builder
+                .build()
+                .expect("Unable to build the runtime of the unit test timeline.")
+                .block_on(self.drive(config, test_result_rxs))
+        })
+        .await
+        .expect("An unexpected error occurred while executing unit tests. Please try again.")
+    }
+
+    async fn drive(
+        self,
+        config: Config,
+        test_result_rxs: Vec<Receiver<UnitTestSinkResult>>,
+    ) -> Vec<String> {
+        let diff = config::ConfigDiff::initial(&config);
+        let pieces =
+            match TopologyPieces::build(&config, &diff, HashMap::new(), Default::default()).await {
+                Ok(pieces) => pieces,
+                Err(errors) => return errors,
+            };
+        let (topology, _) = RunningTopology::start_validated(config, diff, pieces)
+            .await
+            .unwrap();
+
+        let Self {
+            steps,
+            senders,
+            current,
+        } = self;
+        let start = Instant::now();
+        let started_at = Utc::now();
+        let mut elapsed = Duration::ZERO;
+        for (index, step) in steps.into_iter().enumerate() {
+            // Settling may have moved the clock past the time of the step, which then only
+            // inserts its inputs.
+            elapsed += step.advance;
+            time::sleep_until(start + elapsed).await;
+
+            let timestamp = started_at
+                + chrono::Duration::from_std(elapsed).expect("timeline duration out of range");
+            for (insert_at, event) in step.inputs {
+                // Events keep the timestamp set by their input, if any.
+                let event = match event {
+                    Event::Log(mut log) => {
+                        if let Some(timestamp_key) = log_schema().timestamp_key_target_path()
+                            && !log.contains(timestamp_key)
+                        {
+                            log.insert(timestamp_key, timestamp);
+                        }
+                        Event::Log(log)
+                    }
+                    Event::Metric(metric) if metric.timestamp().is_none() => {
+                        Event::Metric(metric.with_timestamp(Some(timestamp)))
+                    }
+                    event => event,
+                };
+                senders
+                    .get(&insert_at)
+                    .expect("Corresponding source must exist")
+                    .send(event)
+                    .expect("Unit test source stopped before the end of the timeline");
+            }
+
+            time::sleep(SETTLE_DURATION).await;
+            current.store(index + 1, Ordering::Release);
+        }
+
+        // Closing the inputs stops the sources, so that the transforms flush on shutdown.
+        drop(senders);
+        topology.sources_finished().await;
+        let _stop_complete = topology.stop();
+
+        collect_results(test_result_rxs).await
+    }
+}
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
 
 use futures::{Sink, Stream, stream};
 use futures_util::{FutureExt, StreamExt, future, stream::BoxStream};
@@ -127,6 +130,19 @@ pub enum UnitTestSinkCheck {
     NoOp,
 }
 
+/// A step of a unit test timeline, during which the events received by a sink are checked.
+#[derive(Clone, Debug)]
+pub struct UnitTestSinkStep {
+    /// The index of the step, which is the number of steps in the timeline for the shutdown.
+    pub index: usize,
+
+    /// The index of the step the timeline is currently at.
+    pub current: Arc<AtomicUsize>,
+
+    /// The name of the step, prefixing the errors of its checks.
+    pub name: String,
+}
+
 #[derive(Debug)]
 pub struct UnitTestSinkResult {
     pub test_name: String,
@@ -152,6 +168,10 @@ pub struct UnitTestSinkConfig {
     #[serde(skip)]
     #[derivative(Debug = "ignore")]
     pub check: UnitTestSinkCheck,
+
+    /// The timeline step during which events are checked, or `None` to check all of them.
+    #[serde(skip)]
+    pub step: Option<UnitTestSinkStep>,
 }
 
 impl_generate_config_from_default!(UnitTestSinkConfig);
@@ -166,6 +186,7 @@ impl SinkConfig for UnitTestSinkConfig {
             transform_ids: self.transform_ids.clone(),
             result_tx: tx,
             check: self.check.clone(),
+            step: self.step.clone(),
         };
         let healthcheck = future::ok(()).boxed();
 
@@ -187,6 +208,7 @@ pub struct UnitTestSink {
     // None for NoOp test sinks
     pub result_tx: Option<oneshot::Sender<UnitTestSinkResult>>,
     pub check: UnitTestSinkCheck,
+    pub step: Option<UnitTestSinkStep>,
 }
 
 #[async_trait::async_trait]
@@ -199,7 +221,13 @@ impl StreamSink<Event> for UnitTestSink {
         };
 
         while let Some(event) = input.next().await {
-            output_events.push(event);
+            if self
+                .step
+                .as_ref()
+                .is_none_or(|step| step.current.load(Ordering::Acquire) == step.index)
+            {
+                output_events.push(event);
+            }
         }
 
         match self.check {
@@ -261,6 +289,12 @@ impl StreamSink<Event> for UnitTestSink {
             UnitTestSinkCheck::NoOp => {}
         }
 
+        if let Some(step) = &self.step {
+            for error in &mut result.test_errors {
+                error.insert_str(0, &format!("{}: ", step.name));
+            }
+        }
+
         if let Some(tx) = self.result_tx
             && tx.send(result).is_err()
         {
@@ -15,9 +15,13 @@ use vector_lib::{configurable::configurable_component, sink::VectorSink};
 use vrl::path::OwnedTargetPath;
 use vrl::value::Kind;
 
-use crate::config::{EnrichmentTableConfig, SinkConfig, SourceConfig, SourceContext, SourceOutput};
+use crate::config::{
+    EnrichmentTableConfig, Resource, SinkConfig, SourceConfig, SourceContext, SourceOutput,
+};
 
 use super::internal_events::InternalMetricsConfig;
+use super::persistence::MemoryPersistenceConfig;
+use super::replication::MemoryReplicationConfig;
 use super::source::MemorySourceConfig;
 
 /// Configuration for the `memory` enrichment table.
@@ -61,6 +65,14 @@ pub struct MemoryConfig {
     #[configurable(derived)]
     #[serde(skip_serializing_if = "vector_lib::serde::is_default")]
     pub source_config: Option<MemorySourceConfig>,
+    /// Configuration for persisting the table to disk.
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "vector_lib::serde::is_default")]
+    pub persistence: Option<MemoryPersistenceConfig>,
+    /// Configuration for replicating the writes of the table to other Vector instances.
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replication: Option<MemoryReplicationConfig>,
 
     #[serde(skip)]
     memory: Arc<Mutex<Option<Box<Memory>>>>,
@@ -85,6 +97,8 @@ impl Default for MemoryConfig {
             max_byte_size: None,
             log_namespace: None,
             source_config: None,
+            persistence: None,
+            replication: None,
             internal_metrics: InternalMetricsConfig::default(),
         }
     }
@@ -139,8 +153,14 @@ impl EnrichmentTableConfig for MemoryConfig {
 #[async_trait]
 #[typetag::serde(name = "memory_enrichment_table")]
 impl SinkConfig for MemoryConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = VectorSink::from_event_streamsink(self.get_or_build_memory().await);
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let memory = self.get_or_build_memory().await;
+        let sink = match &self.replication {
+            Some(replication) => {
+                VectorSink::from_event_streamsink(replication.build(memory, cx.proxy()).await?)
+            }
+            None => VectorSink::from_event_streamsink(memory),
+        };
 
         Ok((sink, future::ok(()).boxed()))
     }
@@ -149,6 +169,13 @@ impl SinkConfig for MemoryConfig {
         Input::log()
     }
 
+    fn resources(&self) -> Vec<Resource> {
+        self.replication
+            .iter()
+            .map(|replication| Resource::tcp(replication.address))
+            .collect()
+    }
+
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &AcknowledgementsConfig::DEFAULT
     }
@@ -196,6 +223,8 @@ impl std::fmt::Debug for MemoryConfig {
             .field("scan_interval", &self.scan_interval)
             .field("flush_interval", &self.flush_interval)
             .field("max_byte_size", &self.max_byte_size)
+            .field("persistence", &self.persistence)
+            .field("replication", &self.replication)
             .finish()
     }
 }
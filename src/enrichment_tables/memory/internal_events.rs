@@ -1,6 +1,8 @@
+use std::path::Path;
+
 use metrics::{counter, gauge};
 use vector_lib::configurable::configurable_component;
-use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{InternalEvent, error_stage, error_type};
 
 /// Configuration of internal metrics for enrichment memory table.
 #[configurable_component]
@@ -152,3 +154,98 @@ impl InternalEvent for MemoryEnrichmentTableInsertFailed<'_> {
         Some("MemoryEnrichmentTableInsertFailed")
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct MemoryEnrichmentTableSnapshotFailed<'a> {
+    pub path: &'a Path,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MemoryEnrichmentTableSnapshotFailed<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to write snapshot of the table.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "snapshot_failed",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "snapshot_failed",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MemoryEnrichmentTableSnapshotFailed")
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct MemoryEnrichmentTableRestoreFailed<'a> {
+    pub path: &'a Path,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MemoryEnrichmentTableRestoreFailed<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to restore the table from its snapshot, starting empty.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "restore_failed",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::INITIALIZING,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "restore_failed",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::INITIALIZING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MemoryEnrichmentTableRestoreFailed")
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct MemoryEnrichmentTableReplicationFailed<'a> {
+    pub peer: &'a str,
+    pub error: crate::Error,
+    pub count: usize,
+}
+
+impl InternalEvent for MemoryEnrichmentTableReplicationFailed<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to replicate writes to peer.",
+            peer = %self.peer,
+            error = %self.error,
+            count = %self.count,
+            error_code = "replication_failed",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "replication_failed",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        )
+        .increment(1);
+        counter!("memory_enrichment_table_failed_replications").increment(self.count as u64);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MemoryEnrichmentTableReplicationFailed")
+    }
+}
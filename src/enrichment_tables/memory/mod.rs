@@ -2,8 +2,12 @@
 
 mod config;
 mod internal_events;
+mod persistence;
+mod replication;
 mod source;
 mod table;
 
 pub use config::*;
+pub use persistence::MemoryPersistenceConfig;
+pub use replication::MemoryReplicationConfig;
 pub use table::*;
@@ -0,0 +1,214 @@
+use std::{
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use vector_lib::configurable::configurable_component;
+
+use super::{
+    Memory, MemoryEntry,
+    internal_events::{MemoryEnrichmentTableRestoreFailed, MemoryEnrichmentTableSnapshotFailed},
+};
+
+/// Configuration for persisting the memory enrichment table to disk.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryPersistenceConfig {
+    /// The file in which snapshots of the table are written.
+    ///
+    /// When the table is built, it is restored from this file, skipping the entries whose TTL
+    /// expired in the meantime.
+    #[configurable(metadata(docs::examples = "/var/lib/vector/memory_table.json"))]
+    pub path: PathBuf,
+    /// The interval, in seconds, between snapshots of the table.
+    ///
+    /// A snapshot is also written when the table shuts down.
+    #[serde(default = "default_snapshot_interval")]
+    pub snapshot_interval: NonZeroU64,
+}
+
+const fn default_snapshot_interval() -> NonZeroU64 {
+    unsafe { NonZeroU64::new_unchecked(60) }
+}
+
+/// An entry of the table, as written in snapshots.
+#[derive(Debug, Deserialize, Serialize)]
+struct SnapshotEntry {
+    key: String,
+    /// The value, encoded as JSON.
+    value: String,
+    /// The time of the last update of the entry, in milliseconds since the Unix epoch.
+    updated_at: u64,
+}
+
+impl Memory {
+    /// Restores the entries of the snapshot at `path` that have not expired yet.
+    ///
+    /// A missing snapshot is not an error, as it is only written after the table first ran.
+    pub(super) fn restore_snapshot(&self, path: &Path) {
+        let entries = match std::fs::read(path) {
+            Ok(snapshot) => match serde_json::from_slice::<Vec<SnapshotEntry>>(&snapshot) {
+                Ok(entries) => entries,
+                Err(error) => {
+                    emit!(MemoryEnrichmentTableRestoreFailed {
+                        path,
+                        error: error.into(),
+                    });
+                    return;
+                }
+            },
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return,
+            Err(error) => {
+                emit!(MemoryEnrichmentTableRestoreFailed {
+                    path,
+                    error: error.into(),
+                });
+                return;
+            }
+        };
+
+        let now = Instant::now();
+        let system_now = SystemTime::now();
+        let mut writer = self.write_handle.lock().expect("mutex poisoned");
+        for entry in entries {
+            let updated_at = SystemTime::UNIX_EPOCH + Duration::from_millis(entry.updated_at);
+            let age = system_now.duration_since(updated_at).unwrap_or_default();
+            if age.as_secs() > self.config.ttl {
+                continue;
+            }
+
+            writer.write_handle.update(
+                entry.key,
+                MemoryEntry {
+                    value: entry.value,
+                    update_time: now.checked_sub(age).unwrap_or(now).into(),
+                },
+            );
+        }
+        self.flush(writer);
+    }
+
+    /// Writes all entries of the table to the snapshot at `path`.
+    ///
+    /// The snapshot is first written to a temporary file, so that a failure never leaves a
+    /// partial snapshot behind.
+    pub(super) async fn write_snapshot(&self, path: &Path) {
+        let now = Instant::now();
+        let system_now = SystemTime::now();
+        let entries = self
+            .get_read_handle()
+            .read()
+            .map(|reader| {
+                reader
+                    .iter()
+                    .filter_map(|(key, values)| {
+                        let entry = values.get_one()?;
+                        let updated_at = system_now - now.duration_since(*entry.update_time);
+                        Some(SnapshotEntry {
+                            key: key.clone(),
+                            value: entry.value.clone(),
+                            updated_at: updated_at
+                                .duration_since(SystemTime::UNIX_EPOCH)
+                                .unwrap_or_default()
+                                .as_millis() as u64,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if let Err(error) = write_atomically(path, &entries).await {
+            emit!(MemoryEnrichmentTableSnapshotFailed { path, error });
+        }
+    }
+}
+
+async fn write_atomically(path: &Path, entries: &[SnapshotEntry]) -> crate::Result<()> {
+    let snapshot = serde_json::to_vec(entries)?;
+    let mut temporary = path.as_os_str().to_os_string();
+    temporary.push(".tmp");
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&temporary, snapshot).await?;
+    tokio::fs::rename(&temporary, path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::enrichment::{Case, Condition, Table};
+    use vrl::value::{ObjectMap, Value};
+
+    use super::*;
+    use crate::{enrichment_tables::memory::MemoryConfig, test_util::temp_file};
+
+    fn find(memory: &Memory, key: &str) -> Option<ObjectMap> {
+        let condition = Condition::Equals {
+            field: "key",
+            value: Value::from(key),
+        };
+        memory
+            .find_table_rows(Case::Sensitive, &[condition], None, None, None)
+            .unwrap()
+            .pop()
+    }
+
+    #[tokio::test]
+    async fn restores_snapshot() {
+        let path = temp_file();
+        let mut config = MemoryConfig::default();
+        config.persistence = Some(MemoryPersistenceConfig {
+            path: path.clone(),
+            snapshot_interval: default_snapshot_interval(),
+        });
+
+        let memory = Memory::new(config.clone());
+        memory.handle_value(ObjectMap::from([("test_key".into(), Value::from(5))]));
+        memory.write_snapshot(&path).await;
+
+        let restored = Memory::new(config);
+        let row = find(&restored, "test_key").expect("entry should be restored");
+        assert_eq!(row["value"], Value::from(5));
+    }
+
+    #[test]
+    fn skips_expired_entries() {
+        let path = temp_file();
+        let updated_at = SystemTime::now() - Duration::from_secs(120);
+        let entries = [
+            SnapshotEntry {
+                key: "expired_key".to_string(),
+                value: "5".to_string(),
+                updated_at: updated_at
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis() as u64,
+            },
+            SnapshotEntry {
+                key: "test_key".to_string(),
+                value: "5".to_string(),
+                updated_at: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis() as u64,
+            },
+        ];
+        std::fs::write(&path, serde_json::to_vec(&entries).unwrap()).unwrap();
+
+        let mut config = MemoryConfig::default();
+        config.ttl = 60;
+        config.persistence = Some(MemoryPersistenceConfig {
+            path,
+            snapshot_interval: default_snapshot_interval(),
+        });
+        let memory = Memory::new(config);
+
+        assert!(find(&memory, "expired_key").is_none());
+        assert!(find(&memory, "test_key").is_some());
+    }
+}
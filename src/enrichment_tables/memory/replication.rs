@@ -0,0 +1,288 @@
+use std::{net::SocketAddr, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{StreamExt, TryFutureExt, stream::BoxStream};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tonic::{Request, Response, Status};
+use vector_lib::{
+    configurable::configurable_component,
+    event::{Event, LogEvent, proto::EventWrapper},
+    shutdown::ShutdownSignal,
+    sink::StreamSink,
+};
+use vrl::value::{ObjectMap, Value};
+
+use super::{Memory, internal_events::MemoryEnrichmentTableReplicationFailed};
+use crate::{
+    config::ProxyConfig,
+    proto::vector as proto,
+    sinks::vector::{HyperSvc, new_client, with_default_scheme},
+    sources::util::grpc::run_grpc_server_with_listener,
+    tls::{MaybeTlsListener, MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// The maximum number of writes sent to a peer in a single request.
+const MAX_WRITES_PER_REQUEST: usize = 1000;
+
+/// Configuration for replicating the writes of the memory enrichment table between Vector
+/// instances.
+///
+/// Writes received by the table are sent to each peer over the native `vector` protocol, and
+/// writes received from peers are applied to the table without being replicated any further.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryReplicationConfig {
+    /// The socket address to listen on for writes replicated by peers.
+    #[configurable(metadata(docs::examples = "0.0.0.0:6100"))]
+    pub address: SocketAddr,
+    /// The addresses of the peers to replicate writes to.
+    ///
+    /// Each of them must include a port.
+    #[configurable(metadata(docs::examples = "vector-1.internal:6100"))]
+    pub peers: Vec<String>,
+    /// The maximum number of writes waiting to be sent to each peer.
+    ///
+    /// Writes are dropped for peers that fall further behind.
+    #[serde(default = "default_buffer_size")]
+    pub buffer_size: NonZeroUsize,
+    /// Configures the TLS options for both incoming and outgoing connections.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: Option<TlsEnableableConfig>,
+}
+
+const fn default_buffer_size() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+impl MemoryReplicationConfig {
+    /// Builds a sink that writes to `memory` and replicates these writes to the peers.
+    ///
+    /// The address to listen on for writes replicated by peers is bound right away, so that a
+    /// failure to bind it fails the build.
+    pub(super) async fn build(
+        &self,
+        memory: Memory,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<ReplicatedMemory> {
+        let server_tls = MaybeTlsSettings::from_config(self.tls.as_ref(), true)?;
+        let listener = server_tls.bind(&self.address).await?;
+        let client_tls = MaybeTlsSettings::from_config(self.tls.as_ref(), false)?;
+        let client = new_client(&client_tls, proxy)?;
+
+        let peers = self
+            .peers
+            .iter()
+            .map(|peer| {
+                let uri = with_default_scheme(peer, client_tls.is_tls())?;
+                Ok(Peer {
+                    address: peer.clone(),
+                    client: proto::Client::new(HyperSvc::new(uri, client.clone())),
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(ReplicatedMemory {
+            memory,
+            listener,
+            peers,
+            buffer_size: self.buffer_size.get(),
+        })
+    }
+}
+
+/// The memory enrichment table, along with the peers its writes are replicated to.
+pub(super) struct ReplicatedMemory {
+    memory: Memory,
+    listener: MaybeTlsListener,
+    peers: Vec<Peer>,
+    buffer_size: usize,
+}
+
+#[async_trait]
+impl StreamSink<Event> for ReplicatedMemory {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            memory,
+            listener,
+            peers,
+            buffer_size,
+        } = *self;
+
+        // The server stops once the trigger is dropped, when the table shuts down.
+        let (_trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let service = proto::Server::new(ReplicationService {
+            memory: memory.clone(),
+        })
+        .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+        .max_decoding_message_size(usize::MAX);
+        tokio::spawn(
+            run_grpc_server_with_listener(listener, service, shutdown).map_err(|error| {
+                error!(message = "Replication server failed.", %error);
+            }),
+        );
+
+        let senders = peers
+            .into_iter()
+            .map(|peer| {
+                let (sender, receiver) = mpsc::channel(buffer_size);
+                let address = peer.address.clone();
+                tokio::spawn(peer.run(receiver));
+                (address, sender)
+            })
+            .collect::<Vec<_>>();
+
+        let input = input
+            .map(move |event| {
+                if let Event::Log(log) = &event
+                    && let Value::Object(writes) = log.value()
+                {
+                    for (address, sender) in &senders {
+                        if let Err(TrySendError::Full(_)) = sender.try_send(writes.clone()) {
+                            emit!(MemoryEnrichmentTableReplicationFailed {
+                                peer: address,
+                                error: "Too many writes are waiting to be sent.".into(),
+                                count: 1,
+                            });
+                        }
+                    }
+                }
+                event
+            })
+            .boxed();
+
+        Box::new(memory).run(input).await
+    }
+}
+
+/// A peer to which the writes of the table are sent.
+struct Peer {
+    address: String,
+    client: proto::Client<HyperSvc>,
+}
+
+impl Peer {
+    /// Sends the writes to the peer until the table shuts down.
+    ///
+    /// Writes that fail to be sent are dropped, as the peer keeps receiving newer writes.
+    async fn run(mut self, mut receiver: mpsc::Receiver<ObjectMap>) {
+        let mut writes = Vec::with_capacity(MAX_WRITES_PER_REQUEST);
+        while receiver
+            .recv_many(&mut writes, MAX_WRITES_PER_REQUEST)
+            .await
+            > 0
+        {
+            let count = writes.len();
+            let request = proto::PushEventsRequest {
+                events: writes
+                    .drain(..)
+                    .map(|writes| EventWrapper::from(Event::Log(LogEvent::from(writes))))
+                    .collect(),
+            };
+
+            if let Err(error) = self.client.push_events(request).await {
+                emit!(MemoryEnrichmentTableReplicationFailed {
+                    peer: &self.address,
+                    error: error.into(),
+                    count,
+                });
+            }
+        }
+    }
+}
+
+/// Applies the writes replicated by peers to the table.
+#[derive(Clone)]
+struct ReplicationService {
+    memory: Memory,
+}
+
+#[tonic::async_trait]
+impl proto::Service for ReplicationService {
+    async fn push_events(
+        &self,
+        request: Request<proto::PushEventsRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        for event in request.into_inner().events {
+            if let Event::Log(log) = Event::from(event)
+                && let (Value::Object(writes), _) = log.into_parts()
+            {
+                self.memory.handle_value(writes);
+            }
+        }
+
+        Ok(Response::new(proto::PushEventsResponse {}))
+    }
+
+    async fn health_check(
+        &self,
+        _: Request<proto::HealthCheckRequest>,
+    ) -> Result<Response<proto::HealthCheckResponse>, Status> {
+        Ok(Response::new(proto::HealthCheckResponse {
+            status: proto::ServingStatus::Serving.into(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{future::ready, stream};
+
+    use super::*;
+    use crate::test_util::{next_addr, wait_for, wait_for_tcp};
+
+    fn replication_config(address: SocketAddr, peers: Vec<String>) -> MemoryReplicationConfig {
+        MemoryReplicationConfig {
+            address,
+            peers,
+            buffer_size: default_buffer_size(),
+            tls: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn replicates_writes_to_peers() {
+        let peer_address = next_addr();
+        let peer = Memory::new(Default::default());
+        let peer_sink = replication_config(peer_address, Vec::new())
+            .build(peer.clone(), &ProxyConfig::default())
+            .await
+            .unwrap();
+        let (_peer_input, peer_receiver) = futures::channel::mpsc::channel::<Event>(1);
+        tokio::spawn(Box::new(peer_sink).run(peer_receiver.boxed()));
+        wait_for_tcp(peer_address).await;
+
+        let memory = Memory::new(Default::default());
+        let sink = replication_config(next_addr(), vec![peer_address.to_string()])
+            .build(memory.clone(), &ProxyConfig::default())
+            .await
+            .unwrap();
+        let event = Event::Log(LogEvent::from(ObjectMap::from([(
+            "test_key".into(),
+            Value::from(5),
+        )])));
+        Box::new(sink)
+            .run(stream::once(ready(event)).boxed())
+            .await
+            .unwrap();
+
+        assert!(memory.get_read_handle().get_one("test_key").is_some());
+        wait_for(|| {
+            let peer = peer.clone();
+            async move { peer.get_read_handle().get_one("test_key").is_some() }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn build_fails_when_address_is_in_use() {
+        let address = next_addr();
+        let _listener = std::net::TcpListener::bind(address).unwrap();
+
+        let result = replication_config(address, Vec::new())
+            .build(Memory::new(Default::default()), &ProxyConfig::default())
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -36,8 +36,8 @@ use super::source::MemorySource;
 /// Single memory entry containing the value and TTL
 #[derive(Clone, Eq, PartialEq, Hash, ShallowCopy)]
 pub struct MemoryEntry {
-    value: String,
-    update_time: CopyValue<Instant>,
+    pub(super) value: String,
+    pub(super) update_time: CopyValue<Instant>,
 }
 
 impl ByteSizeOf for MemoryEntry {
@@ -97,9 +97,11 @@ pub struct Memory {
 
 impl Memory {
     /// Creates a new [Memory] based on the provided config.
+    ///
+    /// If persistence is enabled, the table is restored from its last snapshot.
     pub fn new(config: MemoryConfig) -> Self {
         let (read_handle, write_handle) = evmap::new();
-        Self {
+        let memory = Self {
             config,
             read_handle_factory: read_handle.factory(),
             read_handle: ThreadLocal::new(),
@@ -107,7 +109,11 @@ impl Memory {
                 write_handle,
                 metadata: MemoryMetadata::default(),
             })),
+        };
+        if let Some(persistence) = &memory.config.persistence {
+            memory.restore_snapshot(&persistence.path);
         }
+        memory
     }
 
     pub(super) fn get_read_handle(&self) -> &evmap::ReadHandle<String, MemoryEntry> {
@@ -115,7 +121,7 @@ impl Memory {
             .get_or(|| self.read_handle_factory.handle())
     }
 
-    fn handle_value(&self, value: ObjectMap) {
+    pub(super) fn handle_value(&self, value: ObjectMap) {
         let mut writer = self.write_handle.lock().expect("mutex poisoned");
         let now = Instant::now();
 
@@ -197,7 +203,7 @@ impl Memory {
         }
     }
 
-    fn flush(&self, mut writer: MutexGuard<'_, MemoryWriter>) {
+    pub(super) fn flush(&self, mut writer: MutexGuard<'_, MemoryWriter>) {
         writer.write_handle.refresh();
         if let Some(reader) = self.get_read_handle().read() {
             let mut byte_size = 0;
@@ -330,6 +336,13 @@ impl StreamSink<Event> for Memory {
         let mut scan_interval = IntervalStream::new(interval(Duration::from_secs(
             self.config.scan_interval.into(),
         )));
+        let mut snapshot_interval = IntervalStream::new(interval(
+            self.config
+                .persistence
+                .as_ref()
+                .map(|persistence| Duration::from_secs(persistence.snapshot_interval.into()))
+                .unwrap_or(Duration::MAX),
+        ));
 
         loop {
             tokio::select! {
@@ -364,8 +377,19 @@ impl StreamSink<Event> for Memory {
                     let writer = self.write_handle.lock().expect("mutex poisoned");
                     self.scan(writer);
                 }
+
+                Some(_) = snapshot_interval.next() => {
+                    if let Some(persistence) = &self.config.persistence {
+                        self.write_snapshot(&persistence.path).await;
+                    }
+                }
             }
         }
+
+        if let Some(persistence) = &self.config.persistence {
+            self.flush(self.write_handle.lock().expect("mutex poisoned"));
+            self.write_snapshot(&persistence.path).await;
+        }
         Ok(())
     }
 }
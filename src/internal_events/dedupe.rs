@@ -1,4 +1,9 @@
-use vector_lib::internal_event::{ComponentEventsDropped, INTENTIONAL, InternalEvent};
+use std::path::Path;
+
+use metrics::counter;
+use vector_lib::internal_event::{
+    ComponentEventsDropped, INTENTIONAL, InternalEvent, error_stage, error_type,
+};
 
 #[derive(Debug)]
 pub struct DedupeEventsDropped {
@@ -13,3 +18,57 @@ impl InternalEvent for DedupeEventsDropped {
         });
     }
 }
+
+#[derive(Debug)]
+pub struct DedupeCacheSnapshotLoadError<'a> {
+    pub error: String,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for DedupeCacheSnapshotLoadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed loading deduplication cache snapshot.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "loading_cache_snapshot",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "loading_cache_snapshot",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct DedupeCacheSnapshotWriteError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for DedupeCacheSnapshotWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing deduplication cache snapshot.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "writing_cache_snapshot",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "writing_cache_snapshot",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
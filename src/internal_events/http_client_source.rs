@@ -1,5 +1,7 @@
 #![allow(dead_code)] // TODO requires optional feature compilation
 
+use std::path::Path;
+
 use metrics::counter;
 use vector_lib::internal_event::InternalEvent;
 use vector_lib::{
@@ -89,3 +91,57 @@ impl InternalEvent for HttpClientHttpError {
         .increment(1);
     }
 }
+
+#[derive(Debug)]
+pub struct HttpClientCheckpointLoadError<'a> {
+    pub error: String,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for HttpClientCheckpointLoadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed loading checkpoint.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "loading_checkpoint",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "loading_checkpoint",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientCheckpointWriteError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for HttpClientCheckpointWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing checkpoint.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "writing_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "writing_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
@@ -62,6 +62,36 @@ impl InternalEvent for LogToMetricParseFloatError<'_> {
     }
 }
 
+pub struct LogToMetricInvalidSampleRateError<'a> {
+    pub field: &'a str,
+    pub value: &'a str,
+}
+
+impl InternalEvent for LogToMetricInvalidSampleRateError<'_> {
+    fn emit(self) {
+        let reason = "Invalid sample rate.";
+        error!(
+            message = reason,
+            field = %self.field,
+            value = %self.value,
+            error_code = "invalid_sample_rate",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "invalid_sample_rate",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+            "field" => self.field.to_string(),
+        )
+        .increment(1);
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason })
+    }
+}
+
 //  Metric Metadata Events and Errors
 pub struct MetricMetadataInvalidFieldValueError<'a> {
     pub field: &'a str,
@@ -0,0 +1,70 @@
+use std::net::IpAddr;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub(crate) struct NetflowDecodeError<E> {
+    pub error: E,
+    pub exporter: IpAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for NetflowDecodeError<E> {
+    fn emit(self) {
+        error!(
+            message = "Error occurred while decoding flow export packet.",
+            error = %self.error,
+            exporter = %self.exporter,
+            stage = error_stage::PROCESSING,
+            error_type = error_type::PARSER_FAILED,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct NetflowTemplateMiss {
+    pub protocol: &'static str,
+    pub template_id: u16,
+    pub exporter: IpAddr,
+}
+
+impl InternalEvent for NetflowTemplateMiss {
+    fn emit(self) {
+        debug!(
+            message = "Dropping data records of a template not yet announced by the exporter.",
+            protocol = self.protocol,
+            template_id = self.template_id,
+            exporter = %self.exporter,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "netflow_template_misses_total",
+            "protocol" => self.protocol,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct NetflowTemplateEvicted {
+    pub exporter: IpAddr,
+}
+
+impl InternalEvent for NetflowTemplateEvicted {
+    fn emit(self) {
+        warn!(
+            message = "Template cache is full, evicting the least recently announced template.",
+            exporter = %self.exporter,
+            internal_log_rate_limit = true,
+        );
+        counter!("netflow_templates_evicted_total").increment(1);
+    }
+}
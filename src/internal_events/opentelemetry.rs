@@ -0,0 +1,34 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{ComponentEventsDropped, UNINTENTIONAL, error_stage, error_type};
+
+use crate::event::metric::Metric;
+
+#[derive(Debug)]
+pub struct OpenTelemetryUnsupportedMetricError<'a> {
+    pub metric: &'a Metric,
+}
+
+impl InternalEvent for OpenTelemetryUnsupportedMetricError<'_> {
+    fn emit(self) {
+        let reason = "Metric type not supported by OTLP.";
+        error!(
+            message = reason,
+            error_code = "unsupported_metric_type",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            name = %self.metric.name(),
+            value = ?self.metric.value(),
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "unsupported_metric_type",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,30 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub(crate) struct SnmpTrapDecodeError<E> {
+    pub error: E,
+    pub peer: SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for SnmpTrapDecodeError<E> {
+    fn emit(self) {
+        error!(
+            message = "Error occurred while decoding SNMP message.",
+            error = %self.error,
+            peer = %self.peer,
+            stage = error_stage::PROCESSING,
+            error_type = error_type::PARSER_FAILED,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        )
+        .increment(1);
+    }
+}
@@ -1,4 +1,4 @@
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_lib::internal_event::{ComponentEventsDropped, INTENTIONAL, InternalEvent};
 
 pub struct TagCardinalityLimitRejectingEvent<'a> {
@@ -44,6 +44,26 @@ impl InternalEvent for TagCardinalityLimitRejectingTag<'_> {
     }
 }
 
+pub struct TagCardinalityLimitAggregatingTag<'a> {
+    pub metric_name: &'a str,
+    pub tag_key: &'a str,
+    pub tag_value: &'a str,
+}
+
+impl InternalEvent for TagCardinalityLimitAggregatingTag<'_> {
+    fn emit(self) {
+        debug!(
+            message =
+                "Replacing tag value with '__other__' after hitting configured 'value_limit'.",
+            metric_name = self.metric_name,
+            tag_key = self.tag_key,
+            tag_value = self.tag_value,
+            internal_log_rate_limit = true,
+        );
+        counter!("tag_value_limit_exceeded_total").increment(1);
+    }
+}
+
 pub struct TagCardinalityValueLimitReached<'a> {
     pub key: &'a str,
 }
@@ -57,3 +77,37 @@ impl InternalEvent for TagCardinalityValueLimitReached<'_> {
         counter!("value_limit_reached_total").increment(1);
     }
 }
+
+pub struct TagCardinalityEstimatesLimitReached {
+    pub max_tag_keys: usize,
+}
+
+impl InternalEvent for TagCardinalityEstimatesLimitReached {
+    fn emit(self) {
+        warn!(
+            message = "Maximum number of estimated tag keys reached. New tag keys won't be estimated until the next interval.",
+            max_tag_keys = %self.max_tag_keys,
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+pub struct TagCardinalityEstimated<'a> {
+    pub metric_namespace: Option<&'a str>,
+    pub metric_name: &'a str,
+    pub tag_key: &'a str,
+    pub cardinality: f64,
+}
+
+impl InternalEvent for TagCardinalityEstimated<'_> {
+    fn emit(self) {
+        let mut labels = vec![
+            ("metric_name", self.metric_name.to_owned()),
+            ("tag_key", self.tag_key.to_owned()),
+        ];
+        if let Some(namespace) = self.metric_namespace {
+            labels.push(("metric_namespace", namespace.to_owned()));
+        }
+        gauge!("tag_cardinality_estimate", &labels).set(self.cardinality);
+    }
+}
@@ -0,0 +1,32 @@
+use metrics::counter;
+use vector_lib::internal_event::{ComponentEventsDropped, INTENTIONAL, InternalEvent};
+
+#[derive(Debug)]
+pub(crate) struct TailSamplingSpansDiscarded {
+    pub count: usize,
+}
+
+impl InternalEvent for TailSamplingSpansDiscarded {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Trace not sampled."
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TailSamplingTraceEvicted {
+    pub max_traces: usize,
+}
+
+impl InternalEvent for TailSamplingTraceEvicted {
+    fn emit(self) {
+        debug!(
+            message = "Too many pending traces, deciding on the oldest one early.",
+            max_traces = self.max_traces,
+            internal_log_rate_limit = true,
+        );
+        counter!("tail_sampling_traces_evicted_total").increment(1);
+    }
+}
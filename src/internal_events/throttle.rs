@@ -1,5 +1,9 @@
-use metrics::counter;
-use vector_lib::internal_event::{ComponentEventsDropped, INTENTIONAL, InternalEvent};
+use std::time::Duration;
+
+use metrics::{counter, gauge, histogram};
+use vector_lib::internal_event::{
+    ComponentEventsDropped, INTENTIONAL, InternalEvent, error_stage, error_type,
+};
 
 #[derive(Debug)]
 pub(crate) struct ThrottleEventDiscarded {
@@ -31,3 +35,52 @@ impl InternalEvent for ThrottleEventDiscarded {
         })
     }
 }
+
+#[cfg(feature = "transforms-throttle-redis")]
+#[derive(Debug)]
+pub(crate) struct ThrottleBackendError {
+    pub error: String,
+}
+
+#[cfg(feature = "transforms-throttle-redis")]
+impl InternalEvent for ThrottleBackendError {
+    fn emit(self) {
+        error!(
+            message = "Failed to apply shared rate limits, applying them locally.",
+            error = %self.error,
+            error_code = "shared_rate_limits",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "shared_rate_limits",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ThrottleQueuedEvents {
+    pub count: usize,
+}
+
+impl InternalEvent for ThrottleQueuedEvents {
+    fn emit(self) {
+        gauge!("throttle_queued_events").set(self.count as f64);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ThrottleEventDelayed {
+    pub delay: Duration,
+}
+
+impl InternalEvent for ThrottleEventDelayed {
+    fn emit(self) {
+        histogram!("throttle_event_delay_seconds").record(self.delay);
+    }
+}
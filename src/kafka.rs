@@ -1,7 +1,13 @@
 #![allow(missing_docs)]
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, atomic::AtomicBool},
+};
 
-use rdkafka::{ClientConfig, ClientContext, Statistics, consumer::ConsumerContext};
+use rdkafka::{
+    ClientConfig, ClientContext, Statistics,
+    consumer::{ConsumerContext, ConsumerGroupMetadata},
+};
 use snafu::Snafu;
 use tracing::Span;
 use vector_lib::configurable::configurable_component;
@@ -167,6 +173,24 @@ fn pathbuf_to_string(path: &Path) -> crate::Result<&str> {
         .ok_or_else(|| KafkaError::InvalidPath { path: path.into() }.into())
 }
 
+/// The position of a message consumed by the `kafka` source.
+///
+/// It is attached to the batch of the events decoded from the message, so that a transactional
+/// `kafka` sink can commit the offset of the message in the same transaction as these events.
+pub(crate) struct KafkaSourceOffset {
+    pub(crate) group_id: Arc<str>,
+    /// The metadata of the consumer group at the time the message was consumed, which fences
+    /// commits made by consumers that were since removed from the group.
+    pub(crate) group_metadata: ConsumerGroupMetadata,
+    pub(crate) topic: String,
+    pub(crate) partition: i32,
+    pub(crate) offset: i64,
+    /// Shared by the messages of the partition, and set by transactional sinks committing their
+    /// offsets. The source then consumes the messages of aborted transactions again, as their
+    /// offsets are no longer committed past them.
+    pub(crate) transactional: Arc<AtomicBool>,
+}
+
 pub(crate) struct KafkaStatisticsContext {
     pub(crate) expose_lag_metrics: bool,
     pub span: Span,
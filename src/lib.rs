@@ -72,6 +72,7 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub mod buffer;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub mod common;
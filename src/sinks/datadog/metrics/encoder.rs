@@ -12,7 +12,7 @@ use vector_lib::request_metadata::GroupedCountByteSize;
 use vector_lib::{
     EstimatedJsonEncodedSizeOf,
     config::{LogSchema, log_schema, telemetry},
-    event::{DatadogMetricOriginMetadata, Metric, MetricTags, MetricValue, metric::MetricSketch},
+    event::{DatadogMetricOriginMetadata, Metric, MetricTags, MetricValue},
     metrics::AgentDDSketch,
 };
 
@@ -290,25 +290,24 @@ impl DatadogMetricsEncoder {
             },
             // Sketches are encoded via ProtoBuf, also in an incremental fashion.
             DatadogMetricsEndpoint::Sketches => match metric.value() {
-                MetricValue::Sketch { sketch } => match sketch {
-                    MetricSketch::AgentDDSketch(ddsketch) => {
-                        if let Some(sketch_proto) = sketch_to_proto_message(
-                            &metric,
-                            ddsketch,
-                            &self.default_namespace,
-                            self.log_schema,
-                            self.origin_product_value,
-                        ) {
-                            encode_proto_key_and_message(
-                                sketch_proto,
-                                get_sketch_payload_sketches_field_number(),
-                                &mut self.state.buf,
-                            )?;
-                        } else {
-                            // If the sketch was empty, that's fine too
-                        }
+                MetricValue::Sketch { sketch } => {
+                    let ddsketch = sketch.to_agent_ddsketch();
+                    if let Some(sketch_proto) = sketch_to_proto_message(
+                        &metric,
+                        &ddsketch,
+                        &self.default_namespace,
+                        self.log_schema,
+                        self.origin_product_value,
+                    ) {
+                        encode_proto_key_and_message(
+                            sketch_proto,
+                            get_sketch_payload_sketches_field_number(),
+                            &mut self.state.buf,
+                        )?;
+                    } else {
+                        // If the sketch was empty, that's fine too
                     }
-                },
+                }
                 value => {
                     return Err(EncoderError::InvalidMetric {
                         expected: "sketches",
@@ -1001,8 +1000,7 @@ mod tests {
         config::{LogSchema, log_schema},
         event::{
             DatadogMetricOriginMetadata, EventMetadata, Metric, MetricKind, MetricTags,
-            MetricValue,
-            metric::{MetricSketch, TagValue},
+            MetricValue, metric::TagValue,
         },
         metric_tags,
         metrics::AgentDDSketch,
@@ -1106,13 +1104,12 @@ mod tests {
             let MetricValue::Sketch { sketch } = metric.value() else {
                 panic!("must be sketch")
             };
-            match sketch {
-                MetricSketch::AgentDDSketch(ddsketch) => {
-                    if let Some(sketch) =
-                        sketch_to_proto_message(metric, ddsketch, default_namespace, log_schema, 14)
-                    {
-                        sketches.push(sketch);
-                    }
+            {
+                let ddsketch = sketch.to_agent_ddsketch();
+                if let Some(sketch) =
+                    sketch_to_proto_message(metric, &ddsketch, default_namespace, log_schema, 14)
+                {
+                    sketches.push(sketch);
                 }
             }
         }
@@ -1490,20 +1487,19 @@ mod tests {
         let mut incremental_buf = Vec::new();
         for metric in &metrics {
             match metric.value() {
-                MetricValue::Sketch { sketch } => match sketch {
-                    MetricSketch::AgentDDSketch(ddsketch) => {
-                        if let Some(sketch_proto) =
-                            sketch_to_proto_message(metric, ddsketch, &None, log_schema(), 14)
-                        {
-                            encode_proto_key_and_message(
-                                sketch_proto,
-                                get_sketch_payload_sketches_field_number(),
-                                &mut incremental_buf,
-                            )
-                            .unwrap();
-                        }
+                MetricValue::Sketch { sketch } => {
+                    let ddsketch = sketch.to_agent_ddsketch();
+                    if let Some(sketch_proto) =
+                        sketch_to_proto_message(metric, &ddsketch, &None, log_schema(), 14)
+                    {
+                        encode_proto_key_and_message(
+                            sketch_proto,
+                            get_sketch_payload_sketches_field_number(),
+                            &mut incremental_buf,
+                        )
+                        .unwrap();
                     }
-                },
+                }
                 _ => panic!("should be a sketch"),
             }
         }
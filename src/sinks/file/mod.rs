@@ -37,8 +37,10 @@ use crate::{
 };
 
 mod bytes_path;
+mod rotation;
 
 use bytes_path::BytesPath;
+pub use rotation::RotationConfig;
 
 /// Configuration for the `file` sink.
 #[serde_as]
@@ -73,6 +75,10 @@ pub struct FileSinkConfig {
     #[serde(default, skip_serializing_if = "crate::serde::is_default")]
     pub compression: Compression,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub rotation: Option<RotationConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -97,6 +103,7 @@ impl GenerateConfig for FileSinkConfig {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Default::default(),
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: Default::default(),
@@ -131,6 +138,19 @@ pub enum Compression {
     None,
 }
 
+/// An open file, along with the amount of data written to it.
+struct OpenFile {
+    file: OutFile,
+    bytes_written: u64,
+    events_written: u64,
+}
+
+impl OpenFile {
+    async fn close(&mut self) -> Result<(), std::io::Error> {
+        self.file.close().await
+    }
+}
+
 enum OutFile {
     Regular(File),
     Gzip(GzipEncoder<File>),
@@ -206,8 +226,9 @@ pub struct FileSink {
     transformer: Transformer,
     encoder: Encoder<Framer>,
     idle_timeout: Duration,
-    files: ExpiringHashMap<Bytes, OutFile>,
+    files: ExpiringHashMap<Bytes, OpenFile>,
     compression: Compression,
+    rotation: Option<RotationConfig>,
     events_sent: Registered<EventsSent>,
     include_file_metric_tag: bool,
 }
@@ -223,6 +244,13 @@ impl FileSink {
             .or(cx.globals.timezone)
             .and_then(timezone_to_offset);
 
+        if let Some(rotation) = &config.rotation
+            && rotation.compression != Compression::None
+            && config.compression != Compression::None
+        {
+            return Err("Rotated files can't be compressed when `compression` is enabled.".into());
+        }
+
         Ok(Self {
             path: config.path.clone().with_tz_offset(offset),
             transformer,
@@ -230,6 +258,7 @@ impl FileSink {
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
             compression: config.compression,
+            rotation: config.rotation.clone(),
             events_sent: register!(EventsSent::from(Output(None))),
             include_file_metric_tag: config.internal_metrics.include_file_tag,
         })
@@ -323,6 +352,37 @@ impl FileSink {
         Ok(())
     }
 
+    /// Closes the file at `path` and rotates it, so that the next event opens a new one.
+    async fn rotate_file(&mut self, path: &Bytes) {
+        let (Some(rotation), Some((mut file, _))) = (&self.rotation, self.files.remove(path))
+        else {
+            return;
+        };
+
+        if let Err(error) = file.close().await {
+            emit!(FileIoError {
+                error,
+                code: "failed_closing_file",
+                message: "Failed to close file.",
+                path,
+                dropped_events: 0,
+            });
+        }
+        emit!(FileOpen {
+            count: self.files.len()
+        });
+
+        if let Err(error) = rotation.rotate(BytesPath::new(path.clone()).as_ref()).await {
+            emit!(FileIoError {
+                error,
+                code: "failed_rotating_file",
+                message: "Failed to rotate file.",
+                path,
+                dropped_events: 0,
+            });
+        }
+    }
+
     async fn process_event(&mut self, mut event: Event) {
         let path = match self.partition_event(&event) {
             Some(path) => path,
@@ -344,7 +404,7 @@ impl FileSink {
             file
         } else {
             trace!(message = "Opening new file.", ?path);
-            let file = match open_file(BytesPath::new(path.clone())).await {
+            let (file, bytes_written) = match open_file(BytesPath::new(path.clone())).await {
                 Ok(file) => file,
                 Err(error) => {
                     // We couldn't open the file for this event.
@@ -362,7 +422,11 @@ impl FileSink {
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
+            let outfile = OpenFile {
+                file: OutFile::new(file, self.compression),
+                bytes_written,
+                events_written: 0,
+            };
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
             emit!(FileOpen {
@@ -374,8 +438,15 @@ impl FileSink {
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(&mut file.file, event, &self.transformer, &mut self.encoder).await
+        {
             Ok(byte_size) => {
+                file.bytes_written += byte_size as u64;
+                file.events_written += 1;
+                let rotate = self.rotation.as_ref().is_some_and(|rotation| {
+                    rotation.is_due(file.bytes_written, file.events_written)
+                });
+
                 finalizers.update_status(EventStatus::Delivered);
                 self.events_sent.emit(CountByteSize(1, event_size));
                 emit!(FileBytesSent {
@@ -383,6 +454,10 @@ impl FileSink {
                     file: String::from_utf8_lossy(&path),
                     include_file_metric_tag: self.include_file_metric_tag,
                 });
+
+                if rotate {
+                    self.rotate_file(&path).await;
+                }
             }
             Err(error) => {
                 finalizers.update_status(EventStatus::Errored);
@@ -398,20 +473,23 @@ impl FileSink {
     }
 }
 
-async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
+/// Opens the file for appending, returning it along with its current size.
+async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<(File, u64)> {
     let parent = path.as_ref().parent();
 
     if let Some(parent) = parent {
         fs::create_dir_all(parent).await?;
     }
 
-    fs::OpenOptions::new()
+    let file = fs::OpenOptions::new()
         .read(false)
         .write(true)
         .create(true)
         .append(true)
         .open(path)
-        .await
+        .await?;
+    let size = file.metadata().await?.len();
+    Ok((file, size))
 }
 
 async fn write_event_to_file(
@@ -476,6 +554,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -502,6 +581,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Gzip,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -528,6 +608,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Zstd,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -545,6 +626,40 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn log_rotation_by_event_count() {
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            compression: Compression::None,
+            rotation: Some(RotationConfig {
+                max_events: std::num::NonZeroU64::new(10),
+                ..Default::default()
+            }),
+            acknowledgements: Default::default(),
+            timezone: Default::default(),
+            internal_metrics: FileInternalMetricsConfig {
+                include_file_tag: true,
+            },
+        };
+
+        let (input, _events) = random_lines_with_stream(25, 64, None);
+
+        run_assert_log_sink(&config, input.clone()).await;
+
+        let segment = |index: usize| {
+            let mut path = template.clone().into_os_string();
+            path.push(format!(".{index}"));
+            lines_from_file(path)
+        };
+        assert_eq!(segment(2), input[..10].to_vec());
+        assert_eq!(segment(1), input[10..20].to_vec());
+        assert_eq!(lines_from_file(&template), input[20..].to_vec());
+    }
+
     #[tokio::test]
     async fn log_many_partitions() {
         let directory = temp_dir();
@@ -559,6 +674,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -641,6 +757,7 @@ mod tests {
             idle_timeout: Duration::from_secs(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -697,6 +814,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -728,6 +846,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -779,6 +898,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, JsonSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -0,0 +1,301 @@
+//! Logrotate-style rotation of the files written by the sink.
+
+use std::{
+    io,
+    num::{NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
+use serde_with::serde_as;
+use tokio::{
+    fs::{self, File},
+    io::{AsyncWrite, AsyncWriteExt},
+};
+use vector_lib::configurable::configurable_component;
+
+use super::Compression;
+
+/// Rotation of the files written by the sink.
+///
+/// Once a file reaches `max_bytes` or `max_events`, it is closed and renamed with an index suffix:
+/// the most recent segment of `/tmp/vector.log` is `/tmp/vector.log.1`, the one before it
+/// `/tmp/vector.log.2`, and so on. Retention applies to the segments of each rendered path, each
+/// time it is rotated.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    /// The number of bytes after which a file is rotated.
+    ///
+    /// Bytes are counted before compression, on top of the size of the file when it was opened.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 104857600))]
+    #[serde(default)]
+    pub max_bytes: Option<NonZeroU64>,
+
+    /// The number of events after which a file is rotated.
+    ///
+    /// Events are counted from the moment the file is opened.
+    #[configurable(metadata(docs::type_unit = "events"))]
+    #[configurable(metadata(docs::examples = 1000000))]
+    #[serde(default)]
+    pub max_events: Option<NonZeroU64>,
+
+    /// The compression applied to rotated segments, whose extension is appended to their name.
+    ///
+    /// Files that are already compressed with the `compression` option of the sink can't be
+    /// compressed again.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub compression: Compression,
+
+    /// The maximum number of rotated segments kept for each file.
+    ///
+    /// The oldest segments are deleted first.
+    #[configurable(metadata(docs::examples = 7))]
+    #[serde(default)]
+    pub max_files: Option<NonZeroUsize>,
+
+    /// The maximum age of rotated segments, after which they are deleted.
+    ///
+    /// The age of a segment is based on its last modification time.
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(default, rename = "max_age_secs")]
+    #[configurable(metadata(docs::examples = 604800))]
+    #[configurable(metadata(docs::human_name = "Maximum Age"))]
+    pub max_age: Option<Duration>,
+}
+
+impl RotationConfig {
+    /// Whether or not a file has to be rotated after the given amount of data was written to it.
+    pub(super) fn is_due(&self, bytes_written: u64, events_written: u64) -> bool {
+        self.max_bytes.is_some_and(|max| bytes_written >= max.get())
+            || self
+                .max_events
+                .is_some_and(|max| events_written >= max.get())
+    }
+
+    /// Renames the closed file at `path` to its first segment, after shifting the existing
+    /// segments and deleting the ones that are not retained anymore.
+    pub(super) async fn rotate(&self, path: &Path) -> io::Result<()> {
+        let mut segments = find_segments(path).await?;
+        // The oldest segments are shifted first, so that none of them is overwritten.
+        segments.sort_unstable_by(|a, b| b.index.cmp(&a.index));
+
+        let now = SystemTime::now();
+        for segment in segments {
+            let expired = self.max_files.is_some_and(|max| segment.index >= max.get())
+                || self.max_age.is_some_and(|max_age| {
+                    segment
+                        .modified
+                        .and_then(|modified| now.duration_since(modified).ok())
+                        .is_some_and(|age| age > max_age)
+                });
+            if expired {
+                debug!(message = "Deleting rotated file.", path = ?segment.path);
+                fs::remove_file(&segment.path).await?;
+            } else {
+                let shifted = segment_path(path, segment.index + 1, &segment.extension);
+                fs::rename(&segment.path, shifted).await?;
+            }
+        }
+
+        let rotated = segment_path(path, 1, "");
+        fs::rename(path, &rotated).await?;
+        if let Some(extension) = extension(self.compression) {
+            compress(
+                &rotated,
+                &segment_path(path, 1, extension),
+                self.compression,
+            )
+            .await?;
+            fs::remove_file(&rotated).await?;
+        }
+        debug!(message = "Rotated file.", ?path);
+
+        Ok(())
+    }
+}
+
+/// A rotated segment of a file, named after it with an index suffix and an optional extension.
+struct Segment {
+    path: PathBuf,
+    index: usize,
+    extension: String,
+    modified: Option<SystemTime>,
+}
+
+async fn find_segments(path: &Path) -> io::Result<Vec<Segment>> {
+    let Some(name) = path.file_name() else {
+        return Ok(Vec::new());
+    };
+    let prefix = format!("{}.", name.to_string_lossy());
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut segments = Vec::new();
+    let mut entries = fs::read_dir(directory).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name();
+        let Some(suffix) = file_name
+            .to_str()
+            .and_then(|file_name| file_name.strip_prefix(&prefix))
+        else {
+            continue;
+        };
+        let (index, extension) = suffix.split_once('.').unwrap_or((suffix, ""));
+        match index.parse() {
+            Ok(index) if index > 0 => segments.push(Segment {
+                path: entry.path(),
+                index,
+                extension: extension.to_string(),
+                modified: entry.metadata().await?.modified().ok(),
+            }),
+            _ => continue,
+        }
+    }
+
+    Ok(segments)
+}
+
+fn segment_path(path: &Path, index: usize, extension: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{index}"));
+    if !extension.is_empty() {
+        name.push(format!(".{extension}"));
+    }
+    path.with_file_name(name)
+}
+
+const fn extension(compression: Compression) -> Option<&'static str> {
+    match compression {
+        Compression::Gzip => Some("gz"),
+        Compression::Zstd => Some("zst"),
+        Compression::None => None,
+    }
+}
+
+async fn compress(source: &Path, destination: &Path, compression: Compression) -> io::Result<()> {
+    let input = File::open(source).await?;
+    let output = File::create(destination).await?;
+    match compression {
+        Compression::Gzip => copy(input, GzipEncoder::new(output)).await,
+        Compression::Zstd => copy(input, ZstdEncoder::new(output)).await,
+        Compression::None => copy(input, output).await,
+    }
+}
+
+async fn copy(mut input: File, mut output: impl AsyncWrite + Unpin) -> io::Result<()> {
+    tokio::io::copy(&mut input, &mut output).await?;
+    output.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn names(directory: &Path) -> Vec<String> {
+        let mut names = std::fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn shifts_segments_and_keeps_max_files() {
+        let directory = temp_dir();
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("vector.log");
+        let rotation = RotationConfig {
+            max_files: NonZeroUsize::new(2),
+            ..Default::default()
+        };
+
+        for content in ["first", "second", "third"] {
+            std::fs::write(&path, content).unwrap();
+            rotation.rotate(&path).await.unwrap();
+        }
+
+        assert_eq!(names(&directory), ["vector.log.1", "vector.log.2"]);
+        assert_eq!(
+            std::fs::read_to_string(directory.join("vector.log.1")).unwrap(),
+            "third"
+        );
+        assert_eq!(
+            std::fs::read_to_string(directory.join("vector.log.2")).unwrap(),
+            "second"
+        );
+    }
+
+    #[tokio::test]
+    async fn compresses_rotated_segments() {
+        let directory = temp_dir();
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("vector.log");
+        let rotation = RotationConfig {
+            compression: Compression::Gzip,
+            ..Default::default()
+        };
+
+        for content in ["first", "second"] {
+            std::fs::write(&path, content).unwrap();
+            rotation.rotate(&path).await.unwrap();
+        }
+
+        assert_eq!(names(&directory), ["vector.log.1.gz", "vector.log.2.gz"]);
+        assert_eq!(
+            crate::test_util::lines_from_gzip_file(directory.join("vector.log.2.gz")),
+            ["first"]
+        );
+    }
+
+    #[tokio::test]
+    async fn deletes_expired_segments() {
+        let directory = temp_dir();
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("vector.log");
+        let expired = directory.join("vector.log.1");
+        std::fs::write(&expired, "expired").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&expired)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+
+        let rotation = RotationConfig {
+            max_age: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        std::fs::write(&path, "recent").unwrap();
+        rotation.rotate(&path).await.unwrap();
+
+        assert_eq!(names(&directory), ["vector.log.1"]);
+        assert_eq!(
+            std::fs::read_to_string(directory.join("vector.log.1")).unwrap(),
+            "recent"
+        );
+    }
+
+    #[test]
+    fn is_due_on_either_limit() {
+        let rotation = RotationConfig {
+            max_bytes: NonZeroU64::new(100),
+            max_events: NonZeroU64::new(10),
+            ..Default::default()
+        };
+
+        assert!(!rotation.is_due(99, 9));
+        assert!(rotation.is_due(100, 1));
+        assert!(rotation.is_due(1, 10));
+        assert!(!RotationConfig::default().is_due(u64::MAX, u64::MAX));
+    }
+}
@@ -580,7 +580,7 @@ struct Label {
 impl ChronicleRequestBuilder {
     fn new(config: &ChronicleUnstructuredConfig) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
-        let serializer = config.encoding.config().build()?;
+        let serializer = config.encoding.build()?;
         let compression = Compression::from(config.compression);
         let encoder = crate::codecs::Encoder::<()>::new(serializer);
         let encoder = ChronicleEncoder {
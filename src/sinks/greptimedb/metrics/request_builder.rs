@@ -4,7 +4,7 @@ use greptimedb_ingester::{api::v1::*, helpers::values::*};
 use vector_lib::{
     event::{
         Metric, MetricValue,
-        metric::{Bucket, MetricSketch, Quantile, Sample},
+        metric::{Bucket, Quantile, Sample},
     },
     metrics::AgentDDSketch,
 };
@@ -126,8 +126,7 @@ pub fn metric_to_insert_request(
             encode_f64_value("sum", *sum, &mut schema, &mut columns);
         }
         MetricValue::Sketch { sketch } => {
-            let MetricSketch::AgentDDSketch(sketch) = sketch;
-            encode_sketch(sketch, &mut schema, &mut columns);
+            encode_sketch(&sketch.to_agent_ddsketch(), &mut schema, &mut columns);
         }
     }
 
@@ -240,7 +239,7 @@ mod tests {
     use similar_asserts::assert_eq;
 
     use super::*;
-    use crate::event::metric::{MetricKind, StatisticKind};
+    use crate::event::metric::{MetricKind, MetricSketch, StatisticKind};
 
     fn get_column(rows: &Rows, name: &str) -> f64 {
         let (col_index, _) = rows
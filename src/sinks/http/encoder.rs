@@ -12,7 +12,7 @@ use vector_lib::codecs::{
     encoding::{
         Framer,
         Framer::{CharacterDelimited, NewlineDelimited},
-        Serializer::Json,
+        Serializer::{Json, Parquet},
     },
 };
 
@@ -49,6 +49,11 @@ impl SinkEncoder<Vec<Event>> for HttpEncoder {
         events: Vec<Event>,
         writer: &mut dyn io::Write,
     ) -> io::Result<(usize, GroupedCountByteSize)> {
+        if let Parquet(_) = self.encoder.serializer() {
+            // The whole batch is written as a single Parquet file.
+            return (self.transformer.clone(), self.encoder.clone()).encode_input(events, writer);
+        }
+
         let mut encoder = self.encoder.clone();
         let mut byte_size = telemetry().create_request_count_byte_size();
         let mut body = BytesMut::new();
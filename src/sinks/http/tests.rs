@@ -13,7 +13,7 @@ use hyper::{Body, Method, Response, StatusCode};
 use serde::{Deserialize, de};
 use vector_lib::codecs::{
     JsonSerializerConfig, NewlineDelimitedEncoderConfig, TextSerializerConfig,
-    encoding::{Framer, FramingConfig},
+    encoding::{Framer, FramingConfig, ParquetSerializerConfig},
 };
 use vector_lib::event::{BatchNotifier, BatchStatus, Event, LogEvent};
 use vector_lib::finalization::AddBatchNotifier;
@@ -119,6 +119,32 @@ fn http_encode_event_ndjson() {
     assert_eq!(output.message, "hello world".to_string());
 }
 
+#[test]
+fn http_encode_events_parquet() {
+    let events = vec![
+        Event::Log(LogEvent::from("hello")),
+        Event::Log(LogEvent::from("world")),
+    ];
+
+    let cfg = default_cfg((None::<FramingConfig>, ParquetSerializerConfig::default()).into());
+    let encoder = cfg.build_encoder().unwrap();
+    let transformer = cfg.encoding.transformer();
+
+    let encoder = HttpEncoder::new(encoder, transformer, "".to_owned(), "".to_owned());
+
+    let mut encoded = vec![];
+    let (encoded_size, _byte_size) = encoder.encode_input(events, &mut encoded).unwrap();
+
+    // The batch is written as a single file, which starts and ends with the Parquet magic.
+    assert_eq!(encoded.len(), encoded_size);
+    assert!(encoded.starts_with(b"PAR1"));
+    assert!(encoded.ends_with(b"PAR1"));
+    assert_eq!(
+        encoded.windows(4).filter(|bytes| bytes == b"PAR1").count(),
+        2
+    );
+}
+
 #[test]
 fn http_validates_normal_headers() {
     let config = r#"
@@ -6,7 +6,7 @@ use tower::Service;
 use vector_lib::configurable::configurable_component;
 use vector_lib::{
     ByteSizeOf, EstimatedJsonEncodedSizeOf,
-    event::metric::{MetricSketch, MetricTags, Quantile},
+    event::metric::{MetricTags, Quantile},
 };
 
 use crate::{
@@ -363,41 +363,40 @@ fn get_type_and_fields(
             let fields = encode_distribution(samples, quantiles);
             ("distribution", fields)
         }
-        MetricValue::Sketch { sketch } => match sketch {
-            MetricSketch::AgentDDSketch(ddsketch) => {
-                // Hard-coded quantiles because InfluxDB can't natively do anything useful with the
-                // actual bins.
-                let mut fields = [0.5, 0.75, 0.9, 0.99]
-                    .iter()
-                    .map(|q| {
-                        let quantile = Quantile {
-                            quantile: *q,
-                            value: ddsketch.quantile(*q).unwrap_or(0.0),
-                        };
-                        (
-                            quantile.to_percentile_string().into(),
-                            Field::Float(quantile.value),
-                        )
-                    })
-                    .collect::<HashMap<KeyString, _>>();
-                fields.insert(
-                    "count".into(),
-                    Field::UnsignedInt(u64::from(ddsketch.count())),
-                );
-                fields.insert(
-                    "min".into(),
-                    Field::Float(ddsketch.min().unwrap_or(f64::MAX)),
-                );
-                fields.insert(
-                    "max".into(),
-                    Field::Float(ddsketch.max().unwrap_or(f64::MIN)),
-                );
-                fields.insert("sum".into(), Field::Float(ddsketch.sum().unwrap_or(0.0)));
-                fields.insert("avg".into(), Field::Float(ddsketch.avg().unwrap_or(0.0)));
-
-                ("sketch", Some(fields))
-            }
-        },
+        MetricValue::Sketch { sketch } => {
+            let ddsketch = sketch.to_agent_ddsketch();
+            // Hard-coded quantiles because InfluxDB can't natively do anything useful with the
+            // actual bins.
+            let mut fields = [0.5, 0.75, 0.9, 0.99]
+                .iter()
+                .map(|q| {
+                    let quantile = Quantile {
+                        quantile: *q,
+                        value: ddsketch.quantile(*q).unwrap_or(0.0),
+                    };
+                    (
+                        quantile.to_percentile_string().into(),
+                        Field::Float(quantile.value),
+                    )
+                })
+                .collect::<HashMap<KeyString, _>>();
+            fields.insert(
+                "count".into(),
+                Field::UnsignedInt(u64::from(ddsketch.count())),
+            );
+            fields.insert(
+                "min".into(),
+                Field::Float(ddsketch.min().unwrap_or(f64::MAX)),
+            );
+            fields.insert(
+                "max".into(),
+                Field::Float(ddsketch.max().unwrap_or(f64::MIN)),
+            );
+            fields.insert("sum".into(), Field::Float(ddsketch.sum().unwrap_or(0.0)));
+            fields.insert("avg".into(), Field::Float(ddsketch.avg().unwrap_or(0.0)));
+
+            ("sketch", Some(fields))
+        }
     }
 }
 
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
 
 use futures::FutureExt;
 use rdkafka::ClientConfig;
@@ -129,6 +129,10 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<ConfigTargetPath>,
 
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub transaction: Option<KafkaTransactionConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -138,6 +142,63 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Configuration for producing events within Kafka transactions.
+///
+/// Events are grouped into transactions, each of them committed atomically. When events come from
+/// a `kafka` source with acknowledgements enabled, the offsets of their messages are committed to
+/// the consumer group of the source within the same transaction, so that a Kafka-to-Kafka pipeline
+/// delivers each message exactly once. Acknowledgements must then be enabled on this sink, and
+/// consumers of the topic must use `isolation.level=read_committed` to only read committed events.
+///
+/// When a transaction is aborted, the source consumes the partitions of its messages again from
+/// the first one, and the events of the later messages it had already consumed are rejected.
+///
+/// In this mode, the `rate_limit_*` options apply to transactions instead of single events.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionConfig {
+    /// The transactional ID of the producer.
+    ///
+    /// It must be unique to this sink and stable across restarts, as Kafka uses it to abort the
+    /// transactions left open by a previous instance of the producer.
+    #[configurable(metadata(docs::examples = "vector-kafka-sink"))]
+    pub transactional_id: String,
+
+    /// The maximum number of events within a single transaction.
+    #[serde(default = "default_transaction_max_events")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub max_events: NonZeroUsize,
+
+    /// The maximum time, in milliseconds, events wait before their transaction is committed.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_commit_interval_ms")]
+    #[configurable(metadata(docs::human_name = "Commit Interval"))]
+    pub commit_interval_ms: Duration,
+
+    /// The maximum time, in milliseconds, a transaction can remain open before the broker aborts
+    /// it.
+    ///
+    /// Also used as the timeout of the operations committing and aborting transactions.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_timeout_ms")]
+    #[configurable(metadata(docs::human_name = "Transaction Timeout"))]
+    pub timeout_ms: Duration,
+}
+
+const fn default_transaction_max_events() -> NonZeroUsize {
+    NonZeroUsize::new(1000).expect("static non-zero number")
+}
+
+const fn default_transaction_commit_interval_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+const fn default_transaction_timeout_ms() -> Duration {
+    Duration::from_millis(60000) // default in librdkafka
+}
+
 const fn default_socket_timeout_ms() -> Duration {
     Duration::from_millis(60000) // default in librdkafka
 }
@@ -243,6 +304,15 @@ impl KafkaSinkConfig {
             client_config.set(key, value.to_string());
         }
 
+        if let Some(transaction) = &self.transaction {
+            client_config
+                .set("transactional.id", &transaction.transactional_id)
+                .set(
+                    "transaction.timeout.ms",
+                    transaction.timeout_ms.as_millis().to_string(),
+                );
+        }
+
         for (key, value) in self.librdkafka_options.iter() {
             debug!(option = %key, value = %value, "Setting librdkafka option.");
             client_config.set(key.as_str(), value.as_str());
@@ -269,6 +339,7 @@ impl GenerateConfig for KafkaSinkConfig {
             rate_limit_num: default_rate_limit_num(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            transaction: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -304,4 +375,26 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn sets_transactional_id() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic-1234"
+            encoding.codec = "json"
+            transaction.transactional_id = "vector-kafka-sink"
+            "#,
+        )
+        .unwrap();
+        let transaction = config.transaction.as_ref().unwrap();
+        assert_eq!(transaction.max_events, default_transaction_max_events());
+
+        let client_config = config.to_rdkafka().unwrap();
+        assert_eq!(
+            client_config.get("transactional.id"),
+            Some("vector-kafka-sink")
+        );
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("60000"));
+    }
 }
@@ -33,9 +33,9 @@ pub struct KafkaRequestMetadata {
 }
 
 pub struct KafkaResponse {
-    event_byte_size: GroupedCountByteSize,
-    raw_byte_size: usize,
-    event_status: EventStatus,
+    pub(super) event_byte_size: GroupedCountByteSize,
+    pub(super) raw_byte_size: usize,
+    pub(super) event_status: EventStatus,
 }
 
 impl DriverResponse for KafkaResponse {
@@ -6,16 +6,20 @@ use rdkafka::{
     producer::{BaseProducer, FutureProducer, Producer},
 };
 use snafu::{ResultExt, Snafu};
-use tower::limit::RateLimit;
 use tracing::Span;
 use vrl::path::OwnedTargetPath;
 
-use super::config::KafkaSinkConfig;
+use super::config::{KafkaSinkConfig, KafkaTransactionConfig};
 use crate::{
     config::SinkHealthcheckOptions,
+    internal_events::KafkaTransactionError,
     kafka::KafkaStatisticsContext,
     sinks::{
-        kafka::{request_builder::KafkaRequestBuilder, service::KafkaService},
+        kafka::{
+            request_builder::KafkaRequestBuilder,
+            service::KafkaService,
+            transaction::{KafkaTransactionRequest, KafkaTransactionalService},
+        },
         prelude::*,
     },
 };
@@ -30,7 +34,10 @@ pub(super) enum BuildError {
 pub struct KafkaSink {
     transformer: Transformer,
     encoder: Encoder<()>,
-    service: RateLimit<KafkaService>,
+    producer: FutureProducer<KafkaStatisticsContext>,
+    rate_limit_num: u64,
+    rate_limit_duration: Duration,
+    transaction: Option<KafkaTransactionConfig>,
     topic: Template,
     key_field: Option<OwnedTargetPath>,
     headers_key: Option<OwnedTargetPath>,
@@ -60,12 +67,10 @@ impl KafkaSink {
             headers_key: config.headers_key.map(|key| key.0),
             transformer,
             encoder,
-            service: ServiceBuilder::new()
-                .rate_limit(
-                    config.rate_limit_num,
-                    Duration::from_secs(config.rate_limit_duration_secs),
-                )
-                .service(KafkaService::new(producer)),
+            producer,
+            rate_limit_num: config.rate_limit_num,
+            rate_limit_duration: Duration::from_secs(config.rate_limit_duration_secs),
+            transaction: config.transaction,
             topic: config.topic,
             key_field: config.key_field.map(|key| key.0),
         })
@@ -78,7 +83,7 @@ impl KafkaSink {
             encoder: (self.transformer, self.encoder),
         };
 
-        input
+        let requests = input
             .filter_map(|event| {
                 // Compute the topic.
                 future::ready(
@@ -104,11 +109,38 @@ impl KafkaSink {
                     }
                     Ok(req) => Some(req),
                 }
-            })
-            .into_driver(self.service)
-            .protocol("kafka")
-            .run()
-            .await
+            });
+
+        let service =
+            ServiceBuilder::new().rate_limit(self.rate_limit_num, self.rate_limit_duration);
+        let Some(transaction) = self.transaction else {
+            return requests
+                .into_driver(service.service(KafkaService::new(self.producer)))
+                .protocol("kafka")
+                .run()
+                .await;
+        };
+
+        let transactional_service =
+            KafkaTransactionalService::init(self.producer, transaction.timeout_ms)
+                .await
+                .map_err(|error| {
+                    emit!(KafkaTransactionError {
+                        error,
+                        operation: "init",
+                    });
+                })?;
+        // Transactions can't overlap, so they are committed one at a time.
+        tokio_stream::StreamExt::chunks_timeout(
+            requests,
+            transaction.max_events.get(),
+            transaction.commit_interval_ms,
+        )
+        .map(KafkaTransactionRequest::new)
+        .into_driver(service.concurrency_limit(1).service(transactional_service))
+        .protocol("kafka")
+        .run()
+        .await
     }
 }
 
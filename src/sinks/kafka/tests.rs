@@ -3,33 +3,46 @@
 #[cfg(feature = "kafka-integration-tests")]
 #[cfg(test)]
 mod integration_test {
-    use std::{collections::HashMap, future::ready, thread, time::Duration};
+    use std::{collections::HashMap, future::ready, num::NonZeroUsize, thread, time::Duration};
 
     use bytes::Bytes;
     use futures::StreamExt;
     use rdkafka::{
         Message, Offset, TopicPartitionList,
+        admin::{
+            AdminClient, AdminOptions, AlterConfig, NewTopic, ResourceSpecifier, TopicReplication,
+        },
+        client::DefaultClientContext,
         consumer::{BaseConsumer, Consumer},
         message::Headers,
+        producer::{BaseRecord, Producer},
     };
     use vector_lib::codecs::TextSerializerConfig;
     use vector_lib::lookup::lookup_v2::ConfigTargetPath;
     use vector_lib::{
         config::{Tags, Telemetry, init_telemetry},
-        event::{BatchNotifier, BatchStatus},
+        event::{BatchNotifier, BatchStatus, EventArray},
     };
 
-    use super::super::{config::KafkaSinkConfig, sink::KafkaSink, *};
+    use super::super::{
+        config::{KafkaSinkConfig, KafkaTransactionConfig},
+        sink::KafkaSink,
+        *,
+    };
     use crate::{
+        SourceSender,
+        config::{SourceConfig, SourceContext},
         event::{ObjectMap, Value},
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
+        shutdown::ShutdownSignal,
         sinks::prelude::*,
+        sources::kafka::KafkaSourceConfig,
         test_util::{
             components::{
                 DATA_VOLUME_SINK_TAGS, SINK_TAGS, assert_data_volume_sink_compliance,
                 assert_sink_compliance,
             },
-            random_lines_with_stream, random_string, wait_for,
+            random_lines_with_stream, random_string, wait_for, wait_for_duration,
         },
         tls::{TEST_PEM_INTERMEDIATE_CA_PATH, TlsConfig, TlsEnableableConfig},
     };
@@ -63,6 +76,7 @@ mod integration_test {
             rate_limit_num: i64::MAX as u64,
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config, Default::default())
@@ -91,6 +105,7 @@ mod integration_test {
             rate_limit_num: i64::MAX as u64,
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config, Default::default())
@@ -194,6 +209,7 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka()?;
@@ -350,6 +366,7 @@ mod integration_test {
             rate_limit_num: i64::MAX as u64,
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            transaction: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -449,4 +466,180 @@ mod integration_test {
         assert_eq!(out.len(), input.len());
         assert_eq!(out, input);
     }
+
+    fn committed_offset(consumer: &BaseConsumer, input_topic: &str) -> Offset {
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(input_topic, 0);
+        consumer
+            .committed_offsets(tpl, Duration::from_secs(10))
+            .unwrap()
+            .find_partition(input_topic, 0)
+            .unwrap()
+            .offset()
+    }
+
+    /// Sets the size of the largest record batch accepted by `topic`.
+    async fn set_max_message_bytes(server: &str, topic: &str, max_message_bytes: usize) {
+        let admin: AdminClient<DefaultClientContext> = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", server)
+            .create()
+            .unwrap();
+        let max_message_bytes = max_message_bytes.to_string();
+        let results = admin
+            .alter_configs(
+                [&AlterConfig::new(ResourceSpecifier::Topic(topic))
+                    .set("max.message.bytes", &max_message_bytes)],
+                &AdminOptions::default(),
+            )
+            .await
+            .unwrap();
+        for result in results {
+            result.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn kafka_exactly_once_aborts_and_retries() {
+        crate::test_util::trace_init();
+
+        let server = kafka_address(9091);
+        let input_topic = format!("test-input-{}", random_string(10));
+        let topic = format!("test-{}", random_string(10));
+
+        // The output topic first rejects the records larger than this, failing their transaction.
+        let admin: AdminClient<DefaultClientContext> = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", server.as_str())
+            .create()
+            .unwrap();
+        for result in admin
+            .create_topics(
+                [&NewTopic::new(&topic, 1, TopicReplication::Fixed(1))
+                    .set("max.message.bytes", "1500")],
+                &AdminOptions::default(),
+            )
+            .await
+            .unwrap()
+        {
+            result.unwrap();
+        }
+
+        let lines = (0..10)
+            .map(|offset| random_string(if offset == 7 { 3000 } else { 100 }))
+            .collect::<Vec<_>>();
+        let producer: rdkafka::producer::BaseProducer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", server.as_str())
+            .create()
+            .unwrap();
+        for line in &lines {
+            producer
+                .send(BaseRecord::<(), _>::to(&input_topic).payload(line))
+                .unwrap();
+        }
+        producer.flush(Duration::from_secs(10)).unwrap();
+
+        let group_id = random_string(10);
+        let source_config: KafkaSourceConfig = toml::from_str(&format!(
+            r#"
+                bootstrap_servers = "{server}"
+                topics = ["{input_topic}"]
+                group_id = "{group_id}"
+                auto_offset_reset = "beginning"
+            "#
+        ))
+        .unwrap();
+        let (out, events) = SourceSender::new_test();
+        let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
+        let source = source_config
+            .build(SourceContext {
+                shutdown,
+                acknowledgements: true,
+                ..SourceContext::new_test(out, None)
+            })
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            healthcheck_topic: None,
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            rate_limit_duration_secs: 1,
+            rate_limit_num: i64::MAX as u64,
+            librdkafka_options: HashMap::from([("batch.size".into(), "1000".into())]),
+            headers_key: None,
+            transaction: Some(KafkaTransactionConfig {
+                transactional_id: format!("test-{}", random_string(10)),
+                max_events: NonZeroUsize::new(5).unwrap(),
+                commit_interval_ms: Duration::from_millis(1000),
+                timeout_ms: Duration::from_millis(60000),
+            }),
+            acknowledgements: Default::default(),
+        };
+        let sink = VectorSink::from_event_streamsink(KafkaSink::new(config).unwrap());
+        let sink = tokio::spawn(sink.run(events.map(EventArray::from)));
+
+        // The transaction holding the large record keeps being aborted, and neither its records
+        // nor the offsets of its messages are committed, as the source consumes them again.
+        let consumer: BaseConsumer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", server.as_str())
+            .set("group.id", &group_id)
+            .create()
+            .unwrap();
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let offset = committed_offset(&consumer, &input_topic);
+        assert!(
+            matches!(offset, Offset::Invalid | Offset::Offset(0..=7)),
+            "{offset:?}"
+        );
+
+        // Once the record is accepted, the messages consumed again are committed.
+        set_max_message_bytes(&server, &topic, 10000).await;
+        wait_for_duration(
+            || ready(committed_offset(&consumer, &input_topic) == Offset::Offset(10)),
+            Duration::from_secs(60),
+        )
+        .await;
+
+        drop(trigger_shutdown);
+        shutdown_done.await;
+        sink.await.unwrap().unwrap();
+
+        // Consumers reading committed records see each of them exactly once.
+        let reader: BaseConsumer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", server.as_str())
+            .set("group.id", random_string(10))
+            .set("isolation.level", "read_committed")
+            .create()
+            .unwrap();
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+        reader.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 {
+            match reader.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                None if out.len() >= lines.len() => break,
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, lines);
+    }
 }
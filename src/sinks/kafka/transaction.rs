@@ -0,0 +1,415 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, atomic::Ordering},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use rdkafka::{
+    Offset, TopicPartitionList,
+    error::{KafkaError, KafkaResult},
+    producer::{FutureProducer, Producer},
+};
+use snafu::Snafu;
+
+use super::service::{KafkaRequest, KafkaResponse, KafkaService};
+use crate::{
+    internal_events::KafkaTransactionError,
+    kafka::{KafkaSourceOffset, KafkaStatisticsContext},
+    sinks::prelude::*,
+};
+
+#[derive(Debug, Snafu)]
+pub enum TransactionError {
+    #[snafu(display("Kafka transaction failed: {}", source))]
+    Kafka { source: KafkaError },
+
+    #[snafu(display("Kafka transaction aborted, as some of its records were not delivered."))]
+    RecordsNotDelivered,
+
+    #[snafu(display(
+        "Kafka transaction skipped, as its source messages are consumed again after an aborted transaction."
+    ))]
+    ConsumedAgain,
+}
+
+impl From<KafkaError> for TransactionError {
+    fn from(source: KafkaError) -> Self {
+        Self::Kafka { source }
+    }
+}
+
+/// The events produced within a single transaction.
+pub struct KafkaTransactionRequest {
+    requests: Vec<KafkaRequest>,
+    offsets: Vec<GroupOffsets>,
+    finalizers: EventFinalizers,
+    request_metadata: RequestMetadata,
+}
+
+impl KafkaTransactionRequest {
+    pub(super) fn new(mut requests: Vec<KafkaRequest>) -> Self {
+        let finalizers = requests.take_finalizers();
+        let request_metadata = RequestMetadata::from_batch(
+            requests
+                .iter()
+                .map(|request| request.request_metadata.clone()),
+        );
+
+        Self {
+            offsets: group_offsets(&finalizers),
+            requests,
+            finalizers,
+            request_metadata,
+        }
+    }
+}
+
+impl Finalizable for KafkaTransactionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransactionRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
+/// The offsets consumed by a consumer group, to commit within a transaction.
+struct GroupOffsets {
+    /// The latest position consumed by the group, holding its most recent metadata.
+    latest: Arc<KafkaSourceOffset>,
+    /// The lowest offset consumed from each topic partition.
+    first_offsets: HashMap<(String, i32), i64>,
+    /// The highest offset consumed from each topic partition.
+    offsets: HashMap<(String, i32), i64>,
+}
+
+impl GroupOffsets {
+    /// Builds the list of offsets to commit, which are the ones of the next messages to consume.
+    fn to_topic_partition_list(&self) -> KafkaResult<TopicPartitionList> {
+        let mut tpl = TopicPartitionList::new();
+        for ((topic, partition), offset) in &self.offsets {
+            tpl.add_partition_offset(topic, *partition, Offset::Offset(offset + 1))?;
+        }
+        Ok(tpl)
+    }
+}
+
+/// Groups the positions handed over by `kafka` sources through the finalizers of the events.
+///
+/// This marks the partitions of these positions as transactional, so that the sources consume
+/// the messages of aborted transactions again.
+fn group_offsets(finalizers: &EventFinalizers) -> Vec<GroupOffsets> {
+    let mut groups = HashMap::<Arc<str>, GroupOffsets>::new();
+    for position in finalizers.batch_contexts::<KafkaSourceOffset>() {
+        position.transactional.store(true, Ordering::Relaxed);
+        let group = groups
+            .entry(Arc::clone(&position.group_id))
+            .or_insert_with(|| GroupOffsets {
+                latest: Arc::clone(&position),
+                first_offsets: HashMap::new(),
+                offsets: HashMap::new(),
+            });
+        group
+            .first_offsets
+            .entry((position.topic.clone(), position.partition))
+            .and_modify(|offset| *offset = (*offset).min(position.offset))
+            .or_insert(position.offset);
+        group
+            .offsets
+            .entry((position.topic.clone(), position.partition))
+            .and_modify(|offset| *offset = (*offset).max(position.offset))
+            .or_insert(position.offset);
+        group.latest = position;
+    }
+    groups.into_values().collect()
+}
+
+/// The first offset of each partition whose transaction was aborted, by consumer group.
+///
+/// The `kafka` source consumes these partitions again from these offsets, so the events of the
+/// later messages it had already consumed are rejected rather than committed past them.
+#[derive(Clone, Default)]
+struct AbortedOffsets(Arc<Mutex<HashMap<GroupPartition, i64>>>);
+
+/// A topic partition consumed by a consumer group.
+type GroupPartition = (Arc<str>, String, i32);
+
+impl AbortedOffsets {
+    /// Lists the first offset consumed from each partition of the given groups.
+    fn first_offsets(groups: &[GroupOffsets]) -> impl Iterator<Item = (GroupPartition, i64)> + '_ {
+        groups.iter().flat_map(|group| {
+            group
+                .first_offsets
+                .iter()
+                .map(|((topic, partition), offset)| {
+                    (
+                        (
+                            Arc::clone(&group.latest.group_id),
+                            topic.clone(),
+                            *partition,
+                        ),
+                        *offset,
+                    )
+                })
+        })
+    }
+
+    /// Returns whether some of the given messages were consumed before their partition was
+    /// rewound. Otherwise, the partitions of the messages are consumed again, and no longer
+    /// tracked.
+    fn consumed_before_rewind(&self, groups: &[GroupOffsets]) -> bool {
+        let mut aborted = self.0.lock().expect("poisoned lock");
+        if Self::first_offsets(groups)
+            .any(|(key, offset)| aborted.get(&key).is_some_and(|aborted| offset > *aborted))
+        {
+            return true;
+        }
+        for (key, _) in Self::first_offsets(groups) {
+            aborted.remove(&key);
+        }
+        false
+    }
+
+    fn insert(&self, groups: &[GroupOffsets]) {
+        let mut aborted = self.0.lock().expect("poisoned lock");
+        for (key, offset) in Self::first_offsets(groups) {
+            aborted
+                .entry(key)
+                .and_modify(|aborted| *aborted = (*aborted).min(offset))
+                .or_insert(offset);
+        }
+    }
+}
+
+/// Produces each request within a transaction, along with the offsets of the source messages of
+/// its events.
+///
+/// Transactions can't overlap, so this service must not be called concurrently.
+#[derive(Clone)]
+pub struct KafkaTransactionalService {
+    service: KafkaService,
+    producer: FutureProducer<KafkaStatisticsContext>,
+    timeout: Duration,
+    aborted: AbortedOffsets,
+}
+
+impl KafkaTransactionalService {
+    /// Initializes the transactions of the producer, aborting the ones left open by a previous
+    /// instance with the same transactional ID.
+    pub(super) async fn init(
+        producer: FutureProducer<KafkaStatisticsContext>,
+        timeout: Duration,
+    ) -> KafkaResult<Self> {
+        run_blocking(&producer, move |producer| {
+            producer.init_transactions(timeout)
+        })
+        .await?;
+
+        Ok(Self {
+            service: KafkaService::new(producer.clone()),
+            producer,
+            timeout,
+            aborted: AbortedOffsets::default(),
+        })
+    }
+
+    async fn commit(
+        &mut self,
+        requests: Vec<KafkaRequest>,
+        offsets: &[GroupOffsets],
+    ) -> Result<usize, TransactionError> {
+        let sends = requests
+            .into_iter()
+            .map(|request| self.service.call(request))
+            .collect::<Vec<_>>();
+        let mut raw_byte_size = 0;
+        for response in future::try_join_all(sends).await? {
+            if response.event_status != EventStatus::Delivered {
+                return Err(TransactionError::RecordsNotDelivered);
+            }
+            raw_byte_size += response.raw_byte_size;
+        }
+
+        let timeout = self.timeout;
+        let offsets = offsets
+            .iter()
+            .map(|group| Ok((group.to_topic_partition_list()?, Arc::clone(&group.latest))))
+            .collect::<KafkaResult<Vec<_>>>()?;
+        run_blocking(&self.producer, move |producer| {
+            for (tpl, latest) in &offsets {
+                producer.send_offsets_to_transaction(tpl, &latest.group_metadata, timeout)?;
+            }
+            producer.commit_transaction(timeout)
+        })
+        .await?;
+
+        Ok(raw_byte_size)
+    }
+
+    async fn abort(&self) {
+        let timeout = self.timeout;
+        if let Err(error) = run_blocking(&self.producer, move |producer| {
+            producer.abort_transaction(timeout)
+        })
+        .await
+        {
+            emit!(KafkaTransactionError {
+                error,
+                operation: "abort",
+            });
+        }
+    }
+}
+
+impl Service<KafkaTransactionRequest> for KafkaTransactionalService {
+    type Response = KafkaResponse;
+    type Error = TransactionError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: KafkaTransactionRequest) -> Self::Future {
+        let mut this = self.clone();
+
+        Box::pin(async move {
+            let event_byte_size = request
+                .request_metadata
+                .into_events_estimated_json_encoded_byte_size();
+
+            if this.aborted.consumed_before_rewind(&request.offsets) {
+                return Err(TransactionError::ConsumedAgain);
+            }
+
+            run_blocking(&this.producer, |producer| producer.begin_transaction()).await?;
+            match this.commit(request.requests, &request.offsets).await {
+                Ok(raw_byte_size) => Ok(KafkaResponse {
+                    event_byte_size,
+                    raw_byte_size,
+                    event_status: EventStatus::Delivered,
+                }),
+                Err(error) => {
+                    this.abort().await;
+                    this.aborted.insert(&request.offsets);
+                    Err(error)
+                }
+            }
+        })
+    }
+}
+
+/// Runs a blocking operation of the producer, such as the ones committing transactions.
+async fn run_blocking<F>(producer: &FutureProducer<KafkaStatisticsContext>, f: F) -> KafkaResult<()>
+where
+    F: FnOnce(&FutureProducer<KafkaStatisticsContext>) -> KafkaResult<()> + Send + 'static,
+{
+    let producer = producer.clone();
+    tokio::task::spawn_blocking(move || f(&producer))
+        .await
+        .expect("Kafka producer operation panicked")
+}
+
+#[cfg(test)]
+mod tests {
+    use rdkafka::{
+        ClientConfig,
+        consumer::{BaseConsumer, Consumer},
+    };
+    use vector_lib::finalization::{BatchNotifier, EventFinalizer};
+
+    use super::*;
+
+    fn finalizers(
+        consumer: &BaseConsumer,
+        positions: &[(&str, &str, i32, i64)],
+    ) -> EventFinalizers {
+        let mut finalizers = EventFinalizers::default();
+        for &(group_id, topic, partition, offset) in positions {
+            let (batch, _) =
+                BatchNotifier::new_with_receiver_and_context(Arc::new(KafkaSourceOffset {
+                    group_id: group_id.into(),
+                    group_metadata: consumer.group_metadata().unwrap(),
+                    topic: topic.into(),
+                    partition,
+                    offset,
+                    transactional: Default::default(),
+                }));
+            finalizers.add(EventFinalizer::new(batch));
+        }
+        finalizers
+    }
+
+    fn consumer() -> BaseConsumer {
+        ClientConfig::new()
+            .set("bootstrap.servers", "localhost:9092")
+            .set("group.id", "group-1")
+            .create()
+            .unwrap()
+    }
+
+    #[test]
+    fn groups_highest_offsets() {
+        let consumer = consumer();
+        let mut finalizers = finalizers(
+            &consumer,
+            &[
+                ("group-1", "topic-1", 0, 5),
+                ("group-1", "topic-1", 0, 3),
+                ("group-1", "topic-1", 1, 7),
+                ("group-2", "topic-1", 0, 1),
+            ],
+        );
+        // Events not consumed by a `kafka` source have no offset to commit.
+        let (batch, _receiver) = BatchNotifier::new_with_receiver();
+        finalizers.add(EventFinalizer::new(batch));
+
+        let mut groups = group_offsets(&finalizers);
+        groups.sort_by(|a, b| a.latest.group_id.cmp(&b.latest.group_id));
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[0].offsets,
+            HashMap::from([(("topic-1".into(), 0), 5), (("topic-1".into(), 1), 7)])
+        );
+        assert_eq!(
+            groups[1].offsets,
+            HashMap::from([(("topic-1".into(), 0), 1)])
+        );
+
+        let tpl = groups[0].to_topic_partition_list().unwrap();
+        assert_eq!(
+            tpl.find_partition("topic-1", 0).unwrap().offset(),
+            Offset::Offset(6)
+        );
+    }
+
+    #[test]
+    fn rejects_messages_consumed_before_rewind() {
+        let consumer = consumer();
+        let aborted = AbortedOffsets::default();
+        let groups =
+            |positions: &[(&str, &str, i32, i64)]| group_offsets(&finalizers(&consumer, positions));
+
+        let transaction = groups(&[("group-1", "topic-1", 0, 5), ("group-1", "topic-1", 0, 6)]);
+        assert!(!aborted.consumed_before_rewind(&transaction));
+        aborted.insert(&transaction);
+
+        // Messages consumed after the aborted ones are rejected, unlike other partitions.
+        assert!(aborted.consumed_before_rewind(&groups(&[("group-1", "topic-1", 0, 7)])));
+        assert!(!aborted.consumed_before_rewind(&groups(&[("group-1", "topic-1", 1, 7)])));
+        assert!(!aborted.consumed_before_rewind(&groups(&[("group-2", "topic-1", 0, 7)])));
+
+        // Once the aborted messages are consumed again, the partition is no longer tracked.
+        assert!(!aborted.consumed_before_rewind(&groups(&[("group-1", "topic-1", 0, 5)])));
+        assert!(!aborted.consumed_before_rewind(&groups(&[("group-1", "topic-1", 0, 7)])));
+    }
+}
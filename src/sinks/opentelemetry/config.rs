@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use tonic::metadata::{AsciiMetadataValue, MetadataKey, MetadataMap};
+
+use super::{
+    service::{OtlpGrpcRetryLogic, OtlpGrpcService, OtlpHttpRequestBuilder, OtlpHttpService},
+    sink::OtlpSink,
+};
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::{
+            RealtimeSizeBasedDefaultBatchSettings,
+            http::{HttpService, http_response_retry_logic, validate_headers},
+        },
+        vector::{new_client, with_default_scheme},
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// Configuration for sending OTLP, encoded as protobuf.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OtlpSinkConfig {
+    /// The endpoint of the OTLP receiver.
+    ///
+    /// With gRPC, the address of the receiver, which must include a port. With HTTP, the base URL
+    /// of the receiver: logs, metrics and traces are sent to the `/v1/logs`, `/v1/metrics` and
+    /// `/v1/traces` paths under it.
+    #[configurable(validation(format = "uri"))]
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    #[configurable(metadata(docs::examples = "https://otlp.example.com:4318"))]
+    pub endpoint: String,
+
+    /// Whether or not to compress requests.
+    ///
+    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub compression: bool,
+
+    /// Headers sent with each request, such as the credentials expected by the receiver.
+    ///
+    /// With gRPC, they are sent as request metadata, so their names must be lowercase.
+    #[configurable(metadata(
+        docs::additional_props_description = "An HTTP request header and its value."
+    ))]
+    #[configurable(metadata(docs::examples = "headers_examples()"))]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn headers_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([(
+        "authorization".to_string(),
+        "Bearer ${OTLP_TOKEN}".to_string(),
+    )])
+}
+
+impl OtlpSinkConfig {
+    /// Builds a sink sending export requests to the OTLP/gRPC services of the receiver.
+    pub(super) fn build_grpc(&self, cx: &SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(self.tls.as_ref(), false)?;
+        let uri = with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let client = new_client(&tls, cx.proxy())?;
+
+        let mut headers = MetadataMap::new();
+        for (name, value) in &self.headers {
+            headers.insert(
+                MetadataKey::from_bytes(name.as_bytes())?,
+                AsciiMetadataValue::try_from(value.as_str())?,
+            );
+        }
+
+        let service = OtlpGrpcService::new(client, uri, self.compression, headers);
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), OtlpGrpcRetryLogic)
+            .service(service);
+
+        let sink = OtlpSink {
+            batch_settings: self.batch.validate()?.into_batcher_settings()?,
+            service,
+        };
+
+        // OTLP receivers have no health check of their own.
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            future::ok(()).boxed(),
+        ))
+    }
+
+    /// Builds a sink sending export requests to the OTLP/HTTP endpoints of the receiver.
+    pub(super) fn build_http(&self, cx: &SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(self.tls.as_ref(), false)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let request_builder = OtlpHttpRequestBuilder {
+            endpoint: self.endpoint.trim_end_matches('/').to_string(),
+            headers: validate_headers(&self.headers)?,
+            compression: self.compression,
+        };
+        let service = OtlpHttpService::new(HttpService::new(client, request_builder));
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), http_response_retry_logic())
+            .service(service);
+
+        let sink = OtlpSink {
+            batch_settings: self.batch.validate()?.into_batcher_settings()?,
+            service,
+        };
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            future::ok(()).boxed(),
+        ))
+    }
+}
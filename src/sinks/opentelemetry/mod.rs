@@ -1,3 +1,8 @@
+mod config;
+mod request;
+mod service;
+mod sink;
+
 use crate::codecs::{EncodingConfigWithFraming, Transformer};
 use crate::config::{AcknowledgementsConfig, Input, SinkConfig, SinkContext};
 use crate::sinks::http::config::{HttpMethod, HttpSinkConfig};
@@ -8,8 +13,10 @@ use vector_lib::codecs::JsonSerializerConfig;
 use vector_lib::codecs::encoding::{FramingConfig, SerializerConfig};
 use vector_lib::configurable::configurable_component;
 
+pub use config::OtlpSinkConfig;
+
 /// Configuration for the `OpenTelemetry` sink.
-#[configurable_component(sink("opentelemetry", "Deliver OTLP data over HTTP."))]
+#[configurable_component(sink("opentelemetry", "Deliver OTLP data over gRPC or HTTP."))]
 #[derive(Clone, Debug, Default)]
 pub struct OpenTelemetryConfig {
     /// Protocol configuration
@@ -18,15 +25,26 @@ pub struct OpenTelemetryConfig {
 }
 
 /// The protocol used to send data to OpenTelemetry.
-/// Currently only HTTP is supported, but we plan to support gRPC.
+///
+/// With `grpc` and `http_protobuf`, logs, metrics and traces are encoded into OTLP export
+/// requests, grouped by resource and instrumentation scope. The OTLP fields are read from where
+/// the `opentelemetry` source writes them.
 /// The proto definitions are defined [here](https://github.com/vectordotdev/vector/blob/master/lib/opentelemetry-proto/src/proto/opentelemetry-proto/opentelemetry/proto/README.md).
 #[configurable_component]
 #[derive(Clone, Debug)]
 #[serde(rename_all = "snake_case", tag = "type")]
 #[configurable(metadata(docs::enum_tag_description = "The communication protocol."))]
 pub enum Protocol {
-    /// Send data over HTTP.
+    /// Send data over HTTP, encoded with the configured codec.
+    ///
+    /// The events must already be in the shape of OTLP JSON.
     Http(HttpSinkConfig),
+
+    /// Send OTLP over gRPC.
+    Grpc(OtlpSinkConfig),
+
+    /// Send OTLP over HTTP, encoded as protobuf.
+    HttpProtobuf(OtlpSinkConfig),
 }
 
 impl Default for Protocol {
@@ -70,26 +88,27 @@ impl SinkConfig for OpenTelemetryConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         match &self.protocol {
             Protocol::Http(config) => config.build(cx).await,
+            Protocol::Grpc(config) => config.build_grpc(&cx),
+            Protocol::HttpProtobuf(config) => config.build_http(&cx),
         }
     }
 
     fn input(&self) -> Input {
         match &self.protocol {
             Protocol::Http(config) => config.input(),
+            Protocol::Grpc(_) | Protocol::HttpProtobuf(_) => Input::all(),
         }
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         match self.protocol {
             Protocol::Http(ref config) => config.acknowledgements(),
+            Protocol::Grpc(ref config) | Protocol::HttpProtobuf(ref config) => {
+                &config.acknowledgements
+            }
         }
     }
 }
 
 #[cfg(test)]
-mod test {
-    #[test]
-    fn generate_config() {
-        crate::test_util::test_generate_config::<super::OpenTelemetryConfig>();
-    }
-}
+mod test;
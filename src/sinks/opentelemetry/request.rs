@@ -0,0 +1,117 @@
+use prost::Message;
+use vector_lib::opentelemetry::{
+    logs, metrics,
+    proto::collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::ExportTraceServiceRequest,
+    },
+    spans,
+};
+
+use crate::sinks::prelude::*;
+
+/// The OTLP signals, each of them sent with its own type of request.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(super) enum Signal {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+impl Signal {
+    /// The path under which OTLP/HTTP receivers accept the signal.
+    pub(super) const fn http_path(self) -> &'static str {
+        match self {
+            Self::Logs => "/v1/logs",
+            Self::Metrics => "/v1/metrics",
+            Self::Traces => "/v1/traces",
+        }
+    }
+}
+
+/// Partitions events by signal, as each request only holds one of them.
+pub(super) struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = Signal;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => Signal::Logs,
+            Event::Metric(_) => Signal::Metrics,
+            Event::Trace(_) => Signal::Traces,
+        }
+    }
+}
+
+/// An OTLP export request of any signal.
+#[derive(Clone, Debug)]
+pub(super) enum ExportRequest {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl ExportRequest {
+    /// Encodes a batch of events of the given signal, grouped by resource and scope.
+    pub(super) fn new(signal: Signal, events: Vec<Event>) -> Self {
+        let events = events.into_iter();
+        match signal {
+            Signal::Logs => Self::Logs(logs::build_export_request(events.map(Event::into_log))),
+            Signal::Metrics => Self::Metrics(metrics::build_export_request(
+                events.map(Event::into_metric),
+            )),
+            Signal::Traces => {
+                Self::Traces(spans::build_export_request(events.map(Event::into_trace)))
+            }
+        }
+    }
+
+    pub(super) const fn signal(&self) -> Signal {
+        match self {
+            Self::Logs(_) => Signal::Logs,
+            Self::Metrics(_) => Signal::Metrics,
+            Self::Traces(_) => Signal::Traces,
+        }
+    }
+
+    pub(super) fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+
+    pub(super) fn encode_to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Logs(request) => request.encode_to_vec(),
+            Self::Metrics(request) => request.encode_to_vec(),
+            Self::Traces(request) => request.encode_to_vec(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct OtlpRequest {
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+    pub(super) payload: ExportRequest,
+}
+
+impl Finalizable for OtlpRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for OtlpRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
@@ -0,0 +1,256 @@
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http::{
+    HeaderValue, Request, Uri,
+    header::{CONTENT_ENCODING, CONTENT_TYPE},
+};
+use hyper::client::HttpConnector;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use snafu::{ResultExt, Snafu};
+use tonic::{body::BoxBody, codec::CompressionEncoding, metadata::MetadataMap};
+use vector_lib::opentelemetry::proto::collector::{
+    logs::v1::logs_service_client::LogsServiceClient,
+    metrics::v1::metrics_service_client::MetricsServiceClient,
+    trace::v1::trace_service_client::TraceServiceClient,
+};
+
+use super::request::{ExportRequest, OtlpRequest, Signal};
+use crate::{
+    internal_events::EndpointBytesSent,
+    sinks::{
+        HTTPRequestBuilderSnafu,
+        prelude::*,
+        util::{
+            Compressor,
+            http::{
+                HttpRequest, HttpResponse, HttpService, HttpServiceRequestBuilder,
+                OrderedHeaderName,
+            },
+            uri,
+        },
+        vector::HyperSvc,
+    },
+};
+
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+#[derive(Debug, Snafu)]
+pub(super) enum OtlpGrpcError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+}
+
+pub(super) struct OtlpGrpcResponse {
+    events_byte_size: GroupedCountByteSize,
+    raw_byte_size: usize,
+}
+
+impl DriverResponse for OtlpGrpcResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.raw_byte_size)
+    }
+}
+
+/// Sends export requests to the OTLP/gRPC services of the receiver.
+#[derive(Clone, Debug)]
+pub(super) struct OtlpGrpcService {
+    logs: LogsServiceClient<HyperSvc>,
+    metrics: MetricsServiceClient<HyperSvc>,
+    traces: TraceServiceClient<HyperSvc>,
+    headers: MetadataMap,
+    protocol: String,
+    endpoint: String,
+}
+
+impl OtlpGrpcService {
+    pub(super) fn new(
+        client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        uri: Uri,
+        compression: bool,
+        headers: MetadataMap,
+    ) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
+        let service = HyperSvc::new(uri, client);
+        let mut logs = LogsServiceClient::new(service.clone());
+        let mut metrics = MetricsServiceClient::new(service.clone());
+        let mut traces = TraceServiceClient::new(service);
+
+        if compression {
+            logs = logs.send_compressed(CompressionEncoding::Gzip);
+            metrics = metrics.send_compressed(CompressionEncoding::Gzip);
+            traces = traces.send_compressed(CompressionEncoding::Gzip);
+        }
+        Self {
+            logs,
+            metrics,
+            traces,
+            headers,
+            protocol,
+            endpoint,
+        }
+    }
+
+    fn request<T>(&self, message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        *request.metadata_mut() = self.headers.clone();
+        request
+    }
+}
+
+impl Service<OtlpRequest> for OtlpGrpcService {
+    type Response = OtlpGrpcResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Readiness of the clients is checked when the request is sent, as done by the `vector` sink.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut request: OtlpRequest) -> Self::Future {
+        let mut service = self.clone();
+        let byte_size = request.payload.encoded_len();
+        let metadata = std::mem::take(request.metadata_mut());
+        let events_byte_size = metadata.into_events_estimated_json_encoded_byte_size();
+
+        Box::pin(async move {
+            // Partially accepted requests are not retried, as the receiver already processed them.
+            let result = match request.payload {
+                ExportRequest::Logs(payload) => {
+                    let payload = service.request(payload);
+                    service.logs.export(payload).await.map(|_| ())
+                }
+                ExportRequest::Metrics(payload) => {
+                    let payload = service.request(payload);
+                    service.metrics.export(payload).await.map(|_| ())
+                }
+                ExportRequest::Traces(payload) => {
+                    let payload = service.request(payload);
+                    service.traces.export(payload).await.map(|_| ())
+                }
+            };
+            result.context(RequestSnafu)?;
+
+            emit!(EndpointBytesSent {
+                byte_size,
+                protocol: &service.protocol,
+                endpoint: &service.endpoint,
+            });
+            Ok(OtlpGrpcResponse {
+                events_byte_size,
+                raw_byte_size: byte_size,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct OtlpGrpcRetryLogic;
+
+impl RetryLogic for OtlpGrpcRetryLogic {
+    type Error = OtlpGrpcError;
+    type Request = OtlpRequest;
+    type Response = OtlpGrpcResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        let OtlpGrpcError::Request { source } = error;
+        // Retryable codes, as listed by the OTLP specification.
+        //
+        // See: <https://opentelemetry.io/docs/specs/otlp/#failures>
+        matches!(
+            source.code(),
+            Cancelled
+                | DeadlineExceeded
+                | ResourceExhausted
+                | Aborted
+                | OutOfRange
+                | Unavailable
+                | DataLoss
+        )
+    }
+}
+
+/// Builds the requests to the OTLP/HTTP endpoints of the receiver, one per signal.
+#[derive(Clone, Debug)]
+pub(super) struct OtlpHttpRequestBuilder {
+    pub(super) endpoint: String,
+    pub(super) headers: BTreeMap<OrderedHeaderName, HeaderValue>,
+    pub(super) compression: bool,
+}
+
+impl HttpServiceRequestBuilder<Signal> for OtlpHttpRequestBuilder {
+    fn build(&self, mut request: HttpRequest<Signal>) -> Result<Request<Bytes>, crate::Error> {
+        let uri = format!(
+            "{}{}",
+            self.endpoint,
+            request.get_additional_metadata().http_path()
+        );
+        let mut builder = Request::post(uri).header(CONTENT_TYPE, PROTOBUF_CONTENT_TYPE);
+        for (name, value) in &self.headers {
+            builder = builder.header(name.inner(), value);
+        }
+
+        let mut payload = request.take_payload();
+        if self.compression {
+            let mut compressor = Compressor::from(Compression::gzip_default());
+            compressor.write_all(&payload)?;
+            payload = compressor.finish()?.freeze();
+            builder = builder.header(CONTENT_ENCODING, "gzip");
+        }
+
+        builder
+            .body(payload)
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
+
+/// Sends export requests, encoded as protobuf, to the OTLP/HTTP endpoints of the receiver.
+#[derive(Clone)]
+pub(super) struct OtlpHttpService {
+    inner: HttpService<OtlpHttpRequestBuilder, Signal>,
+}
+
+impl OtlpHttpService {
+    pub(super) const fn new(inner: HttpService<OtlpHttpRequestBuilder, Signal>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Service<OtlpRequest> for OtlpHttpService {
+    type Response = HttpResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: OtlpRequest) -> Self::Future {
+        let signal = request.payload.signal();
+        let payload = Bytes::from(request.payload.encode_to_vec());
+        self.inner.call(HttpRequest::new(
+            payload,
+            request.finalizers,
+            request.metadata,
+            signal,
+        ))
+    }
+}
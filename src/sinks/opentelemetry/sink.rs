@@ -0,0 +1,64 @@
+use std::{fmt, num::NonZeroUsize};
+
+use vector_lib::opentelemetry::metrics::is_supported;
+
+use super::request::{ExportRequest, OtlpRequest, SignalPartitioner};
+use crate::{internal_events::OpenTelemetryUnsupportedMetricError, sinks::prelude::*};
+
+pub(super) struct OtlpSink<S> {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: S,
+}
+
+impl<S> OtlpSink<S>
+where
+    S: Service<OtlpRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+
+        input
+            .filter(|event| {
+                future::ready(match event {
+                    Event::Metric(metric) if !is_supported(metric.value()) => {
+                        emit!(OpenTelemetryUnsupportedMetricError { metric });
+                        false
+                    }
+                    _ => true,
+                })
+            })
+            .batched_partitioned(SignalPartitioner, || batch_settings.as_byte_size_config())
+            .map(|(signal, mut events)| {
+                let finalizers = events.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&events);
+                let payload = ExportRequest::new(signal, events);
+                let request_size = NonZeroUsize::new(payload.encoded_len())
+                    .expect("payload should never be zero length");
+
+                OtlpRequest {
+                    finalizers,
+                    metadata: builder.with_request_size(request_size),
+                    payload,
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OtlpSink<S>
+where
+    S: Service<OtlpRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,206 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{StreamExt, stream};
+use prost::Message;
+use vector_lib::{
+    event::{BatchNotifier, BatchStatus, Metric, MetricKind, MetricValue, TraceEvent},
+    opentelemetry::proto::{
+        collector::{
+            logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+            metrics::v1::ExportMetricsServiceRequest,
+            trace::v1::ExportTraceServiceRequest,
+        },
+        common::v1::any_value::Value as PBValue,
+    },
+};
+use vrl::event_path;
+
+use super::OpenTelemetryConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    sinks::util::test::{build_test_server, build_test_server_generic},
+    test_util::{
+        components::{HTTP_SINK_TAGS, run_and_assert_sink_compliance},
+        next_addr,
+    },
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<OpenTelemetryConfig>();
+}
+
+// One byte for the compression flag plus four bytes for the length.
+const GRPC_HEADER_SIZE: usize = 5;
+
+fn log(service: &str, message: &str) -> Event {
+    let mut log = LogEvent::from(message);
+    log.insert(event_path!("resources", "service.name"), service);
+    log.insert(event_path!("severity_text"), "INFO");
+    log.into()
+}
+
+fn events(batch: BatchNotifier) -> Vec<Event> {
+    let mut trace = TraceEvent::default();
+    trace.insert(event_path!("trace_id"), "0102030405060708090a0b0c0d0e0f10");
+    trace.insert(event_path!("name"), "checkout");
+
+    vec![
+        log("checkout", "first"),
+        log("payment", "second"),
+        log("checkout", "third"),
+        Event::Metric(Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )),
+        Event::Trace(trace),
+    ]
+    .into_iter()
+    .map(|event| event.with_batch_notifier(&batch))
+    .collect()
+}
+
+#[tokio::test]
+async fn http_protobuf_sends_each_signal_to_its_path() {
+    let addr = next_addr();
+    let config: OpenTelemetryConfig = toml::from_str(&format!(
+        r#"
+            [protocol]
+            type = "http_protobuf"
+            endpoint = "http://{addr}/"
+            headers.x-api-key = "secret"
+        "#
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+    let (rx, trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    run_and_assert_sink_compliance(sink, stream::iter(events(batch)), &HTTP_SINK_TAGS).await;
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let mut requests = rx.collect::<Vec<_>>().await;
+    requests.sort_by(|(a, _), (b, _)| a.uri.path().cmp(b.uri.path()));
+    let paths = requests
+        .iter()
+        .map(|(parts, _)| parts.uri.path())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, ["/v1/logs", "/v1/metrics", "/v1/traces"]);
+    for (parts, _) in &requests {
+        assert_eq!(parts.headers["content-type"], "application/x-protobuf");
+        assert_eq!(parts.headers["x-api-key"], "secret");
+    }
+
+    let logs = ExportLogsServiceRequest::decode(requests[0].1.clone()).unwrap();
+    assert_eq!(logs.resource_logs.len(), 2);
+    let bodies = logs.resource_logs[0].scope_logs[0]
+        .log_records
+        .iter()
+        .map(|record| record.body.clone().and_then(|body| body.value))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bodies,
+        [
+            Some(PBValue::StringValue("first".to_string())),
+            Some(PBValue::StringValue("third".to_string())),
+        ]
+    );
+
+    let metrics = ExportMetricsServiceRequest::decode(requests[1].1.clone()).unwrap();
+    assert_eq!(
+        metrics.resource_metrics[0].scope_metrics[0].metrics[0].name,
+        "requests"
+    );
+
+    let traces = ExportTraceServiceRequest::decode(requests[2].1.clone()).unwrap();
+    let span = &traces.resource_spans[0].scope_spans[0].spans[0];
+    assert_eq!(span.name, "checkout");
+    assert_eq!(span.trace_id, (1..=16).collect::<Vec<u8>>());
+}
+
+#[tokio::test]
+async fn grpc_exports_logs() {
+    let addr = next_addr();
+    let config: OpenTelemetryConfig = toml::from_str(&format!(
+        r#"
+            [protocol]
+            type = "grpc"
+            endpoint = "http://{addr}"
+            headers.authorization = "Bearer secret"
+        "#
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+    let (rx, trigger, server) = build_test_server_generic(addr, || {
+        hyper::Response::builder()
+            .header("grpc-status", "0") // OK
+            .header("content-type", "application/grpc")
+            .body(hyper::Body::from(encode_body(
+                ExportLogsServiceResponse::default(),
+            )))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![log("checkout", "first").with_batch_notifier(&batch)];
+    drop(batch);
+    run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let requests = rx.collect::<Vec<_>>().await;
+    assert_eq!(requests.len(), 1);
+    let (parts, body) = &requests[0];
+    assert_eq!(
+        parts.uri.path(),
+        "/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+    );
+    assert_eq!(parts.headers["authorization"], "Bearer secret");
+
+    let logs = ExportLogsServiceRequest::decode(body.slice(GRPC_HEADER_SIZE..)).unwrap();
+    let record = &logs.resource_logs[0].scope_logs[0].log_records[0];
+    assert_eq!(record.severity_text, "INFO");
+}
+
+#[tokio::test]
+async fn grpc_rejects_events_on_permanent_errors() {
+    let addr = next_addr();
+    let config: OpenTelemetryConfig = toml::from_str(&format!(
+        r#"
+            [protocol]
+            type = "grpc"
+            endpoint = "http://{addr}"
+        "#
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+    let (_rx, trigger, server) = build_test_server_generic(addr, || {
+        hyper::Response::builder()
+            .header("grpc-status", "3") // invalid argument
+            .header("content-type", "application/grpc")
+            .body(tonic::body::empty_body())
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![log("checkout", "first").with_batch_notifier(&batch)];
+    drop(batch);
+    sink.run_events(events).await.expect("Running sink failed");
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+/// Frames a message as the body of a gRPC response.
+fn encode_body<T: Message>(msg: T) -> Bytes {
+    let mut buf = BytesMut::with_capacity(GRPC_HEADER_SIZE + msg.encoded_len());
+    // No compression, followed by the length of the message.
+    buf.put_u8(0);
+    buf.put_u32(msg.encoded_len() as u32);
+    msg.encode(&mut buf).unwrap();
+    buf.freeze()
+}
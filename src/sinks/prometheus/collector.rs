@@ -3,7 +3,12 @@ use std::{collections::BTreeMap, fmt::Write as _};
 use chrono::Utc;
 use indexmap::map::IndexMap;
 use vector_lib::event::metric::{MetricSketch, MetricTags, Quantile, samples_to_buckets};
-use vector_lib::prometheus::parser::{METRIC_NAME_LABEL, proto};
+use vector_lib::metrics::{ExponentialBuckets, ExponentialHistogram};
+use vector_lib::prometheus::parser::{
+    EXEMPLARS_KEY, MAX_SCHEMA, METADATA_KEY, METRIC_NAME_LABEL, MIN_SCHEMA, NativeHistogramBuckets,
+    NativeHistogramMetric, proto,
+};
+use vrl::{path, value::Value};
 
 use crate::{
     event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
@@ -15,7 +20,7 @@ pub(super) trait MetricCollector {
 
     fn new() -> Self;
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue);
+    fn emit_metadata(&mut self, fullname: &str, metric: &Metric);
 
     fn emit_value(
         &mut self,
@@ -27,6 +32,18 @@ pub(super) trait MetricCollector {
         extra: Option<(&str, String)>,
     );
 
+    /// Returns whether an exponential histogram can be emitted as a native histogram, rather than
+    /// as a summary.
+    fn accepts_native_histogram(&self, histogram: &ExponentialHistogram) -> bool;
+
+    fn emit_native_histogram(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        histogram: &ExponentialHistogram,
+        tags: Option<&MetricTags>,
+    );
+
     fn finish(self) -> Self::Output;
 
     fn encode_metric(
@@ -42,7 +59,7 @@ pub(super) trait MetricCollector {
 
         if metric.kind() == MetricKind::Absolute {
             let tags = metric.tags();
-            self.emit_metadata(metric.name(), name, metric.value());
+            self.emit_metadata(name, metric);
 
             match metric.value() {
                 MetricValue::Counter { value } => {
@@ -179,40 +196,44 @@ pub(super) trait MetricCollector {
                     self.emit_value(timestamp, name, "_sum", *sum, tags, None);
                     self.emit_value(timestamp, name, "_count", *count as f64, tags, None);
                 }
-                MetricValue::Sketch { sketch } => match sketch {
-                    MetricSketch::AgentDDSketch(ddsketch) => {
-                        for q in quantiles {
-                            let quantile = Quantile {
-                                quantile: *q,
-                                value: ddsketch.quantile(*q).unwrap_or(0.0),
-                            };
-                            self.emit_value(
-                                timestamp,
-                                name,
-                                "",
-                                quantile.value,
-                                tags,
-                                Some(("quantile", quantile.quantile.to_string())),
-                            );
-                        }
-                        self.emit_value(
-                            timestamp,
-                            name,
-                            "_sum",
-                            ddsketch.sum().unwrap_or(0.0),
-                            tags,
-                            None,
-                        );
+                MetricValue::Sketch {
+                    sketch: MetricSketch::ExponentialHistogram(histogram),
+                } if self.accepts_native_histogram(histogram) => {
+                    self.emit_native_histogram(timestamp, name, histogram, tags);
+                }
+                MetricValue::Sketch { sketch } => {
+                    let ddsketch = sketch.to_agent_ddsketch();
+                    for q in quantiles {
+                        let quantile = Quantile {
+                            quantile: *q,
+                            value: ddsketch.quantile(*q).unwrap_or(0.0),
+                        };
                         self.emit_value(
                             timestamp,
                             name,
-                            "_count",
-                            ddsketch.count() as f64,
+                            "",
+                            quantile.value,
                             tags,
-                            None,
+                            Some(("quantile", quantile.quantile.to_string())),
                         );
                     }
-                },
+                    self.emit_value(
+                        timestamp,
+                        name,
+                        "_sum",
+                        ddsketch.sum().unwrap_or(0.0),
+                        tags,
+                        None,
+                    );
+                    self.emit_value(
+                        timestamp,
+                        name,
+                        "_count",
+                        ddsketch.count() as f64,
+                        tags,
+                        None,
+                    );
+                }
             }
         }
     }
@@ -231,13 +252,27 @@ impl MetricCollector for StringCollector {
         Self { processed }
     }
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
+    fn emit_metadata(&mut self, fullname: &str, metric: &Metric) {
         if !self.processed.contains_key(fullname) {
-            let header = Self::encode_header(name, fullname, value);
+            let header = Self::encode_header(fullname, metric);
             self.processed.insert(fullname.into(), header);
         }
     }
 
+    fn accepts_native_histogram(&self, _histogram: &ExponentialHistogram) -> bool {
+        false
+    }
+
+    fn emit_native_histogram(
+        &mut self,
+        _timestamp_millis: Option<i64>,
+        _name: &str,
+        _histogram: &ExponentialHistogram,
+        _tags: Option<&MetricTags>,
+    ) {
+        unreachable!("the text exposition format has no native histograms");
+    }
+
     fn emit_value(
         &mut self,
         timestamp_millis: Option<i64>,
@@ -288,9 +323,14 @@ impl StringCollector {
         .ok();
     }
 
-    fn encode_header(name: &str, fullname: &str, value: &MetricValue) -> String {
-        let r#type = prometheus_metric_type(value).as_str();
-        format!("# HELP {fullname} {name}\n# TYPE {fullname} {type}\n")
+    fn encode_header(fullname: &str, metric: &Metric) -> String {
+        let r#type = prometheus_metric_type(metric.value()).as_str();
+        let help = FamilyMetadata::from_metric(metric)
+            .help
+            .unwrap_or(metric.name())
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n");
+        format!("# HELP {fullname} {help}\n# TYPE {fullname} {type}\n")
     }
 
     fn format_tag(key: &str, mut value: &str) -> String {
@@ -313,8 +353,15 @@ impl StringCollector {
 
 type Labels = Vec<proto::Label>;
 
+#[derive(Default)]
+struct Series {
+    samples: Vec<proto::Sample>,
+    histograms: Vec<proto::Histogram>,
+    exemplars: Vec<proto::Exemplar>,
+}
+
 pub(super) struct TimeSeries {
-    buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    buffer: IndexMap<Labels, Series>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
 }
@@ -351,6 +398,61 @@ impl TimeSeries {
             .timestamp
             .get_or_insert_with(|| Utc::now().timestamp_millis())
     }
+
+    /// Attaches the exemplars kept in the metadata of a metric to its series. The exemplars of
+    /// classic histograms go to the bucket holding their value.
+    pub(super) fn encode_exemplars(
+        &mut self,
+        default_namespace: Option<&str>,
+        buckets: &[f64],
+        metric: &Metric,
+    ) {
+        if metric.kind() != MetricKind::Absolute {
+            return;
+        }
+        let upper_limits = match metric.value() {
+            MetricValue::Counter { .. } | MetricValue::Gauge { .. } => None,
+            MetricValue::Sketch {
+                sketch: MetricSketch::ExponentialHistogram(histogram),
+            } if self.accepts_native_histogram(histogram) => None,
+            MetricValue::Distribution {
+                statistic: StatisticKind::Histogram,
+                ..
+            } => Some(buckets.to_vec()),
+            MetricValue::AggregatedHistogram { buckets, .. } => Some(
+                buckets
+                    .iter()
+                    .map(|bucket| bucket.upper_limit)
+                    .filter(|upper_limit| upper_limit.is_finite())
+                    .collect(),
+            ),
+            // Prometheus only keeps the exemplars of counters, gauges and histograms.
+            _ => return,
+        };
+
+        let timestamp = metric
+            .timestamp()
+            .map(|timestamp| timestamp.timestamp_millis())
+            .unwrap_or_else(|| self.default_timestamp());
+        let name = encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
+        for exemplar in exemplars_from_metadata(metric, timestamp) {
+            let labels = match &upper_limits {
+                None => Self::make_labels(metric.tags(), &name, "", None),
+                Some(upper_limits) => {
+                    let le = upper_limits
+                        .iter()
+                        .find(|upper_limit| exemplar.value <= **upper_limit)
+                        .map_or_else(|| "+Inf".to_string(), ToString::to_string);
+                    Self::make_labels(metric.tags(), &name, "_bucket", Some(("le", le)))
+                }
+            };
+            self.buffer
+                .entry(labels)
+                .or_default()
+                .exemplars
+                .push(exemplar);
+        }
+    }
 }
 
 impl MetricCollector for TimeSeries {
@@ -364,19 +466,64 @@ impl MetricCollector for TimeSeries {
         }
     }
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
+    fn emit_metadata(&mut self, fullname: &str, metric: &Metric) {
+        let name = metric.name();
         if !self.metadata.contains_key(name) {
-            let r#type = prometheus_metric_type(value);
+            let r#type = match metric.value() {
+                MetricValue::Sketch {
+                    sketch: MetricSketch::ExponentialHistogram(histogram),
+                } if self.accepts_native_histogram(histogram) => proto::MetricType::Histogram,
+                value => prometheus_metric_type(value),
+            };
+            let family = FamilyMetadata::from_metric(metric);
             let metadata = proto::MetricMetadata {
-                r#type: r#type as i32,
+                r#type: family.r#type(r#type) as i32,
                 metric_family_name: fullname.into(),
-                help: name.into(),
-                unit: String::new(),
+                help: family.help.unwrap_or(name).into(),
+                unit: family.unit.unwrap_or_default().into(),
             };
             self.metadata.insert(name.into(), metadata);
         }
     }
 
+    fn accepts_native_histogram(&self, histogram: &ExponentialHistogram) -> bool {
+        // Histograms can be brought down to a lower resolution, but not up to a higher one.
+        histogram.scale >= MIN_SCHEMA
+    }
+
+    fn emit_native_histogram(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        histogram: &ExponentialHistogram,
+        tags: Option<&MetricTags>,
+    ) {
+        let timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
+        let mut histogram = histogram.clone();
+        histogram.downscale(MAX_SCHEMA);
+
+        // The bucket at index `i` of an exponential histogram is the bucket at index `i + 1` of a
+        // native histogram.
+        let buckets = |buckets: ExponentialBuckets| NativeHistogramBuckets {
+            offset: buckets.offset + 1,
+            counts: buckets.counts,
+        };
+        let histogram = NativeHistogramMetric {
+            schema: histogram.scale,
+            zero_threshold: histogram.zero_threshold,
+            zero_count: histogram.zero_count,
+            positive: buckets(histogram.positive),
+            negative: buckets(histogram.negative),
+            count: histogram.count,
+            sum: histogram.sum,
+        };
+        self.buffer
+            .entry(Self::make_labels(tags, name, "", None))
+            .or_default()
+            .histograms
+            .push(histogram.into_proto(timestamp));
+    }
+
     fn emit_value(
         &mut self,
         timestamp_millis: Option<i64>,
@@ -390,6 +537,7 @@ impl MetricCollector for TimeSeries {
         self.buffer
             .entry(Self::make_labels(tags, name, suffix, extra))
             .or_default()
+            .samples
             .push(proto::Sample { value, timestamp });
     }
 
@@ -397,7 +545,12 @@ impl MetricCollector for TimeSeries {
         let timeseries = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
+            .map(|(labels, series)| proto::TimeSeries {
+                labels,
+                samples: series.samples,
+                exemplars: series.exemplars,
+                histograms: series.histograms,
+            })
             .collect::<Vec<_>>();
         let metadata = self
             .metadata
@@ -411,6 +564,89 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// The type, help and unit of the family of a metric, kept in its metadata by the
+/// `prometheus_remote_write` source.
+#[derive(Default)]
+struct FamilyMetadata<'a> {
+    r#type: Option<proto::MetricType>,
+    help: Option<&'a str>,
+    unit: Option<&'a str>,
+}
+
+impl<'a> FamilyMetadata<'a> {
+    fn from_metric(metric: &'a Metric) -> Self {
+        let Some(Value::Object(object)) = metric.metadata().value().get(path!(METADATA_KEY)) else {
+            return Self::default();
+        };
+        let field = |key: &str| match object.get(key) {
+            Some(Value::Bytes(bytes)) => std::str::from_utf8(bytes).ok(),
+            _ => None,
+        };
+        Self {
+            r#type: field("type")
+                .and_then(|r#type| proto::MetricType::from_str_name(&r#type.to_uppercase())),
+            help: field("help"),
+            unit: field("unit"),
+        }
+    }
+
+    /// Returns the kept type of the family if it agrees with the type of the value of the metric,
+    /// such as a gauge histogram for a histogram, or else the type of the value.
+    fn r#type(&self, value_type: proto::MetricType) -> proto::MetricType {
+        use proto::MetricType::*;
+        match (self.r#type, value_type) {
+            (Some(r#type @ (Unknown | Info | Stateset)), Gauge)
+            | (Some(r#type @ Gaugehistogram), Histogram) => r#type,
+            _ => value_type,
+        }
+    }
+}
+
+/// Builds back the exemplars kept in the metadata of a metric, with their attributes and their
+/// trace and span IDs as labels.
+fn exemplars_from_metadata(metric: &Metric, default_timestamp: i64) -> Vec<proto::Exemplar> {
+    let Some(Value::Array(exemplars)) = metric.metadata().value().get(path!(EXEMPLARS_KEY)) else {
+        return Vec::new();
+    };
+    exemplars
+        .iter()
+        .filter_map(Value::as_object)
+        .filter_map(|exemplar| {
+            let value = match exemplar.get("value")? {
+                Value::Float(value) => value.into_inner(),
+                Value::Integer(value) => *value as f64,
+                _ => return None,
+            };
+            let timestamp = match exemplar.get("timestamp") {
+                Some(Value::Timestamp(timestamp)) => timestamp.timestamp_millis(),
+                _ => default_timestamp,
+            };
+            let attributes = exemplar
+                .get("attributes")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(name, value)| (name.as_str(), value));
+            let ids = ["trace_id", "span_id"]
+                .into_iter()
+                .filter_map(|name| exemplar.get(name).map(|value| (name, value)));
+            let mut labels = attributes
+                .chain(ids)
+                .map(|(name, value)| proto::Label {
+                    name: name.into(),
+                    value: value.to_string_lossy().into_owned(),
+                })
+                .collect::<Labels>();
+            labels.sort();
+            Some(proto::Exemplar {
+                labels,
+                value,
+                timestamp,
+            })
+        })
+        .collect()
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -439,6 +675,8 @@ mod tests {
     use similar_asserts::assert_eq;
     use vector_lib::metric_tags;
 
+    use vrl::value::ObjectMap;
+
     use super::{super::default_summary_quantiles, *};
     use crate::{
         event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
@@ -487,6 +725,7 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            ..Default::default()
                         },
                     )*
                 ],
@@ -908,6 +1147,227 @@ mod tests {
         assert!(encoded.timeseries[0].samples[0].timestamp >= now);
     }
 
+    fn with_family_metadata(metric: Metric, r#type: &str, help: &str, unit: &str) -> Metric {
+        let mut metric = metric;
+        let mut object = ObjectMap::new();
+        object.insert("type".into(), r#type.into());
+        object.insert("help".into(), help.into());
+        object.insert("unit".into(), unit.into());
+        metric
+            .metadata_mut()
+            .value_mut()
+            .insert(path!(METADATA_KEY), Value::Object(object));
+        metric
+    }
+
+    #[test]
+    fn encodes_family_metadata_text() {
+        let metric = Metric::new(
+            "temperature".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 2.5 },
+        );
+        let metric = with_family_metadata(metric, "gauge", "Line one\nC:\\ line two", "celsius");
+        let encoded = encode_one::<StringCollector>(None, &[], &[], &metric);
+        assert_eq!(
+            encoded,
+            indoc! {r#"
+                # HELP temperature Line one\nC:\\ line two
+                # TYPE temperature gauge
+                temperature 2.5
+            "#}
+        );
+    }
+
+    #[test]
+    fn encodes_family_metadata_request() {
+        let metric = Metric::new(
+            "uptime".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 2.5 },
+        )
+        .with_timestamp(Some(timestamp()));
+        let metric = with_family_metadata(metric, "unknown", "Time since start.", "seconds");
+        let encoded = encode_one::<TimeSeries>(None, &[], &[], &metric);
+        assert_eq!(
+            encoded.metadata,
+            vec![proto::MetricMetadata {
+                r#type: proto::MetricType::Unknown as i32,
+                metric_family_name: "uptime".into(),
+                help: "Time since start.".into(),
+                unit: "seconds".into(),
+            }]
+        );
+
+        // A kept type that disagrees with the value is replaced.
+        let metric = with_family_metadata(metric, "counter", "Time since start.", "seconds");
+        let encoded = encode_one::<TimeSeries>(None, &[], &[], &metric);
+        assert_eq!(encoded.metadata[0].r#type, proto::MetricType::Gauge as i32);
+    }
+
+    fn native_histogram_metric(scale: i32) -> Metric {
+        Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Sketch {
+                sketch: MetricSketch::ExponentialHistogram(ExponentialHistogram {
+                    scale,
+                    zero_count: 1,
+                    zero_threshold: 0.001,
+                    positive: ExponentialBuckets {
+                        offset: -1,
+                        counts: vec![2, 3, 4, 5],
+                    },
+                    negative: ExponentialBuckets::default(),
+                    count: 15,
+                    sum: 12.5,
+                    min: None,
+                    max: None,
+                }),
+            },
+        )
+        .with_tags(Some(tags()))
+        .with_timestamp(Some(timestamp()))
+    }
+
+    #[test]
+    fn encodes_native_histogram_request() {
+        let encoded = encode_one::<TimeSeries>(None, &[], &[], &native_histogram_metric(9));
+        assert_eq!(encoded.timeseries.len(), 1);
+        let series = &encoded.timeseries[0];
+        assert_eq!(
+            series.labels,
+            vec![
+                proto::Label {
+                    name: "__name__".into(),
+                    value: "latency".into(),
+                },
+                proto::Label {
+                    name: "code".into(),
+                    value: "200".into(),
+                },
+            ]
+        );
+        assert!(series.samples.is_empty());
+        assert_eq!(
+            encoded.metadata[0].r#type,
+            proto::MetricType::Histogram as i32
+        );
+
+        // The histogram is brought down to the highest schema, merging pairs of buckets.
+        let histogram = NativeHistogramMetric::try_from(series.histograms[0].clone()).unwrap();
+        assert_eq!(
+            histogram,
+            NativeHistogramMetric {
+                schema: MAX_SCHEMA,
+                zero_threshold: 0.001,
+                zero_count: 1,
+                positive: NativeHistogramBuckets {
+                    offset: 0,
+                    counts: vec![2, 7, 5],
+                },
+                negative: NativeHistogramBuckets::default(),
+                count: 15,
+                sum: 12.5,
+            }
+        );
+        assert_eq!(series.histograms[0].timestamp, 1612325106789);
+    }
+
+    #[test]
+    fn encodes_low_resolution_histogram_as_summary() {
+        let metric = native_histogram_metric(MIN_SCHEMA - 1);
+        let encoded = encode_one::<TimeSeries>(None, &[], &[], &metric);
+        assert!(
+            encoded
+                .timeseries
+                .iter()
+                .all(|series| series.histograms.is_empty())
+        );
+        assert_eq!(
+            encoded.metadata[0].r#type,
+            proto::MetricType::Summary as i32
+        );
+
+        let encoded = encode_one::<StringCollector>(None, &[], &[], &native_histogram_metric(3));
+        assert!(encoded.contains("# TYPE latency summary\n"));
+    }
+
+    fn with_exemplar(metric: Metric, value: f64) -> Metric {
+        let mut metric = metric;
+        let mut attributes = ObjectMap::new();
+        attributes.insert("trace_id".into(), "4bf92f3577b34da6".into());
+        let mut exemplar = ObjectMap::new();
+        exemplar.insert("value".into(), Value::from_f64_or_zero(value));
+        exemplar.insert("timestamp".into(), Value::Timestamp(timestamp()));
+        exemplar.insert("attributes".into(), Value::Object(attributes));
+        metric.metadata_mut().value_mut().insert(
+            path!(EXEMPLARS_KEY),
+            Value::Array(vec![Value::Object(exemplar)]),
+        );
+        metric
+    }
+
+    fn encode_exemplars(metric: &Metric) -> proto::WriteRequest {
+        let mut time_series = TimeSeries::new();
+        time_series.encode_metric(None, &[], &[], metric);
+        time_series.encode_exemplars(None, &[], metric);
+        time_series.finish()
+    }
+
+    #[test]
+    fn encodes_histogram_exemplars() {
+        let mut histogram = VariableHistogram::new(&[1.0, 2.1, 3.0]);
+        histogram.record_many(&[0.4, 2.0, 1.75]);
+        let metric = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: histogram.buckets(),
+                count: histogram.count(),
+                sum: histogram.sum(),
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+
+        for (value, le) in [(2.0, "2.1"), (1.0, "1"), (4.5, "+Inf")] {
+            let encoded = encode_exemplars(&with_exemplar(metric.clone(), value));
+            let series = encoded
+                .timeseries
+                .iter()
+                .filter(|series| !series.exemplars.is_empty())
+                .collect::<Vec<_>>();
+            assert_eq!(series.len(), 1);
+            assert_eq!(series[0].labels[0].value, "requests_bucket");
+            assert_eq!(series[0].labels[1].value, le);
+            assert_eq!(
+                series[0].exemplars,
+                vec![proto::Exemplar {
+                    labels: vec![proto::Label {
+                        name: "trace_id".into(),
+                        value: "4bf92f3577b34da6".into(),
+                    }],
+                    value,
+                    timestamp: 1612325106789,
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn encodes_counter_exemplars() {
+        let metric = Metric::new(
+            "hits".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 10.0 },
+        )
+        .with_timestamp(Some(timestamp()));
+        let encoded = encode_exemplars(&with_exemplar(metric, 1.0));
+        assert_eq!(encoded.timeseries.len(), 1);
+        assert_eq!(encoded.timeseries[0].samples.len(), 1);
+        assert_eq!(encoded.timeseries[0].exemplars.len(), 1);
+    }
+
     fn timestamp() -> DateTime<Utc> {
         Utc.with_ymd_and_hms(2021, 2, 3, 4, 5, 6)
             .single()
@@ -34,6 +34,24 @@ pub struct RemoteWriteBatchConfig {
     pub aggregate: bool,
 }
 
+/// The version of the remote write protocol to send requests with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteWriteVersion {
+    /// [Remote write 1.0][remote_write_v1], accepted by all receivers.
+    ///
+    /// [remote_write_v1]: https://prometheus.io/docs/specs/prw/remote_write_spec/
+    #[default]
+    V1,
+
+    /// [Remote write 2.0][remote_write_v2], which interns the strings of each request and sends
+    /// the metadata of each series along with its samples.
+    ///
+    /// [remote_write_v2]: https://prometheus.io/docs/specs/prw/remote_write_spec_2_0/
+    V2,
+}
+
 /// Configuration for the `prometheus_remote_write` sink.
 #[configurable_component(sink(
     "prometheus_remote_write",
@@ -125,6 +143,11 @@ pub struct RemoteWriteConfig {
     #[serde(default = "default_compression")]
     #[derivative(Default(value = "default_compression()"))]
     pub compression: Compression,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub version: RemoteWriteVersion,
 }
 
 const fn default_compression() -> Compression {
@@ -184,6 +207,7 @@ impl SinkConfig for RemoteWriteConfig {
             client.clone(),
             endpoint.clone(),
             self.compression,
+            self.version,
             auth.clone(),
         )
         .boxed();
@@ -193,6 +217,7 @@ impl SinkConfig for RemoteWriteConfig {
             client,
             auth,
             compression: self.compression,
+            version: self.version,
         };
         let service = ServiceBuilder::new()
             .settings(request_settings, http_response_retry_logic())
@@ -210,6 +235,7 @@ impl SinkConfig for RemoteWriteConfig {
             buckets,
             quantiles,
             default_namespace,
+            version: self.version,
             expire_metrics_secs: self.expire_metrics_secs,
             service,
         };
@@ -226,11 +252,20 @@ async fn healthcheck(
     client: HttpClient,
     endpoint: Uri,
     compression: Compression,
+    version: RemoteWriteVersion,
     auth: Option<Auth>,
 ) -> crate::Result<()> {
     let body = bytes::Bytes::new();
-    let request =
-        build_request(http::Method::GET, &endpoint, compression, body, None, auth).await?;
+    let request = build_request(
+        http::Method::GET,
+        &endpoint,
+        compression,
+        version,
+        body,
+        None,
+        auth,
+    )
+    .await?;
     let response = client.send(request).await?;
 
     match response.status() {
@@ -24,7 +24,7 @@ mod tests;
 mod integration_tests;
 
 #[cfg(all(test, feature = "sources-prometheus-remote-write"))]
-pub use config::RemoteWriteConfig;
+pub use config::{RemoteWriteConfig, RemoteWriteVersion};
 
 #[cfg(feature = "aws-core")]
 #[derive(Debug, snafu::Snafu)]
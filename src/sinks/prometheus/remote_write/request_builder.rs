@@ -2,19 +2,20 @@ use std::io;
 
 use bytes::{Bytes, BytesMut};
 use prost::Message;
-use vector_lib::{config::telemetry, event::Metric};
+use vector_lib::{config::telemetry, event::Metric, prometheus::parser::proto};
 
 use crate::sinks::{
     prelude::*,
     prometheus::{collector, collector::MetricCollector as _},
 };
 
-use super::{PartitionKey, sink::EventCollection};
+use super::{PartitionKey, config::RemoteWriteVersion, sink::EventCollection};
 
 pub(crate) struct RemoteWriteEncoder {
     pub(super) default_namespace: Option<String>,
     pub(super) buckets: Vec<f64>,
     pub(super) quantiles: Vec<f64>,
+    pub(super) version: RemoteWriteVersion,
 }
 
 impl encoding::Encoder<Vec<Metric>> for RemoteWriteEncoder {
@@ -36,12 +37,14 @@ impl encoding::Encoder<Vec<Metric>> for RemoteWriteEncoder {
                 &self.quantiles,
                 &metric,
             );
+            time_series.encode_exemplars(self.default_namespace.as_deref(), &self.buckets, &metric);
         }
         let request = time_series.finish();
 
-        let mut out = BytesMut::with_capacity(request.encoded_len());
-        request.encode(&mut out).expect("Out of memory");
-        let body = out.freeze();
+        let body = match self.version {
+            RemoteWriteVersion::V1 => encode_message(&request),
+            RemoteWriteVersion::V2 => encode_message(&proto::v2::Request::from(request)),
+        };
 
         write_all(writer, len, body.as_ref())?;
 
@@ -49,6 +52,12 @@ impl encoding::Encoder<Vec<Metric>> for RemoteWriteEncoder {
     }
 }
 
+fn encode_message(message: &impl Message) -> Bytes {
+    let mut out = BytesMut::with_capacity(message.encoded_len());
+    message.encode(&mut out).expect("Out of memory");
+    out.freeze()
+}
+
 #[derive(Clone)]
 pub(super) struct RemoteWriteRequest {
     pub(super) request: Bytes,
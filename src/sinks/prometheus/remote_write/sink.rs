@@ -8,6 +8,7 @@ use crate::sinks::{prelude::*, util::buffer::metrics::MetricSet};
 
 use super::{
     PartitionKey, PrometheusMetricNormalize,
+    config::RemoteWriteVersion,
     request_builder::{RemoteWriteEncoder, RemoteWriteRequest, RemoteWriteRequestBuilder},
 };
 
@@ -149,6 +150,7 @@ pub(super) struct RemoteWriteSink<S> {
     pub(super) default_namespace: Option<String>,
     pub(super) buckets: Vec<f64>,
     pub(super) quantiles: Vec<f64>,
+    pub(super) version: RemoteWriteVersion,
     pub(super) expire_metrics_secs: Option<f64>,
     pub(super) service: S,
 }
@@ -167,6 +169,7 @@ where
                 default_namespace: self.default_namespace.clone(),
                 buckets: self.buckets.clone(),
                 quantiles: self.quantiles.clone(),
+                version: self.version,
             },
         };
 
@@ -10,7 +10,10 @@ use super::*;
 use crate::{
     config::SinkContext,
     event::{MetricKind, MetricValue},
-    sinks::{prometheus::remote_write::config::RemoteWriteConfig, util::test::build_test_server},
+    sinks::{
+        prometheus::remote_write::config::{RemoteWriteConfig, RemoteWriteVersion},
+        util::test::build_test_server,
+    },
     test_util::{
         self,
         components::{HTTP_SINK_TAGS, assert_sink_compliance},
@@ -51,6 +54,40 @@ async fn sends_request() {
     assert_eq!(req.metadata[0].metric_family_name, "gauge-2");
 }
 
+#[tokio::test]
+async fn sends_v2_request() {
+    let outputs = send_request(
+        r#"version = "v2""#,
+        vec![
+            create_event("gauge-2".into(), 32.0),
+            create_event("gauge-3".into(), 8.0),
+        ],
+    )
+    .await;
+    assert_eq!(outputs.len(), 1);
+    let (_, req) = &outputs[0];
+
+    assert_eq!(req.timeseries.len(), 2);
+    let series = req
+        .timeseries
+        .iter()
+        .find(|series| series.labels[0].value == "gauge-3")
+        .unwrap();
+    assert_eq!(
+        series.labels,
+        labels!("__name__" => "gauge-3", "production" => "true", "region" => "us-west-1")
+    );
+    assert_eq!(series.samples[0].value, 8.0);
+    assert_eq!(req.metadata.len(), 2);
+    let metadata = req
+        .metadata
+        .iter()
+        .find(|metadata| metadata.metric_family_name == "gauge-3")
+        .unwrap();
+    assert_eq!(metadata.r#type, proto::MetricType::Gauge as i32);
+    assert_eq!(metadata.help, "gauge-3");
+}
+
 #[tokio::test]
 async fn sends_authenticated_request() {
     let outputs = send_request(
@@ -248,9 +285,16 @@ async fn send_request(config: &str, events: Vec<Event>) -> Vec<(HeaderMap, proto
             assert_eq!(parts.method, "POST");
             assert_eq!(parts.uri.path(), "/write");
             let headers = parts.headers;
-            assert_eq!(headers["x-prometheus-remote-write-version"], "0.1.0");
+            let (version, content_type) = match config.version {
+                RemoteWriteVersion::V1 => ("0.1.0", "application/x-protobuf"),
+                RemoteWriteVersion::V2 => (
+                    "2.0.0",
+                    "application/x-protobuf;proto=io.prometheus.write.v2.Request",
+                ),
+            };
+            assert_eq!(headers["x-prometheus-remote-write-version"], version);
             assert_eq!(headers["content-encoding"], "snappy");
-            assert_eq!(headers["content-type"], "application/x-protobuf");
+            assert_eq!(headers["content-type"], content_type);
 
             if config.auth.is_some() {
                 assert!(headers.contains_key("authorization"));
@@ -259,8 +303,16 @@ async fn send_request(config: &str, events: Vec<Event>) -> Vec<(HeaderMap, proto
             let decoded = snap::raw::Decoder::new()
                 .decompress_vec(&body)
                 .expect("Invalid snappy compressed data");
-            let request =
-                proto::WriteRequest::decode(Bytes::from(decoded)).expect("Invalid protobuf");
+            let decoded = Bytes::from(decoded);
+            let request = match config.version {
+                RemoteWriteVersion::V1 => {
+                    proto::WriteRequest::decode(decoded).expect("Invalid protobuf")
+                }
+                RemoteWriteVersion::V2 => proto::v2::Request::decode(decoded)
+                    .expect("Invalid protobuf")
+                    .try_into()
+                    .expect("Invalid symbol references"),
+            };
             (headers, request)
         })
         .collect::<Vec<_>>()
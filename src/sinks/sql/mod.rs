@@ -0,0 +1,12 @@
+//! The generic SQL sink, which writes the fields of events as the columns of rows of a MySQL,
+//! MariaDB or SQLite table.
+
+mod config;
+mod query;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::SqlSinkConfig;
@@ -0,0 +1,341 @@
+use std::fmt::Write as _;
+
+use sqlx::{Any, any::AnyArguments, query::Query};
+use vector_lib::event::{LogEvent, Value};
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vrl::path::PathPrefix;
+
+use super::config::SqlWriteMode;
+
+/// The SQL dialect of a database.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Dialect {
+    MySql,
+    Sqlite,
+}
+
+impl Dialect {
+    pub const fn protocol(self) -> &'static str {
+        match self {
+            Self::MySql => "mysql",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    /// The maximum number of parameters that can be bound to a statement.
+    const fn max_parameters(self) -> usize {
+        match self {
+            Self::MySql => u16::MAX as usize,
+            // The limit of SQLite since version 3.32.0.
+            Self::Sqlite => 32_766,
+        }
+    }
+
+    fn quote_identifier(self, identifier: &str) -> String {
+        let quote = match self {
+            Self::MySql => '`',
+            Self::Sqlite => '"',
+        };
+        let mut quoted = String::with_capacity(identifier.len() + 2);
+        quoted.push(quote);
+        for c in identifier.chars() {
+            if c == quote {
+                quoted.push(quote);
+            }
+            quoted.push(c);
+        }
+        quoted.push(quote);
+        quoted
+    }
+
+    fn quote_table(self, table: &TableName) -> String {
+        match table {
+            TableName::Static(name) => name
+                .split('.')
+                .map(|part| self.quote_identifier(part))
+                .collect::<Vec<_>>()
+                .join("."),
+            TableName::Rendered(name) => self.quote_identifier(name),
+        }
+    }
+}
+
+/// The name of the table that rows are inserted into.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TableName {
+    /// A name from the configuration, which can include the name of its database or schema,
+    /// such as `database.table`.
+    Static(String),
+
+    /// A name rendered from a template for some events, which is quoted as a single identifier.
+    Rendered(String),
+}
+
+/// The `INSERT` statement of a sink, which is built for each table and number of rows.
+#[derive(Clone, Debug)]
+pub struct InsertStatement {
+    pub dialect: Dialect,
+    pub mode: SqlWriteMode,
+    pub columns: Vec<String>,
+    pub conflict_columns: Vec<String>,
+}
+
+impl InsertStatement {
+    /// Returns the number of rows that can be inserted by a single statement.
+    pub fn max_rows(&self) -> usize {
+        (self.dialect.max_parameters() / self.columns.len()).max(1)
+    }
+
+    /// Builds the statement inserting `rows` rows into `table`, with positional parameters.
+    pub fn build(&self, table: &TableName, rows: usize) -> String {
+        let dialect = self.dialect;
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| dialect.quote_identifier(column))
+            .collect::<Vec<_>>();
+
+        let mut sql = format!(
+            "INSERT INTO {} ({}) VALUES ",
+            dialect.quote_table(table),
+            columns.join(", ")
+        );
+        let row = format!("({})", vec!["?"; columns.len()].join(", "));
+        for index in 0..rows {
+            if index > 0 {
+                sql.push_str(", ");
+            }
+            sql.push_str(&row);
+        }
+
+        if self.mode == SqlWriteMode::Upsert {
+            let updated = self
+                .columns
+                .iter()
+                .zip(&columns)
+                .filter(|(column, _)| !self.conflict_columns.contains(column))
+                .map(|(_, quoted)| quoted);
+            match dialect {
+                Dialect::MySql => {
+                    let updates = updated
+                        .map(|column| format!("{column} = VALUES({column})"))
+                        .collect::<Vec<_>>();
+                    sql.push_str(" ON DUPLICATE KEY UPDATE ");
+                    if updates.is_empty() {
+                        // Leaves the existing row as it is.
+                        let column = dialect.quote_identifier(&self.conflict_columns[0]);
+                        _ = write!(sql, "{column} = {column}");
+                    } else {
+                        sql.push_str(&updates.join(", "));
+                    }
+                }
+                Dialect::Sqlite => {
+                    let conflict_columns = self
+                        .conflict_columns
+                        .iter()
+                        .map(|column| dialect.quote_identifier(column))
+                        .collect::<Vec<_>>();
+                    let updates = updated
+                        .map(|column| format!("{column} = excluded.{column}"))
+                        .collect::<Vec<_>>();
+                    _ = write!(sql, " ON CONFLICT ({}) DO ", conflict_columns.join(", "));
+                    if updates.is_empty() {
+                        sql.push_str("NOTHING");
+                    } else {
+                        _ = write!(sql, "UPDATE SET {}", updates.join(", "));
+                    }
+                }
+            }
+        }
+
+        sql
+    }
+}
+
+/// A value bound to a statement parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Text(String),
+}
+
+impl SqlValue {
+    pub fn bind<'q>(
+        self,
+        query: Query<'q, Any, AnyArguments<'q>>,
+    ) -> Query<'q, Any, AnyArguments<'q>> {
+        match self {
+            Self::Null => query.bind(None::<String>),
+            Self::Integer(value) => query.bind(value),
+            Self::Float(value) => query.bind(value),
+            Self::Boolean(value) => query.bind(value),
+            Self::Text(value) => query.bind(value),
+        }
+    }
+}
+
+impl From<Option<&Value>> for SqlValue {
+    fn from(value: Option<&Value>) -> Self {
+        match value {
+            None | Some(Value::Null) => Self::Null,
+            Some(Value::Integer(value)) => Self::Integer(*value),
+            Some(Value::Float(value)) => Self::Float(value.into_inner()),
+            Some(Value::Boolean(value)) => Self::Boolean(*value),
+            Some(Value::Bytes(bytes)) => Self::Text(String::from_utf8_lossy(bytes).into_owned()),
+            Some(Value::Timestamp(timestamp)) => {
+                Self::Text(timestamp.format("%Y-%m-%d %H:%M:%S%.6f").to_string())
+            }
+            Some(Value::Regex(regex)) => Self::Text(regex.as_str().to_owned()),
+            Some(value @ (Value::Object(_) | Value::Array(_))) => {
+                Self::Text(serde_json::to_string(value).expect("values are always serializable"))
+            }
+        }
+    }
+}
+
+/// Returns the values of the columns of an event.
+pub fn row(log: &LogEvent, columns: &[ConfigValuePath]) -> Vec<SqlValue> {
+    columns
+        .iter()
+        .map(|column| SqlValue::from(log.get((PathPrefix::Event, &column.0))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn table(name: &str) -> TableName {
+        TableName::Static(name.into())
+    }
+
+    fn statement(
+        dialect: Dialect,
+        mode: SqlWriteMode,
+        conflict_columns: &[&str],
+    ) -> InsertStatement {
+        InsertStatement {
+            dialect,
+            mode,
+            columns: vec!["id".into(), "message".into(), "host".into()],
+            conflict_columns: conflict_columns
+                .iter()
+                .map(|column| column.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn builds_insert_statements() {
+        assert_eq!(
+            statement(Dialect::MySql, SqlWriteMode::Insert, &[]).build(&table("logs.events"), 2),
+            "INSERT INTO `logs`.`events` (`id`, `message`, `host`) VALUES (?, ?, ?), (?, ?, ?)"
+        );
+        assert_eq!(
+            statement(Dialect::Sqlite, SqlWriteMode::Insert, &[]).build(&table("events"), 1),
+            r#"INSERT INTO "events" ("id", "message", "host") VALUES (?, ?, ?)"#
+        );
+    }
+
+    #[test]
+    fn builds_upsert_statements() {
+        assert_eq!(
+            statement(Dialect::MySql, SqlWriteMode::Upsert, &["id"]).build(&table("events"), 1),
+            "INSERT INTO `events` (`id`, `message`, `host`) VALUES (?, ?, ?) \
+             ON DUPLICATE KEY UPDATE `message` = VALUES(`message`), `host` = VALUES(`host`)"
+        );
+        assert_eq!(
+            statement(Dialect::Sqlite, SqlWriteMode::Upsert, &["id", "host"])
+                .build(&table("events"), 1),
+            r#"INSERT INTO "events" ("id", "message", "host") VALUES (?, ?, ?) ON CONFLICT ("id", "host") DO UPDATE SET "message" = excluded."message""#
+        );
+    }
+
+    #[test]
+    fn builds_upsert_statements_without_updated_columns() {
+        assert_eq!(
+            statement(
+                Dialect::MySql,
+                SqlWriteMode::Upsert,
+                &["id", "message", "host"]
+            )
+            .build(&table("events"), 1),
+            "INSERT INTO `events` (`id`, `message`, `host`) VALUES (?, ?, ?) \
+             ON DUPLICATE KEY UPDATE `id` = `id`"
+        );
+        assert_eq!(
+            statement(
+                Dialect::Sqlite,
+                SqlWriteMode::Upsert,
+                &["id", "message", "host"]
+            )
+            .build(&table("events"), 1),
+            r#"INSERT INTO "events" ("id", "message", "host") VALUES (?, ?, ?) ON CONFLICT ("id", "message", "host") DO NOTHING"#
+        );
+    }
+
+    #[test]
+    fn quotes_identifiers() {
+        assert_eq!(
+            Dialect::MySql
+                .quote_table(&TableName::Rendered("events`; DROP TABLE users; --".into())),
+            "`events``; DROP TABLE users; --`"
+        );
+        assert_eq!(
+            Dialect::MySql.quote_table(&TableName::Rendered("logs.events".into())),
+            "`logs.events`"
+        );
+        assert_eq!(Dialect::Sqlite.quote_identifier(r#"a"b"#), r#""a""b""#);
+    }
+
+    #[test]
+    fn limits_rows_per_statement() {
+        let statement = statement(Dialect::Sqlite, SqlWriteMode::Insert, &[]);
+        assert_eq!(statement.max_rows(), 10_922);
+    }
+
+    #[test]
+    fn converts_event_values() {
+        let timestamp = Utc.with_ymd_and_hms(2025, 3, 4, 15, 9, 25).unwrap();
+        let log = LogEvent::from(btreemap! {
+            "id" => 42,
+            "ratio" => 0.5,
+            "ok" => true,
+            "message" => "hello",
+            "timestamp" => timestamp,
+            "host" => btreemap! { "name" => "example.com" },
+            "tags" => vec!["a", "b"],
+        });
+        let columns = [
+            "id",
+            "ratio",
+            "ok",
+            "message",
+            "timestamp",
+            "host.name",
+            "tags",
+            "missing",
+        ]
+        .map(ConfigValuePath::from);
+
+        assert_eq!(
+            row(&log, &columns),
+            vec![
+                SqlValue::Integer(42),
+                SqlValue::Float(0.5),
+                SqlValue::Boolean(true),
+                SqlValue::Text("hello".into()),
+                SqlValue::Text("2025-03-04 15:09:25.000000".into()),
+                SqlValue::Text("example.com".into()),
+                SqlValue::Text(r#"["a","b"]"#.into()),
+                SqlValue::Null,
+            ]
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use snafu::{ResultExt, Snafu};
+use sqlx::{AnyPool, error::DatabaseError as _, mysql::MySqlDatabaseError, sqlite::SqliteError};
+use tower::Service;
+use vector_lib::event::{EventFinalizers, EventStatus, Finalizable};
+use vector_lib::request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata};
+use vector_lib::stream::DriverResponse;
+
+use super::query::{InsertStatement, SqlValue, TableName};
+use crate::internal_events::EndpointBytesSent;
+use crate::sinks::prelude::RetryLogic;
+
+/// MySQL errors that go away when the statement is retried: lock wait timeouts, deadlocks, too
+/// many connections, and lost connections.
+const MYSQL_TRANSIENT_ERRORS: [u16; 5] = [1205, 1213, 1040, 2006, 2013];
+
+/// SQLite errors that go away when the statement is retried: `SQLITE_BUSY` and `SQLITE_LOCKED`.
+const SQLITE_TRANSIENT_ERRORS: [i32; 2] = [5, 6];
+
+#[derive(Clone)]
+pub struct SqlRetryLogic;
+
+impl RetryLogic for SqlRetryLogic {
+    type Error = SqlServiceError;
+    type Request = SqlRequest;
+    type Response = SqlResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        let SqlServiceError::Database { source } = error;
+        is_transient(source)
+    }
+}
+
+fn is_transient(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::WorkerCrashed => true,
+        sqlx::Error::Database(error) => {
+            if let Some(error) = error.try_downcast_ref::<MySqlDatabaseError>() {
+                MYSQL_TRANSIENT_ERRORS.contains(&error.number())
+            } else if let Some(error) = error.try_downcast_ref::<SqliteError>() {
+                // The primary result code is kept in the lowest byte of extended result codes.
+                error
+                    .code()
+                    .and_then(|code| code.parse::<i32>().ok())
+                    .is_some_and(|code| SQLITE_TRANSIENT_ERRORS.contains(&(code & 0xff)))
+            } else {
+                false
+            }
+        }
+        _ => false,
+    }
+}
+
+#[derive(Clone)]
+pub struct SqlService {
+    connection_pool: AnyPool,
+    statement: InsertStatement,
+    endpoint: String,
+}
+
+impl SqlService {
+    pub const fn new(
+        connection_pool: AnyPool,
+        statement: InsertStatement,
+        endpoint: String,
+    ) -> Self {
+        Self {
+            connection_pool,
+            statement,
+            endpoint,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SqlRequest {
+    pub table: TableName,
+    pub rows: Vec<Vec<SqlValue>>,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for SqlRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for SqlRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub struct SqlResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for SqlResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum SqlServiceError {
+    #[snafu(display("Database error: {source}"))]
+    Database { source: sqlx::Error },
+}
+
+impl Service<SqlRequest> for SqlService {
+    type Response = SqlResponse;
+    type Error = SqlServiceError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SqlRequest) -> Self::Future {
+        let service = self.clone();
+        let future = async move {
+            let statement = &service.statement;
+            let metadata = request.metadata;
+
+            // The rows are split across statements to stay under the limit of parameters, within
+            // a transaction so that the batch is written as a whole.
+            let mut transaction = service
+                .connection_pool
+                .begin()
+                .await
+                .context(DatabaseSnafu)?;
+            for rows in request.rows.chunks(statement.max_rows()) {
+                let sql = statement.build(&request.table, rows.len());
+                let query = rows
+                    .iter()
+                    .flatten()
+                    .fold(sqlx::query(&sql), |query, value| value.clone().bind(query));
+                query
+                    .execute(&mut *transaction)
+                    .await
+                    .context(DatabaseSnafu)?;
+            }
+            transaction.commit().await.context(DatabaseSnafu)?;
+
+            emit!(EndpointBytesSent {
+                byte_size: metadata.request_encoded_size(),
+                protocol: statement.dialect.protocol(),
+                endpoint: &service.endpoint,
+            });
+
+            Ok(SqlResponse { metadata })
+        };
+
+        Box::pin(future)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_transient_errors() {
+        assert!(is_transient(&sqlx::Error::PoolTimedOut));
+        assert!(is_transient(&sqlx::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::ConnectionReset
+        ))));
+        assert!(!is_transient(&sqlx::Error::RowNotFound));
+        assert!(!is_transient(&sqlx::Error::ColumnNotFound("id".into())));
+    }
+}
@@ -0,0 +1,125 @@
+use std::num::NonZeroUsize;
+
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+
+use super::{
+    query::{self, TableName},
+    service::{SqlRequest, SqlRetryLogic, SqlService},
+};
+use crate::sinks::prelude::*;
+
+/// Partitions events by the name of the table they are written to.
+struct TablePartitioner {
+    table: Template,
+}
+
+impl Partitioner for TablePartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.table
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub struct SqlSink {
+    service: Svc<SqlService, SqlRetryLogic>,
+    table: Template,
+    columns: Vec<ConfigValuePath>,
+    batch_settings: BatcherSettings,
+}
+
+impl SqlSink {
+    pub const fn new(
+        service: Svc<SqlService, SqlRetryLogic>,
+        table: Template,
+        columns: Vec<ConfigValuePath>,
+        batch_settings: BatcherSettings,
+    ) -> Self {
+        Self {
+            service,
+            table,
+            columns,
+            batch_settings,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let columns = self.columns;
+        // Only the names from the configuration are split into their database or schema and
+        // table, so that the rendered parts of a name can't refer to another database.
+        let dynamic = self.table.is_dynamic();
+        input
+            .batched_partitioned(TablePartitioner { table: self.table }, || {
+                batch_settings.as_byte_size_config()
+            })
+            .filter_map(move |(table, events)| async move {
+                table.map(move |table| {
+                    let table = if dynamic {
+                        TableName::Rendered(table)
+                    } else {
+                        TableName::Static(table)
+                    };
+                    (table, events)
+                })
+            })
+            .filter_map(move |(table, events)| {
+                let request = build_request(table, events, &columns);
+                async move {
+                    match request {
+                        Ok(request) => Some(request),
+                        Err(e) => {
+                            warn!(
+                                message = "Error creating sql sink's request.",
+                                error = %e,
+                                internal_log_rate_limit=true
+                            );
+                            None
+                        }
+                    }
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+fn build_request(
+    table: TableName,
+    mut events: Vec<Event>,
+    columns: &[ConfigValuePath],
+) -> Result<SqlRequest, &'static str> {
+    let finalizers = events.take_finalizers();
+    let metadata_builder = RequestMetadataBuilder::from_events(&events);
+    let events_size = NonZeroUsize::new(events.estimated_json_encoded_size_of().get())
+        .ok_or("payload should never be zero length")?;
+    let metadata = metadata_builder.with_request_size(events_size);
+    let rows = events
+        .iter()
+        .map(|event| query::row(event.as_log(), columns))
+        .collect();
+    Ok(SqlRequest {
+        table,
+        rows,
+        finalizers,
+        metadata,
+    })
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for SqlSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,167 @@
+use futures::stream;
+use sqlx::{Connection, SqliteConnection};
+use vector_lib::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+use super::SqlSinkConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    sinks::util::test::load_sink,
+    test_util::{components::run_and_assert_sink_compliance, temp_file, trace_init},
+};
+
+const SQL_SINK_TAGS: [&str; 2] = ["endpoint", "protocol"];
+
+fn create_event(id: i64, application: &str, message: &str) -> Event {
+    let mut event = LogEvent::from(message);
+    event.insert("id", id);
+    event.insert("application", application);
+    event.insert("host.name", "example.com");
+    event.into()
+}
+
+async fn prepare_database(tables: &[&str]) -> (String, SqliteConnection) {
+    let endpoint = format!("sqlite://{}?mode=rwc", temp_file().display());
+    let mut connection = SqliteConnection::connect(&endpoint)
+        .await
+        .expect("Failed to open SQLite database");
+    for table in tables {
+        sqlx::query(&format!(
+            "CREATE TABLE {table} (id INTEGER PRIMARY KEY, message TEXT, host TEXT)"
+        ))
+        .execute(&mut connection)
+        .await
+        .unwrap();
+    }
+    (endpoint, connection)
+}
+
+async fn select_rows(connection: &mut SqliteConnection, table: &str) -> Vec<(i64, String, String)> {
+    sqlx::query_as(&format!(
+        "SELECT id, message, host FROM {table} ORDER BY id"
+    ))
+    .fetch_all(connection)
+    .await
+    .unwrap()
+}
+
+fn load_config(endpoint: &str, extra: &str) -> SqlSinkConfig {
+    let config = format!(
+        r#"
+            endpoint = "{endpoint}"
+            table = "events_{{{{ application }}}}"
+            columns.id = "id"
+            columns.message = "message"
+            columns.host = "host.name"
+            pool_size = 1
+            {extra}
+        "#,
+    );
+    load_sink::<SqlSinkConfig>(&config).unwrap().0
+}
+
+#[tokio::test]
+async fn healthcheck_passes() {
+    trace_init();
+
+    let (endpoint, _connection) = prepare_database(&[]).await;
+    let (_sink, healthcheck) = load_config(&endpoint, "")
+        .build(SinkContext::default())
+        .await
+        .expect("sink should build successfully");
+    assert!(healthcheck.await.is_ok());
+}
+
+#[tokio::test]
+async fn inserts_events_into_templated_tables() {
+    trace_init();
+
+    let (endpoint, mut connection) = prepare_database(&["events_api", "events_web"]).await;
+    let (sink, _healthcheck) = load_config(&endpoint, "")
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![
+        create_event(1, "api", "first"),
+        create_event(2, "web", "second"),
+        create_event(3, "api", "third"),
+    ]
+    .into_iter()
+    .map(|event| event.with_batch_notifier(&batch))
+    .collect::<Vec<_>>();
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SQL_SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    assert_eq!(
+        select_rows(&mut connection, "events_api").await,
+        vec![
+            (1, "first".into(), "example.com".into()),
+            (3, "third".into(), "example.com".into()),
+        ]
+    );
+    assert_eq!(
+        select_rows(&mut connection, "events_web").await,
+        vec![(2, "second".into(), "example.com".into())]
+    );
+}
+
+#[tokio::test]
+async fn upserts_events() {
+    trace_init();
+
+    let (endpoint, mut connection) = prepare_database(&["events_api"]).await;
+    let config = load_config(
+        &endpoint,
+        r#"
+            mode = "upsert"
+            conflict_columns = ["id"]
+        "#,
+    );
+
+    for message in ["first", "second"] {
+        let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+        let events = vec![
+            create_event(1, "api", message),
+            create_event(2, "api", message),
+        ];
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SQL_SINK_TAGS).await;
+    }
+
+    assert_eq!(
+        select_rows(&mut connection, "events_api").await,
+        vec![
+            (1, "second".into(), "example.com".into()),
+            (2, "second".into(), "example.com".into()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn rejects_batches_violating_constraints() {
+    trace_init();
+
+    let (endpoint, mut connection) = prepare_database(&["events_api"]).await;
+    let (sink, _healthcheck) = load_config(&endpoint, "")
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![
+        create_event(1, "api", "first"),
+        create_event(1, "api", "again"),
+    ]
+    .into_iter()
+    .map(|event| event.with_batch_notifier(&batch))
+    .collect::<Vec<_>>();
+    drop(batch);
+
+    sink.run_events(events).await.unwrap();
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+
+    // The batch is written in a single transaction, so none of its rows are kept.
+    assert!(select_rows(&mut connection, "events_api").await.is_empty());
+}
@@ -3,11 +3,15 @@ use std::io;
 use bytes::BytesMut;
 use itertools::{Itertools, Position};
 use tokio_util::codec::Encoder as _;
-use vector_lib::codecs::encoding::Framer;
+use vector_lib::codecs::encoding::{Framer, ParquetSerializer, Serializer};
 use vector_lib::request_metadata::GroupedCountByteSize;
 use vector_lib::{EstimatedJsonEncodedSizeOf, config::telemetry};
 
-use crate::{codecs::Transformer, event::Event, internal_events::EncoderWriteError};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    internal_events::{EncoderSerializeError, EncoderWriteError},
+};
 
 pub trait Encoder<T> {
     /// Encodes the input into the provided writer.
@@ -28,6 +32,10 @@ impl Encoder<Vec<Event>> for (Transformer, crate::codecs::Encoder<Framer>) {
         events: Vec<Event>,
         writer: &mut dyn io::Write,
     ) -> io::Result<(usize, GroupedCountByteSize)> {
+        if let Serializer::Parquet(serializer) = self.1.serializer() {
+            return encode_parquet_batch(&self.0, serializer, events, writer);
+        }
+
         let mut encoder = self.1.clone();
         let mut bytes_written = 0;
         let mut n_events_pending = events.len();
@@ -75,6 +83,34 @@ impl Encoder<Vec<Event>> for (Transformer, crate::codecs::Encoder<Framer>) {
     }
 }
 
+/// Encodes the whole batch as a single Parquet file, since the columnar layout can't be produced
+/// one event at a time.
+fn encode_parquet_batch(
+    transformer: &Transformer,
+    serializer: &ParquetSerializer,
+    mut events: Vec<Event>,
+    writer: &mut dyn io::Write,
+) -> io::Result<(usize, GroupedCountByteSize)> {
+    let mut byte_size = telemetry().create_request_count_byte_size();
+    let n_events = events.len();
+
+    for event in events.iter_mut() {
+        transformer.transform(event);
+        byte_size.add_event(event, event.estimated_json_encoded_size_of());
+    }
+
+    let mut bytes = BytesMut::new();
+    serializer
+        .encode_batch(events, &mut bytes)
+        .map_err(|error| {
+            emit!(EncoderSerializeError { error: &error });
+            io::Error::new(io::ErrorKind::InvalidData, error)
+        })?;
+    write_all(writer, n_events, &bytes)?;
+
+    Ok((bytes.len(), byte_size))
+}
+
 impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
     fn encode_input(
         &self,
@@ -152,9 +188,11 @@ mod tests {
     use std::path::PathBuf;
 
     use bytes::{BufMut, Bytes};
-    use vector_lib::codecs::encoding::{ProtobufSerializerConfig, ProtobufSerializerOptions};
+    use vector_lib::codecs::encoding::{
+        ParquetSerializerConfig, ProtobufSerializerConfig, ProtobufSerializerOptions,
+    };
     use vector_lib::codecs::{
-        CharacterDelimitedEncoder, JsonSerializerConfig, LengthDelimitedEncoder,
+        BytesEncoder, CharacterDelimitedEncoder, JsonSerializerConfig, LengthDelimitedEncoder,
         NewlineDelimitedEncoder, TextSerializerConfig,
     };
     use vector_lib::event::LogEvent;
@@ -404,6 +442,7 @@ mod tests {
             protobuf: ProtobufSerializerOptions {
                 desc_file: test_data_dir().join("test_proto.desc"),
                 message_type: "test_proto.User".to_string(),
+                schema_registry: None,
             },
         };
 
@@ -458,6 +497,7 @@ mod tests {
             protobuf: ProtobufSerializerOptions {
                 desc_file: test_data_dir().join("test_proto.desc"),
                 message_type: "test_proto.User".to_string(),
+                schema_registry: None,
             },
         };
 
@@ -503,4 +543,40 @@ mod tests {
         assert_eq!(CountByteSize(2, input_json_size), size.size().unwrap());
         assert_eq!(Bytes::copy_from_slice(&writer), expected_bytes);
     }
+
+    #[test]
+    fn test_encode_batch_parquet() {
+        let encoding = (
+            Transformer::default(),
+            crate::codecs::Encoder::<Framer>::new(
+                BytesEncoder.into(),
+                ParquetSerializerConfig::default().build().unwrap().into(),
+            ),
+        );
+
+        let input = vec![
+            Event::Log(LogEvent::from(BTreeMap::from([(
+                KeyString::from("key"),
+                Value::from("value1"),
+            )]))),
+            Event::Log(LogEvent::from(BTreeMap::from([(
+                KeyString::from("key"),
+                Value::from("value2"),
+            )]))),
+        ];
+
+        let input_json_size: JsonSize = input
+            .iter()
+            .map(|event| event.estimated_json_encoded_size_of())
+            .sum();
+
+        let mut writer = Vec::new();
+        let (written, size) = encoding.encode_input(input, &mut writer).unwrap();
+
+        // A Parquet file starts and ends with the `PAR1` magic bytes.
+        assert_eq!(written, writer.len());
+        assert!(writer.starts_with(b"PAR1"));
+        assert!(writer.ends_with(b"PAR1"));
+        assert_eq!(CountByteSize(2, input_json_size), size.size().unwrap());
+    }
 }
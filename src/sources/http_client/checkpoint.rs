@@ -0,0 +1,319 @@
+//! Persisted checkpoint of the `http_client` source, tracking the last event received.
+
+use std::{
+    cmp::Ordering,
+    io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use vector_lib::{
+    configurable::configurable_component,
+    event::{Event, Value},
+    lookup::lookup_v2::ConfigValuePath,
+};
+use vrl::path::PathPrefix;
+
+use crate::{
+    config::SourceContext,
+    internal_events::{HttpClientCheckpointLoadError, HttpClientCheckpointWriteError},
+};
+
+const CHECKPOINT_FILE_NAME: &str = "checkpoint.json";
+
+/// A checkpoint tracking the last event received, which is sent in a query parameter so that
+/// each scrape only requests the events received since the previous one.
+///
+/// The checkpoint, or watermark, is the greatest value of a field of the events received, such as
+/// a timestamp or an increasing identifier. It is saved in the data directory after each scrape
+/// whose events were all sent, up to its last page, and is kept across restarts.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointConfig {
+    /// The field of the events holding the watermark.
+    ///
+    /// Integers and floats are compared numerically, timestamps chronologically, and strings
+    /// lexicographically, which matches the chronological order of RFC 3339 timestamps of the same
+    /// time zone. Events without the field are ignored.
+    #[configurable(metadata(docs::examples = "published"))]
+    #[configurable(metadata(docs::examples = "meta.id"))]
+    pub field: ConfigValuePath,
+
+    /// The query parameter the watermark is sent in.
+    ///
+    /// Timestamps are sent in the RFC 3339 format.
+    #[configurable(metadata(docs::examples = "since"))]
+    pub param: String,
+
+    /// The value sent in `param` until a first watermark is saved.
+    ///
+    /// By default, the parameter isn't sent until a first watermark is saved.
+    #[configurable(metadata(docs::examples = "2025-01-01T00:00:00Z"))]
+    pub initial: Option<String>,
+
+    /// The directory used to persist the checkpoint.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+}
+
+/// A watermark, as stored on disk.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Watermark {
+    Integer(i64),
+    Float(f64),
+    Timestamp(DateTime<Utc>),
+    String(String),
+}
+
+impl Watermark {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(integer) => Some(Self::Integer(*integer)),
+            Value::Float(float) => Some(Self::Float(float.into_inner())),
+            Value::Timestamp(timestamp) => Some(Self::Timestamp(*timestamp)),
+            Value::Bytes(bytes) => Some(Self::String(String::from_utf8_lossy(bytes).into_owned())),
+            _ => None,
+        }
+    }
+
+    /// Compares two watermarks, which can't be compared if they are of different types.
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => Some(a.cmp(b)),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::Timestamp(a), Self::Timestamp(b)) => Some(a.cmp(b)),
+            (Self::String(a), Self::String(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    fn to_param(&self) -> String {
+        match self {
+            Self::Integer(integer) => integer.to_string(),
+            Self::Float(float) => float.to_string(),
+            Self::Timestamp(timestamp) => timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            Self::String(string) => string.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CheckpointFile {
+    watermark: Watermark,
+}
+
+/// Tracks the watermark of the source, and handles reading and writing it.
+#[derive(Debug)]
+pub(super) struct Checkpoint {
+    path: PathBuf,
+    field: ConfigValuePath,
+    param: String,
+    initial: Option<String>,
+    watermark: Option<Watermark>,
+}
+
+impl Checkpoint {
+    /// Creates the checkpoint of a source, loading the last watermark saved, if any.
+    pub(super) fn from_config(
+        config: &CheckpointConfig,
+        cx: &SourceContext,
+    ) -> crate::Result<Self> {
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), cx.key.id())?;
+        let path = data_dir.join(CHECKPOINT_FILE_NAME);
+        let watermark = load(&path);
+
+        Ok(Self {
+            path,
+            field: config.field.clone(),
+            param: config.param.clone(),
+            initial: config.initial.clone(),
+            watermark,
+        })
+    }
+
+    /// Returns the query parameter holding the watermark, if there is one to send.
+    pub(super) fn query(&self) -> Option<(String, String)> {
+        self.watermark
+            .as_ref()
+            .map(Watermark::to_param)
+            .or_else(|| self.initial.clone())
+            .map(|value| (self.param.clone(), value))
+    }
+
+    /// Updates the greatest watermark of a scrape with the events of one of its pages.
+    pub(super) fn observe(&self, greatest: &mut Option<Watermark>, events: &[Event]) {
+        let watermarks = events
+            .iter()
+            .filter_map(Event::maybe_as_log)
+            .filter_map(|log| log.get((PathPrefix::Event, &self.field.0)))
+            .filter_map(Watermark::from_value);
+        for watermark in watermarks {
+            match greatest {
+                Some(current) if watermark.compare(current) != Some(Ordering::Greater) => {}
+                _ => *greatest = Some(watermark),
+            }
+        }
+    }
+
+    /// Saves the greatest watermark of a scrape, if it is past the current one.
+    ///
+    /// A watermark of a different type than the current one replaces it, as the type of the field
+    /// changed.
+    pub(super) async fn commit(&mut self, greatest: Option<Watermark>) {
+        let Some(greatest) = greatest else {
+            return;
+        };
+        if let Some(current) = &self.watermark
+            && greatest.compare(current).is_some_and(Ordering::is_le)
+        {
+            return;
+        }
+
+        let file = CheckpointFile {
+            watermark: greatest,
+        };
+        if let Err(error) = write(&self.path, &file).await {
+            emit!(HttpClientCheckpointWriteError {
+                error,
+                path: &self.path,
+            });
+        }
+        self.watermark = Some(file.watermark);
+    }
+}
+
+fn load(path: &Path) -> Option<Watermark> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+        Err(error) => {
+            emit!(HttpClientCheckpointLoadError {
+                error: error.to_string(),
+                path,
+            });
+            return None;
+        }
+    };
+
+    match serde_json::from_slice::<CheckpointFile>(&data) {
+        Ok(file) => Some(file.watermark),
+        Err(error) => {
+            emit!(HttpClientCheckpointLoadError {
+                error: error.to_string(),
+                path,
+            });
+            None
+        }
+    }
+}
+
+/// Writes the checkpoint to a temporary file first, and then renames it over the previous one, so
+/// that a crash while writing never leaves a partially written checkpoint behind.
+async fn write(path: &Path, file: &CheckpointFile) -> io::Result<()> {
+    let data = serde_json::to_vec(file)?;
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    tokio::fs::write(&tmp_path, data).await?;
+    tokio::fs::rename(&tmp_path, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_lib::event::LogEvent;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn checkpoint(path: PathBuf) -> Checkpoint {
+        Checkpoint {
+            path,
+            field: ConfigValuePath::from("id"),
+            param: "since_id".into(),
+            initial: Some("0".into()),
+            watermark: None,
+        }
+    }
+
+    fn events(ids: impl IntoIterator<Item = Value>) -> Vec<Event> {
+        ids.into_iter()
+            .map(|id| {
+                let mut log = LogEvent::default();
+                log.insert("id", id);
+                log.into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_greatest_watermark() {
+        let checkpoint = checkpoint(PathBuf::new());
+        let mut greatest = None;
+        checkpoint.observe(
+            &mut greatest,
+            &events([Value::Integer(3), Value::Integer(7), Value::Integer(5)]),
+        );
+        checkpoint.observe(
+            &mut greatest,
+            &events([Value::from("ignored"), Value::Integer(6)]),
+        );
+        assert_eq!(greatest, Some(Watermark::Integer(7)));
+
+        let earlier = Utc.with_ymd_and_hms(2025, 3, 4, 15, 9, 25).unwrap();
+        let later = Utc.with_ymd_and_hms(2025, 3, 4, 15, 10, 0).unwrap();
+        let mut greatest = None;
+        checkpoint.observe(
+            &mut greatest,
+            &events([Value::from(later), Value::from(earlier)]),
+        );
+        assert_eq!(greatest, Some(Watermark::Timestamp(later)));
+        assert_eq!(
+            greatest.unwrap().to_param(),
+            "2025-03-04T15:10:00Z".to_owned()
+        );
+    }
+
+    #[tokio::test]
+    async fn persists_watermark() {
+        let path = temp_dir().join(CHECKPOINT_FILE_NAME);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let mut checkpoint = checkpoint(path.clone());
+        assert_eq!(
+            checkpoint.query(),
+            Some(("since_id".to_owned(), "0".to_owned()))
+        );
+
+        checkpoint.commit(Some(Watermark::Integer(42))).await;
+        // Older watermarks are not saved.
+        checkpoint.commit(Some(Watermark::Integer(12))).await;
+        assert_eq!(
+            checkpoint.query(),
+            Some(("since_id".to_owned(), "42".to_owned()))
+        );
+        assert_eq!(load(&path), Some(Watermark::Integer(42)));
+    }
+
+    #[test]
+    fn ignores_corrupted_checkpoint() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CHECKPOINT_FILE_NAME);
+        assert_eq!(load(&path), None);
+
+        std::fs::write(&path, b"{not json").unwrap();
+        assert_eq!(load(&path), None);
+    }
+}
@@ -8,22 +8,30 @@ use http::{Uri, response::Parts};
 use serde_with::serde_as;
 use snafu::ResultExt;
 use std::{collections::HashMap, time::Duration};
+use tokio::time::MissedTickBehavior;
 use tokio_util::codec::Decoder as _;
 use vrl::diagnostic::Formatter;
 
+use super::{
+    checkpoint::{Checkpoint, CheckpointConfig},
+    pagination::{PaginationConfig, Paginator, with_query},
+};
 use crate::http::{ParamType, ParameterValue, QueryParameterValue, QueryParameters};
-use crate::sources::util::http_client;
+use crate::sources::util::http_client::{self, HttpClientContext as _};
 use crate::{
+    SourceSender,
     codecs::{Decoder, DecodingConfig},
     config::{SourceConfig, SourceContext},
-    http::Auth,
+    http::{Auth, HttpClient},
+    internal_events::{HttpClientHttpError, HttpClientHttpResponseError, StreamClosedError},
     serde::{default_decoding, default_framing_message_based},
     sources,
     sources::util::{
         http::HttpMethod,
         http_client::{
-            GenericHttpClientInputs, HttpClientBuilder, build_url, call, default_interval,
-            default_timeout, warn_if_interval_too_low,
+            GenericHttpClientInputs, HttpClientBuilder, build_request, build_url, call,
+            default_interval, default_timeout, emit_events_received, send_request,
+            warn_if_interval_too_low,
         },
     },
     tls::{TlsConfig, TlsSettings},
@@ -121,6 +129,14 @@ pub struct HttpClientConfig {
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub pagination: Option<PaginationConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub checkpoint: Option<CheckpointConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -186,6 +202,8 @@ impl Default for HttpClientConfig {
             method: default_http_method(),
             tls: None,
             auth: None,
+            pagination: None,
+            checkpoint: None,
             log_namespace: None,
         }
     }
@@ -330,6 +348,17 @@ impl SourceConfig for HttpClientConfig {
             query,
         };
 
+        let paginator = self
+            .pagination
+            .as_ref()
+            .map(PaginationConfig::build)
+            .transpose()?;
+        let checkpoint = self
+            .checkpoint
+            .as_ref()
+            .map(|checkpoint| Checkpoint::from_config(checkpoint, &cx))
+            .transpose()?;
+
         warn_if_interval_too_low(self.timeout, self.interval);
 
         let inputs = GenericHttpClientInputs {
@@ -344,7 +373,20 @@ impl SourceConfig for HttpClientConfig {
             shutdown: cx.shutdown,
         };
 
-        Ok(call(inputs, context, cx.out, self.method).boxed())
+        if self.pagination.is_none() && self.checkpoint.is_none() {
+            return Ok(call(inputs, context, cx.out, self.method).boxed());
+        }
+
+        let scraper = PageScraper {
+            client: HttpClient::new(inputs.tls.clone(), &inputs.proxy)?,
+            inputs,
+            context,
+            paginator,
+            checkpoint,
+            http_method: self.method,
+        };
+
+        Ok(scraper.run(cx.out).boxed())
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
@@ -548,3 +590,135 @@ impl http_client::HttpClientContext for HttpClientContext {
         }
     }
 }
+
+/// Scrapes the endpoint at an interval, following the pages of each response and tracking the
+/// checkpoint of the source.
+///
+/// Unlike `call`, scrapes don't overlap, as each one starts from the checkpoint saved by the
+/// previous one.
+struct PageScraper {
+    client: HttpClient,
+    inputs: GenericHttpClientInputs,
+    context: HttpClientContext,
+    paginator: Option<Paginator>,
+    checkpoint: Option<Checkpoint>,
+    http_method: HttpMethod,
+}
+
+impl PageScraper {
+    async fn run(mut self, mut out: SourceSender) -> Result<(), ()> {
+        let mut shutdown = self.inputs.shutdown.clone();
+        let mut interval = tokio::time::interval(self.inputs.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {}
+            }
+
+            for base_url in self.inputs.urls.clone() {
+                tokio::select! {
+                    _ = &mut shutdown => return Ok(()),
+                    result = self.scrape(&base_url, &mut out) => result?,
+                }
+            }
+        }
+
+        debug!("Finished sending.");
+        Ok(())
+    }
+
+    /// Requests the pages of a scrape one after the other, and saves the checkpoint once the last
+    /// page is reached and all the events are sent.
+    async fn scrape(&mut self, base_url: &Uri, out: &mut SourceSender) -> Result<(), ()> {
+        let endpoint = base_url.to_string();
+        let mut url = self
+            .context
+            .process_url(base_url)
+            .unwrap_or_else(|| base_url.clone());
+        if let Some(param) = self.checkpoint.as_ref().and_then(Checkpoint::query) {
+            url = with_query(&url, [param]);
+        }
+        if let Some(paginator) = &self.paginator {
+            url = paginator.first_url(&url);
+        }
+
+        let max_pages = self
+            .paginator
+            .as_ref()
+            .map_or(1, |paginator| paginator.max_pages);
+        let mut watermark = None;
+        let mut last_page_reached = false;
+        for _ in 0..max_pages {
+            let request = build_request(
+                &url,
+                self.http_method,
+                &self.inputs.headers,
+                &self.inputs.content_type,
+                self.inputs.auth.as_ref(),
+            );
+            let (header, body) =
+                match send_request(&self.client, request, self.inputs.timeout, &endpoint).await {
+                    Ok((header, body)) if header.status == hyper::StatusCode::OK => (header, body),
+                    Ok((header, _)) => {
+                        emit!(HttpClientHttpResponseError {
+                            code: header.status,
+                            url: url.to_string(),
+                        });
+                        return Ok(());
+                    }
+                    Err(error) => {
+                        emit!(HttpClientHttpError {
+                            error,
+                            url: url.to_string()
+                        });
+                        return Ok(());
+                    }
+                };
+
+            let mut events = self
+                .context
+                .on_response(&url, &header, &body)
+                .unwrap_or_default();
+            emit_events_received(&url, &events);
+            if let Some(checkpoint) = &self.checkpoint {
+                checkpoint.observe(&mut watermark, &events);
+            }
+            let count = events.len();
+
+            // We'll enrich after receiving the events so that the byte sizes are accurate.
+            self.context.enrich_events(&mut events);
+            if out.send_batch(events).await.is_err() {
+                emit!(StreamClosedError { count });
+                return Err(());
+            }
+
+            match self
+                .paginator
+                .as_ref()
+                .and_then(|paginator| paginator.next_url(&url, &header, &body, count))
+            {
+                Some(next_url) => url = next_url,
+                None => {
+                    last_page_reached = true;
+                    break;
+                }
+            }
+        }
+
+        if !last_page_reached {
+            warn!(
+                message = "Reached the maximum number of pages of a scrape before its last page.",
+                max_pages,
+                url = %url,
+                internal_log_rate_limit = true,
+            );
+            return Ok(());
+        }
+        if let Some(checkpoint) = &mut self.checkpoint {
+            checkpoint.commit(watermark).await;
+        }
+        Ok(())
+    }
+}
@@ -61,6 +61,8 @@ async fn invalid_endpoint() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -80,6 +82,8 @@ async fn collected_logs_bytes() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -105,6 +109,8 @@ async fn collected_logs_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -130,6 +136,8 @@ async fn collected_metrics_native_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -160,6 +168,8 @@ async fn collected_trace_native_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -185,6 +195,8 @@ async fn unauthorized_no_auth() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -207,6 +219,8 @@ async fn unauthorized_wrong_auth() {
             user: "white_rabbit".to_string(),
             password: "morpheus".to_string().into(),
         }),
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -229,6 +243,8 @@ async fn authorized() {
             user: "user".to_string(),
             password: "pass".to_string().into(),
         }),
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -251,6 +267,8 @@ async fn tls_invalid_ca() {
             ..Default::default()
         }),
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -273,6 +291,8 @@ async fn tls_valid() {
             ..Default::default()
         }),
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -293,6 +313,8 @@ async fn shutdown() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     };
 
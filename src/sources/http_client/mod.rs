@@ -1,5 +1,9 @@
 #[cfg(feature = "sources-http_client")]
+mod checkpoint;
+#[cfg(feature = "sources-http_client")]
 pub mod client;
+#[cfg(feature = "sources-http_client")]
+mod pagination;
 
 #[cfg(test)]
 mod tests;
@@ -7,4 +11,6 @@ mod tests;
 #[cfg(all(test, feature = "http-client-integration-tests"))]
 mod integration_tests;
 
+pub use checkpoint::CheckpointConfig;
 pub use client::HttpClientConfig;
+pub use pagination::{PaginationConfig, PaginationStrategy};
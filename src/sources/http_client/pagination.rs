@@ -0,0 +1,629 @@
+//! Pagination of the responses of the `http_client` source.
+
+use std::{collections::BTreeMap, num::NonZeroU64};
+
+use bytes::Bytes;
+use http::{Uri, response::Parts};
+use vector_lib::{
+    TimeZone, compile_vrl,
+    configurable::configurable_component,
+    event::{Event, LogEvent, VrlTarget},
+    lookup::lookup_v2::ConfigValuePath,
+};
+use vrl::{
+    compiler::{CompileConfig, Program, runtime::Runtime},
+    diagnostic::Formatter,
+    path::PathPrefix,
+    prelude::TypeState,
+    value::{KeyString, Value},
+};
+
+const fn default_max_pages() -> NonZeroU64 {
+    NonZeroU64::new(100).expect("static non-zero number")
+}
+
+/// Pagination of the responses.
+///
+/// When pagination is enabled, the pages of a scrape are requested one after the other, and a
+/// scrape is not started before the previous one is done.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct PaginationConfig {
+    #[serde(flatten)]
+    pub strategy: PaginationStrategy,
+
+    /// The maximum number of pages requested per scrape.
+    ///
+    /// The next scrape starts again from the first page, or from the checkpoint if one is
+    /// configured. The checkpoint is only saved when the last page is reached, so a scrape that
+    /// stops at this limit is started again from the previous checkpoint.
+    #[serde(default = "default_max_pages")]
+    pub max_pages: NonZeroU64,
+}
+
+/// How the next page of a response is requested.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "strategy", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(
+    docs::enum_tag_description = "How the next page of a response is requested."
+))]
+pub enum PaginationStrategy {
+    /// Requests the URL of the `next` relation of the `Link` header of responses, as described in
+    /// [RFC 8288][rfc8288].
+    ///
+    /// Relative URLs are resolved against the URL of the request. Pagination stops when a
+    /// response has no `next` link, or when the link is on another scheme, host, or port than the
+    /// request.
+    ///
+    /// [rfc8288]: https://www.rfc-editor.org/rfc/rfc8288
+    LinkHeader,
+
+    /// Sends the cursor found in the body of each response in a query parameter of the next
+    /// request.
+    ///
+    /// The body must be a JSON document. Pagination stops when the cursor is missing, null, or
+    /// empty.
+    Cursor {
+        /// The field of the response body holding the cursor of the next page.
+        #[configurable(metadata(docs::examples = "next_cursor"))]
+        #[configurable(metadata(docs::examples = "meta.next"))]
+        cursor_field: ConfigValuePath,
+
+        /// The query parameter the cursor is sent in.
+        #[configurable(metadata(docs::examples = "cursor"))]
+        cursor_param: String,
+    },
+
+    /// Increments an offset query parameter by the number of events received in each page.
+    ///
+    /// Pagination stops after a page with fewer events than `page_size`.
+    Offset {
+        /// The query parameter the offset is sent in. The first page is requested with an offset
+        /// of `0`.
+        #[configurable(metadata(docs::examples = "offset"))]
+        offset_param: String,
+
+        /// The query parameter the page size is sent in, if any.
+        #[configurable(metadata(docs::examples = "limit"))]
+        limit_param: Option<String>,
+
+        /// The number of events in a full page.
+        #[configurable(metadata(docs::examples = 100))]
+        page_size: NonZeroU64,
+    },
+
+    /// Computes the query parameters of the next request with a VRL program.
+    ///
+    /// The program runs on an object with the `status` code, the `headers` and the `body` of the
+    /// response, the `query` parameters of the request, and the number of `events` decoded from
+    /// the response. The body is parsed if it is a JSON document, and is a string otherwise.
+    ///
+    /// The program returns an object of query parameters which are set on the next request, in
+    /// addition to the parameters of the previous request, or `null` to stop pagination.
+    Vrl {
+        /// The VRL program.
+        #[configurable(metadata(
+            docs::examples = "if .body.has_more == true { {\"page\": to_int!(.query.page) + 1} } else { null }"
+        ))]
+        source: String,
+    },
+}
+
+impl PaginationConfig {
+    /// Builds the paginator of the source, compiling its VRL program if there is one.
+    pub(super) fn build(&self) -> crate::Result<Paginator> {
+        let strategy = match &self.strategy {
+            PaginationStrategy::LinkHeader => Strategy::LinkHeader,
+            PaginationStrategy::Cursor {
+                cursor_field,
+                cursor_param,
+            } => Strategy::Cursor {
+                field: cursor_field.clone(),
+                param: cursor_param.clone(),
+            },
+            PaginationStrategy::Offset {
+                offset_param,
+                limit_param,
+                page_size,
+            } => Strategy::Offset {
+                param: offset_param.clone(),
+                limit_param: limit_param.clone(),
+                page_size: page_size.get(),
+            },
+            PaginationStrategy::Vrl { source } => {
+                let functions = vrl::stdlib::all()
+                    .into_iter()
+                    .chain(vector_lib::enrichment::vrl_functions())
+                    .chain(vector_vrl_functions::all())
+                    .collect::<Vec<_>>();
+                let result = compile_vrl(
+                    source,
+                    &functions,
+                    &TypeState::default(),
+                    CompileConfig::default(),
+                )
+                .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+                if !result.warnings.is_empty() {
+                    let warnings = Formatter::new(source, result.warnings)
+                        .colored()
+                        .to_string();
+                    warn!(message = "VRL compilation warnings.", %warnings);
+                }
+                Strategy::Vrl {
+                    program: Box::new(result.program),
+                }
+            }
+        };
+
+        Ok(Paginator {
+            strategy,
+            max_pages: self.max_pages.get(),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Strategy {
+    LinkHeader,
+    Cursor {
+        field: ConfigValuePath,
+        param: String,
+    },
+    Offset {
+        param: String,
+        limit_param: Option<String>,
+        page_size: u64,
+    },
+    Vrl {
+        program: Box<Program>,
+    },
+}
+
+/// Computes the URLs of the pages of a scrape.
+#[derive(Clone, Debug)]
+pub(super) struct Paginator {
+    strategy: Strategy,
+    pub(super) max_pages: u64,
+}
+
+impl Paginator {
+    /// Returns the URL of the first page of a scrape.
+    pub(super) fn first_url(&self, url: &Uri) -> Uri {
+        match &self.strategy {
+            Strategy::Offset {
+                param,
+                limit_param,
+                page_size,
+            } => {
+                let mut params = vec![(param.clone(), "0".to_owned())];
+                if let Some(limit_param) = limit_param {
+                    params.push((limit_param.clone(), page_size.to_string()));
+                }
+                with_query(url, params)
+            }
+            _ => url.clone(),
+        }
+    }
+
+    /// Returns the URL of the page following the response to a request to `url`, or `None` if
+    /// it was the last page.
+    pub(super) fn next_url(
+        &self,
+        url: &Uri,
+        header: &Parts,
+        body: &Bytes,
+        event_count: usize,
+    ) -> Option<Uri> {
+        match &self.strategy {
+            Strategy::LinkHeader => {
+                let link = next_link(header)?;
+                let base = url::Url::parse(&url.to_string()).ok()?;
+                let next = base.join(&link).ok()?;
+                // Following links to other servers would send them the headers and credentials
+                // of the source.
+                if next.scheme() != base.scheme()
+                    || next.host_str() != base.host_str()
+                    || next.port_or_known_default() != base.port_or_known_default()
+                {
+                    warn!(
+                        message = "Next link of the response is on another server, stopping pagination.",
+                        link = %next,
+                        internal_log_rate_limit = true,
+                    );
+                    return None;
+                }
+                next.as_str().parse().ok()
+            }
+            Strategy::Cursor { field, param } => {
+                let body = LogEvent::from(parse_body(body)?);
+                let cursor = match body.get((PathPrefix::Event, &field.0))? {
+                    Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+                    Value::Integer(integer) => integer.to_string(),
+                    _ => return None,
+                };
+                (!cursor.is_empty()).then(|| with_query(url, [(param.clone(), cursor)]))
+            }
+            Strategy::Offset {
+                param, page_size, ..
+            } => {
+                if (event_count as u64) < *page_size {
+                    return None;
+                }
+                let offset = query_params(url)
+                    .get(param)
+                    .and_then(|offset| offset.parse::<u64>().ok())
+                    .unwrap_or(0);
+                let offset = offset + event_count as u64;
+                Some(with_query(url, [(param.clone(), offset.to_string())]))
+            }
+            Strategy::Vrl { program } => {
+                let params = resolve_next_query(program, url, header, body, event_count)?;
+                Some(with_query(url, params))
+            }
+        }
+    }
+}
+
+/// Runs the VRL program of the `vrl` strategy on a response.
+fn resolve_next_query(
+    program: &Program,
+    url: &Uri,
+    header: &Parts,
+    body: &Bytes,
+    event_count: usize,
+) -> Option<Vec<(String, String)>> {
+    let headers = header
+        .headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                KeyString::from(name.as_str()),
+                Value::from(String::from_utf8_lossy(value.as_bytes()).into_owned()),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+    let query = query_params(url)
+        .into_iter()
+        .map(|(name, value)| (KeyString::from(name), Value::from(value)))
+        .collect::<BTreeMap<_, _>>();
+    let body =
+        parse_body(body).unwrap_or_else(|| Value::from(String::from_utf8_lossy(body).into_owned()));
+
+    let input = BTreeMap::from([
+        (
+            "status".into(),
+            Value::from(i64::from(header.status.as_u16())),
+        ),
+        ("headers".into(), Value::Object(headers)),
+        ("body".into(), body),
+        ("query".into(), Value::Object(query)),
+        ("events".into(), Value::from(event_count as i64)),
+    ]);
+    let mut target = VrlTarget::new(
+        Event::Log(LogEvent::from(Value::Object(input))),
+        program.info(),
+        false,
+    );
+
+    match Runtime::default().resolve(&mut target, program, &TimeZone::default()) {
+        Ok(Value::Object(params)) => Some(
+            params
+                .into_iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                        value => value.to_string(),
+                    };
+                    (name.as_str().to_owned(), value)
+                })
+                .collect(),
+        ),
+        Ok(Value::Null) => None,
+        Ok(value) => {
+            warn!(
+                message = "Pagination VRL program returned neither an object nor null, stopping pagination.",
+                kind = %value.kind_str(),
+                internal_log_rate_limit = true,
+            );
+            None
+        }
+        Err(error) => {
+            warn!(message = "Pagination VRL runtime error, stopping pagination.", %error, internal_log_rate_limit = true);
+            None
+        }
+    }
+}
+
+fn parse_body(body: &Bytes) -> Option<Value> {
+    serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .map(Value::from)
+}
+
+/// Returns the target of the `next` link of the `Link` headers of a response.
+fn next_link(header: &Parts) -> Option<String> {
+    header
+        .headers
+        .get_all(http::header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(parse_links)
+        .find_map(|(target, params)| {
+            // Only the first `rel` parameter of a link is taken into account.
+            let (_, rel) = params.iter().find(|(name, _)| name == "rel")?;
+            rel.split_ascii_whitespace()
+                .any(|rel| rel.eq_ignore_ascii_case("next"))
+                .then(|| target.to_owned())
+        })
+}
+
+/// Parses the links of a `Link` header value into their targets and parameters, with the names of
+/// the parameters in lowercase.
+///
+/// The targets are delimited by `<` and `>`, as they can contain commas and semicolons, and the
+/// parameter values can be quoted strings. Parsing stops at the first malformed link.
+fn parse_links(value: &str) -> Vec<(&str, Vec<(String, String)>)> {
+    let mut links = Vec::new();
+    let mut rest = value;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        let Some((target, after)) = rest
+            .strip_prefix('<')
+            .and_then(|after| after.split_once('>'))
+        else {
+            break;
+        };
+        rest = after;
+
+        let mut params = Vec::new();
+        while let Some(after) = rest.trim_start().strip_prefix(';') {
+            let end = after.find(['=', ';', ',']).unwrap_or(after.len());
+            let name = after[..end].trim().to_ascii_lowercase();
+            rest = &after[end..];
+
+            let mut value = String::new();
+            if let Some(after) = rest.strip_prefix('=') {
+                let after = after.trim_start();
+                if let Some(quoted) = after.strip_prefix('"') {
+                    let mut end = quoted.len();
+                    let mut chars = quoted.char_indices();
+                    while let Some((index, c)) = chars.next() {
+                        match c {
+                            '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                            '"' => {
+                                end = index + 1;
+                                break;
+                            }
+                            c => value.push(c),
+                        }
+                    }
+                    rest = &quoted[end..];
+                } else {
+                    let end = after.find([';', ',']).unwrap_or(after.len());
+                    value = after[..end].trim().to_owned();
+                    rest = &after[end..];
+                }
+            }
+            params.push((name, value));
+        }
+        links.push((target, params));
+
+        if !rest.trim_start().starts_with(',') && !rest.trim().is_empty() {
+            break;
+        }
+    }
+    links
+}
+
+/// Returns the query parameters of a URL, keeping the last value of repeated parameters.
+fn query_params(url: &Uri) -> BTreeMap<String, String> {
+    url::form_urlencoded::parse(url.query().unwrap_or_default().as_bytes())
+        .into_owned()
+        .collect()
+}
+
+/// Returns `url` with the given query parameters, replacing any parameters of the same names.
+pub(super) fn with_query(url: &Uri, params: impl IntoIterator<Item = (String, String)>) -> Uri {
+    let params = params.into_iter().collect::<Vec<_>>();
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    serializer.extend_pairs(
+        url::form_urlencoded::parse(url.query().unwrap_or_default().as_bytes())
+            .filter(|(name, _)| !params.iter().any(|(param, _)| param == name)),
+    );
+    serializer.extend_pairs(&params);
+
+    let path_and_query = match serializer.finish() {
+        query if !query.is_empty() => format!("{}?{}", url.path(), query),
+        _ => url.path().to_string(),
+    };
+    let mut parts = url.clone().into_parts();
+    parts.path_and_query = Some(path_and_query.parse().expect("the query is encoded"));
+    Uri::from_parts(parts).expect("only the query of a valid URL is changed")
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Response;
+
+    use super::*;
+
+    fn parts(link: Option<&str>) -> Parts {
+        let mut response = Response::builder();
+        if let Some(link) = link {
+            response = response.header(http::header::LINK, link);
+        }
+        response.body(()).unwrap().into_parts().0
+    }
+
+    fn paginator(config: &str) -> Paginator {
+        toml::from_str::<PaginationConfig>(config)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn replaces_query_parameters() {
+        let url = "http://example.com/logs?cursor=a&limit=10".parse().unwrap();
+        assert_eq!(
+            with_query(&url, [("cursor".to_owned(), "b c".to_owned())]).to_string(),
+            "http://example.com/logs?limit=10&cursor=b+c"
+        );
+    }
+
+    #[test]
+    fn follows_link_headers() {
+        let paginator = paginator(r#"strategy = "link_header""#);
+        let url = "http://example.com/logs?page=1".parse().unwrap();
+        let link =
+            r#"<https://example.com/logs?page=1>; rel="prev", </logs?page=2>; rel="next last""#;
+
+        assert_eq!(
+            paginator
+                .next_url(&url, &parts(Some(link)), &Bytes::new(), 10)
+                .unwrap()
+                .to_string(),
+            "http://example.com/logs?page=2"
+        );
+        assert!(
+            paginator
+                .next_url(&url, &parts(None), &Bytes::new(), 10)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parses_links_with_commas() {
+        let paginator = paginator(r#"strategy = "link_header""#);
+        let url = "http://example.com/logs".parse().unwrap();
+        let link = r#"</logs?fields=a,b;c>; title="first, then; next"; rel=prev, </logs?fields=a,b&page=2>; REL="next""#;
+
+        assert_eq!(
+            paginator
+                .next_url(&url, &parts(Some(link)), &Bytes::new(), 10)
+                .unwrap()
+                .to_string(),
+            "http://example.com/logs?fields=a,b&page=2"
+        );
+    }
+
+    #[test]
+    fn ignores_links_to_other_servers() {
+        let paginator = paginator(r#"strategy = "link_header""#);
+        let url = "http://example.com/logs".parse().unwrap();
+
+        for link in [
+            r#"<http://example.org/logs?page=2>; rel="next""#,
+            r#"<https://example.com/logs?page=2>; rel="next""#,
+            r#"<http://example.com:8080/logs?page=2>; rel="next""#,
+            r#"<//example.org/logs?page=2>; rel="next""#,
+        ] {
+            assert!(
+                paginator
+                    .next_url(&url, &parts(Some(link)), &Bytes::new(), 10)
+                    .is_none(),
+                "{link}"
+            );
+        }
+        let link = r#"<http://example.com:80/logs?page=2>; rel="next""#;
+        assert!(
+            paginator
+                .next_url(&url, &parts(Some(link)), &Bytes::new(), 10)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn sends_cursors() {
+        let paginator = paginator(
+            r#"
+            strategy = "cursor"
+            cursor_field = "meta.next"
+            cursor_param = "after"
+        "#,
+        );
+        let url = "http://example.com/logs?after=a".parse().unwrap();
+
+        let body = Bytes::from_static(br#"{"data": [], "meta": {"next": "b"}}"#);
+        assert_eq!(
+            paginator
+                .next_url(&url, &parts(None), &body, 0)
+                .unwrap()
+                .to_string(),
+            "http://example.com/logs?after=b"
+        );
+        let body = Bytes::from_static(br#"{"data": [], "meta": {"next": null}}"#);
+        assert!(paginator.next_url(&url, &parts(None), &body, 0).is_none());
+    }
+
+    #[test]
+    fn increments_offsets() {
+        let paginator = paginator(
+            r#"
+            strategy = "offset"
+            offset_param = "offset"
+            limit_param = "limit"
+            page_size = 2
+        "#,
+        );
+        let url = paginator.first_url(&"http://example.com/logs".parse().unwrap());
+        assert_eq!(url.to_string(), "http://example.com/logs?offset=0&limit=2");
+
+        let url = paginator
+            .next_url(&url, &parts(None), &Bytes::new(), 2)
+            .unwrap();
+        assert_eq!(url.to_string(), "http://example.com/logs?limit=2&offset=2");
+        assert!(
+            paginator
+                .next_url(&url, &parts(None), &Bytes::new(), 1)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn resolves_next_query_with_vrl() {
+        let paginator = paginator(
+            r#"
+            strategy = "vrl"
+            source = '''
+                if .body.has_more == true && .status == 200 {
+                    { "page": to_int!(.query.page) + 1, "since": .headers."x-since" }
+                } else {
+                    null
+                }
+            '''
+        "#,
+        );
+        let url = "http://example.com/logs?page=1".parse().unwrap();
+        let mut header = parts(None);
+        header
+            .headers
+            .insert("x-since", "2025-01-01".parse().unwrap());
+
+        let body = Bytes::from_static(br#"{"has_more": true}"#);
+        assert_eq!(
+            paginator
+                .next_url(&url, &header, &body, 1)
+                .unwrap()
+                .to_string(),
+            "http://example.com/logs?page=2&since=2025-01-01"
+        );
+        let body = Bytes::from_static(br#"{"has_more": false}"#);
+        assert!(paginator.next_url(&url, &header, &body, 1).is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_vrl() {
+        let error = toml::from_str::<PaginationConfig>(
+            r#"
+            strategy = "vrl"
+            source = "{ .page + }"
+        "#,
+        )
+        .unwrap()
+        .build()
+        .unwrap_err();
+        assert!(error.to_string().contains("error"));
+    }
+}
@@ -1,5 +1,5 @@
 use http::Uri;
-use std::collections::HashMap;
+use std::{collections::HashMap, num::NonZeroU64};
 use tokio::time::Duration;
 use vector_lib::config::LogNamespace;
 use warp::{Filter, http::HeaderMap};
@@ -14,10 +14,10 @@ use vector_lib::codecs::decoding::{
 };
 use vector_lib::event::Event;
 
-use super::HttpClientConfig;
+use super::{CheckpointConfig, HttpClientConfig, PaginationConfig, PaginationStrategy};
 use crate::test_util::{
     components::{HTTP_PULL_SOURCE_TAGS, run_and_assert_source_compliance},
-    next_addr, test_generate_config, wait_for_tcp,
+    next_addr, temp_dir, test_generate_config, wait_for_tcp,
 };
 
 pub(crate) const INTERVAL: Duration = Duration::from_secs(1);
@@ -96,6 +96,8 @@ async fn bytes_decoding() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -125,6 +127,8 @@ async fn json_decoding_newline_delimited() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -159,6 +163,8 @@ async fn json_decoding_character_delimited() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -199,6 +205,8 @@ async fn request_query_applied() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -310,6 +318,8 @@ async fn request_query_vrl_applied() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -391,6 +401,8 @@ async fn request_query_vrl_dynamic_updates() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -458,6 +470,8 @@ async fn headers_applied() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
@@ -487,7 +501,151 @@ async fn accept_header_override() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        checkpoint: None,
         log_namespace: None,
     })
     .await;
 }
+
+/// Pages should be requested one after the other, following the cursors of the responses.
+#[tokio::test]
+async fn cursor_pagination() {
+    let in_addr = next_addr();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::<HashMap<String, String>>())
+        .map(
+            |query: HashMap<String, String>| match query.get("cursor").map(String::as_str) {
+                None => r#"{"page": 1, "next": "b"}"#,
+                Some("b") => r#"{"page": 2, "next": "c"}"#,
+                Some("c") => r#"{"page": 3, "next": null}"#,
+                Some(_) => r#"{"page": 0}"#,
+            },
+        );
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{in_addr}/endpoint"),
+        decoding: DeserializerConfig::Json(Default::default()),
+        pagination: Some(PaginationConfig {
+            strategy: PaginationStrategy::Cursor {
+                cursor_field: "next".into(),
+                cursor_param: "cursor".to_string(),
+            },
+            max_pages: NonZeroU64::new(10).unwrap(),
+        }),
+        ..Default::default()
+    })
+    .await;
+
+    let pages = events
+        .iter()
+        .map(|event| event.as_log().get("page").unwrap().as_integer().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(pages[..3], [1, 2, 3]);
+}
+
+/// The greatest watermark of each scrape should be sent in the next one, and saved in the data
+/// directory.
+#[tokio::test]
+async fn checkpoint_sent_in_query() {
+    let in_addr = next_addr();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::<HashMap<String, String>>())
+        .map(|query: HashMap<String, String>| {
+            let since_id = query["since_id"].parse::<i64>().unwrap();
+            format!(r#"{{"id": {}}}"#, since_id + 1)
+        });
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let data_dir = temp_dir();
+    std::fs::create_dir_all(&data_dir).unwrap();
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{in_addr}/endpoint"),
+        interval: Duration::from_millis(500),
+        decoding: DeserializerConfig::Json(Default::default()),
+        checkpoint: Some(CheckpointConfig {
+            field: "id".into(),
+            param: "since_id".to_string(),
+            initial: Some("0".to_string()),
+            data_dir: Some(data_dir.clone()),
+        }),
+        ..Default::default()
+    })
+    .await;
+
+    let ids = events
+        .iter()
+        .map(|event| event.as_log().get("id").unwrap().as_integer().unwrap())
+        .collect::<Vec<_>>();
+    assert!(ids.len() >= 2);
+    assert_eq!(ids, (1..=ids.len() as i64).collect::<Vec<_>>());
+
+    let checkpoint = std::fs::read_dir(&data_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path().join("checkpoint.json"))
+        .find(|path| path.exists())
+        .expect("checkpoint should be saved");
+    let checkpoint: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(checkpoint).unwrap()).unwrap();
+    // The last scrape may be interrupted by the shutdown before its checkpoint is saved.
+    let watermark = checkpoint["watermark"]["integer"].as_i64().unwrap();
+    assert!(ids.len() as i64 - watermark <= 1);
+}
+
+/// A scrape stopping at `max_pages` before its last page shouldn't save its checkpoint, so that
+/// the next scrape starts again from the previous one.
+#[tokio::test]
+async fn checkpoint_not_saved_before_last_page() {
+    let in_addr = next_addr();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::<HashMap<String, String>>())
+        .map(|query: HashMap<String, String>| {
+            let since_id = query["since_id"].parse::<i64>().unwrap();
+            format!(r#"{{"id": {}, "next": "more"}}"#, since_id + 1)
+        });
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let data_dir = temp_dir();
+    std::fs::create_dir_all(&data_dir).unwrap();
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{in_addr}/endpoint"),
+        interval: Duration::from_millis(500),
+        decoding: DeserializerConfig::Json(Default::default()),
+        pagination: Some(PaginationConfig {
+            strategy: PaginationStrategy::Cursor {
+                cursor_field: "next".into(),
+                cursor_param: "cursor".to_string(),
+            },
+            max_pages: NonZeroU64::new(2).unwrap(),
+        }),
+        checkpoint: Some(CheckpointConfig {
+            field: "id".into(),
+            param: "since_id".to_string(),
+            initial: Some("0".to_string()),
+            data_dir: Some(data_dir.clone()),
+        }),
+        ..Default::default()
+    })
+    .await;
+
+    assert!(!events.is_empty());
+    assert!(
+        events
+            .iter()
+            .all(|event| event.as_log().get("id").unwrap().as_integer() == Some(1))
+    );
+    let saved = std::fs::read_dir(&data_dir)
+        .unwrap()
+        .any(|entry| entry.unwrap().path().join("checkpoint.json").exists());
+    assert!(!saved, "checkpoint shouldn't be saved");
+}
@@ -4,6 +4,7 @@ use std::{
     pin::Pin,
     sync::{
         Arc, OnceLock, Weak,
+        atomic::{AtomicBool, Ordering},
         mpsc::{SyncSender, sync_channel},
     },
     time::Duration,
@@ -15,7 +16,7 @@ use chrono::{DateTime, TimeZone, Utc};
 use futures::{Stream, StreamExt};
 use futures_util::future::OptionFuture;
 use rdkafka::{
-    ClientConfig, ClientContext, Statistics, TopicPartitionList,
+    ClientConfig, ClientContext, Offset, Statistics, TopicPartitionList,
     consumer::{
         BaseConsumer, CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer,
         stream_consumer::StreamPartitionQueue,
@@ -63,7 +64,7 @@ use crate::{
         KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
         StreamClosedError,
     },
-    kafka,
+    kafka::{self, KafkaSourceOffset},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
 };
@@ -594,6 +595,8 @@ impl ConsumerStateInner<Consuming> {
         let decoder = self.decoder.clone();
         let log_namespace = self.log_namespace;
         let mut out = self.out.clone();
+        let group_id: Arc<str> = self.config.group_id.as_str().into();
+        let transactional = Arc::new(AtomicBool::new(false));
 
         let (end_tx, mut end_signal) = oneshot::channel::<()>();
 
@@ -608,6 +611,12 @@ impl ConsumerStateInner<Consuming> {
 
             let mut status = PartitionConsumerStatus::NormalExit;
 
+            // After the partition is rewound, the acknowledgements of the messages consumed
+            // before are ignored, as these messages are consumed again. They end when the offset
+            // of the acknowledged messages goes back.
+            let mut rewound = false;
+            let mut last_acknowledged = None;
+
             loop {
                 tokio::select!(
                     // Make sure to handle the acknowledgement stream before new messages to prevent
@@ -622,10 +631,24 @@ impl ConsumerStateInner<Consuming> {
 
                     ack = ack_stream.next() => match ack {
                         Some((status, entry)) => {
-                            if status == BatchStatus::Delivered
-                                && let Err(error) =  consumer.store_offset(&entry.topic, entry.partition, entry.offset) {
+                            rewound &= last_acknowledged.is_some_and(|last| entry.offset > last);
+                            last_acknowledged = Some(entry.offset);
+                            if rewound {
+                                // The message is consumed again, and acknowledged later.
+                            } else if status == BatchStatus::Delivered {
+                                if let Err(error) = consumer.store_offset(&entry.topic, entry.partition, entry.offset) {
                                     emit!(KafkaOffsetUpdateError { error });
                                 }
+                            } else if transactional.load(Ordering::Relaxed) {
+                                // The transaction holding the message was aborted, so the
+                                // messages are consumed again from this one. A zero timeout
+                                // makes the seek asynchronous.
+                                debug!("Rewinding partition {}:{} to offset {}.", &entry.topic, entry.partition, entry.offset);
+                                match consumer.seek(&entry.topic, entry.partition, Offset::Offset(entry.offset), Duration::ZERO) {
+                                    Ok(()) => rewound = true,
+                                    Err(error) => emit!(KafkaOffsetUpdateError { error }),
+                                }
+                            }
                         }
                         None if finalizer.is_none() => {
                             debug!("Acknowledgement stream complete for partition {}:{}.", &tp.0, tp.1);
@@ -653,7 +676,20 @@ impl ConsumerStateInner<Consuming> {
                                 topic: msg.topic(),
                                 partition: msg.partition(),
                             });
-                            parse_message(msg, decoder.clone(), &keys, &mut out, acknowledgements, &finalizer, log_namespace).await;
+                            // The position of the message is handed over to transactional sinks,
+                            // which can only commit it once acknowledged.
+                            let offset = acknowledgements
+                                .then(|| consumer.group_metadata())
+                                .flatten()
+                                .map(|group_metadata| KafkaSourceOffset {
+                                    group_id: Arc::clone(&group_id),
+                                    group_metadata,
+                                    topic: msg.topic().into(),
+                                    partition: msg.partition(),
+                                    offset: msg.offset(),
+                                    transactional: Arc::clone(&transactional),
+                                });
+                            parse_message(msg, decoder.clone(), &keys, &mut out, acknowledgements, offset, &finalizer, log_namespace).await;
                         }
                     },
                 )
@@ -956,11 +992,15 @@ async fn parse_message(
     keys: &'_ Keys,
     out: &mut SourceSender,
     acknowledgements: bool,
+    offset: Option<KafkaSourceOffset>,
     finalizer: &Option<OrderedFinalizer<FinalizerEntry>>,
     log_namespace: LogNamespace,
 ) {
     if let Some((count, stream)) = parse_stream(&msg, decoder, keys, log_namespace) {
-        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let (batch, receiver) = match offset {
+            Some(offset) => BatchNotifier::new_with_receiver_and_context(Arc::new(offset)),
+            None => BatchNotifier::new_with_receiver(),
+        };
         let mut stream = stream.map(|event| {
             // All acknowledgements flow through the normal Finalizer stream so
             // that they can be handled in one place, but are only tied to the
@@ -0,0 +1,129 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use snafu::Snafu;
+use vrl::value::ObjectMap;
+
+use super::{ipfix, netflow_v5, netflow_v9, sflow, templates::TemplateCache};
+
+/// An error that occurred while decoding a flow export packet.
+#[derive(Debug, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("packet truncated while reading {}", what))]
+    Truncated { what: &'static str },
+
+    #[snafu(display("unsupported flow export version {}", version))]
+    UnsupportedVersion { version: u32 },
+
+    #[snafu(display("invalid {}: {}", what, reason))]
+    Invalid {
+        what: &'static str,
+        reason: &'static str,
+    },
+}
+
+pub type DecodeResult<T> = Result<T, DecodeError>;
+
+/// Reads big-endian values from a byte slice, failing instead of panicking on truncated input.
+#[derive(Clone, Copy, Debug)]
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub const fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn bytes(&mut self, len: usize, what: &'static str) -> DecodeResult<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(DecodeError::Truncated { what });
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    /// Splits off the next `len` bytes into their own reader.
+    pub fn sub_reader(&mut self, len: usize, what: &'static str) -> DecodeResult<ByteReader<'a>> {
+        self.bytes(len, what).map(ByteReader::new)
+    }
+
+    pub fn skip(&mut self, len: usize, what: &'static str) -> DecodeResult<()> {
+        self.bytes(len, what).map(|_| ())
+    }
+
+    pub fn u8(&mut self, what: &'static str) -> DecodeResult<u8> {
+        Ok(self.bytes(1, what)?[0])
+    }
+
+    pub fn u16(&mut self, what: &'static str) -> DecodeResult<u16> {
+        let bytes = self.bytes(2, what)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn u32(&mut self, what: &'static str) -> DecodeResult<u32> {
+        let bytes = self.bytes(4, what)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn u64(&mut self, what: &'static str) -> DecodeResult<u64> {
+        let bytes = self.bytes(8, what)?;
+        let mut buf = [0; 8];
+        buf.copy_from_slice(bytes);
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    pub fn ipv4(&mut self, what: &'static str) -> DecodeResult<Ipv4Addr> {
+        self.u32(what).map(Ipv4Addr::from)
+    }
+
+    pub fn ipv6(&mut self, what: &'static str) -> DecodeResult<Ipv6Addr> {
+        let bytes = self.bytes(16, what)?;
+        let mut buf = [0; 16];
+        buf.copy_from_slice(bytes);
+        Ok(Ipv6Addr::from(buf))
+    }
+}
+
+/// Decodes flow export packets into one object per flow record.
+///
+/// The decoder holds the templates announced by each exporter, which NetFlow v9 and IPFIX need to
+/// decode data records, so a single decoder should be used for all packets received by a source.
+#[derive(Debug)]
+pub struct FlowDecoder {
+    templates: TemplateCache,
+}
+
+impl FlowDecoder {
+    pub const fn new(templates: TemplateCache) -> Self {
+        Self { templates }
+    }
+
+    pub fn decode(&mut self, exporter: IpAddr, packet: &[u8]) -> DecodeResult<Vec<ObjectMap>> {
+        // NetFlow and IPFIX start with a 16-bit version, while sFlow starts with a 32-bit version,
+        // so a leading zero can only be sFlow.
+        let mut reader = ByteReader::new(packet);
+        match reader.u16("version")? {
+            netflow_v5::VERSION => netflow_v5::decode(reader),
+            netflow_v9::VERSION => netflow_v9::decode(reader, exporter, &mut self.templates),
+            ipfix::VERSION => ipfix::decode(reader, exporter, &mut self.templates),
+            0 => match reader.u16("version")? {
+                sflow::VERSION => sflow::decode(reader),
+                version => Err(DecodeError::UnsupportedVersion {
+                    version: u32::from(version),
+                }),
+            },
+            version => Err(DecodeError::UnsupportedVersion {
+                version: u32::from(version),
+            }),
+        }
+    }
+}
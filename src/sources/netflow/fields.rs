@@ -0,0 +1,245 @@
+use std::{
+    fmt::Write as _,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use vrl::value::Value;
+
+/// How the value of a field is represented on the wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    Unsigned,
+    Ipv4,
+    Ipv6,
+    Mac,
+    String,
+}
+
+/// Known NetFlow v9 field types and IPFIX information elements.
+///
+/// NetFlow v9 field types share their numbering with the IPFIX information elements defined before
+/// IPFIX itself. Those use the lowercased NetFlow v9 names, while IPFIX-only information elements
+/// use their IANA names in snake case.
+const FIELDS: &[(u16, &str, FieldKind)] = &[
+    (1, "in_bytes", FieldKind::Unsigned),
+    (2, "in_pkts", FieldKind::Unsigned),
+    (3, "flows", FieldKind::Unsigned),
+    (4, "protocol", FieldKind::Unsigned),
+    (5, "src_tos", FieldKind::Unsigned),
+    (6, "tcp_flags", FieldKind::Unsigned),
+    (7, "l4_src_port", FieldKind::Unsigned),
+    (8, "ipv4_src_addr", FieldKind::Ipv4),
+    (9, "src_mask", FieldKind::Unsigned),
+    (10, "input_snmp", FieldKind::Unsigned),
+    (11, "l4_dst_port", FieldKind::Unsigned),
+    (12, "ipv4_dst_addr", FieldKind::Ipv4),
+    (13, "dst_mask", FieldKind::Unsigned),
+    (14, "output_snmp", FieldKind::Unsigned),
+    (15, "ipv4_next_hop", FieldKind::Ipv4),
+    (16, "src_as", FieldKind::Unsigned),
+    (17, "dst_as", FieldKind::Unsigned),
+    (18, "bgp_ipv4_next_hop", FieldKind::Ipv4),
+    (19, "mul_dst_pkts", FieldKind::Unsigned),
+    (20, "mul_dst_bytes", FieldKind::Unsigned),
+    (21, "last_switched", FieldKind::Unsigned),
+    (22, "first_switched", FieldKind::Unsigned),
+    (23, "out_bytes", FieldKind::Unsigned),
+    (24, "out_pkts", FieldKind::Unsigned),
+    (25, "min_pkt_lngth", FieldKind::Unsigned),
+    (26, "max_pkt_lngth", FieldKind::Unsigned),
+    (27, "ipv6_src_addr", FieldKind::Ipv6),
+    (28, "ipv6_dst_addr", FieldKind::Ipv6),
+    (29, "ipv6_src_mask", FieldKind::Unsigned),
+    (30, "ipv6_dst_mask", FieldKind::Unsigned),
+    (31, "ipv6_flow_label", FieldKind::Unsigned),
+    (32, "icmp_type", FieldKind::Unsigned),
+    (33, "mul_igmp_type", FieldKind::Unsigned),
+    (34, "sampling_interval", FieldKind::Unsigned),
+    (35, "sampling_algorithm", FieldKind::Unsigned),
+    (36, "flow_active_timeout", FieldKind::Unsigned),
+    (37, "flow_inactive_timeout", FieldKind::Unsigned),
+    (38, "engine_type", FieldKind::Unsigned),
+    (39, "engine_id", FieldKind::Unsigned),
+    (40, "total_bytes_exp", FieldKind::Unsigned),
+    (41, "total_pkts_exp", FieldKind::Unsigned),
+    (42, "total_flows_exp", FieldKind::Unsigned),
+    (46, "mpls_top_label_type", FieldKind::Unsigned),
+    (47, "mpls_top_label_ip_addr", FieldKind::Ipv4),
+    (48, "flow_sampler_id", FieldKind::Unsigned),
+    (49, "flow_sampler_mode", FieldKind::Unsigned),
+    (50, "flow_sampler_random_interval", FieldKind::Unsigned),
+    (52, "min_ttl", FieldKind::Unsigned),
+    (53, "max_ttl", FieldKind::Unsigned),
+    (54, "ipv4_ident", FieldKind::Unsigned),
+    (55, "dst_tos", FieldKind::Unsigned),
+    (56, "in_src_mac", FieldKind::Mac),
+    (57, "out_dst_mac", FieldKind::Mac),
+    (58, "src_vlan", FieldKind::Unsigned),
+    (59, "dst_vlan", FieldKind::Unsigned),
+    (60, "ip_protocol_version", FieldKind::Unsigned),
+    (61, "direction", FieldKind::Unsigned),
+    (62, "ipv6_next_hop", FieldKind::Ipv6),
+    (63, "bgp_ipv6_next_hop", FieldKind::Ipv6),
+    (64, "ipv6_option_headers", FieldKind::Unsigned),
+    (70, "mpls_label_1", FieldKind::Unsigned),
+    (71, "mpls_label_2", FieldKind::Unsigned),
+    (72, "mpls_label_3", FieldKind::Unsigned),
+    (73, "mpls_label_4", FieldKind::Unsigned),
+    (74, "mpls_label_5", FieldKind::Unsigned),
+    (75, "mpls_label_6", FieldKind::Unsigned),
+    (76, "mpls_label_7", FieldKind::Unsigned),
+    (77, "mpls_label_8", FieldKind::Unsigned),
+    (78, "mpls_label_9", FieldKind::Unsigned),
+    (79, "mpls_label_10", FieldKind::Unsigned),
+    (80, "in_dst_mac", FieldKind::Mac),
+    (81, "out_src_mac", FieldKind::Mac),
+    (82, "if_name", FieldKind::String),
+    (83, "if_desc", FieldKind::String),
+    (84, "sampler_name", FieldKind::String),
+    (85, "in_permanent_bytes", FieldKind::Unsigned),
+    (86, "in_permanent_pkts", FieldKind::Unsigned),
+    (88, "fragment_offset", FieldKind::Unsigned),
+    (89, "forwarding_status", FieldKind::Unsigned),
+    (92, "src_traffic_index", FieldKind::Unsigned),
+    (93, "dst_traffic_index", FieldKind::Unsigned),
+    (94, "application_description", FieldKind::String),
+    (96, "application_name", FieldKind::String),
+    (130, "exporter_ipv4_address", FieldKind::Ipv4),
+    (131, "exporter_ipv6_address", FieldKind::Ipv6),
+    (136, "flow_end_reason", FieldKind::Unsigned),
+    (148, "flow_id", FieldKind::Unsigned),
+    (149, "observation_domain_id", FieldKind::Unsigned),
+    (150, "flow_start_seconds", FieldKind::Unsigned),
+    (151, "flow_end_seconds", FieldKind::Unsigned),
+    (152, "flow_start_milliseconds", FieldKind::Unsigned),
+    (153, "flow_end_milliseconds", FieldKind::Unsigned),
+    (160, "system_init_time_milliseconds", FieldKind::Unsigned),
+    (176, "icmp_type_ipv4", FieldKind::Unsigned),
+    (177, "icmp_code_ipv4", FieldKind::Unsigned),
+    (178, "icmp_type_ipv6", FieldKind::Unsigned),
+    (179, "icmp_code_ipv6", FieldKind::Unsigned),
+    (180, "udp_source_port", FieldKind::Unsigned),
+    (181, "udp_destination_port", FieldKind::Unsigned),
+    (182, "tcp_source_port", FieldKind::Unsigned),
+    (183, "tcp_destination_port", FieldKind::Unsigned),
+    (225, "post_nat_source_ipv4_address", FieldKind::Ipv4),
+    (226, "post_nat_destination_ipv4_address", FieldKind::Ipv4),
+    (227, "post_napt_source_transport_port", FieldKind::Unsigned),
+    (
+        228,
+        "post_napt_destination_transport_port",
+        FieldKind::Unsigned,
+    ),
+    (234, "ingress_vrfid", FieldKind::Unsigned),
+    (235, "egress_vrfid", FieldKind::Unsigned),
+    (236, "vrf_name", FieldKind::String),
+];
+
+/// Gets the name and kind of a field, given its type and enterprise number.
+///
+/// Unknown fields are named after their type, and decoded as unsigned integers when they fit in
+/// one, or as hex-encoded strings otherwise.
+pub fn field_info(field_type: u16, enterprise: Option<u32>) -> (String, Option<FieldKind>) {
+    if let Some(enterprise) = enterprise {
+        return (format!("enterprise_{enterprise}_{field_type}"), None);
+    }
+
+    match FIELDS.binary_search_by_key(&field_type, |(id, _, _)| *id) {
+        Ok(idx) => (FIELDS[idx].1.to_string(), Some(FIELDS[idx].2)),
+        Err(_) => (format!("field_{field_type}"), None),
+    }
+}
+
+/// Decodes the raw bytes of a field into a value.
+pub fn decode_field(kind: Option<FieldKind>, data: &[u8]) -> Value {
+    match (kind, data.len()) {
+        (Some(FieldKind::Ipv4), 4) => {
+            Value::from(Ipv4Addr::new(data[0], data[1], data[2], data[3]).to_string())
+        }
+        (Some(FieldKind::Ipv6), 16) => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(data);
+            Value::from(Ipv6Addr::from(octets).to_string())
+        }
+        (Some(FieldKind::Mac), 6) => Value::from(format_mac(data)),
+        (Some(FieldKind::String), _) => {
+            let data = match data.iter().position(|b| *b == 0) {
+                Some(end) => &data[..end],
+                None => data,
+            };
+            Value::from(String::from_utf8_lossy(data).into_owned())
+        }
+        // Unsigned integers may use reduced-size encoding, so any length up to 8 bytes is valid.
+        (Some(FieldKind::Unsigned) | None, 1..=8) => unsigned_value(
+            data.iter()
+                .fold(0_u64, |value, byte| (value << 8) | u64::from(*byte)),
+        ),
+        _ => Value::from(data.iter().fold(String::new(), |mut hex, byte| {
+            _ = write!(hex, "{byte:02x}");
+            hex
+        })),
+    }
+}
+
+pub fn int_value(value: impl Into<i64>) -> Value {
+    Value::Integer(value.into())
+}
+
+/// Converts an unsigned integer to a value, falling back to a float for values beyond `i64::MAX`.
+pub fn unsigned_value(value: u64) -> Value {
+    i64::try_from(value)
+        .map(Value::from)
+        .unwrap_or_else(|_| Value::from_f64_or_zero(value as f64))
+}
+
+pub fn format_mac(data: &[u8]) -> String {
+    let mut mac = String::with_capacity(data.len() * 3);
+    for (idx, byte) in data.iter().enumerate() {
+        if idx > 0 {
+            mac.push(':');
+        }
+        _ = write!(mac, "{byte:02x}");
+    }
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_are_sorted() {
+        assert!(FIELDS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn decodes_known_and_unknown_fields() {
+        assert_eq!(
+            field_info(8, None),
+            ("ipv4_src_addr".to_string(), Some(FieldKind::Ipv4))
+        );
+        assert_eq!(field_info(999, None), ("field_999".to_string(), None));
+        assert_eq!(field_info(1, Some(9)), ("enterprise_9_1".to_string(), None));
+
+        assert_eq!(
+            decode_field(Some(FieldKind::Ipv4), &[10, 0, 0, 1]),
+            Value::from("10.0.0.1")
+        );
+        assert_eq!(
+            decode_field(Some(FieldKind::Mac), &[0, 0x1b, 0x2c, 0x3d, 0x4e, 0x5f]),
+            Value::from("00:1b:2c:3d:4e:5f")
+        );
+        assert_eq!(
+            decode_field(Some(FieldKind::String), b"eth0\0\0"),
+            Value::from("eth0")
+        );
+        assert_eq!(
+            decode_field(Some(FieldKind::Unsigned), &[1, 0]),
+            Value::from(256)
+        );
+        assert_eq!(
+            decode_field(None, &[0; 10]),
+            Value::from("00000000000000000000")
+        );
+    }
+}
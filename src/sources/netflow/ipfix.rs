@@ -0,0 +1,288 @@
+use std::net::IpAddr;
+
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    decoder::{ByteReader, DecodeError, DecodeResult},
+    fields::int_value,
+    templates::{Template, TemplateCache, TemplateField, TemplateKey},
+};
+use crate::internal_events::NetflowTemplateMiss;
+
+pub const VERSION: u16 = 10;
+
+const HEADER_LENGTH: usize = 16;
+const TEMPLATE_SET_ID: u16 = 2;
+const OPTIONS_TEMPLATE_SET_ID: u16 = 3;
+const MIN_DATA_SET_ID: u16 = 256;
+
+const ENTERPRISE_BIT: u16 = 0x8000;
+
+/// Decodes an IPFIX message, following its version number.
+///
+/// Templates are added to and withdrawn from the cache as they are announced, and data records
+/// whose template is not known yet are dropped.
+pub fn decode(
+    mut packet: ByteReader<'_>,
+    exporter: IpAddr,
+    templates: &mut TemplateCache,
+) -> DecodeResult<Vec<ObjectMap>> {
+    let length = packet.u16("header")?;
+    let export_time = packet.u32("header")?;
+    let sequence = packet.u32("header")?;
+    let domain_id = packet.u32("header")?;
+
+    // The message length covers the header, and anything beyond it is not part of the message.
+    let body_length =
+        usize::from(length)
+            .checked_sub(HEADER_LENGTH)
+            .ok_or(DecodeError::Invalid {
+                what: "message",
+                reason: "length is shorter than its header",
+            })?;
+    let mut packet = packet.sub_reader(body_length, "message")?;
+
+    let header: ObjectMap = [
+        ("version", int_value(VERSION)),
+        ("length", int_value(length)),
+        ("export_time", int_value(export_time)),
+        ("sequence", int_value(sequence)),
+        ("observation_domain_id", int_value(domain_id)),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.into(), value))
+    .collect();
+    let header = Value::Object(header);
+
+    let key = |template_id| TemplateKey {
+        exporter,
+        version: VERSION,
+        domain_id,
+        template_id,
+    };
+
+    let mut records = Vec::new();
+    while !packet.is_empty() {
+        let set_id = packet.u16("set header")?;
+        let length = usize::from(packet.u16("set header")?);
+        let body_length = length.checked_sub(4).ok_or(DecodeError::Invalid {
+            what: "set",
+            reason: "length is shorter than its header",
+        })?;
+        let mut set = packet.sub_reader(body_length, "set")?;
+
+        match set_id {
+            TEMPLATE_SET_ID | OPTIONS_TEMPLATE_SET_ID => {
+                let is_options = set_id == OPTIONS_TEMPLATE_SET_ID;
+                // Template sets may be padded, and a template record has a 4 byte header.
+                while set.remaining() >= 4 {
+                    let template_id = set.u16("template")?;
+                    let field_count = set.u16("template")?;
+
+                    if field_count == 0 {
+                        // A withdrawal using the ID of its set withdraws every template of the
+                        // set's type (RFC 7011, section 8.1).
+                        if template_id == set_id {
+                            templates.remove_all(exporter, VERSION, domain_id, is_options);
+                        } else {
+                            templates.remove(&key(template_id));
+                        }
+                        continue;
+                    }
+
+                    let scope_field_count = if is_options {
+                        set.u16("options template")?
+                    } else {
+                        0
+                    };
+                    if scope_field_count > field_count {
+                        return Err(DecodeError::Invalid {
+                            what: "options template",
+                            reason: "scope field count exceeds field count",
+                        });
+                    }
+
+                    let scope_fields = read_fields(&mut set, scope_field_count)?;
+                    let fields = read_fields(&mut set, field_count - scope_field_count)?;
+                    templates.insert(key(template_id), Template::new(scope_fields, fields)?);
+                }
+            }
+            MIN_DATA_SET_ID.. => match templates.get(&key(set_id)) {
+                Some(template) => {
+                    records.extend(template.decode_records(set)?.into_iter().map(|mut record| {
+                        record.insert("flow_type".into(), Value::from("ipfix"));
+                        record.insert("header".into(), header.clone());
+                        record
+                    }));
+                }
+                None => emit!(NetflowTemplateMiss {
+                    protocol: "ipfix",
+                    template_id: set_id,
+                    exporter,
+                }),
+            },
+            // Set IDs 0, 1 and 4 to 255 are reserved.
+            _ => {}
+        }
+    }
+
+    Ok(records)
+}
+
+fn read_fields(set: &mut ByteReader<'_>, count: u16) -> DecodeResult<Vec<TemplateField>> {
+    (0..count)
+        .map(|_| {
+            let field_type = set.u16("template field")?;
+            let length = set.u16("template field")?;
+            let enterprise = if field_type & ENTERPRISE_BIT == 0 {
+                None
+            } else {
+                Some(set.u32("template field")?)
+            };
+            Ok(TemplateField::new(
+                field_type & !ENTERPRISE_BIT,
+                enterprise,
+                length,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+    use crate::sources::netflow::templates::VARIABLE_LENGTH;
+
+    const EXPORTER: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    fn message(sets: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let length: usize =
+            HEADER_LENGTH + sets.iter().map(|(_, body)| body.len() + 4).sum::<usize>();
+        let mut message = Vec::new();
+        message.extend((length as u16).to_be_bytes());
+        message.extend(1_700_000_000_u32.to_be_bytes());
+        message.extend(3_u32.to_be_bytes());
+        message.extend(42_u32.to_be_bytes());
+        for (id, body) in sets {
+            message.extend(id.to_be_bytes());
+            message.extend((body.len() as u16 + 4).to_be_bytes());
+            message.extend(body);
+        }
+        message
+    }
+
+    fn template() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(300_u16.to_be_bytes());
+        body.extend(3_u16.to_be_bytes());
+        // sourceIPv4Address
+        body.extend([0, 8, 0, 4]);
+        // A variable-length enterprise-specific field.
+        body.extend((ENTERPRISE_BIT | 5).to_be_bytes());
+        body.extend(VARIABLE_LENGTH.to_be_bytes());
+        body.extend(29305_u32.to_be_bytes());
+        // applicationName
+        body.extend([0, 96]);
+        body.extend(VARIABLE_LENGTH.to_be_bytes());
+        body
+    }
+
+    fn data() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend([192, 168, 1, 10]);
+        body.extend([2, 0xab, 0xcd]);
+        body.extend([255, 0, 5]);
+        body.extend(b"https");
+        body
+    }
+
+    #[test]
+    fn decodes_data_with_template() {
+        let mut templates = TemplateCache::default();
+        let message = message(&[(TEMPLATE_SET_ID, template()), (300, data())]);
+        let records = decode(ByteReader::new(&message), EXPORTER, &mut templates).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record["flow_type"], "ipfix".into());
+        assert_eq!(record["record_type"], "flow".into());
+        assert_eq!(record["ipv4_src_addr"], "192.168.1.10".into());
+        assert_eq!(record["enterprise_29305_5"], 0xabcd.into());
+        assert_eq!(record["application_name"], "https".into());
+
+        let Value::Object(header) = &record["header"] else {
+            panic!("header should be an object");
+        };
+        assert_eq!(header["observation_domain_id"], 42.into());
+    }
+
+    #[test]
+    fn withdraws_templates() {
+        let mut templates = TemplateCache::default();
+        let announce = message(&[(TEMPLATE_SET_ID, template())]);
+        decode(ByteReader::new(&announce), EXPORTER, &mut templates).unwrap();
+
+        let mut withdrawal = Vec::new();
+        withdrawal.extend(TEMPLATE_SET_ID.to_be_bytes());
+        withdrawal.extend(0_u16.to_be_bytes());
+        let withdraw = message(&[(TEMPLATE_SET_ID, withdrawal)]);
+        decode(ByteReader::new(&withdraw), EXPORTER, &mut templates).unwrap();
+
+        let data = message(&[(300, data())]);
+        let records = decode(ByteReader::new(&data), EXPORTER, &mut templates).unwrap();
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn withdraws_all_options_templates() {
+        let mut options_template = Vec::new();
+        options_template.extend(301_u16.to_be_bytes());
+        options_template.extend(2_u16.to_be_bytes());
+        options_template.extend(1_u16.to_be_bytes());
+        // sourceIPv4Address as the scope, and applicationName.
+        options_template.extend([0, 8, 0, 4]);
+        options_template.extend([0, 96]);
+        options_template.extend(VARIABLE_LENGTH.to_be_bytes());
+
+        let mut templates = TemplateCache::default();
+        let announce = message(&[
+            (TEMPLATE_SET_ID, template()),
+            (OPTIONS_TEMPLATE_SET_ID, options_template),
+        ]);
+        decode(ByteReader::new(&announce), EXPORTER, &mut templates).unwrap();
+
+        let mut withdrawal = Vec::new();
+        withdrawal.extend(OPTIONS_TEMPLATE_SET_ID.to_be_bytes());
+        withdrawal.extend(0_u16.to_be_bytes());
+        let withdraw = message(&[(OPTIONS_TEMPLATE_SET_ID, withdrawal)]);
+        decode(ByteReader::new(&withdraw), EXPORTER, &mut templates).unwrap();
+
+        let mut options_data = Vec::new();
+        options_data.extend([192, 168, 1, 10]);
+        options_data.push(5);
+        options_data.extend(b"https");
+        let data = message(&[(300, data()), (301, options_data)]);
+        let records = decode(ByteReader::new(&data), EXPORTER, &mut templates).unwrap();
+
+        // Only the options template was withdrawn.
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["record_type"], "flow".into());
+    }
+
+    #[test]
+    fn rejects_short_message_length() {
+        let mut message = message(&[]);
+        message[0..2].copy_from_slice(&8_u16.to_be_bytes());
+        let mut templates = TemplateCache::default();
+
+        assert!(matches!(
+            decode(ByteReader::new(&message), EXPORTER, &mut templates),
+            Err(DecodeError::Invalid {
+                what: "message",
+                ..
+            })
+        ));
+    }
+}
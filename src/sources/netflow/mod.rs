@@ -0,0 +1,391 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    num::NonZeroUsize,
+    time::Duration,
+};
+
+use bytes::BytesMut;
+use chrono::Utc;
+use listenfd::ListenFd;
+use serde_with::serde_as;
+use vector_lib::config::{LegacyKey, LogNamespace, log_schema};
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
+use vector_lib::{EstimatedJsonEncodedSizeOf, schema::Definition};
+use vrl::value::{Kind, kind::Collection};
+
+use crate::{
+    SourceSender,
+    config::{DataType, GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    event::{Event, LogEvent},
+    internal_events::{
+        EventsReceived, NetflowDecodeError, SocketBindError, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    net,
+    shutdown::ShutdownSignal,
+    sources::{
+        Source,
+        util::net::{SocketListenAddr, try_bind_udp_socket},
+    },
+};
+
+mod decoder;
+mod fields;
+mod ipfix;
+mod netflow_v5;
+mod netflow_v9;
+mod sflow;
+mod templates;
+
+use decoder::FlowDecoder;
+use templates::{TemplateCache, default_max_templates, default_template_ttl};
+
+/// Configuration for the `netflow` source.
+#[serde_as]
+#[configurable_component(source(
+    "netflow",
+    "Collect flow records exported by network devices over NetFlow, IPFIX and sFlow."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    /// The address to listen for flow export packets on.
+    ///
+    /// NetFlow v5 and v9, IPFIX and sFlow v5 packets are all accepted on the same address, and
+    /// told apart by their version number.
+    #[configurable(metadata(docs::examples = "0.0.0.0:2055"))]
+    #[configurable(metadata(docs::examples = "0.0.0.0:6343"))]
+    #[configurable(metadata(docs::examples = "systemd"))]
+    #[configurable(metadata(docs::examples = "systemd#3"))]
+    address: SocketListenAddr,
+
+    /// The maximum size of incoming packets.
+    ///
+    /// Packets larger than this are truncated, and fail to decode.
+    #[serde(default = "default_max_length")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    max_length: usize,
+
+    /// The size of the receive buffer used for the listening socket.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    /// Overrides the name of the log field used to add the exporter's address to each event.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// Set to `""` to suppress this key.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    host_key: Option<OptionalValuePath>,
+
+    /// Overrides the name of the log field used to add the exporter's port to each event.
+    ///
+    /// By default, `"port"` is used.
+    ///
+    /// Set to `""` to suppress this key.
+    #[serde(default = "default_port_key")]
+    port_key: OptionalValuePath,
+
+    /// The maximum number of templates cached across all exporters.
+    ///
+    /// NetFlow v9 and IPFIX data records can only be decoded once the template describing them
+    /// has been announced. When the cache is full, the least recently announced template is
+    /// evicted.
+    #[serde(default = "default_max_templates")]
+    max_templates: NonZeroUsize,
+
+    /// The time after which a template expires, unless the exporter announces it again.
+    ///
+    /// Exporters periodically announce their templates again, so this only drops the templates
+    /// of exporters that went away or stopped using them.
+    #[serde(default = "default_template_ttl")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Template TTL"))]
+    template_ttl_secs: Duration,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+const fn default_max_length() -> usize {
+    // The largest possible UDP payload, as flow export packets are never split.
+    65_535
+}
+
+fn default_host_key() -> OptionalValuePath {
+    log_schema().host_key().cloned().into()
+}
+
+fn default_port_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("port"))
+}
+
+impl NetflowConfig {
+    pub fn from_address(address: SocketListenAddr) -> Self {
+        Self {
+            address,
+            max_length: default_max_length(),
+            receive_buffer_bytes: None,
+            host_key: None,
+            port_key: default_port_key(),
+            max_templates: default_max_templates(),
+            template_ttl_secs: default_template_ttl(),
+            log_namespace: None,
+        }
+    }
+
+    fn host_key(&self) -> OptionalValuePath {
+        self.host_key.clone().unwrap_or_else(default_host_key)
+    }
+}
+
+impl GenerateConfig for NetflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::from_address(SocketListenAddr::SocketAddr(
+            SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 2055),
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "netflow")]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        Ok(netflow_udp(
+            self.clone(),
+            log_namespace,
+            cx.shutdown,
+            cx.out,
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let legacy_host_key = self.host_key().path.map(LegacyKey::InsertIfEmpty);
+        let legacy_port_key = self.port_key.clone().path.map(LegacyKey::InsertIfEmpty);
+
+        let schema_definition =
+            Definition::new_with_default_metadata(Kind::object(Collection::any()), [log_namespace])
+                .with_standard_vector_source_metadata()
+                .with_source_metadata(
+                    Self::NAME,
+                    legacy_host_key,
+                    &owned_value_path!("host"),
+                    Kind::bytes(),
+                    Some("host"),
+                )
+                .with_source_metadata(
+                    Self::NAME,
+                    legacy_port_key,
+                    &owned_value_path!("port"),
+                    Kind::integer(),
+                    None,
+                );
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+fn netflow_udp(
+    config: NetflowConfig,
+    log_namespace: LogNamespace,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Source {
+    Box::pin(async move {
+        let listenfd = ListenFd::from_env();
+        let socket = try_bind_udp_socket(config.address, listenfd)
+            .await
+            .map_err(|error| {
+                emit!(SocketBindError {
+                    mode: SocketMode::Udp,
+                    error,
+                })
+            })?;
+
+        if let Some(receive_buffer_bytes) = config.receive_buffer_bytes
+            && let Err(error) = net::set_receive_buffer_size(&socket, receive_buffer_bytes)
+        {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+
+        let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+        let events_received = register!(EventsReceived);
+        let legacy_host_key = config.host_key().path;
+        let legacy_port_key = config.port_key.clone().path;
+
+        // Templates are only announced periodically, so they have to outlive any single packet.
+        let mut decoder = FlowDecoder::new(TemplateCache::new(
+            config.max_templates,
+            config.template_ttl_secs,
+        ));
+
+        info!(message = "Listening.", address = %config.address);
+        let mut buf = BytesMut::with_capacity(config.max_length);
+        loop {
+            buf.resize(config.max_length, 0);
+            tokio::select! {
+                recv = socket.recv_from(&mut buf) => {
+                    let (byte_size, address) = recv.map_err(|error| {
+                        emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error,
+                        })
+                    })?;
+                    bytes_received.emit(ByteSize(byte_size));
+
+                    let records = match decoder.decode(address.ip(), &buf[..byte_size]) {
+                        Ok(records) => records,
+                        Err(error) => {
+                            emit!(NetflowDecodeError {
+                                error,
+                                exporter: address.ip(),
+                            });
+                            continue;
+                        }
+                    };
+                    if records.is_empty() {
+                        continue;
+                    }
+
+                    let now = Utc::now();
+                    let events = records
+                        .into_iter()
+                        .map(|record| {
+                            let mut log = LogEvent::from(record);
+                            log_namespace.insert_standard_vector_source_metadata(
+                                &mut log,
+                                NetflowConfig::NAME,
+                                now,
+                            );
+                            log_namespace.insert_source_metadata(
+                                NetflowConfig::NAME,
+                                &mut log,
+                                legacy_host_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                                path!("host"),
+                                address.ip().to_string(),
+                            );
+                            log_namespace.insert_source_metadata(
+                                NetflowConfig::NAME,
+                                &mut log,
+                                legacy_port_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                                path!("port"),
+                                address.port(),
+                            );
+                            Event::from(log)
+                        })
+                        .collect::<Vec<_>>();
+
+                    let count = events.len();
+                    events_received.emit(CountByteSize(
+                        count,
+                        events.estimated_json_encoded_size_of(),
+                    ));
+
+                    tokio::select! {
+                        result = out.send_batch(events) => {
+                            if result.is_err() {
+                                emit!(StreamClosedError { count });
+                                return Ok(());
+                            }
+                        }
+                        _ = &mut shutdown => return Ok(()),
+                    }
+                }
+                _ = &mut shutdown => return Ok(()),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        net::UdpSocket,
+        time::{Duration, sleep},
+    };
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{SOCKET_PUSH_SOURCE_TAGS, assert_source_compliance},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+
+    fn netflow_v5_packet() -> Vec<u8> {
+        let mut packet = Vec::new();
+        packet.extend(5_u16.to_be_bytes());
+        packet.extend(1_u16.to_be_bytes());
+        packet.extend([0; 20]);
+        packet.extend([192, 168, 0, 1]);
+        packet.extend([192, 168, 0, 2]);
+        packet.extend([0; 12]);
+        packet.extend(1234_u32.to_be_bytes());
+        packet.extend([0; 8]);
+        packet.extend(53_u16.to_be_bytes());
+        packet.extend(53_u16.to_be_bytes());
+        packet.extend([0, 0, 17, 0]);
+        packet.extend([0; 8]);
+        packet
+    }
+
+    #[tokio::test]
+    async fn receives_flow_records() {
+        assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let in_addr = next_addr();
+            let (tx, rx) = SourceSender::new_test();
+            let source = NetflowConfig::from_address(in_addr.into())
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind(next_addr()).await.unwrap();
+            // Packets which fail to decode are reported, and do not stop the source.
+            socket.send_to(&[0xff, 0xff], in_addr).await.unwrap();
+            socket.send_to(&netflow_v5_packet(), in_addr).await.unwrap();
+
+            let events = collect_n(rx, 1).await;
+            let log = events[0].as_log();
+            assert_eq!(log["flow_type"], "netflow_v5".into());
+            assert_eq!(log["ipv4_src_addr"], "192.168.0.1".into());
+            assert_eq!(log["in_bytes"], 1234.into());
+            assert_eq!(log["protocol"], 17.into());
+            assert_eq!(
+                log["port"],
+                i64::from(socket.local_addr().unwrap().port()).into()
+            );
+            assert_eq!(
+                *log.get_host().unwrap(),
+                socket.local_addr().unwrap().ip().to_string().into()
+            );
+        })
+        .await;
+    }
+}
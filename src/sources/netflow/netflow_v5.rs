@@ -0,0 +1,160 @@
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    decoder::{ByteReader, DecodeError, DecodeResult},
+    fields::int_value,
+};
+
+pub const VERSION: u16 = 5;
+
+const RECORD_LENGTH: usize = 48;
+
+/// Decodes a NetFlow v5 packet, following its version number.
+pub fn decode(mut packet: ByteReader<'_>) -> DecodeResult<Vec<ObjectMap>> {
+    let count = packet.u16("header")?;
+    let sys_uptime = packet.u32("header")?;
+    let unix_secs = packet.u32("header")?;
+    let unix_nsecs = packet.u32("header")?;
+    let flow_sequence = packet.u32("header")?;
+    let engine_type = packet.u8("header")?;
+    let engine_id = packet.u8("header")?;
+    let sampling = packet.u16("header")?;
+
+    if packet.remaining() < usize::from(count) * RECORD_LENGTH {
+        return Err(DecodeError::Truncated { what: "records" });
+    }
+
+    let header: ObjectMap = [
+        ("version", int_value(VERSION)),
+        ("count", int_value(count)),
+        ("sys_uptime", int_value(sys_uptime)),
+        ("unix_secs", int_value(unix_secs)),
+        ("unix_nsecs", int_value(unix_nsecs)),
+        ("flow_sequence", int_value(flow_sequence)),
+        ("engine_type", int_value(engine_type)),
+        ("engine_id", int_value(engine_id)),
+        ("sampling_mode", int_value(sampling >> 14)),
+        ("sampling_interval", int_value(sampling & 0x3fff)),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.into(), value))
+    .collect();
+    let header = Value::Object(header);
+
+    (0..count)
+        .map(|_| {
+            let mut record = decode_record(&mut packet)?;
+            record.insert("flow_type".into(), Value::from("netflow_v5"));
+            record.insert("header".into(), header.clone());
+            Ok(record)
+        })
+        .collect()
+}
+
+fn decode_record(packet: &mut ByteReader<'_>) -> DecodeResult<ObjectMap> {
+    let mut record = ObjectMap::new();
+    let mut insert = |name: &str, value: Value| {
+        record.insert(name.into(), value);
+    };
+
+    insert("ipv4_src_addr", packet.ipv4("record")?.to_string().into());
+    insert("ipv4_dst_addr", packet.ipv4("record")?.to_string().into());
+    insert("ipv4_next_hop", packet.ipv4("record")?.to_string().into());
+    insert("input_snmp", int_value(packet.u16("record")?));
+    insert("output_snmp", int_value(packet.u16("record")?));
+    insert("in_pkts", int_value(packet.u32("record")?));
+    insert("in_bytes", int_value(packet.u32("record")?));
+    insert("first_switched", int_value(packet.u32("record")?));
+    insert("last_switched", int_value(packet.u32("record")?));
+    insert("l4_src_port", int_value(packet.u16("record")?));
+    insert("l4_dst_port", int_value(packet.u16("record")?));
+    packet.skip(1, "record")?;
+    insert("tcp_flags", int_value(packet.u8("record")?));
+    insert("protocol", int_value(packet.u8("record")?));
+    insert("src_tos", int_value(packet.u8("record")?));
+    insert("src_as", int_value(packet.u16("record")?));
+    insert("dst_as", int_value(packet.u16("record")?));
+    insert("src_mask", int_value(packet.u8("record")?));
+    insert("dst_mask", int_value(packet.u8("record")?));
+    packet.skip(2, "record")?;
+    insert("record_type", "flow".into());
+
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn packet(records: u16) -> Vec<u8> {
+        let mut packet = Vec::new();
+        packet.extend(VERSION.to_be_bytes());
+        packet.extend(records.to_be_bytes());
+        packet.extend(1000_u32.to_be_bytes());
+        packet.extend(1_700_000_000_u32.to_be_bytes());
+        packet.extend(0_u32.to_be_bytes());
+        packet.extend(42_u32.to_be_bytes());
+        packet.extend([1, 2]);
+        packet.extend(((1_u16 << 14) | 100).to_be_bytes());
+        for _ in 0..records {
+            packet.extend(Ipv4Addr::new(10, 0, 0, 1).octets());
+            packet.extend(Ipv4Addr::new(10, 0, 0, 2).octets());
+            packet.extend(Ipv4Addr::new(10, 0, 0, 254).octets());
+            packet.extend(1_u16.to_be_bytes());
+            packet.extend(2_u16.to_be_bytes());
+            packet.extend(10_u32.to_be_bytes());
+            packet.extend(1500_u32.to_be_bytes());
+            packet.extend(900_u32.to_be_bytes());
+            packet.extend(950_u32.to_be_bytes());
+            packet.extend(51234_u16.to_be_bytes());
+            packet.extend(443_u16.to_be_bytes());
+            packet.extend([0, 0x12, 6, 0]);
+            packet.extend(64512_u16.to_be_bytes());
+            packet.extend(64513_u16.to_be_bytes());
+            packet.extend([24, 16, 0, 0]);
+        }
+        packet
+    }
+
+    #[test]
+    fn decodes_records() {
+        let packet = packet(2);
+        let mut reader = ByteReader::new(&packet);
+        reader.u16("version").unwrap();
+        let records = decode(reader).unwrap();
+
+        assert_eq!(records.len(), 2);
+        let record = &records[0];
+        assert_eq!(record["flow_type"], "netflow_v5".into());
+        assert_eq!(record["ipv4_src_addr"], "10.0.0.1".into());
+        assert_eq!(record["ipv4_dst_addr"], "10.0.0.2".into());
+        assert_eq!(record["l4_dst_port"], 443.into());
+        assert_eq!(record["protocol"], 6.into());
+        assert_eq!(record["tcp_flags"], 0x12.into());
+        assert_eq!(record["in_bytes"], 1500.into());
+        assert_eq!(record["src_as"], 64512.into());
+        assert_eq!(record["dst_mask"], 16.into());
+
+        let Value::Object(header) = &record["header"] else {
+            panic!("header should be an object");
+        };
+        assert_eq!(header["flow_sequence"], 42.into());
+        assert_eq!(header["sampling_mode"], 1.into());
+        assert_eq!(header["sampling_interval"], 100.into());
+    }
+
+    #[test]
+    fn rejects_truncated_records() {
+        let mut packet = packet(2);
+        packet.truncate(packet.len() - 1);
+        let mut reader = ByteReader::new(&packet);
+        reader.u16("version").unwrap();
+
+        assert!(matches!(
+            decode(reader),
+            Err(DecodeError::Truncated { what: "records" })
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+use std::net::IpAddr;
+
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    decoder::{ByteReader, DecodeError, DecodeResult},
+    fields::int_value,
+    templates::{Template, TemplateCache, TemplateField, TemplateKey},
+};
+use crate::internal_events::NetflowTemplateMiss;
+
+pub const VERSION: u16 = 9;
+
+const TEMPLATE_FLOWSET_ID: u16 = 0;
+const OPTIONS_TEMPLATE_FLOWSET_ID: u16 = 1;
+const MIN_DATA_FLOWSET_ID: u16 = 256;
+
+/// Decodes a NetFlow v9 packet, following its version number.
+///
+/// Templates are added to the cache as they are announced, and data records whose template is not
+/// known yet are dropped.
+pub fn decode(
+    mut packet: ByteReader<'_>,
+    exporter: IpAddr,
+    templates: &mut TemplateCache,
+) -> DecodeResult<Vec<ObjectMap>> {
+    let count = packet.u16("header")?;
+    let sys_uptime = packet.u32("header")?;
+    let unix_secs = packet.u32("header")?;
+    let sequence = packet.u32("header")?;
+    let source_id = packet.u32("header")?;
+
+    let header: ObjectMap = [
+        ("version", int_value(VERSION)),
+        ("count", int_value(count)),
+        ("sys_uptime", int_value(sys_uptime)),
+        ("unix_secs", int_value(unix_secs)),
+        ("sequence", int_value(sequence)),
+        ("source_id", int_value(source_id)),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.into(), value))
+    .collect();
+    let header = Value::Object(header);
+
+    let mut records = Vec::new();
+    while !packet.is_empty() {
+        let flowset_id = packet.u16("flowset header")?;
+        let length = usize::from(packet.u16("flowset header")?);
+        let body_length = length.checked_sub(4).ok_or(DecodeError::Invalid {
+            what: "flowset",
+            reason: "length is shorter than its header",
+        })?;
+        let mut flowset = packet.sub_reader(body_length, "flowset")?;
+
+        let key = |template_id| TemplateKey {
+            exporter,
+            version: VERSION,
+            domain_id: source_id,
+            template_id,
+        };
+
+        match flowset_id {
+            TEMPLATE_FLOWSET_ID => {
+                // Template flowsets may be padded, and a template has at least one field.
+                while flowset.remaining() >= 8 {
+                    let template_id = flowset.u16("template")?;
+                    let field_count = flowset.u16("template")?;
+                    let fields = read_fields(&mut flowset, field_count)?;
+                    templates.insert(key(template_id), Template::new(Vec::new(), fields)?);
+                }
+            }
+            OPTIONS_TEMPLATE_FLOWSET_ID => {
+                while flowset.remaining() >= 10 {
+                    let template_id = flowset.u16("options template")?;
+                    let scope_length = flowset.u16("options template")?;
+                    let options_length = flowset.u16("options template")?;
+                    let scope_fields = read_scope_fields(&mut flowset, scope_length / 4)?;
+                    let fields = read_fields(&mut flowset, options_length / 4)?;
+                    templates.insert(key(template_id), Template::new(scope_fields, fields)?);
+                }
+            }
+            MIN_DATA_FLOWSET_ID.. => match templates.get(&key(flowset_id)) {
+                Some(template) => {
+                    records.extend(template.decode_records(flowset)?.into_iter().map(
+                        |mut record| {
+                            record.insert("flow_type".into(), Value::from("netflow_v9"));
+                            record.insert("header".into(), header.clone());
+                            record
+                        },
+                    ));
+                }
+                None => emit!(NetflowTemplateMiss {
+                    protocol: "netflow_v9",
+                    template_id: flowset_id,
+                    exporter,
+                }),
+            },
+            // Flowset IDs 2 to 255 are reserved.
+            _ => {}
+        }
+    }
+
+    Ok(records)
+}
+
+fn read_fields(flowset: &mut ByteReader<'_>, count: u16) -> DecodeResult<Vec<TemplateField>> {
+    (0..count)
+        .map(|_| {
+            let field_type = flowset.u16("template field")?;
+            let length = flowset.u16("template field")?;
+            Ok(TemplateField::new(field_type, None, length))
+        })
+        .collect()
+}
+
+/// Reads the scope fields of an options template, whose types have their own numbering.
+fn read_scope_fields(flowset: &mut ByteReader<'_>, count: u16) -> DecodeResult<Vec<TemplateField>> {
+    (0..count)
+        .map(|_| {
+            let scope_type = flowset.u16("template scope field")?;
+            let length = flowset.u16("template scope field")?;
+            let name = match scope_type {
+                1 => "scope_system".to_string(),
+                2 => "scope_interface".to_string(),
+                3 => "scope_line_card".to_string(),
+                4 => "scope_cache".to_string(),
+                5 => "scope_template".to_string(),
+                _ => format!("scope_{scope_type}"),
+            };
+            Ok(TemplateField {
+                name,
+                kind: None,
+                length,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    const EXPORTER: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    fn packet(flowsets: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut packet = Vec::new();
+        packet.extend((flowsets.len() as u16).to_be_bytes());
+        packet.extend(1000_u32.to_be_bytes());
+        packet.extend(1_700_000_000_u32.to_be_bytes());
+        packet.extend(7_u32.to_be_bytes());
+        packet.extend(1_u32.to_be_bytes());
+        for (id, body) in flowsets {
+            packet.extend(id.to_be_bytes());
+            packet.extend((body.len() as u16 + 4).to_be_bytes());
+            packet.extend(body);
+        }
+        packet
+    }
+
+    fn template() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(256_u16.to_be_bytes());
+        body.extend(3_u16.to_be_bytes());
+        for (field_type, length) in [(8_u16, 4_u16), (7, 2), (1, 4)] {
+            body.extend(field_type.to_be_bytes());
+            body.extend(length.to_be_bytes());
+        }
+        body
+    }
+
+    fn data() -> Vec<u8> {
+        let mut body = Vec::new();
+        for (addr, port, bytes) in [([10, 0, 0, 1], 80_u16, 100_u32), ([10, 0, 0, 2], 443, 200)] {
+            body.extend(addr);
+            body.extend(port.to_be_bytes());
+            body.extend(bytes.to_be_bytes());
+        }
+        // Padding up to a 4-byte boundary.
+        body.extend([0, 0, 0, 0]);
+        body
+    }
+
+    #[test]
+    fn decodes_data_with_template() {
+        let mut templates = TemplateCache::default();
+        let packet = packet(&[(0, template()), (256, data())]);
+        let records = decode(ByteReader::new(&packet), EXPORTER, &mut templates).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["flow_type"], "netflow_v9".into());
+        assert_eq!(records[0]["record_type"], "flow".into());
+        assert_eq!(records[0]["ipv4_src_addr"], "10.0.0.1".into());
+        assert_eq!(records[1]["l4_src_port"], 443.into());
+        assert_eq!(records[1]["in_bytes"], 200.into());
+    }
+
+    #[test]
+    fn caches_templates_across_packets() {
+        let mut templates = TemplateCache::default();
+
+        let packet_data = packet(&[(256, data())]);
+        let records = decode(ByteReader::new(&packet_data), EXPORTER, &mut templates).unwrap();
+        assert!(records.is_empty());
+
+        let packet_template = packet(&[(0, template())]);
+        decode(ByteReader::new(&packet_template), EXPORTER, &mut templates).unwrap();
+
+        let records = decode(ByteReader::new(&packet_data), EXPORTER, &mut templates).unwrap();
+        assert_eq!(records.len(), 2);
+
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 9));
+        let records = decode(ByteReader::new(&packet_data), other, &mut templates).unwrap();
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn decodes_options_data() {
+        let mut body = Vec::new();
+        body.extend(257_u16.to_be_bytes());
+        body.extend(4_u16.to_be_bytes());
+        body.extend(4_u16.to_be_bytes());
+        // System scope, followed by the sampling interval.
+        body.extend([0, 1, 0, 4]);
+        body.extend([0, 34, 0, 4]);
+        body.extend([0, 0]);
+
+        let mut data = Vec::new();
+        data.extend(1_u32.to_be_bytes());
+        data.extend(512_u32.to_be_bytes());
+
+        let mut templates = TemplateCache::default();
+        let packet = packet(&[(1, body), (257, data)]);
+        let records = decode(ByteReader::new(&packet), EXPORTER, &mut templates).unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["record_type"], "options".into());
+        assert_eq!(records[0]["scope_system"], 1.into());
+        assert_eq!(records[0]["sampling_interval"], 512.into());
+    }
+}
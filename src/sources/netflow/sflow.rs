@@ -0,0 +1,459 @@
+use std::net::IpAddr;
+
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    decoder::{ByteReader, DecodeError, DecodeResult},
+    fields::{format_mac, int_value, unsigned_value},
+};
+
+pub const VERSION: u16 = 5;
+
+const FLOW_SAMPLE: u32 = 1;
+const COUNTER_SAMPLE: u32 = 2;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+const EXPANDED_COUNTER_SAMPLE: u32 = 4;
+
+const RAW_HEADER_RECORD: u32 = 1;
+const ETHERNET_RECORD: u32 = 2;
+const IPV4_RECORD: u32 = 3;
+const IPV6_RECORD: u32 = 4;
+const EXTENDED_SWITCH_RECORD: u32 = 1001;
+const EXTENDED_ROUTER_RECORD: u32 = 1002;
+
+const GENERIC_INTERFACE_COUNTERS_RECORD: u32 = 1;
+
+const ETHERNET_HEADER_PROTOCOL: u32 = 1;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const IP_PROTOCOL_TCP: u8 = 6;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// Decodes an sFlow v5 datagram, following its version number.
+///
+/// Each flow or counter sample becomes a single record, holding the fields of every flow or
+/// counter record of the sample. Samples and records of unknown formats are skipped.
+pub fn decode(mut packet: ByteReader<'_>) -> DecodeResult<Vec<ObjectMap>> {
+    let agent_address = read_address(&mut packet, "header")?;
+    let sub_agent_id = packet.u32("header")?;
+    let sequence = packet.u32("header")?;
+    let uptime = packet.u32("header")?;
+    let sample_count = packet.u32("header")?;
+
+    let header: ObjectMap = [
+        ("version", int_value(VERSION)),
+        ("agent_address", Value::from(agent_address.to_string())),
+        ("sub_agent_id", int_value(sub_agent_id)),
+        ("sequence", int_value(sequence)),
+        ("uptime", int_value(uptime)),
+        ("sample_count", int_value(sample_count)),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.into(), value))
+    .collect();
+    let header = Value::Object(header);
+
+    let mut records = Vec::new();
+    for _ in 0..sample_count {
+        let format = packet.u32("sample header")?;
+        let length = packet.u32("sample header")?;
+        let sample = packet.sub_reader(length as usize, "sample")?;
+
+        // Samples defined by other enterprises have a non-zero enterprise in the upper 20 bits.
+        let record = match format {
+            FLOW_SAMPLE => decode_flow_sample(sample, false)?,
+            EXPANDED_FLOW_SAMPLE => decode_flow_sample(sample, true)?,
+            COUNTER_SAMPLE => decode_counter_sample(sample, false)?,
+            EXPANDED_COUNTER_SAMPLE => decode_counter_sample(sample, true)?,
+            _ => continue,
+        };
+        records.push(record);
+    }
+
+    for record in &mut records {
+        record.insert("flow_type".into(), Value::from("sflow_v5"));
+        record.insert("header".into(), header.clone());
+    }
+    Ok(records)
+}
+
+fn read_address(packet: &mut ByteReader<'_>, what: &'static str) -> DecodeResult<IpAddr> {
+    match packet.u32(what)? {
+        1 => packet.ipv4(what).map(IpAddr::V4),
+        2 => packet.ipv6(what).map(IpAddr::V6),
+        _ => Err(DecodeError::Invalid {
+            what,
+            reason: "unknown address type",
+        }),
+    }
+}
+
+/// Reads the source ID of a sample, which the compact formats pack into a single word.
+fn read_source_id(
+    sample: &mut ByteReader<'_>,
+    record: &mut ObjectMap,
+    expanded: bool,
+) -> DecodeResult<()> {
+    let (source_id_type, source_id_index) = if expanded {
+        (sample.u32("sample")?, sample.u32("sample")?)
+    } else {
+        let source_id = sample.u32("sample")?;
+        (source_id >> 24, source_id & 0x00ff_ffff)
+    };
+    record.insert("source_id_type".into(), int_value(source_id_type));
+    record.insert("source_id_index".into(), int_value(source_id_index));
+    Ok(())
+}
+
+/// Reads an interface of an expanded flow sample, or of a compact one, which packs the format in
+/// the upper 2 bits.
+fn read_interface(sample: &mut ByteReader<'_>, expanded: bool) -> DecodeResult<u32> {
+    if expanded {
+        sample.u32("sample")?;
+        sample.u32("sample")
+    } else {
+        sample
+            .u32("sample")
+            .map(|interface| interface & 0x3fff_ffff)
+    }
+}
+
+fn decode_flow_sample(mut sample: ByteReader<'_>, expanded: bool) -> DecodeResult<ObjectMap> {
+    let mut record = ObjectMap::new();
+    record.insert("record_type".into(), Value::from("flow"));
+    record.insert("sample_sequence".into(), int_value(sample.u32("sample")?));
+    read_source_id(&mut sample, &mut record, expanded)?;
+    record.insert("sampling_rate".into(), int_value(sample.u32("sample")?));
+    record.insert("sample_pool".into(), int_value(sample.u32("sample")?));
+    record.insert("drops".into(), int_value(sample.u32("sample")?));
+    let input = read_interface(&mut sample, expanded)?;
+    record.insert("input_snmp".into(), int_value(input));
+    let output = read_interface(&mut sample, expanded)?;
+    record.insert("output_snmp".into(), int_value(output));
+
+    let record_count = sample.u32("sample")?;
+    for _ in 0..record_count {
+        let format = sample.u32("flow record header")?;
+        let length = sample.u32("flow record header")?;
+        let mut data = sample.sub_reader(length as usize, "flow record")?;
+        match format {
+            RAW_HEADER_RECORD => decode_raw_header(&mut data, &mut record)?,
+            ETHERNET_RECORD => {
+                let what = "ethernet record";
+                record.insert("frame_length".into(), int_value(data.u32(what)?));
+                // MAC addresses are padded to 8 bytes.
+                let src = format_mac(&data.bytes(8, what)?[..6]);
+                let dst = format_mac(&data.bytes(8, what)?[..6]);
+                record.insert("in_src_mac".into(), src.into());
+                record.insert("in_dst_mac".into(), dst.into());
+                record.insert("ethernet_type".into(), int_value(data.u32(what)?));
+            }
+            IPV4_RECORD => {
+                record.insert("ip_length".into(), int_value(data.u32("ipv4 record")?));
+                record.insert("protocol".into(), int_value(data.u32("ipv4 record")?));
+                let src = data.ipv4("ipv4 record")?;
+                let dst = data.ipv4("ipv4 record")?;
+                record.insert("ipv4_src_addr".into(), src.to_string().into());
+                record.insert("ipv4_dst_addr".into(), dst.to_string().into());
+                decode_ip_record_tail(&mut data, &mut record, "ipv4 record")?;
+            }
+            IPV6_RECORD => {
+                record.insert("ip_length".into(), int_value(data.u32("ipv6 record")?));
+                record.insert("protocol".into(), int_value(data.u32("ipv6 record")?));
+                let src = data.ipv6("ipv6 record")?;
+                let dst = data.ipv6("ipv6 record")?;
+                record.insert("ipv6_src_addr".into(), src.to_string().into());
+                record.insert("ipv6_dst_addr".into(), dst.to_string().into());
+                decode_ip_record_tail(&mut data, &mut record, "ipv6 record")?;
+            }
+            EXTENDED_SWITCH_RECORD => {
+                record.insert("src_vlan".into(), int_value(data.u32("switch record")?));
+                record.insert("src_priority".into(), int_value(data.u32("switch record")?));
+                record.insert("dst_vlan".into(), int_value(data.u32("switch record")?));
+                record.insert("dst_priority".into(), int_value(data.u32("switch record")?));
+            }
+            EXTENDED_ROUTER_RECORD => {
+                let next_hop = read_address(&mut data, "router record")?;
+                record.insert("next_hop".into(), next_hop.to_string().into());
+                record.insert("src_mask".into(), int_value(data.u32("router record")?));
+                record.insert("dst_mask".into(), int_value(data.u32("router record")?));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(record)
+}
+
+/// Decodes the ports, TCP flags and type of service shared by the IPv4 and IPv6 records.
+fn decode_ip_record_tail(
+    data: &mut ByteReader<'_>,
+    record: &mut ObjectMap,
+    what: &'static str,
+) -> DecodeResult<()> {
+    record.insert("l4_src_port".into(), int_value(data.u32(what)?));
+    record.insert("l4_dst_port".into(), int_value(data.u32(what)?));
+    record.insert("tcp_flags".into(), int_value(data.u32(what)?));
+    record.insert("src_tos".into(), int_value(data.u32(what)?));
+    Ok(())
+}
+
+fn decode_raw_header(data: &mut ByteReader<'_>, record: &mut ObjectMap) -> DecodeResult<()> {
+    let protocol = data.u32("raw header record")?;
+    record.insert(
+        "frame_length".into(),
+        int_value(data.u32("raw header record")?),
+    );
+    record.insert("stripped".into(), int_value(data.u32("raw header record")?));
+    let header_length = data.u32("raw header record")?;
+    let header = data.bytes(header_length as usize, "raw header record")?;
+
+    if protocol == ETHERNET_HEADER_PROTOCOL {
+        // Sampled headers are cut off at an arbitrary length, so whatever could be parsed before
+        // running out of bytes is kept.
+        _ = decode_ethernet_header(ByteReader::new(header), record);
+    }
+    Ok(())
+}
+
+fn decode_ethernet_header(mut header: ByteReader<'_>, record: &mut ObjectMap) -> DecodeResult<()> {
+    let what = "sampled header";
+    record.insert(
+        "in_dst_mac".into(),
+        format_mac(header.bytes(6, what)?).into(),
+    );
+    record.insert(
+        "in_src_mac".into(),
+        format_mac(header.bytes(6, what)?).into(),
+    );
+
+    let mut ethertype = header.u16(what)?;
+    if ethertype == ETHERTYPE_VLAN {
+        record.insert("src_vlan".into(), int_value(header.u16(what)? & 0x0fff));
+        ethertype = header.u16(what)?;
+    }
+
+    let protocol = match ethertype {
+        ETHERTYPE_IPV4 => {
+            let version_ihl = header.u8(what)?;
+            record.insert("src_tos".into(), int_value(header.u8(what)?));
+            header.skip(7, what)?;
+            let protocol = header.u8(what)?;
+            header.skip(2, what)?;
+            record.insert(
+                "ipv4_src_addr".into(),
+                header.ipv4(what)?.to_string().into(),
+            );
+            record.insert(
+                "ipv4_dst_addr".into(),
+                header.ipv4(what)?.to_string().into(),
+            );
+            // Skip any options, as the header length is given in 32-bit words.
+            let header_length = usize::from(version_ihl & 0x0f) * 4;
+            header.skip(header_length.saturating_sub(20), what)?;
+            protocol
+        }
+        ETHERTYPE_IPV6 => {
+            header.skip(6, what)?;
+            let protocol = header.u8(what)?;
+            header.skip(1, what)?;
+            record.insert(
+                "ipv6_src_addr".into(),
+                header.ipv6(what)?.to_string().into(),
+            );
+            record.insert(
+                "ipv6_dst_addr".into(),
+                header.ipv6(what)?.to_string().into(),
+            );
+            protocol
+        }
+        _ => return Ok(()),
+    };
+    record.insert("protocol".into(), int_value(protocol));
+
+    if matches!(protocol, IP_PROTOCOL_TCP | IP_PROTOCOL_UDP) {
+        record.insert("l4_src_port".into(), int_value(header.u16(what)?));
+        record.insert("l4_dst_port".into(), int_value(header.u16(what)?));
+    }
+    if protocol == IP_PROTOCOL_TCP {
+        header.skip(9, what)?;
+        record.insert("tcp_flags".into(), int_value(header.u8(what)?));
+    }
+    Ok(())
+}
+
+fn decode_counter_sample(mut sample: ByteReader<'_>, expanded: bool) -> DecodeResult<ObjectMap> {
+    let mut record = ObjectMap::new();
+    record.insert("record_type".into(), Value::from("counter"));
+    record.insert("sample_sequence".into(), int_value(sample.u32("sample")?));
+    read_source_id(&mut sample, &mut record, expanded)?;
+
+    let record_count = sample.u32("sample")?;
+    for _ in 0..record_count {
+        let format = sample.u32("counter record header")?;
+        let length = sample.u32("counter record header")?;
+        let mut data = sample.sub_reader(length as usize, "counter record")?;
+        if format != GENERIC_INTERFACE_COUNTERS_RECORD {
+            continue;
+        }
+
+        let what = "interface counters record";
+        let counters = [
+            ("if_index", false),
+            ("if_type", false),
+            ("if_speed", true),
+            ("if_direction", false),
+            ("if_status", false),
+            ("if_in_octets", true),
+            ("if_in_ucast_pkts", false),
+            ("if_in_multicast_pkts", false),
+            ("if_in_broadcast_pkts", false),
+            ("if_in_discards", false),
+            ("if_in_errors", false),
+            ("if_in_unknown_protos", false),
+            ("if_out_octets", true),
+            ("if_out_ucast_pkts", false),
+            ("if_out_multicast_pkts", false),
+            ("if_out_broadcast_pkts", false),
+            ("if_out_discards", false),
+            ("if_out_errors", false),
+            ("if_promiscuous_mode", false),
+        ];
+        for (name, is_64_bit) in counters {
+            let value = if is_64_bit {
+                unsigned_value(data.u64(what)?)
+            } else {
+                int_value(data.u32(what)?)
+            };
+            record.insert(name.into(), value);
+        }
+    }
+
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn datagram(samples: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut datagram = Vec::new();
+        datagram.extend(1_u32.to_be_bytes());
+        datagram.extend(Ipv4Addr::new(172, 16, 0, 1).octets());
+        datagram.extend(0_u32.to_be_bytes());
+        datagram.extend(12_u32.to_be_bytes());
+        datagram.extend(60_000_u32.to_be_bytes());
+        datagram.extend((samples.len() as u32).to_be_bytes());
+        for (format, body) in samples {
+            datagram.extend(format.to_be_bytes());
+            datagram.extend((body.len() as u32).to_be_bytes());
+            datagram.extend(body);
+        }
+        datagram
+    }
+
+    fn sampled_header() -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        header.extend([0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb]);
+        header.extend(ETHERTYPE_VLAN.to_be_bytes());
+        header.extend(100_u16.to_be_bytes());
+        header.extend(ETHERTYPE_IPV4.to_be_bytes());
+        header.extend([0x45, 0x10, 0, 60, 0, 0, 0, 0, 64, IP_PROTOCOL_TCP, 0, 0]);
+        header.extend([10, 1, 1, 1]);
+        header.extend([10, 2, 2, 2]);
+        header.extend(40000_u16.to_be_bytes());
+        header.extend(22_u16.to_be_bytes());
+        header.extend([0; 9]);
+        header.extend([0x02]);
+        // Pad to a 4-byte boundary.
+        header.extend([0; 2]);
+        header
+    }
+
+    fn flow_sample() -> Vec<u8> {
+        let header = sampled_header();
+        let mut record = Vec::new();
+        record.extend(ETHERNET_HEADER_PROTOCOL.to_be_bytes());
+        record.extend(1514_u32.to_be_bytes());
+        record.extend(4_u32.to_be_bytes());
+        record.extend((header.len() as u32 - 2).to_be_bytes());
+        record.extend(header);
+
+        let mut sample = Vec::new();
+        sample.extend(1_u32.to_be_bytes());
+        sample.extend(3_u32.to_be_bytes());
+        sample.extend(512_u32.to_be_bytes());
+        sample.extend(4096_u32.to_be_bytes());
+        sample.extend(0_u32.to_be_bytes());
+        sample.extend(3_u32.to_be_bytes());
+        sample.extend(4_u32.to_be_bytes());
+        sample.extend(1_u32.to_be_bytes());
+        sample.extend(RAW_HEADER_RECORD.to_be_bytes());
+        sample.extend((record.len() as u32).to_be_bytes());
+        sample.extend(record);
+        sample
+    }
+
+    #[test]
+    fn decodes_flow_samples() {
+        let datagram = datagram(&[(FLOW_SAMPLE, flow_sample()), (0xfff, vec![0; 4])]);
+        let records = decode(ByteReader::new(&datagram)).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record["flow_type"], "sflow_v5".into());
+        assert_eq!(record["record_type"], "flow".into());
+        assert_eq!(record["sampling_rate"], 512.into());
+        assert_eq!(record["input_snmp"], 3.into());
+        assert_eq!(record["frame_length"], 1514.into());
+        assert_eq!(record["in_src_mac"], "66:77:88:99:aa:bb".into());
+        assert_eq!(record["src_vlan"], 100.into());
+        assert_eq!(record["ipv4_src_addr"], "10.1.1.1".into());
+        assert_eq!(record["ipv4_dst_addr"], "10.2.2.2".into());
+        assert_eq!(record["protocol"], 6.into());
+        assert_eq!(record["l4_dst_port"], 22.into());
+        assert_eq!(record["tcp_flags"], 2.into());
+
+        let Value::Object(header) = &record["header"] else {
+            panic!("header should be an object");
+        };
+        assert_eq!(header["agent_address"], "172.16.0.1".into());
+    }
+
+    #[test]
+    fn decodes_counter_samples() {
+        let mut counters = Vec::new();
+        counters.extend(7_u32.to_be_bytes());
+        counters.extend(6_u32.to_be_bytes());
+        counters.extend(10_000_000_000_u64.to_be_bytes());
+        counters.extend([0; 8]);
+        counters.extend(123_456_u64.to_be_bytes());
+        counters.extend([0; 24]);
+        counters.extend(654_321_u64.to_be_bytes());
+        counters.extend([0; 24]);
+
+        let mut sample = Vec::new();
+        sample.extend(1_u32.to_be_bytes());
+        sample.extend(7_u32.to_be_bytes());
+        sample.extend(1_u32.to_be_bytes());
+        sample.extend(GENERIC_INTERFACE_COUNTERS_RECORD.to_be_bytes());
+        sample.extend((counters.len() as u32).to_be_bytes());
+        sample.extend(counters);
+
+        let datagram = datagram(&[(COUNTER_SAMPLE, sample)]);
+        let records = decode(ByteReader::new(&datagram)).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record["record_type"], "counter".into());
+        assert_eq!(record["source_id_index"], 7.into());
+        assert_eq!(record["if_index"], 7.into());
+        assert_eq!(record["if_speed"], 10_000_000_000_i64.into());
+        assert_eq!(record["if_in_octets"], 123_456.into());
+        assert_eq!(record["if_out_octets"], 654_321.into());
+    }
+}
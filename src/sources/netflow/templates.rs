@@ -0,0 +1,264 @@
+use std::{collections::HashMap, net::IpAddr, num::NonZeroUsize, time::Duration};
+
+use tokio::time::Instant;
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    decoder::{ByteReader, DecodeError, DecodeResult},
+    fields::{FieldKind, decode_field, field_info},
+};
+use crate::internal_events::NetflowTemplateEvicted;
+
+/// The field length used by IPFIX to mark a variable-length field.
+pub const VARIABLE_LENGTH: u16 = u16::MAX;
+
+/// A single field of a template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateField {
+    pub name: String,
+    pub kind: Option<FieldKind>,
+    pub length: u16,
+}
+
+impl TemplateField {
+    pub fn new(field_type: u16, enterprise: Option<u32>, length: u16) -> Self {
+        let (name, kind) = field_info(field_type, enterprise);
+        Self { name, kind, length }
+    }
+}
+
+/// A template describing the layout of data records.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    /// The scope fields of an options template, which are empty for regular templates.
+    pub scope_fields: Vec<TemplateField>,
+    pub fields: Vec<TemplateField>,
+}
+
+impl Template {
+    pub fn new(scope_fields: Vec<TemplateField>, fields: Vec<TemplateField>) -> DecodeResult<Self> {
+        let template = Self {
+            scope_fields,
+            fields,
+        };
+        // Records with no data would never consume any bytes from a data set.
+        if template.min_record_length() == 0 {
+            return Err(DecodeError::Invalid {
+                what: "template",
+                reason: "template describes empty records",
+            });
+        }
+        Ok(template)
+    }
+
+    pub fn is_options(&self) -> bool {
+        !self.scope_fields.is_empty()
+    }
+
+    fn all_fields(&self) -> impl Iterator<Item = &TemplateField> {
+        self.scope_fields.iter().chain(&self.fields)
+    }
+
+    /// The length of the smallest possible record, where variable-length fields are empty.
+    fn min_record_length(&self) -> usize {
+        self.all_fields()
+            .map(|field| match field.length {
+                VARIABLE_LENGTH => 1,
+                length => usize::from(length),
+            })
+            .sum()
+    }
+
+    /// Decodes all of the records of a data set.
+    ///
+    /// Data sets may be padded up to a 4-byte boundary, so any trailing bytes which are too short
+    /// to hold a record are ignored.
+    pub fn decode_records(&self, mut data: ByteReader<'_>) -> DecodeResult<Vec<ObjectMap>> {
+        let min_record_length = self.min_record_length();
+        let mut records = Vec::new();
+        while data.remaining() >= min_record_length {
+            let mut record = ObjectMap::new();
+            for field in self.all_fields() {
+                let length = match field.length {
+                    VARIABLE_LENGTH => match data.u8("variable field length")? {
+                        255 => data.u16("variable field length")?,
+                        length => u16::from(length),
+                    },
+                    length => length,
+                };
+                let value = decode_field(field.kind, data.bytes(usize::from(length), "field")?);
+                record.insert(field.name.as_str().into(), value);
+            }
+            record.insert(
+                "record_type".into(),
+                Value::from(if self.is_options() { "options" } else { "flow" }),
+            );
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+/// Identifies a template announced by an exporter.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct TemplateKey {
+    pub exporter: IpAddr,
+    pub version: u16,
+    /// The NetFlow v9 source ID, or IPFIX observation domain ID.
+    pub domain_id: u32,
+    pub template_id: u16,
+}
+
+/// The templates announced by every exporter.
+///
+/// Templates expire when they haven't been announced again within the configured time to live,
+/// and the least recently announced template is evicted when the cache is full, so exporters
+/// that go away or announce an unbounded number of templates can't grow the cache indefinitely.
+#[derive(Debug)]
+pub struct TemplateCache {
+    templates: HashMap<TemplateKey, CachedTemplate>,
+    max_templates: NonZeroUsize,
+    ttl: Duration,
+}
+
+#[derive(Debug)]
+struct CachedTemplate {
+    template: Template,
+    announced_at: Instant,
+}
+
+impl Default for TemplateCache {
+    fn default() -> Self {
+        Self::new(default_max_templates(), default_template_ttl())
+    }
+}
+
+impl TemplateCache {
+    pub fn new(max_templates: NonZeroUsize, ttl: Duration) -> Self {
+        Self {
+            templates: HashMap::new(),
+            max_templates,
+            ttl,
+        }
+    }
+
+    pub fn get(&mut self, key: &TemplateKey) -> Option<&Template> {
+        let ttl = self.ttl;
+        if self
+            .templates
+            .get(key)
+            .is_some_and(|cached| cached.announced_at.elapsed() >= ttl)
+        {
+            self.templates.remove(key);
+        }
+        self.templates.get(key).map(|cached| &cached.template)
+    }
+
+    pub fn insert(&mut self, key: TemplateKey, template: Template) {
+        if !self.templates.contains_key(&key) && self.templates.len() >= self.max_templates.get() {
+            self.evict();
+        }
+        self.templates.insert(
+            key,
+            CachedTemplate {
+                template,
+                announced_at: Instant::now(),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, key: &TemplateKey) {
+        self.templates.remove(key);
+    }
+
+    /// Removes every template, or every options template, of an observation domain, as done by
+    /// an IPFIX "all templates" withdrawal.
+    pub fn remove_all(&mut self, exporter: IpAddr, version: u16, domain_id: u32, options: bool) {
+        self.templates.retain(|key, cached| {
+            key.exporter != exporter
+                || key.version != version
+                || key.domain_id != domain_id
+                || cached.template.is_options() != options
+        });
+    }
+
+    /// Makes room for a new template, by removing the expired templates, or the least recently
+    /// announced template if none expired.
+    fn evict(&mut self) {
+        let ttl = self.ttl;
+        self.templates
+            .retain(|_, cached| cached.announced_at.elapsed() < ttl);
+        if self.templates.len() < self.max_templates.get() {
+            return;
+        }
+
+        let oldest = self
+            .templates
+            .iter()
+            .min_by_key(|(_, cached)| cached.announced_at)
+            .map(|(key, _)| *key);
+        if let Some(key) = oldest {
+            emit!(NetflowTemplateEvicted {
+                exporter: key.exporter,
+            });
+            self.templates.remove(&key);
+        }
+    }
+}
+
+pub const fn default_max_templates() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+pub const fn default_template_ttl() -> Duration {
+    Duration::from_secs(30 * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn key(template_id: u16) -> TemplateKey {
+        TemplateKey {
+            exporter: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            version: 10,
+            domain_id: 0,
+            template_id,
+        }
+    }
+
+    fn template() -> Template {
+        Template::new(Vec::new(), vec![TemplateField::new(8, None, 4)]).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn evicts_least_recently_announced_template() {
+        let mut cache = TemplateCache::new(NonZeroUsize::new(2).unwrap(), Duration::from_secs(60));
+        cache.insert(key(256), template());
+        tokio::time::advance(Duration::from_secs(1)).await;
+        cache.insert(key(257), template());
+        tokio::time::advance(Duration::from_secs(1)).await;
+
+        // Announcing a template again refreshes it.
+        cache.insert(key(256), template());
+        cache.insert(key(258), template());
+
+        assert!(cache.get(&key(256)).is_some());
+        assert!(cache.get(&key(257)).is_none());
+        assert!(cache.get(&key(258)).is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn expires_templates() {
+        let mut cache = TemplateCache::new(NonZeroUsize::new(2).unwrap(), Duration::from_secs(60));
+        cache.insert(key(256), template());
+        tokio::time::advance(Duration::from_secs(30)).await;
+        cache.insert(key(257), template());
+        tokio::time::advance(Duration::from_secs(30)).await;
+
+        assert!(cache.get(&key(256)).is_none());
+        assert!(cache.get(&key(257)).is_some());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::config::OutputId;
-use crate::event::metric::{Bucket, Quantile};
+use crate::event::metric::{Bucket, MetricSketch, Quantile};
 use crate::event::{MetricKind, MetricTags, MetricValue};
 use crate::{
     SourceSender,
@@ -24,6 +24,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tonic::Request;
 use vector_lib::config::LogNamespace;
 use vector_lib::lookup::path;
+use vector_lib::metrics::ExponentialBuckets;
 use vector_lib::opentelemetry::proto::{
     collector::{
         logs::v1::{ExportLogsServiceRequest, logs_service_client::LogsServiceClient},
@@ -890,31 +891,26 @@ async fn receive_expontential_histogram_metric() {
             MetricEvent::new(
                 "some.random.metric",
                 MetricKind::Absolute,
-                MetricValue::AggregatedHistogram {
-                    buckets: vec![
-                        Bucket {
-                            count: 1,
-                            upper_limit: -0.8408964152537146,
-                        },
-                        Bucket {
-                            count: 2,
-                            upper_limit: -1.0,
-                        },
-                        Bucket {
-                            count: 1,
-                            upper_limit: 0f64,
+                MetricValue::Sketch {
+                    sketch: MetricSketch::ExponentialHistogram(
+                        vector_lib::metrics::ExponentialHistogram {
+                            scale: 2,
+                            zero_count: 1,
+                            zero_threshold: 0.0,
+                            positive: ExponentialBuckets {
+                                offset: 0,
+                                counts: vec![2, 1],
+                            },
+                            negative: ExponentialBuckets {
+                                offset: -1,
+                                counts: vec![1, 2],
+                            },
+                            count: 7,
+                            sum: 700.0,
+                            min: Some(-120.0),
+                            max: Some(150.0),
                         },
-                        Bucket {
-                            count: 2,
-                            upper_limit: 1.189207115002721,
-                        },
-                        Bucket {
-                            count: 1,
-                            upper_limit: 1.4142135623730951,
-                        },
-                    ],
-                    count: 7,
-                    sum: 700.00,
+                    ),
                 },
             )
             .with_timestamp(Some(DateTime::<Utc>::from(event_time)))
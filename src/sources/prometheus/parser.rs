@@ -1,13 +1,24 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::BTreeMap};
 
 use chrono::{DateTime, TimeZone, Utc};
+use indexmap::IndexMap;
 #[cfg(feature = "sources-prometheus-remote-write")]
 use vector_lib::prometheus::parser::proto;
-use vector_lib::prometheus::parser::{GroupKind, MetricGroup, ParserError};
+use vector_lib::{
+    metrics::{ExponentialBuckets, ExponentialHistogram},
+    prometheus::parser::{
+        EXEMPLARS_KEY, Exemplar, GroupKind, GroupMetadata, METADATA_KEY, MetricGroup,
+        NativeHistogramBuckets, NativeHistogramMetric, ParserError,
+    },
+};
+use vrl::{
+    path,
+    value::{ObjectMap, Value},
+};
 
 use crate::event::{
     Event,
-    metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
+    metric::{Bucket, Metric, MetricKind, MetricSketch, MetricTags, MetricValue, Quantile},
 };
 
 fn utc_timestamp(timestamp: Option<i64>, default: DateTime<Utc>) -> DateTime<Utc> {
@@ -41,6 +52,12 @@ pub(super) fn parse_request(request: proto::WriteRequest) -> Result<Vec<Event>,
         .map(|group| reparse_groups(group, vec![], false))
 }
 
+#[cfg(feature = "sources-prometheus-remote-write")]
+pub(super) fn parse_request_v2(request: proto::v2::Request) -> Result<Vec<Event>, ParserError> {
+    vector_lib::prometheus::parser::parse_request_v2(request)
+        .map(|group| reparse_groups(group, vec![], false))
+}
+
 fn reparse_groups(
     groups: Vec<MetricGroup>,
     tag_overrides: impl IntoIterator<Item = (String, String)> + Clone,
@@ -56,6 +73,7 @@ fn reparse_groups(
     };
 
     for group in groups {
+        let group_start = result.len();
         match group.metrics {
             GroupKind::Counter(metrics) => {
                 for (key, metric) in metrics {
@@ -159,11 +177,118 @@ fn reparse_groups(
                 }
             }
         }
+
+        for (key, histogram) in group.native_histograms {
+            let tags = combine_tags(key.labels, tag_overrides.clone());
+
+            result.push(
+                Metric::new(
+                    group.name.clone(),
+                    metric_kind,
+                    MetricValue::Sketch {
+                        sketch: MetricSketch::ExponentialHistogram(exponential_histogram(
+                            histogram,
+                        )),
+                    },
+                )
+                .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
+                .with_tags(tags.as_option())
+                .into(),
+            );
+        }
+
+        with_metadata(&mut result[group_start..], group.metadata, group.exemplars);
     }
 
     result
 }
 
+fn exponential_histogram(histogram: NativeHistogramMetric) -> ExponentialHistogram {
+    // The bucket at index `i` of a native histogram is the bucket at index `i - 1` of an
+    // exponential histogram.
+    let buckets = |buckets: NativeHistogramBuckets| ExponentialBuckets {
+        offset: buckets.offset - 1,
+        counts: buckets.counts,
+    };
+    ExponentialHistogram {
+        scale: histogram.schema,
+        zero_count: histogram.zero_count,
+        zero_threshold: histogram.zero_threshold,
+        positive: buckets(histogram.positive),
+        negative: buckets(histogram.negative),
+        count: histogram.count,
+        sum: histogram.sum,
+        min: None,
+        max: None,
+    }
+}
+
+/// Keeps the metadata of a metric family in the metadata of its metrics, along with the exemplars
+/// of each series, which go to its latest metric.
+fn with_metadata(
+    events: &mut [Event],
+    metadata: GroupMetadata,
+    exemplars: IndexMap<BTreeMap<String, String>, Vec<Exemplar>>,
+) {
+    let mut object = ObjectMap::new();
+    if let Some(r#type) = metadata.r#type {
+        object.insert("type".into(), Value::from(r#type.as_str()));
+    }
+    if let Some(help) = metadata.help {
+        object.insert("help".into(), Value::from(help));
+    }
+    if let Some(unit) = metadata.unit {
+        object.insert("unit".into(), Value::from(unit));
+    }
+    if !object.is_empty() {
+        let object = Value::Object(object);
+        for event in events.iter_mut() {
+            event
+                .as_mut_metric()
+                .metadata_mut()
+                .value_mut()
+                .insert(path!(METADATA_KEY), object.clone());
+        }
+    }
+
+    for (labels, exemplars) in exemplars {
+        let series = events.iter_mut().rev().find(|event| {
+            let tags = event.as_metric().tags();
+            labels
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .eq(tags.into_iter().flat_map(MetricTags::iter_single))
+        });
+        if let Some(event) = series {
+            let exemplars = exemplars.into_iter().map(exemplar_into_value).collect();
+            event
+                .as_mut_metric()
+                .metadata_mut()
+                .value_mut()
+                .insert(path!(EXEMPLARS_KEY), Value::Array(exemplars));
+        }
+    }
+}
+
+/// Converts an exemplar to the layout of the exemplars of the `opentelemetry` source, with its
+/// labels as attributes.
+fn exemplar_into_value(exemplar: Exemplar) -> Value {
+    let mut object = ObjectMap::new();
+    object.insert("value".into(), Value::from_f64_or_zero(exemplar.value));
+    if let Some(timestamp) = Utc.timestamp_millis_opt(exemplar.timestamp).latest() {
+        object.insert("timestamp".into(), Value::Timestamp(timestamp));
+    }
+    if !exemplar.labels.is_empty() {
+        let labels = exemplar
+            .labels
+            .into_iter()
+            .map(|(name, value)| (name.into(), Value::from(value)))
+            .collect();
+        object.insert("attributes".into(), Value::Object(labels));
+    }
+    Value::Object(object)
+}
+
 fn combine_tags(
     base_tags: impl Into<MetricTags>,
     tag_overrides: impl IntoIterator<Item = (String, String)>,
@@ -5,7 +5,7 @@ use prost::Message;
 use vector_lib::config::LogNamespace;
 use vector_lib::configurable::configurable_component;
 use vector_lib::prometheus::parser::proto;
-use warp::http::{HeaderMap, StatusCode};
+use warp::http::{HeaderMap, StatusCode, header::CONTENT_TYPE};
 
 use super::parser;
 use crate::{
@@ -24,7 +24,16 @@ use crate::{
     tls::TlsEnableableConfig,
 };
 
+/// The protobuf message of remote write 1.0 requests.
+const PROTO_V1: &str = "prometheus.WriteRequest";
+
+/// The protobuf message of remote write 2.0 requests.
+const PROTO_V2: &str = "io.prometheus.write.v2.Request";
+
 /// Configuration for the `prometheus_remote_write` source.
+///
+/// Both the 1.0 and 2.0 versions of the protocol are accepted, based on the `proto` parameter of
+/// the `Content-Type` header of requests.
 #[configurable_component(source(
     "prometheus_remote_write",
     "Receive metric via the Prometheus Remote Write protocol."
@@ -111,17 +120,30 @@ impl SourceConfig for PrometheusRemoteWriteConfig {
 struct RemoteWriteSource;
 
 impl RemoteWriteSource {
-    fn decode_body(&self, body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
-        let request = proto::WriteRequest::decode(body).map_err(|error| {
-            emit!(PrometheusRemoteWriteParseError {
-                error: error.clone()
-            });
+    fn decode_body(&self, body: Bytes, message: &str) -> Result<Vec<Event>, ErrorMessage> {
+        let events = match message {
+            PROTO_V1 => Self::decode_message(body).map(parser::parse_request)?,
+            PROTO_V2 => Self::decode_message(body).map(parser::parse_request_v2)?,
+            _ => {
+                return Err(ErrorMessage::new(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    format!("Unsupported remote write message: {message}"),
+                ));
+            }
+        };
+        events.map_err(|error| {
             ErrorMessage::new(
                 StatusCode::BAD_REQUEST,
                 format!("Could not decode write request: {error}"),
             )
-        })?;
-        parser::parse_request(request).map_err(|error| {
+        })
+    }
+
+    fn decode_message<M: Message + Default>(body: Bytes) -> Result<M, ErrorMessage> {
+        M::decode(body).map_err(|error| {
+            emit!(PrometheusRemoteWriteParseError {
+                error: error.clone()
+            });
             ErrorMessage::new(
                 StatusCode::BAD_REQUEST,
                 format!("Could not decode write request: {error}"),
@@ -130,6 +152,22 @@ impl RemoteWriteSource {
     }
 }
 
+/// Returns the protobuf message of a request, from the `proto` parameter of its content type.
+///
+/// Requests without this parameter are remote write 1.0 ones.
+fn proto_message(header_map: &HeaderMap) -> &str {
+    header_map
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| {
+            content_type.split(';').skip(1).find_map(|parameter| {
+                let (name, value) = parameter.split_once('=')?;
+                (name.trim() == "proto").then(|| value.trim().trim_matches('"'))
+            })
+        })
+        .unwrap_or(PROTO_V1)
+}
+
 impl HttpSource for RemoteWriteSource {
     fn decode(&self, encoding_header: Option<&str>, body: Bytes) -> Result<Bytes, ErrorMessage> {
         // Default to snappy decoding the request body.
@@ -139,11 +177,11 @@ impl HttpSource for RemoteWriteSource {
     fn build_events(
         &self,
         body: Bytes,
-        _header_map: &HeaderMap,
+        header_map: &HeaderMap,
         _query_parameters: &HashMap<String, String>,
         _full_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        let events = self.decode_body(body)?;
+        let events = self.decode_body(body, proto_message(header_map))?;
         Ok(events)
     }
 }
@@ -152,15 +190,18 @@ impl HttpSource for RemoteWriteSource {
 mod test {
     use chrono::{SubsecRound as _, Utc};
     use vector_lib::{
-        event::{EventStatus, Metric, MetricKind, MetricValue},
+        event::{EventStatus, Metric, MetricKind, MetricValue, metric::MetricSketch},
         metric_tags,
+        metrics::{ExponentialBuckets, ExponentialHistogram},
+        prometheus::parser::METADATA_KEY,
     };
+    use vrl::{path, value::Value};
 
     use super::*;
     use crate::{
         SourceSender,
         config::{SinkConfig, SinkContext},
-        sinks::prometheus::remote_write::RemoteWriteConfig,
+        sinks::prometheus::remote_write::{RemoteWriteConfig, RemoteWriteVersion},
         test_util::{self, wait_for_tcp},
         tls::MaybeTlsSettings,
     };
@@ -271,6 +312,102 @@ mod test {
         ]
     }
 
+    #[tokio::test]
+    async fn receives_native_histograms_over_v2() {
+        let address = test_util::next_addr();
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+
+        let source = PrometheusRemoteWriteConfig::from_address(address)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let sink = RemoteWriteConfig {
+            endpoint: format!("http://localhost:{}/", address.port()),
+            version: RemoteWriteVersion::V2,
+            ..Default::default()
+        };
+        let (sink, _) = sink
+            .build(SinkContext::default())
+            .await
+            .expect("Error building config.");
+
+        let mut family = vrl::value::ObjectMap::new();
+        family.insert("type".into(), "histogram".into());
+        family.insert("help".into(), "Request latency.".into());
+        family.insert("unit".into(), "seconds".into());
+        let family = Value::Object(family);
+
+        let mut metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::Sketch {
+                sketch: MetricSketch::ExponentialHistogram(ExponentialHistogram {
+                    scale: 3,
+                    zero_count: 2,
+                    zero_threshold: 0.001,
+                    positive: ExponentialBuckets {
+                        offset: -2,
+                        counts: vec![1, 0, 4],
+                    },
+                    negative: ExponentialBuckets {
+                        offset: 5,
+                        counts: vec![3],
+                    },
+                    count: 10,
+                    sum: 4.25,
+                    min: None,
+                    max: None,
+                }),
+            },
+        )
+        .with_tags(Some(metric_tags!("code" => "200")))
+        .with_timestamp(Some(Utc::now().trunc_subsecs(3)));
+        metric
+            .metadata_mut()
+            .value_mut()
+            .insert(path!(METADATA_KEY), family.clone());
+        let events = vec![Event::from(metric)];
+
+        let events_copy = events.clone();
+        let output = test_util::spawn_collect_ready(
+            async move {
+                sink.run_events(events_copy).await.unwrap();
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        vector_lib::assert_event_data_eq!(events, output);
+        assert_eq!(
+            output[0]
+                .as_metric()
+                .metadata()
+                .value()
+                .get(path!(METADATA_KEY)),
+            Some(&family)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_proto_messages() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            "application/x-protobuf;proto=io.prometheus.write.v3.Request"
+                .parse()
+                .unwrap(),
+        );
+
+        let error = RemoteWriteSource
+            .decode_body(Bytes::new(), proto_message(&headers))
+            .unwrap_err();
+        assert_eq!(error.code(), StatusCode::UNSUPPORTED_MEDIA_TYPE.as_u16());
+    }
+
     /// According to the [spec](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md?plain=1#L115)
     /// > Label names MUST be unique within a LabelSet.
     /// Prometheus itself will reject the metric with an error. Largely to remain backward compatible with older versions of Vector,
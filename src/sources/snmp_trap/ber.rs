@@ -0,0 +1,299 @@
+use std::fmt;
+
+use snafu::Snafu;
+
+use super::usm::UsmError;
+
+pub const INTEGER: u8 = 0x02;
+pub const OCTET_STRING: u8 = 0x04;
+pub const NULL: u8 = 0x05;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+pub const IP_ADDRESS: u8 = 0x40;
+pub const COUNTER32: u8 = 0x41;
+pub const GAUGE32: u8 = 0x42;
+pub const TIME_TICKS: u8 = 0x43;
+pub const OPAQUE: u8 = 0x44;
+pub const COUNTER64: u8 = 0x46;
+pub const NO_SUCH_OBJECT: u8 = 0x80;
+pub const NO_SUCH_INSTANCE: u8 = 0x81;
+pub const END_OF_MIB_VIEW: u8 = 0x82;
+
+/// An error that occurred while decoding an SNMP message.
+#[derive(Debug, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("message truncated while reading {}", what))]
+    Truncated { what: &'static str },
+
+    #[snafu(display("expected tag {:#04x} for {}, found {:#04x}", expected, what, actual))]
+    UnexpectedTag {
+        what: &'static str,
+        expected: u8,
+        actual: u8,
+    },
+
+    #[snafu(display("invalid {}: {}", what, reason))]
+    Invalid {
+        what: &'static str,
+        reason: &'static str,
+    },
+
+    #[snafu(display("unsupported SNMP version {}", version))]
+    UnsupportedVersion { version: i64 },
+
+    #[snafu(context(false), display("{}", source))]
+    Usm { source: UsmError },
+}
+
+pub type DecodeResult<T> = Result<T, DecodeError>;
+
+/// An object identifier, such as `1.3.6.1.2.1.1.3.0`.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Oid(pub Vec<u32>);
+
+impl fmt::Display for Oid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, arc) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{arc}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads BER-encoded values, as used by SNMP, failing instead of panicking on truncated input.
+///
+/// The reader keeps track of its offset in the whole message, which USM needs to find the
+/// authentication parameters in the message it authenticates.
+#[derive(Clone, Copy, Debug)]
+pub struct BerReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BerReader<'a> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The offset of the next value within the message.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn take(&mut self, len: usize, what: &'static str) -> DecodeResult<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(DecodeError::Truncated { what });
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    pub fn peek_tag(&self, what: &'static str) -> DecodeResult<u8> {
+        self.data
+            .first()
+            .copied()
+            .ok_or(DecodeError::Truncated { what })
+    }
+
+    /// Reads the next value, returning its tag and a reader over its contents.
+    pub fn read_any(&mut self, what: &'static str) -> DecodeResult<(u8, BerReader<'a>)> {
+        let tag = self.take(1, what)?[0];
+        if tag & 0x1f == 0x1f {
+            return Err(DecodeError::Invalid {
+                what,
+                reason: "multi-byte tags are not used by SNMP",
+            });
+        }
+
+        let first = self.take(1, what)?[0];
+        let len = match first {
+            0..=0x7f => usize::from(first),
+            0x80 => {
+                return Err(DecodeError::Invalid {
+                    what,
+                    reason: "indefinite lengths are not allowed",
+                });
+            }
+            _ => {
+                let count = usize::from(first & 0x7f);
+                if count > 4 {
+                    return Err(DecodeError::Invalid {
+                        what,
+                        reason: "length is too large",
+                    });
+                }
+                self.take(count, what)?
+                    .iter()
+                    .fold(0, |len, byte| (len << 8) | usize::from(*byte))
+            }
+        };
+
+        let offset = self.offset;
+        let data = self.take(len, what)?;
+        Ok((tag, BerReader { data, offset }))
+    }
+
+    /// Reads the next value, which must have the given tag.
+    pub fn read(&mut self, tag: u8, what: &'static str) -> DecodeResult<BerReader<'a>> {
+        let (actual, contents) = self.read_any(what)?;
+        if actual != tag {
+            return Err(DecodeError::UnexpectedTag {
+                what,
+                expected: tag,
+                actual,
+            });
+        }
+        Ok(contents)
+    }
+
+    /// The remaining bytes of the reader, such as the contents of a primitive value.
+    pub const fn bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn integer(&mut self, what: &'static str) -> DecodeResult<i64> {
+        let contents = self.read(INTEGER, what)?;
+        decode_integer(contents.bytes(), what)
+    }
+
+    pub fn octet_string(&mut self, what: &'static str) -> DecodeResult<&'a [u8]> {
+        self.read(OCTET_STRING, what)
+            .map(|contents| contents.bytes())
+    }
+
+    pub fn oid(&mut self, what: &'static str) -> DecodeResult<Oid> {
+        let contents = self.read(OBJECT_IDENTIFIER, what)?;
+        decode_oid(contents.bytes(), what)
+    }
+}
+
+/// Decodes a two's complement signed integer.
+pub fn decode_integer(bytes: &[u8], what: &'static str) -> DecodeResult<i64> {
+    if bytes.is_empty() || bytes.len() > 8 {
+        return Err(DecodeError::Invalid {
+            what,
+            reason: "integer has an invalid length",
+        });
+    }
+    let initial = if bytes[0] & 0x80 == 0 { 0 } else { -1 };
+    Ok(bytes
+        .iter()
+        .fold(initial, |value, byte| (value << 8) | i64::from(*byte)))
+}
+
+/// Decodes an unsigned integer, such as a counter, which may have a leading zero byte to keep
+/// it from being negative.
+pub fn decode_unsigned(bytes: &[u8], what: &'static str) -> DecodeResult<u64> {
+    let bytes = match bytes {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        bytes => bytes,
+    };
+    if bytes.is_empty() || bytes.len() > 8 {
+        return Err(DecodeError::Invalid {
+            what,
+            reason: "unsigned integer has an invalid length",
+        });
+    }
+    Ok(bytes
+        .iter()
+        .fold(0, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
+pub fn decode_oid(bytes: &[u8], what: &'static str) -> DecodeResult<Oid> {
+    let mut arcs = Vec::with_capacity(bytes.len() + 1);
+    let mut arc: u32 = 0;
+    for (idx, byte) in bytes.iter().enumerate() {
+        arc = arc
+            .checked_mul(128)
+            .map(|arc| arc | u32::from(byte & 0x7f))
+            .ok_or(DecodeError::Invalid {
+                what,
+                reason: "object identifier arc is too large",
+            })?;
+        if byte & 0x80 != 0 {
+            if idx == bytes.len() - 1 {
+                return Err(DecodeError::Truncated { what });
+            }
+            continue;
+        }
+
+        // The first two arcs are packed into a single subidentifier.
+        if arcs.is_empty() {
+            let first = (arc / 40).min(2);
+            arcs.push(first);
+            arcs.push(arc - first * 40);
+        } else {
+            arcs.push(arc);
+        }
+        arc = 0;
+    }
+    if arcs.is_empty() {
+        return Err(DecodeError::Invalid {
+            what,
+            reason: "object identifier is empty",
+        });
+    }
+    Ok(Oid(arcs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_lengths_and_offsets() {
+        let mut data = vec![SEQUENCE, 0x81, 0x86, OCTET_STRING, 0x81, 0x80];
+        data.extend([b'x'; 0x80]);
+        data.extend([INTEGER, 1, 42]);
+
+        let mut reader = BerReader::new(&data);
+        let mut sequence = reader.read(SEQUENCE, "sequence").unwrap();
+        assert!(reader.is_empty());
+
+        assert_eq!(sequence.offset(), 3);
+        assert_eq!(sequence.octet_string("string").unwrap().len(), 0x80);
+        assert_eq!(sequence.offset(), 134);
+        assert_eq!(sequence.integer("integer").unwrap(), 42);
+        assert!(sequence.is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_values() {
+        let mut reader = BerReader::new(&[OCTET_STRING, 4, 1, 2]);
+        assert!(matches!(
+            reader.octet_string("string"),
+            Err(DecodeError::Truncated { what: "string" })
+        ));
+    }
+
+    #[test]
+    fn decodes_integers() {
+        assert_eq!(decode_integer(&[0x7f], "int").unwrap(), 127);
+        assert_eq!(decode_integer(&[0xff], "int").unwrap(), -1);
+        assert_eq!(decode_integer(&[0x00, 0x80], "int").unwrap(), 128);
+        assert_eq!(
+            decode_unsigned(&[0x00, 0xff, 0xff, 0xff, 0xff], "uint").unwrap(),
+            u64::from(u32::MAX)
+        );
+        assert_eq!(
+            decode_unsigned(&[0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], "uint").unwrap(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn decodes_oids() {
+        let oid = decode_oid(&[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x01], "oid").unwrap();
+        assert_eq!(oid.to_string(), "1.3.6.1.4.1.311.1");
+        assert!(decode_oid(&[0x2b, 0x82], "oid").is_err());
+    }
+}
@@ -0,0 +1,680 @@
+use std::net::Ipv4Addr;
+
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    ber::{self, BerReader, DecodeError, DecodeResult, Oid},
+    mib::MibTree,
+    usm::{SecurityLevel, SecurityParameters, Usm},
+};
+
+const VERSION_1: i64 = 0;
+const VERSION_2C: i64 = 1;
+const VERSION_3: i64 = 3;
+
+const RESPONSE_PDU: u8 = 0xa2;
+const TRAP_V1_PDU: u8 = 0xa4;
+const INFORM_PDU: u8 = 0xa6;
+const TRAP_V2_PDU: u8 = 0xa7;
+
+const USM_SECURITY_MODEL: i64 = 3;
+const AUTH_FLAG: u8 = 0x01;
+const PRIV_FLAG: u8 = 0x02;
+
+/// `sysUpTime.0`, the first varbind of SNMPv2 notifications.
+const SYS_UP_TIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+/// `snmpTrapOID.0`, the second varbind of SNMPv2 notifications.
+const SNMP_TRAP_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+/// `snmpTraps`, under which the generic SNMPv1 traps are defined.
+const SNMP_TRAPS: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 5];
+/// The generic trap number of enterprise-specific SNMPv1 traps.
+const ENTERPRISE_SPECIFIC: i64 = 6;
+
+/// A decoded trap or inform.
+pub struct Notification {
+    pub fields: ObjectMap,
+    /// The response to send back to the sender, for informs.
+    pub response: Option<Vec<u8>>,
+}
+
+/// Decodes SNMP notifications, authenticating and decrypting SNMPv3 messages.
+pub struct MessageDecoder {
+    usm: Usm,
+    mibs: Option<MibTree>,
+}
+
+impl MessageDecoder {
+    pub const fn new(usm: Usm, mibs: Option<MibTree>) -> Self {
+        Self { usm, mibs }
+    }
+
+    pub fn decode(&self, packet: &[u8]) -> DecodeResult<Notification> {
+        let mut message = BerReader::new(packet).read(ber::SEQUENCE, "message")?;
+        let mut fields = ObjectMap::new();
+
+        let version = message.integer("version")?;
+        match version {
+            VERSION_1 | VERSION_2C => {
+                fields.insert(
+                    "snmp_version".into(),
+                    Value::from(if version == VERSION_1 { "1" } else { "2c" }),
+                );
+                let community = message.octet_string("community")?;
+                fields.insert(
+                    "community".into(),
+                    String::from_utf8_lossy(community).into_owned().into(),
+                );
+
+                let pdu_offset = message.offset();
+                let is_inform = self.decode_pdu(&mut message, version, &mut fields)?;
+
+                // Informs are acknowledged with a response echoing the inform, which only differs
+                // from it by the type of its PDU.
+                let response = is_inform.then(|| {
+                    let mut response = packet.to_vec();
+                    response[pdu_offset] = RESPONSE_PDU;
+                    response
+                });
+                Ok(Notification { fields, response })
+            }
+            VERSION_3 => {
+                fields.insert("snmp_version".into(), Value::from("3"));
+                self.decode_v3(packet, message, &mut fields)?;
+                Ok(Notification {
+                    fields,
+                    response: None,
+                })
+            }
+            version => Err(DecodeError::UnsupportedVersion { version }),
+        }
+    }
+
+    fn decode_v3(
+        &self,
+        packet: &[u8],
+        mut message: BerReader<'_>,
+        fields: &mut ObjectMap,
+    ) -> DecodeResult<()> {
+        let mut global = message.read(ber::SEQUENCE, "global data")?;
+        global.integer("message id")?;
+        global.integer("maximum message size")?;
+        let flags = match global.octet_string("message flags")? {
+            [flags] => *flags,
+            _ => {
+                return Err(DecodeError::Invalid {
+                    what: "message flags",
+                    reason: "flags must be a single byte",
+                });
+            }
+        };
+        if global.integer("security model")? != USM_SECURITY_MODEL {
+            return Err(DecodeError::Invalid {
+                what: "security model",
+                reason: "only the user-based security model is supported",
+            });
+        }
+        let level = match (flags & AUTH_FLAG != 0, flags & PRIV_FLAG != 0) {
+            (false, false) => SecurityLevel::NoAuthNoPriv,
+            (true, false) => SecurityLevel::AuthNoPriv,
+            (true, true) => SecurityLevel::AuthPriv,
+            (false, true) => {
+                return Err(DecodeError::Invalid {
+                    what: "message flags",
+                    reason: "privacy requires authentication",
+                });
+            }
+        };
+
+        let mut security = message
+            .read(ber::OCTET_STRING, "security parameters")?
+            .read(ber::SEQUENCE, "security parameters")?;
+        let engine_id = security.octet_string("authoritative engine id")?;
+        let engine_boots = security.integer("authoritative engine boots")?;
+        let engine_time = security.integer("authoritative engine time")?;
+        let user = security.octet_string("user name")?;
+        let auth_params = security.read(ber::OCTET_STRING, "authentication parameters")?;
+        let priv_params = security.octet_string("privacy parameters")?;
+        let params = SecurityParameters {
+            engine_id,
+            engine_boots: u32::try_from(engine_boots).unwrap_or_default(),
+            engine_time: u32::try_from(engine_time).unwrap_or_default(),
+            user,
+            auth_params: (auth_params.offset(), auth_params.bytes().len()),
+            priv_params,
+        };
+
+        let decryptor = self.usm.process(packet, level, &params)?;
+
+        fields.insert("authoritative_engine_id".into(), hex(engine_id).into());
+        fields.insert(
+            "user".into(),
+            String::from_utf8_lossy(user).into_owned().into(),
+        );
+        fields.insert("security_level".into(), level.as_str().into());
+
+        match decryptor {
+            Some(decryptor) => {
+                let encrypted = message.octet_string("encrypted scoped pdu")?;
+                let decrypted = decryptor.decrypt(&params, encrypted)?;
+                // The decrypted scoped PDU may be followed by padding.
+                let mut scoped = BerReader::new(&decrypted);
+                self.decode_scoped_pdu(&mut scoped, fields)
+            }
+            None => self.decode_scoped_pdu(&mut message, fields),
+        }
+    }
+
+    fn decode_scoped_pdu(
+        &self,
+        message: &mut BerReader<'_>,
+        fields: &mut ObjectMap,
+    ) -> DecodeResult<()> {
+        let mut scoped = message.read(ber::SEQUENCE, "scoped pdu")?;
+        let context_engine_id = scoped.octet_string("context engine id")?;
+        let context_name = scoped.octet_string("context name")?;
+        fields.insert("context_engine_id".into(), hex(context_engine_id).into());
+        fields.insert(
+            "context_name".into(),
+            String::from_utf8_lossy(context_name).into_owned().into(),
+        );
+
+        // SNMPv3 informs are not acknowledged, as responding requires the receiver to act as the
+        // authoritative engine, including engine discovery.
+        self.decode_pdu(&mut scoped, VERSION_3, fields).map(|_| ())
+    }
+
+    /// Decodes a trap or inform PDU, returning whether it is an inform.
+    fn decode_pdu(
+        &self,
+        message: &mut BerReader<'_>,
+        version: i64,
+        fields: &mut ObjectMap,
+    ) -> DecodeResult<bool> {
+        let (tag, mut pdu) = message.read_any("pdu")?;
+        match tag {
+            TRAP_V1_PDU if version == VERSION_1 => {
+                fields.insert("pdu_type".into(), Value::from("trap"));
+                self.decode_v1_trap(&mut pdu, fields)?;
+                Ok(false)
+            }
+            TRAP_V2_PDU | INFORM_PDU if version != VERSION_1 => {
+                let is_inform = tag == INFORM_PDU;
+                fields.insert(
+                    "pdu_type".into(),
+                    Value::from(if is_inform { "inform" } else { "snmpv2_trap" }),
+                );
+                fields.insert("request_id".into(), Value::from(pdu.integer("request id")?));
+                pdu.integer("error status")?;
+                pdu.integer("error index")?;
+
+                let varbinds = decode_varbinds(&mut pdu)?;
+                let mut trap_oid = None;
+                for varbind in &varbinds {
+                    if varbind.oid.0 == SYS_UP_TIME {
+                        fields.insert("uptime".into(), varbind.decode_value()?);
+                    } else if varbind.oid.0 == SNMP_TRAP_OID
+                        && varbind.tag == ber::OBJECT_IDENTIFIER
+                    {
+                        trap_oid = Some(ber::decode_oid(varbind.value, "trap oid")?);
+                    }
+                }
+                self.insert_varbinds(varbinds, trap_oid, fields)?;
+                Ok(is_inform)
+            }
+            _ => Err(DecodeError::Invalid {
+                what: "pdu",
+                reason: "only traps and informs are supported",
+            }),
+        }
+    }
+
+    fn decode_v1_trap(&self, pdu: &mut BerReader<'_>, fields: &mut ObjectMap) -> DecodeResult<()> {
+        let enterprise = pdu.oid("enterprise")?;
+        let agent_address = match pdu.read(ber::IP_ADDRESS, "agent address")?.bytes() {
+            [a, b, c, d] => Ipv4Addr::new(*a, *b, *c, *d),
+            _ => {
+                return Err(DecodeError::Invalid {
+                    what: "agent address",
+                    reason: "address must be 4 bytes long",
+                });
+            }
+        };
+        let generic_trap = pdu.integer("generic trap")?;
+        let specific_trap = pdu.integer("specific trap")?;
+        let uptime = ber::decode_unsigned(
+            pdu.read(ber::TIME_TICKS, "time stamp")?.bytes(),
+            "time stamp",
+        )?;
+
+        // SNMPv1 traps are given the identifier of the equivalent SNMPv2 notification, as defined
+        // by RFC 3584, so that both can be handled the same way.
+        let mut trap_oid = Oid(SNMP_TRAPS.to_vec());
+        if generic_trap == ENTERPRISE_SPECIFIC {
+            trap_oid = enterprise.clone();
+            trap_oid.0.push(0);
+            trap_oid
+                .0
+                .push(u32::try_from(specific_trap).unwrap_or_default());
+        } else {
+            trap_oid
+                .0
+                .push(u32::try_from(generic_trap + 1).unwrap_or_default());
+        }
+
+        fields.insert("enterprise".into(), enterprise.to_string().into());
+        fields.insert("agent_address".into(), agent_address.to_string().into());
+        fields.insert("generic_trap".into(), Value::from(generic_trap));
+        fields.insert("specific_trap".into(), Value::from(specific_trap));
+        fields.insert("uptime".into(), unsigned_value(uptime));
+
+        let varbinds = decode_varbinds(pdu)?;
+        self.insert_varbinds(varbinds, Some(trap_oid), fields)
+    }
+
+    /// Inserts the varbinds as a list of objects, along with the identifier of the notification
+    /// and its resolved name.
+    fn insert_varbinds(
+        &self,
+        varbinds: Vec<Varbind<'_>>,
+        trap_oid: Option<Oid>,
+        fields: &mut ObjectMap,
+    ) -> DecodeResult<()> {
+        let resolve = |oid: &Oid| self.mibs.as_ref().and_then(|mibs| mibs.resolve(oid));
+
+        if let Some(trap_oid) = trap_oid {
+            if let Some(name) = resolve(&trap_oid) {
+                fields.insert("trap_name".into(), name.into());
+            }
+            fields.insert("trap_oid".into(), trap_oid.to_string().into());
+        }
+
+        let varbinds = varbinds
+            .into_iter()
+            .map(|varbind| {
+                let mut object = ObjectMap::new();
+                if let Some(name) = resolve(&varbind.oid) {
+                    object.insert("name".into(), name.into());
+                }
+                object.insert("oid".into(), varbind.oid.to_string().into());
+                object.insert("value".into(), varbind.decode_value()?);
+                Ok(Value::Object(object))
+            })
+            .collect::<DecodeResult<Vec<_>>>()?;
+        fields.insert("varbinds".into(), Value::Array(varbinds));
+        Ok(())
+    }
+}
+
+/// A variable binding, holding the still encoded value of a variable.
+struct Varbind<'a> {
+    oid: Oid,
+    tag: u8,
+    value: &'a [u8],
+}
+
+impl Varbind<'_> {
+    fn decode_value(&self) -> DecodeResult<Value> {
+        decode_value(self.tag, self.value)
+    }
+}
+
+fn decode_varbinds<'a>(pdu: &mut BerReader<'a>) -> DecodeResult<Vec<Varbind<'a>>> {
+    let mut list = pdu.read(ber::SEQUENCE, "varbinds")?;
+    let mut varbinds = Vec::new();
+    while !list.is_empty() {
+        let mut varbind = list.read(ber::SEQUENCE, "varbind")?;
+        let oid = varbind.oid("varbind name")?;
+        let (tag, value) = varbind.read_any("varbind value")?;
+        varbinds.push(Varbind {
+            oid,
+            tag,
+            value: value.bytes(),
+        });
+    }
+    Ok(varbinds)
+}
+
+/// Decodes the value of a varbind.
+///
+/// Octet strings are kept as strings when they hold printable UTF-8, and hex-encoded otherwise, as
+/// they are commonly used for both text and binary values such as MAC addresses.
+fn decode_value(tag: u8, bytes: &[u8]) -> DecodeResult<Value> {
+    Ok(match tag {
+        ber::INTEGER => Value::from(ber::decode_integer(bytes, "integer value")?),
+        ber::OCTET_STRING => match std::str::from_utf8(bytes) {
+            Ok(text) if !text.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+                Value::from(text)
+            }
+            _ => Value::from(hex(bytes)),
+        },
+        ber::OPAQUE => Value::from(hex(bytes)),
+        ber::OBJECT_IDENTIFIER => Value::from(ber::decode_oid(bytes, "oid value")?.to_string()),
+        ber::IP_ADDRESS => match bytes {
+            [a, b, c, d] => Value::from(Ipv4Addr::new(*a, *b, *c, *d).to_string()),
+            _ => Value::from(hex(bytes)),
+        },
+        ber::COUNTER32 | ber::GAUGE32 | ber::TIME_TICKS | ber::COUNTER64 => {
+            unsigned_value(ber::decode_unsigned(bytes, "unsigned value")?)
+        }
+        ber::NULL | ber::NO_SUCH_OBJECT | ber::NO_SUCH_INSTANCE | ber::END_OF_MIB_VIEW => {
+            Value::Null
+        }
+        _ => Value::from(hex(bytes)),
+    })
+}
+
+/// Converts an unsigned integer to a value, falling back to a float for values beyond `i64::MAX`.
+fn unsigned_value(value: u64) -> Value {
+    i64::try_from(value)
+        .map(Value::from)
+        .unwrap_or_else(|_| Value::from_f64_or_zero(value as f64))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::snmp_trap::usm::{
+        AuthProtocol, AuthenticationConfig, UserConfig, UsmError,
+    };
+
+    fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![tag];
+        match u8::try_from(contents.len()) {
+            Ok(len) if len < 0x80 => encoded.push(len),
+            _ => {
+                let len = u16::try_from(contents.len()).unwrap();
+                encoded.push(0x82);
+                encoded.extend(len.to_be_bytes());
+            }
+        }
+        encoded.extend(contents);
+        encoded
+    }
+
+    fn integer(value: u8) -> Vec<u8> {
+        tlv(ber::INTEGER, &[value])
+    }
+
+    fn oid(arcs: &[u32]) -> Vec<u8> {
+        let mut contents = Vec::new();
+        for arc in std::iter::once(arcs[0] * 40 + arcs[1]).chain(arcs[2..].iter().copied()) {
+            let mut bytes = vec![(arc & 0x7f) as u8];
+            let mut rest = arc >> 7;
+            while rest > 0 {
+                bytes.push((rest & 0x7f) as u8 | 0x80);
+                rest >>= 7;
+            }
+            contents.extend(bytes.into_iter().rev());
+        }
+        tlv(ber::OBJECT_IDENTIFIER, &contents)
+    }
+
+    fn varbind(name: &[u32], value: Vec<u8>) -> Vec<u8> {
+        tlv(ber::SEQUENCE, &[oid(name), value].concat())
+    }
+
+    const IF_DESCR_2: &[u32] = &[1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 2];
+    const LINK_DOWN: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 5, 3];
+
+    fn v2_pdu(tag: u8) -> Vec<u8> {
+        let varbinds = [
+            varbind(SYS_UP_TIME, tlv(ber::TIME_TICKS, &[0x04, 0xd2])),
+            varbind(SNMP_TRAP_OID, oid(LINK_DOWN)),
+            varbind(IF_DESCR_2, tlv(ber::OCTET_STRING, b"eth0")),
+            varbind(
+                &[1, 3, 6, 1, 4, 1, 9999, 1],
+                tlv(ber::OCTET_STRING, &[0, 0x1b, 0xff]),
+            ),
+        ]
+        .concat();
+        tlv(
+            tag,
+            &[
+                integer(42),
+                integer(0),
+                integer(0),
+                tlv(ber::SEQUENCE, &varbinds),
+            ]
+            .concat(),
+        )
+    }
+
+    fn community_message(version: u8, pdu: Vec<u8>) -> Vec<u8> {
+        tlv(
+            ber::SEQUENCE,
+            &[integer(version), tlv(ber::OCTET_STRING, b"public"), pdu].concat(),
+        )
+    }
+
+    fn v3_message(user: &[u8], flags: u8, auth_params: &[u8], pdu: Vec<u8>) -> Vec<u8> {
+        let global = tlv(
+            ber::SEQUENCE,
+            &[
+                integer(1),
+                tlv(ber::INTEGER, &[0x00, 0xff, 0xe3]),
+                tlv(ber::OCTET_STRING, &[flags]),
+                integer(3),
+            ]
+            .concat(),
+        );
+        let security = tlv(
+            ber::SEQUENCE,
+            &[
+                tlv(ber::OCTET_STRING, &[0x80, 0x00, 0x1f, 0x88, 0x04]),
+                integer(1),
+                integer(10),
+                tlv(ber::OCTET_STRING, user),
+                tlv(ber::OCTET_STRING, auth_params),
+                tlv(ber::OCTET_STRING, &[]),
+            ]
+            .concat(),
+        );
+        let scoped = tlv(
+            ber::SEQUENCE,
+            &[
+                tlv(ber::OCTET_STRING, &[0x80, 0x00, 0x1f, 0x88, 0x04]),
+                tlv(ber::OCTET_STRING, b""),
+                pdu,
+            ]
+            .concat(),
+        );
+        tlv(
+            ber::SEQUENCE,
+            &[
+                integer(3),
+                global,
+                tlv(ber::OCTET_STRING, &security),
+                scoped,
+            ]
+            .concat(),
+        )
+    }
+
+    fn decoder(users: &[UserConfig], mibs: Option<MibTree>) -> MessageDecoder {
+        MessageDecoder::new(Usm::new(users).unwrap(), mibs)
+    }
+
+    fn field<'a>(notification: &'a Notification, name: &str) -> &'a Value {
+        notification.fields.get(name).unwrap()
+    }
+
+    #[test]
+    fn decodes_v2c_traps() {
+        let notification = decoder(&[], None)
+            .decode(&community_message(1, v2_pdu(TRAP_V2_PDU)))
+            .unwrap();
+
+        assert!(notification.response.is_none());
+        assert_eq!(field(&notification, "snmp_version"), &Value::from("2c"));
+        assert_eq!(field(&notification, "community"), &Value::from("public"));
+        assert_eq!(
+            field(&notification, "pdu_type"),
+            &Value::from("snmpv2_trap")
+        );
+        assert_eq!(field(&notification, "request_id"), &Value::from(42));
+        assert_eq!(field(&notification, "uptime"), &Value::from(1234));
+        assert_eq!(
+            field(&notification, "trap_oid"),
+            &Value::from("1.3.6.1.6.3.1.1.5.3")
+        );
+        assert!(!notification.fields.contains_key("trap_name"));
+
+        let varbinds = field(&notification, "varbinds").as_array().unwrap();
+        assert_eq!(varbinds.len(), 4);
+        let descr = varbinds[2].as_object().unwrap();
+        assert_eq!(descr["oid"], Value::from("1.3.6.1.2.1.2.2.1.2.2"));
+        assert_eq!(descr["value"], Value::from("eth0"));
+        let binary = varbinds[3].as_object().unwrap();
+        assert_eq!(binary["value"], Value::from("001bff"));
+    }
+
+    #[test]
+    fn resolves_names_from_mibs() {
+        let mibs = MibTree::parse(
+            r#"
+            ifDescr OBJECT-TYPE ::= { mib-2 2 2 1 2 }
+            linkDown NOTIFICATION-TYPE ::= { snmpTraps 3 }
+            snmpTraps OBJECT IDENTIFIER ::= { 1 3 6 1 6 3 1 1 5 }
+            "#,
+        );
+        let notification = decoder(&[], Some(mibs))
+            .decode(&community_message(1, v2_pdu(TRAP_V2_PDU)))
+            .unwrap();
+
+        assert_eq!(field(&notification, "trap_name"), &Value::from("linkDown"));
+        let varbinds = field(&notification, "varbinds").as_array().unwrap();
+        assert_eq!(
+            varbinds[2].as_object().unwrap()["name"],
+            Value::from("ifDescr.2")
+        );
+    }
+
+    #[test]
+    fn acknowledges_informs() {
+        let packet = community_message(1, v2_pdu(INFORM_PDU));
+        let notification = decoder(&[], None).decode(&packet).unwrap();
+
+        assert_eq!(field(&notification, "pdu_type"), &Value::from("inform"));
+        let response = notification.response.unwrap();
+        let mut message = BerReader::new(&response)
+            .read(ber::SEQUENCE, "message")
+            .unwrap();
+        message.integer("version").unwrap();
+        message.octet_string("community").unwrap();
+        let (tag, _) = message.read_any("pdu").unwrap();
+        assert_eq!(tag, RESPONSE_PDU);
+        assert_eq!(response.len(), packet.len());
+    }
+
+    #[test]
+    fn decodes_v1_traps() {
+        let enterprise = &[1, 3, 6, 1, 4, 1, 9999];
+        let pdu = |generic_trap, specific_trap| {
+            tlv(
+                TRAP_V1_PDU,
+                &[
+                    oid(enterprise),
+                    tlv(ber::IP_ADDRESS, &[192, 0, 2, 1]),
+                    integer(generic_trap),
+                    integer(specific_trap),
+                    tlv(ber::TIME_TICKS, &[0x10]),
+                    tlv(ber::SEQUENCE, &varbind(IF_DESCR_2, integer(7))),
+                ]
+                .concat(),
+            )
+        };
+        let decoder = decoder(&[], None);
+
+        let notification = decoder.decode(&community_message(0, pdu(2, 0))).unwrap();
+        assert_eq!(field(&notification, "snmp_version"), &Value::from("1"));
+        assert_eq!(field(&notification, "pdu_type"), &Value::from("trap"));
+        assert_eq!(
+            field(&notification, "enterprise"),
+            &Value::from("1.3.6.1.4.1.9999")
+        );
+        assert_eq!(
+            field(&notification, "agent_address"),
+            &Value::from("192.0.2.1")
+        );
+        assert_eq!(field(&notification, "uptime"), &Value::from(16));
+        assert_eq!(
+            field(&notification, "trap_oid"),
+            &Value::from("1.3.6.1.6.3.1.1.5.3")
+        );
+
+        let notification = decoder.decode(&community_message(0, pdu(6, 17))).unwrap();
+        assert_eq!(
+            field(&notification, "trap_oid"),
+            &Value::from("1.3.6.1.4.1.9999.0.17")
+        );
+    }
+
+    #[test]
+    fn decodes_v3_traps() {
+        let users = [UserConfig {
+            name: "traps".to_string(),
+            authentication: None,
+            privacy: None,
+        }];
+        let notification = decoder(&users, None)
+            .decode(&v3_message(b"traps", 0, &[], v2_pdu(TRAP_V2_PDU)))
+            .unwrap();
+
+        assert_eq!(field(&notification, "snmp_version"), &Value::from("3"));
+        assert_eq!(field(&notification, "user"), &Value::from("traps"));
+        assert_eq!(
+            field(&notification, "security_level"),
+            &Value::from("no_auth_no_priv")
+        );
+        assert_eq!(
+            field(&notification, "authoritative_engine_id"),
+            &Value::from("80001f8804")
+        );
+        assert_eq!(field(&notification, "context_name"), &Value::from(""));
+        assert_eq!(
+            field(&notification, "trap_oid"),
+            &Value::from("1.3.6.1.6.3.1.1.5.3")
+        );
+    }
+
+    #[test]
+    fn rejects_unauthenticated_v3_traps() {
+        let users = [UserConfig {
+            name: "traps".to_string(),
+            authentication: Some(AuthenticationConfig {
+                protocol: AuthProtocol::Sha,
+                password: "authpassword".to_string().into(),
+            }),
+            privacy: None,
+        }];
+        let decoder = decoder(&users, None);
+
+        let forged = v3_message(b"traps", AUTH_FLAG, &[0; 12], v2_pdu(TRAP_V2_PDU));
+        assert!(matches!(
+            decoder.decode(&forged),
+            Err(DecodeError::Usm {
+                source: UsmError::AuthenticationFailed { .. }
+            })
+        ));
+
+        let downgraded = v3_message(b"traps", 0, &[], v2_pdu(TRAP_V2_PDU));
+        assert!(matches!(
+            decoder.decode(&downgraded),
+            Err(DecodeError::Usm {
+                source: UsmError::UnsupportedSecurityLevel { .. }
+            })
+        ));
+
+        let unknown = v3_message(b"other", 0, &[], v2_pdu(TRAP_V2_PDU));
+        assert!(matches!(
+            decoder.decode(&unknown),
+            Err(DecodeError::Usm {
+                source: UsmError::UnknownUser { .. }
+            })
+        ));
+    }
+}
@@ -0,0 +1,416 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use snafu::{ResultExt, Snafu};
+
+use super::ber::Oid;
+
+/// An error that occurred while loading MIB files.
+#[derive(Debug, Snafu)]
+pub enum MibError {
+    #[snafu(display("could not read MIB path {:?}: {}", path, source))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// The macros defining object identifiers in SMIv1 and SMIv2 modules.
+const OID_MACROS: &[&str] = &[
+    "OBJECT",
+    "OBJECT-TYPE",
+    "OBJECT-IDENTITY",
+    "MODULE-IDENTITY",
+    "NOTIFICATION-TYPE",
+    "OBJECT-GROUP",
+    "NOTIFICATION-GROUP",
+    "MODULE-COMPLIANCE",
+    "AGENT-CAPABILITIES",
+    "TRAP-TYPE",
+];
+
+/// The nodes defined by the SMI itself, which every MIB module builds on.
+const ROOTS: &[(&str, &[u32])] = &[
+    ("ccitt", &[0]),
+    ("iso", &[1]),
+    ("joint-iso-ccitt", &[2]),
+    ("org", &[1, 3]),
+    ("dod", &[1, 3, 6]),
+    ("internet", &[1, 3, 6, 1]),
+    ("directory", &[1, 3, 6, 1, 1]),
+    ("mgmt", &[1, 3, 6, 1, 2]),
+    ("mib-2", &[1, 3, 6, 1, 2, 1]),
+    ("transmission", &[1, 3, 6, 1, 2, 1, 10]),
+    ("experimental", &[1, 3, 6, 1, 3]),
+    ("private", &[1, 3, 6, 1, 4]),
+    ("enterprises", &[1, 3, 6, 1, 4, 1]),
+    ("security", &[1, 3, 6, 1, 5]),
+    ("snmpV2", &[1, 3, 6, 1, 6]),
+    ("snmpDomains", &[1, 3, 6, 1, 6, 1]),
+    ("snmpProxys", &[1, 3, 6, 1, 6, 2]),
+    ("snmpModules", &[1, 3, 6, 1, 6, 3]),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+enum Component {
+    Number(u32),
+    Name(String, Option<u32>),
+}
+
+#[derive(Debug)]
+struct Definition {
+    name: String,
+    components: Vec<Component>,
+}
+
+/// Resolves object identifiers to the names defined in MIB modules.
+///
+/// Only the object identifier assignments of the modules are parsed, without validating the rest
+/// of their syntax. Names are global rather than scoped to their module, as it is exceedingly rare
+/// for two modules to give different values to the same name.
+#[derive(Debug, Default)]
+pub struct MibTree {
+    names: HashMap<Vec<u32>, String>,
+}
+
+impl MibTree {
+    /// Loads every MIB module in the given files, or directories of files.
+    pub fn load(paths: &[PathBuf]) -> Result<Self, MibError> {
+        let mut definitions = Vec::new();
+        for path in paths {
+            let metadata = fs::metadata(path).context(ReadSnafu { path })?;
+            if metadata.is_dir() {
+                let mut files = fs::read_dir(path)
+                    .context(ReadSnafu { path })?
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()
+                    .context(ReadSnafu { path })?;
+                files.sort();
+                for file in files.iter().filter(|file| file.is_file()) {
+                    definitions.extend(parse_file(file)?);
+                }
+            } else {
+                definitions.extend(parse_file(path)?);
+            }
+        }
+        Ok(Self::from_definitions(definitions))
+    }
+
+    #[cfg(test)]
+    pub fn parse(source: &str) -> Self {
+        Self::from_definitions(parse(source))
+    }
+
+    fn from_definitions(definitions: Vec<Definition>) -> Self {
+        let mut oids: HashMap<String, Vec<u32>> = ROOTS
+            .iter()
+            .map(|(name, oid)| ((*name).to_string(), oid.to_vec()))
+            .collect();
+
+        // Definitions may refer to names defined later on, or in other modules, so they are
+        // resolved until no more progress can be made.
+        let mut pending = definitions;
+        loop {
+            let before = pending.len();
+            pending.retain(|definition| !resolve(definition, &mut oids));
+            if pending.is_empty() || pending.len() == before {
+                break;
+            }
+        }
+        if !pending.is_empty() {
+            warn!(
+                message = "Some MIB definitions refer to unknown names and were ignored.",
+                count = pending.len(),
+            );
+        }
+
+        let mut names = HashMap::with_capacity(oids.len());
+        for (name, oid) in oids {
+            names
+                .entry(oid)
+                .and_modify(|existing: &mut String| {
+                    // Keep the result deterministic when several names share an identifier.
+                    if name < *existing {
+                        existing.clone_from(&name);
+                    }
+                })
+                .or_insert(name);
+        }
+        Self { names }
+    }
+
+    /// Resolves an object identifier to the name of its closest known ancestor, followed by the
+    /// remaining arcs, such as `ifDescr.3`.
+    pub fn resolve(&self, oid: &Oid) -> Option<String> {
+        (1..=oid.0.len()).rev().find_map(|len| {
+            let name = self.names.get(&oid.0[..len])?;
+            let mut resolved = name.clone();
+            for arc in &oid.0[len..] {
+                resolved.push('.');
+                resolved.push_str(&arc.to_string());
+            }
+            Some(resolved)
+        })
+    }
+}
+
+/// Resolves a definition if the name it builds on is known, returning whether it was resolved.
+fn resolve(definition: &Definition, oids: &mut HashMap<String, Vec<u32>>) -> bool {
+    let mut oid = match definition.components.first() {
+        Some(Component::Number(arc) | Component::Name(_, Some(arc))) => vec![*arc],
+        Some(Component::Name(parent, None)) => match oids.get(parent) {
+            Some(oid) => oid.clone(),
+            None => return false,
+        },
+        None => return true,
+    };
+    if let Some(Component::Name(name, Some(_))) = definition.components.first() {
+        oids.entry(name.clone()).or_insert_with(|| oid.clone());
+    }
+
+    for component in &definition.components[1..] {
+        match component {
+            Component::Number(arc) => oid.push(*arc),
+            Component::Name(name, Some(arc)) => {
+                oid.push(*arc);
+                oids.entry(name.clone()).or_insert_with(|| oid.clone());
+            }
+            // Names without a number can only refer to the parent.
+            Component::Name(_, None) => return true,
+        }
+    }
+    oids.entry(definition.name.clone()).or_insert(oid);
+    true
+}
+
+fn parse_file(path: &Path) -> Result<Vec<Definition>, MibError> {
+    let bytes = fs::read(path).context(ReadSnafu { path })?;
+    Ok(parse(&String::from_utf8_lossy(&bytes)))
+}
+
+fn parse(source: &str) -> Vec<Definition> {
+    let tokens = tokenize(source);
+    let mut definitions = Vec::new();
+    // The name of the definition being parsed, along with the macro defining it.
+    let mut current: Option<(&str, &str)> = None;
+    let mut enterprise = None;
+
+    let mut idx = 0;
+    while idx < tokens.len() {
+        let token = tokens[idx];
+        let next = tokens.get(idx + 1).copied();
+
+        if starts_lowercase(token)
+            && let Some(next) = next
+            && OID_MACROS.contains(&next)
+            && (next != "OBJECT" || tokens.get(idx + 2) == Some(&"IDENTIFIER"))
+        {
+            current = Some((token, next));
+            enterprise = None;
+            idx += 2;
+            continue;
+        }
+
+        match (token, next) {
+            ("ENTERPRISE", Some(name)) if current.is_some() => {
+                enterprise = Some(name);
+                idx += 2;
+            }
+            ("::=", Some("{")) => {
+                let end = tokens[idx..]
+                    .iter()
+                    .position(|token| *token == "}")
+                    .map_or(tokens.len(), |end| idx + end);
+                if let Some((name, _)) = current.take() {
+                    let components = parse_components(&tokens[idx + 2..end]);
+                    if !components.is_empty() {
+                        definitions.push(Definition {
+                            name: name.to_string(),
+                            components,
+                        });
+                    }
+                }
+                idx = end + 1;
+            }
+            // SMIv1 traps are numbered within their enterprise, and correspond to the SMIv2
+            // notification `<enterprise>.0.<number>`.
+            ("::=", Some(number)) => {
+                if let (Some((name, "TRAP-TYPE")), Some(enterprise), Ok(number)) =
+                    (current.take(), enterprise.take(), number.parse())
+                {
+                    definitions.push(Definition {
+                        name: name.to_string(),
+                        components: vec![
+                            Component::Name(enterprise.to_string(), None),
+                            Component::Number(0),
+                            Component::Number(number),
+                        ],
+                    });
+                }
+                idx += 2;
+            }
+            _ => idx += 1,
+        }
+    }
+    definitions
+}
+
+fn parse_components(tokens: &[&str]) -> Vec<Component> {
+    let mut components = Vec::new();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        let token = tokens[idx];
+        if let Ok(number) = token.parse() {
+            components.push(Component::Number(number));
+            idx += 1;
+        } else if let ["(", number, ")", ..] = &tokens[idx + 1..] {
+            components.push(Component::Name(token.to_string(), number.parse().ok()));
+            idx += 4;
+        } else {
+            components.push(Component::Name(token.to_string(), None));
+            idx += 1;
+        }
+    }
+    components
+}
+
+fn starts_lowercase(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_lowercase())
+}
+
+/// Splits a MIB module into tokens, dropping comments and quoted strings.
+fn tokenize(source: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if let Some(comment) = rest.strip_prefix("--") {
+            // Comments end at the end of the line, or at the next `--`.
+            let end = comment
+                .find(['\n', '\r'])
+                .unwrap_or(comment.len())
+                .min(comment.find("--").map_or(usize::MAX, |end| end + 2));
+            rest = &comment[end..];
+        } else if c == '"' {
+            let end = rest[1..].find('"').map_or(rest.len(), |end| end + 2);
+            rest = &rest[end..];
+        } else if let Some(after) = rest.strip_prefix("::=") {
+            tokens.push("::=");
+            rest = after;
+        } else if c.is_ascii_alphanumeric() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(&rest[..end]);
+            rest = &rest[end..];
+        } else {
+            tokens.push(&rest[..c.len_utf8()]);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: &str = r#"
+IF-MIB DEFINITIONS ::= BEGIN
+
+IMPORTS
+    MODULE-IDENTITY, OBJECT-TYPE, NOTIFICATION-TYPE, mib-2
+        FROM SNMPv2-SMI;
+
+ifMIB MODULE-IDENTITY
+    LAST-UPDATED "200006140000Z"
+    DESCRIPTION "The MIB module to describe generic objects ::= { 1 2 } -- not a comment"
+    ::= { mib-2 31 }
+
+-- the interfaces table ::= { mib-2 99 }
+interfaces   OBJECT IDENTIFIER ::= { mib-2 2 }
+
+ifTable OBJECT-TYPE
+    SYNTAX      SEQUENCE OF IfEntry
+    MAX-ACCESS  not-accessible
+    ::= { interfaces 2 }
+
+ifEntry OBJECT-TYPE
+    SYNTAX      IfEntry
+    INDEX   { ifIndex }
+    ::= { ifTable 1 }
+
+IfEntry ::= SEQUENCE { ifIndex InterfaceIndex, ifDescr DisplayString }
+
+ifDescr OBJECT-TYPE
+    SYNTAX      DisplayString (SIZE (0..255))
+    ::= { ifEntry 2 }
+
+ifAdminStatus OBJECT-TYPE
+    SYNTAX  INTEGER { up(1), down(2), testing(3) }
+    DEFVAL { up }
+    ::= { ifEntry 7 }
+
+linkDown NOTIFICATION-TYPE
+    OBJECTS { ifIndex, ifAdminStatus, ifOperStatus }
+    ::= { snmpTraps 3 }
+
+snmpTraps OBJECT IDENTIFIER ::= { iso org(3) dod(6) internet(1) snmpV2(6) 3 1 1 5 }
+
+acmeFanFailure TRAP-TYPE
+    ENTERPRISE acme
+    VARIABLES { ifIndex }
+    ::= 4
+
+acme OBJECT IDENTIFIER ::= { enterprises 9999 }
+
+END
+"#;
+
+    fn resolve(tree: &MibTree, oid: &[u32]) -> Option<String> {
+        tree.resolve(&Oid(oid.to_vec()))
+    }
+
+    #[test]
+    fn resolves_definitions() {
+        let tree = MibTree::parse(MIB);
+
+        assert_eq!(
+            resolve(&tree, &[1, 3, 6, 1, 2, 1, 31]).as_deref(),
+            Some("ifMIB")
+        );
+        assert_eq!(
+            resolve(&tree, &[1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 3]).as_deref(),
+            Some("ifDescr.3")
+        );
+        assert_eq!(
+            resolve(&tree, &[1, 3, 6, 1, 2, 1, 2, 2, 1, 7, 3]).as_deref(),
+            Some("ifAdminStatus.3")
+        );
+        assert_eq!(
+            resolve(&tree, &[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]).as_deref(),
+            Some("linkDown")
+        );
+        assert_eq!(
+            resolve(&tree, &[1, 3, 6, 1, 4, 1, 9999, 0, 4]).as_deref(),
+            Some("acmeFanFailure")
+        );
+        assert_eq!(
+            resolve(&tree, &[1, 3, 6, 1, 2, 1, 99]).as_deref(),
+            Some("mib-2.99")
+        );
+        assert_eq!(resolve(&tree, &[3, 1]), None);
+    }
+
+    #[test]
+    fn skips_comments_and_strings() {
+        assert_eq!(
+            tokenize("a -- comment -- b \"quoted ::= { }\" c -- trailing\nd"),
+            ["a", "b", "c", "d"]
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+use bytes::BytesMut;
+use chrono::Utc;
+use listenfd::ListenFd;
+use vector_lib::config::{LegacyKey, LogNamespace, log_schema};
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
+use vector_lib::{EstimatedJsonEncodedSizeOf, schema::Definition};
+use vrl::value::{Kind, kind::Collection};
+
+use crate::{
+    SourceSender,
+    config::{DataType, GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    event::{Event, LogEvent},
+    internal_events::{
+        EventsReceived, SnmpTrapDecodeError, SocketBindError, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    net,
+    shutdown::ShutdownSignal,
+    sources::{
+        Source,
+        util::net::{SocketListenAddr, try_bind_udp_socket},
+    },
+};
+
+mod ber;
+mod message;
+mod mib;
+mod usm;
+
+use message::MessageDecoder;
+use mib::MibTree;
+use usm::Usm;
+pub use usm::{AuthProtocol, AuthenticationConfig, PrivacyConfig, PrivacyProtocol, UserConfig};
+
+/// Configuration for the `snmp_trap` source.
+#[configurable_component(source(
+    "snmp_trap",
+    "Receive SNMPv1, SNMPv2c and SNMPv3 traps and informs."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpTrapConfig {
+    /// The address to listen for traps on.
+    #[configurable(metadata(docs::examples = "0.0.0.0:162"))]
+    #[configurable(metadata(docs::examples = "systemd"))]
+    #[configurable(metadata(docs::examples = "systemd#3"))]
+    address: SocketListenAddr,
+
+    /// The maximum size of incoming messages.
+    ///
+    /// Messages larger than this are truncated, and fail to decode.
+    #[serde(default = "default_max_length")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    max_length: usize,
+
+    /// The size of the receive buffer used for the listening socket.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    /// The SNMPv3 users to accept traps from.
+    ///
+    /// SNMPv3 traps from any other user are rejected. SNMPv1 and SNMPv2c traps are accepted
+    /// regardless of their community.
+    #[serde(default)]
+    users: Vec<UserConfig>,
+
+    /// A list of MIB files, or directories of MIB files, used to resolve object identifiers to
+    /// names.
+    ///
+    /// When set, events include the resolved name of the trap, and of each of its variables.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/usr/share/snmp/mibs"))]
+    mib_paths: Vec<PathBuf>,
+
+    /// Overrides the name of the log field used to add the sender's address to each event.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// Set to `""` to suppress this key.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    host_key: Option<OptionalValuePath>,
+
+    /// Overrides the name of the log field used to add the sender's port to each event.
+    ///
+    /// By default, `"port"` is used.
+    ///
+    /// Set to `""` to suppress this key.
+    #[serde(default = "default_port_key")]
+    port_key: OptionalValuePath,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+const fn default_max_length() -> usize {
+    // The largest possible UDP payload, as SNMP messages are never split.
+    65_535
+}
+
+fn default_host_key() -> OptionalValuePath {
+    log_schema().host_key().cloned().into()
+}
+
+fn default_port_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("port"))
+}
+
+impl SnmpTrapConfig {
+    pub fn from_address(address: SocketListenAddr) -> Self {
+        Self {
+            address,
+            max_length: default_max_length(),
+            receive_buffer_bytes: None,
+            users: Vec::new(),
+            mib_paths: Vec::new(),
+            host_key: None,
+            port_key: default_port_key(),
+            log_namespace: None,
+        }
+    }
+
+    fn host_key(&self) -> OptionalValuePath {
+        self.host_key.clone().unwrap_or_else(default_host_key)
+    }
+
+    fn build_decoder(&self) -> crate::Result<MessageDecoder> {
+        let usm = Usm::new(&self.users)?;
+        let mibs = if self.mib_paths.is_empty() {
+            None
+        } else {
+            Some(MibTree::load(&self.mib_paths)?)
+        };
+        Ok(MessageDecoder::new(usm, mibs))
+    }
+}
+
+impl GenerateConfig for SnmpTrapConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::from_address(SocketListenAddr::SocketAddr(
+            SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 162),
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "snmp_trap")]
+impl SourceConfig for SnmpTrapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        // Deriving the keys of SNMPv3 users and parsing MIB files may take a while.
+        let config = self.clone();
+        let decoder = tokio::task::spawn_blocking(move || config.build_decoder()).await??;
+        Ok(snmp_trap_udp(
+            self.clone(),
+            decoder,
+            log_namespace,
+            cx.shutdown,
+            cx.out,
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let legacy_host_key = self.host_key().path.map(LegacyKey::InsertIfEmpty);
+        let legacy_port_key = self.port_key.clone().path.map(LegacyKey::InsertIfEmpty);
+
+        let schema_definition =
+            Definition::new_with_default_metadata(Kind::object(Collection::any()), [log_namespace])
+                .with_standard_vector_source_metadata()
+                .with_source_metadata(
+                    Self::NAME,
+                    legacy_host_key,
+                    &owned_value_path!("host"),
+                    Kind::bytes(),
+                    Some("host"),
+                )
+                .with_source_metadata(
+                    Self::NAME,
+                    legacy_port_key,
+                    &owned_value_path!("port"),
+                    Kind::integer(),
+                    None,
+                );
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+fn snmp_trap_udp(
+    config: SnmpTrapConfig,
+    decoder: MessageDecoder,
+    log_namespace: LogNamespace,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Source {
+    Box::pin(async move {
+        let listenfd = ListenFd::from_env();
+        let socket = try_bind_udp_socket(config.address, listenfd)
+            .await
+            .map_err(|error| {
+                emit!(SocketBindError {
+                    mode: SocketMode::Udp,
+                    error,
+                })
+            })?;
+
+        if let Some(receive_buffer_bytes) = config.receive_buffer_bytes
+            && let Err(error) = net::set_receive_buffer_size(&socket, receive_buffer_bytes)
+        {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+
+        let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+        let events_received = register!(EventsReceived);
+        let legacy_host_key = config.host_key().path;
+        let legacy_port_key = config.port_key.clone().path;
+
+        info!(message = "Listening.", address = %config.address);
+        let mut buf = BytesMut::with_capacity(config.max_length);
+        loop {
+            buf.resize(config.max_length, 0);
+            tokio::select! {
+                recv = socket.recv_from(&mut buf) => {
+                    let (byte_size, address) = recv.map_err(|error| {
+                        emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error,
+                        })
+                    })?;
+                    bytes_received.emit(ByteSize(byte_size));
+
+                    let notification = match decoder.decode(&buf[..byte_size]) {
+                        Ok(notification) => notification,
+                        Err(error) => {
+                            emit!(SnmpTrapDecodeError {
+                                error,
+                                peer: address,
+                            });
+                            continue;
+                        }
+                    };
+
+                    if let Some(response) = notification.response
+                        && let Err(error) = socket.send_to(&response, address).await
+                    {
+                        warn!(
+                            message = "Failed acknowledging inform.",
+                            %error,
+                            peer = %address,
+                            internal_log_rate_limit = true,
+                        );
+                    }
+
+                    let mut log = LogEvent::from(notification.fields);
+                    log_namespace.insert_standard_vector_source_metadata(
+                        &mut log,
+                        SnmpTrapConfig::NAME,
+                        Utc::now(),
+                    );
+                    log_namespace.insert_source_metadata(
+                        SnmpTrapConfig::NAME,
+                        &mut log,
+                        legacy_host_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                        path!("host"),
+                        address.ip().to_string(),
+                    );
+                    log_namespace.insert_source_metadata(
+                        SnmpTrapConfig::NAME,
+                        &mut log,
+                        legacy_port_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                        path!("port"),
+                        address.port(),
+                    );
+                    let event = Event::from(log);
+                    events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
+
+                    tokio::select! {
+                        result = out.send_event(event) => {
+                            if result.is_err() {
+                                emit!(StreamClosedError { count: 1 });
+                                return Ok(());
+                            }
+                        }
+                        _ = &mut shutdown => return Ok(()),
+                    }
+                }
+                _ = &mut shutdown => return Ok(()),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        net::UdpSocket,
+        time::{Duration, sleep, timeout},
+    };
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{SOCKET_PUSH_SOURCE_TAGS, assert_source_compliance},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpTrapConfig>();
+    }
+
+    /// An SNMPv2c `linkDown` inform from the `public` community.
+    fn inform_packet() -> Vec<u8> {
+        let mut packet = vec![0x30, 0x31, 0x02, 0x01, 0x01, 0x04, 0x06];
+        packet.extend(b"public");
+        packet.extend([
+            0xa6, 0x24, 0x02, 0x01, 0x07, 0x02, 0x01, 0x00, 0x02, 0x01, 0x00,
+        ]);
+        packet.extend([0x30, 0x19, 0x30, 0x17]);
+        packet.extend([
+            0x06, 0x0a, 0x2b, 0x06, 0x01, 0x06, 0x03, 0x01, 0x01, 0x04, 0x01, 0x00,
+        ]);
+        packet.extend([
+            0x06, 0x09, 0x2b, 0x06, 0x01, 0x06, 0x03, 0x01, 0x01, 0x05, 0x03,
+        ]);
+        packet
+    }
+
+    #[tokio::test]
+    async fn receives_and_acknowledges_informs() {
+        assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let in_addr = next_addr();
+            let (tx, rx) = SourceSender::new_test();
+            let source = SnmpTrapConfig::from_address(in_addr.into())
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind(next_addr()).await.unwrap();
+            // Messages which fail to decode are reported, and do not stop the source.
+            socket.send_to(&[0x30, 0x01], in_addr).await.unwrap();
+            socket.send_to(&inform_packet(), in_addr).await.unwrap();
+
+            let events = collect_n(rx, 1).await;
+            let log = events[0].as_log();
+            assert_eq!(log["snmp_version"], "2c".into());
+            assert_eq!(log["community"], "public".into());
+            assert_eq!(log["pdu_type"], "inform".into());
+            assert_eq!(log["request_id"], 7.into());
+            assert_eq!(log["trap_oid"], "1.3.6.1.6.3.1.1.5.3".into());
+            assert_eq!(
+                log["port"],
+                i64::from(socket.local_addr().unwrap().port()).into()
+            );
+            assert_eq!(
+                *log.get_host().unwrap(),
+                socket.local_addr().unwrap().ip().to_string().into()
+            );
+
+            let mut response = [0; 64];
+            let (len, _) = timeout(Duration::from_secs(1), socket.recv_from(&mut response))
+                .await
+                .unwrap()
+                .unwrap();
+            let mut expected = inform_packet();
+            expected[13] = 0xa2;
+            assert_eq!(&response[..len], &expected[..]);
+        })
+        .await;
+    }
+}
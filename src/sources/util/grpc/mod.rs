@@ -1,7 +1,7 @@
 use crate::{
     internal_events::{GrpcServerRequestReceived, GrpcServerResponseSent},
     shutdown::{ShutdownSignal, ShutdownSignalToken},
-    tls::MaybeTlsSettings,
+    tls::{MaybeTlsListener, MaybeTlsSettings},
 };
 use futures::FutureExt;
 use http::{Request, Response};
@@ -25,6 +25,25 @@ pub async fn run_grpc_server<S>(
     service: S,
     shutdown: ShutdownSignal,
 ) -> crate::Result<()>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + NamedService
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    let listener = tls_settings.bind(&address).await?;
+    run_grpc_server_with_listener(listener, service, shutdown).await
+}
+
+/// Runs a gRPC server on a listener which was bound beforehand, such as while building a
+/// component so that a failure to bind is reported at that point.
+pub async fn run_grpc_server_with_listener<S>(
+    listener: MaybeTlsListener,
+    service: S,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
 where
     S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
         + NamedService
@@ -35,7 +54,7 @@ where
 {
     let span = Span::current();
     let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
-    let listener = tls_settings.bind(&address).await?;
+    let address = listener.local_addr()?;
     let stream = listener.accept_stream();
 
     info!(%address, "Building gRPC server.");
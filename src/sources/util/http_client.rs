@@ -12,7 +12,7 @@
 #![cfg_attr(feature = "sources-okta", allow(dead_code))]
 
 use bytes::Bytes;
-use futures_util::{FutureExt, StreamExt, TryFutureExt, stream};
+use futures_util::{FutureExt, StreamExt, stream};
 use http::{Uri, response::Parts};
 use hyper::{Body, Request};
 use std::time::Duration;
@@ -137,6 +137,92 @@ pub(crate) fn warn_if_interval_too_low(timeout: Duration, interval: Duration) {
     }
 }
 
+/// Builds a request to `url` with the headers and authentication configured for the source.
+pub(crate) fn build_request(
+    url: &Uri,
+    http_method: HttpMethod,
+    headers: &HashMap<String, Vec<String>>,
+    content_type: &str,
+    auth: Option<&Auth>,
+) -> Request<Body> {
+    let mut builder = match http_method {
+        HttpMethod::Head => Request::head(url),
+        HttpMethod::Get => Request::get(url),
+        HttpMethod::Post => Request::post(url),
+        HttpMethod::Put => Request::put(url),
+        HttpMethod::Patch => Request::patch(url),
+        HttpMethod::Delete => Request::delete(url),
+        HttpMethod::Options => Request::options(url),
+    };
+
+    // add user specified headers
+    for (header, values) in headers {
+        for value in values {
+            builder = builder.header(header, value);
+        }
+    }
+
+    // set ACCEPT header if not user specified
+    if !headers.contains_key(http::header::ACCEPT.as_str()) {
+        builder = builder.header(http::header::ACCEPT, content_type);
+    }
+
+    // building an empty request should be infallible
+    let mut request = builder.body(Body::empty()).expect("error creating request");
+
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    request
+}
+
+/// Sends a request and reads the body of its response, failing if it takes longer than `timeout`.
+pub(crate) async fn send_request(
+    client: &HttpClient,
+    request: Request<Body>,
+    timeout: Duration,
+    endpoint: &str,
+) -> crate::Result<(Parts, Bytes)> {
+    let response = match tokio::time::timeout(timeout, client.send(request)).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(error)) => return Err(error.into()),
+        Err(_) => {
+            return Err(
+                format!("Timeout error: request exceeded {}s", timeout.as_secs_f64()).into(),
+            );
+        }
+    };
+
+    let (header, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body).await?;
+    emit!(EndpointBytesReceived {
+        byte_size: body.len(),
+        protocol: "http",
+        endpoint,
+    });
+    Ok((header, body))
+}
+
+/// Reports the events decoded from the response to a request to `url`.
+pub(crate) fn emit_events_received(url: &Uri, events: &Vec<Event>) {
+    let byte_size = if events.is_empty() {
+        // We need to explicitly set the byte size to 0 since
+        // `estimated_json_encoded_size_of` returns at least 1 for an empty
+        // collection. For the purposes of the HttpClientEventsReceived event,
+        // we should emit 0 when there aren't any usable metrics.
+        JsonSize::zero()
+    } else {
+        events.estimated_json_encoded_size_of()
+    };
+
+    emit!(HttpClientEventsReceived {
+        byte_size,
+        count: events.len(),
+        url: url.to_string()
+    });
+}
+
 /// Calls one or more urls at an interval.
 ///   - The HTTP request is built per the options in provided generic inputs.
 ///   - The HTTP response is decoded/parsed into events by the specific context.
@@ -168,81 +254,22 @@ pub(crate) async fn call<
             // Check if we need to process the URL dynamically (for updating VRL expressions)
             let url = context.process_url(&base_url).unwrap_or(base_url);
 
-            let mut builder = match http_method {
-                HttpMethod::Head => Request::head(&url),
-                HttpMethod::Get => Request::get(&url),
-                HttpMethod::Post => Request::post(&url),
-                HttpMethod::Put => Request::put(&url),
-                HttpMethod::Patch => Request::patch(&url),
-                HttpMethod::Delete => Request::delete(&url),
-                HttpMethod::Options => Request::options(&url),
-            };
-
-            // add user specified headers
-            for (header, values) in &inputs.headers {
-                for value in values {
-                    builder = builder.header(header, value);
-                }
-            }
-
-            // set ACCEPT header if not user specified
-            if !inputs.headers.contains_key(http::header::ACCEPT.as_str()) {
-                builder = builder.header(http::header::ACCEPT, &inputs.content_type);
-            }
-
-            // building an empty request should be infallible
-            let mut request = builder.body(Body::empty()).expect("error creating request");
+            let request = build_request(
+                &url,
+                http_method,
+                &inputs.headers,
+                &inputs.content_type,
+                inputs.auth.as_ref(),
+            );
+            let timeout = inputs.timeout;
 
-            if let Some(auth) = &inputs.auth {
-                auth.apply(&mut request);
-            }
-
-            tokio::time::timeout(inputs.timeout, client.send(request))
-                .then(move |result| async move {
-                    match result {
-                        Ok(Ok(response)) => Ok(response),
-                        Ok(Err(error)) => Err(error.into()),
-                        Err(_) => Err(format!(
-                            "Timeout error: request exceeded {}s",
-                            inputs.timeout.as_secs_f64()
-                        )
-                        .into()),
-                    }
-                })
-                .and_then(|response| async move {
-                    let (header, body) = response.into_parts();
-                    let body = hyper::body::to_bytes(body).await?;
-                    emit!(EndpointBytesReceived {
-                        byte_size: body.len(),
-                        protocol: "http",
-                        endpoint: endpoint.as_str(),
-                    });
-                    Ok((header, body))
-                })
+            async move { send_request(&client, request, timeout, &endpoint).await }
                 .into_stream()
                 .filter_map(move |response| {
                     ready(match response {
                         Ok((header, body)) if header.status == hyper::StatusCode::OK => {
                             context.on_response(&url, &header, &body).map(|mut events| {
-                                let byte_size = if events.is_empty() {
-                                    // We need to explicitly set the byte size
-                                    // to 0 since
-                                    // `estimated_json_encoded_size_of` returns
-                                    // at least 1 for an empty collection. For
-                                    // the purposes of the
-                                    // HttpClientEventsReceived event, we should
-                                    // emit 0 when there aren't any usable
-                                    // metrics.
-                                    JsonSize::zero()
-                                } else {
-                                    events.estimated_json_encoded_size_of()
-                                };
-
-                                emit!(HttpClientEventsReceived {
-                                    byte_size,
-                                    count: events.len(),
-                                    url: url.to_string()
-                                });
+                                emit_events_received(&url, &events);
 
                                 // We'll enrich after receiving the events so
                                 // that the byte sizes are accurate.
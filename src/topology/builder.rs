@@ -580,6 +580,7 @@ impl<'a> Builder<'a> {
                         match sink.buffer.stages().first().expect("cant ever be empty") {
                             BufferType::Memory { .. } => "memory",
                             BufferType::DiskV2 { .. } => "disk",
+                            BufferType::MemorySpill { .. } => "memory_spill",
                         };
                     let buffer_span = error_span!("sink", buffer_type);
                     let buffer = sink
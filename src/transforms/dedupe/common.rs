@@ -1,4 +1,4 @@
-use std::{num::NonZeroUsize, time::Duration};
+use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
 
 use serde_with::serde_as;
 use vector_lib::{configurable::configurable_component, lookup::lookup_v2::ConfigTargetPath};
@@ -29,6 +29,42 @@ pub struct TimedCacheConfig {
     pub refresh_on_drop: bool,
 }
 
+/// Configuration for persisting the deduplication cache across restarts.
+///
+/// When enabled, a snapshot of the cache is written periodically, as well as when the transform
+/// shuts down, and the last snapshot written is restored when the transform is built. This avoids
+/// forwarding duplicates of events that were already forwarded before a restart or reload, such as
+/// those redelivered by sources with at-least-once delivery.
+///
+/// Snapshots are discarded when the field matching configuration has changed since they were
+/// written.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PersistenceConfig {
+    /// The directory used to persist the deduplication cache.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+
+    /// The interval between snapshots of the deduplication cache.
+    #[serde(default = "default_snapshot_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Snapshot Interval"))]
+    pub snapshot_interval_secs: Duration,
+}
+
+const fn default_snapshot_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
 pub const fn default_cache_config() -> CacheConfig {
     CacheConfig {
         num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
@@ -51,7 +87,7 @@ pub const fn default_cache_config() -> CacheConfig {
 // need to emit something in that output to indicate as much, and further, actually use it on the
 // Cue side to add some sort of boilerplate about them being mutually exclusive, etc.
 #[configurable_component]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub enum FieldMatchConfig {
     /// Matches events using only the specified fields.
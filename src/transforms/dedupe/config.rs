@@ -14,9 +14,10 @@ use crate::{
 
 use super::{
     common::{
-        CacheConfig, FieldMatchConfig, TimedCacheConfig, default_cache_config,
+        CacheConfig, FieldMatchConfig, PersistenceConfig, TimedCacheConfig, default_cache_config,
         fill_default_fields_match,
     },
+    persistence::CachePersistence,
     timed_transform::TimedDedupe,
     transform::Dedupe,
 };
@@ -37,6 +38,10 @@ pub struct DedupeConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub time_settings: Option<TimedCacheConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub persistence: Option<PersistenceConfig>,
 }
 
 impl GenerateConfig for DedupeConfig {
@@ -45,6 +50,7 @@ impl GenerateConfig for DedupeConfig {
             fields: None,
             cache: default_cache_config(),
             time_settings: None,
+            persistence: None,
         })
         .unwrap()
     }
@@ -53,18 +59,32 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let persistence = self
+            .persistence
+            .as_ref()
+            .map(|config| CachePersistence::from_config(config, context))
+            .transpose()?;
+
         if let Some(time_config) = &self.time_settings {
-            Ok(Transform::event_task(TimedDedupe::new(
+            let mut dedupe = TimedDedupe::new(
                 self.cache.num_events,
                 fill_default_fields_match(self.fields.as_ref()),
                 time_config.clone(),
-            )))
+            );
+            if let Some(persistence) = persistence {
+                dedupe = dedupe.with_persistence(persistence);
+            }
+            Ok(Transform::event_task(dedupe))
         } else {
-            Ok(Transform::event_task(Dedupe::new(
+            let mut dedupe = Dedupe::new(
                 self.cache.num_events,
                 fill_default_fields_match(self.fields.as_ref()),
-            )))
+            );
+            if let Some(persistence) = persistence {
+                dedupe = dedupe.with_persistence(persistence);
+            }
+            Ok(Transform::event_task(dedupe))
         }
     }
 
@@ -90,17 +110,20 @@ mod tests {
     use std::sync::Arc;
     use std::time::Duration;
 
+    use futures::{StreamExt, stream};
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
     use vector_lib::config::ComponentKey;
+    use vector_lib::config::GlobalOptions;
     use vector_lib::config::OutputId;
     use vector_lib::lookup::lookup_v2::ConfigTargetPath;
 
     use crate::config::schema::Definition;
-    use crate::transforms::dedupe::common::TimedCacheConfig;
+    use crate::config::{TransformConfig, TransformContext};
+    use crate::transforms::dedupe::common::{PersistenceConfig, TimedCacheConfig};
     use crate::{
         event::{Event, LogEvent, ObjectMap, Value},
-        test_util::components::assert_transform_compliance,
+        test_util::{components::assert_transform_compliance, temp_dir},
         transforms::{
             dedupe::config::{CacheConfig, DedupeConfig, FieldMatchConfig},
             test::create_topology,
@@ -122,6 +145,7 @@ mod tests {
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
             time_settings: None,
+            persistence: None,
         }
     }
 
@@ -139,6 +163,7 @@ mod tests {
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
             time_settings: None,
+            persistence: None,
         }
     }
 
@@ -640,4 +665,163 @@ mod tests {
         })
         .await;
     }
+
+    /// Runs the given events through a freshly built transform, returning the events that were not
+    /// dropped as duplicates.
+    async fn run_persistent(
+        transform_config: &DedupeConfig,
+        data_dir: &std::path::Path,
+        events: Vec<Event>,
+    ) -> Vec<Event> {
+        let context = TransformContext {
+            key: Some(ComponentKey::from("dedupe")),
+            globals: GlobalOptions {
+                data_dir: Some(data_dir.to_path_buf()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let transform = transform_config.build(&context).await.unwrap();
+
+        transform
+            .into_task()
+            .transform_events(Box::pin(stream::iter(events)))
+            .collect()
+            .await
+    }
+
+    fn make_persistent_config(transform_config: DedupeConfig) -> DedupeConfig {
+        DedupeConfig {
+            persistence: Some(PersistenceConfig {
+                data_dir: None,
+                snapshot_interval_secs: Duration::from_secs(60),
+            }),
+            ..transform_config
+        }
+    }
+
+    fn make_matched_event(value: &str) -> Event {
+        let mut event = Event::Log(LogEvent::from("message"));
+        event.as_mut_log().insert("matched", value);
+        event
+    }
+
+    #[tokio::test]
+    async fn dedupe_match_persisted_across_restarts() {
+        let data_dir = temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let transform_config =
+            make_persistent_config(make_match_transform_config(5, vec!["matched".into()]));
+
+        let output = run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![make_matched_event("value1"), make_matched_event("value2")],
+        )
+        .await;
+        assert_eq!(output.len(), 2);
+
+        // The cache is restored from the snapshot written on shutdown, so only the new event is
+        // forwarded.
+        let output = run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![
+                make_matched_event("value1"),
+                make_matched_event("value3"),
+                make_matched_event("value2"),
+            ],
+        )
+        .await;
+        assert_eq!(output, vec![make_matched_event("value3")]);
+    }
+
+    #[tokio::test]
+    async fn dedupe_ignore_persisted_across_restarts() {
+        let data_dir = temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let transform_config = make_persistent_config(make_ignore_transform_config(5, vec![]));
+
+        let output = run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![make_matched_event("value1")],
+        )
+        .await;
+        assert_eq!(output.len(), 1);
+
+        let output = run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![make_matched_event("value1")],
+        )
+        .await;
+        assert!(output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dedupe_timed_persisted_across_restarts() {
+        let data_dir = temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let transform_config = make_persistent_config(DedupeConfig {
+            time_settings: Some(TimedCacheConfig {
+                max_age_ms: Duration::from_millis(500),
+                refresh_on_drop: false,
+            }),
+            ..make_match_transform_config(5, vec!["matched".into()])
+        });
+
+        let output = run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![make_matched_event("value1")],
+        )
+        .await;
+        assert_eq!(output.len(), 1);
+
+        let output = run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![make_matched_event("value1")],
+        )
+        .await;
+        assert!(output.is_empty());
+
+        // Once the restored entry has aged out, the event is forwarded again.
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        let output = run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![make_matched_event("value1")],
+        )
+        .await;
+        assert_eq!(output.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn dedupe_persisted_snapshot_discarded_on_fields_change() {
+        let data_dir = temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        let transform_config =
+            make_persistent_config(make_match_transform_config(5, vec!["matched".into()]));
+        run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![make_matched_event("value1")],
+        )
+        .await;
+
+        let transform_config = make_persistent_config(make_match_transform_config(
+            5,
+            vec!["matched".into(), "other".into()],
+        ));
+        let output = run_persistent(
+            &transform_config,
+            &data_dir,
+            vec![make_matched_event("value1")],
+        )
+        .await;
+        assert_eq!(output.len(), 1);
+    }
 }
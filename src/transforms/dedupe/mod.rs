@@ -4,6 +4,9 @@ pub mod config;
 #[cfg(feature = "transforms-impl-dedupe")]
 pub mod common;
 
+#[cfg(feature = "transforms-impl-dedupe")]
+mod persistence;
+
 #[cfg(feature = "transforms-impl-dedupe")]
 pub mod transform;
 
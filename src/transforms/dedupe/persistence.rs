@@ -0,0 +1,298 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_stream::stream;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
+
+use crate::{
+    config::TransformContext,
+    event::Event,
+    internal_events::{DedupeCacheSnapshotLoadError, DedupeCacheSnapshotWriteError},
+};
+
+use super::{
+    common::{FieldMatchConfig, PersistenceConfig},
+    transform::{CacheEntry, TypeId},
+};
+
+const SNAPSHOT_FILE_NAME: &str = "dedupe_cache.json";
+
+/// A point-in-time copy of a deduplication cache, as stored on disk.
+///
+/// Entries are stored from least to most recently used, so that inserting them back into an empty
+/// cache in order reproduces the original eviction order.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct CacheSnapshot {
+    fields: FieldMatchConfig,
+    entries: Vec<SnapshotEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SnapshotEntry {
+    key: SnapshotKey,
+
+    /// When the entry was last seen, in milliseconds since the Unix epoch.
+    ///
+    /// Only set for caches which track the age of their entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seen_at_ms: Option<u64>,
+}
+
+/// The serializable form of a `CacheEntry`.
+///
+/// Paths are stored in their string form, as `OwnedTargetPath` has no serialized representation of
+/// its own.
+#[derive(Debug, Deserialize, Serialize)]
+enum SnapshotKey {
+    Match(Vec<Option<(TypeId, Bytes)>>),
+    Ignore(Vec<(String, TypeId, Bytes)>),
+}
+
+impl From<&CacheEntry> for SnapshotKey {
+    fn from(entry: &CacheEntry) -> Self {
+        match entry {
+            CacheEntry::Match(values) => Self::Match(values.clone()),
+            CacheEntry::Ignore(values) => Self::Ignore(
+                values
+                    .iter()
+                    .map(|(path, type_id, value)| (path.to_string(), *type_id, value.clone()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl TryFrom<SnapshotKey> for CacheEntry {
+    type Error = vrl::path::PathParseError;
+
+    fn try_from(key: SnapshotKey) -> Result<Self, Self::Error> {
+        Ok(match key {
+            SnapshotKey::Match(values) => Self::Match(values),
+            SnapshotKey::Ignore(values) => Self::Ignore(
+                values
+                    .into_iter()
+                    .map(|(path, type_id, value)| {
+                        ConfigTargetPath::try_from(path).map(|path| (path.0, type_id, value))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+impl CacheSnapshot {
+    /// Takes a snapshot of a cache whose entries don't track when they were last seen.
+    pub(crate) fn untimed<V>(fields: &FieldMatchConfig, cache: &LruCache<CacheEntry, V>) -> Self {
+        Self::new(fields, cache, |_| None)
+    }
+
+    /// Takes a snapshot of a cache whose entries track the instant they were last seen.
+    pub(crate) fn timed(fields: &FieldMatchConfig, cache: &LruCache<CacheEntry, Instant>) -> Self {
+        let now = Instant::now();
+        let system_now = SystemTime::now();
+        Self::new(fields, cache, |seen_at| {
+            let seen_at = system_now.checked_sub(now.saturating_duration_since(*seen_at))?;
+            let seen_at_ms = seen_at.duration_since(UNIX_EPOCH).ok()?.as_millis();
+            u64::try_from(seen_at_ms).ok()
+        })
+    }
+
+    fn new<V>(
+        fields: &FieldMatchConfig,
+        cache: &LruCache<CacheEntry, V>,
+        seen_at_ms: impl Fn(&V) -> Option<u64>,
+    ) -> Self {
+        let entries = cache
+            .iter()
+            .rev()
+            .map(|(entry, value)| SnapshotEntry {
+                key: entry.into(),
+                seen_at_ms: seen_at_ms(value),
+            })
+            .collect();
+
+        Self {
+            fields: fields.clone(),
+            entries,
+        }
+    }
+
+    /// Restores the snapshot into a cache whose entries don't track when they were last seen.
+    pub(crate) fn restore_untimed(self, cache: &mut LruCache<CacheEntry, bool>) {
+        for entry in self.entries {
+            if let Ok(entry) = CacheEntry::try_from(entry.key) {
+                cache.put(entry, true);
+            }
+        }
+    }
+
+    /// Restores the snapshot into a cache whose entries track the instant they were last seen.
+    ///
+    /// Entries which are already older than `max_age`, or which were stored without the time they
+    /// were last seen, are skipped, as they would not cause any events to be dropped anyways.
+    pub(crate) fn restore_timed(
+        self,
+        cache: &mut LruCache<CacheEntry, Instant>,
+        max_age: Duration,
+    ) {
+        let now = Instant::now();
+        let system_now = SystemTime::now();
+        for entry in self.entries {
+            let Some(seen_at_ms) = entry.seen_at_ms else {
+                continue;
+            };
+            let age = system_now
+                .duration_since(UNIX_EPOCH + Duration::from_millis(seen_at_ms))
+                .unwrap_or_default();
+            if age >= max_age {
+                continue;
+            }
+            if let Some(seen_at) = now.checked_sub(age)
+                && let Ok(entry) = CacheEntry::try_from(entry.key)
+            {
+                cache.put(entry, seen_at);
+            }
+        }
+    }
+}
+
+/// Handles reading and writing snapshots of a deduplication cache.
+#[derive(Clone, Debug)]
+pub(crate) struct CachePersistence {
+    path: PathBuf,
+    snapshot_interval: Duration,
+}
+
+impl CachePersistence {
+    pub(crate) fn from_config(
+        config: &PersistenceConfig,
+        context: &TransformContext,
+    ) -> crate::Result<Self> {
+        if config.snapshot_interval_secs.is_zero() {
+            return Err("`persistence.snapshot_interval_secs` must be greater than zero".into());
+        }
+
+        let key = context
+            .key
+            .as_ref()
+            .ok_or("persisting the deduplication cache requires a component key")?;
+        let data_dir = context
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), key.id())?;
+
+        Ok(Self {
+            path: data_dir.join(SNAPSHOT_FILE_NAME),
+            snapshot_interval: config.snapshot_interval_secs,
+        })
+    }
+
+    /// Loads the last snapshot written, if any.
+    ///
+    /// Snapshots taken with a different field matching configuration are discarded, as their
+    /// entries cannot be compared against the entries built for incoming events.
+    pub(crate) fn load(&self, fields: &FieldMatchConfig) -> Option<CacheSnapshot> {
+        let data = match std::fs::read(&self.path) {
+            Ok(data) => data,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                emit!(DedupeCacheSnapshotLoadError {
+                    error: error.to_string(),
+                    path: &self.path,
+                });
+                return None;
+            }
+        };
+
+        match serde_json::from_slice::<CacheSnapshot>(&data) {
+            Ok(snapshot) if snapshot.fields == *fields => Some(snapshot),
+            Ok(_) => {
+                debug!(
+                    message = "Discarding deduplication cache snapshot taken with different field matching configuration.",
+                    path = ?self.path,
+                );
+                None
+            }
+            Err(error) => {
+                emit!(DedupeCacheSnapshotLoadError {
+                    error: error.to_string(),
+                    path: &self.path,
+                });
+                None
+            }
+        }
+    }
+
+    /// Writes a snapshot, replacing the previous one.
+    ///
+    /// The snapshot is written to a temporary file first, and then renamed over the previous
+    /// snapshot, so that a crash while writing never leaves a partially written snapshot behind.
+    async fn write(&self, snapshot: &CacheSnapshot) {
+        if let Err(error) = self.try_write(snapshot).await {
+            emit!(DedupeCacheSnapshotWriteError {
+                error,
+                path: &self.path,
+            });
+        }
+    }
+
+    async fn try_write(&self, snapshot: &CacheSnapshot) -> io::Result<()> {
+        let data = serde_json::to_vec(snapshot)?;
+        let tmp_path = tmp_path(&self.path);
+        tokio::fs::write(&tmp_path, data).await?;
+        tokio::fs::rename(&tmp_path, &self.path).await
+    }
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    tmp_path.into()
+}
+
+/// A deduplication transform whose cache can be persisted.
+pub(crate) trait PersistentCache: Send + 'static {
+    fn transform_one(&mut self, event: Event) -> Option<Event>;
+
+    fn snapshot(&self) -> CacheSnapshot;
+}
+
+/// Runs a deduplication transform, periodically writing snapshots of its cache, as well as once
+/// the input stream ends.
+pub(crate) fn transform_with_snapshots<T: PersistentCache>(
+    mut inner: Box<T>,
+    persistence: CachePersistence,
+    mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+) -> Pin<Box<dyn Stream<Item = Event> + Send>> {
+    let start = tokio::time::Instant::now() + persistence.snapshot_interval;
+    let mut snapshot_interval = tokio::time::interval_at(start, persistence.snapshot_interval);
+
+    Box::pin(stream! {
+        loop {
+            tokio::select! {
+                _ = snapshot_interval.tick() => {
+                    persistence.write(&inner.snapshot()).await;
+                },
+                maybe_event = input_rx.next() => {
+                    match maybe_event {
+                        None => {
+                            persistence.write(&inner.snapshot()).await;
+                            break;
+                        }
+                        Some(event) => if let Some(event) = inner.transform_one(event) {
+                            yield event;
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
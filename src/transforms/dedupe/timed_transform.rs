@@ -1,12 +1,14 @@
-use std::{future::ready, num::NonZeroUsize, pin::Pin, time::Instant};
+use std::{future::ready, num::NonZeroUsize, pin::Pin};
 
 use futures::{Stream, StreamExt};
 use lru::LruCache;
+use tokio::time::Instant;
 
 use crate::{event::Event, internal_events::DedupeEventsDropped, transforms::TaskTransform};
 
 use super::{
     common::{FieldMatchConfig, TimedCacheConfig},
+    persistence::{CachePersistence, CacheSnapshot, PersistentCache, transform_with_snapshots},
     transform::{CacheEntry, build_cache_entry},
 };
 
@@ -15,6 +17,7 @@ pub struct TimedDedupe {
     fields: FieldMatchConfig,
     cache: LruCache<CacheEntry, Instant>,
     time_config: TimedCacheConfig,
+    persistence: Option<CachePersistence>,
 }
 
 impl TimedDedupe {
@@ -27,9 +30,19 @@ impl TimedDedupe {
             fields,
             cache: LruCache::new(num_entries),
             time_config,
+            persistence: None,
         }
     }
 
+    /// Persists the cache using the given persistence, restoring the last snapshot written, if any.
+    pub(crate) fn with_persistence(mut self, persistence: CachePersistence) -> Self {
+        if let Some(snapshot) = persistence.load(&self.fields) {
+            snapshot.restore_timed(&mut self.cache, self.time_config.max_age_ms);
+        }
+        self.persistence = Some(persistence);
+        self
+    }
+
     pub fn transform_one(&mut self, event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
         let now = Instant::now();
@@ -55,14 +68,28 @@ impl TimedDedupe {
     }
 }
 
+impl PersistentCache for TimedDedupe {
+    fn transform_one(&mut self, event: Event) -> Option<Event> {
+        TimedDedupe::transform_one(self, event)
+    }
+
+    fn snapshot(&self) -> CacheSnapshot {
+        CacheSnapshot::timed(&self.fields, &self.cache)
+    }
+}
+
 impl TaskTransform<Event> for TimedDedupe {
     fn transform(
-        self: Box<Self>,
+        mut self: Box<Self>,
         task: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
         Self: 'static,
     {
+        if let Some(persistence) = self.persistence.take() {
+            return transform_with_snapshots(self, persistence, task);
+        }
+
         let mut inner = self;
         Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
     }
@@ -12,15 +12,19 @@ use crate::{
     transforms::TaskTransform,
 };
 
-use super::common::FieldMatchConfig;
+use super::{
+    common::FieldMatchConfig,
+    persistence::{CachePersistence, CacheSnapshot, PersistentCache, transform_with_snapshots},
+};
 
 #[derive(Clone)]
 pub struct Dedupe {
     fields: FieldMatchConfig,
     cache: LruCache<CacheEntry, bool>,
+    persistence: Option<CachePersistence>,
 }
 
-type TypeId = u8;
+pub(crate) type TypeId = u8;
 
 /// A CacheEntry comes in two forms, depending on the FieldMatchConfig in use.
 ///
@@ -71,7 +75,17 @@ impl Dedupe {
         Self {
             fields,
             cache: LruCache::new(num_entries),
+            persistence: None,
+        }
+    }
+
+    /// Persists the cache using the given persistence, restoring the last snapshot written, if any.
+    pub(crate) fn with_persistence(mut self, persistence: CachePersistence) -> Self {
+        if let Some(snapshot) = persistence.load(&self.fields) {
+            snapshot.restore_untimed(&mut self.cache);
         }
+        self.persistence = Some(persistence);
+        self
     }
 
     pub fn transform_one(&mut self, event: Event) -> Option<Event> {
@@ -121,14 +135,28 @@ pub(crate) fn build_cache_entry(event: &Event, fields: &FieldMatchConfig) -> Cac
     }
 }
 
+impl PersistentCache for Dedupe {
+    fn transform_one(&mut self, event: Event) -> Option<Event> {
+        Dedupe::transform_one(self, event)
+    }
+
+    fn snapshot(&self) -> CacheSnapshot {
+        CacheSnapshot::untimed(&self.fields, &self.cache)
+    }
+}
+
 impl TaskTransform<Event> for Dedupe {
     fn transform(
-        self: Box<Self>,
+        mut self: Box<Self>,
         task: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
         Self: 'static,
     {
+        if let Some(persistence) = self.persistence.take() {
+            return transform_with_snapshots(self, persistence, task);
+        }
+
         let mut inner = self;
         Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
     }
@@ -10,8 +10,9 @@ use vector_lib::{
     event::DatadogMetricOriginMetadata,
     event::{
         metric::Sample,
-        metric::{Bucket, Quantile},
+        metric::{Bucket, MetricSketch, Quantile},
     },
+    lookup::lookup_v2::ConfigTargetPath,
 };
 use vrl::path::{PathParseError, parse_target_path};
 use vrl::{event_path, path};
@@ -29,10 +30,12 @@ use crate::{
         metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind, TagValue},
     },
     internal_events::{
-        DROP_EVENT, LogToMetricFieldNullError, LogToMetricParseFloatError,
-        MetricMetadataInvalidFieldValueError, MetricMetadataMetricDetailsNotFoundError,
-        MetricMetadataParseError, ParserMissingFieldError,
+        DROP_EVENT, LogToMetricFieldNullError, LogToMetricInvalidSampleRateError,
+        LogToMetricParseFloatError, MetricMetadataInvalidFieldValueError,
+        MetricMetadataMetricDetailsNotFoundError, MetricMetadataParseError,
+        ParserMissingFieldError,
     },
+    metrics::AgentDDSketch,
     schema,
     template::{Template, TemplateRenderingError},
     transforms::{FunctionTransform, OutputBuffer, Transform},
@@ -89,6 +92,28 @@ pub struct CounterConfig {
     #[configurable(derived)]
     #[serde(default = "default_kind")]
     pub kind: MetricKind,
+
+    /// The field holding the sample rate of the event.
+    ///
+    /// The counter is incremented by the sample rate, so that counts derived from sampled events
+    /// are correctly weighted. See the `sample_rate_field` option of distributions for details.
+    #[configurable(metadata(docs::examples = "sample_rate"))]
+    pub sample_rate_field: Option<ConfigTargetPath>,
+}
+
+/// Specification of a distribution derived from a log event.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct DistributionConfig {
+    /// The field holding the sample rate of the event.
+    ///
+    /// The values of an event sampled one in `N` times, such as by the `sample` transform, have a
+    /// sample rate of `N` and each count as `N` values, so that distributions derived from sampled
+    /// events are correctly weighted. The sample rate must be a number greater than or equal to
+    /// `1`, and is rounded to the nearest integer. Events without the field have a sample rate of
+    /// `1`.
+    #[configurable(metadata(docs::examples = "sample_rate"))]
+    pub sample_rate_field: Option<ConfigTargetPath>,
 }
 
 /// Specification of a metric derived from a log event.
@@ -101,6 +126,10 @@ pub struct CounterConfig {
 #[derive(Clone, Debug)]
 pub struct MetricConfig {
     /// Name of the field in the event to generate the metric.
+    ///
+    /// If the field holds an array, each of its values is used: a counter incremented by value and
+    /// a gauge are generated for each value, while the values of a distribution or a set are all
+    /// held in a single metric.
     pub field: Template,
 
     /// Overrides the name of the counter.
@@ -147,7 +176,7 @@ pub enum MetricTypeConfig {
     Counter(CounterConfig),
 
     /// A histogram.
-    Histogram,
+    Histogram(DistributionConfig),
 
     /// A gauge.
     Gauge,
@@ -156,7 +185,15 @@ pub enum MetricTypeConfig {
     Set,
 
     /// A summary.
-    Summary,
+    Summary(DistributionConfig),
+
+    /// A sketch.
+    ///
+    /// The values are held in a [DDSketch][ddsketch], which summarizes distributions with a fixed
+    /// relative error and can be merged across events.
+    ///
+    /// [ddsketch]: https://www.vldb.org/pvldb/vol12/p2195-masson.pdf
+    Sketch(DistributionConfig),
 }
 
 impl MetricConfig {
@@ -190,6 +227,7 @@ impl GenerateConfig for LogToMetricConfig {
                 metric: MetricTypeConfig::Counter(CounterConfig {
                     increment_by_value: false,
                     kind: MetricKind::Incremental,
+                    sample_rate_field: None,
                 }),
             }]),
             all_metrics: Some(true),
@@ -265,6 +303,10 @@ enum TransformError {
         path: String,
         error: ParseFloatError,
     },
+    InvalidSampleRate {
+        path: String,
+        value: String,
+    },
     TemplateRenderingError(TemplateRenderingError),
     PairExpansionError {
         key: String,
@@ -373,7 +415,10 @@ fn render_tag_into(
     Ok(())
 }
 
-fn to_metric_with_config(config: &MetricConfig, event: &Event) -> Result<Metric, TransformError> {
+fn to_metrics_with_config(
+    config: &MetricConfig,
+    event: &Event,
+) -> Result<Vec<Metric>, TransformError> {
     let log = event.as_log();
 
     let timestamp = log
@@ -406,6 +451,13 @@ fn to_metric_with_config(config: &MetricConfig, event: &Event) -> Result<Metric,
         }),
         Some(value) => Ok(value),
     }?;
+    let values = match value {
+        Value::Array(values) => values.iter().collect::<Vec<_>>(),
+        value => vec![value],
+    };
+    if values.is_empty() {
+        return Ok(Vec::new());
+    }
 
     let name = config.name.as_ref().unwrap_or(&config.field);
     let name = render_template(name, event)?;
@@ -417,78 +469,123 @@ fn to_metric_with_config(config: &MetricConfig, event: &Event) -> Result<Metric,
 
     let tags = render_tags(&config.tags, event)?;
 
-    let (kind, value) = match &config.metric {
+    let path = field.to_string();
+    let metric = |kind, value| {
+        Metric::new_with_metadata(name.clone(), kind, value, metadata.clone())
+            .with_namespace(namespace.clone())
+            .with_tags(tags.clone())
+            .with_timestamp(timestamp)
+    };
+
+    let metrics = match &config.metric {
         MetricTypeConfig::Counter(counter) => {
-            let value = if counter.increment_by_value {
-                value.to_string_lossy().parse().map_err(|error| {
-                    TransformError::ParseFloatError {
-                        path: config.field.get_ref().to_owned(),
-                        error,
-                    }
-                })?
+            let rate = f64::from(sample_rate(log, counter.sample_rate_field.as_ref())?);
+            if counter.increment_by_value {
+                values
+                    .into_iter()
+                    .map(|value| {
+                        let value = parse_float(value, config.field.get_ref())?;
+                        Ok(metric(
+                            counter.kind,
+                            MetricValue::Counter {
+                                value: value * rate,
+                            },
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?
             } else {
-                1.0
-            };
-
-            (counter.kind, MetricValue::Counter { value })
-        }
-        MetricTypeConfig::Histogram => {
-            let value = value.to_string_lossy().parse().map_err(|error| {
-                TransformError::ParseFloatError {
-                    path: field.to_string(),
-                    error,
-                }
-            })?;
-
-            (
-                MetricKind::Incremental,
-                MetricValue::Distribution {
-                    samples: vector_lib::samples![value => 1],
-                    statistic: StatisticKind::Histogram,
-                },
-            )
+                vec![metric(counter.kind, MetricValue::Counter { value: rate })]
+            }
         }
-        MetricTypeConfig::Summary => {
-            let value = value.to_string_lossy().parse().map_err(|error| {
-                TransformError::ParseFloatError {
-                    path: field.to_string(),
-                    error,
-                }
-            })?;
+        MetricTypeConfig::Histogram(distribution) => vec![metric(
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: samples(log, &values, distribution, &path)?,
+                statistic: StatisticKind::Histogram,
+            },
+        )],
+        MetricTypeConfig::Summary(distribution) => vec![metric(
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: samples(log, &values, distribution, &path)?,
+                statistic: StatisticKind::Summary,
+            },
+        )],
+        MetricTypeConfig::Sketch(distribution) => {
+            let mut sketch = AgentDDSketch::with_agent_defaults();
+            for sample in samples(log, &values, distribution, &path)? {
+                sketch.insert_n(sample.value, sample.rate);
+            }
 
-            (
+            vec![metric(
                 MetricKind::Incremental,
-                MetricValue::Distribution {
-                    samples: vector_lib::samples![value => 1],
-                    statistic: StatisticKind::Summary,
+                MetricValue::Sketch {
+                    sketch: MetricSketch::AgentDDSketch(sketch),
                 },
-            )
+            )]
         }
-        MetricTypeConfig::Gauge => {
-            let value = value.to_string_lossy().parse().map_err(|error| {
-                TransformError::ParseFloatError {
-                    path: field.to_string(),
-                    error,
-                }
-            })?;
+        MetricTypeConfig::Gauge => values
+            .into_iter()
+            .map(|value| {
+                let value = parse_float(value, &path)?;
+                Ok(metric(MetricKind::Absolute, MetricValue::Gauge { value }))
+            })
+            .collect::<Result<_, _>>()?,
+        MetricTypeConfig::Set => vec![metric(
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: values
+                    .into_iter()
+                    .map(|value| value.to_string_lossy().into_owned())
+                    .collect(),
+            },
+        )],
+    };
+    Ok(metrics)
+}
 
-            (MetricKind::Absolute, MetricValue::Gauge { value })
-        }
-        MetricTypeConfig::Set => {
-            let value = value.to_string_lossy().into_owned();
+fn parse_float(value: &Value, path: &str) -> Result<f64, TransformError> {
+    value
+        .to_string_lossy()
+        .parse()
+        .map_err(|error| TransformError::ParseFloatError {
+            path: path.to_owned(),
+            error,
+        })
+}
 
-            (
-                MetricKind::Incremental,
-                MetricValue::Set {
-                    values: std::iter::once(value).collect(),
-                },
-            )
-        }
+/// Returns the sample rate of an event, which is `1` if the event doesn't have one.
+fn sample_rate(log: &LogEvent, field: Option<&ConfigTargetPath>) -> Result<u32, TransformError> {
+    let value = match field.and_then(|field| log.get(&field.0)) {
+        None | Some(Value::Null) => return Ok(1),
+        Some(value) => value,
     };
-    Ok(Metric::new_with_metadata(name, kind, value, metadata)
-        .with_namespace(namespace)
-        .with_tags(tags)
-        .with_timestamp(timestamp))
+
+    match value.to_string_lossy().parse::<f64>() {
+        Ok(rate) if (1.0..=f64::from(u32::MAX)).contains(&rate) => Ok(rate.round() as u32),
+        _ => Err(TransformError::InvalidSampleRate {
+            path: field.map(|field| field.0.to_string()).unwrap_or_default(),
+            value: value.to_string_lossy().into_owned(),
+        }),
+    }
+}
+
+fn samples(
+    log: &LogEvent,
+    values: &[&Value],
+    config: &DistributionConfig,
+    path: &str,
+) -> Result<Vec<Sample>, TransformError> {
+    let rate = sample_rate(log, config.sample_rate_field.as_ref())?;
+    values
+        .iter()
+        .map(|value| {
+            Ok(Sample {
+                value: parse_float(value, path)?,
+                rate,
+            })
+        })
+        .collect()
 }
 
 fn bytes_to_str(value: &Value) -> Option<String> {
@@ -904,9 +1001,9 @@ impl FunctionTransform for LogToMetric {
             }
         } else {
             for config in self.metrics.iter() {
-                match to_metric_with_config(config, &event) {
-                    Ok(metric) => {
-                        buffer.push(Event::Metric(metric));
+                match to_metrics_with_config(config, &event) {
+                    Ok(metrics) => {
+                        buffer.extend(metrics.into_iter().map(Event::Metric));
                     }
                     Err(err) => {
                         match err {
@@ -926,6 +1023,12 @@ impl FunctionTransform for LogToMetric {
                                     error
                                 })
                             }
+                            TransformError::InvalidSampleRate { path, value } => {
+                                emit!(LogToMetricInvalidSampleRateError {
+                                    field: path.as_ref(),
+                                    value: value.as_ref(),
+                                })
+                            }
                             TransformError::TemplateRenderingError(error) => {
                                 emit!(crate::internal_events::TemplateRenderingError {
                                     error,
@@ -1811,6 +1914,118 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn sampled_response_times_histogram() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_times"
+            sample_rate_field = "sample_rate"
+            "#,
+        );
+
+        let mut event = create_event("response_times", vec![Value::from(2.5), Value::from("4")]);
+        event.as_mut_log().insert("sample_rate", "10");
+        let metric = do_transform(config, event).await.unwrap().into_metric();
+
+        assert_eq!(
+            metric.value(),
+            &MetricValue::Distribution {
+                samples: vector_lib::samples![2.5 => 10, 4.0 => 10],
+                statistic: StatisticKind::Histogram
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn sampled_response_times_sketch() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "sketch"
+            field = "response_times"
+            sample_rate_field = "sample_rate"
+            "#,
+        );
+
+        let mut event = create_event("response_times", vec![1.0, 3.0]);
+        event.as_mut_log().insert("sample_rate", 4_i64);
+        let metric = do_transform(config, event).await.unwrap().into_metric();
+
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        let MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } = metric.value()
+        else {
+            panic!("expected a sketch, got {:?}", metric.value());
+        };
+        assert_eq!(sketch.count(), 8);
+        assert_eq!(sketch.sum(), Some(16.0));
+        assert_eq!(sketch.min(), Some(1.0));
+        assert_eq!(sketch.max(), Some(3.0));
+    }
+
+    #[tokio::test]
+    async fn sampled_counter() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            sample_rate_field = "sample_rate"
+            "#,
+        );
+
+        let mut event = create_event("status", "200");
+        event.as_mut_log().insert("sample_rate", 5.0);
+        let metric = do_transform(config, event).await.unwrap().into_metric();
+
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 5.0 });
+    }
+
+    #[tokio::test]
+    async fn invalid_sample_rate() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "summary"
+            field = "response_time"
+            sample_rate_field = "sample_rate"
+            "#,
+        );
+
+        let mut event = create_event("response_time", "2.5");
+        event.as_mut_log().insert("sample_rate", "0.2");
+        assert_eq!(do_transform(config, event).await, None);
+    }
+
+    #[tokio::test]
+    async fn gauges_from_array() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "gauge"
+            field = "memory_rss"
+            "#,
+        );
+
+        let event = create_event("memory_rss", vec![123_i64, 456]);
+        let output = do_transform_multiple_events(config, event, 2).await;
+
+        let values = output
+            .into_iter()
+            .map(|event| event.into_metric().value().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            [
+                MetricValue::Gauge { value: 123.0 },
+                MetricValue::Gauge { value: 456.0 }
+            ]
+        );
+    }
+
     //  Metric Metadata Tests
     //
     fn create_log_event(json_str: &str) -> Event {
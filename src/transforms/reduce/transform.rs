@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::pin::Pin;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use crate::internal_events::ReduceAddEventError;
 use crate::transforms::reduce::merge_strategy::{
@@ -15,6 +15,7 @@ use crate::{
 };
 use futures::Stream;
 use indexmap::IndexMap;
+use tokio::time::Instant;
 use vector_lib::stream::expiration_map::{Emitter, map_with_expiration};
 use vrl::path::{OwnedTargetPath, parse_target_path};
 use vrl::prelude::KeyString;
@@ -1,3 +1,6 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use serde_with::serde_as;
 use snafu::Snafu;
 use vector_lib::config::{LegacyKey, LogNamespace};
 use vector_lib::configurable::configurable_component;
@@ -15,7 +18,10 @@ use crate::{
     transforms::Transform,
 };
 
-use super::transform::{Sample, SampleMode};
+use super::{
+    dynamic::DynamicSampler,
+    transform::{Sample, SampleMode},
+};
 
 #[derive(Debug, Snafu)]
 pub enum SampleError {
@@ -28,10 +34,96 @@ pub enum SampleError {
     #[snafu(display("Only non-zero numbers are allowed values for `rate`"))]
     InvalidRate,
 
+    #[snafu(display("Exactly one value must be provided for either 'rate', 'ratio' or 'dynamic'"))]
+    InvalidConfiguration,
+
     #[snafu(display(
-        "Exactly one value must be provided for either 'rate' or 'ratio', but not both"
+        "Only positive, non-zero numbers are allowed values for `dynamic.target_events_per_second`, value: {target}"
     ))]
-    InvalidConfiguration,
+    InvalidTarget { target: f64 },
+
+    #[snafu(display("`dynamic.adjustment_interval_secs` must be greater than zero"))]
+    InvalidAdjustmentInterval,
+
+    #[snafu(display(
+        "Only numbers greater than 0 and at most 1 are allowed values for `dynamic.ema_weight`, value: {weight}"
+    ))]
+    InvalidEmaWeight { weight: f64 },
+}
+
+/// Configuration for dynamic sampling.
+///
+/// Instead of a fixed rate, the sample rate of each key is adjusted so that the total number of
+/// events forwarded meets a target throughput, with a larger share of the events of rare keys
+/// forwarded than of frequent ones. Keys are taken from `group_by`, and events without a key share a
+/// single sample rate.
+///
+/// Each forwarded event is annotated with the sample rate of its key, expressed as `1/N`, in
+/// `sample_rate_key`.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DynamicSamplingConfig {
+    /// The total number of events to forward per second, shared between all keys.
+    #[configurable(metadata(docs::examples = 100.0))]
+    pub target_events_per_second: f64,
+
+    /// The interval at which the sample rates are recomputed from the throughput of each key.
+    ///
+    /// Keys seen for the first time are forwarded in full until the next adjustment.
+    #[serde(default = "default_adjustment_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Adjustment Interval"))]
+    pub adjustment_interval_secs: Duration,
+
+    /// The weight of the last interval in the moving average of the throughput of each key.
+    ///
+    /// Higher values make sample rates react faster to changes in throughput, while lower values
+    /// smooth out bursts.
+    #[serde(default = "default_ema_weight")]
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub ema_weight: f64,
+
+    /// The maximum number of keys to track.
+    ///
+    /// Once reached, events of any new key share a single sample rate.
+    #[serde(default = "default_max_keys")]
+    pub max_keys: NonZeroUsize,
+}
+
+const fn default_adjustment_interval() -> Duration {
+    Duration::from_secs(15)
+}
+
+const fn default_ema_weight() -> f64 {
+    0.5
+}
+
+const fn default_max_keys() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+impl DynamicSamplingConfig {
+    fn build(&self) -> Result<DynamicSampler, SampleError> {
+        let target = self.target_events_per_second;
+        if !target.is_finite() || target <= 0.0 {
+            return Err(SampleError::InvalidTarget { target });
+        }
+        if self.adjustment_interval_secs.is_zero() {
+            return Err(SampleError::InvalidAdjustmentInterval);
+        }
+        let weight = self.ema_weight;
+        if weight.is_nan() || weight <= 0.0 || weight > 1.0 {
+            return Err(SampleError::InvalidEmaWeight { weight });
+        }
+        Ok(DynamicSampler::new(
+            target,
+            self.adjustment_interval_secs,
+            weight,
+            self.max_keys.get(),
+        ))
+    }
 }
 
 /// Configuration for the `sample` transform.
@@ -46,7 +138,8 @@ pub struct SampleConfig {
     ///
     /// For example, `rate = 1500` means 1 out of every 1500 events are forwarded and the rest are
     /// dropped. This differs from `ratio` which allows more precise control over the number of events
-    /// retained and values greater than 1/2. It is an error to provide a value for both `rate` and `ratio`.
+    /// retained and values greater than 1/2. It is an error to provide a value for more than one of
+    /// `rate`, `ratio` and `dynamic`.
     #[configurable(metadata(docs::examples = 1500))]
     pub rate: Option<u64>,
 
@@ -55,11 +148,14 @@ pub struct SampleConfig {
     /// For example, `ratio = .13` means that 13% out of all events on the stream are forwarded and
     /// the rest are dropped. This differs from `rate` allowing the configuration of a higher
     /// precision value and also the ability to retain values of greater than 50% of all events. It is
-    /// an error to provide a value for both `rate` and `ratio`.
+    /// an error to provide a value for more than one of `rate`, `ratio` and `dynamic`.
     #[configurable(metadata(docs::examples = 0.13))]
     #[configurable(validation(range(min = 0.0, max = 1.0)))]
     pub ratio: Option<f64>,
 
+    #[configurable(derived)]
+    pub dynamic: Option<DynamicSamplingConfig>,
+
     /// The name of the field whose value is hashed to determine if the event should be
     /// sampled.
     ///
@@ -94,22 +190,23 @@ pub struct SampleConfig {
 }
 
 impl SampleConfig {
-    fn sample_rate(&self) -> Result<SampleMode, SampleError> {
-        match (self.rate, self.ratio) {
-            (None, Some(ratio)) => {
+    pub(super) fn sample_rate(&self) -> Result<SampleMode, SampleError> {
+        match (self.rate, self.ratio, &self.dynamic) {
+            (None, Some(ratio), None) => {
                 if ratio <= 0.0 {
                     Err(SampleError::InvalidRatio { ratio })
                 } else {
                     Ok(SampleMode::new_ratio(ratio))
                 }
             }
-            (Some(rate), None) => {
+            (Some(rate), None, None) => {
                 if rate == 0 {
                     Err(SampleError::InvalidRate)
                 } else {
                     Ok(SampleMode::new_rate(rate))
                 }
             }
+            (None, None, Some(dynamic)) => dynamic.build().map(SampleMode::new_dynamic),
             _ => Err(SampleError::InvalidConfiguration),
         }
     }
@@ -120,6 +217,7 @@ impl GenerateConfig for SampleConfig {
         toml::Value::try_from(Self {
             rate: None,
             ratio: Some(0.1),
+            dynamic: None,
             key_field: None,
             group_by: None,
             exclude: None::<AnyCondition>,
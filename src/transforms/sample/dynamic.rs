@@ -0,0 +1,294 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Keys whose moving average falls below this number of events per interval are forgotten.
+const AGE_OUT_COUNT: f64 = 0.5;
+
+/// The sampling state of a single key.
+#[derive(Clone, Debug)]
+struct KeyState {
+    /// The number of events seen in the current interval.
+    count: u64,
+    /// The exponential moving average of `count` over past intervals.
+    moving_count: f64,
+    /// The current sample rate, expressed as `1/N`.
+    rate: u64,
+    /// The number of events seen at the current rate, used to sample every `rate`th event.
+    seen: u64,
+}
+
+impl Default for KeyState {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            moving_count: 0.0,
+            rate: 1,
+            seen: 0,
+        }
+    }
+}
+
+/// Samples events so that the total throughput meets a target, while forwarding a larger share of
+/// the events of rare keys than of frequent ones.
+///
+/// The throughput of each key is tracked as a moving average, and the sample rates are recomputed
+/// from it at a fixed interval. The budget of events is shared between keys proportionally to the
+/// logarithm of their throughput, so that a key seeing a hundred times more events than another only
+/// gets about twice as many of them forwarded. Keys seen for the first time are forwarded in full
+/// until the next adjustment.
+#[derive(Clone, Debug)]
+pub struct DynamicSampler {
+    target_events_per_second: f64,
+    adjustment_interval: Duration,
+    ema_weight: f64,
+    max_keys: usize,
+    keys: HashMap<Option<String>, KeyState>,
+    /// Shared by the keys seen once `max_keys` is reached.
+    overflow: KeyState,
+    next_adjustment: Option<Instant>,
+    adjusted: bool,
+}
+
+impl DynamicSampler {
+    pub fn new(
+        target_events_per_second: f64,
+        adjustment_interval: Duration,
+        ema_weight: f64,
+        max_keys: usize,
+    ) -> Self {
+        Self {
+            target_events_per_second,
+            adjustment_interval,
+            ema_weight,
+            max_keys,
+            keys: HashMap::default(),
+            overflow: KeyState::default(),
+            next_adjustment: None,
+            adjusted: false,
+        }
+    }
+
+    pub const fn target_events_per_second(&self) -> f64 {
+        self.target_events_per_second
+    }
+
+    /// Decides whether to keep an event, returning the sample rate of its key if it is kept.
+    ///
+    /// When a hash of the event is given, it is used instead of a counter, so that events sharing
+    /// the same hashed value are sampled together.
+    pub fn sample(&mut self, key: &Option<String>, hash: Option<u64>, now: Instant) -> Option<u64> {
+        self.maybe_adjust(now);
+
+        let state = if self.keys.contains_key(key) || self.keys.len() < self.max_keys {
+            self.keys.entry(key.clone()).or_default()
+        } else {
+            &mut self.overflow
+        };
+        state.count += 1;
+
+        let keep = match hash {
+            Some(hash) => hash % state.rate == 0,
+            None => {
+                let keep = state.seen % state.rate == 0;
+                state.seen += 1;
+                keep
+            }
+        };
+        keep.then_some(state.rate)
+    }
+
+    fn maybe_adjust(&mut self, now: Instant) {
+        match self.next_adjustment {
+            Some(next) if now < next => {}
+            Some(_) => {
+                self.adjust();
+                self.next_adjustment = Some(now + self.adjustment_interval);
+            }
+            None => self.next_adjustment = Some(now + self.adjustment_interval),
+        }
+    }
+
+    /// Updates the moving averages with the counts of the last interval, and recomputes the
+    /// sample rates from them.
+    fn adjust(&mut self) {
+        let ema_weight = self.ema_weight;
+        let adjusted = self.adjusted;
+        let update = |state: &mut KeyState| {
+            let count = state.count as f64;
+            state.moving_count = if adjusted {
+                ema_weight * count + (1.0 - ema_weight) * state.moving_count
+            } else {
+                count
+            };
+            state.count = 0;
+        };
+        self.keys.values_mut().for_each(update);
+        update(&mut self.overflow);
+        self.keys
+            .retain(|_, state| state.moving_count >= AGE_OUT_COUNT);
+        self.adjusted = true;
+
+        // Keys are visited in a stable order, so that the leftover budget is shared the same way
+        // between adjustments.
+        let mut keys = self.keys.iter_mut().collect::<Vec<_>>();
+        keys.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        let mut states = keys
+            .into_iter()
+            .map(|(_, state)| state)
+            .chain(std::iter::once(&mut self.overflow))
+            .collect::<Vec<_>>();
+        let goal = self.target_events_per_second * self.adjustment_interval.as_secs_f64();
+        assign_rates(&mut states, goal);
+    }
+
+    #[cfg(test)]
+    fn rate(&self, key: &Option<String>) -> Option<u64> {
+        self.keys.get(key).map(|state| state.rate)
+    }
+}
+
+/// Assigns sample rates so that the expected number of events kept per interval meets `goal`.
+///
+/// Each key is given a share of the goal proportional to the logarithm of its count. Keys needing
+/// less than their share keep all of their events, and the remainder is spread over the keys that
+/// follow.
+fn assign_rates(states: &mut [&mut KeyState], goal: f64) {
+    let total = states.iter().map(|state| state.moving_count).sum::<f64>();
+    let log_sum = states
+        .iter()
+        .map(|state| state.moving_count.max(1.0).log10())
+        .sum::<f64>();
+
+    if total <= goal {
+        states.iter_mut().for_each(|state| set_rate(state, 1));
+        return;
+    }
+    if log_sum == 0.0 {
+        // Every key saw at most a single event, so they are all sampled alike.
+        let rate = (total / goal).ceil() as u64;
+        states.iter_mut().for_each(|state| set_rate(state, rate));
+        return;
+    }
+
+    let goal_ratio = goal / log_sum;
+    let mut extra = 0.0;
+    let mut remaining = states.len();
+    for state in states.iter_mut() {
+        let count = state.moving_count;
+        let share = extra / remaining as f64;
+        let key_goal = (count.max(1.0).log10() * goal_ratio).max(1.0) + share;
+        extra -= share;
+        remaining -= 1;
+
+        if count <= key_goal {
+            set_rate(state, 1);
+            extra += key_goal - count;
+        } else {
+            let rate = (count / key_goal).ceil();
+            set_rate(state, rate as u64);
+            extra += key_goal - count / rate;
+        }
+    }
+}
+
+fn set_rate(state: &mut KeyState, rate: u64) {
+    if state.rate != rate {
+        state.rate = rate.max(1);
+        state.seen = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: &str) -> Option<String> {
+        Some(key.to_string())
+    }
+
+    /// Sends `events` events for each key within an interval, and returns how many were kept per key.
+    fn run_interval(
+        sampler: &mut DynamicSampler,
+        start: Instant,
+        events: &[(&str, u64)],
+    ) -> Vec<u64> {
+        events
+            .iter()
+            .map(|(name, count)| {
+                (0..*count)
+                    .filter(|_| sampler.sample(&key(name), None, start).is_some())
+                    .count() as u64
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_everything_under_the_target() {
+        let mut sampler = DynamicSampler::new(10.0, Duration::from_secs(10), 0.5, 100);
+        let start = Instant::now();
+        let events = [("a", 40), ("b", 20)];
+
+        assert_eq!(run_interval(&mut sampler, start, &events), [40, 20]);
+        let next = start + Duration::from_secs(10);
+        assert_eq!(run_interval(&mut sampler, next, &events), [40, 20]);
+        assert_eq!(sampler.rate(&key("a")), Some(1));
+    }
+
+    #[test]
+    fn favors_rare_keys() {
+        let mut sampler = DynamicSampler::new(10.0, Duration::from_secs(10), 0.5, 100);
+        let start = Instant::now();
+        let events = [("frequent", 10_000), ("rare", 10)];
+
+        // Keys are not sampled until the first adjustment.
+        assert_eq!(run_interval(&mut sampler, start, &events), [10_000, 10]);
+
+        let next = start + Duration::from_secs(10);
+        let kept = run_interval(&mut sampler, next, &events);
+        assert_eq!(sampler.rate(&key("rare")), Some(1));
+        assert_eq!(kept[1], 10);
+        let frequent_rate = sampler.rate(&key("frequent")).unwrap();
+        assert!(frequent_rate > 100, "rate was {frequent_rate}");
+        // The kept events stay close to the budget of 100 events per interval.
+        let total = kept.iter().sum::<u64>();
+        assert!((90..=110).contains(&total), "kept {total} events");
+    }
+
+    #[test]
+    fn forgets_idle_keys() {
+        let mut sampler = DynamicSampler::new(1.0, Duration::from_secs(1), 0.5, 100);
+        let start = Instant::now();
+        run_interval(&mut sampler, start, &[("a", 10)]);
+        run_interval(&mut sampler, start + Duration::from_secs(1), &[("b", 10)]);
+        assert!(sampler.rate(&key("a")).is_some());
+
+        // The moving average of `a` halves at every adjustment.
+        for secs in 2..8 {
+            run_interval(
+                &mut sampler,
+                start + Duration::from_secs(secs),
+                &[("b", 10)],
+            );
+        }
+        assert_eq!(sampler.rate(&key("a")), None);
+        assert!(sampler.rate(&key("b")).is_some());
+    }
+
+    #[test]
+    fn limits_the_number_of_keys() {
+        let mut sampler = DynamicSampler::new(1.0, Duration::from_secs(1), 0.5, 2);
+        let start = Instant::now();
+        run_interval(
+            &mut sampler,
+            start,
+            &[("a", 1), ("b", 1), ("c", 100), ("d", 100)],
+        );
+        run_interval(&mut sampler, start + Duration::from_secs(1), &[("a", 1)]);
+
+        assert_eq!(sampler.keys.len(), 2);
+        assert!(sampler.rate(&key("c")).is_none());
+        assert!(sampler.overflow.rate > 1);
+    }
+}
@@ -1,6 +1,8 @@
 #[cfg(feature = "transforms-sample")]
 pub mod config;
 
+pub mod dynamic;
+
 pub mod transform;
 
 #[cfg(all(test, feature = "transforms-sample"))]
@@ -24,6 +24,7 @@ async fn emits_internal_events() {
         let config = SampleConfig {
             rate: None,
             ratio: Some(1.0),
+            dynamic: None,
             key_field: None,
             group_by: None,
             exclude: None,
@@ -322,6 +323,44 @@ fn sample_at_rates_higher_then_half() {
     }
 }
 
+#[test]
+fn dynamic_sampling_annotates_the_rate_of_each_key() {
+    let config: SampleConfig = toml::from_str(
+        r#"
+        group_by = "{{ service }}"
+        dynamic.target_events_per_second = 10
+        "#,
+    )
+    .unwrap();
+    let mut sampler = Sample::new(
+        "sample".to_string(),
+        config.sample_rate().unwrap(),
+        None,
+        config.group_by.clone(),
+        None,
+        default_sample_rate_key(),
+    );
+
+    // Keys are forwarded in full until their throughput is known.
+    let mut event = Event::Log(LogEvent::from("hello"));
+    event.as_mut_log().insert("service", "api");
+    let passing = transform_one(&mut sampler, event).unwrap();
+    assert_eq!(passing.as_log()["sample_rate"], "1".into());
+}
+
+#[test]
+fn dynamic_sampling_excludes_fixed_rates() {
+    for config in [
+        "rate = 10\ndynamic.target_events_per_second = 10",
+        "dynamic.target_events_per_second = 0",
+        "dynamic.target_events_per_second = 10\ndynamic.ema_weight = 0",
+        "dynamic.target_events_per_second = 10\ndynamic.adjustment_interval_secs = 0",
+    ] {
+        let config: SampleConfig = toml::from_str(config).unwrap();
+        assert!(config.sample_rate().is_err(), "{config:?}");
+    }
+}
+
 fn condition_contains(key: &str, needle: &str) -> Condition {
     let vrl_config = VrlConfig {
         source: format!(r#"contains!(."{key}", "{needle}")"#),
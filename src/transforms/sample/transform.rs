@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap, fmt};
+use std::{borrow::Cow, collections::HashMap, fmt, time::Instant};
 use vector_lib::config::LegacyKey;
 
 use crate::{
@@ -12,32 +12,28 @@ use crate::{
 use vector_lib::lookup::OwnedTargetPath;
 use vector_lib::lookup::lookup_v2::OptionalValuePath;
 
-/// Exists only for backwards compatability purposes so that the value of sample_rate_key is
-/// consistent after the internal implementation of the Sample class was modified to work in terms
-/// of percentages
+use super::dynamic::DynamicSampler;
+
+/// How events are chosen to be kept.
 #[derive(Clone, Debug)]
 pub enum SampleMode {
-    Rate {
-        rate: u64,
-        counters: HashMap<Option<String>, u64>,
-    },
-    Ratio {
-        ratio: f64,
-        values: HashMap<Option<String>, f64>,
-        hash_ratio_threshold: u64,
-    },
+    /// Keeps a fixed share of the events.
+    Fixed(FixedSampleRate),
+
+    /// Adjusts the share of the events kept to reach a target throughput.
+    Dynamic(DynamicSampler),
 }
 
 impl SampleMode {
     pub fn new_rate(rate: u64) -> Self {
-        Self::Rate {
+        Self::Fixed(FixedSampleRate::Rate {
             rate,
             counters: HashMap::default(),
-        }
+        })
     }
 
     pub fn new_ratio(ratio: f64) -> Self {
-        Self::Ratio {
+        Self::Fixed(FixedSampleRate::Ratio {
             ratio,
             values: HashMap::default(),
             // Supports the 'key_field' option, assuming an equal distribution of values for a given
@@ -48,9 +44,64 @@ impl SampleMode {
             // to the ratio. However to address issues with precision, here the ratio is scaled to
             // meet the width of the type of the hash.
             hash_ratio_threshold: (ratio * (u64::MAX as u128) as f64) as u64,
+        })
+    }
+
+    pub const fn new_dynamic(sampler: DynamicSampler) -> Self {
+        Self::Dynamic(sampler)
+    }
+
+    /// Decides whether to keep an event, returning the sample rate to annotate it with if it is
+    /// kept.
+    fn sample(
+        &mut self,
+        group_by_key: &Option<String>,
+        value: &Option<Cow<'_, str>>,
+    ) -> Option<String> {
+        match self {
+            Self::Fixed(rate) => rate
+                .increment(group_by_key, value)
+                .then(|| rate.to_string()),
+            Self::Dynamic(sampler) => {
+                let hash = value.as_ref().map(|value| seahash::hash(value.as_bytes()));
+                sampler
+                    .sample(group_by_key, hash, Instant::now())
+                    .map(|rate| rate.to_string())
+            }
         }
     }
+}
 
+impl fmt::Display for SampleMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Fixed(rate) => write!(f, "{rate}"),
+            Self::Dynamic(sampler) => write!(
+                f,
+                "dynamic ({} events/s)",
+                sampler.target_events_per_second()
+            ),
+        }
+    }
+}
+
+/// Exists only for backwards compatability purposes so that the value of sample_rate_key is
+/// consistent after the internal implementation of the Sample class was modified to work in terms
+/// of percentages
+#[derive(Clone, Debug)]
+pub enum FixedSampleRate {
+    Rate {
+        rate: u64,
+        counters: HashMap<Option<String>, u64>,
+    },
+    Ratio {
+        ratio: f64,
+        values: HashMap<Option<String>, f64>,
+        hash_ratio_threshold: u64,
+    },
+}
+
+impl FixedSampleRate {
     fn increment(&mut self, group_by_key: &Option<String>, value: &Option<Cow<'_, str>>) -> bool {
         let threshold_exceeded = match self {
             Self::Rate { rate, counters } => {
@@ -89,7 +140,7 @@ impl SampleMode {
     }
 }
 
-impl fmt::Display for SampleMode {
+impl fmt::Display for FixedSampleRate {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Avoids the print of an additional '.0' which was not performed in the previous
         // implementation
@@ -135,8 +186,9 @@ impl Sample {
     #[cfg(test)]
     pub fn ratio(&self) -> f64 {
         match self.rate {
-            SampleMode::Rate { rate, .. } => 1.0f64 / rate as f64,
-            SampleMode::Ratio { ratio, .. } => ratio,
+            SampleMode::Fixed(FixedSampleRate::Rate { rate, .. }) => 1.0f64 / rate as f64,
+            SampleMode::Fixed(FixedSampleRate::Ratio { ratio, .. }) => ratio,
+            SampleMode::Dynamic(_) => panic!("dynamic sample rates have no fixed ratio"),
         }
     }
 }
@@ -198,8 +250,7 @@ impl FunctionTransform for Sample {
             Event::Metric(_) => panic!("component can never receive metric events"),
         });
 
-        let should_sample = self.rate.increment(&group_by_key, &value);
-        if should_sample {
+        if let Some(sample_rate) = self.rate.sample(&group_by_key, &value) {
             if let Some(path) = &self.sample_rate_key.path {
                 match event {
                     Event::Log(ref mut event) => {
@@ -208,11 +259,11 @@ impl FunctionTransform for Sample {
                             event,
                             Some(LegacyKey::Overwrite(path)),
                             path,
-                            self.rate.to_string(),
+                            sample_rate,
                         );
                     }
                     Event::Trace(ref mut event) => {
-                        event.insert(&OwnedTargetPath::event(path.clone()), self.rate.to_string());
+                        event.insert(&OwnedTargetPath::event(path.clone()), sample_rate);
                     }
                     Event::Metric(_) => panic!("component can never receive metric events"),
                 };
@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::time::Duration;
 
 use crate::config::{
     DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext, TransformOutput,
@@ -6,6 +8,7 @@ use crate::config::{
 use crate::schema;
 use crate::transforms::Transform;
 use crate::transforms::tag_cardinality_limit::TagCardinalityLimit;
+use serde_with::serde_as;
 use vector_lib::config::LogNamespace;
 use vector_lib::configurable::configurable_component;
 
@@ -26,6 +29,60 @@ pub struct TagCardinalityLimitConfig {
     )]
     #[serde(default)]
     pub per_metric_limits: HashMap<String, PerMetricConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub internal_metrics: TagCardinalityLimitInternalMetricsConfig,
+}
+
+/// Configuration of internal metrics for the `tag_cardinality_limit` transform.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TagCardinalityLimitInternalMetricsConfig {
+    /// Whether or not to periodically emit the `tag_cardinality_estimate` internal metric.
+    ///
+    /// The metric is a gauge of the estimated number of distinct values of each tag key of each
+    /// metric, tagged with the `metric_name`, `metric_namespace` and `tag_key`, which helps finding
+    /// which tags are exploding. All the values received are counted, including the values over the
+    /// limits.
+    ///
+    /// Cardinalities are estimated using HyperLogLog, with a standard error of about 3%, and use
+    /// 1 KiB of memory for each tag key of each metric. The estimates are reset each time they are
+    /// emitted, so they count the distinct values received during each `estimates_interval_secs`.
+    ///
+    /// The gauges of the tag keys which are no longer received keep their last value, unless the
+    /// [global `expire_metrics_secs` option][global_expire_metrics] is set.
+    ///
+    /// [global_expire_metrics]: https://vector.dev/docs/reference/configuration/global-options/#expire_metrics_secs
+    #[serde(default)]
+    pub emit_cardinality_estimates: bool,
+
+    /// How often the `tag_cardinality_estimate` internal metric is emitted, in seconds.
+    #[serde(default = "default_estimates_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Estimates Interval"))]
+    pub estimates_interval_secs: Duration,
+
+    /// The maximum number of tag keys whose cardinality is estimated during each interval, across
+    /// all the metrics.
+    ///
+    /// This bounds the memory used by the estimates, and the number of `tag_cardinality_estimate`
+    /// gauges emitted. The tag keys first received once this limit is reached are not estimated
+    /// until the next interval.
+    #[serde(default = "default_max_estimated_tag_keys")]
+    pub max_estimated_tag_keys: NonZeroUsize,
+}
+
+impl Default for TagCardinalityLimitInternalMetricsConfig {
+    fn default() -> Self {
+        Self {
+            emit_cardinality_estimates: false,
+            estimates_interval_secs: default_estimates_interval(),
+            max_estimated_tag_keys: default_max_estimated_tag_keys(),
+        }
+    }
 }
 
 /// Configuration for the `tag_cardinality_limit` transform for a specific group of metrics.
@@ -42,6 +99,28 @@ pub struct TagCardinalityLimitInnerConfig {
 
     #[serde(flatten)]
     pub mode: Mode,
+
+    /// Tag values that are always accepted, per tag key.
+    ///
+    /// These values bypass the limit: they are accepted even once `value_limit` is reached, and
+    /// don't count towards it. A tag with several values is only accepted this way if all of its
+    /// values are allowed.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The values always accepted for a tag key."
+    ))]
+    pub allowed_values: HashMap<String, HashSet<String>>,
+
+    /// Tag values that are always over the limit, per tag key.
+    ///
+    /// The `limit_exceeded_action` is applied to these values even before `value_limit` is
+    /// reached. A tag with several values is over the limit this way if any of its values is
+    /// denied.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The values always over the limit for a tag key."
+    ))]
+    pub denied_values: HashMap<String, HashSet<String>>,
 }
 
 /// Controls the approach taken for tracking tag cardinality.
@@ -91,6 +170,12 @@ pub enum LimitExceededAction {
 
     /// Drop the entire event itself.
     DropEvent,
+
+    /// Replace the value(s) of the tag(s) that would exceed the configured limit with `__other__`.
+    ///
+    /// The metrics over the limit are aggregated in the `__other__` bucket of the tag downstream,
+    /// instead of losing the tag.
+    AggregateOverflow,
 }
 
 /// Tag cardinality limit configuration per metric name.
@@ -117,6 +202,14 @@ pub(crate) const fn default_cache_size() -> usize {
     5 * 1024 // 5KB
 }
 
+const fn default_estimates_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+const fn default_max_estimated_tag_keys() -> NonZeroUsize {
+    NonZeroUsize::new(1000).expect("static non-zero number")
+}
+
 impl GenerateConfig for TagCardinalityLimitConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
@@ -124,8 +217,11 @@ impl GenerateConfig for TagCardinalityLimitConfig {
                 mode: Mode::Exact,
                 value_limit: default_value_limit(),
                 limit_exceeded_action: default_limit_exceeded_action(),
+                allowed_values: HashMap::default(),
+                denied_values: HashMap::default(),
             },
             per_metric_limits: HashMap::default(),
+            internal_metrics: Default::default(),
         })
         .unwrap()
     }
@@ -135,6 +231,11 @@ impl GenerateConfig for TagCardinalityLimitConfig {
 #[typetag::serde(name = "tag_cardinality_limit")]
 impl TransformConfig for TagCardinalityLimitConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.internal_metrics.emit_cardinality_estimates
+            && self.internal_metrics.estimates_interval_secs.is_zero()
+        {
+            return Err("`internal_metrics.estimates_interval_secs` must be non-zero".into());
+        }
         Ok(Transform::event_task(TagCardinalityLimit::new(
             self.clone(),
         )))
@@ -1,3 +1,4 @@
+use async_stream::stream;
 use futures::{Stream, StreamExt};
 use hashbrown::HashMap;
 use std::{future::ready, pin::Pin};
@@ -6,35 +7,125 @@ use crate::transforms::tag_cardinality_limit::config::LimitExceededAction;
 use crate::{
     event::Event,
     internal_events::{
-        TagCardinalityLimitRejectingEvent, TagCardinalityLimitRejectingTag,
-        TagCardinalityValueLimitReached,
+        TagCardinalityEstimated, TagCardinalityEstimatesLimitReached,
+        TagCardinalityLimitAggregatingTag, TagCardinalityLimitRejectingEvent,
+        TagCardinalityLimitRejectingTag, TagCardinalityValueLimitReached,
     },
     transforms::TaskTransform,
 };
 
 mod config;
+mod hyperloglog;
 mod tag_value_set;
 
 #[cfg(test)]
 mod tests;
 
-use crate::event::metric::TagValueSet;
+use crate::event::metric::{MetricTags, TagValueSet};
 pub use config::{TagCardinalityLimitConfig, TagCardinalityLimitInnerConfig};
+use hyperloglog::HyperLogLog;
 use tag_value_set::AcceptedTagValueSet;
 
 type MetricId = (Option<String>, String);
 
+/// The value given to the tags over the limit by the `aggregate_overflow` action.
+const OVERFLOW_TAG_VALUE: &str = "__other__";
+
+/// The estimates of the cardinality of the tags of the metrics received since they were last
+/// emitted.
+#[derive(Debug)]
+struct CardinalityEstimates {
+    metrics: HashMap<MetricId, HashMap<String, HyperLogLog>>,
+    tag_keys: usize,
+    max_tag_keys: usize,
+    limit_reached: bool,
+}
+
+impl CardinalityEstimates {
+    fn new(max_tag_keys: usize) -> Self {
+        Self {
+            metrics: HashMap::new(),
+            tag_keys: 0,
+            max_tag_keys,
+            limit_reached: false,
+        }
+    }
+
+    /// Adds the tags of a metric to the estimates, except for the tag keys which aren't estimated
+    /// yet once the maximum number of tag keys is reached.
+    fn insert(&mut self, metric_id: &MetricId, tags: &MetricTags) {
+        if !self.metrics.contains_key(metric_id) {
+            if self.tag_keys >= self.max_tag_keys {
+                self.reach_limit();
+                return;
+            }
+            self.metrics.insert(metric_id.clone(), HashMap::new());
+        }
+        let metric_estimates = self
+            .metrics
+            .get_mut(metric_id)
+            .expect("the estimates of the metric were just inserted");
+
+        let mut limit_reached = false;
+        for (key, value) in tags.iter_sets() {
+            if let Some(estimate) = metric_estimates.get_mut(key) {
+                estimate.insert(value);
+            } else if self.tag_keys < self.max_tag_keys {
+                self.tag_keys += 1;
+                metric_estimates.entry_ref(key).or_default().insert(value);
+            } else {
+                limit_reached = true;
+            }
+        }
+        if limit_reached {
+            self.reach_limit();
+        }
+    }
+
+    fn reach_limit(&mut self) {
+        if !self.limit_reached {
+            self.limit_reached = true;
+            emit!(TagCardinalityEstimatesLimitReached {
+                max_tag_keys: self.max_tag_keys,
+            });
+        }
+    }
+
+    /// Emits the estimates, and resets them so that the next ones only count the values received
+    /// from now on.
+    fn flush(&mut self) {
+        for ((namespace, name), metric_estimates) in self.metrics.drain() {
+            for (key, estimate) in metric_estimates {
+                emit!(TagCardinalityEstimated {
+                    metric_namespace: namespace.as_deref(),
+                    metric_name: &name,
+                    tag_key: &key,
+                    cardinality: estimate.estimate(),
+                });
+            }
+        }
+        self.tag_keys = 0;
+        self.limit_reached = false;
+    }
+}
+
 #[derive(Debug)]
 pub struct TagCardinalityLimit {
     config: TagCardinalityLimitConfig,
     accepted_tags: HashMap<Option<MetricId>, HashMap<String, AcceptedTagValueSet>>,
+    cardinality_estimates: Option<CardinalityEstimates>,
 }
 
 impl TagCardinalityLimit {
     fn new(config: TagCardinalityLimitConfig) -> Self {
+        let internal_metrics = &config.internal_metrics;
+        let cardinality_estimates = internal_metrics
+            .emit_cardinality_estimates
+            .then(|| CardinalityEstimates::new(internal_metrics.max_estimated_tag_keys.get()));
         Self {
             config,
             accepted_tags: HashMap::new(),
+            cardinality_estimates,
         }
     }
 
@@ -70,11 +161,17 @@ impl TagCardinalityLimit {
         key: &str,
         value: &TagValueSet,
     ) -> bool {
-        let config = self.get_config_for_metric(metric_key).clone();
+        let config = self.get_config_for_metric(metric_key);
+        if let Some(allowed) = listed_tag_value(config, key, value) {
+            return allowed;
+        }
+        let value_limit = config.value_limit;
+        let mode = config.mode.clone();
+
         let metric_accepted_tags = self.accepted_tags.entry(metric_key.cloned()).or_default();
         let tag_value_set = metric_accepted_tags
             .entry_ref(key)
-            .or_insert_with(|| AcceptedTagValueSet::new(config.value_limit, &config.mode));
+            .or_insert_with(|| AcceptedTagValueSet::new(value_limit, &mode));
 
         if tag_value_set.contains(value) {
             // Tag value has already been accepted, nothing more to do.
@@ -82,11 +179,11 @@ impl TagCardinalityLimit {
         }
 
         // Tag value not yet part of the accepted set.
-        if tag_value_set.len() < config.value_limit {
+        if tag_value_set.len() < value_limit {
             // accept the new value
             tag_value_set.insert(value.clone());
 
-            if tag_value_set.len() == config.value_limit {
+            if tag_value_set.len() == value_limit {
                 emit!(TagCardinalityValueLimitReached { key });
             }
 
@@ -105,6 +202,10 @@ impl TagCardinalityLimit {
         key: &str,
         value: &TagValueSet,
     ) -> bool {
+        if let Some(allowed) = listed_tag_value(self.get_config_for_metric(metric_key), key, value)
+        {
+            return !allowed;
+        }
         self.accepted_tags
             .get(&metric_key.cloned())
             .and_then(|metric_accepted_tags| {
@@ -117,15 +218,37 @@ impl TagCardinalityLimit {
     }
 
     /// Record a key and value corresponding to a tag on an incoming Metric.
+    ///
+    /// Allowed values don't count towards the limit, so they aren't recorded.
     fn record_tag_value(&mut self, metric_key: Option<&MetricId>, key: &str, value: &TagValueSet) {
-        let config = self.get_config_for_metric(metric_key).clone();
+        let config = self.get_config_for_metric(metric_key);
+        if listed_tag_value(config, key, value).is_some() {
+            return;
+        }
+        let value_limit = config.value_limit;
+        let mode = config.mode.clone();
+
         let metric_accepted_tags = self.accepted_tags.entry(metric_key.cloned()).or_default();
         metric_accepted_tags
             .entry_ref(key)
-            .or_insert_with(|| AcceptedTagValueSet::new(config.value_limit, &config.mode))
+            .or_insert_with(|| AcceptedTagValueSet::new(value_limit, &mode))
             .insert(value.clone());
     }
 
+    /// Adds the tags of an incoming Metric to the estimates of the cardinality of its tags, if
+    /// they are reported.
+    fn estimate_cardinalities(&mut self, metric_id: &MetricId, tags: &MetricTags) {
+        if let Some(cardinality_estimates) = self.cardinality_estimates.as_mut() {
+            cardinality_estimates.insert(metric_id, tags);
+        }
+    }
+
+    fn emit_cardinality_estimates(&mut self) {
+        if let Some(cardinality_estimates) = self.cardinality_estimates.as_mut() {
+            cardinality_estimates.flush();
+        }
+    }
+
     fn transform_one(&mut self, mut event: Event) -> Option<Event> {
         let metric = event.as_mut_metric();
         let metric_name = metric.name().to_string();
@@ -134,11 +257,12 @@ impl TagCardinalityLimit {
             *name == metric_name
                 && (config.namespace.is_none() || config.namespace == metric_namespace)
         });
-        let metric_key = if has_per_metric_config {
-            Some((metric_namespace, metric_name.clone()))
-        } else {
-            None
-        };
+        let metric_id = (metric_namespace, metric_name);
+        if let Some(tags) = metric.tags() {
+            self.estimate_cardinalities(&metric_id, tags);
+        }
+        let metric_key = has_per_metric_config.then(|| metric_id.clone());
+        let metric_name = metric_id.1;
         if let Some(tags_map) = metric.tags_mut() {
             match self
                 .get_config_for_metric(metric_key.as_ref())
@@ -176,21 +300,79 @@ impl TagCardinalityLimit {
                         }
                     });
                 }
+                LimitExceededAction::AggregateOverflow => {
+                    tags_map.retain(|key, value| {
+                        if !self.try_accept_tag(metric_key.as_ref(), key, value) {
+                            emit!(TagCardinalityLimitAggregatingTag {
+                                metric_name: &metric_name,
+                                tag_key: key,
+                                tag_value: &value.to_string(),
+                            });
+                            *value = TagValueSet::from([OVERFLOW_TAG_VALUE.to_string()]);
+                        }
+                        true
+                    });
+                }
             }
         }
         Some(event)
     }
 }
 
+/// Returns whether a tag value is always accepted or always over the limit, if it is listed in the
+/// `allowed_values` or `denied_values` of the configuration.
+fn listed_tag_value(
+    config: &TagCardinalityLimitInnerConfig,
+    key: &str,
+    value: &TagValueSet,
+) -> Option<bool> {
+    if let Some(denied) = config.denied_values.get(key)
+        && value.iter().flatten().any(|value| denied.contains(value))
+    {
+        return Some(false);
+    }
+    if let Some(allowed) = config.allowed_values.get(key)
+        && !value.is_empty()
+        && value
+            .iter()
+            .all(|value| value.is_some_and(|value| allowed.contains(value)))
+    {
+        return Some(true);
+    }
+    None
+}
+
 impl TaskTransform<Event> for TagCardinalityLimit {
     fn transform(
         self: Box<Self>,
-        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+        mut task: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
         Self: 'static,
     {
         let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        if inner.cardinality_estimates.is_none() {
+            return Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))));
+        }
+
+        let mut estimates_interval =
+            tokio::time::interval(inner.config.internal_metrics.estimates_interval_secs);
+        Box::pin(stream! {
+            loop {
+                let next = tokio::select! {
+                    _ = estimates_interval.tick() => None,
+                    event = task.next() => Some(event),
+                };
+                match next {
+                    None => inner.emit_cardinality_estimates(),
+                    Some(Some(event)) => {
+                        if let Some(event) = inner.transform_one(event) {
+                            yield event;
+                        }
+                    }
+                    Some(None) => break,
+                }
+            }
+        })
     }
 }
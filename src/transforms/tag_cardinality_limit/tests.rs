@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use config::PerMetricConfig;
@@ -53,8 +54,11 @@ fn make_transform_hashset(
             value_limit,
             limit_exceeded_action,
             mode: Mode::Exact,
+            allowed_values: HashMap::new(),
+            denied_values: HashMap::new(),
         },
         per_metric_limits: HashMap::new(),
+        internal_metrics: Default::default(),
     }
 }
 
@@ -69,12 +73,15 @@ fn make_transform_bloom(
             mode: Mode::Probabilistic(BloomFilterConfig {
                 cache_size_per_key: default_cache_size(),
             }),
+            allowed_values: HashMap::new(),
+            denied_values: HashMap::new(),
         },
         per_metric_limits: HashMap::new(),
+        internal_metrics: Default::default(),
     }
 }
 
-const fn make_transform_hashset_with_per_metric_limits(
+fn make_transform_hashset_with_per_metric_limits(
     value_limit: usize,
     limit_exceeded_action: LimitExceededAction,
     per_metric_limits: HashMap<String, PerMetricConfig>,
@@ -84,12 +91,15 @@ const fn make_transform_hashset_with_per_metric_limits(
             value_limit,
             limit_exceeded_action,
             mode: Mode::Exact,
+            allowed_values: HashMap::new(),
+            denied_values: HashMap::new(),
         },
         per_metric_limits,
+        internal_metrics: Default::default(),
     }
 }
 
-const fn make_transform_bloom_with_per_metric_limits(
+fn make_transform_bloom_with_per_metric_limits(
     value_limit: usize,
     limit_exceeded_action: LimitExceededAction,
     per_metric_limits: HashMap<String, PerMetricConfig>,
@@ -101,8 +111,11 @@ const fn make_transform_bloom_with_per_metric_limits(
             mode: Mode::Probabilistic(BloomFilterConfig {
                 cache_size_per_key: default_cache_size(),
             }),
+            allowed_values: HashMap::new(),
+            denied_values: HashMap::new(),
         },
         per_metric_limits,
+        internal_metrics: Default::default(),
     }
 }
 
@@ -592,3 +605,145 @@ async fn separate_value_limit_per_metric_name(config: TagCardinalityLimitConfig)
     })
     .await;
 }
+
+#[test]
+fn tag_cardinality_limit_aggregate_overflow_hashset() {
+    aggregate_overflow(make_transform_hashset(
+        2,
+        LimitExceededAction::AggregateOverflow,
+    ));
+}
+
+#[test]
+fn tag_cardinality_limit_aggregate_overflow_bloom() {
+    aggregate_overflow(make_transform_bloom(
+        2,
+        LimitExceededAction::AggregateOverflow,
+    ));
+}
+
+fn aggregate_overflow(config: TagCardinalityLimitConfig) {
+    let mut transform = TagCardinalityLimit::new(config);
+
+    let event1 = make_metric(metric_tags!("tag1" => "val1", "tag2" => "val1"));
+    let event2 = make_metric(metric_tags!("tag1" => "val2", "tag2" => "val1"));
+    let event3 = make_metric(metric_tags!("tag1" => "val3", "tag2" => "val1"));
+    let event4 = make_metric(metric_tags!("tag1" => "val4", "tag2" => "val1"));
+
+    assert_eq!(transform.transform_one(event1.clone()), Some(event1));
+    assert_eq!(transform.transform_one(event2.clone()), Some(event2));
+    for event in [event3, event4] {
+        // The values over the limit are replaced, instead of dropping the tag.
+        let new_event = transform.transform_one(event).unwrap();
+        assert_eq!(
+            new_event.as_metric().tags(),
+            Some(&metric_tags!("tag1" => "__other__", "tag2" => "val1"))
+        );
+    }
+}
+
+#[test]
+fn tag_cardinality_limit_allowed_and_denied_values() {
+    let mut config = make_transform_hashset(1, LimitExceededAction::DropTag);
+    config.global.allowed_values = HashMap::from([(
+        "tag1".to_string(),
+        HashSet::from(["ok1".to_string(), "ok2".to_string()]),
+    )]);
+    config.global.denied_values =
+        HashMap::from([("tag1".to_string(), HashSet::from(["bad".to_string()]))]);
+    let mut transform = TagCardinalityLimit::new(config);
+
+    let mut accepted = |value: &str| {
+        let event = make_metric(metric_tags!("tag1" => value));
+        let new_event = transform.transform_one(event).unwrap();
+        new_event.as_metric().tags().unwrap().contains_key("tag1")
+    };
+
+    // Denied values are over the limit even before it is reached.
+    assert!(!accepted("bad"));
+    // Allowed values don't count towards the limit.
+    assert!(accepted("ok1"));
+    assert!(accepted("val1"));
+    assert!(accepted("ok2"));
+    assert!(!accepted("val2"));
+    assert!(accepted("ok1"));
+}
+
+#[test]
+fn tag_cardinality_limit_denied_values_drop_event() {
+    let mut config = make_transform_hashset(2, LimitExceededAction::DropEvent);
+    config.global.denied_values =
+        HashMap::from([("tag1".to_string(), HashSet::from(["bad".to_string()]))]);
+    let mut transform = TagCardinalityLimit::new(config);
+
+    let event1 = make_metric(metric_tags!("tag1" => "val1"));
+    let event2 = make_metric(metric_tags!("tag1" => "bad"));
+
+    assert_eq!(transform.transform_one(event1.clone()), Some(event1));
+    assert_eq!(transform.transform_one(event2), None);
+}
+
+#[test]
+fn tag_cardinality_limit_estimates_cardinalities() {
+    let mut config = make_transform_hashset(2, LimitExceededAction::DropTag);
+    config.internal_metrics.emit_cardinality_estimates = true;
+    let mut transform = TagCardinalityLimit::new(config);
+
+    for i in 0..10 {
+        let event = make_metric(metric_tags!("tag1" => format!("val{i}"), "tag2" => "val1"));
+        transform.transform_one(event).unwrap();
+    }
+
+    // The values over the limit are counted as well.
+    let estimates = transform
+        .cardinality_estimates
+        .as_ref()
+        .unwrap()
+        .metrics
+        .get(&(None::<String>, "event".to_string()))
+        .unwrap();
+    assert!((estimates["tag1"].estimate() - 10.0).abs() < 1.0);
+    assert!((estimates["tag2"].estimate() - 1.0).abs() < 0.5);
+}
+
+#[test]
+fn tag_cardinality_limit_bounds_and_resets_estimates() {
+    let mut config = make_transform_hashset(2, LimitExceededAction::DropTag);
+    config.internal_metrics.emit_cardinality_estimates = true;
+    config.internal_metrics.max_estimated_tag_keys = NonZeroUsize::new(3).unwrap();
+    let mut transform = TagCardinalityLimit::new(config);
+
+    let estimated_tag_keys = |transform: &TagCardinalityLimit| {
+        let estimates = transform.cardinality_estimates.as_ref().unwrap();
+        let mut keys = estimates
+            .metrics
+            .iter()
+            .flat_map(|((_, name), metric_estimates)| {
+                metric_estimates
+                    .keys()
+                    .map(move |key| format!("{name}.{key}"))
+            })
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys
+    };
+
+    let event = make_metric(metric_tags!("tag1" => "val1", "tag2" => "val1"));
+    transform.transform_one(event).unwrap();
+    let event = make_metric(metric_tags!("tag1" => "val2", "tag3" => "val1", "tag4" => "val1"));
+    transform.transform_one(event).unwrap();
+    let other = make_metric_with_name(metric_tags!("tag1" => "val1"), "other");
+    transform.transform_one(other.clone()).unwrap();
+
+    // The tag keys received once the limit is reached aren't estimated.
+    assert_eq!(
+        estimated_tag_keys(&transform),
+        ["event.tag1", "event.tag2", "event.tag3"]
+    );
+
+    // The estimates are reset once they are emitted.
+    transform.emit_cardinality_estimates();
+    assert!(estimated_tag_keys(&transform).is_empty());
+    transform.transform_one(other).unwrap();
+    assert_eq!(estimated_tag_keys(&transform), ["other.tag1"]);
+}
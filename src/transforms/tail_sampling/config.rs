@@ -0,0 +1,132 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use serde_with::serde_as;
+use vector_lib::config::{LogNamespace, clone_input_definitions};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
+
+use super::{policy::PolicyConfig, transform::TailSampling};
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema,
+    transforms::Transform,
+};
+
+/// Configuration for the `tail_sampling` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "tail_sampling",
+    "Sample whole traces, based on policies evaluated once their spans are collected."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TailSamplingConfig {
+    /// The field holding the trace ID of each span.
+    ///
+    /// Spans without a trace ID are forwarded as is.
+    #[serde(default = "default_trace_id_field")]
+    #[configurable(metadata(docs::examples = "trace_id"))]
+    pub trace_id_field: ConfigTargetPath,
+
+    /// The field holding the ID of the parent of each span.
+    ///
+    /// Spans without a parent, or with an empty parent, are the root span of their trace. A trace
+    /// is considered complete once its root span is received.
+    #[serde(default = "default_parent_span_id_field")]
+    #[configurable(metadata(docs::examples = "parent_span_id"))]
+    pub parent_span_id_field: ConfigTargetPath,
+
+    /// The maximum amount of time to wait for the spans of a trace, from its first span, before
+    /// deciding whether to sample it.
+    ///
+    /// Spans received after the decision was made, such as spans ending after their root span,
+    /// follow the decision as long as it is remembered, which is for the same amount of time.
+    #[serde(default = "default_decision_wait")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Decision Wait"))]
+    pub decision_wait_secs: Duration,
+
+    /// The maximum number of traces waiting for a decision.
+    ///
+    /// Once reached, the decision on the oldest trace is made early to make room for new traces.
+    #[serde(default = "default_max_traces")]
+    pub max_traces: NonZeroUsize,
+
+    /// The policies deciding whether to sample a trace.
+    ///
+    /// A trace is sampled, with all of its spans forwarded, if any of the policies matches it.
+    /// Otherwise, all of its spans are dropped.
+    pub policies: Vec<PolicyConfig>,
+}
+
+fn default_trace_id_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("trace_id")
+}
+
+fn default_parent_span_id_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("parent_span_id")
+}
+
+const fn default_decision_wait() -> Duration {
+    Duration::from_secs(30)
+}
+
+const fn default_max_traces() -> NonZeroUsize {
+    NonZeroUsize::new(50_000).expect("static non-zero number")
+}
+
+impl GenerateConfig for TailSamplingConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc::indoc! {r#"
+            [[policies]]
+            type = "error"
+
+            [[policies]]
+            type = "latency"
+            threshold_ms = 500
+
+            [[policies]]
+            type = "probabilistic"
+            ratio = 0.1
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tail_sampling")]
+impl TransformConfig for TailSamplingConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        TailSampling::new(self, &context.enrichment_tables).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Trace)
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // Spans are not modified, so the definition is passed through as-is
+        vec![TransformOutput::new(
+            DataType::Trace,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TailSamplingConfig;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TailSamplingConfig>();
+    }
+}
@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use vector_lib::configurable::configurable_component;
+use vrl::event_path;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    event::{Event, Value},
+};
+
+/// The OTLP status code of spans which failed.
+const STATUS_CODE_ERROR: i64 = 2;
+
+/// A policy deciding whether to sample a trace.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The type of policy."))]
+pub enum PolicyConfig {
+    /// Samples traces containing at least one span whose `status.code` is `2`, the OTLP code of
+    /// failed operations.
+    Error,
+
+    /// Samples traces lasting at least a given amount of time.
+    ///
+    /// The duration of a trace is measured from the earliest `start_time_unix_nano` to the latest
+    /// `end_time_unix_nano` among its spans.
+    Latency {
+        /// The minimum duration of sampled traces.
+        #[configurable(metadata(docs::examples = 500))]
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        threshold_ms: u64,
+    },
+
+    /// Samples traces containing at least one span matching a condition, such as the value of
+    /// one of its attributes.
+    Condition {
+        /// The condition to match spans against.
+        #[configurable(metadata(
+            docs::examples = "'.attributes.\"http.route\" == \"/checkout\"'"
+        ))]
+        condition: AnyCondition,
+    },
+
+    /// Samples a ratio of traces, decided from a hash of their trace ID.
+    ///
+    /// Every instance of this transform samples the same traces, given the same ratio.
+    Probabilistic {
+        /// The ratio of traces to sample.
+        #[configurable(metadata(docs::examples = 0.1))]
+        #[configurable(validation(range(min = 0.0, max = 1.0)))]
+        ratio: f64,
+    },
+}
+
+impl PolicyConfig {
+    pub fn build(
+        &self,
+        enrichment_tables: &vector_lib::enrichment::TableRegistry,
+    ) -> crate::Result<Policy> {
+        Ok(match self {
+            Self::Error => Policy::Error,
+            Self::Latency { threshold_ms } => Policy::Latency(Duration::from_millis(*threshold_ms)),
+            Self::Condition { condition } => Policy::Condition(condition.build(enrichment_tables)?),
+            Self::Probabilistic { ratio } => {
+                if !(0.0..=1.0).contains(ratio) {
+                    return Err(format!(
+                        "`ratio` of probabilistic policies must be between 0 and 1, value: {ratio}"
+                    )
+                    .into());
+                }
+                Policy::Probabilistic {
+                    // The ratio is scaled to the width of the hash, as done by the `sample`
+                    // transform, to avoid precision issues.
+                    threshold: (ratio * (u64::MAX as u128) as f64) as u64,
+                }
+            }
+        })
+    }
+}
+
+/// A policy built from its configuration.
+#[derive(Clone)]
+pub enum Policy {
+    Error,
+    Latency(Duration),
+    Condition(Condition),
+    Probabilistic { threshold: u64 },
+}
+
+impl Policy {
+    /// Returns whether the trace with the given ID and spans should be sampled.
+    pub fn evaluate(&self, trace_id: &str, spans: &mut Vec<Event>) -> bool {
+        match self {
+            Self::Error => spans.iter().any(|span| {
+                span.as_trace()
+                    .get(event_path!("status", "code"))
+                    .and_then(Value::as_integer)
+                    == Some(STATUS_CODE_ERROR)
+            }),
+            Self::Latency(threshold) => {
+                let start = spans
+                    .iter()
+                    .filter_map(|span| timestamp(span, "start_time_unix_nano"))
+                    .min();
+                let end = spans
+                    .iter()
+                    .filter_map(|span| timestamp(span, "end_time_unix_nano"))
+                    .max();
+                match (start, end) {
+                    (Some(start), Some(end)) => (end - start)
+                        .to_std()
+                        .is_ok_and(|duration| duration >= *threshold),
+                    _ => false,
+                }
+            }
+            Self::Condition(condition) => {
+                // Conditions take ownership of the events they check.
+                let mut matched = false;
+                *spans = std::mem::take(spans)
+                    .into_iter()
+                    .map(|span| {
+                        if matched {
+                            return span;
+                        }
+                        let (result, span) = condition.check(span);
+                        matched = result;
+                        span
+                    })
+                    .collect();
+                matched
+            }
+            Self::Probabilistic { threshold } => seahash::hash(trace_id.as_bytes()) <= *threshold,
+        }
+    }
+}
+
+fn timestamp(span: &Event, field: &'static str) -> Option<DateTime<Utc>> {
+    span.as_trace()
+        .get(event_path!(field))
+        .and_then(Value::as_timestamp)
+        .copied()
+}
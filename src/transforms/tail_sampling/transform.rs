@@ -0,0 +1,424 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use futures::Stream;
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
+use vector_lib::stream::expiration_map::{Emitter, map_with_expiration};
+
+use super::{config::TailSamplingConfig, policy::Policy};
+use crate::{
+    event::{Event, Value},
+    internal_events::{TailSamplingSpansDiscarded, TailSamplingTraceEvicted},
+    transforms::TaskTransform,
+};
+
+/// The longest interval between checks for traces whose decision wait has elapsed.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+struct PendingTrace {
+    spans: Vec<Event>,
+    deadline: Instant,
+}
+
+pub struct TailSampling {
+    trace_id_field: ConfigTargetPath,
+    parent_span_id_field: ConfigTargetPath,
+    decision_wait: Duration,
+    max_traces: usize,
+    policies: Vec<Policy>,
+    pending: HashMap<String, PendingTrace>,
+    /// The deadline of each pending trace, in the order they were received. Traces decided on
+    /// before their deadline are skipped when popped.
+    deadlines: VecDeque<(Instant, String)>,
+    /// Whether recently decided traces were sampled, so that their late spans follow the decision.
+    decisions: HashMap<String, bool>,
+    /// The expiration of each decision, in the order they were made.
+    expirations: VecDeque<(Instant, String)>,
+}
+
+impl TailSampling {
+    pub fn new(
+        config: &TailSamplingConfig,
+        enrichment_tables: &vector_lib::enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        if config.policies.is_empty() {
+            return Err("at least one policy must be configured".into());
+        }
+        if config.decision_wait_secs.is_zero() {
+            return Err("`decision_wait_secs` must be greater than zero".into());
+        }
+        let policies = config
+            .policies
+            .iter()
+            .map(|policy| policy.build(enrichment_tables))
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self {
+            trace_id_field: config.trace_id_field.clone(),
+            parent_span_id_field: config.parent_span_id_field.clone(),
+            decision_wait: config.decision_wait_secs,
+            max_traces: config.max_traces.get(),
+            policies,
+            pending: HashMap::new(),
+            deadlines: VecDeque::new(),
+            decisions: HashMap::new(),
+            expirations: VecDeque::new(),
+        })
+    }
+
+    fn transform_one(&mut self, emitter: &mut Emitter<Event>, event: Event, now: Instant) {
+        let trace = event.as_trace();
+        let Some(trace_id) = trace
+            .get(&self.trace_id_field)
+            .map(|value| value.to_string_lossy().into_owned())
+        else {
+            emitter.emit(event);
+            return;
+        };
+        let is_root = match trace.get(&self.parent_span_id_field) {
+            None | Some(Value::Null) => true,
+            Some(Value::Bytes(parent)) => parent.is_empty(),
+            Some(_) => false,
+        };
+
+        if let Some(sampled) = self.decisions.get(&trace_id) {
+            if *sampled {
+                emitter.emit(event);
+            } else {
+                emit!(TailSamplingSpansDiscarded { count: 1 });
+            }
+            return;
+        }
+
+        match self.pending.get_mut(&trace_id) {
+            Some(pending) => pending.spans.push(event),
+            None => {
+                if self.pending.len() >= self.max_traces {
+                    emit!(TailSamplingTraceEvicted {
+                        max_traces: self.max_traces
+                    });
+                    self.decide_oldest(emitter, now);
+                }
+                let deadline = now + self.decision_wait;
+                self.deadlines.push_back((deadline, trace_id.clone()));
+                self.pending.insert(
+                    trace_id.clone(),
+                    PendingTrace {
+                        spans: vec![event],
+                        deadline,
+                    },
+                );
+            }
+        }
+
+        if is_root && let Some(pending) = self.pending.remove(&trace_id) {
+            self.decide(emitter, trace_id, pending.spans, now);
+        }
+    }
+
+    /// Decides on the traces whose decision wait has elapsed, and forgets expired decisions.
+    fn flush_into(&mut self, emitter: &mut Emitter<Event>, now: Instant) {
+        while let Some((deadline, _)) = self.deadlines.front()
+            && *deadline <= now
+        {
+            let (deadline, trace_id) = self.deadlines.pop_front().expect("front exists");
+            self.decide_pending(emitter, trace_id, deadline, now);
+        }
+
+        while let Some((expiration, _)) = self.expirations.front()
+            && *expiration <= now
+        {
+            let (_, trace_id) = self.expirations.pop_front().expect("front exists");
+            self.decisions.remove(&trace_id);
+        }
+    }
+
+    fn flush_all_into(&mut self, emitter: &mut Emitter<Event>, now: Instant) {
+        while let Some((deadline, trace_id)) = self.deadlines.pop_front() {
+            self.decide_pending(emitter, trace_id, deadline, now);
+        }
+    }
+
+    fn decide_oldest(&mut self, emitter: &mut Emitter<Event>, now: Instant) {
+        while let Some((deadline, trace_id)) = self.deadlines.pop_front() {
+            if self.decide_pending(emitter, trace_id, deadline, now) {
+                return;
+            }
+        }
+    }
+
+    /// Decides on a pending trace, unless it was already decided on. Returns whether a decision
+    /// was made.
+    fn decide_pending(
+        &mut self,
+        emitter: &mut Emitter<Event>,
+        trace_id: String,
+        deadline: Instant,
+        now: Instant,
+    ) -> bool {
+        // Trace IDs may be reused after their decision expired, so the deadline identifies which
+        // trace this is.
+        match self.pending.get(&trace_id) {
+            Some(pending) if pending.deadline == deadline => {
+                let pending = self.pending.remove(&trace_id).expect("trace is pending");
+                self.decide(emitter, trace_id, pending.spans, now);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn decide(
+        &mut self,
+        emitter: &mut Emitter<Event>,
+        trace_id: String,
+        mut spans: Vec<Event>,
+        now: Instant,
+    ) {
+        let sampled = self
+            .policies
+            .iter()
+            .any(|policy| policy.evaluate(&trace_id, &mut spans));
+        if sampled {
+            spans.into_iter().for_each(|span| emitter.emit(span));
+        } else {
+            emit!(TailSamplingSpansDiscarded { count: spans.len() });
+        }
+
+        // Decisions are bounded like pending traces, forgetting the oldest ones first.
+        while self.decisions.len() >= self.max_traces {
+            match self.expirations.pop_front() {
+                Some((_, expired)) => _ = self.decisions.remove(&expired),
+                None => break,
+            }
+        }
+        self.expirations
+            .push_back((now + self.decision_wait, trace_id.clone()));
+        self.decisions.insert(trace_id, sampled);
+    }
+}
+
+impl TaskTransform<Event> for TailSampling {
+    fn transform(
+        self: Box<Self>,
+        input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let check_interval = self.decision_wait.min(MAX_CHECK_INTERVAL);
+        Box::pin(map_with_expiration(
+            self,
+            input_rx,
+            check_interval,
+            |me, event, emitter| me.transform_one(emitter, event, Instant::now()),
+            |me, emitter| me.flush_into(emitter, Instant::now()),
+            |me, emitter| me.flush_all_into(emitter, Instant::now()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::TraceEvent, test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    fn config(policies: &str) -> TailSamplingConfig {
+        toml::from_str(&format!("decision_wait_secs = 10\n{policies}")).unwrap()
+    }
+
+    fn span(trace_id: &str, parent: &str, code: i64, start_ms: i64, end_ms: i64) -> Event {
+        let mut span = TraceEvent::default();
+        span.insert("trace_id", trace_id);
+        span.insert("parent_span_id", parent);
+        span.insert("status.code", code);
+        span.insert(
+            "start_time_unix_nano",
+            Utc.timestamp_millis_opt(start_ms).unwrap(),
+        );
+        span.insert(
+            "end_time_unix_nano",
+            Utc.timestamp_millis_opt(end_ms).unwrap(),
+        );
+        Event::Trace(span)
+    }
+
+    fn run(sampler: &mut TailSampling, spans: Vec<Event>, now: Instant) -> Vec<Event> {
+        let mut emitter = Emitter::new();
+        for span in spans {
+            sampler.transform_one(&mut emitter, span, now);
+        }
+        emitter.into_values()
+    }
+
+    fn flush(sampler: &mut TailSampling, now: Instant) -> Vec<Event> {
+        let mut emitter = Emitter::new();
+        sampler.flush_into(&mut emitter, now);
+        emitter.into_values()
+    }
+
+    fn build(config: &TailSamplingConfig) -> TailSampling {
+        TailSampling::new(config, &Default::default()).unwrap()
+    }
+
+    #[test]
+    fn samples_traces_with_errors() {
+        let mut sampler = build(&config("[[policies]]\ntype = \"error\""));
+        let now = Instant::now();
+
+        // Traces are buffered until their root span is received.
+        let out = run(
+            &mut sampler,
+            vec![span("a", "root", 2, 0, 10), span("b", "root", 1, 0, 10)],
+            now,
+        );
+        assert!(out.is_empty());
+        let out = run(&mut sampler, vec![span("a", "", 0, 0, 20)], now);
+        assert_eq!(out.len(), 2);
+        let out = run(&mut sampler, vec![span("b", "", 0, 0, 20)], now);
+        assert!(out.is_empty());
+
+        // Late spans follow the decision made for their trace.
+        let out = run(
+            &mut sampler,
+            vec![span("a", "root", 0, 0, 10), span("b", "root", 0, 0, 10)],
+            now,
+        );
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].as_trace().get("trace_id"), Some(&"a".into()));
+    }
+
+    #[test]
+    fn samples_slow_traces() {
+        let mut sampler = build(&config(
+            "[[policies]]\ntype = \"latency\"\nthreshold_ms = 500",
+        ));
+        let now = Instant::now();
+
+        let out = run(
+            &mut sampler,
+            vec![
+                span("fast", "root", 0, 100, 300),
+                span("fast", "", 0, 0, 400),
+                span("slow", "root", 0, 300, 600),
+                span("slow", "", 0, 0, 400),
+            ],
+            now,
+        );
+        assert_eq!(out.len(), 2);
+        assert!(
+            out.iter()
+                .all(|span| span.as_trace().get("trace_id") == Some(&"slow".into()))
+        );
+    }
+
+    #[test]
+    fn samples_traces_matching_conditions() {
+        let mut sampler = build(&config(
+            "[[policies]]\ntype = \"condition\"\ncondition = '.attributes.tenant == \"acme\"'",
+        ));
+        let now = Instant::now();
+
+        let mut matching = span("a", "root", 0, 0, 10);
+        matching.as_mut_trace().insert("attributes.tenant", "acme");
+        let out = run(
+            &mut sampler,
+            vec![
+                matching,
+                span("a", "", 0, 0, 10),
+                span("b", "root", 0, 0, 10),
+                span("b", "", 0, 0, 10),
+            ],
+            now,
+        );
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[1].as_trace().get("trace_id"), Some(&"a".into()));
+    }
+
+    #[test]
+    fn samples_traces_probabilistically() {
+        let mut always = build(&config(
+            "[[policies]]\ntype = \"probabilistic\"\nratio = 1.0",
+        ));
+        let mut never = build(&config(
+            "[[policies]]\ntype = \"probabilistic\"\nratio = 0.0",
+        ));
+        let now = Instant::now();
+        let spans = (0..100)
+            .map(|idx| span(&idx.to_string(), "", 0, 0, 10))
+            .collect::<Vec<_>>();
+
+        assert_eq!(run(&mut always, spans.clone(), now).len(), 100);
+        assert!(run(&mut never, spans, now).is_empty());
+    }
+
+    #[test]
+    fn decides_after_the_decision_wait() {
+        let mut sampler = build(&config("[[policies]]\ntype = \"error\""));
+        let now = Instant::now();
+
+        run(&mut sampler, vec![span("a", "root", 2, 0, 10)], now);
+        assert!(flush(&mut sampler, now + Duration::from_secs(9)).is_empty());
+        assert_eq!(flush(&mut sampler, now + Duration::from_secs(10)).len(), 1);
+
+        // The decision is forgotten once it expires.
+        assert!(sampler.decisions.contains_key("a"));
+        flush(&mut sampler, now + Duration::from_secs(20));
+        assert!(sampler.decisions.is_empty());
+    }
+
+    #[test]
+    fn decides_early_when_full() {
+        let mut config = config("[[policies]]\ntype = \"error\"");
+        config.max_traces = 2.try_into().unwrap();
+        let mut sampler = build(&config);
+        let now = Instant::now();
+
+        let out = run(
+            &mut sampler,
+            vec![
+                span("a", "root", 2, 0, 10),
+                span("b", "root", 2, 0, 10),
+                span("c", "root", 2, 0, 10),
+            ],
+            now,
+        );
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].as_trace().get("trace_id"), Some(&"a".into()));
+        assert_eq!(sampler.pending.len(), 2);
+    }
+
+    #[test]
+    fn forwards_spans_without_trace_id() {
+        let mut sampler = build(&config("[[policies]]\ntype = \"error\""));
+        let event = Event::Trace(TraceEvent::default());
+        assert_eq!(run(&mut sampler, vec![event], Instant::now()).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn emits_internal_events() {
+        assert_transform_compliance(async move {
+            let config = config("[[policies]]\ntype = \"probabilistic\"\nratio = 1.0");
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(span("a", "", 0, 0, 10)).await.unwrap();
+            assert!(out.recv().await.is_some());
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+}
@@ -1,10 +1,11 @@
-use governor::clock;
 use serde_with::serde_as;
-use std::time::Duration;
+use std::{num::NonZeroUsize, time::Duration};
 use vector_lib::config::{LogNamespace, clone_input_definitions};
 use vector_lib::configurable::configurable_component;
 
-use super::transform::Throttle;
+#[cfg(feature = "transforms-throttle-redis")]
+use super::redis::RedisBackendConfig;
+use super::{rate_limiter::TokioClock, transform::Throttle};
 use crate::{
     conditions::AnyCondition,
     config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
@@ -31,10 +32,53 @@ pub struct ThrottleInternalMetricsConfig {
     pub emit_events_discarded_per_key: bool,
 }
 
+/// What happens to the events over the rate limits.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleMode {
+    /// The events over the rate limits are dropped.
+    #[default]
+    Drop,
+
+    /// The events over the rate limits are delayed until their bucket allows them.
+    ///
+    /// Delayed events are held in a queue of up to `max_queued_events` events. Once the queue is
+    /// full, no more events are accepted, which applies backpressure to the upstream components.
+    /// Events of different buckets don't delay each other.
+    ///
+    /// This mode can't be used with the `redis` backend.
+    Shape,
+}
+
+const fn default_max_queued_events() -> NonZeroUsize {
+    NonZeroUsize::new(1000).expect("static non-zero number")
+}
+
+/// Where the rate limits are held.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "Where the rate limits are held."))]
+pub enum ThrottleBackend {
+    /// The rate limits are held in memory, and apply to each Vector instance separately.
+    #[default]
+    Local,
+
+    /// The rate limits are held in Redis, and shared by all the Vector instances using the same
+    /// Redis server and key prefix.
+    ///
+    /// The rate limits of the events which are ready are checked at once, using a single request
+    /// to Redis. While the Redis server can't be reached, the rate limits are applied to each
+    /// instance separately, as with the `local` backend.
+    #[cfg(feature = "transforms-throttle-redis")]
+    Redis(RedisBackendConfig),
+}
+
 /// Configuration for the `throttle` transform.
 #[serde_as]
 #[configurable_component(transform("throttle", "Rate limit logs passing through a topology."))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct ThrottleConfig {
     /// The number of events allowed for a given bucket per configured `window_secs`.
@@ -60,6 +104,33 @@ pub struct ThrottleConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub internal_metrics: ThrottleInternalMetricsConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub backend: ThrottleBackend,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: ThrottleMode,
+
+    /// The maximum number of events delayed at once in the `shape` mode.
+    #[serde(default = "default_max_queued_events")]
+    pub max_queued_events: NonZeroUsize,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0,
+            window_secs: Duration::ZERO,
+            key_field: None,
+            exclude: None,
+            internal_metrics: Default::default(),
+            backend: Default::default(),
+            mode: Default::default(),
+            max_queued_events: default_max_queued_events(),
+        }
+    }
 }
 
 impl_generate_config_from_default!(ThrottleConfig);
@@ -68,7 +139,7 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        Throttle::new(self, context, TokioClock).map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
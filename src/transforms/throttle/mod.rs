@@ -1,3 +1,6 @@
 pub mod config;
 pub mod rate_limiter;
+#[cfg(feature = "transforms-throttle-redis")]
+pub mod redis;
+pub mod shaping;
 pub mod transform;
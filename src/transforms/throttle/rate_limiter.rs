@@ -1,4 +1,4 @@
-use governor::clock;
+use governor::clock::{self, Clock as _};
 use governor::middleware::NoOpMiddleware;
 use governor::state::keyed::DashMapStateStore;
 use governor::{Quota, RateLimiter};
@@ -7,6 +7,18 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio;
 
+/// A clock following the time of the Tokio runtime, which unit tests can pause and advance.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioClock;
+
+impl clock::Clock for TokioClock {
+    type Instant = std::time::Instant;
+
+    fn now(&self) -> Self::Instant {
+        tokio::time::Instant::now().into_std()
+    }
+}
+
 /// Re-usable wrapper around the structs/type from the governor crate.
 /// Spawns a background task that periodically flushes keys that haven't been accessed recently.
 pub struct RateLimiterRunner<K, C>
@@ -15,6 +27,7 @@ where
     C: clock::Clock,
 {
     pub rate_limiter: Arc<RateLimiter<K, DashMapStateStore<K>, C, NoOpMiddleware<C::Instant>>>,
+    clock: C,
     flush_handle: tokio::task::JoinHandle<()>,
 }
 
@@ -24,7 +37,7 @@ where
     C: clock::Clock + Clone + Send + Sync + 'static,
 {
     pub fn start(quota: Quota, clock: C, flush_keys_interval: Duration) -> Self {
-        let rate_limiter = Arc::new(RateLimiter::dashmap_with_clock(quota, clock));
+        let rate_limiter = Arc::new(RateLimiter::dashmap_with_clock(quota, clock.clone()));
 
         let rate_limiter_clone = Arc::clone(&rate_limiter);
         let flush_handle = tokio::spawn(async move {
@@ -37,6 +50,7 @@ where
 
         Self {
             rate_limiter,
+            clock,
             flush_handle,
         }
     }
@@ -44,6 +58,14 @@ where
     pub fn check_key(&self, key: &K) -> bool {
         self.rate_limiter.check_key(key).is_ok()
     }
+
+    /// Checks the rate limit of a key, returning how long to wait before it allows an event
+    /// otherwise.
+    pub fn check_key_or_wait(&self, key: &K) -> Result<(), Duration> {
+        self.rate_limiter
+            .check_key(key)
+            .map_err(|not_until| not_until.wait_time_from(self.clock.now()))
+    }
 }
 
 impl<K, C> Drop for RateLimiterRunner<K, C>
@@ -0,0 +1,334 @@
+use std::time::Duration;
+
+use indexmap::IndexMap;
+use redis::{Client, RedisResult, Script, aio::ConnectionManager};
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use tokio::time::Instant;
+use vector_lib::configurable::configurable_component;
+
+use crate::internal_events::ThrottleBackendError;
+
+/// How long rate limits are applied locally after the Redis server couldn't be reached, before
+/// trying to reach it again.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Takes tokens from the buckets given as keys, returning the number of tokens granted in each.
+///
+/// Each bucket holds up to `capacity` tokens, and is refilled at a rate of `capacity` tokens per
+/// `window_ms` milliseconds, from the clock of the Redis server so that all the instances agree on
+/// it. Buckets are deleted once they have been full for a whole window.
+const TOKEN_BUCKET_SCRIPT: &str = r"
+local capacity = tonumber(ARGV[1])
+local window_ms = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local granted = {}
+for i, key in ipairs(KEYS) do
+    local requested = tonumber(ARGV[i + 2])
+    local bucket = redis.call('HMGET', key, 'tokens', 'updated')
+    local tokens = tonumber(bucket[1]) or capacity
+    local updated = tonumber(bucket[2]) or now
+    tokens = math.min(capacity, tokens + math.max(0, now - updated) * capacity / window_ms)
+    local allowed = math.min(requested, math.floor(tokens))
+    redis.call('HSET', key, 'tokens', tostring(tokens - allowed), 'updated', now)
+    redis.call('PEXPIRE', key, window_ms)
+    granted[i] = allowed
+end
+return granted
+";
+
+const fn default_timeout() -> Duration {
+    Duration::from_millis(500)
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create Redis client: {source}"))]
+    RedisCreateFailed { source: redis::RedisError },
+}
+
+/// Configuration of the Redis backend.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedisBackendConfig {
+    /// The URL of the Redis server.
+    ///
+    /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be
+    /// `redis` or `rediss` for connections secured using TLS.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    pub endpoint: String,
+
+    /// The prefix of the Redis keys holding the rate limits.
+    ///
+    /// The instances sharing rate limits must use the same prefix. By default, the prefix is
+    /// `vector:throttle:` followed by the ID of the component and a `:`.
+    ///
+    /// The bucket of each key is held in the prefix followed by `key:` and the key, and the bucket
+    /// of the events without a key in the prefix followed by `none`.
+    #[configurable(metadata(docs::examples = "vector:throttle:audit_logs:"))]
+    pub key_prefix: Option<String>,
+
+    /// The timeout of the requests to the Redis server, in seconds.
+    ///
+    /// Events are rate limited locally while the server can't be reached.
+    #[serde(default = "default_timeout")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Timeout"))]
+    pub timeout_secs: Duration,
+}
+
+/// A rate limiter whose buckets are held in Redis, so that they are shared by several instances.
+#[derive(Clone)]
+pub struct RedisRateLimiter {
+    client: Client,
+    connection: Option<ConnectionManager>,
+    script: Script,
+    key_prefix: String,
+    capacity: u32,
+    window_ms: u64,
+    timeout: Duration,
+    retry_at: Option<Instant>,
+}
+
+impl RedisRateLimiter {
+    pub fn new(
+        config: &RedisBackendConfig,
+        component_id: Option<&str>,
+        capacity: u32,
+        window: Duration,
+    ) -> crate::Result<Self> {
+        let client = Client::open(config.endpoint.as_str()).context(RedisCreateFailedSnafu)?;
+        let key_prefix = config
+            .key_prefix
+            .clone()
+            .unwrap_or_else(|| match component_id {
+                Some(id) => format!("vector:throttle:{id}:"),
+                None => "vector:throttle:".to_owned(),
+            });
+
+        Ok(Self {
+            client,
+            connection: None,
+            script: Script::new(TOKEN_BUCKET_SCRIPT),
+            key_prefix,
+            capacity,
+            window_ms: window.as_millis().max(1) as u64,
+            timeout: config.timeout_secs,
+            retry_at: None,
+        })
+    }
+
+    /// Takes the requested number of tokens from each bucket, returning the number of tokens
+    /// granted in each, in the order of the requests.
+    ///
+    /// Returns `None` if the Redis server couldn't be reached, in which case the rate limits must
+    /// be applied locally.
+    pub async fn acquire(&mut self, requests: &IndexMap<Option<String>, u32>) -> Option<Vec<u32>> {
+        if self
+            .retry_at
+            .is_some_and(|retry_at| Instant::now() < retry_at)
+        {
+            return None;
+        }
+
+        let error = match tokio::time::timeout(self.timeout, self.try_acquire(requests)).await {
+            Ok(Ok(granted)) if granted.len() == requests.len() => {
+                self.retry_at = None;
+                return Some(granted);
+            }
+            Ok(Ok(granted)) => format!(
+                "expected {} buckets in the response, got {}",
+                requests.len(),
+                granted.len()
+            ),
+            Ok(Err(error)) => error.to_string(),
+            Err(_) => format!(
+                "request exceeded the timeout of {}s",
+                self.timeout.as_secs_f64()
+            ),
+        };
+
+        emit!(ThrottleBackendError { error });
+        self.retry_at = Some(Instant::now() + RETRY_DELAY);
+        None
+    }
+
+    /// Returns the Redis key of a bucket.
+    ///
+    /// Events without a key, as `key_field` isn't set or couldn't be rendered, share a bucket
+    /// distinct from the one of events whose key is empty.
+    fn bucket_key(&self, key: Option<&str>) -> String {
+        match key {
+            Some(key) => format!("{}key:{key}", self.key_prefix),
+            None => format!("{}none", self.key_prefix),
+        }
+    }
+
+    async fn try_acquire(
+        &mut self,
+        requests: &IndexMap<Option<String>, u32>,
+    ) -> RedisResult<Vec<u32>> {
+        if self.connection.is_none() {
+            self.connection = Some(self.client.get_connection_manager().await?);
+        }
+        let connection = self.connection.as_mut().expect("connection was just set");
+
+        let mut invocation = self.script.prepare_invoke();
+        invocation.arg(self.capacity).arg(self.window_ms);
+        for (key, count) in requests {
+            invocation.key(self.bucket_key(key.as_deref())).arg(*count);
+        }
+        invocation.invoke_async(connection).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_keys_are_distinct() {
+        let config = toml::from_str::<RedisBackendConfig>(
+            r#"
+            endpoint = "redis://127.0.0.1:6379/0"
+            key_prefix = "throttle:"
+        "#,
+        )
+        .unwrap();
+        let limiter = RedisRateLimiter::new(&config, None, 1, Duration::from_secs(1)).unwrap();
+
+        assert_eq!(limiter.bucket_key(None), "throttle:none");
+        assert_eq!(limiter.bucket_key(Some("")), "throttle:key:");
+        assert_eq!(limiter.bucket_key(Some("none")), "throttle:key:none");
+    }
+}
+
+#[cfg(all(test, feature = "redis-integration-tests"))]
+mod integration_tests {
+    use std::time::Duration;
+
+    use futures::{StreamExt, stream};
+    use governor::clock;
+    use vector_lib::event::{Event, LogEvent};
+
+    use crate::{
+        config::TransformContext,
+        test_util::random_string,
+        transforms::{
+            Transform,
+            throttle::{config::ThrottleConfig, transform::Throttle},
+        },
+    };
+
+    fn redis_server() -> String {
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/0".to_owned())
+    }
+
+    async fn run_instance(config: &ThrottleConfig, count: usize) -> usize {
+        let throttle = Throttle::new(
+            config,
+            &TransformContext::default(),
+            clock::FakeRelativeClock::default(),
+        )
+        .map(Transform::event_task)
+        .unwrap()
+        .into_task();
+
+        let events = (0..count)
+            .map(|_| Event::from(LogEvent::default()))
+            .collect::<Vec<_>>();
+        throttle
+            .transform_events(Box::pin(stream::iter(events)))
+            .count()
+            .await
+    }
+
+    #[tokio::test]
+    async fn shares_rate_limits_between_instances() {
+        let config = toml::from_str::<ThrottleConfig>(&format!(
+            r#"
+            threshold = 10
+            window_secs = 60
+            backend.type = "redis"
+            backend.endpoint = "{}"
+            backend.key_prefix = "vector:throttle:{}:"
+        "#,
+            redis_server(),
+            random_string(10)
+        ))
+        .unwrap();
+
+        let first = run_instance(&config, 8).await;
+        let second = run_instance(&config, 8).await;
+        assert_eq!(first, 8);
+        assert_eq!(second, 2);
+    }
+
+    #[tokio::test]
+    async fn buckets_are_separate() {
+        let config = toml::from_str::<ThrottleConfig>(&format!(
+            r#"
+            threshold = 1
+            window_secs = 60
+            key_field = "{{{{ bucket }}}}"
+            backend.type = "redis"
+            backend.endpoint = "{}"
+            backend.key_prefix = "vector:throttle:{}:"
+        "#,
+            redis_server(),
+            random_string(10)
+        ))
+        .unwrap();
+
+        let throttle = Throttle::new(
+            &config,
+            &TransformContext::default(),
+            clock::FakeRelativeClock::default(),
+        )
+        .map(Transform::event_task)
+        .unwrap()
+        .into_task();
+
+        let events = ["a", "b", "a", "c"].map(|bucket| {
+            let mut log = LogEvent::default();
+            log.insert("bucket", bucket);
+            Event::from(log)
+        });
+        let output = throttle
+            .transform_events(Box::pin(stream::iter(events)))
+            .map(|event| {
+                event
+                    .as_log()
+                    .get("bucket")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output, ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn bucket_refills_over_window() {
+        let config = toml::from_str::<ThrottleConfig>(&format!(
+            r#"
+            threshold = 2
+            window_secs = 0.2
+            backend.type = "redis"
+            backend.endpoint = "{}"
+            backend.key_prefix = "vector:throttle:{}:"
+        "#,
+            redis_server(),
+            random_string(10)
+        ))
+        .unwrap();
+
+        assert_eq!(run_instance(&config, 3).await, 2);
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(run_instance(&config, 3).await, 2);
+    }
+}
@@ -0,0 +1,108 @@
+use std::{collections::VecDeque, time::Duration};
+
+use governor::clock;
+use indexmap::IndexMap;
+use tokio::time::Instant;
+
+use super::rate_limiter::RateLimiterRunner;
+use crate::{
+    event::Event,
+    internal_events::{ThrottleEventDelayed, ThrottleQueuedEvents},
+};
+
+/// The events held back by the `shape` mode until their bucket allows them.
+///
+/// Events are queued per bucket, so that a bucket over its rate limit doesn't delay the events of
+/// the other buckets, and are released in the order they were received in each bucket.
+pub struct ShapingQueue {
+    queues: IndexMap<Option<String>, VecDeque<(Event, Instant)>>,
+    len: usize,
+    capacity: usize,
+    deadline: Option<Instant>,
+}
+
+impl ShapingQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queues: IndexMap::new(),
+            len: 0,
+            capacity,
+            deadline: None,
+        }
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len >= self.capacity
+    }
+
+    /// Returns when the next queued event can be released, if any events are queued.
+    pub const fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Queues an event behind the other events of its bucket, or returns it if it can be sent
+    /// right away.
+    pub fn push<C>(
+        &mut self,
+        key: Option<String>,
+        event: Event,
+        limiter: &RateLimiterRunner<Option<String>, C>,
+    ) -> Option<Event>
+    where
+        C: clock::Clock + Clone + Send + Sync + 'static,
+    {
+        let now = Instant::now();
+        match self.queues.get_mut(&key) {
+            Some(queue) => queue.push_back((event, now)),
+            None => match limiter.check_key_or_wait(&key) {
+                Ok(()) => return Some(event),
+                Err(wait) => {
+                    self.schedule(now + wait);
+                    self.queues.insert(key, VecDeque::from([(event, now)]));
+                }
+            },
+        }
+
+        self.len += 1;
+        emit!(ThrottleQueuedEvents { count: self.len });
+        None
+    }
+
+    /// Releases the queued events that their buckets allow.
+    pub fn release<C>(&mut self, limiter: &RateLimiterRunner<Option<String>, C>) -> Vec<Event>
+    where
+        C: clock::Clock + Clone + Send + Sync + 'static,
+    {
+        let now = Instant::now();
+        let mut released = Vec::new();
+        let mut deadline = None::<Instant>;
+        self.queues.retain(|key, queue| {
+            while !queue.is_empty() {
+                match limiter.check_key_or_wait(key) {
+                    Ok(()) => {
+                        let (event, queued_at) = queue.pop_front().expect("queue is not empty");
+                        emit!(ThrottleEventDelayed {
+                            delay: now.saturating_duration_since(queued_at),
+                        });
+                        released.push(event);
+                    }
+                    Err(wait) => {
+                        let next = now + wait.max(Duration::from_millis(1));
+                        deadline = Some(deadline.map_or(next, |deadline| deadline.min(next)));
+                        break;
+                    }
+                }
+            }
+            !queue.is_empty()
+        });
+
+        self.deadline = deadline;
+        self.len -= released.len();
+        emit!(ThrottleQueuedEvents { count: self.len });
+        released
+    }
+
+    fn schedule(&mut self, at: Instant) {
+        self.deadline = Some(self.deadline.map_or(at, |deadline| deadline.min(at)));
+    }
+}
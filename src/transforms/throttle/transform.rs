@@ -1,13 +1,18 @@
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use governor::{Quota, clock};
+#[cfg(feature = "transforms-throttle-redis")]
+use indexmap::IndexMap;
 use snafu::Snafu;
 use std::hash::Hash;
 use std::{num::NonZeroU32, pin::Pin, time::Duration};
 
+#[cfg(feature = "transforms-throttle-redis")]
+use super::{config::ThrottleBackend, redis::RedisRateLimiter};
 use super::{
-    config::{ThrottleConfig, ThrottleInternalMetricsConfig},
+    config::{ThrottleConfig, ThrottleInternalMetricsConfig, ThrottleMode},
     rate_limiter::RateLimiterRunner,
+    shaping::ShapingQueue,
 };
 use crate::{
     conditions::Condition,
@@ -18,6 +23,10 @@ use crate::{
     transforms::TaskTransform,
 };
 
+/// The maximum number of events whose rate limits are checked at once against a shared backend.
+#[cfg(feature = "transforms-throttle-redis")]
+const MAX_SHARED_BATCH_SIZE: usize = 1000;
+
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     pub quota: Quota,
@@ -26,6 +35,9 @@ pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     exclude: Option<Condition>,
     pub clock: C,
     internal_metrics: ThrottleInternalMetricsConfig,
+    #[cfg(feature = "transforms-throttle-redis")]
+    shared_limiter: Option<RedisRateLimiter>,
+    max_queued_events: Option<usize>,
 }
 
 impl<C, I> Throttle<C, I>
@@ -56,6 +68,24 @@ where
             .as_ref()
             .map(|condition| condition.build(&context.enrichment_tables))
             .transpose()?;
+        #[cfg(feature = "transforms-throttle-redis")]
+        let shared_limiter = match &config.backend {
+            ThrottleBackend::Local => None,
+            ThrottleBackend::Redis(backend) => Some(RedisRateLimiter::new(
+                backend,
+                context.key.as_ref().map(|key| key.id()),
+                threshold.get(),
+                flush_keys_interval,
+            )?),
+        };
+        let max_queued_events = match config.mode {
+            ThrottleMode::Drop => None,
+            #[cfg(feature = "transforms-throttle-redis")]
+            ThrottleMode::Shape if shared_limiter.is_some() => {
+                return Err(Box::new(ConfigError::ShapingWithSharedBackend));
+            }
+            ThrottleMode::Shape => Some(config.max_queued_events.get()),
+        };
 
         Ok(Self {
             quota,
@@ -64,6 +94,9 @@ where
             key_field: config.key_field.clone(),
             exclude,
             internal_metrics: config.internal_metrics.clone(),
+            #[cfg(feature = "transforms-throttle-redis")]
+            shared_limiter,
+            max_queued_events,
         })
     }
 
@@ -75,6 +108,33 @@ where
         RateLimiterRunner::start(self.quota, self.clock.clone(), self.flush_keys_interval)
     }
 
+    /// Returns whether an event is rate limited, along with the key of its bucket.
+    fn bucket(&self, event: Event) -> (Event, Option<Option<String>>) {
+        let (throttle, event) = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                (!result, event)
+            }
+            _ => (true, event),
+        };
+        if !throttle {
+            return (event, None);
+        }
+
+        let key = self.key_field.as_ref().and_then(|t| {
+            t.render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+        (event, Some(key))
+    }
+
     pub fn emit_event_discarded(&self, key: String) {
         emit!(ThrottleEventDiscarded {
             key,
@@ -97,36 +157,108 @@ where
     {
         let limiter = self.start_rate_limiter();
 
+        #[cfg(feature = "transforms-throttle-redis")]
+        if let Some(mut shared_limiter) = self.shared_limiter.clone() {
+            // The events which are ready are checked at once, so that the shared backend is only
+            // requested once for each of their buckets.
+            let mut input_rx = input_rx.ready_chunks(MAX_SHARED_BATCH_SIZE);
+            return Box::pin(stream! {
+                while let Some(events) = input_rx.next().await {
+                    let events = events
+                        .into_iter()
+                        .map(|event| self.bucket(event))
+                        .collect::<Vec<_>>();
+                    let mut requests = IndexMap::<Option<String>, u32>::new();
+                    for key in events.iter().filter_map(|(_, key)| key.as_ref()) {
+                        *requests.entry(key.clone()).or_default() += 1;
+                    }
+                    let granted = if requests.is_empty() {
+                        None
+                    } else {
+                        shared_limiter.acquire(&requests).await
+                    };
+                    if let Some(granted) = &granted {
+                        for (remaining, granted) in requests.values_mut().zip(granted) {
+                            *remaining = *granted;
+                        }
+                    }
+
+                    for (event, key) in events {
+                        let Some(key) = key else {
+                            yield event;
+                            continue;
+                        };
+                        let allowed = if granted.is_some() {
+                            let remaining = requests
+                                .get_mut(&key)
+                                .expect("every bucket is requested");
+                            let allowed = *remaining > 0;
+                            *remaining = remaining.saturating_sub(1);
+                            allowed
+                        } else {
+                            // The shared backend can't be reached, so the limits are applied to
+                            // this instance alone.
+                            limiter.check_key(&key)
+                        };
+                        if allowed {
+                            yield event;
+                        } else {
+                            self.emit_event_discarded(key.unwrap_or_else(|| "None".to_string()));
+                        }
+                    }
+                }
+            });
+        }
+
+        if let Some(max_queued_events) = self.max_queued_events {
+            // Events over the rate limit of their bucket are held back until it allows them. Once
+            // the queue is full, no more events are read, applying backpressure upstream.
+            let mut queue = ShapingQueue::new(max_queued_events);
+            return Box::pin(stream! {
+                let mut input_done = false;
+                loop {
+                    let deadline = queue.deadline();
+                    let release_at = deadline.unwrap_or_else(tokio::time::Instant::now);
+                    let next = tokio::select! {
+                        biased;
+
+                        _ = tokio::time::sleep_until(release_at), if deadline.is_some() => None,
+                        event = input_rx.next(), if !input_done && !queue.is_full() => Some(event),
+                        else => break,
+                    };
+
+                    match next {
+                        None => {
+                            for event in queue.release(&limiter) {
+                                yield event;
+                            }
+                        }
+                        Some(Some(event)) => match self.bucket(event) {
+                            (event, Some(key)) => {
+                                if let Some(event) = queue.push(key, event, &limiter) {
+                                    yield event;
+                                }
+                            }
+                            (event, None) => yield event,
+                        },
+                        Some(None) => input_done = true,
+                    }
+                }
+            });
+        }
+
         Box::pin(stream! {
             while let Some(event) = input_rx.next().await {
-                let (throttle, event) = match self.exclude.as_ref() {
-                    Some(condition) => {
-                        let (result, event) = condition.check(event);
-                        (!result, event)
-                    },
-                    _ => (true, event)
-                };
-                let output = if throttle {
-                    let key = self.key_field.as_ref().and_then(|t| {
-                        t.render_string(&event)
-                            .map_err(|error| {
-                                emit!(TemplateRenderingError {
-                                    error,
-                                    field: Some("key_field"),
-                                    drop_event: false,
-                                })
-                            })
-                            .ok()
-                    });
-
-                    if limiter.check_key(&key) {
-                        Some(event)
-                    } else {
-                        self.emit_event_discarded(key.unwrap_or_else(|| "None".to_string()));
-                        None
+                let output = match self.bucket(event) {
+                    (event, Some(key)) => {
+                        if limiter.check_key(&key) {
+                            Some(event)
+                        } else {
+                            self.emit_event_discarded(key.unwrap_or_else(|| "None".to_string()));
+                            None
+                        }
                     }
-                } else {
-                    Some(event)
+                    (event, None) => Some(event),
                 };
                 if let Some(event) = output {
                     yield event;
@@ -140,16 +272,20 @@ where
 pub enum ConfigError {
     #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
     NonZero,
+
+    #[cfg(feature = "transforms-throttle-redis")]
+    #[snafu(display("The `shape` mode can't be used with the `redis` backend"))]
+    ShapingWithSharedBackend,
 }
 
 #[cfg(test)]
 mod tests {
-    use std::task::Poll;
+    use std::{num::NonZeroUsize, task::Poll};
 
     use futures::SinkExt;
 
     use super::*;
-    use crate::transforms::Transform;
+    use crate::transforms::{Transform, throttle::rate_limiter::TokioClock};
     use crate::{
         event::LogEvent, test_util::components::assert_transform_compliance,
         transforms::test::create_topology,
@@ -361,6 +497,137 @@ key_field = "{{ bucket }}"
         assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
     }
 
+    #[cfg(feature = "transforms-throttle-redis")]
+    #[tokio::test]
+    async fn throttle_falls_back_to_local_limits() {
+        let clock = clock::FakeRelativeClock::default();
+        let config = toml::from_str::<ThrottleConfig>(&format!(
+            r#"
+threshold = 2
+window_secs = 5
+backend.type = "redis"
+backend.endpoint = "redis://{}/0"
+"#,
+            crate::test_util::next_addr()
+        ))
+        .unwrap();
+
+        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
+            .map(Transform::event_task)
+            .unwrap();
+
+        let throttle = throttle.into_task();
+
+        // No Redis server listens on the endpoint, so the events are rate limited locally.
+        let events = (0..3).map(|_| LogEvent::default().into());
+        let out_stream = throttle.transform_events(Box::pin(futures::stream::iter(events)));
+        assert_eq!(out_stream.count().await, 2);
+    }
+
+    async fn shape(config: &str, count: usize) -> usize {
+        let config = toml::from_str::<ThrottleConfig>(config).unwrap();
+        let throttle = Throttle::new(&config, &TransformContext::default(), TokioClock)
+            .map(Transform::event_task)
+            .unwrap()
+            .into_task();
+
+        let events = (0..count).map(|_| LogEvent::default().into());
+        throttle
+            .transform_events(Box::pin(futures::stream::iter(events)))
+            .count()
+            .await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throttle_shapes_events() {
+        let start = tokio::time::Instant::now();
+        let count = shape(
+            r#"
+threshold = 2
+window_secs = 2
+mode = "shape"
+"#,
+            5,
+        )
+        .await;
+
+        // The first two events are sent right away, and the others one every second.
+        assert_eq!(count, 5);
+        assert!(start.elapsed() >= Duration::from_secs(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throttle_shaping_applies_backpressure() {
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 1
+mode = "shape"
+max_queued_events = 1
+"#,
+        )
+        .unwrap();
+        let throttle = Throttle::new(&config, &TransformContext::default(), TokioClock)
+            .map(Transform::event_task)
+            .unwrap()
+            .into_task();
+
+        let (tx, rx) = mpsc::channel(1);
+        let mut out_stream = throttle.transform_events(Box::pin(ReceiverStream::new(rx)));
+
+        // The first event is sent right away, and the second one fills the queue.
+        tx.send(LogEvent::default().into()).await.unwrap();
+        assert!(matches!(
+            futures::poll!(out_stream.next()),
+            Poll::Ready(Some(_))
+        ));
+        tx.send(LogEvent::default().into()).await.unwrap();
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        // The input is no longer read, so once its channel is full, sending stays pending until
+        // the clock releases the queued event.
+        tx.send(LogEvent::default().into()).await.unwrap();
+        let mut send = Box::pin(tx.send(LogEvent::default().into()));
+        assert!(futures::poll!(&mut send).is_pending());
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(futures::poll!(&mut send).is_pending());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(matches!(
+            futures::poll!(out_stream.next()),
+            Poll::Ready(Some(_))
+        ));
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(matches!(futures::poll!(&mut send), Poll::Ready(Ok(()))));
+
+        drop(send);
+        drop(tx);
+        assert_eq!(out_stream.count().await, 2);
+    }
+
+    #[cfg(feature = "transforms-throttle-redis")]
+    #[test]
+    fn throttle_shaping_requires_local_backend() {
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 1
+mode = "shape"
+backend.type = "redis"
+backend.endpoint = "redis://127.0.0.1:6379/0"
+"#,
+        )
+        .unwrap();
+
+        let error = Throttle::new(&config, &TransformContext::default(), TokioClock)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "The `shape` mode can't be used with the `redis` backend"
+        );
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
@@ -370,6 +637,9 @@ key_field = "{{ bucket }}"
                 key_field: None,
                 exclude: None,
                 internal_metrics: Default::default(),
+                backend: Default::default(),
+                mode: Default::default(),
+                max_queued_events: NonZeroUsize::new(1000).unwrap(),
             };
             let (tx, rx) = mpsc::channel(1);
             let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
@@ -204,6 +204,98 @@ cli: {
 	options: _core_options
 
 	commands: {
+		"buffer inspect": {
+			description: """
+				Inspect the disk buffer of a sink without modifying it, so that it can be inspected
+				while Vector is running. Prints the number of waiting records and events, the
+				timestamps of the oldest and newest events, and a sample of the oldest events.
+				"""
+
+			example: "vector buffer inspect --config /etc/vector/vector.yaml my_sink --samples 5"
+
+			options: _core_options & {
+				"buffer-dir": {
+					description: "The directory of the buffer, used instead of a sink of the configuration"
+					type:        "string"
+				}
+				"samples": {
+					description: "The number of events to print as samples, starting with the oldest ones"
+					type:        "integer"
+					default:     10
+				}
+				"format": {
+					description: "Set the output format"
+					default:     "text"
+					enum: {
+						text: "Output a human-readable summary"
+						json: "Output the summary as JSON, with events in the native JSON format"
+					}
+				}
+			}
+
+			args: {
+				component_id: {
+					description: "The ID of the sink whose buffer is inspected"
+					type:        "string"
+				}
+			}
+		}
+
+		"buffer validate": {
+			description: """
+				Validate the CRC32C checksum of every record of a stopped disk buffer, reporting corrupted
+				data file tails, and compare its ledger to the one rebuilt from its data files. Exits with
+				a non-zero code if the buffer needs to be repaired.
+				"""
+
+			example: "vector buffer validate --config /etc/vector/vector.yaml my_sink"
+
+			options: _core_options & {
+				"buffer-dir": {
+					description: "The directory of the buffer, used instead of a sink of the configuration"
+					type:        "string"
+				}
+			}
+		}
+
+		"buffer repair": {
+			description: """
+				Repair a stopped disk buffer: truncate the corrupted tails of its data files, delete the
+				data files left without any valid record, and rebuild its ledger from them.
+				"""
+
+			example: "vector buffer repair --buffer-dir /var/lib/vector/buffer/v2/my_sink"
+
+			options: _core_options & {
+				"buffer-dir": {
+					description: "The directory of the buffer, used instead of a sink of the configuration"
+					type:        "string"
+				}
+			}
+		}
+
+		"buffer export": {
+			description: """
+				Export the unread events of a stopped disk buffer as native JSON, one event per line,
+				without modifying it. Events are read using the ledger rebuilt from the data files, so
+				that they can be salvaged even when the ledger is corrupted.
+				"""
+
+			example: "vector buffer export --config /etc/vector/vector.yaml my_sink --output events.json"
+
+			options: _core_options & {
+				"buffer-dir": {
+					description: "The directory of the buffer, used instead of a sink of the configuration"
+					type:        "string"
+				}
+				"output": {
+					_short:      "o"
+					description: "The file to write the events to, instead of the standard output"
+					type:        "string"
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
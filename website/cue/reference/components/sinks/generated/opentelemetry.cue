@@ -285,10 +285,66 @@ generated: components: sinks: opentelemetry: configuration: protocol: {
 					description:   "Apache Avro-specific encoder options."
 					relevant_when: "codec = \"avro\""
 					required:      true
-					type: object: options: schema: {
-						description: "The Avro schema."
-						required:    true
-						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					type: object: options: {
+						schema: {
+							description: "The Avro schema."
+							required:    false
+							type: string: {
+								default: ""
+								examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+							}
+						}
+						schema_registry: {
+							description: """
+																	Prefixes each message with the ID of its schema in a Schema Registry, in the Confluent wire
+																	format.
+
+																	When `schema` is not set, the latest schema registered under the subject is used.
+																	"""
+							required: false
+							type: object: options: {
+								auto_register: {
+									description: """
+																			Whether the configured schema is registered under `subject` if it is not already.
+
+																			Otherwise, the configured schema must already be registered under `subject`. If no schema
+																			is configured, the latest schema registered under `subject` is used.
+
+																			Only supported by the `avro` codec.
+																			"""
+									required: false
+									type: bool: default: false
+								}
+								password: {
+									description: "The password to authenticate to the Schema Registry with, using HTTP basic authentication."
+									required:    false
+									type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+								}
+								subject: {
+									description: "The subject under which the schema is registered."
+									required:    true
+									type: string: examples: ["logs-value"]
+								}
+								timeout_secs: {
+									description: "The timeout, in seconds, of requests to the Schema Registry."
+									required:    false
+									type: uint: {
+										default: 10
+										unit:    "seconds"
+									}
+								}
+								url: {
+									description: "The URL of the Schema Registry."
+									required:    true
+									type: string: examples: ["http://localhost:8081"]
+								}
+								username: {
+									description: "The username to authenticate to the Schema Registry with, using HTTP basic authentication."
+									required:    false
+									type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+								}
+							}
+						}
 					}
 				}
 				cef: {
@@ -597,14 +653,68 @@ generated: components: sinks: opentelemetry: configuration: protocol: {
 
 																				You can read more [here](https://buf.build/docs/reference/images/#how-buf-images-work).
 																				"""
-							required: true
-							type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+							required: false
+							type: string: {
+								default: ""
+								examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+							}
 						}
 						message_type: {
 							description: "The name of the message type to use for serializing."
 							required:    true
 							type: string: examples: ["package.Message"]
 						}
+						schema_registry: {
+							description: """
+																	Encodes messages in the Confluent wire format, prefixing them with the ID of the latest
+																	schema registered under the subject in a Schema Registry.
+
+																	When set, `desc_file` is ignored and `message_type` is looked up in that schema.
+																	"""
+							required: false
+							type: object: options: {
+								auto_register: {
+									description: """
+																			Whether the configured schema is registered under `subject` if it is not already.
+
+																			Otherwise, the configured schema must already be registered under `subject`. If no schema
+																			is configured, the latest schema registered under `subject` is used.
+
+																			Only supported by the `avro` codec.
+																			"""
+									required: false
+									type: bool: default: false
+								}
+								password: {
+									description: "The password to authenticate to the Schema Registry with, using HTTP basic authentication."
+									required:    false
+									type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+								}
+								subject: {
+									description: "The subject under which the schema is registered."
+									required:    true
+									type: string: examples: ["logs-value"]
+								}
+								timeout_secs: {
+									description: "The timeout, in seconds, of requests to the Schema Registry."
+									required:    false
+									type: uint: {
+										default: 10
+										unit:    "seconds"
+									}
+								}
+								url: {
+									description: "The URL of the Schema Registry."
+									required:    true
+									type: string: examples: ["http://localhost:8081"]
+								}
+								username: {
+									description: "The username to authenticate to the Schema Registry with, using HTTP basic authentication."
+									required:    false
+									type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+								}
+							}
+						}
 					}
 				}
 				timestamp_format: {
@@ -1039,7 +1149,15 @@ generated: components: sinks: opentelemetry: configuration: protocol: {
 		type: {
 			description: "The communication protocol."
 			required:    true
-			type: string: enum: http: "Send data over HTTP."
+			type: string: enum: {
+				grpc: "Send OTLP over gRPC."
+				http: """
+					Send data over HTTP, encoded with the configured codec.
+
+					The events must already be in the shape of OTLP JSON.
+					"""
+				http_protobuf: "Send OTLP over HTTP, encoded as protobuf."
+			}
 		}
 		uri: {
 			description: """
@@ -634,4 +634,24 @@ generated: components: sinks: prometheus_remote_write: configuration: {
 			}
 		}
 	}
+	version: {
+		description: "The version of the remote write protocol to send requests with."
+		required:    false
+		type: string: {
+			default: "v1"
+			enum: {
+				v1: """
+					[Remote write 1.0][remote_write_v1], accepted by all receivers.
+
+					[remote_write_v1]: https://prometheus.io/docs/specs/prw/remote_write_spec/
+					"""
+				v2: """
+					[Remote write 2.0][remote_write_v2], which interns the strings of each request and sends
+					the metadata of each series along with its samples.
+
+					[remote_write_v2]: https://prometheus.io/docs/specs/prw/remote_write_spec_2_0/
+					"""
+			}
+		}
+	}
 }
@@ -0,0 +1,93 @@
+package metadata
+
+components: sinks: sql: {
+	title: "SQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			tls: enabled:         false
+			to: {
+				service: services.sql
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "unix"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+			The tables must exist before events are written to them, with a column for each of the
+			`columns`. The sink doesn't create or change tables.
+			""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: generated.components.sinks.sql.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		inserting_rows: {
+			title: "Inserting rows"
+			body: """
+				Each event is written as a row, with the values of the `columns` taken from the fields of
+				the event. Events are batched by table, and each batch is written in a single transaction,
+				with as many `INSERT` statements as needed to stay under the limit of parameters of the
+				database. If a statement fails, the transaction is rolled back and the batch is retried
+				or dropped as a whole.
+
+				The values are bound as statement parameters. The names of the tables and columns are
+				quoted instead, as they can't be parameters: a static table name such as
+				`database.table` is quoted part by part, while a templated table name is always quoted as
+				a single identifier.
+				"""
+		}
+		upserts: {
+			title: "Upserts"
+			body: """
+				In the `upsert` mode, a row whose `conflict_columns` match an existing row updates the
+				other columns of that row instead of failing. This uses `ON DUPLICATE KEY UPDATE` with
+				MySQL and MariaDB, and [`ON CONFLICT`](\(urls.sqlite_upsert)) with SQLite, so the
+				`conflict_columns` must be covered by a primary key or a unique index of the table.
+
+				As retried batches update the rows that were already written, upserts make the delivery of
+				events idempotent.
+				"""
+		}
+	}
+}
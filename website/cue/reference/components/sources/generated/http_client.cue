@@ -173,6 +173,60 @@ generated: components: sources: http_client: configuration: {
 			}
 		}
 	}
+	checkpoint: {
+		description: """
+			A checkpoint tracking the last event received, which is sent in a query parameter so that
+			each scrape only requests the events received since the previous one.
+
+			The checkpoint, or watermark, is the greatest value of a field of the events received, such as
+			a timestamp or an increasing identifier. It is saved in the data directory after each scrape
+			whose events were all sent, up to its last page, and is kept across restarts.
+			"""
+		required: false
+		type: object: options: {
+			data_dir: {
+				description: """
+					The directory used to persist the checkpoint.
+
+					By default, the [global `data_dir` option][global_data_dir] is used.
+					Make sure the running user has write permissions to this directory.
+
+					[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+					"""
+				required: false
+				type: string: examples: ["/var/lib/vector"]
+			}
+			field: {
+				description: """
+					The field of the events holding the watermark.
+
+					Integers and floats are compared numerically, timestamps chronologically, and strings
+					lexicographically, which matches the chronological order of RFC 3339 timestamps of the same
+					time zone. Events without the field are ignored.
+					"""
+				required: true
+				type: string: examples: ["published", "meta.id"]
+			}
+			initial: {
+				description: """
+					The value sent in `param` until a first watermark is saved.
+
+					By default, the parameter isn't sent until a first watermark is saved.
+					"""
+				required: false
+				type: string: examples: ["2025-01-01T00:00:00Z"]
+			}
+			param: {
+				description: """
+					The query parameter the watermark is sent in.
+
+					Timestamps are sent in the RFC 3339 format.
+					"""
+				required: true
+				type: string: examples: ["since"]
+			}
+		}
+	}
 	decoding: {
 		description: """
 			Configures how events are decoded from raw bytes. Note some decoders can also determine the event output
@@ -195,16 +249,52 @@ generated: components: sources: http_client: configuration: {
 																* `Fixed`
 																* `TimeMillis`
 																"""
-						required: true
-						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+						required: false
+						type: string: {
+							default: ""
+							examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+						}
+					}
+					schema_registry: {
+						description: """
+																Looks up the schema of each message in a Schema Registry, by the schema ID prefixing it.
+
+																When set, `schema` and `strip_schema_id_prefix` are ignored.
+																"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password to authenticate to the Schema Registry with, using HTTP basic authentication."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							timeout_secs: {
+								description: "The timeout, in seconds, of requests to the Schema Registry."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the Schema Registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username to authenticate to the Schema Registry with, using HTTP basic authentication."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
 					}
 					strip_schema_id_prefix: {
 						description: """
 																For Avro datum encoded in Kafka messages, the bytes are prefixed with the schema ID.  Set this to `true` to strip the schema ID prefix.
 																According to [Confluent Kafka's document](https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format).
 																"""
-						required: true
-						type: bool: {}
+						required: false
+						type: bool: default: false
 					}
 				}
 			}
@@ -378,6 +468,40 @@ generated: components: sources: http_client: configuration: {
 							examples: ["package.Message"]
 						}
 					}
+					schema_registry: {
+						description: """
+																Decodes messages in the Confluent wire format, looking up their schema in a Schema Registry
+																by the ID prefixing them.
+
+																When set, `desc_file` and `message_type` are ignored.
+																"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password to authenticate to the Schema Registry with, using HTTP basic authentication."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							timeout_secs: {
+								description: "The timeout, in seconds, of requests to the Schema Registry."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the Schema Registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username to authenticate to the Schema Registry with, using HTTP basic authentication."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			syslog: {
@@ -653,6 +777,105 @@ generated: components: sources: http_client: configuration: {
 			}
 		}
 	}
+	pagination: {
+		description: """
+			Pagination of the responses.
+
+			When pagination is enabled, the pages of a scrape are requested one after the other, and a
+			scrape is not started before the previous one is done.
+			"""
+		required: false
+		type: object: options: {
+			cursor_field: {
+				description:   "The field of the response body holding the cursor of the next page."
+				relevant_when: "strategy = \"cursor\""
+				required:      true
+				type: string: examples: ["next_cursor", "meta.next"]
+			}
+			cursor_param: {
+				description:   "The query parameter the cursor is sent in."
+				relevant_when: "strategy = \"cursor\""
+				required:      true
+				type: string: examples: ["cursor"]
+			}
+			limit_param: {
+				description:   "The query parameter the page size is sent in, if any."
+				relevant_when: "strategy = \"offset\""
+				required:      false
+				type: string: examples: ["limit"]
+			}
+			max_pages: {
+				description: """
+					The maximum number of pages requested per scrape.
+
+					The next scrape starts again from the first page, or from the checkpoint if one is
+					configured. The checkpoint is only saved when the last page is reached, so a scrape that
+					stops at this limit is started again from the previous checkpoint.
+					"""
+				required: false
+				type: uint: default: 100
+			}
+			offset_param: {
+				description: """
+					The query parameter the offset is sent in. The first page is requested with an offset
+					of `0`.
+					"""
+				relevant_when: "strategy = \"offset\""
+				required:      true
+				type: string: examples: ["offset"]
+			}
+			page_size: {
+				description:   "The number of events in a full page."
+				relevant_when: "strategy = \"offset\""
+				required:      true
+				type: uint: examples: [100]
+			}
+			source: {
+				description:   "The VRL program."
+				relevant_when: "strategy = \"vrl\""
+				required:      true
+				type: string: examples: ["if .body.has_more == true { {\"page\": to_int!(.query.page) + 1} } else { null }"]
+			}
+			strategy: {
+				description: "How the next page of a response is requested."
+				required:    true
+				type: string: enum: {
+					cursor: """
+						Sends the cursor found in the body of each response in a query parameter of the next
+						request.
+
+						The body must be a JSON document. Pagination stops when the cursor is missing, null, or
+						empty.
+						"""
+					link_header: """
+						Requests the URL of the `next` relation of the `Link` header of responses, as described in
+						[RFC 8288][rfc8288].
+
+						Relative URLs are resolved against the URL of the request. Pagination stops when a
+						response has no `next` link, or when the link is on another scheme, host, or port than the
+						request.
+
+						[rfc8288]: https://www.rfc-editor.org/rfc/rfc8288
+						"""
+					offset: """
+						Increments an offset query parameter by the number of events received in each page.
+
+						Pagination stops after a page with fewer events than `page_size`.
+						"""
+					vrl: """
+						Computes the query parameters of the next request with a VRL program.
+
+						The program runs on an object with the `status` code, the `headers` and the `body` of the
+						response, the `query` parameters of the request, and the number of `events` decoded from
+						the response. The body is parsed if it is a JSON document, and is a string otherwise.
+
+						The program returns an object of query parameters which are set on the next request, in
+						addition to the parameters of the previous request, or `null` to stop pagination.
+						"""
+				}
+			}
+		}
+	}
 	query: {
 		description: """
 			Custom parameters for the HTTP request query string.
@@ -0,0 +1,79 @@
+package metadata
+
+generated: components: sources: netflow: configuration: {
+	address: {
+		description: """
+			The address to listen for flow export packets on.
+
+			NetFlow v5 and v9, IPFIX and sFlow v5 packets are all accepted on the same address, and
+			told apart by their version number.
+			"""
+		required: true
+		type: string: examples: ["0.0.0.0:2055", "0.0.0.0:6343", "systemd", "systemd#3"]
+	}
+	host_key: {
+		description: """
+			Overrides the name of the log field used to add the exporter's address to each event.
+
+			By default, the [global `log_schema.host_key` option][global_host_key] is used.
+
+			Set to `""` to suppress this key.
+
+			[global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+			"""
+		required: false
+		type: string: {}
+	}
+	max_length: {
+		description: """
+			The maximum size of incoming packets.
+
+			Packets larger than this are truncated, and fail to decode.
+			"""
+		required: false
+		type: uint: {
+			default: 65535
+			unit:    "bytes"
+		}
+	}
+	max_templates: {
+		description: """
+			The maximum number of templates cached across all exporters.
+
+			NetFlow v9 and IPFIX data records can only be decoded once the template describing them
+			has been announced. When the cache is full, the least recently announced template is
+			evicted.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	port_key: {
+		description: """
+			Overrides the name of the log field used to add the exporter's port to each event.
+
+			By default, `"port"` is used.
+
+			Set to `""` to suppress this key.
+			"""
+		required: false
+		type: string: default: "port"
+	}
+	receive_buffer_bytes: {
+		description: "The size of the receive buffer used for the listening socket."
+		required:    false
+		type: uint: unit: "bytes"
+	}
+	template_ttl_secs: {
+		description: """
+			The time after which a template expires, unless the exporter announces it again.
+
+			Exporters periodically announce their templates again, so this only drops the templates
+			of exporters that went away or stopped using them.
+			"""
+		required: false
+		type: uint: {
+			default: 1800
+			unit:    "seconds"
+		}
+	}
+}
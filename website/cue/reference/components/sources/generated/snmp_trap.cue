@@ -0,0 +1,141 @@
+package metadata
+
+generated: components: sources: snmp_trap: configuration: {
+	address: {
+		description: "The address to listen for traps on."
+		required:    true
+		type: string: examples: ["0.0.0.0:162", "systemd", "systemd#3"]
+	}
+	host_key: {
+		description: """
+			Overrides the name of the log field used to add the sender's address to each event.
+
+			By default, the [global `log_schema.host_key` option][global_host_key] is used.
+
+			Set to `""` to suppress this key.
+
+			[global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+			"""
+		required: false
+		type: string: {}
+	}
+	max_length: {
+		description: """
+			The maximum size of incoming messages.
+
+			Messages larger than this are truncated, and fail to decode.
+			"""
+		required: false
+		type: uint: {
+			default: 65535
+			unit:    "bytes"
+		}
+	}
+	mib_paths: {
+		description: """
+			A list of MIB files, or directories of MIB files, used to resolve object identifiers to
+			names.
+
+			When set, events include the resolved name of the trap, and of each of its variables.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["/usr/share/snmp/mibs"]
+		}
+	}
+	port_key: {
+		description: """
+			Overrides the name of the log field used to add the sender's port to each event.
+
+			By default, `"port"` is used.
+
+			Set to `""` to suppress this key.
+			"""
+		required: false
+		type: string: default: "port"
+	}
+	receive_buffer_bytes: {
+		description: "The size of the receive buffer used for the listening socket."
+		required:    false
+		type: uint: unit: "bytes"
+	}
+	users: {
+		description: """
+			The SNMPv3 users to accept traps from.
+
+			SNMPv3 traps from any other user are rejected. SNMPv1 and SNMPv2c traps are accepted
+			regardless of their community.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				authentication: {
+					description: """
+						The authentication settings of the user.
+
+						Traps from users without authentication are only accepted when they are neither
+						authenticated nor encrypted.
+						"""
+					required: false
+					type: object: options: {
+						password: {
+							description: """
+								The authentication password of the user.
+
+								This can be loaded from a secrets backend with the `SECRET[<backend>.<key>]` syntax.
+								"""
+							required: true
+							type: string: examples: ["SECRET[backend.snmp_auth_password]"]
+						}
+						protocol: {
+							description: "The protocol used to authenticate messages."
+							required:    true
+							type: string: enum: {
+								md5:    "HMAC-MD5-96, as defined by RFC 3414."
+								sha:    "HMAC-SHA-96, as defined by RFC 3414."
+								sha224: "HMAC-SHA-224, as defined by RFC 7860."
+								sha256: "HMAC-SHA-256, as defined by RFC 7860."
+								sha384: "HMAC-SHA-384, as defined by RFC 7860."
+								sha512: "HMAC-SHA-512, as defined by RFC 7860."
+							}
+						}
+					}
+				}
+				name: {
+					description: "The name of the user."
+					required:    true
+					type: string: examples: ["traps"]
+				}
+				privacy: {
+					description: """
+						The privacy settings of the user, used to decrypt traps.
+
+						Privacy requires authentication to be configured.
+						"""
+					required: false
+					type: object: options: {
+						password: {
+							description: """
+								The privacy password of the user.
+
+								This can be loaded from a secrets backend with the `SECRET[<backend>.<key>]` syntax.
+								"""
+							required: true
+							type: string: examples: ["SECRET[backend.snmp_privacy_password]"]
+						}
+						protocol: {
+							description: "The protocol used to encrypt messages."
+							required:    false
+							type: string: {
+								default: "aes"
+								enum: aes: "AES-128 in CFB mode, as defined by RFC 3826."
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -682,6 +682,23 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		netflow_template_misses_total: {
+			description:       "The total number of NetFlow v9 or IPFIX data sets dropped because their template wasn't announced yet."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				protocol: {
+					description: "The flow export protocol, either `netflow_v9` or `ipfix`."
+					required:    true
+				}
+			}
+		}
+		netflow_templates_evicted_total: {
+			description:       "The total number of templates evicted because the template cache was full."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		open_connections: {
 			description:       "The number of current open connections to Vector."
 			type:              "gauge"
@@ -769,6 +786,28 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tag_cardinality_estimate: {
+			description: """
+				The estimated number of distinct values of a tag key of a metric received during the
+				last `estimates_interval_secs`, including the values over the configured `value_limit`.
+				"""
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				metric_name: {
+					description: "The name of the metric."
+					required:    true
+				}
+				metric_namespace: {
+					description: "The namespace of the metric."
+					required:    false
+				}
+				tag_key: {
+					description: "The tag key."
+					required:    true
+				}
+			}
+		}
 		tag_value_limit_exceeded_total: {
 			description: """
 				The total number of events discarded because the tag has been rejected after
@@ -778,6 +817,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tail_sampling_traces_evicted_total: {
+			description:       "The total number of traces decided on early because the maximum number of pending traces was reached."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
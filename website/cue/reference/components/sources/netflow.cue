@@ -0,0 +1,151 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+				interface: socket: {
+					api: {
+						title: "NetFlow"
+						url:   urls.netflow
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: generated.components.sources.netflow.configuration
+
+	output: logs: record: {
+		description: "A single flow record, or options record, decoded from a flow export packet."
+		fields: {
+			flow_type: {
+				description: "The protocol of the packet the record was decoded from."
+				required:    true
+				type: string: {
+					enum: {
+						netflow_v5: "NetFlow version 5."
+						netflow_v9: "NetFlow version 9."
+						ipfix:      "IPFIX, also known as NetFlow version 10."
+						sflow_v5:   "sFlow version 5."
+					}
+				}
+			}
+			header: {
+				description: "The fields of the header of the packet the record was decoded from, such as its sequence number."
+				required:    true
+				type: object: {}
+			}
+			host: {
+				description: "The IP address of the exporter."
+				required:    true
+				type: string: {
+					examples: ["192.168.0.1"]
+				}
+			}
+			port: {
+				description: "The port the exporter sent the packet from."
+				required:    true
+				type: uint: {
+					examples: [51234]
+					unit: null
+				}
+			}
+			record_type: {
+				description: "The type of the record."
+				required:    true
+				type: string: {
+					enum: {
+						counter: "An sFlow counter sample, holding the counters of an interface."
+						flow:    "A flow record, or an sFlow flow sample."
+						options: "An options record, describing the exporter or the flows it exports."
+					}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["netflow"]
+				}
+			}
+			timestamp: {
+				description: "The time the packet was received."
+				required:    true
+				type: timestamp: {}
+			}
+			"*": {
+				description: "The fields of the record. Well-known fields are named after their NetFlow v9 or IPFIX information element, such as `ipv4_src_addr` or `in_bytes`, and other fields after their numeric type, such as `field_400` or `enterprise_29305_5`."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		protocols: {
+			title: "Protocols"
+			body: """
+				NetFlow v5 and v9, [IPFIX](\(urls.ipfix)) and [sFlow v5](\(urls.sflow)) packets
+				are all accepted on the same address. The protocol of each packet is detected from its
+				version number, so exporters using different protocols can share a single source.
+
+				Each sFlow flow or counter sample becomes a single record, holding the fields of all of
+				its flow or counter records. Samples and records of unknown formats are skipped.
+				"""
+		}
+		templates: {
+			title: "Templates"
+			body: """
+				NetFlow v9 and IPFIX data records can only be decoded with the template describing
+				their layout, which exporters announce periodically. Templates are cached per exporter
+				address and observation domain, and data records received before their template are
+				dropped, which is reported by the `netflow_template_misses_total` internal metric.
+
+				The cache holds at most `max_templates` templates, evicting the least recently announced
+				template when it is full, and templates that are not announced again within
+				`template_ttl_secs` expire.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_bytes:        components.sources.internal_metrics.output.metrics.component_received_bytes
+		netflow_template_misses_total:   components.sources.internal_metrics.output.metrics.netflow_template_misses_total
+		netflow_templates_evicted_total: components.sources.internal_metrics.output.metrics.netflow_templates_evicted_total
+	}
+}
@@ -0,0 +1,248 @@
+package metadata
+
+components: sources: snmp_trap: {
+	_port: 162
+
+	title: "SNMP Trap"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.snmp
+				interface: socket: {
+					api: {
+						title: "SNMP"
+						url:   urls.snmp_trap
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: generated.components.sources.snmp_trap.configuration
+
+	output: logs: trap: {
+		description: "A single trap or inform."
+		fields: {
+			agent_address: {
+				description: "The address of the agent that generated the trap. Only set for SNMPv1 traps."
+				required:    false
+				type: string: {
+					examples: ["192.168.0.10"]
+				}
+			}
+			authoritative_engine_id: {
+				description: "The hex-encoded ID of the SNMP engine that sent the trap. Only set for SNMPv3 traps."
+				required:    false
+				type: string: {
+					examples: ["80001f8880e9630000d61ff449"]
+				}
+			}
+			community: {
+				description: "The community of the trap. Only set for SNMPv1 and SNMPv2c traps."
+				required:    false
+				type: string: {
+					examples: ["public"]
+				}
+			}
+			context_engine_id: {
+				description: "The hex-encoded context engine ID of the trap. Only set for SNMPv3 traps."
+				required:    false
+				type: string: {
+					examples: ["80001f8880e9630000d61ff449"]
+				}
+			}
+			context_name: {
+				description: "The context name of the trap. Only set for SNMPv3 traps."
+				required:    false
+				type: string: {
+					examples: [""]
+				}
+			}
+			enterprise: {
+				description: "The object identifier of the type of the agent that generated the trap. Only set for SNMPv1 traps."
+				required:    false
+				type: string: {
+					examples: ["1.3.6.1.4.1.8072.3.2.10"]
+				}
+			}
+			generic_trap: {
+				description: "The generic trap type. Only set for SNMPv1 traps."
+				required:    false
+				type: uint: {
+					examples: [2]
+					unit: null
+				}
+			}
+			host: {
+				description: "The IP address of the sender."
+				required:    true
+				type: string: {
+					examples: ["192.168.0.10"]
+				}
+			}
+			pdu_type: {
+				description: "The type of the notification."
+				required:    true
+				type: string: {
+					enum: {
+						inform:      "An inform, which is acknowledged to the sender."
+						snmpv2_trap: "An SNMPv2c or SNMPv3 trap."
+						trap:        "An SNMPv1 trap."
+					}
+				}
+			}
+			port: {
+				description: "The port the trap was sent from."
+				required:    true
+				type: uint: {
+					examples: [51234]
+					unit: null
+				}
+			}
+			request_id: {
+				description: "The request ID of the notification. Not set for SNMPv1 traps."
+				required:    false
+				type: int: {
+					examples: [1234]
+				}
+			}
+			security_level: {
+				description: "The security level of the trap. Only set for SNMPv3 traps."
+				required:    false
+				type: string: {
+					enum: {
+						auth_no_priv:    "The trap was authenticated, but not encrypted."
+						auth_priv:       "The trap was authenticated and encrypted."
+						no_auth_no_priv: "The trap was neither authenticated nor encrypted."
+					}
+				}
+			}
+			snmp_version: {
+				description: "The SNMP version of the trap."
+				required:    true
+				type: string: {
+					enum: {
+						"1":  "SNMPv1."
+						"2c": "SNMPv2c."
+						"3":  "SNMPv3."
+					}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["snmp_trap"]
+				}
+			}
+			specific_trap: {
+				description: "The enterprise-specific trap type. Only set for SNMPv1 traps."
+				required:    false
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			timestamp: {
+				description: "The time the trap was received."
+				required:    true
+				type: timestamp: {}
+			}
+			trap_name: {
+				description: "The name of the trap, resolved from the configured MIB files."
+				required:    false
+				type: string: {
+					examples: ["IF-MIB::linkDown"]
+				}
+			}
+			trap_oid: {
+				description: "The object identifier of the trap. Not set for SNMPv1 traps."
+				required:    false
+				type: string: {
+					examples: ["1.3.6.1.6.3.1.1.5.3"]
+				}
+			}
+			uptime: {
+				description: "The time since the sender was started, in hundredths of a second."
+				required:    false
+				type: uint: {
+					examples: [123456]
+					unit: null
+				}
+			}
+			user: {
+				description: "The SNMPv3 user that sent the trap. Only set for SNMPv3 traps."
+				required:    false
+				type: string: {
+					examples: ["traps"]
+				}
+			}
+			varbinds: {
+				description: "The variables of the trap, each with its `oid`, its `value`, and its `name` when resolved from the configured MIB files."
+				required:    true
+				type: array: items: type: object: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		versions: {
+			title: "SNMP versions"
+			body: """
+				SNMPv1 and SNMPv2c traps are accepted from any community. SNMPv3 traps are only
+				accepted from the configured `users`, and are authenticated and decrypted with the
+				[user-based security model](\(urls.snmpv3_usm)) using the passwords of the user.
+				"""
+		}
+		informs: {
+			title: "Informs"
+			body: """
+				SNMPv2c informs are acknowledged with a response to their sender as soon
+				as they are decoded, so that the sender stops retransmitting them. SNMPv3 informs are
+				received, but not acknowledged.
+				"""
+		}
+		mibs: {
+			title: "MIB files"
+			body: """
+				Traps and their variables are identified by numeric object identifiers. When
+				`mib_paths` is set, the object identifiers are additionally resolved to names using the
+				definitions of the MIB files found at these paths.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_bytes: components.sources.internal_metrics.output.metrics.component_received_bytes
+	}
+}
@@ -34,6 +34,42 @@ generated: components: transforms: dedupe: configuration: {
 			}
 		}
 	}
+	persistence: {
+		description: """
+			Configuration for persisting the deduplication cache across restarts.
+
+			When enabled, a snapshot of the cache is written periodically, as well as when the transform
+			shuts down, and the last snapshot written is restored when the transform is built. This avoids
+			forwarding duplicates of events that were already forwarded before a restart or reload, such as
+			those redelivered by sources with at-least-once delivery.
+
+			Snapshots are discarded when the field matching configuration has changed since they were
+			written.
+			"""
+		required: false
+		type: object: options: {
+			data_dir: {
+				description: """
+					The directory used to persist the deduplication cache.
+
+					By default, the [global `data_dir` option][global_data_dir] is used.
+					Make sure the running user has write permissions to this directory.
+
+					[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+					"""
+				required: false
+				type: string: examples: ["/var/lib/vector"]
+			}
+			snapshot_interval_secs: {
+				description: "The interval between snapshots of the deduplication cache."
+				required:    false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+		}
+	}
 	time_settings: {
 		description: "Configuration for time based cache."
 		required:    false
@@ -39,8 +39,14 @@ generated: components: transforms: log_to_metric: configuration: {
 		required:    false
 		type: array: items: type: object: options: {
 			field: {
-				description: "Name of the field in the event to generate the metric."
-				required:    true
+				description: """
+					Name of the field in the event to generate the metric.
+
+					If the field holds an array, each of its values is used: a counter incremented by value and
+					a gauge are generated for each value, while the values of a distribution or a set are all
+					held in a single metric.
+					"""
+				required: true
 				type: string: syntax: "template"
 			}
 			increment_by_value: {
@@ -85,6 +91,20 @@ generated: components: transforms: log_to_metric: configuration: {
 				required:    false
 				type: string: syntax: "template"
 			}
+			sample_rate_field: {
+				description: """
+					The field holding the sample rate of the event.
+
+					The values of an event sampled one in `N` times, such as by the `sample` transform, have a
+					sample rate of `N` and each count as `N` values, so that distributions derived from sampled
+					events are correctly weighted. The sample rate must be a number greater than or equal to
+					`1`, and is rounded to the nearest integer. Events without the field have a sample rate of
+					`1`.
+					"""
+				relevant_when: "type = \"counter\" or type = \"histogram\" or type = \"summary\" or type = \"sketch\""
+				required:      false
+				type: string: examples: ["sample_rate"]
+			}
 			tags: {
 				description: """
 					Tags to apply to the metric.
@@ -106,7 +126,15 @@ generated: components: transforms: log_to_metric: configuration: {
 					gauge:     "A gauge."
 					histogram: "A histogram."
 					set:       "A set."
-					summary:   "A summary."
+					sketch: """
+						A sketch.
+
+						The values are held in a [DDSketch][ddsketch], which summarizes distributions with a fixed
+						relative error and can be merged across events.
+
+						[ddsketch]: https://www.vldb.org/pvldb/vol12/p2195-masson.pdf
+						"""
+					summary: "A summary."
 				}
 			}
 		}
@@ -1,6 +1,60 @@
 package metadata
 
 generated: components: transforms: sample: configuration: {
+	dynamic: {
+		description: """
+			Configuration for dynamic sampling.
+
+			Instead of a fixed rate, the sample rate of each key is adjusted so that the total number of
+			events forwarded meets a target throughput, with a larger share of the events of rare keys
+			forwarded than of frequent ones. Keys are taken from `group_by`, and events without a key share a
+			single sample rate.
+
+			Each forwarded event is annotated with the sample rate of its key, expressed as `1/N`, in
+			`sample_rate_key`.
+			"""
+		required: false
+		type: object: options: {
+			adjustment_interval_secs: {
+				description: """
+					The interval at which the sample rates are recomputed from the throughput of each key.
+
+					Keys seen for the first time are forwarded in full until the next adjustment.
+					"""
+				required: false
+				type: uint: {
+					default: 15
+					unit:    "seconds"
+				}
+			}
+			ema_weight: {
+				description: """
+					The weight of the last interval in the moving average of the throughput of each key.
+
+					Higher values make sample rates react faster to changes in throughput, while lower values
+					smooth out bursts.
+					"""
+				required: false
+				type: float: default: 0.5
+			}
+			max_keys: {
+				description: """
+					The maximum number of keys to track.
+
+					Once reached, events of any new key share a single sample rate.
+					"""
+				required: false
+				type: uint: default: 10000
+			}
+			target_events_per_second: {
+				description: "The total number of events to forward per second, shared between all keys."
+				required:    true
+				type: float: examples: [
+					100.0,
+				]
+			}
+		}
+	}
 	exclude: {
 		description: "A logical condition used to exclude events from sampling."
 		required:    false
@@ -42,7 +96,8 @@ generated: components: transforms: sample: configuration: {
 
 			For example, `rate = 1500` means 1 out of every 1500 events are forwarded and the rest are
 			dropped. This differs from `ratio` which allows more precise control over the number of events
-			retained and values greater than 1/2. It is an error to provide a value for both `rate` and `ratio`.
+			retained and values greater than 1/2. It is an error to provide a value for more than one of
+			`rate`, `ratio` and `dynamic`.
 			"""
 		required: false
 		type: uint: examples: [
@@ -56,7 +111,7 @@ generated: components: transforms: sample: configuration: {
 			For example, `ratio = .13` means that 13% out of all events on the stream are forwarded and
 			the rest are dropped. This differs from `rate` allowing the configuration of a higher
 			precision value and also the ability to retain values of greater than 50% of all events. It is
-			an error to provide a value for both `rate` and `ratio`.
+			an error to provide a value for more than one of `rate`, `ratio` and `dynamic`.
 			"""
 		required: false
 		type: float: examples: [
@@ -1,6 +1,21 @@
 package metadata
 
 generated: components: transforms: tag_cardinality_limit: configuration: {
+	allowed_values: {
+		description: """
+			Tag values that are always accepted, per tag key.
+
+			These values bypass the limit: they are accepted even once `value_limit` is reached, and
+			don't count towards it. A tag with several values is only accepted this way if all of its
+			values are allowed.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "The values always accepted for a tag key."
+			required:    true
+			type: array: items: type: string: {}
+		}
+	}
 	cache_size_per_key: {
 		description: """
 			The size of the cache for detecting duplicate tags, in bytes.
@@ -12,6 +27,68 @@ generated: components: transforms: tag_cardinality_limit: configuration: {
 		required:      false
 		type: uint: default: 5120
 	}
+	denied_values: {
+		description: """
+			Tag values that are always over the limit, per tag key.
+
+			The `limit_exceeded_action` is applied to these values even before `value_limit` is
+			reached. A tag with several values is over the limit this way if any of its values is
+			denied.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "The values always over the limit for a tag key."
+			required:    true
+			type: array: items: type: string: {}
+		}
+	}
+	internal_metrics: {
+		description: "Configuration of internal metrics for the `tag_cardinality_limit` transform."
+		required:    false
+		type: object: options: {
+			emit_cardinality_estimates: {
+				description: """
+					Whether or not to periodically emit the `tag_cardinality_estimate` internal metric.
+
+					The metric is a gauge of the estimated number of distinct values of each tag key of each
+					metric, tagged with the `metric_name`, `metric_namespace` and `tag_key`, which helps finding
+					which tags are exploding. All the values received are counted, including the values over the
+					limits.
+
+					Cardinalities are estimated using HyperLogLog, with a standard error of about 3%, and use
+					1 KiB of memory for each tag key of each metric. The estimates are reset each time they are
+					emitted, so they count the distinct values received during each `estimates_interval_secs`.
+
+					The gauges of the tag keys which are no longer received keep their last value, unless the
+					[global `expire_metrics_secs` option][global_expire_metrics] is set.
+
+					[global_expire_metrics]: https://vector.dev/docs/reference/configuration/global-options/#expire_metrics_secs
+					"""
+				required: false
+				type: bool: default: false
+			}
+			estimates_interval_secs: {
+				description: "How often the `tag_cardinality_estimate` internal metric is emitted, in seconds."
+				required:    false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+			max_estimated_tag_keys: {
+				description: """
+					The maximum number of tag keys whose cardinality is estimated during each interval, across
+					all the metrics.
+
+					This bounds the memory used by the estimates, and the number of `tag_cardinality_estimate`
+					gauges emitted. The tag keys first received once this limit is reached are not estimated
+					until the next interval.
+					"""
+				required: false
+				type: uint: default: 1000
+			}
+		}
+	}
 	limit_exceeded_action: {
 		description: """
 			Possible actions to take when an event arrives that would exceed the cardinality limit for one
@@ -21,6 +98,12 @@ generated: components: transforms: tag_cardinality_limit: configuration: {
 		type: string: {
 			default: "drop_tag"
 			enum: {
+				aggregate_overflow: """
+					Replace the value(s) of the tag(s) that would exceed the configured limit with `__other__`.
+
+					The metrics over the limit are aggregated in the `__other__` bucket of the tag downstream,
+					instead of losing the tag.
+					"""
 				drop_event: "Drop the entire event itself."
 				drop_tag:   "Drop the tag(s) that would exceed the configured limit."
 			}
@@ -53,6 +136,21 @@ generated: components: transforms: tag_cardinality_limit: configuration: {
 			description: "An individual metric configuration."
 			required:    true
 			type: object: options: {
+				allowed_values: {
+					description: """
+						Tag values that are always accepted, per tag key.
+
+						These values bypass the limit: they are accepted even once `value_limit` is reached, and
+						don't count towards it. A tag with several values is only accepted this way if all of its
+						values are allowed.
+						"""
+					required: false
+					type: object: options: "*": {
+						description: "The values always accepted for a tag key."
+						required:    true
+						type: array: items: type: string: {}
+					}
+				}
 				cache_size_per_key: {
 					description: """
 						The size of the cache for detecting duplicate tags, in bytes.
@@ -64,6 +162,21 @@ generated: components: transforms: tag_cardinality_limit: configuration: {
 					required:      false
 					type: uint: default: 5120
 				}
+				denied_values: {
+					description: """
+						Tag values that are always over the limit, per tag key.
+
+						The `limit_exceeded_action` is applied to these values even before `value_limit` is
+						reached. A tag with several values is over the limit this way if any of its values is
+						denied.
+						"""
+					required: false
+					type: object: options: "*": {
+						description: "The values always over the limit for a tag key."
+						required:    true
+						type: array: items: type: string: {}
+					}
+				}
 				limit_exceeded_action: {
 					description: """
 						Possible actions to take when an event arrives that would exceed the cardinality limit for one
@@ -73,6 +186,12 @@ generated: components: transforms: tag_cardinality_limit: configuration: {
 					type: string: {
 						default: "drop_tag"
 						enum: {
+							aggregate_overflow: """
+								Replace the value(s) of the tag(s) that would exceed the configured limit with `__other__`.
+
+								The metrics over the limit are aggregated in the `__other__` bucket of the tag downstream,
+								instead of losing the tag.
+								"""
 							drop_event: "Drop the entire event itself."
 							drop_tag:   "Drop the tag(s) that would exceed the configured limit."
 						}
@@ -0,0 +1,113 @@
+package metadata
+
+generated: components: transforms: tail_sampling: configuration: {
+	decision_wait_secs: {
+		description: """
+			The maximum amount of time to wait for the spans of a trace, from its first span, before
+			deciding whether to sample it.
+
+			Spans received after the decision was made, such as spans ending after their root span,
+			follow the decision as long as it is remembered, which is for the same amount of time.
+			"""
+		required: false
+		type: float: {
+			default: 30.0
+			unit:    "seconds"
+		}
+	}
+	max_traces: {
+		description: """
+			The maximum number of traces waiting for a decision.
+
+			Once reached, the decision on the oldest trace is made early to make room for new traces.
+			"""
+		required: false
+		type: uint: default: 50000
+	}
+	parent_span_id_field: {
+		description: """
+			The field holding the ID of the parent of each span.
+
+			Spans without a parent, or with an empty parent, are the root span of their trace. A trace
+			is considered complete once its root span is received.
+			"""
+		required: false
+		type: string: {
+			default: "parent_span_id"
+			examples: ["parent_span_id"]
+		}
+	}
+	policies: {
+		description: """
+			The policies deciding whether to sample a trace.
+
+			A trace is sampled, with all of its spans forwarded, if any of the policies matches it.
+			Otherwise, all of its spans are dropped.
+			"""
+		required: true
+		type: array: items: type: object: options: {
+			condition: {
+				description:   "The condition to match spans against."
+				relevant_when: "type = \"condition\""
+				required:      true
+				type: condition: {}
+			}
+			ratio: {
+				description:   "The ratio of traces to sample."
+				relevant_when: "type = \"probabilistic\""
+				required:      true
+				type: float: examples: [
+					0.1,
+				]
+			}
+			threshold_ms: {
+				description:   "The minimum duration of sampled traces."
+				relevant_when: "type = \"latency\""
+				required:      true
+				type: uint: {
+					examples: [
+						500,
+					]
+					unit: "milliseconds"
+				}
+			}
+			type: {
+				description: "The type of policy."
+				required:    true
+				type: string: enum: {
+					condition: """
+						Samples traces containing at least one span matching a condition, such as the value of
+						one of its attributes.
+						"""
+					error: """
+						Samples traces containing at least one span whose `status.code` is `2`, the OTLP code of
+						failed operations.
+						"""
+					latency: """
+						Samples traces lasting at least a given amount of time.
+
+						The duration of a trace is measured from the earliest `start_time_unix_nano` to the latest
+						`end_time_unix_nano` among its spans.
+						"""
+					probabilistic: """
+						Samples a ratio of traces, decided from a hash of their trace ID.
+
+						Every instance of this transform samples the same traces, given the same ratio.
+						"""
+				}
+			}
+		}
+	}
+	trace_id_field: {
+		description: """
+			The field holding the trace ID of each span.
+
+			Spans without a trace ID are forwarded as is.
+			"""
+		required: false
+		type: string: {
+			default: "trace_id"
+			examples: ["trace_id"]
+		}
+	}
+}
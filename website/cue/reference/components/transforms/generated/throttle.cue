@@ -1,6 +1,68 @@
 package metadata
 
 generated: components: transforms: throttle: configuration: {
+	backend: {
+		description: "Where the rate limits are held."
+		required:    false
+		type: object: options: {
+			endpoint: {
+				description: """
+					The URL of the Redis server.
+
+					The URL must take the form of `protocol://server:port/db` where the `protocol` can either be
+					`redis` or `rediss` for connections secured using TLS.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: examples: ["redis://127.0.0.1:6379/0"]
+			}
+			key_prefix: {
+				description: """
+					The prefix of the Redis keys holding the rate limits.
+
+					The instances sharing rate limits must use the same prefix. By default, the prefix is
+					`vector:throttle:` followed by the ID of the component and a `:`.
+
+					The bucket of each key is held in the prefix followed by `key:` and the key, and the bucket
+					of the events without a key in the prefix followed by `none`.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: string: examples: ["vector:throttle:audit_logs:"]
+			}
+			timeout_secs: {
+				description: """
+					The timeout of the requests to the Redis server, in seconds.
+
+					Events are rate limited locally while the server can't be reached.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: float: {
+					default: 0.5
+					unit:    "seconds"
+				}
+			}
+			type: {
+				description: "Where the rate limits are held."
+				required:    false
+				type: string: {
+					default: "local"
+					enum: {
+						local: "The rate limits are held in memory, and apply to each Vector instance separately."
+						redis: """
+							The rate limits are held in Redis, and shared by all the Vector instances using the same
+							Redis server and key prefix.
+
+							The rate limits of the events which are ready are checked at once, using a single request
+							to Redis. While the Redis server can't be reached, the rate limits are applied to each
+							instance separately, as with the `local` backend.
+							"""
+					}
+				}
+			}
+		}
+	}
 	exclude: {
 		description: "A logical condition used to exclude events from sampling."
 		required:    false
@@ -38,6 +100,30 @@ generated: components: transforms: throttle: configuration: {
 			syntax: "template"
 		}
 	}
+	max_queued_events: {
+		description: "The maximum number of events delayed at once in the `shape` mode."
+		required:    false
+		type: uint: default: 1000
+	}
+	mode: {
+		description: "What happens to the events over the rate limits."
+		required:    false
+		type: string: {
+			default: "drop"
+			enum: {
+				drop: "The events over the rate limits are dropped."
+				shape: """
+					The events over the rate limits are delayed until their bucket allows them.
+
+					Delayed events are held in a queue of up to `max_queued_events` events. Once the queue is
+					full, no more events are accepted, which applies backpressure to the upstream components.
+					Events of different buckets don't delay each other.
+
+					This mode can't be used with the `redis` backend.
+					"""
+			}
+		}
+	}
 	threshold: {
 		description: """
 			The number of events allowed for a given bucket per configured `window_secs`.
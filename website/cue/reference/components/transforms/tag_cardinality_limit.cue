@@ -187,6 +187,7 @@ components: transforms: tag_cardinality_limit: {
 	}
 
 	telemetry: metrics: {
+		tag_cardinality_estimate:       components.sources.internal_metrics.output.metrics.tag_cardinality_estimate
 		tag_value_limit_exceeded_total: components.sources.internal_metrics.output.metrics.tag_value_limit_exceeded_total
 		value_limit_reached_total:      components.sources.internal_metrics.output.metrics.value_limit_reached_total
 	}
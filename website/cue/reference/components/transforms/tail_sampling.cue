@@ -0,0 +1,75 @@
+package metadata
+
+components: transforms: tail_sampling: {
+	title: "Tail Sampling"
+
+	description: """
+		Samples whole traces, based on policies evaluated once all of their spans are collected.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				The spans of a trace are held in memory until a decision is made on the trace, which
+				takes up to `decision_wait_secs`. Memory usage grows with the number of spans received
+				during that time, up to `max_traces` traces.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: generated.components.transforms.tail_sampling.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	output: {
+		traces: "": {
+			description: "The spans of the sampled traces, unmodified."
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:   components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		tail_sampling_traces_evicted_total: components.sources.internal_metrics.output.metrics.tail_sampling_traces_evicted_total
+	}
+
+	how_it_works: {
+		decisions: {
+			title: "Decisions"
+			body:  """
+				Spans are grouped by the value of `trace_id_field`, and held until a decision is made on
+				their trace. The decision is made as soon as the root span of the trace, a span without a
+				`parent_span_id_field`, is received, or once `decision_wait_secs` have elapsed since the
+				first span of the trace, whichever comes first.
+
+				Spans of a trace received after its decision follow that decision, as long as it is
+				remembered, which is for `decision_wait_secs`.
+				"""
+		}
+		policies: {
+			title: "Policies"
+			body:  """
+				A trace is sampled if any of the configured `policies` matches it, in which case all of
+				its spans are forwarded. Otherwise, all of its spans are dropped. Policies are evaluated
+				on the spans of the trace as [OpenTelemetry](\(urls.opentelemetry)) spans, such as those
+				received by the `opentelemetry` source.
+				"""
+		}
+	}
+}
@@ -116,6 +116,16 @@ components: transforms: throttle: {
 						by the bucket's `key`.
 						"""
 				},
+				{
+					title: "Shaping"
+					body: """
+						With `mode` set to `shape`, the events over the rate limits are delayed instead of dropped, and
+						released as soon as their bucket allows them. Up to `max_queued_events` events are held at once,
+						after which backpressure is applied to the upstream components. The depth of the queue is tracked
+						by a `throttle_queued_events` gauge, and the delay of each event by a `throttle_event_delay_seconds`
+						histogram.
+						"""
+				},
 			]
 		}
 	}
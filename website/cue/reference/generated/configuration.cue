@@ -157,6 +157,72 @@ generated: configuration: configuration: {
 				required:      false
 				relevant_when: "type = \"memory\""
 			}
+			persistence: {
+				type: object: options: {
+					path: {
+						type: string: examples: ["/var/lib/vector/memory_table.json"]
+						description: """
+														The file in which snapshots of the table are written.
+
+														When the table is built, it is restored from this file, skipping the entries whose TTL
+														expired in the meantime.
+														"""
+						required: true
+					}
+					snapshot_interval: {
+						type: uint: default: 60
+						description: """
+														The interval, in seconds, between snapshots of the table.
+
+														A snapshot is also written when the table shuts down.
+														"""
+						required: false
+					}
+				}
+				description:   "Configuration for persisting the table to disk."
+				required:      false
+				relevant_when: "type = \"memory\""
+			}
+			replication: {
+				type: object: options: {
+					address: {
+						type: string: examples: ["0.0.0.0:6100"]
+						description: "The socket address to listen on for writes replicated by peers."
+						required:    true
+					}
+					buffer_size: {
+						type: uint: default: 10000
+						description: """
+														The maximum number of writes waiting to be sent to each peer.
+
+														Writes are dropped for peers that fall further behind.
+														"""
+						required: false
+					}
+					peers: {
+						type: array: items: type: string: examples: ["vector-1.internal:6100"]
+						description: """
+														The addresses of the peers to replicate writes to.
+
+														Each of them must include a port.
+														"""
+						required: true
+					}
+					tls: {
+						type: object: {}
+						description: "Configures the TLS options for both incoming and outgoing connections."
+						required:    false
+					}
+				}
+				description: """
+					Configuration for replicating the writes of the table to other Vector instances.
+
+					Writes received by the table are sent to each peer over the native `vector` protocol, and
+					writes received from peers are applied to the table without being replicated any further.
+					"""
+				required:      false
+				relevant_when: "type = \"memory\""
+			}
 			source_config: {
 				type: object: options: {
 					export_batch_size: {
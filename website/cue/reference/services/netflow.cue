@@ -0,0 +1,10 @@
+package metadata
+
+services: netflow: {
+	name:     "NetFlow"
+	thing:    "a \(name) exporter"
+	url:      urls.netflow
+	versions: null
+
+	description: "[NetFlow](\(urls.netflow)) and its successor [IPFIX](\(urls.ipfix)) are protocols used by routers, switches and firewalls to export summaries of the network flows going through them. [sFlow](\(urls.sflow)) is a similar protocol exporting samples of packets and interface counters."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: snmp: {
+	name:     "SNMP"
+	thing:    "an \(name) agent"
+	url:      urls.snmp_trap
+	versions: null
+
+	description: "The [Simple Network Management Protocol](\(urls.snmp_trap)) (SNMP) is used to monitor and manage network devices, which send traps and informs to notify managers of events."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: sql: {
+	name:     "SQL"
+	thing:    "a MySQL, MariaDB or SQLite database"
+	url:      urls.sqlx_any
+	versions: null
+
+	description: "[MySQL](\(urls.mysql)) and [MariaDB](\(urls.mariadb)) are open source relational database servers, and [SQLite](\(urls.sqlite)) is an embedded relational database stored in a single file."
+}
@@ -282,6 +282,7 @@ urls: {
 	ip_ntoa:                                    "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                    "https://linux.die.net/man/3/inet_ntop"
 	ip_pton:                                    "https://linux.die.net/man/3/inet_pton"
+	ipfix:                                      "https://datatracker.ietf.org/doc/html/rfc7011"
 	is_normal:                                  "https://doc.rust-lang.org/std/primitive.f64.html#method.is_normal"
 	iso_8601:                                   "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                  "\(wikipedia)/wiki/ISO_3166-2"
@@ -353,6 +354,7 @@ urls: {
 	macos:                                      "\(wikipedia)/wiki/MacOS"
 	magic_bytes:                                "\(wikipedia)/wiki/List_of_file_signatures"
 	mailing_list:                               "/community/"
+	mariadb:                                    "https://mariadb.org/"
 	maxmind:                                    "https://www.maxmind.com/en/home"
 	maxmind_db_file_format:                     "https://maxmind.github.io/MaxMind-DB/"
 	maxmind_geoip2:                             "https://dev.maxmind.com/geoip/geoip2/downloadable"
@@ -368,12 +370,14 @@ urls: {
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	mysql:                                      "https://www.mysql.com/"
 	mqtt:                                       "https://mqtt.org/"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
+	netflow:                                    "\(wikipedia)/wiki/NetFlow"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                  "https://newrelic.com/"
@@ -483,6 +487,7 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	sflow:                                      "https://sflow.org/sflow_version_5.txt"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
@@ -490,6 +495,8 @@ urls: {
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
+	snmp_trap:                                  "\(wikipedia)/wiki/Simple_Network_Management_Protocol#Trap_and_InformRequest"
+	snmpv3_usm:                                 "https://datatracker.ietf.org/doc/html/rfc3414"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
@@ -502,6 +509,9 @@ urls: {
 	splunk_hec_setup:                           "https://docs.splunk.com/Documentation/Splunk/latest/Data/UsetheHTTPEventCollector"
 	splunk_hec_metadata:                        "https://docs.splunk.com/Documentation/Splunk/latest/Data/FormateventsforHTTPEventCollector#Event_metadata"
 	specs_instrumentation:                      "\(vector_repo)/blob/master/docs/specs/instrumentation.md)"
+	sqlite:                                     "https://www.sqlite.org/"
+	sqlite_upsert:                              "https://www.sqlite.org/lang_upsert.html"
+	sqlx_any:                                   "https://docs.rs/sqlx/latest/sqlx/any/index.html"
 	standard_streams:                           "\(wikipedia)/wiki/Standard_streams"
 	statsd:                                     "\(github)/statsd/statsd"
 	statsd_multi:                               "\(github)/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets"